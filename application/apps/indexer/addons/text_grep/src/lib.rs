@@ -82,9 +82,17 @@
 pub mod buffer;
 use crate::buffer::CancellableBufReader;
 use grep_regex::{RegexMatcher, RegexMatcherBuilder};
-use grep_searcher::{Searcher, sinks::UTF8};
+use grep_searcher::{
+    Searcher,
+    sinks::{Lossy, UTF8},
+};
 use regex::Regex;
-use std::{collections::HashMap, fs::File, io, path::PathBuf};
+use std::{
+    collections::HashMap,
+    fs::File,
+    io,
+    path::{Path, PathBuf},
+};
 use thiserror::Error;
 use tokio_util::sync::CancellationToken;
 
@@ -140,6 +148,17 @@ pub struct SearchResult {
     pub error_message: Option<String>,
 }
 
+/// A line of a file matched by at least one of the patterns.
+#[derive(Debug, Clone)]
+pub struct LineMatch {
+    /// Zero-based number of the line in the file.
+    pub line: u64,
+    /// Positions of the matched patterns.
+    pub patterns: Vec<usize>,
+    /// Content of the line without the line terminator.
+    pub content: String,
+}
+
 /// Constructs a `RegexMatcher` based on the provided patterns and case sensitivity flag.
 ///
 /// # Arguments
@@ -288,3 +307,58 @@ pub async fn count_occurrences(
     }
     Ok(results)
 }
+
+/// Searches a file for the lines matching at least one of the given regular expressions.
+/// Unlike `count_occurrences`, patterns are used as they are, without escaping.
+///
+/// # Arguments
+///
+/// * `patterns` - Regular expressions to search for.
+/// * `file_path` - Path to the file.
+/// * `cancel_token` - A cancellation token to cancel the operation.
+/// * `handle` - Called for each matched line; returning `false` stops the search.
+///
+/// # Returns
+///
+/// `GrepError::OperationCancelled` if the search was cancelled.
+pub fn find_matches<F: FnMut(LineMatch) -> bool>(
+    patterns: &[Regex],
+    file_path: &Path,
+    cancel_token: &CancellationToken,
+    mut handle: F,
+) -> Result<(), GrepError> {
+    if patterns.is_empty() {
+        return Ok(());
+    }
+    let matcher = RegexMatcherBuilder::new().build(
+        &patterns
+            .iter()
+            .map(|pattern| format!("(?:{})", pattern.as_str()))
+            .collect::<Vec<String>>()
+            .join("|"),
+    )?;
+    let file = File::open(file_path)?;
+    let reader = CancellableBufReader::new(file, cancel_token.clone());
+    Searcher::new()
+        .search_reader(
+            &matcher,
+            reader,
+            Lossy(|line_num, line| {
+                let content = line.trim_end_matches(['\n', '\r']);
+                Ok(handle(LineMatch {
+                    line: line_num.saturating_sub(1),
+                    patterns: patterns
+                        .iter()
+                        .enumerate()
+                        .filter_map(|(i, pattern)| pattern.is_match(content).then_some(i))
+                        .collect(),
+                    content: content.to_owned(),
+                }))
+            }),
+        )
+        .map_err(|e| GrepError::FileProcessingError(format!("Error processing file: {e}")))?;
+    if cancel_token.is_cancelled() {
+        return Err(GrepError::OperationCancelled);
+    }
+    Ok(())
+}
@@ -1,10 +1,11 @@
 #[cfg(test)]
 mod tests {
+    use regex::Regex;
     use std::fs::File;
     use std::io::Write;
     use std::path::PathBuf;
     use tempfile::tempdir;
-    use text_grep::{GrepError, GrepError::RegExError, count_occurrences};
+    use text_grep::{GrepError, GrepError::RegExError, count_occurrences, find_matches};
     use tokio_util::sync::CancellationToken;
 
     // Function to create a temporary test file with given content
@@ -137,4 +138,45 @@ mod tests {
             "Result is not Err(RegExError)"
         );
     }
+
+    #[test]
+    fn test_find_matches() {
+        let content = "This is a test file\r\n\
+                       with multiple lines\n\
+                       to test pattern matching";
+        let (file_path, _) = create_temp_file(content);
+
+        let patterns = vec![
+            Regex::new("(?i)this").unwrap(),
+            Regex::new(r"te\w+").unwrap(),
+        ];
+        let cancel_token = CancellationToken::new();
+        let mut matches = Vec::new();
+        find_matches(&patterns, &file_path, &cancel_token, |matched| {
+            matches.push(matched);
+            true
+        })
+        .expect("search is done");
+        assert_eq!(matches.len(), 2);
+        assert_eq!(matches[0].line, 0);
+        assert_eq!(matches[0].patterns, vec![0, 1]);
+        assert_eq!(matches[0].content, "This is a test file");
+        assert_eq!(matches[1].line, 2);
+        assert_eq!(matches[1].patterns, vec![1]);
+
+        // Stops on the first match
+        let mut count = 0;
+        find_matches(&patterns, &file_path, &cancel_token, |_| {
+            count += 1;
+            false
+        })
+        .expect("search is done");
+        assert_eq!(count, 1);
+
+        cancel_token.cancel();
+        assert!(matches!(
+            find_matches(&patterns, &file_path, &cancel_token, |_| true),
+            Err(GrepError::OperationCancelled)
+        ));
+    }
 }
@@ -0,0 +1,253 @@
+//! # Vector ASCII logging format (ASC)
+//!
+//! Supported are the header line `base <hex|dec> timestamps <absolute|relative>` and the
+//! events of classic CAN frames, CAN FD frames and error frames:
+//! ```text
+//!    0.015991 1  6F9             Rx   d 8 05 0C 00 00 00 00 00 00
+//!    0.016234 1  1F334455x       Tx   r
+//!    0.017001 1  ErrorFrame
+//!    0.018003 CANFD   2 Rx        300  Name  1 0 9 12 01 02 03 04 05 06 07 08 09 0A 0B 0C
+//! ```
+//! All other lines (comments, statistics, trigger blocks, ...) are ignored.
+use super::{CanFrame, Direction, FrameKind, len_from_dlc, parse_seconds};
+use std::time::Duration;
+
+/// Parsing state of an ASC file which is given by its header lines.
+#[derive(Debug, Clone)]
+pub struct AscState {
+    /// Radix of identifiers and data bytes.
+    radix: u32,
+    /// Whether timestamps are relative to the previous event.
+    relative: bool,
+    /// Timestamp of the previous event.
+    last_timestamp: Duration,
+}
+
+impl Default for AscState {
+    fn default() -> Self {
+        AscState {
+            radix: 16,
+            relative: false,
+            last_timestamp: Duration::ZERO,
+        }
+    }
+}
+
+impl AscState {
+    /// Parses a single line of an ASC file.
+    ///
+    /// Header lines update the state of the parser. `None` is returned for all lines
+    /// which don't contain a frame.
+    pub fn parse_line(&mut self, line: &str) -> Option<CanFrame> {
+        let tokens: Vec<&str> = line.split_whitespace().collect();
+        match tokens.as_slice() {
+            ["base", base, "timestamps", mode, ..] => {
+                self.radix = if base.eq_ignore_ascii_case("dec") {
+                    10
+                } else {
+                    16
+                };
+                self.relative = mode.eq_ignore_ascii_case("relative");
+                None
+            }
+            [ts, rest @ ..] => {
+                let offset = parse_seconds(ts)?;
+                let frame = match rest {
+                    ["CANFD", channel, dir, id, rest @ ..] => self.parse_fd(channel, dir, id, rest),
+                    [channel, "ErrorFrame", ..] => Some(CanFrame {
+                        timestamp: Duration::ZERO,
+                        channel: channel.to_string(),
+                        id: 0,
+                        extended: false,
+                        kind: FrameKind::Error,
+                        direction: None,
+                        dlc: 0,
+                        data: Vec::new(),
+                    }),
+                    [channel, id, dir, kind, rest @ ..] => {
+                        self.parse_classic(channel, id, dir, kind, rest)
+                    }
+                    _ => None,
+                }?;
+                let timestamp = if self.relative {
+                    self.last_timestamp + offset
+                } else {
+                    offset
+                };
+                self.last_timestamp = timestamp;
+                Some(CanFrame { timestamp, ..frame })
+            }
+            _ => None,
+        }
+    }
+
+    /// Parses `<channel> <id>[x] <Rx|Tx> <d|r> [<dlc> <data>...]`.
+    fn parse_classic(
+        &self,
+        channel: &str,
+        id: &str,
+        dir: &str,
+        kind: &str,
+        rest: &[&str],
+    ) -> Option<CanFrame> {
+        channel.parse::<u16>().ok()?;
+        let (id, extended) = self.parse_id(id)?;
+        let direction = parse_direction(dir)?;
+        let (kind, dlc, data) = match kind {
+            "d" => {
+                let dlc = u8::from_str_radix(rest.first()?, 16).ok()?;
+                let len = len_from_dlc(dlc, false);
+                let data = self.parse_data(rest.get(1..=len)?)?;
+                (FrameKind::Data, dlc, data)
+            }
+            "r" => {
+                let dlc = match rest.first() {
+                    Some(dlc) => u8::from_str_radix(dlc, 16).unwrap_or(0),
+                    None => 0,
+                };
+                (FrameKind::Remote, dlc, Vec::new())
+            }
+            _ => return None,
+        };
+        Some(CanFrame {
+            timestamp: Duration::ZERO,
+            channel: channel.to_owned(),
+            id,
+            extended,
+            kind,
+            direction: Some(direction),
+            dlc,
+            data,
+        })
+    }
+
+    /// Parses `<channel> <Rx|Tx> <id>[x] [<name>] <brs> <esi> <dlc> <len> <data>...`.
+    fn parse_fd(&self, channel: &str, dir: &str, id: &str, rest: &[&str]) -> Option<CanFrame> {
+        channel.parse::<u16>().ok()?;
+        let direction = parse_direction(dir)?;
+        let (id, extended) = self.parse_id(id)?;
+        // The symbolic name of the message is optional, the flags are the first two
+        // tokens being a single `0` or `1`.
+        let flags = rest
+            .windows(2)
+            .position(|w| w.iter().all(|t| *t == "0" || *t == "1"))?;
+        let [brs, esi, dlc, len, ..] = &rest[flags..] else {
+            return None;
+        };
+        let dlc = u8::from_str_radix(dlc, 16).ok()?;
+        let len = len.parse::<usize>().ok()?;
+        if len != len_from_dlc(dlc, true) {
+            return None;
+        }
+        let data = self.parse_data(rest.get(flags + 4..flags + 4 + len)?)?;
+        Some(CanFrame {
+            timestamp: Duration::ZERO,
+            channel: channel.to_owned(),
+            id,
+            extended,
+            kind: FrameKind::Fd {
+                brs: *brs == "1",
+                esi: *esi == "1",
+            },
+            direction: Some(direction),
+            dlc,
+            data,
+        })
+    }
+
+    /// Parses an identifier, extended identifiers are suffixed with `x`.
+    fn parse_id(&self, id: &str) -> Option<(u32, bool)> {
+        let (id, extended) = match id.strip_suffix(['x', 'X']) {
+            Some(id) => (id, true),
+            None => (id, false),
+        };
+        Some((u32::from_str_radix(id, self.radix).ok()?, extended))
+    }
+
+    fn parse_data(&self, bytes: &[&str]) -> Option<Vec<u8>> {
+        bytes
+            .iter()
+            .map(|b| u8::from_str_radix(b, self.radix).ok())
+            .collect()
+    }
+}
+
+fn parse_direction(dir: &str) -> Option<Direction> {
+    match dir {
+        "Rx" => Some(Direction::Rx),
+        "Tx" | "TxRq" => Some(Direction::Tx),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn classic_frames() {
+        let mut state = AscState::default();
+        let frame = state
+            .parse_line("17.876707 1  6F9             Rx   d 8 05 0C 00 00 00 00 00 00  Length = 240015 BitCount = 124 ID = 1785")
+            .expect("frame");
+        assert_eq!(frame.id, 0x6F9);
+        assert!(!frame.extended);
+        assert_eq!(frame.channel, "1");
+        assert_eq!(frame.direction, Some(Direction::Rx));
+        assert_eq!(frame.dlc, 8);
+        assert_eq!(frame.data, vec![0x05, 0x0C, 0, 0, 0, 0, 0, 0]);
+        assert_eq!(frame.timestamp.as_micros(), 17_876_707);
+
+        let frame = state
+            .parse_line("17.9 2  1F334455x       Tx   r")
+            .expect("remote");
+        assert!(frame.extended);
+        assert_eq!(frame.kind, FrameKind::Remote);
+        assert_eq!(frame.direction, Some(Direction::Tx));
+
+        let frame = state.parse_line("18.0 1  ErrorFrame").expect("error");
+        assert_eq!(frame.kind, FrameKind::Error);
+    }
+
+    #[test]
+    fn fd_frames() {
+        let mut state = AscState::default();
+        let frame = state
+            .parse_line("30.005071 CANFD   2 Rx        300                                   Generic_Name_12                  1 0 9 12 01 02 03 04 05 06 07 08 09 0A 0B 0C   102203  133   303000 e0006659")
+            .expect("frame");
+        assert_eq!(frame.id, 0x300);
+        assert_eq!(frame.channel, "2");
+        assert_eq!(
+            frame.kind,
+            FrameKind::Fd {
+                brs: true,
+                esi: false
+            }
+        );
+        assert_eq!(frame.data.len(), 12);
+
+        let frame = state
+            .parse_line("30.1 CANFD   1 Tx 301 0 0 8 8 01 02 03 04 05 06 07 08")
+            .expect("frame without name");
+        assert_eq!(frame.data, vec![1, 2, 3, 4, 5, 6, 7, 8]);
+    }
+
+    #[test]
+    fn header_lines() {
+        let mut state = AscState::default();
+        assert!(
+            state
+                .parse_line("date Mon Sep 30 15:06:13.191 pm 2019")
+                .is_none()
+        );
+        assert!(state.parse_line("base dec  timestamps relative").is_none());
+        assert!(state.parse_line("// version 9.0.0").is_none());
+        assert!(state.parse_line("Begin Triggerblock").is_none());
+        assert!(state.parse_line("0.000000 Start of measurement").is_none());
+        let frame = state.parse_line("1.0 1 100 Rx d 2 10 255").expect("frame");
+        assert_eq!(frame.id, 100);
+        assert_eq!(frame.data, vec![10, 255]);
+        let frame = state.parse_line("0.5 1 100 Rx d 0").expect("frame");
+        assert_eq!(frame.timestamp.as_millis(), 1500);
+    }
+}
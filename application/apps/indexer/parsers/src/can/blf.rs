@@ -0,0 +1,361 @@
+//! # Objects of the Vector binary logging format (BLF)
+//!
+//! A BLF file starts with a file header (signature `LOGG`) followed by objects (signature
+//! `LOBJ`). Most objects are stored compressed inside of log containers. This module only
+//! covers the layout of the objects themselves, unpacking of the containers is done by the
+//! BLF byte source.
+use super::{CanFrame, Direction, FrameKind, len_from_dlc};
+use crate::Error;
+use std::time::Duration;
+
+/// Signature of the file header.
+pub const FILE_SIGNATURE: &[u8] = b"LOGG";
+/// Signature of each object.
+pub const OBJECT_SIGNATURE: &[u8] = b"LOBJ";
+/// Length of the base header shared by all objects.
+pub const BASE_HEADER_LEN: usize = 16;
+
+pub const CAN_MESSAGE: u32 = 1;
+pub const LOG_CONTAINER: u32 = 10;
+pub const CAN_ERROR_EXT: u32 = 73;
+pub const CAN_MESSAGE2: u32 = 86;
+pub const CAN_FD_MESSAGE: u32 = 100;
+pub const CAN_FD_MESSAGE_64: u32 = 101;
+
+/// Timestamps of the object are in units of 10 microseconds.
+const TIME_TEN_MICS: u32 = 0x1;
+/// Timestamps of the object are in units of nanoseconds.
+const TIME_ONE_NANS: u32 = 0x2;
+/// Extended identifier flag within the identifier of CAN objects.
+const EXTENDED_ID_FLAG: u32 = 0x8000_0000;
+/// Transmit direction flag of `CAN_MESSAGE` and `CAN_FD_MESSAGE` objects.
+const MSG_FLAG_TX: u8 = 0x01;
+/// Remote frame flag of `CAN_MESSAGE` and `CAN_FD_MESSAGE` objects.
+const MSG_FLAG_RTR: u8 = 0x80;
+/// Flags of `CAN_FD_MESSAGE` objects.
+const FD_FLAG_EDL: u8 = 0x01;
+const FD_FLAG_BRS: u8 = 0x02;
+const FD_FLAG_ESI: u8 = 0x04;
+/// Flags of `CAN_FD_MESSAGE_64` objects.
+const FD64_FLAG_RTR: u32 = 0x0010;
+const FD64_FLAG_EDL: u32 = 0x1000;
+const FD64_FLAG_BRS: u32 = 0x2000;
+const FD64_FLAG_ESI: u32 = 0x4000;
+
+/// Base header of an object.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ObjectHeader {
+    pub header_size: u16,
+    pub header_version: u16,
+    /// Size of the object including all headers.
+    pub object_size: u32,
+    pub object_type: u32,
+}
+
+impl ObjectHeader {
+    /// Parses the base header of the object at the start of the given input.
+    pub fn parse(input: &[u8]) -> Result<Self, Error> {
+        if input.len() < BASE_HEADER_LEN {
+            return Err(Error::Incomplete);
+        }
+        if !input.starts_with(OBJECT_SIGNATURE) {
+            return Err(Error::Parse("BLF object signature not found".to_owned()));
+        }
+        let header = ObjectHeader {
+            header_size: read_u16(input, 4),
+            header_version: read_u16(input, 6),
+            object_size: read_u32(input, 8),
+            object_type: read_u32(input, 12),
+        };
+        if (header.object_size as usize) < BASE_HEADER_LEN
+            || header.header_size as usize > header.object_size as usize
+        {
+            return Err(Error::Parse(format!(
+                "Invalid BLF object header: {header:?}"
+            )));
+        }
+        Ok(header)
+    }
+
+    /// Returns the size of the object including the padding to the next object.
+    pub fn padded_size(&self) -> usize {
+        let size = self.object_size as usize;
+        if self.object_type == CAN_FD_MESSAGE_64 {
+            size
+        } else {
+            size + size % 4
+        }
+    }
+}
+
+/// Returns the size of the file header if the input starts with one.
+///
+/// Returns `None` if the input doesn't start with the signature of the file header.
+pub fn file_header_size(input: &[u8]) -> Option<Result<usize, Error>> {
+    if !input.starts_with(FILE_SIGNATURE) {
+        return None;
+    }
+    if input.len() < 8 {
+        return Some(Err(Error::Incomplete));
+    }
+    Some(Ok(read_u32(input, 4) as usize))
+}
+
+/// Parses the object at the start of the given input.
+///
+/// Returns the consumed bytes including padding and the CAN frame if the object contains
+/// one. Objects of other types are consumed without returning a frame.
+pub fn parse_object(input: &[u8]) -> Result<(usize, Option<CanFrame>), Error> {
+    let header = ObjectHeader::parse(input)?;
+    let consumed = header.padded_size();
+    if input.len() < consumed {
+        return Err(Error::Incomplete);
+    }
+    let object = &input[..header.object_size as usize];
+    let data = &object[header.header_size as usize..];
+    let timestamp = timestamp(&header, object);
+    let frame = match header.object_type {
+        CAN_MESSAGE | CAN_MESSAGE2 => can_message(data, timestamp),
+        CAN_FD_MESSAGE => can_fd_message(data, timestamp),
+        CAN_FD_MESSAGE_64 => can_fd_message_64(data, timestamp),
+        CAN_ERROR_EXT if data.len() >= 2 => Some(CanFrame {
+            timestamp,
+            channel: read_u16(data, 0).to_string(),
+            id: 0,
+            extended: false,
+            kind: FrameKind::Error,
+            direction: None,
+            dlc: 0,
+            data: Vec::new(),
+        }),
+        _ => None,
+    };
+    Ok((consumed, frame))
+}
+
+/// Reads the timestamp of the object header, which is at the same position for header
+/// version 1 and 2.
+fn timestamp(header: &ObjectHeader, object: &[u8]) -> Duration {
+    if (header.header_size as usize) < BASE_HEADER_LEN + 16 || object.len() < 32 {
+        return Duration::ZERO;
+    }
+    let flags = read_u32(object, 16);
+    let value = read_u64(object, 24);
+    if flags & TIME_ONE_NANS != 0 {
+        Duration::from_nanos(value)
+    } else if flags & TIME_TEN_MICS != 0 {
+        Duration::from_micros(value.saturating_mul(10))
+    } else {
+        Duration::ZERO
+    }
+}
+
+/// Layout: channel (u16), flags (u8), dlc (u8), id (u32), data (8 bytes).
+fn can_message(data: &[u8], timestamp: Duration) -> Option<CanFrame> {
+    if data.len() < 16 {
+        return None;
+    }
+    let flags = data[2];
+    let dlc = data[3];
+    let raw_id = read_u32(data, 4);
+    let remote = flags & MSG_FLAG_RTR != 0;
+    Some(CanFrame {
+        timestamp,
+        channel: read_u16(data, 0).to_string(),
+        id: raw_id & !EXTENDED_ID_FLAG,
+        extended: raw_id & EXTENDED_ID_FLAG != 0,
+        kind: if remote {
+            FrameKind::Remote
+        } else {
+            FrameKind::Data
+        },
+        direction: Some(direction(flags & MSG_FLAG_TX != 0)),
+        dlc,
+        data: if remote {
+            Vec::new()
+        } else {
+            data[8..8 + len_from_dlc(dlc, false)].to_vec()
+        },
+    })
+}
+
+/// Layout: channel (u16), flags (u8), dlc (u8), id (u32), frame length (u32),
+/// bit count (u8), FD flags (u8), valid data bytes (u8), reserved (5 bytes),
+/// data (64 bytes).
+fn can_fd_message(data: &[u8], timestamp: Duration) -> Option<CanFrame> {
+    if data.len() < 20 {
+        return None;
+    }
+    let flags = data[2];
+    let dlc = data[3];
+    let raw_id = read_u32(data, 4);
+    let fd_flags = data[13];
+    let len = (data[14] as usize).min(data.len() - 20);
+    let kind = if fd_flags & FD_FLAG_EDL != 0 {
+        FrameKind::Fd {
+            brs: fd_flags & FD_FLAG_BRS != 0,
+            esi: fd_flags & FD_FLAG_ESI != 0,
+        }
+    } else if flags & MSG_FLAG_RTR != 0 {
+        FrameKind::Remote
+    } else {
+        FrameKind::Data
+    };
+    Some(CanFrame {
+        timestamp,
+        channel: read_u16(data, 0).to_string(),
+        id: raw_id & !EXTENDED_ID_FLAG,
+        extended: raw_id & EXTENDED_ID_FLAG != 0,
+        kind,
+        direction: Some(direction(flags & MSG_FLAG_TX != 0)),
+        dlc,
+        data: if kind == FrameKind::Remote {
+            Vec::new()
+        } else {
+            data[20..20 + len].to_vec()
+        },
+    })
+}
+
+/// Layout: channel (u8), dlc (u8), valid data bytes (u8), tx count (u8), id (u32),
+/// frame length (u32), flags (u32), bit timings (4 * u32), bit count (u16),
+/// direction (u8), extended data offset (u8), crc (u32), data.
+fn can_fd_message_64(data: &[u8], timestamp: Duration) -> Option<CanFrame> {
+    if data.len() < 40 {
+        return None;
+    }
+    let dlc = data[1];
+    let raw_id = read_u32(data, 4);
+    let flags = read_u32(data, 12);
+    let len = (data[2] as usize).min(data.len() - 40);
+    let kind = if flags & FD64_FLAG_EDL != 0 {
+        FrameKind::Fd {
+            brs: flags & FD64_FLAG_BRS != 0,
+            esi: flags & FD64_FLAG_ESI != 0,
+        }
+    } else if flags & FD64_FLAG_RTR != 0 {
+        FrameKind::Remote
+    } else {
+        FrameKind::Data
+    };
+    Some(CanFrame {
+        timestamp,
+        channel: data[0].to_string(),
+        id: raw_id & !EXTENDED_ID_FLAG,
+        extended: raw_id & EXTENDED_ID_FLAG != 0,
+        kind,
+        direction: Some(direction(data[34] != 0)),
+        dlc,
+        data: if kind == FrameKind::Remote {
+            Vec::new()
+        } else {
+            data[40..40 + len].to_vec()
+        },
+    })
+}
+
+fn direction(tx: bool) -> Direction {
+    if tx { Direction::Tx } else { Direction::Rx }
+}
+
+fn read_u16(data: &[u8], pos: usize) -> u16 {
+    u16::from_le_bytes([data[pos], data[pos + 1]])
+}
+
+fn read_u32(data: &[u8], pos: usize) -> u32 {
+    u32::from_le_bytes(data[pos..pos + 4].try_into().expect("4 bytes"))
+}
+
+fn read_u64(data: &[u8], pos: usize) -> u64 {
+    u64::from_le_bytes(data[pos..pos + 8].try_into().expect("8 bytes"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Creates an object with a version 1 header and a timestamp in nanoseconds.
+    fn object(object_type: u32, timestamp_ns: u64, data: &[u8]) -> Vec<u8> {
+        let header_size = BASE_HEADER_LEN + 16;
+        let mut obj = Vec::new();
+        obj.extend_from_slice(OBJECT_SIGNATURE);
+        obj.extend_from_slice(&(header_size as u16).to_le_bytes());
+        obj.extend_from_slice(&1u16.to_le_bytes());
+        obj.extend_from_slice(&((header_size + data.len()) as u32).to_le_bytes());
+        obj.extend_from_slice(&object_type.to_le_bytes());
+        obj.extend_from_slice(&TIME_ONE_NANS.to_le_bytes());
+        obj.extend_from_slice(&[0; 4]);
+        obj.extend_from_slice(&timestamp_ns.to_le_bytes());
+        obj.extend_from_slice(data);
+        obj.resize(obj.len() + obj.len() % 4, 0);
+        obj
+    }
+
+    /// Creates a `CAN_MESSAGE` object.
+    fn can_message_object(channel: u16, id: u32, data: &[u8]) -> Vec<u8> {
+        let mut msg = Vec::new();
+        msg.extend_from_slice(&channel.to_le_bytes());
+        msg.push(0);
+        msg.push(data.len() as u8);
+        msg.extend_from_slice(&id.to_le_bytes());
+        msg.extend_from_slice(data);
+        msg.resize(16, 0);
+        object(CAN_MESSAGE, 1_500_000_000, &msg)
+    }
+
+    #[test]
+    fn parse_can_message() {
+        let obj = can_message_object(1, 0x123, &[1, 2, 3]);
+        let (consumed, frame) = parse_object(&obj).expect("object");
+        assert_eq!(consumed, obj.len());
+        let frame = frame.expect("frame");
+        assert_eq!(frame.id, 0x123);
+        assert_eq!(frame.channel, "1");
+        assert_eq!(frame.data, vec![1, 2, 3]);
+        assert_eq!(frame.timestamp, Duration::from_millis(1500));
+        assert_eq!(frame.direction, Some(Direction::Rx));
+    }
+
+    #[test]
+    fn parse_can_fd_message_64() {
+        let mut msg = vec![0u8; 40];
+        msg[0] = 2;
+        msg[1] = 9;
+        msg[2] = 12;
+        msg[4..8].copy_from_slice(&(0x1F33_4455 | EXTENDED_ID_FLAG).to_le_bytes());
+        msg[12..16].copy_from_slice(&(FD64_FLAG_EDL | FD64_FLAG_BRS).to_le_bytes());
+        msg[34] = 1;
+        msg.extend_from_slice(&[0xAA; 12]);
+        let obj = object(CAN_FD_MESSAGE_64, 0, &msg);
+        let (_, frame) = parse_object(&obj).expect("object");
+        let frame = frame.expect("frame");
+        assert!(frame.extended);
+        assert_eq!(frame.id, 0x1F33_4455);
+        assert_eq!(
+            frame.kind,
+            FrameKind::Fd {
+                brs: true,
+                esi: false
+            }
+        );
+        assert_eq!(frame.data, vec![0xAA; 12]);
+        assert_eq!(frame.direction, Some(Direction::Tx));
+    }
+
+    #[test]
+    fn incomplete_and_unknown_objects() {
+        let obj = can_message_object(1, 0x123, &[1, 2, 3]);
+        assert!(matches!(
+            parse_object(&obj[..obj.len() - 4]),
+            Err(Error::Incomplete)
+        ));
+        let obj = object(65, 0, &[0; 8]);
+        let (consumed, frame) = parse_object(&obj).expect("object");
+        assert_eq!(consumed, obj.len());
+        assert!(frame.is_none());
+        assert!(matches!(
+            parse_object(b"LOGG000011112222"),
+            Err(Error::Parse(_))
+        ));
+    }
+}
@@ -0,0 +1,200 @@
+//! # Log format of `candump -l`
+//!
+//! Each line has the form `(<seconds>.<micros>) <interface> <frame>` where the frame
+//! is written in the compact notation of the SocketCAN utilities:
+//! - `<id>#<data>` for classic data frames,
+//! - `<id>#R[<dlc>]` for remote transmission requests,
+//! - `<id>##<flags><data>` for CAN FD frames.
+//!
+//! Identifiers with three digits are standard identifiers and with eight digits extended
+//! identifiers. Error frames are marked with the error flag inside of the identifier.
+use super::{CanFrame, Direction, FrameKind, dlc_from_len, parse_seconds};
+use std::fmt::Write;
+
+/// Flag of an error frame within the identifier.
+const CAN_ERR_FLAG: u32 = 0x2000_0000;
+/// Mask of an extended identifier.
+const CAN_EFF_MASK: u32 = 0x1FFF_FFFF;
+/// Bit rate switch flag of CAN FD frames.
+const CANFD_BRS: u8 = 0x01;
+/// Error state indicator flag of CAN FD frames.
+const CANFD_ESI: u8 = 0x02;
+
+/// Parses a single line of a `candump -l` log.
+///
+/// Returns `None` if the line doesn't contain a valid frame.
+pub fn parse_line(line: &str) -> Option<CanFrame> {
+    let mut parts = line.split_whitespace();
+    let timestamp = parse_seconds(
+        parts
+            .next()?
+            .strip_prefix('(')
+            .and_then(|ts| ts.strip_suffix(')'))?,
+    )?;
+    let channel = parts.next()?.to_owned();
+    let (id, frame) = parts.next()?.split_once('#')?;
+    let direction = match parts.next() {
+        Some("R") => Some(Direction::Rx),
+        Some("T") => Some(Direction::Tx),
+        _ => None,
+    };
+    let extended = id.len() > 3;
+    let raw_id = u32::from_str_radix(id, 16).ok()?;
+    if raw_id & CAN_ERR_FLAG != 0 {
+        let data = parse_hex(frame)?;
+        return Some(CanFrame {
+            timestamp,
+            channel,
+            id: raw_id & CAN_EFF_MASK,
+            extended,
+            kind: FrameKind::Error,
+            direction,
+            dlc: data.len() as u8,
+            data,
+        });
+    }
+    let (kind, dlc, data) = if let Some(fd) = frame.strip_prefix('#') {
+        let flags = u8::from_str_radix(fd.get(..1)?, 16).ok()?;
+        let data = parse_hex(&fd[1..])?;
+        (
+            FrameKind::Fd {
+                brs: flags & CANFD_BRS != 0,
+                esi: flags & CANFD_ESI != 0,
+            },
+            dlc_from_len(data.len()),
+            data,
+        )
+    } else if let Some(dlc) = frame.strip_prefix('R') {
+        let dlc = if dlc.is_empty() { 0 } else { dlc.parse().ok()? };
+        (FrameKind::Remote, dlc, Vec::new())
+    } else {
+        let (data, dlc) = match frame.split_once('_') {
+            Some((data, dlc)) => (data, Some(u8::from_str_radix(dlc, 16).ok()?)),
+            None => (frame, None),
+        };
+        let data = parse_hex(data)?;
+        let dlc = dlc.unwrap_or(data.len() as u8);
+        (FrameKind::Data, dlc, data)
+    };
+    Some(CanFrame {
+        timestamp,
+        channel,
+        id: raw_id & CAN_EFF_MASK,
+        extended,
+        kind,
+        direction,
+        dlc,
+        data,
+    })
+}
+
+/// Writes the frame as a line of a `candump -l` log including the trailing newline.
+pub fn write_line(frame: &CanFrame, out: &mut String) {
+    let _ = write!(
+        out,
+        "({}.{:06}) {} ",
+        frame.timestamp.as_secs(),
+        frame.timestamp.subsec_micros(),
+        frame.channel
+    );
+    let _ = match (frame.kind, frame.extended) {
+        (FrameKind::Error, _) => write!(out, "{:08X}#", frame.id | CAN_ERR_FLAG),
+        (_, true) => write!(out, "{:08X}#", frame.id),
+        (_, false) => write!(out, "{:03X}#", frame.id),
+    };
+    match frame.kind {
+        FrameKind::Remote => {
+            out.push('R');
+            if frame.dlc > 0 {
+                let _ = write!(out, "{}", frame.dlc);
+            }
+        }
+        FrameKind::Fd { brs, esi } => {
+            let flags = if brs { CANFD_BRS } else { 0 } | if esi { CANFD_ESI } else { 0 };
+            let _ = write!(out, "#{flags:X}");
+        }
+        FrameKind::Data | FrameKind::Error => {}
+    }
+    for byte in frame.data.iter() {
+        let _ = write!(out, "{byte:02X}");
+    }
+    out.push('\n');
+}
+
+fn parse_hex(data: &str) -> Option<Vec<u8>> {
+    if !data.len().is_multiple_of(2) {
+        return None;
+    }
+    (0..data.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(data.get(i..i + 2)?, 16).ok())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn data_frames() {
+        let frame = parse_line("(1436509052.249713) vcan0 044#2A366C2BBA").expect("frame");
+        assert_eq!(frame.timestamp, Duration::new(1436509052, 249_713_000));
+        assert_eq!(frame.channel, "vcan0");
+        assert_eq!(frame.id, 0x44);
+        assert!(!frame.extended);
+        assert_eq!(frame.kind, FrameKind::Data);
+        assert_eq!(frame.dlc, 5);
+        assert_eq!(frame.data, vec![0x2A, 0x36, 0x6C, 0x2B, 0xBA]);
+
+        let frame = parse_line("(0.000001) can1 1F334455#").expect("frame");
+        assert!(frame.extended);
+        assert_eq!(frame.id, 0x1F33_4455);
+        assert!(frame.data.is_empty());
+    }
+
+    #[test]
+    fn special_frames() {
+        let frame = parse_line("(1.5) can0 123#R3").expect("remote");
+        assert_eq!(frame.kind, FrameKind::Remote);
+        assert_eq!(frame.dlc, 3);
+
+        let frame = parse_line("(1.5) can0 123##3000102030405060708 T").expect("fd");
+        assert_eq!(
+            frame.kind,
+            FrameKind::Fd {
+                brs: true,
+                esi: true
+            }
+        );
+        assert_eq!(frame.dlc, 9);
+        assert_eq!(frame.data.len(), 9);
+        assert_eq!(frame.direction, Some(Direction::Tx));
+
+        let frame = parse_line("(1.5) can0 20000080#0000000000000000").expect("error");
+        assert_eq!(frame.kind, FrameKind::Error);
+        assert_eq!(frame.id, 0x80);
+    }
+
+    #[test]
+    fn invalid_lines() {
+        assert!(parse_line("(1.5) can0").is_none());
+        assert!(parse_line("(1.5) can0 123#ABC").is_none());
+        assert!(parse_line("(abc) can0 123#AB").is_none());
+        assert!(parse_line("1.5 can0 123#AB").is_none());
+    }
+
+    #[test]
+    fn write_and_parse() {
+        for line in [
+            "(1436509052.249713) vcan0 044#2A366C2BBA\n",
+            "(1.500000) can0 123#R3\n",
+            "(1.500000) can0 1F334455##1AABB\n",
+            "(1.500000) can0 20000080#0000\n",
+        ] {
+            let mut out = String::new();
+            write_line(&parse_line(line).expect("frame"), &mut out);
+            assert_eq!(out, line);
+        }
+    }
+}
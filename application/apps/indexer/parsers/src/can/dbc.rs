@@ -0,0 +1,490 @@
+//! # Decoding of CAN frames with DBC files
+//!
+//! Only the parts of the DBC format which are needed to turn a raw frame into
+//! named signals are supported: messages (`BO_`), signals (`SG_`) including simple
+//! multiplexing and value descriptions (`VAL_`). All other sections are ignored, signals
+//! which cannot be parsed (e.g. with extended multiplexing) are skipped with a warning.
+use log::{debug, warn};
+use std::{
+    collections::HashMap,
+    fmt::{self, Display},
+    fs,
+    path::PathBuf,
+    str::FromStr,
+};
+use thiserror::Error;
+
+/// Flag used in DBC files to mark a message id as an extended (29 bit) identifier.
+pub const EXTENDED_ID_FLAG: u32 = 0x8000_0000;
+/// Mask of a valid CAN identifier.
+pub const CAN_ID_MASK: u32 = 0x1FFF_FFFF;
+
+#[derive(Error, Debug)]
+pub enum DbcError {
+    #[error("Fail to read DBC file: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("Invalid DBC content in line {line}: {msg}")]
+    Syntax { line: usize, msg: String },
+}
+
+/// Byte order of a signal inside of the frame payload.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ByteOrder {
+    /// Intel byte order (`@1` in DBC files).
+    LittleEndian,
+    /// Motorola byte order (`@0` in DBC files).
+    BigEndian,
+}
+
+/// Multiplexing role of a signal.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Multiplex {
+    /// Signal is always present.
+    Plain,
+    /// Signal selects which multiplexed signals are present (`M`).
+    Multiplexor,
+    /// Signal is present only if the multiplexor has the given value (`m<N>`).
+    Multiplexed(u64),
+}
+
+#[derive(Debug, Clone)]
+pub struct DbcSignal {
+    pub name: String,
+    pub start_bit: u16,
+    pub size: u16,
+    pub byte_order: ByteOrder,
+    pub signed: bool,
+    pub factor: f64,
+    pub offset: f64,
+    pub unit: String,
+    pub multiplex: Multiplex,
+    /// Value descriptions (`VAL_`) mapping raw values to labels.
+    pub values: HashMap<i64, String>,
+}
+
+impl DbcSignal {
+    /// Extracts the raw (not scaled) value of the signal from the given payload.
+    ///
+    /// Returns `None` if the payload is too short to contain the signal.
+    pub fn raw_value(&self, data: &[u8]) -> Option<i64> {
+        if self.size == 0 || self.size > 64 {
+            return None;
+        }
+        let mut raw: u64 = 0;
+        match self.byte_order {
+            ByteOrder::LittleEndian => {
+                for i in (0..self.size).rev() {
+                    raw = (raw << 1) | u64::from(bit(data, self.start_bit + i)?);
+                }
+            }
+            ByteOrder::BigEndian => {
+                // Motorola signals are defined by their most significant bit and continue
+                // with the next lower bit, jumping to the MSB of the following byte.
+                let mut pos = self.start_bit;
+                for _ in 0..self.size {
+                    raw = (raw << 1) | u64::from(bit(data, pos)?);
+                    pos = if pos.is_multiple_of(8) {
+                        pos + 15
+                    } else {
+                        pos - 1
+                    };
+                }
+            }
+        }
+        if self.signed && self.size < 64 && raw & (1 << (self.size - 1)) != 0 {
+            raw |= u64::MAX << self.size;
+        }
+        Some(raw as i64)
+    }
+
+    /// Converts a raw value into the physical value (`raw * factor + offset`).
+    pub fn physical(&self, raw: i64) -> f64 {
+        let raw = if self.signed {
+            raw as f64
+        } else {
+            raw as u64 as f64
+        };
+        raw * self.factor + self.offset
+    }
+}
+
+fn bit(data: &[u8], pos: u16) -> Option<u8> {
+    data.get(usize::from(pos / 8))
+        .map(|byte| (byte >> (pos % 8)) & 1)
+}
+
+#[derive(Debug, Clone)]
+pub struct DbcMessage {
+    /// CAN identifier without the extended flag.
+    pub id: u32,
+    pub extended: bool,
+    pub name: String,
+    pub size: u8,
+    pub signals: Vec<DbcSignal>,
+}
+
+/// Signal decoded from a frame.
+#[derive(Debug, Clone)]
+pub struct DecodedSignal<'a> {
+    pub name: &'a str,
+    pub raw: i64,
+    pub value: f64,
+    pub unit: &'a str,
+    pub label: Option<&'a str>,
+}
+
+impl Display for DecodedSignal<'_> {
+    /// Writes the signal as `name=value unit`, which makes the value reachable with
+    /// value search filters like `EngineSpeed=(\d+)`.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}={}", self.name, self.value)?;
+        if !self.unit.is_empty() {
+            write!(f, " {}", self.unit)?;
+        }
+        if let Some(label) = self.label {
+            write!(f, " ({label})")?;
+        }
+        Ok(())
+    }
+}
+
+/// Frame decoded with the DBC definition of its message.
+#[derive(Debug, Clone)]
+pub struct DecodedFrame<'a> {
+    pub message: &'a DbcMessage,
+    pub signals: Vec<DecodedSignal<'a>>,
+}
+
+impl DecodedFrame<'_> {
+    /// Writes the decoded signals using the given separator between them.
+    pub fn write_signals(&self, f: &mut impl fmt::Write, separator: &str) -> fmt::Result {
+        for (i, signal) in self.signals.iter().enumerate() {
+            if i > 0 {
+                f.write_str(separator)?;
+            }
+            write!(f, "{signal}")?;
+        }
+        Ok(())
+    }
+}
+
+impl Display for DecodedFrame<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.message.name)?;
+        if !self.signals.is_empty() {
+            write!(f, " ")?;
+            self.write_signals(f, " ")?;
+        }
+        Ok(())
+    }
+}
+
+/// Key of a message: the CAN identifier without the extended flag and whether the
+/// identifier is an extended one. Standard and extended identifiers with the same value
+/// belong to different messages.
+type MessageKey = (u32, bool);
+
+/// Returns the key of a message from an identifier carrying the extended flag.
+fn key_of(raw_id: u32) -> MessageKey {
+    (raw_id & CAN_ID_MASK, raw_id & EXTENDED_ID_FLAG != 0)
+}
+
+/// Messages collected from one or more DBC files.
+#[derive(Debug, Default, Clone)]
+pub struct DbcMetadata {
+    messages: HashMap<MessageKey, DbcMessage>,
+}
+
+impl DbcMetadata {
+    /// Returns a new meta-data from the given DBC files.
+    ///
+    /// Files which cannot be read or parsed are skipped. `None` is returned if no
+    /// message could be loaded at all.
+    pub fn from_dbc_files(paths: Vec<PathBuf>) -> Option<Self> {
+        let mut metadata = DbcMetadata::default();
+        for path in paths.iter() {
+            match fs::read(path)
+                .map_err(DbcError::from)
+                .and_then(|bytes| String::from_utf8_lossy(&bytes).parse::<DbcMetadata>())
+            {
+                Ok(content) => metadata.messages.extend(content.messages),
+                Err(err) => warn!("Fail to load DBC file {}: {err}", path.display()),
+            }
+        }
+        debug!("DBC: {} messages loaded", metadata.messages.len());
+        if metadata.messages.is_empty() {
+            None
+        } else {
+            Some(metadata)
+        }
+    }
+
+    /// Returns the message definition for the given CAN identifier, if any.
+    ///
+    /// * `id`: CAN identifier without the extended flag.
+    /// * `extended`: Whether the identifier is an extended (29 bit) identifier.
+    pub fn get_message(&self, id: u32, extended: bool) -> Option<&DbcMessage> {
+        self.messages.get(&(id & CAN_ID_MASK, extended))
+    }
+
+    pub fn len(&self) -> usize {
+        self.messages.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.messages.is_empty()
+    }
+
+    /// Decodes the payload of a frame with the given CAN identifier.
+    ///
+    /// Returns `None` if there is no definition for the identifier.
+    pub fn decode<'a>(&'a self, id: u32, extended: bool, data: &[u8]) -> Option<DecodedFrame<'a>> {
+        let message = self.get_message(id, extended)?;
+        Some(DecodedFrame {
+            message,
+            signals: decode_signals(&message.signals, data),
+        })
+    }
+}
+
+/// Decodes the given signals from the payload. Multiplexed signals are decoded only if the
+/// multiplexor has their value, signals exceeding the payload are left out.
+pub fn decode_signals<'a>(signals: &'a [DbcSignal], data: &[u8]) -> Vec<DecodedSignal<'a>> {
+    let mux = signals
+        .iter()
+        .find(|s| s.multiplex == Multiplex::Multiplexor)
+        .and_then(|s| s.raw_value(data));
+    signals
+        .iter()
+        .filter(|s| match s.multiplex {
+            Multiplex::Multiplexed(value) => mux.is_some_and(|mux| mux as u64 == value),
+            _ => true,
+        })
+        .filter_map(|s| {
+            let raw = s.raw_value(data)?;
+            Some(DecodedSignal {
+                name: &s.name,
+                raw,
+                value: s.physical(raw),
+                unit: &s.unit,
+                label: s.values.get(&raw).map(|l| l.as_str()),
+            })
+        })
+        .collect()
+}
+
+impl FromStr for DbcMetadata {
+    type Err = DbcError;
+
+    fn from_str(content: &str) -> Result<Self, Self::Err> {
+        let mut messages: HashMap<MessageKey, DbcMessage> = HashMap::new();
+        let mut current: Option<MessageKey> = None;
+        for (n, line) in content.lines().enumerate() {
+            let line_nr = n + 1;
+            let trimmed = line.trim();
+            if let Some(rest) = trimmed.strip_prefix("BO_ ") {
+                let message = parse_message(rest).ok_or_else(|| DbcError::Syntax {
+                    line: line_nr,
+                    msg: String::from("invalid message definition"),
+                })?;
+                let key = (message.id, message.extended);
+                current = Some(key);
+                messages.insert(key, message);
+            } else if let Some(rest) = trimmed.strip_prefix("SG_ ") {
+                let Some(signal) = parse_signal(rest) else {
+                    warn!("DBC: unsupported signal definition in line {line_nr} is skipped");
+                    continue;
+                };
+                let Some(message) = current.and_then(|key| messages.get_mut(&key)) else {
+                    return Err(DbcError::Syntax {
+                        line: line_nr,
+                        msg: String::from("signal defined outside of a message"),
+                    });
+                };
+                message.signals.push(signal);
+            } else if let Some(rest) = trimmed.strip_prefix("VAL_ ") {
+                current = None;
+                if let Some((key, signal, values)) = parse_value_descriptions(rest)
+                    && let Some(signal) = messages
+                        .get_mut(&key)
+                        .and_then(|m| m.signals.iter_mut().find(|s| s.name == signal))
+                {
+                    signal.values.extend(values);
+                }
+            } else if !trimmed.is_empty() {
+                current = None;
+            }
+        }
+        Ok(DbcMetadata { messages })
+    }
+}
+
+/// Parses `<id> <name>: <size> <transmitter>`
+fn parse_message(input: &str) -> Option<DbcMessage> {
+    let (head, tail) = input.split_once(':')?;
+    let mut head = head.split_whitespace();
+    let raw_id = head.next()?.parse::<u32>().ok()?;
+    let name = head.next()?.to_owned();
+    let size = tail.split_whitespace().next()?.parse::<u8>().ok()?;
+    let (id, extended) = key_of(raw_id);
+    Some(DbcMessage {
+        id,
+        extended,
+        name,
+        size,
+        signals: Vec::new(),
+    })
+}
+
+/// Parses `<name> [M|m<N>] : <start>|<size>@<order><sign> (<factor>,<offset>) [<min>|<max>] "<unit>" <receivers>`
+fn parse_signal(input: &str) -> Option<DbcSignal> {
+    let (head, tail) = input.split_once(':')?;
+    let mut head = head.split_whitespace();
+    let name = head.next()?.to_owned();
+    let multiplex = match head.next() {
+        None => Multiplex::Plain,
+        Some("M") => Multiplex::Multiplexor,
+        Some(m) => Multiplex::Multiplexed(m.strip_prefix('m')?.parse::<u64>().ok()?),
+    };
+    let tail = tail.trim_start();
+    let (layout, tail) = tail.split_once(char::is_whitespace)?;
+    let (start_bit, layout) = layout.split_once('|')?;
+    let (size, layout) = layout.split_once('@')?;
+    let mut flags = layout.chars();
+    let byte_order = match flags.next()? {
+        '0' => ByteOrder::BigEndian,
+        '1' => ByteOrder::LittleEndian,
+        _ => return None,
+    };
+    let signed = match flags.next()? {
+        '-' => true,
+        '+' => false,
+        _ => return None,
+    };
+    let (scaling, tail) = tail.trim_start().strip_prefix('(')?.split_once(')')?;
+    let (factor, offset) = scaling.split_once(',')?;
+    let unit = tail
+        .split_once('"')
+        .and_then(|(_, rest)| rest.split_once('"'))
+        .map(|(unit, _)| unit.to_owned())
+        .unwrap_or_default();
+    Some(DbcSignal {
+        name,
+        start_bit: start_bit.trim().parse().ok()?,
+        size: size.trim().parse().ok()?,
+        byte_order,
+        signed,
+        factor: factor.trim().parse().ok()?,
+        offset: offset.trim().parse().ok()?,
+        unit,
+        multiplex,
+        values: HashMap::new(),
+    })
+}
+
+/// Parses `<id> <signal> <value> "<label>" <value> "<label>" ... ;`
+fn parse_value_descriptions(input: &str) -> Option<(MessageKey, String, HashMap<i64, String>)> {
+    let mut rest = input.trim().trim_end_matches(';');
+    let (id, tail) = rest.split_once(char::is_whitespace)?;
+    let key = key_of(id.parse::<u32>().ok()?);
+    let (signal, tail) = tail.trim_start().split_once(char::is_whitespace)?;
+    rest = tail;
+    let mut values = HashMap::new();
+    while let Some((value, tail)) = rest.trim_start().split_once('"') {
+        let (label, tail) = tail.split_once('"')?;
+        values.insert(value.trim().parse::<i64>().ok()?, label.to_owned());
+        rest = tail;
+    }
+    Some((key, signal.to_owned(), values))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DBC: &str = r#"
+VERSION ""
+
+BU_: ECU1 ECU2
+
+BO_ 100 EngineData: 8 ECU1
+ SG_ EngineSpeed : 0|16@1+ (0.25,0) [0|16383.75] "rpm" ECU2
+ SG_ EngineTemp : 16|8@1- (1,-40) [-40|215] "degC" ECU2
+ SG_ GearState : 24|3@1+ (1,0) [0|7] "" ECU2
+
+BO_ 2147484160 MotorolaData: 4 ECU2
+ SG_ Pressure : 7|16@0+ (0.1,0) [0|6553.5] "bar" ECU1
+
+BO_ 300 MuxData: 8 ECU1
+ SG_ Selector M : 0|8@1+ (1,0) [0|255] "" ECU2
+ SG_ ValueA m0 : 8|8@1+ (1,0) [0|255] "" ECU2
+ SG_ ValueB m1 : 8|8@1+ (2,0) [0|510] "" ECU2
+ SG_ SubSelector m2M : 8|4@1+ (1,0) [0|15] "" ECU2
+
+BO_ 2147483748 ExtendedData: 2 ECU2
+ SG_ Counter : 0|16@1+ (1,0) [0|65535] "" ECU1
+
+VAL_ 100 GearState 0 "Park" 1 "Reverse" 2 "Neutral" 3 "Drive" ;
+"#;
+
+    #[test]
+    fn parse_messages() {
+        let metadata: DbcMetadata = DBC.parse().expect("valid dbc");
+        assert_eq!(metadata.len(), 4);
+        let msg = metadata.get_message(100, false).expect("message 100");
+        assert_eq!(msg.name, "EngineData");
+        assert_eq!(msg.signals.len(), 3);
+        assert_eq!(
+            msg.signals[2].values.get(&3).map(|s| s.as_str()),
+            Some("Drive")
+        );
+        let msg = metadata.get_message(0x200, true).expect("extended message");
+        assert!(msg.extended);
+        assert!(metadata.get_message(0x200, false).is_none());
+        // Standard and extended identifiers with the same value don't collide
+        let msg = metadata
+            .get_message(100, true)
+            .expect("extended message 100");
+        assert_eq!(msg.name, "ExtendedData");
+        // Signals with extended multiplexing are skipped
+        let msg = metadata.get_message(300, false).expect("message 300");
+        assert_eq!(msg.signals.len(), 3);
+    }
+
+    #[test]
+    fn decode_intel() {
+        let metadata: DbcMetadata = DBC.parse().expect("valid dbc");
+        // speed raw = 6000 -> 1500 rpm; temp raw = -10 -> -50 degC; gear = 3
+        let data = [0x70, 0x17, 0xF6, 0x03, 0, 0, 0, 0];
+        let frame = metadata.decode(100, false, &data).expect("decoded");
+        assert_eq!(
+            frame.to_string(),
+            "EngineData EngineSpeed=1500 rpm EngineTemp=-50 degC GearState=3 (Drive)"
+        );
+    }
+
+    #[test]
+    fn decode_motorola() {
+        let metadata: DbcMetadata = DBC.parse().expect("valid dbc");
+        let frame = metadata
+            .decode(0x200, true, &[0x01, 0xF4, 0, 0])
+            .expect("decoded");
+        assert_eq!(frame.signals[0].raw, 500);
+        assert_eq!(frame.signals[0].value, 50.0);
+    }
+
+    #[test]
+    fn decode_multiplexed() {
+        let metadata: DbcMetadata = DBC.parse().expect("valid dbc");
+        let frame = metadata
+            .decode(300, false, &[1, 21, 0, 0, 0, 0, 0, 0])
+            .expect("decoded");
+        assert_eq!(frame.to_string(), "MuxData Selector=1 ValueB=42");
+    }
+
+    #[test]
+    fn unknown_message() {
+        let metadata: DbcMetadata = DBC.parse().expect("valid dbc");
+        assert!(metadata.decode(101, false, &[0; 8]).is_none());
+    }
+}
@@ -0,0 +1,337 @@
+//! # CAN related parsing
+//!
+//! Includes a parser for CAN bus logs and decoding of CAN payloads with DBC files.
+//!
+//! The [`CanParser`] accepts the following formats and can be used with mixed content:
+//! - Log files written by `candump -l` of the Linux SocketCAN utilities.
+//! - Vector ASCII logging files (ASC).
+//! - Objects of Vector binary logging files (BLF). The objects have to be extracted from
+//!   their log containers upfront, which is done by the BLF byte source.
+pub mod asc;
+pub mod blf;
+pub mod candump;
+pub mod dbc;
+
+use crate::{Error, LogMessage, ParseYield, SingleParser};
+use dbc::DbcMetadata;
+use memchr::memchr;
+use serde::Serialize;
+use std::{
+    fmt::{self, Display},
+    io::Write,
+    time::Duration,
+};
+
+/// Marker for a column separator in the output string.
+const COLUMN_SEP: &str = "\u{0004}"; // EOT
+
+/// Data lengths of CAN FD frames for each DLC.
+const FD_DATA_LENGTHS: [usize; 16] = [0, 1, 2, 3, 4, 5, 6, 7, 8, 12, 16, 20, 24, 32, 48, 64];
+
+/// Returns the DLC for the given data length of a frame.
+pub fn dlc_from_len(len: usize) -> u8 {
+    FD_DATA_LENGTHS
+        .iter()
+        .position(|l| *l >= len)
+        .unwrap_or(FD_DATA_LENGTHS.len() - 1) as u8
+}
+
+/// Returns the data length of a frame for the given DLC.
+pub fn len_from_dlc(dlc: u8, fd: bool) -> usize {
+    if fd {
+        FD_DATA_LENGTHS[(dlc as usize).min(FD_DATA_LENGTHS.len() - 1)]
+    } else {
+        (dlc as usize).min(8)
+    }
+}
+
+/// Parses `<seconds>.<fraction>` into a duration.
+fn parse_seconds(ts: &str) -> Option<Duration> {
+    let (secs, fraction) = ts.split_once('.').unwrap_or((ts, "0"));
+    let secs = secs.parse::<u64>().ok()?;
+    if fraction.is_empty() || fraction.len() > 9 || !fraction.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    let nanos = fraction.parse::<u32>().ok()? * 10u32.pow(9 - fraction.len() as u32);
+    Some(Duration::new(secs, nanos))
+}
+
+/// Kind of a CAN frame.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum FrameKind {
+    /// Classic CAN data frame.
+    Data,
+    /// Remote transmission request.
+    Remote,
+    /// Error frame.
+    Error,
+    /// CAN FD data frame with the bit rate switch and error state indicator flags.
+    Fd { brs: bool, esi: bool },
+}
+
+/// Direction of a CAN frame from the view of the logging device.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum Direction {
+    Rx,
+    Tx,
+}
+
+/// A single CAN frame read from a log.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct CanFrame {
+    /// Time of the frame. Depending on the log format this is either the time since
+    /// UNIX epoch (`candump`) or the time since start of the measurement (ASC, BLF).
+    pub timestamp: Duration,
+    /// Name of the interface (`candump`) or number of the channel (ASC, BLF).
+    pub channel: String,
+    /// CAN identifier without any flags.
+    pub id: u32,
+    /// Whether the identifier is an extended (29 bit) identifier.
+    pub extended: bool,
+    pub kind: FrameKind,
+    pub direction: Option<Direction>,
+    /// Data length code as sent on the bus.
+    pub dlc: u8,
+    pub data: Vec<u8>,
+}
+
+impl CanFrame {
+    /// Writes the identifier as hex value with three digits for standard and eight
+    /// digits for extended identifiers.
+    fn write_id(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.extended {
+            write!(f, "{:08X}", self.id)
+        } else {
+            write!(f, "{:03X}", self.id)
+        }
+    }
+
+    fn write_data(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.kind {
+            FrameKind::Remote => return write!(f, "Remote"),
+            FrameKind::Error => write!(f, "Error")?,
+            FrameKind::Data | FrameKind::Fd { .. } => {}
+        }
+        for (i, byte) in self.data.iter().enumerate() {
+            if i > 0 || self.kind == FrameKind::Error {
+                write!(f, " ")?;
+            }
+            write!(f, "{byte:02X}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Represents a CAN log message.
+///
+/// The message is displayed with the columns: timestamp, channel, id, dlc, data and
+/// the decoded signals if a DBC definition for the frame is available.
+#[derive(Debug, Serialize)]
+pub struct CanLogMessage {
+    pub frame: CanFrame,
+    decoded: Option<String>,
+    #[serde(skip)]
+    bytes: Vec<u8>,
+}
+
+impl CanLogMessage {
+    /// Creates a new log message for the given frame and its original bytes.
+    pub fn new(frame: CanFrame, bytes: Vec<u8>, dbc_metadata: Option<&DbcMetadata>) -> Self {
+        let decoded = dbc_metadata
+            .filter(|_| matches!(frame.kind, FrameKind::Data | FrameKind::Fd { .. }))
+            .and_then(|dbc| dbc.decode(frame.id, frame.extended, &frame.data))
+            .map(|decoded| decoded.to_string());
+        CanLogMessage {
+            frame,
+            decoded,
+            bytes,
+        }
+    }
+
+    /// Returns the signals decoded with the DBC files, if any.
+    pub fn decoded(&self) -> Option<&str> {
+        self.decoded.as_deref()
+    }
+}
+
+impl LogMessage for CanLogMessage {
+    fn to_writer<W: Write>(&self, writer: &mut W) -> Result<usize, std::io::Error> {
+        writer.write_all(&self.bytes)?;
+        Ok(self.bytes.len())
+    }
+}
+
+impl Display for CanLogMessage {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let frame = &self.frame;
+        write!(
+            f,
+            "{}.{:06}{COLUMN_SEP}{}{COLUMN_SEP}",
+            frame.timestamp.as_secs(),
+            frame.timestamp.subsec_micros(),
+            frame.channel,
+        )?;
+        frame.write_id(f)?;
+        write!(f, "{COLUMN_SEP}{}{COLUMN_SEP}", frame.dlc)?;
+        frame.write_data(f)?;
+        write!(
+            f,
+            "{COLUMN_SEP}{}",
+            self.decoded.as_deref().unwrap_or_default()
+        )
+    }
+}
+
+/// Parser for CAN bus logs (`candump -l`, ASC and BLF objects).
+#[derive(Default)]
+pub struct CanParser {
+    dbc_metadata: Option<DbcMetadata>,
+    asc: asc::AscState,
+}
+
+impl CanParser {
+    /// Creates a new parser decoding frames with the given DBC meta-data.
+    pub fn new(dbc_metadata: Option<DbcMetadata>) -> Self {
+        CanParser {
+            dbc_metadata,
+            asc: asc::AscState::default(),
+        }
+    }
+
+    fn yield_frame(
+        &self,
+        frame: Option<CanFrame>,
+        bytes: &[u8],
+    ) -> Option<ParseYield<CanLogMessage>> {
+        frame.map(|frame| {
+            CanLogMessage::new(frame, bytes.to_vec(), self.dbc_metadata.as_ref()).into()
+        })
+    }
+
+    /// Parses one line of a text based log.
+    fn parse_line(&mut self, line: &str) -> Option<CanFrame> {
+        let line = line.trim();
+        if line.starts_with('(') {
+            candump::parse_line(line)
+        } else {
+            self.asc.parse_line(line)
+        }
+    }
+}
+
+impl SingleParser<CanLogMessage> for CanParser {
+    fn parse_item(
+        &mut self,
+        input: &[u8],
+        _timestamp: Option<u64>,
+    ) -> Result<(usize, Option<ParseYield<CanLogMessage>>), Error> {
+        let (consumed, frame) = if input.starts_with(blf::OBJECT_SIGNATURE) {
+            blf::parse_object(input)?
+        } else {
+            let Some(line_len) = memchr(b'\n', input) else {
+                return Err(Error::Incomplete);
+            };
+            let line = String::from_utf8_lossy(&input[..line_len]);
+            (line_len + 1, self.parse_line(&line))
+        };
+        Ok((consumed, self.yield_frame(frame, &input[..consumed])))
+    }
+
+    /// Parses the last line of a text based log without a trailing newline. Incomplete BLF
+    /// objects are never parsed.
+    fn parse_rest_item(
+        &mut self,
+        input: &[u8],
+        _timestamp: Option<u64>,
+    ) -> Option<(usize, Option<ParseYield<CanLogMessage>>)> {
+        if input.is_empty() || input.starts_with(blf::OBJECT_SIGNATURE) {
+            return None;
+        }
+        let frame = self.parse_line(&String::from_utf8_lossy(input));
+        Some((input.len(), self.yield_frame(frame, input)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Parser;
+
+    const DBC: &str = r#"
+BO_ 291 Engine: 8 ECU
+ SG_ Speed : 0|16@1+ (0.5,0) [0|0] "rpm" Vector__XXX
+"#;
+
+    fn messages(content: &[u8], dbc: Option<&str>) -> Vec<CanLogMessage> {
+        let mut parser = CanParser::new(dbc.map(|dbc| dbc.parse().expect("valid DBC")));
+        parser
+            .parse(content, None)
+            .expect("parse")
+            .filter_map(|(_, item)| match item {
+                Some(ParseYield::Message(msg)) => Some(msg),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn dlc_mapping() {
+        assert_eq!(dlc_from_len(8), 8);
+        assert_eq!(dlc_from_len(12), 9);
+        assert_eq!(dlc_from_len(64), 15);
+        assert_eq!(len_from_dlc(13, false), 8);
+        assert_eq!(len_from_dlc(13, true), 32);
+    }
+
+    #[test]
+    fn columns() {
+        let msgs = messages(b"(1436509052.249713) vcan0 123#E803\n", None);
+        assert_eq!(msgs.len(), 1);
+        assert_eq!(
+            msgs[0].to_string(),
+            "1436509052.249713\u{4}vcan0\u{4}123\u{4}2\u{4}E8 03\u{4}"
+        );
+    }
+
+    #[test]
+    fn decode_with_dbc() {
+        let msgs = messages(b"(1436509052.249713) vcan0 123#E803\n", Some(DBC));
+        assert_eq!(msgs[0].decoded(), Some("Engine Speed=500 rpm"));
+    }
+
+    #[test]
+    fn mixed_content() {
+        let content = b"(1.000000) can0 123#01\ndate Mon Sep 30 15:06:13.191 pm 2019\n   0.5 1  456 Rx d 1 02\n";
+        let msgs = messages(content, None);
+        assert_eq!(msgs.len(), 2);
+        assert_eq!(msgs[0].frame.channel, "can0");
+        assert_eq!(msgs[1].frame.id, 0x456);
+    }
+
+    #[test]
+    fn last_line_without_newline() {
+        let content = b"(1.000000) can0 123#01\n(2.000000) can0 456#02";
+        let mut parser = CanParser::new(None);
+        let items: Vec<_> = parser.parse(content, None).expect("parse").collect();
+        assert_eq!(items.len(), 1);
+        let rest = &content[items[0].0..];
+        assert!(matches!(parser.parse(rest, None), Err(Error::Incomplete)));
+        match parser.parse_rest(rest, None) {
+            Some((consumed, Some(ParseYield::Message(msg)))) => {
+                assert_eq!(consumed, rest.len());
+                assert_eq!(msg.frame.id, 0x456);
+            }
+            _ => panic!("last line should be parsed"),
+        }
+        assert!(parser.parse_rest(&[], None).is_none());
+    }
+
+    #[test]
+    fn raw_bytes_are_kept() {
+        let line = b"(1.000000) can0 123#01\n";
+        let msgs = messages(line, None);
+        let mut out = Vec::new();
+        assert_eq!(msgs[0].to_writer(&mut out).unwrap(), line.len());
+        assert_eq!(out, line);
+    }
+}
@@ -0,0 +1,539 @@
+//! # Non-verbose DLT metadata from AUTOSAR ARXML files
+//!
+//! The log message catalogue of an ECU can be described in ARXML with `DLT-MESSAGE`
+//! elements (message id, message type and the `DLT-ARGUMENT` list). The data types of the
+//! arguments are resolved through the referenced `SW-BASE-TYPE` elements. Applications
+//! and contexts are taken from `DLT-APPLICATION` and `DLT-CONTEXT` elements, which may
+//! either contain their messages and contexts or reference them.
+//!
+//! References are resolved by the short name of the referenced element only, since the
+//! package structure differs between the tools generating the files.
+use dlt_core::dlt::{
+    ExtendedHeader, FloatWidth, LogLevel, MessageType, StringCoding, TypeInfo, TypeInfoKind,
+    TypeLength,
+};
+use log::{debug, warn};
+use roxmltree::{Document, Node};
+use std::{collections::HashMap, fs, path::PathBuf, str::FromStr};
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum ArxmlError {
+    #[error("Fail to read ARXML file: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("Invalid ARXML content: {0}")]
+    Xml(#[from] roxmltree::Error),
+    #[error("Invalid DLT message {name}: {msg}")]
+    Message { name: String, msg: String },
+}
+
+/// Argument of a non-verbose DLT message.
+#[derive(Debug, Clone)]
+pub struct ArxmlArgument {
+    pub name: String,
+    pub type_info: TypeInfo,
+}
+
+/// Non-verbose DLT message described in ARXML.
+#[derive(Debug, Clone)]
+pub struct ArxmlMessage {
+    pub id: u32,
+    pub name: String,
+    pub application_id: Option<String>,
+    pub context_id: Option<String>,
+    pub message_type: Option<MessageType>,
+    pub arguments: Vec<ArxmlArgument>,
+}
+
+impl ArxmlMessage {
+    /// Returns the type information of all arguments in order of their appearance.
+    pub fn signal_types(&self) -> Vec<TypeInfo> {
+        self.arguments
+            .iter()
+            .map(|arg| arg.type_info.clone())
+            .collect()
+    }
+}
+
+/// Catalogue of non-verbose DLT messages read from ARXML files.
+#[derive(Debug, Default, Clone)]
+pub struct ArxmlMetadata {
+    /// Messages by their id. Different applications may use the same message id.
+    messages: HashMap<u32, Vec<ArxmlMessage>>,
+}
+
+impl ArxmlMetadata {
+    /// Returns a new meta-data from the given ARXML files.
+    ///
+    /// Invalid files and messages are skipped. Returns `None` if no message could be loaded.
+    pub fn from_arxml_files(paths: Vec<PathBuf>) -> Option<Self> {
+        let mut metadata = ArxmlMetadata::default();
+        for path in paths.iter() {
+            match fs::read(path)
+                .map_err(ArxmlError::from)
+                .and_then(|bytes| String::from_utf8_lossy(&bytes).parse::<ArxmlMetadata>())
+            {
+                Ok(content) => metadata.extend(content),
+                Err(err) => warn!("Fail to load ARXML file {}: {err}", path.display()),
+            }
+        }
+        debug!("ARXML: {} DLT messages loaded", metadata.len());
+        if metadata.is_empty() {
+            None
+        } else {
+            Some(metadata)
+        }
+    }
+
+    /// Returns the description of the message with the given id.
+    ///
+    /// If several messages share the id, the one matching the application and context of
+    /// the extended header is preferred.
+    pub fn get_message(
+        &self,
+        id: u32,
+        extended_header: Option<&ExtendedHeader>,
+    ) -> Option<&ArxmlMessage> {
+        let candidates = self.messages.get(&id)?;
+        extended_header
+            .and_then(|eh| {
+                candidates.iter().find(|msg| {
+                    msg.application_id.as_deref() == Some(eh.application_id.as_str())
+                        && msg.context_id.as_deref() == Some(eh.context_id.as_str())
+                })
+            })
+            .or_else(|| candidates.first())
+    }
+
+    pub fn len(&self) -> usize {
+        self.messages.values().map(Vec::len).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.messages.is_empty()
+    }
+
+    fn extend(&mut self, other: ArxmlMetadata) {
+        for (id, messages) in other.messages {
+            self.messages.entry(id).or_default().extend(messages);
+        }
+    }
+}
+
+impl FromStr for ArxmlMetadata {
+    type Err = ArxmlError;
+
+    fn from_str(content: &str) -> Result<Self, Self::Err> {
+        let doc = Document::parse(content)?;
+        let base_types: HashMap<&str, Node> = elements(&doc, "SW-BASE-TYPE")
+            .filter_map(|node| Some((short_name(node)?, node)))
+            .collect();
+        let owners = message_owners(&doc);
+        let mut metadata = ArxmlMetadata::default();
+        for node in elements(&doc, "DLT-MESSAGE") {
+            // A single unsupported message must not make the whole catalogue unusable.
+            let message = match parse_message(node, &base_types, &owners) {
+                Ok(message) => message,
+                Err(err) => {
+                    warn!("ARXML: {err}, the message is skipped");
+                    continue;
+                }
+            };
+            metadata
+                .messages
+                .entry(message.id)
+                .or_default()
+                .push(message);
+        }
+        Ok(metadata)
+    }
+}
+
+/// Application and context id of a message.
+type Owner = (Option<String>, Option<String>);
+
+/// Maps the short names of messages to the application and context they belong to.
+fn message_owners(doc: &Document) -> HashMap<String, Owner> {
+    let mut owners: HashMap<String, Owner> = HashMap::new();
+    for context in elements(doc, "DLT-CONTEXT") {
+        let context_id = child_text(context, "CONTEXT-ID").map(str::to_owned);
+        for msg in referenced(context, "DLT-MESSAGE-REF", "DLT-MESSAGE") {
+            owners.insert(msg.to_owned(), (None, context_id.clone()));
+        }
+    }
+    for app in elements(doc, "DLT-APPLICATION") {
+        let app_id = child_text(app, "APPLICATION-ID").map(str::to_owned);
+        for context in elements_of(app, "DLT-CONTEXT") {
+            for msg in referenced(context, "DLT-MESSAGE-REF", "DLT-MESSAGE") {
+                if let Some(owner) = owners.get_mut(msg) {
+                    owner.0 = app_id.clone();
+                }
+            }
+        }
+        for context in descendant_texts(app, "DLT-CONTEXT-REF").filter_map(last_segment) {
+            let Some(context) =
+                elements(doc, "DLT-CONTEXT").find(|c| short_name(*c) == Some(context))
+            else {
+                continue;
+            };
+            for msg in referenced(context, "DLT-MESSAGE-REF", "DLT-MESSAGE") {
+                if let Some(owner) = owners.get_mut(msg) {
+                    owner.0 = app_id.clone();
+                }
+            }
+        }
+    }
+    owners
+}
+
+fn parse_message(
+    node: Node,
+    base_types: &HashMap<&str, Node>,
+    owners: &HashMap<String, Owner>,
+) -> Result<ArxmlMessage, ArxmlError> {
+    let name = short_name(node).unwrap_or_default().to_owned();
+    let error = |msg: String| ArxmlError::Message {
+        name: name.clone(),
+        msg,
+    };
+    let id = child_text(node, "MESSAGE-ID")
+        .ok_or_else(|| error("MESSAGE-ID is missing".into()))
+        .and_then(|id| parse_number(id).ok_or_else(|| error(format!("invalid id {id}"))))?;
+    let arguments = elements_of(node, "DLT-ARGUMENT")
+        .map(|arg| {
+            let arg_name = short_name(arg).unwrap_or_default().to_owned();
+            let base_type = descendant_texts(arg, "BASE-TYPE-REF")
+                .filter_map(last_segment)
+                .find_map(|name| base_types.get(name))
+                .ok_or_else(|| error(format!("no base type of argument {arg_name}")))?;
+            let type_info = type_info(*base_type)
+                .ok_or_else(|| error(format!("unsupported base type of argument {arg_name}")))?;
+            Ok(ArxmlArgument {
+                name: arg_name,
+                type_info,
+            })
+        })
+        .collect::<Result<Vec<_>, ArxmlError>>()?;
+    let (application_id, context_id) = owners.get(&name).cloned().unwrap_or_default();
+    Ok(ArxmlMessage {
+        id,
+        application_id,
+        context_id,
+        message_type: child_text(node, "MESSAGE-TYPE-INFO").and_then(message_type),
+        arguments,
+        name,
+    })
+}
+
+/// Converts a `SW-BASE-TYPE` into the DLT type information.
+fn type_info(base_type: Node) -> Option<TypeInfo> {
+    let size: u32 = child_text(base_type, "BASE-TYPE-SIZE")?.parse().ok()?;
+    let encoding = child_text(base_type, "BASE-TYPE-ENCODING")
+        .unwrap_or("NONE")
+        .to_ascii_uppercase();
+    let length = || match size {
+        8 => Some(TypeLength::BitLength8),
+        16 => Some(TypeLength::BitLength16),
+        32 => Some(TypeLength::BitLength32),
+        64 => Some(TypeLength::BitLength64),
+        128 => Some(TypeLength::BitLength128),
+        _ => None,
+    };
+    let (kind, coding) = match encoding.as_str() {
+        "2C" | "1C" | "SM" => (TypeInfoKind::Signed(length()?), StringCoding::UTF8),
+        "IEEE754" => (
+            TypeInfoKind::Float(match size {
+                32 => FloatWidth::Width32,
+                64 => FloatWidth::Width64,
+                _ => return None,
+            }),
+            StringCoding::UTF8,
+        ),
+        "BOOLEAN" => (TypeInfoKind::Bool, StringCoding::UTF8),
+        "UTF-8" | "UTF-16" => (TypeInfoKind::StringType, StringCoding::UTF8),
+        "ISO-8859-1" | "WINDOWS-1252" | "ASCII" => (TypeInfoKind::StringType, StringCoding::ASCII),
+        "BCD-P" | "BCD-UP" | "DSP-FRACTIONAL" => return None,
+        _ => (TypeInfoKind::Unsigned(length()?), StringCoding::UTF8),
+    };
+    Some(TypeInfo {
+        kind,
+        coding,
+        has_variable_info: false,
+        has_trace_info: false,
+    })
+}
+
+/// Parses the message type info (e.g. `DLT_LOG_INFO` or `DLT-LOG-INFO`).
+fn message_type(info: &str) -> Option<MessageType> {
+    let level = match info.to_ascii_uppercase().replace('-', "_").as_str() {
+        "DLT_LOG_FATAL" => LogLevel::Fatal,
+        "DLT_LOG_ERROR" => LogLevel::Error,
+        "DLT_LOG_WARN" => LogLevel::Warn,
+        "DLT_LOG_INFO" => LogLevel::Info,
+        "DLT_LOG_DEBUG" => LogLevel::Debug,
+        "DLT_LOG_VERBOSE" => LogLevel::Verbose,
+        _ => return None,
+    };
+    Some(MessageType::Log(level))
+}
+
+fn parse_number(value: &str) -> Option<u32> {
+    match value
+        .strip_prefix("0x")
+        .or_else(|| value.strip_prefix("0X"))
+    {
+        Some(hex) => u32::from_str_radix(hex, 16).ok(),
+        None => value.parse().ok(),
+    }
+}
+
+/// Returns all elements of the document with the given tag name.
+fn elements<'a, 'input>(
+    doc: &'a Document<'input>,
+    tag: &'static str,
+) -> impl Iterator<Item = Node<'a, 'input>> {
+    doc.descendants().filter(move |n| n.has_tag_name(tag))
+}
+
+/// Returns all elements below the node with the given tag name.
+fn elements_of<'a, 'input: 'a>(
+    node: Node<'a, 'input>,
+    tag: &'static str,
+) -> impl Iterator<Item = Node<'a, 'input>> {
+    node.descendants()
+        .skip(1)
+        .filter(move |n| n.has_tag_name(tag))
+}
+
+fn descendant_texts<'a, 'input: 'a>(
+    node: Node<'a, 'input>,
+    tag: &'static str,
+) -> impl Iterator<Item = &'a str> {
+    elements_of(node, tag).filter_map(|n| n.text().map(str::trim))
+}
+
+/// Returns the short names of the elements referenced by the node or contained in it.
+fn referenced<'a, 'input: 'a>(
+    node: Node<'a, 'input>,
+    ref_tag: &'static str,
+    tag: &'static str,
+) -> impl Iterator<Item = &'a str> {
+    descendant_texts(node, ref_tag)
+        .filter_map(last_segment)
+        .chain(elements_of(node, tag).filter_map(short_name))
+}
+
+fn child_text<'a>(node: Node<'a, '_>, tag: &str) -> Option<&'a str> {
+    node.children()
+        .find(|n| n.has_tag_name(tag))
+        .and_then(|n| n.text())
+        .map(str::trim)
+}
+
+fn short_name<'a>(node: Node<'a, '_>) -> Option<&'a str> {
+    child_text(node, "SHORT-NAME")
+}
+
+/// Returns the last segment of a reference path (`/Package/Element` -> `Element`).
+fn last_segment(path: &str) -> Option<&str> {
+    path.rsplit('/').next().filter(|s| !s.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ARXML: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<AUTOSAR xmlns="http://autosar.org/schema/r4.0">
+  <AR-PACKAGES>
+    <AR-PACKAGE>
+      <SHORT-NAME>BaseTypes</SHORT-NAME>
+      <ELEMENTS>
+        <SW-BASE-TYPE>
+          <SHORT-NAME>uint16</SHORT-NAME>
+          <BASE-TYPE-SIZE>16</BASE-TYPE-SIZE>
+          <BASE-TYPE-ENCODING>NONE</BASE-TYPE-ENCODING>
+        </SW-BASE-TYPE>
+        <SW-BASE-TYPE>
+          <SHORT-NAME>sint32</SHORT-NAME>
+          <BASE-TYPE-SIZE>32</BASE-TYPE-SIZE>
+          <BASE-TYPE-ENCODING>2C</BASE-TYPE-ENCODING>
+        </SW-BASE-TYPE>
+        <SW-BASE-TYPE>
+          <SHORT-NAME>float64</SHORT-NAME>
+          <BASE-TYPE-SIZE>64</BASE-TYPE-SIZE>
+          <BASE-TYPE-ENCODING>IEEE754</BASE-TYPE-ENCODING>
+        </SW-BASE-TYPE>
+      </ELEMENTS>
+    </AR-PACKAGE>
+    <AR-PACKAGE>
+      <SHORT-NAME>Dlt</SHORT-NAME>
+      <ELEMENTS>
+        <DLT-APPLICATION>
+          <SHORT-NAME>Engine</SHORT-NAME>
+          <APPLICATION-ID>ENG</APPLICATION-ID>
+          <CONTEXTS>
+            <DLT-CONTEXT>
+              <SHORT-NAME>Speed</SHORT-NAME>
+              <CONTEXT-ID>SPD</CONTEXT-ID>
+              <DLT-MESSAGE-REFS>
+                <DLT-MESSAGE-REF DEST="DLT-MESSAGE">/Dlt/Messages/SpeedChanged</DLT-MESSAGE-REF>
+              </DLT-MESSAGE-REFS>
+            </DLT-CONTEXT>
+          </CONTEXTS>
+        </DLT-APPLICATION>
+        <DLT-APPLICATION>
+          <SHORT-NAME>Brake</SHORT-NAME>
+          <APPLICATION-ID>BRK</APPLICATION-ID>
+          <DLT-CONTEXT-REFS>
+            <DLT-CONTEXT-REF DEST="DLT-CONTEXT">/Dlt/Pressure</DLT-CONTEXT-REF>
+          </DLT-CONTEXT-REFS>
+        </DLT-APPLICATION>
+        <DLT-CONTEXT>
+          <SHORT-NAME>Pressure</SHORT-NAME>
+          <CONTEXT-ID>PRS</CONTEXT-ID>
+          <DLT-MESSAGE-REFS>
+            <DLT-MESSAGE-REF DEST="DLT-MESSAGE">/Dlt/Messages/PressureLow</DLT-MESSAGE-REF>
+          </DLT-MESSAGE-REFS>
+        </DLT-CONTEXT>
+      </ELEMENTS>
+    </AR-PACKAGE>
+    <AR-PACKAGE>
+      <SHORT-NAME>Messages</SHORT-NAME>
+      <ELEMENTS>
+        <DLT-MESSAGE>
+          <SHORT-NAME>SpeedChanged</SHORT-NAME>
+          <DLT-ARGUMENTS>
+            <DLT-ARGUMENT>
+              <SHORT-NAME>speed</SHORT-NAME>
+              <NETWORK-REPRESENTATION>
+                <SW-DATA-DEF-PROPS-VARIANTS>
+                  <SW-DATA-DEF-PROPS-CONDITIONAL>
+                    <BASE-TYPE-REF DEST="SW-BASE-TYPE">/BaseTypes/uint16</BASE-TYPE-REF>
+                  </SW-DATA-DEF-PROPS-CONDITIONAL>
+                </SW-DATA-DEF-PROPS-VARIANTS>
+              </NETWORK-REPRESENTATION>
+            </DLT-ARGUMENT>
+            <DLT-ARGUMENT>
+              <SHORT-NAME>acceleration</SHORT-NAME>
+              <NETWORK-REPRESENTATION>
+                <BASE-TYPE-REF DEST="SW-BASE-TYPE">/BaseTypes/float64</BASE-TYPE-REF>
+              </NETWORK-REPRESENTATION>
+            </DLT-ARGUMENT>
+          </DLT-ARGUMENTS>
+          <MESSAGE-ID>0x100</MESSAGE-ID>
+          <MESSAGE-TYPE-INFO>DLT_LOG_INFO</MESSAGE-TYPE-INFO>
+        </DLT-MESSAGE>
+        <DLT-MESSAGE>
+          <SHORT-NAME>PressureLow</SHORT-NAME>
+          <DLT-ARGUMENTS>
+            <DLT-ARGUMENT>
+              <SHORT-NAME>pressure</SHORT-NAME>
+              <NETWORK-REPRESENTATION>
+                <BASE-TYPE-REF DEST="SW-BASE-TYPE">/BaseTypes/sint32</BASE-TYPE-REF>
+              </NETWORK-REPRESENTATION>
+            </DLT-ARGUMENT>
+          </DLT-ARGUMENTS>
+          <MESSAGE-ID>257</MESSAGE-ID>
+          <MESSAGE-TYPE-INFO>DLT-LOG-WARN</MESSAGE-TYPE-INFO>
+        </DLT-MESSAGE>
+      </ELEMENTS>
+    </AR-PACKAGE>
+  </AR-PACKAGES>
+</AUTOSAR>
+"#;
+
+    #[test]
+    fn parse_messages() {
+        let metadata: ArxmlMetadata = ARXML.parse().expect("valid arxml");
+        assert_eq!(metadata.len(), 2);
+
+        let msg = metadata.get_message(0x100, None).expect("message 0x100");
+        assert_eq!(msg.name, "SpeedChanged");
+        assert_eq!(msg.application_id.as_deref(), Some("ENG"));
+        assert_eq!(msg.context_id.as_deref(), Some("SPD"));
+        assert!(matches!(
+            msg.message_type,
+            Some(MessageType::Log(LogLevel::Info))
+        ));
+        assert_eq!(msg.arguments.len(), 2);
+        assert_eq!(msg.arguments[0].name, "speed");
+        assert!(matches!(
+            msg.arguments[0].type_info.kind,
+            TypeInfoKind::Unsigned(TypeLength::BitLength16)
+        ));
+        assert!(matches!(
+            msg.arguments[1].type_info.kind,
+            TypeInfoKind::Float(FloatWidth::Width64)
+        ));
+
+        let msg = metadata.get_message(257, None).expect("message 257");
+        assert_eq!(msg.application_id.as_deref(), Some("BRK"));
+        assert_eq!(msg.context_id.as_deref(), Some("PRS"));
+        assert!(matches!(
+            msg.message_type,
+            Some(MessageType::Log(LogLevel::Warn))
+        ));
+        assert!(matches!(
+            msg.arguments[0].type_info.kind,
+            TypeInfoKind::Signed(TypeLength::BitLength32)
+        ));
+
+        assert!(metadata.get_message(1, None).is_none());
+    }
+
+    #[test]
+    fn prefer_matching_application() {
+        let mut metadata: ArxmlMetadata = ARXML.parse().expect("valid arxml");
+        let mut other = metadata.get_message(0x100, None).cloned().unwrap();
+        other.application_id = Some(String::from("APP2"));
+        other.name = String::from("Other");
+        metadata.messages.entry(0x100).or_default().push(other);
+
+        let header = ExtendedHeader {
+            verbose: false,
+            argument_count: 0,
+            message_type: MessageType::Log(LogLevel::Info),
+            application_id: String::from("APP2"),
+            context_id: String::from("SPD"),
+        };
+        let msg = metadata.get_message(0x100, Some(&header)).unwrap();
+        assert_eq!(msg.name, "Other");
+        let msg = metadata.get_message(0x100, None).unwrap();
+        assert_eq!(msg.name, "SpeedChanged");
+    }
+
+    #[test]
+    fn invalid_content() {
+        assert!(matches!(
+            "<AUTOSAR>".parse::<ArxmlMetadata>(),
+            Err(ArxmlError::Xml(_))
+        ));
+    }
+
+    #[test]
+    fn skip_invalid_messages() {
+        let content = r#"<AUTOSAR>
+            <SW-BASE-TYPE><SHORT-NAME>uint8</SHORT-NAME><BASE-TYPE-SIZE>8</BASE-TYPE-SIZE>
+            <BASE-TYPE-ENCODING>NONE</BASE-TYPE-ENCODING></SW-BASE-TYPE>
+            <SW-BASE-TYPE><SHORT-NAME>bcd</SHORT-NAME><BASE-TYPE-SIZE>8</BASE-TYPE-SIZE>
+            <BASE-TYPE-ENCODING>BCD-P</BASE-TYPE-ENCODING></SW-BASE-TYPE>
+            <DLT-MESSAGE><SHORT-NAME>MissingType</SHORT-NAME>
+            <MESSAGE-ID>1</MESSAGE-ID><DLT-ARGUMENTS><DLT-ARGUMENT><SHORT-NAME>a</SHORT-NAME>
+            <BASE-TYPE-REF>/Types/unknown</BASE-TYPE-REF></DLT-ARGUMENT></DLT-ARGUMENTS>
+            </DLT-MESSAGE>
+            <DLT-MESSAGE><SHORT-NAME>Unsupported</SHORT-NAME>
+            <MESSAGE-ID>2</MESSAGE-ID><DLT-ARGUMENTS><DLT-ARGUMENT><SHORT-NAME>a</SHORT-NAME>
+            <BASE-TYPE-REF>/Types/bcd</BASE-TYPE-REF></DLT-ARGUMENT></DLT-ARGUMENTS>
+            </DLT-MESSAGE>
+            <DLT-MESSAGE><SHORT-NAME>InvalidId</SHORT-NAME>
+            <MESSAGE-ID>one</MESSAGE-ID></DLT-MESSAGE>
+            <DLT-MESSAGE><SHORT-NAME>Valid</SHORT-NAME>
+            <MESSAGE-ID>4</MESSAGE-ID><DLT-ARGUMENTS><DLT-ARGUMENT><SHORT-NAME>a</SHORT-NAME>
+            <BASE-TYPE-REF>/Types/uint8</BASE-TYPE-REF></DLT-ARGUMENT></DLT-ARGUMENTS>
+            </DLT-MESSAGE></AUTOSAR>"#;
+        let metadata: ArxmlMetadata = content.parse().expect("valid arxml");
+        assert_eq!(metadata.len(), 1);
+        assert_eq!(metadata.get_message(4, None).unwrap().name, "Valid");
+    }
+}
@@ -0,0 +1,666 @@
+//! # FlexRay frame definitions from FIBEX files
+//!
+//! FlexRay frames are identified by the slot they are sent in. A `FRAME-TRIGGERING` of a
+//! channel assigns a `FRAME` to a slot and to the cycles it is sent in (base cycle and cycle
+//! repetition), so frames sharing a slot are told apart by the cycle counter. A frame
+//! carries `PDU-INSTANCES`, each PDU carries `SIGNAL-INSTANCES`, and the coding of a signal
+//! (bit length, sign, linear scaling, value table and unit) is taken from the referenced
+//! `CODING`.
+//!
+//! Bit positions follow the FIBEX convention: they denote the least significant bit of a
+//! signal, for both byte orders. Signals are stored with the layout of DBC signals, so
+//! they are decoded the same way as CAN signals.
+use crate::can::dbc::{ByteOrder, DbcSignal, DecodedSignal, Multiplex, decode_signals};
+use log::{debug, warn};
+use roxmltree::{Document, Node};
+use std::{
+    collections::HashMap,
+    fmt::{self, Display},
+    fs,
+    path::PathBuf,
+    str::FromStr,
+};
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum FlexrayError {
+    #[error("Fail to read FIBEX file: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("Invalid FIBEX content: {0}")]
+    Xml(#[from] roxmltree::Error),
+    #[error("Invalid FlexRay frame {name}: {msg}")]
+    Frame { name: String, msg: String },
+}
+
+/// PDU placed into a FlexRay frame.
+#[derive(Debug, Clone)]
+pub struct FlexrayPdu {
+    pub name: String,
+    /// Offset of the PDU in the payload of the frame in bytes.
+    pub offset: usize,
+    pub signals: Vec<DbcSignal>,
+}
+
+#[derive(Debug, Clone)]
+pub struct FlexrayFrame {
+    pub name: String,
+    pub size: usize,
+    pub pdus: Vec<FlexrayPdu>,
+}
+
+/// Assignment of a frame to a slot and the cycles it is sent in.
+#[derive(Debug, Clone)]
+struct Triggering {
+    base_cycle: u8,
+    repetition: u8,
+    frame: usize,
+}
+
+impl Triggering {
+    fn matches(&self, cycle: u8) -> bool {
+        self.repetition == 0
+            || cycle >= self.base_cycle && (cycle - self.base_cycle) % self.repetition == 0
+    }
+}
+
+/// Frame decoded with its FIBEX definition.
+#[derive(Debug, Clone)]
+pub struct DecodedFlexrayFrame<'a> {
+    pub frame: &'a FlexrayFrame,
+    pub signals: Vec<DecodedSignal<'a>>,
+}
+
+impl DecodedFlexrayFrame<'_> {
+    /// Writes the decoded signals using the given separator between them.
+    pub fn write_signals(&self, f: &mut impl fmt::Write, separator: &str) -> fmt::Result {
+        for (i, signal) in self.signals.iter().enumerate() {
+            if i > 0 {
+                f.write_str(separator)?;
+            }
+            write!(f, "{signal}")?;
+        }
+        Ok(())
+    }
+}
+
+impl Display for DecodedFlexrayFrame<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.frame.name)?;
+        if !self.signals.is_empty() {
+            write!(f, " ")?;
+            self.write_signals(f, " ")?;
+        }
+        Ok(())
+    }
+}
+
+/// FlexRay frames read from FIBEX files.
+#[derive(Debug, Default, Clone)]
+pub struct FlexrayMetadata {
+    frames: Vec<FlexrayFrame>,
+    /// Triggerings of the frames by slot id.
+    slots: HashMap<u16, Vec<Triggering>>,
+}
+
+impl FlexrayMetadata {
+    /// Returns a new meta-data from the given FIBEX files.
+    ///
+    /// Invalid files and frames are skipped. Returns `None` if no frame could be loaded,
+    /// e.g. if the files describe SOME/IP services only.
+    pub fn from_fibex_files(paths: Vec<PathBuf>) -> Option<Self> {
+        let mut metadata = FlexrayMetadata::default();
+        for path in paths.iter() {
+            match fs::read(path)
+                .map_err(FlexrayError::from)
+                .and_then(|bytes| String::from_utf8_lossy(&bytes).parse::<FlexrayMetadata>())
+            {
+                Ok(content) => metadata.extend(content),
+                Err(err) => warn!("Fail to load FIBEX file {}: {err}", path.display()),
+            }
+        }
+        debug!("FIBEX: {} FlexRay frames loaded", metadata.len());
+        if metadata.is_empty() {
+            None
+        } else {
+            Some(metadata)
+        }
+    }
+
+    /// Returns the frame sent in the given slot.
+    ///
+    /// * `slot`: Slot id of the frame.
+    /// * `cycle`: Cycle counter of the frame, if known. Without the cycle counter the first
+    ///   frame assigned to the slot is returned.
+    pub fn get_frame(&self, slot: u16, cycle: Option<u8>) -> Option<&FlexrayFrame> {
+        let triggerings = self.slots.get(&slot)?;
+        cycle
+            .and_then(|cycle| triggerings.iter().find(|t| t.matches(cycle)))
+            .or_else(|| triggerings.first())
+            .and_then(|t| self.frames.get(t.frame))
+    }
+
+    pub fn len(&self) -> usize {
+        self.frames.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.frames.is_empty()
+    }
+
+    /// Decodes the payload of the frame sent in the given slot and cycle.
+    ///
+    /// Returns `None` if there is no frame assigned to the slot.
+    pub fn decode<'a>(
+        &'a self,
+        slot: u16,
+        cycle: Option<u8>,
+        data: &[u8],
+    ) -> Option<DecodedFlexrayFrame<'a>> {
+        let frame = self.get_frame(slot, cycle)?;
+        let signals = frame
+            .pdus
+            .iter()
+            .flat_map(|pdu| decode_signals(&pdu.signals, data.get(pdu.offset..).unwrap_or(&[])))
+            .collect();
+        Some(DecodedFlexrayFrame { frame, signals })
+    }
+
+    fn extend(&mut self, other: FlexrayMetadata) {
+        let shift = self.frames.len();
+        self.frames.extend(other.frames);
+        for (slot, triggerings) in other.slots {
+            self.slots
+                .entry(slot)
+                .or_default()
+                .extend(triggerings.into_iter().map(|mut t| {
+                    t.frame += shift;
+                    t
+                }));
+        }
+    }
+}
+
+impl FromStr for FlexrayMetadata {
+    type Err = FlexrayError;
+
+    fn from_str(content: &str) -> Result<Self, Self::Err> {
+        let doc = Document::parse(content)?;
+        let by_id = |tag: &'static str| -> HashMap<&str, Node> {
+            elements(&doc, tag)
+                .filter_map(|node| Some((node.attribute("ID")?, node)))
+                .collect()
+        };
+        let pdus = by_id("PDU");
+        let signals = by_id("SIGNAL");
+        let codings = by_id("CODING");
+        let units = by_id("UNIT");
+        let mut metadata = FlexrayMetadata::default();
+        let mut frames: HashMap<&str, Option<usize>> = HashMap::new();
+        for triggering in elements(&doc, "FRAME-TRIGGERING") {
+            let Some(timing) = elements_of(triggering, "ABSOLUTELY-SCHEDULED-TIMING").next() else {
+                // Triggerings of other buses (e.g. CAN identifiers) have no slots
+                continue;
+            };
+            let Some(slot) = child_text(timing, "SLOT-ID").and_then(|v| v.parse::<u16>().ok())
+            else {
+                warn!("FIBEX: frame triggering without valid slot id is skipped");
+                continue;
+            };
+            let Some(frame_id) = ref_of(triggering, "FRAME-REF") else {
+                continue;
+            };
+            let index = match frames.get(frame_id) {
+                Some(index) => *index,
+                None => {
+                    // A single unsupported frame must not make the other frames unusable.
+                    let index = elements(&doc, "FRAME")
+                        .find(|node| node.attribute("ID") == Some(frame_id))
+                        .map(|node| parse_frame(node, &pdus, &signals, &codings, &units))
+                        .and_then(|frame| {
+                            frame
+                                .inspect_err(|err| warn!("FIBEX: {err}, the frame is skipped"))
+                                .ok()
+                        })
+                        .map(|frame| {
+                            metadata.frames.push(frame);
+                            metadata.frames.len() - 1
+                        });
+                    frames.insert(frame_id, index);
+                    index
+                }
+            };
+            let Some(frame) = index else {
+                continue;
+            };
+            metadata.slots.entry(slot).or_default().push(Triggering {
+                base_cycle: child_text(timing, "BASE-CYCLE")
+                    .and_then(|v| v.parse().ok())
+                    .unwrap_or(0),
+                repetition: child_text(timing, "CYCLE-REPETITION")
+                    .and_then(|v| v.parse().ok())
+                    .unwrap_or(1),
+                frame,
+            });
+        }
+        Ok(metadata)
+    }
+}
+
+fn parse_frame(
+    node: Node,
+    pdus: &HashMap<&str, Node>,
+    signals: &HashMap<&str, Node>,
+    codings: &HashMap<&str, Node>,
+    units: &HashMap<&str, Node>,
+) -> Result<FlexrayFrame, FlexrayError> {
+    let name = short_name(node).unwrap_or_default().to_owned();
+    let error = |msg: String| FlexrayError::Frame {
+        name: name.clone(),
+        msg,
+    };
+    let size = child_text(node, "BYTE-LENGTH")
+        .and_then(|v| v.parse::<usize>().ok())
+        .ok_or_else(|| error("BYTE-LENGTH is missing".into()))?;
+    let pdus = elements_of(node, "PDU-INSTANCE")
+        .map(|instance| {
+            let pdu = ref_of(instance, "PDU-REF")
+                .and_then(|id| pdus.get(id))
+                .ok_or_else(|| error("unknown PDU".into()))?;
+            let pdu_name = short_name(*pdu).unwrap_or_default().to_owned();
+            let position = child_text(instance, "BIT-POSITION")
+                .and_then(|v| v.parse::<usize>().ok())
+                .unwrap_or(0);
+            if !position.is_multiple_of(8) {
+                return Err(error(format!("PDU {pdu_name} isn't aligned to bytes")));
+            }
+            let signals = elements_of(*pdu, "SIGNAL-INSTANCE")
+                .filter_map(|instance| {
+                    let signal = parse_signal(instance, signals, codings, units);
+                    if signal.is_none() {
+                        warn!("FIBEX: unsupported signal in PDU {pdu_name} is skipped");
+                    }
+                    signal
+                })
+                .collect();
+            Ok(FlexrayPdu {
+                name: pdu_name,
+                offset: position / 8,
+                signals,
+            })
+        })
+        .collect::<Result<Vec<FlexrayPdu>, FlexrayError>>()?;
+    Ok(FlexrayFrame { name, size, pdus })
+}
+
+fn parse_signal(
+    instance: Node,
+    signals: &HashMap<&str, Node>,
+    codings: &HashMap<&str, Node>,
+    units: &HashMap<&str, Node>,
+) -> Option<DbcSignal> {
+    let signal = ref_of(instance, "SIGNAL-REF").and_then(|id| signals.get(id))?;
+    let coding = ref_of(*signal, "CODING-REF").and_then(|id| codings.get(id))?;
+    let coded_type = elements_of(*coding, "CODED-TYPE").next()?;
+    let size: u16 = child_text(coded_type, "BIT-LENGTH")?.parse().ok()?;
+    let signed = coded_type.attributes().any(|attr| match attr.name() {
+        "ENCODING" => matches!(attr.value(), "SIGNED" | "2C"),
+        "BASE-DATA-TYPE" => attr.value().starts_with("A_INT"),
+        _ => false,
+    });
+    let position: u16 = child_text(instance, "BIT-POSITION")?.parse().ok()?;
+    let (byte_order, start_bit) = if child_text(instance, "IS-HIGH-LOW-BYTE-ORDER") == Some("true")
+    {
+        (ByteOrder::BigEndian, motorola_start_bit(position, size)?)
+    } else {
+        (ByteOrder::LittleEndian, position)
+    };
+    let (mut factor, mut offset) = (1.0, 0.0);
+    let mut values = HashMap::new();
+    let mut unit = String::new();
+    if let Some(method) = elements_of(*coding, "COMPU-METHOD").next() {
+        for scale in elements_of(method, "COMPU-SCALE") {
+            if let Some(label) = elements_of(scale, "VT").next().and_then(|n| n.text()) {
+                if let Some(value) = child_text(scale, "LOWER-LIMIT").and_then(|v| v.parse().ok()) {
+                    values.insert(value, label.trim().to_owned());
+                }
+            } else if let Some(coeffs) = elements_of(scale, "COMPU-RATIONAL-COEFFS").next() {
+                let numerator = coefficients(coeffs, "COMPU-NUMERATOR");
+                let denominator = coefficients(coeffs, "COMPU-DENOMINATOR")
+                    .first()
+                    .copied()
+                    .unwrap_or(1.0);
+                if denominator != 0.0 {
+                    offset = numerator.first().copied().unwrap_or(0.0) / denominator;
+                    factor = numerator.get(1).copied().unwrap_or(1.0) / denominator;
+                }
+            }
+        }
+        if let Some(found) = ref_of(method, "UNIT-REF").and_then(|id| units.get(id)) {
+            unit = child_text(*found, "DISPLAY-NAME")
+                .or_else(|| short_name(*found))
+                .unwrap_or_default()
+                .to_owned();
+        }
+    }
+    Some(DbcSignal {
+        name: short_name(*signal)?.to_owned(),
+        start_bit,
+        size,
+        byte_order,
+        signed,
+        factor,
+        offset,
+        unit,
+        multiplex: Multiplex::Plain,
+        values,
+    })
+}
+
+/// Converts the position of the least significant bit of a Motorola signal into the
+/// position of its most significant bit, which is the start bit of DBC signals.
+fn motorola_start_bit(lsb: u16, size: u16) -> Option<u16> {
+    let mut pos = lsb;
+    for _ in 1..size {
+        pos = if pos % 8 == 7 {
+            pos.checked_sub(15)?
+        } else {
+            pos + 1
+        };
+    }
+    Some(pos)
+}
+
+fn coefficients(node: Node, tag: &'static str) -> Vec<f64> {
+    elements_of(node, tag)
+        .flat_map(|n| elements_of(n, "V"))
+        .filter_map(|v| v.text()?.trim().parse().ok())
+        .collect()
+}
+
+/// Returns all elements of the document with the given tag name.
+fn elements<'a, 'input>(
+    doc: &'a Document<'input>,
+    tag: &'static str,
+) -> impl Iterator<Item = Node<'a, 'input>> {
+    doc.descendants().filter(move |n| n.has_tag_name(tag))
+}
+
+/// Returns all elements below the node with the given tag name.
+fn elements_of<'a, 'input: 'a>(
+    node: Node<'a, 'input>,
+    tag: &'static str,
+) -> impl Iterator<Item = Node<'a, 'input>> {
+    node.descendants()
+        .skip(1)
+        .filter(move |n| n.has_tag_name(tag))
+}
+
+fn child_text<'a>(node: Node<'a, '_>, tag: &str) -> Option<&'a str> {
+    node.children()
+        .find(|n| n.has_tag_name(tag))
+        .and_then(|n| n.text())
+        .map(str::trim)
+}
+
+fn short_name<'a>(node: Node<'a, '_>) -> Option<&'a str> {
+    child_text(node, "SHORT-NAME")
+}
+
+/// Returns the id referenced by the `ID-REF` attribute of the child with the given tag.
+fn ref_of<'a>(node: Node<'a, '_>, tag: &str) -> Option<&'a str> {
+    node.children()
+        .find(|n| n.has_tag_name(tag))
+        .and_then(|n| n.attribute("ID-REF"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FIBEX: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<fx:FIBEX xmlns:fx="http://www.asam.net/xml/fbx" xmlns:ho="http://www.asam.net/xml"
+  xmlns:flexray="http://www.asam.net/xml/fbx/flexray">
+  <fx:ELEMENTS>
+    <fx:CHANNELS>
+      <fx:CHANNEL ID="channel_a">
+        <ho:SHORT-NAME>A</ho:SHORT-NAME>
+        <fx:FRAME-TRIGGERINGS>
+          <fx:FRAME-TRIGGERING ID="ft_brake">
+            <fx:TIMINGS>
+              <fx:ABSOLUTELY-SCHEDULED-TIMING>
+                <fx:SLOT-ID>12</fx:SLOT-ID>
+                <fx:BASE-CYCLE>0</fx:BASE-CYCLE>
+                <fx:CYCLE-REPETITION>2</fx:CYCLE-REPETITION>
+              </fx:ABSOLUTELY-SCHEDULED-TIMING>
+            </fx:TIMINGS>
+            <fx:FRAME-REF ID-REF="frame_brake"/>
+          </fx:FRAME-TRIGGERING>
+          <fx:FRAME-TRIGGERING ID="ft_gear">
+            <fx:TIMINGS>
+              <fx:ABSOLUTELY-SCHEDULED-TIMING>
+                <fx:SLOT-ID>12</fx:SLOT-ID>
+                <fx:BASE-CYCLE>1</fx:BASE-CYCLE>
+                <fx:CYCLE-REPETITION>2</fx:CYCLE-REPETITION>
+              </fx:ABSOLUTELY-SCHEDULED-TIMING>
+            </fx:TIMINGS>
+            <fx:FRAME-REF ID-REF="frame_gear"/>
+          </fx:FRAME-TRIGGERING>
+        </fx:FRAME-TRIGGERINGS>
+      </fx:CHANNEL>
+    </fx:CHANNELS>
+    <fx:FRAMES>
+      <fx:FRAME ID="frame_brake">
+        <ho:SHORT-NAME>BrakeStatus</ho:SHORT-NAME>
+        <fx:BYTE-LENGTH>8</fx:BYTE-LENGTH>
+        <fx:FRAME-TYPE>APPLICATION</fx:FRAME-TYPE>
+        <fx:PDU-INSTANCES>
+          <fx:PDU-INSTANCE ID="pi_brake">
+            <fx:PDU-REF ID-REF="pdu_brake"/>
+            <fx:BIT-POSITION>16</fx:BIT-POSITION>
+            <fx:IS-HIGH-LOW-BYTE-ORDER>false</fx:IS-HIGH-LOW-BYTE-ORDER>
+          </fx:PDU-INSTANCE>
+        </fx:PDU-INSTANCES>
+      </fx:FRAME>
+      <fx:FRAME ID="frame_gear">
+        <ho:SHORT-NAME>GearStatus</ho:SHORT-NAME>
+        <fx:BYTE-LENGTH>2</fx:BYTE-LENGTH>
+        <fx:PDU-INSTANCES>
+          <fx:PDU-INSTANCE ID="pi_gear">
+            <fx:PDU-REF ID-REF="pdu_gear"/>
+            <fx:BIT-POSITION>0</fx:BIT-POSITION>
+          </fx:PDU-INSTANCE>
+        </fx:PDU-INSTANCES>
+      </fx:FRAME>
+    </fx:FRAMES>
+    <fx:PDUS>
+      <fx:PDU ID="pdu_brake">
+        <ho:SHORT-NAME>BrakePdu</ho:SHORT-NAME>
+        <fx:BYTE-LENGTH>4</fx:BYTE-LENGTH>
+        <fx:SIGNAL-INSTANCES>
+          <fx:SIGNAL-INSTANCE ID="si_pressure">
+            <fx:BIT-POSITION>8</fx:BIT-POSITION>
+            <fx:IS-HIGH-LOW-BYTE-ORDER>true</fx:IS-HIGH-LOW-BYTE-ORDER>
+            <fx:SIGNAL-REF ID-REF="sig_pressure"/>
+          </fx:SIGNAL-INSTANCE>
+          <fx:SIGNAL-INSTANCE ID="si_temp">
+            <fx:BIT-POSITION>16</fx:BIT-POSITION>
+            <fx:IS-HIGH-LOW-BYTE-ORDER>false</fx:IS-HIGH-LOW-BYTE-ORDER>
+            <fx:SIGNAL-REF ID-REF="sig_temp"/>
+          </fx:SIGNAL-INSTANCE>
+        </fx:SIGNAL-INSTANCES>
+      </fx:PDU>
+      <fx:PDU ID="pdu_gear">
+        <ho:SHORT-NAME>GearPdu</ho:SHORT-NAME>
+        <fx:BYTE-LENGTH>1</fx:BYTE-LENGTH>
+        <fx:SIGNAL-INSTANCES>
+          <fx:SIGNAL-INSTANCE ID="si_gear">
+            <fx:BIT-POSITION>0</fx:BIT-POSITION>
+            <fx:IS-HIGH-LOW-BYTE-ORDER>false</fx:IS-HIGH-LOW-BYTE-ORDER>
+            <fx:SIGNAL-REF ID-REF="sig_gear"/>
+          </fx:SIGNAL-INSTANCE>
+          <fx:SIGNAL-INSTANCE ID="si_unknown">
+            <fx:BIT-POSITION>8</fx:BIT-POSITION>
+            <fx:SIGNAL-REF ID-REF="sig_unknown"/>
+          </fx:SIGNAL-INSTANCE>
+        </fx:SIGNAL-INSTANCES>
+      </fx:PDU>
+    </fx:PDUS>
+    <fx:SIGNALS>
+      <fx:SIGNAL ID="sig_pressure">
+        <ho:SHORT-NAME>Pressure</ho:SHORT-NAME>
+        <fx:CODING-REF ID-REF="coding_pressure"/>
+      </fx:SIGNAL>
+      <fx:SIGNAL ID="sig_temp">
+        <ho:SHORT-NAME>Temperature</ho:SHORT-NAME>
+        <fx:CODING-REF ID-REF="coding_temp"/>
+      </fx:SIGNAL>
+      <fx:SIGNAL ID="sig_gear">
+        <ho:SHORT-NAME>Gear</ho:SHORT-NAME>
+        <fx:CODING-REF ID-REF="coding_gear"/>
+      </fx:SIGNAL>
+    </fx:SIGNALS>
+  </fx:ELEMENTS>
+  <fx:PROCESSING-INFORMATION>
+    <ho:UNIT-SPEC>
+      <ho:UNITS>
+        <ho:UNIT ID="unit_bar">
+          <ho:SHORT-NAME>bar</ho:SHORT-NAME>
+        </ho:UNIT>
+        <ho:UNIT ID="unit_degc">
+          <ho:SHORT-NAME>DegreeCelsius</ho:SHORT-NAME>
+          <ho:DISPLAY-NAME>degC</ho:DISPLAY-NAME>
+        </ho:UNIT>
+      </ho:UNITS>
+    </ho:UNIT-SPEC>
+    <fx:CODINGS>
+      <fx:CODING ID="coding_pressure">
+        <ho:SHORT-NAME>Pressure</ho:SHORT-NAME>
+        <ho:CODED-TYPE ho:BASE-DATA-TYPE="A_UINT16" CATEGORY="STANDARD-LENGTH-TYPE"
+          ENCODING="UNSIGNED">
+          <ho:BIT-LENGTH>16</ho:BIT-LENGTH>
+        </ho:CODED-TYPE>
+        <ho:COMPU-METHODS>
+          <ho:COMPU-METHOD>
+            <ho:SHORT-NAME>Pressure</ho:SHORT-NAME>
+            <ho:CATEGORY>LINEAR</ho:CATEGORY>
+            <ho:UNIT-REF ID-REF="unit_bar"/>
+            <ho:COMPU-INTERNAL-TO-PHYS>
+              <ho:COMPU-SCALES>
+                <ho:COMPU-SCALE>
+                  <ho:COMPU-RATIONAL-COEFFS>
+                    <ho:COMPU-NUMERATOR><ho:V>0</ho:V><ho:V>1</ho:V></ho:COMPU-NUMERATOR>
+                    <ho:COMPU-DENOMINATOR><ho:V>10</ho:V></ho:COMPU-DENOMINATOR>
+                  </ho:COMPU-RATIONAL-COEFFS>
+                </ho:COMPU-SCALE>
+              </ho:COMPU-SCALES>
+            </ho:COMPU-INTERNAL-TO-PHYS>
+          </ho:COMPU-METHOD>
+        </ho:COMPU-METHODS>
+      </fx:CODING>
+      <fx:CODING ID="coding_temp">
+        <ho:SHORT-NAME>Temperature</ho:SHORT-NAME>
+        <ho:CODED-TYPE ho:BASE-DATA-TYPE="A_INT8" CATEGORY="STANDARD-LENGTH-TYPE"
+          ENCODING="SIGNED">
+          <ho:BIT-LENGTH>8</ho:BIT-LENGTH>
+        </ho:CODED-TYPE>
+        <ho:COMPU-METHODS>
+          <ho:COMPU-METHOD>
+            <ho:SHORT-NAME>Temperature</ho:SHORT-NAME>
+            <ho:CATEGORY>LINEAR</ho:CATEGORY>
+            <ho:UNIT-REF ID-REF="unit_degc"/>
+            <ho:COMPU-INTERNAL-TO-PHYS>
+              <ho:COMPU-SCALES>
+                <ho:COMPU-SCALE>
+                  <ho:COMPU-RATIONAL-COEFFS>
+                    <ho:COMPU-NUMERATOR><ho:V>-40</ho:V><ho:V>1</ho:V></ho:COMPU-NUMERATOR>
+                  </ho:COMPU-RATIONAL-COEFFS>
+                </ho:COMPU-SCALE>
+              </ho:COMPU-SCALES>
+            </ho:COMPU-INTERNAL-TO-PHYS>
+          </ho:COMPU-METHOD>
+        </ho:COMPU-METHODS>
+      </fx:CODING>
+      <fx:CODING ID="coding_gear">
+        <ho:SHORT-NAME>Gear</ho:SHORT-NAME>
+        <ho:CODED-TYPE ho:BASE-DATA-TYPE="A_UINT8" CATEGORY="STANDARD-LENGTH-TYPE">
+          <ho:BIT-LENGTH>3</ho:BIT-LENGTH>
+        </ho:CODED-TYPE>
+        <ho:COMPU-METHODS>
+          <ho:COMPU-METHOD>
+            <ho:SHORT-NAME>Gear</ho:SHORT-NAME>
+            <ho:CATEGORY>TEXTTABLE</ho:CATEGORY>
+            <ho:COMPU-INTERNAL-TO-PHYS>
+              <ho:COMPU-SCALES>
+                <ho:COMPU-SCALE>
+                  <ho:LOWER-LIMIT>0</ho:LOWER-LIMIT>
+                  <ho:UPPER-LIMIT>0</ho:UPPER-LIMIT>
+                  <ho:COMPU-CONST><ho:VT>Park</ho:VT></ho:COMPU-CONST>
+                </ho:COMPU-SCALE>
+                <ho:COMPU-SCALE>
+                  <ho:LOWER-LIMIT>3</ho:LOWER-LIMIT>
+                  <ho:UPPER-LIMIT>3</ho:UPPER-LIMIT>
+                  <ho:COMPU-CONST><ho:VT>Drive</ho:VT></ho:COMPU-CONST>
+                </ho:COMPU-SCALE>
+              </ho:COMPU-SCALES>
+            </ho:COMPU-INTERNAL-TO-PHYS>
+          </ho:COMPU-METHOD>
+        </ho:COMPU-METHODS>
+      </fx:CODING>
+    </fx:CODINGS>
+  </fx:PROCESSING-INFORMATION>
+</fx:FIBEX>
+"#;
+
+    #[test]
+    fn parse_frames() {
+        let metadata: FlexrayMetadata = FIBEX.parse().expect("valid fibex");
+        assert_eq!(metadata.len(), 2);
+        let frame = metadata.get_frame(12, Some(0)).expect("frame in cycle 0");
+        assert_eq!(frame.name, "BrakeStatus");
+        assert_eq!(frame.size, 8);
+        assert_eq!(frame.pdus[0].offset, 2);
+        assert_eq!(frame.pdus[0].signals.len(), 2);
+        let pressure = &frame.pdus[0].signals[0];
+        assert_eq!(pressure.byte_order, ByteOrder::BigEndian);
+        assert_eq!(pressure.start_bit, 7);
+        assert_eq!(pressure.factor, 0.1);
+        assert_eq!(pressure.unit, "bar");
+        assert!(metadata.get_frame(13, None).is_none());
+        // The signal without coding is skipped
+        let frame = metadata.get_frame(12, Some(5)).expect("frame in cycle 5");
+        assert_eq!(frame.name, "GearStatus");
+        assert_eq!(frame.pdus[0].signals.len(), 1);
+        // Without the cycle counter the first frame of the slot is used
+        assert_eq!(metadata.get_frame(12, None).unwrap().name, "BrakeStatus");
+    }
+
+    #[test]
+    fn decode_frames() {
+        let metadata: FlexrayMetadata = FIBEX.parse().expect("valid fibex");
+        // pressure raw = 500 -> 50 bar; temperature raw = -10 -> -50 degC
+        let data = [0xFF, 0xFF, 0x01, 0xF4, 0xF6, 0, 0, 0];
+        let frame = metadata.decode(12, Some(2), &data).expect("decoded");
+        assert_eq!(
+            frame.to_string(),
+            "BrakeStatus Pressure=50 bar Temperature=-50 degC"
+        );
+        let frame = metadata.decode(12, Some(3), &[0x03, 0]).expect("decoded");
+        assert_eq!(frame.to_string(), "GearStatus Gear=3 (Drive)");
+    }
+
+    #[test]
+    fn motorola_bit_positions() {
+        assert_eq!(motorola_start_bit(8, 16), Some(7));
+        assert_eq!(motorola_start_bit(0, 8), Some(7));
+        assert_eq!(motorola_start_bit(4, 4), Some(7));
+        assert_eq!(motorola_start_bit(0, 32), None);
+    }
+
+    #[test]
+    fn invalid_content() {
+        assert!(matches!(
+            "<FIBEX>".parse::<FlexrayMetadata>(),
+            Err(FlexrayError::Xml(_))
+        ));
+    }
+}
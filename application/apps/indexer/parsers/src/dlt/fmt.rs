@@ -11,7 +11,11 @@
 // from E.S.R.Labs.
 
 //! # Formatting dlt messages as text
-use crate::someip::{FibexMetadata as FibexSomeipMetadata, SomeipParser};
+use crate::{
+    can::dbc::{CAN_ID_MASK, DbcMetadata, EXTENDED_ID_FLAG},
+    dlt::{arxml::ArxmlMetadata, flexray::FlexrayMetadata, lifecycle::LifecycleInfo},
+    someip::{FibexMetadata as FibexSomeipMetadata, SomeipParser},
+};
 use chrono::{
     TimeZone,
     prelude::{DateTime, Utc},
@@ -19,9 +23,9 @@ use chrono::{
 use chrono_tz::Tz;
 use dlt_core::{
     dlt::{
-        Argument, ControlType, DltTimeStamp, ExtendedHeader, LogLevel, Message, MessageType,
-        NetworkTraceType, PayloadContent, StandardHeader, StorageHeader, StringCoding, TypeInfo,
-        TypeInfoKind, Value,
+        Argument, ControlType, DltTimeStamp, Endianness, ExtendedHeader, LogLevel, Message,
+        MessageType, NetworkTraceType, PayloadContent, StandardHeader, StorageHeader, StringCoding,
+        TypeInfo, TypeInfoKind, Value,
     },
     fibex::{FibexMetadata as FibexDltMetadata, extract_metadata},
     parse::construct_arguments,
@@ -198,12 +202,18 @@ impl From<Option<&String>> for FormatOptions {
     }
 }
 
-/// A dlt message that can be formatted with optional FIBEX data support
+/// A dlt message that can be formatted with optional FIBEX, ARXML and DBC data support
 pub struct FormattableMessage<'a> {
     pub message: Message,
     pub fibex_dlt_metadata: Option<&'a FibexDltMetadata>,
+    pub arxml_metadata: Option<&'a ArxmlMetadata>,
     pub fibex_someip_metadata: Option<&'a FibexSomeipMetadata>,
+    pub dbc_metadata: Option<&'a DbcMetadata>,
+    /// FlexRay frames of the FIBEX files.
+    pub flexray_metadata: Option<&'a FlexrayMetadata>,
     pub options: Option<&'a FormatOptions>,
+    /// Lifecycle of the message if the lifecycle detection is enabled.
+    pub lifecycle: Option<LifecycleInfo>,
 }
 
 impl Serialize for FormattableMessage<'_> {
@@ -211,7 +221,7 @@ impl Serialize for FormattableMessage<'_> {
     where
         S: Serializer,
     {
-        let mut state = serializer.serialize_struct("Message", 10)?;
+        let mut state = serializer.serialize_struct("Message", 12)?;
         let header = &self.message.header;
         let storage_header = &self.message.storage_header;
         let ext_header = &self.message.extended_header;
@@ -291,14 +301,27 @@ impl Serialize for FormattableMessage<'_> {
                 state.serialize_field("app-id", &ext_header_app_id)?;
                 state.serialize_field("context-id", &ext_header_context_id)?;
                 state.serialize_field("message-type", &ext_header_msg_type)?;
-                let arg_string = slices
-                    .iter()
-                    .map(|slice| format!("{slice:02X?}"))
-                    .collect::<Vec<String>>()
-                    .join("|");
+                let arg_string = self.bus_frame_string(slices, "|").unwrap_or_else(|| {
+                    slices
+                        .iter()
+                        .map(|slice| format!("{slice:02X?}"))
+                        .collect::<Vec<String>>()
+                        .join("|")
+                });
                 state.serialize_field("payload", &arg_string)?;
             }
         }
+        // Lifecycle fields are always present to keep the columns stable, but they're empty
+        // if the lifecycle detection is disabled.
+        state.serialize_field("lifecycle", &self.lifecycle.as_ref().map(|lc| lc.id))?;
+        state.serialize_field(
+            "absolute-time",
+            &self
+                .lifecycle
+                .as_ref()
+                .and_then(|lc| lc.absolute_time.as_ref())
+                .map(utc_string),
+        )?;
         state.end()
     }
 }
@@ -308,8 +331,12 @@ impl From<Message> for FormattableMessage<'_> {
         FormattableMessage {
             message,
             fibex_dlt_metadata: None,
+            arxml_metadata: None,
             fibex_someip_metadata: None,
+            dbc_metadata: None,
+            flexray_metadata: None,
             options: None,
+            lifecycle: None,
         }
     }
 }
@@ -405,11 +432,13 @@ impl FormattableMessage<'_> {
                 ))
             }
             PayloadContent::NetworkTrace(slices) => {
-                let payload_string = slices
-                    .iter()
-                    .map(|slice| format!("{slice:02X?}"))
-                    .collect::<Vec<String>>()
-                    .join("|");
+                let payload_string = self.bus_frame_string(slices, "|").unwrap_or_else(|| {
+                    slices
+                        .iter()
+                        .map(|slice| format!("{slice:02X?}"))
+                        .collect::<Vec<String>>()
+                        .join("|")
+                });
                 Ok(PrintableMessage::new(
                     ext_h_app_id,
                     eh_ctx_id,
@@ -492,7 +521,103 @@ impl FormattableMessage<'_> {
         Ok(())
     }
 
+    /// Decodes the payload of a CAN network trace with the DBC metadata or the payload of a
+    /// FlexRay network trace with the FlexRay frames of the FIBEX metadata.
+    ///
+    /// The first slice of the trace is the bus header, the second slice is the frame
+    /// payload. Returns `None` if there is no metadata for the type of the trace, the trace
+    /// has another type or the identifier is unknown.
+    fn bus_frame_string(&self, slices: &[Vec<u8>], separator: &str) -> Option<String> {
+        match self.message.extended_header.as_ref()?.message_type {
+            MessageType::NetworkTrace(NetworkTraceType::Can) => {
+                self.can_frame_string(slices, separator)
+            }
+            MessageType::NetworkTrace(NetworkTraceType::Flexray) => {
+                self.flexray_frame_string(slices, separator)
+            }
+            _ => None,
+        }
+    }
+
+    /// Decodes a CAN frame. The bus header carries the 32 bit CAN identifier with the
+    /// extended flag in its highest bit.
+    fn can_frame_string(&self, slices: &[Vec<u8>], separator: &str) -> Option<String> {
+        let dbc = self.dbc_metadata?;
+        let header = slices.first()?;
+        let payload = slices.get(1)?;
+        let bytes: [u8; 4] = header.get(..4)?.try_into().ok()?;
+        let raw_id = match self.message.header.endianness {
+            Endianness::Big => u32::from_be_bytes(bytes),
+            Endianness::Little => u32::from_le_bytes(bytes),
+        };
+        let id = raw_id & CAN_ID_MASK;
+        let frame = dbc.decode(id, raw_id & EXTENDED_ID_FLAG != 0, payload)?;
+        let mut output = format!("CAN 0x{id:X} {}", frame.message.name);
+        if !frame.signals.is_empty() {
+            output.push_str(separator);
+            frame.write_signals(&mut output, separator).ok()?;
+        }
+        Some(output)
+    }
+
+    /// Decodes a FlexRay frame. The bus header carries the 16 bit slot id, optionally
+    /// followed by the cycle counter, which selects the frame if several frames share the
+    /// slot.
+    fn flexray_frame_string(&self, slices: &[Vec<u8>], separator: &str) -> Option<String> {
+        let flexray = self.flexray_metadata?;
+        let header = slices.first()?;
+        let payload = slices.get(1)?;
+        let bytes: [u8; 2] = header.get(..2)?.try_into().ok()?;
+        let slot = match self.message.header.endianness {
+            Endianness::Big => u16::from_be_bytes(bytes),
+            Endianness::Little => u16::from_le_bytes(bytes),
+        };
+        let frame = flexray.decode(slot, header.get(2).copied(), payload)?;
+        let mut output = format!("FlexRay {slot} {}", frame.frame.name);
+        if !frame.signals.is_empty() {
+            output.push_str(separator);
+            frame.write_signals(&mut output, separator).ok()?;
+        }
+        Some(output)
+    }
+
+    /// Looks up the non-verbose message in the FIBEX metadata and falls back to the ARXML
+    /// metadata if the message isn't described there.
     fn info_from_metadata<'b>(&'b self, id: u32, data: &[u8]) -> Option<NonVerboseInfo<'b>> {
+        self.info_from_fibex(id, data)
+            .or_else(|| self.info_from_arxml(id, data))
+    }
+
+    fn info_from_arxml<'b>(&'b self, id: u32, data: &[u8]) -> Option<NonVerboseInfo<'b>> {
+        let arxml = self.arxml_metadata?;
+        let ext_header = self.message.extended_header.as_ref();
+        let md = arxml.get_message(id, ext_header)?;
+        let arguments =
+            match construct_arguments(self.message.header.endianness, &md.signal_types(), data) {
+                Ok(arguments) => arguments,
+                Err(err) => {
+                    trace!("Fail to construct arguments of {}: {err:?}", md.name);
+                    return None;
+                }
+            };
+        Some(NonVerboseInfo {
+            app_id: md
+                .application_id
+                .as_deref()
+                .or_else(|| ext_header.map(|h| h.application_id.as_str())),
+            context_id: md
+                .context_id
+                .as_deref()
+                .or_else(|| ext_header.map(|h| h.context_id.as_str())),
+            msg_type: md
+                .message_type
+                .clone()
+                .or_else(|| ext_header.map(|h| h.message_type.clone())),
+            arguments,
+        })
+    }
+
+    fn info_from_fibex<'b>(&'b self, id: u32, data: &[u8]) -> Option<NonVerboseInfo<'b>> {
         let fibex = self.fibex_dlt_metadata?;
         let md = extract_metadata(fibex, id, self.message.extended_header.as_ref())?;
         let msg_type: Option<MessageType> = message_type(&self.message, md.message_info.as_deref());
@@ -559,7 +684,30 @@ impl fmt::Display for FormattableMessage<'_> {
     /// context-id
     ///
     /// payload
+    /// ********* lifecycle (empty if not detected) ********
+    /// lifecycle
+    /// absolute time
     fn fmt(&self, f: &mut Formatter) -> Result<(), fmt::Error> {
+        self.write_message(f)?;
+        let Some(lifecycle) = &self.lifecycle else {
+            return write!(f, "{DLT_COLUMN_SENTINAL}{DLT_COLUMN_SENTINAL}");
+        };
+        write!(
+            f,
+            "{DLT_COLUMN_SENTINAL}{}{DLT_COLUMN_SENTINAL}",
+            lifecycle.id
+        )?;
+        match (&lifecycle.absolute_time, self.options.and_then(|o| o.tz)) {
+            (Some(time), Some(tz)) => write_tz_string(f, time, &tz)?,
+            (Some(time), None) => write!(f, "{}", DltDltTimeStamp(time))?,
+            (None, _) => {}
+        }
+        Ok(())
+    }
+}
+
+impl FormattableMessage<'_> {
+    fn write_message(&self, f: &mut Formatter) -> Result<(), fmt::Error> {
         if let Some(h) = &self.message.storage_header {
             let tz = self.options.map(|o| o.tz);
             match tz {
@@ -621,6 +769,12 @@ impl fmt::Display for FormattableMessage<'_> {
                     }
                 }
 
+                if let Some(decoded) =
+                    self.bus_frame_string(slices, &DLT_ARGUMENT_SENTINAL.to_string())
+                {
+                    return write!(f, "{DLT_ARGUMENT_SENTINAL}{decoded}");
+                }
+
                 slices
                     .iter()
                     .try_for_each(|slice| write!(f, "{DLT_ARGUMENT_SENTINAL}{slice:02X?}"))
@@ -0,0 +1,332 @@
+//! # Detection of ECU lifecycles
+//!
+//! A lifecycle is the period between the start and the next reset of an ECU. DLT messages
+//! carry the uptime of the ECU in the standard header, while the storage header holds the
+//! wall-clock time of the logger. A new lifecycle of an ECU is started when its uptime
+//! goes backwards or when the DLT daemon of the ECU announces its start.
+//!
+//! The start of each lifecycle is estimated as the earliest `storage time - uptime` of its
+//! messages, which allows to compute an absolute time for every message from its uptime.
+//! These absolute times are comparable across ECUs, even if the messages were buffered
+//! before they reached the logger. Messages without a storage header (e.g. of streams) are
+//! assigned to lifecycles too, but don't contribute to the start of their lifecycle.
+use dlt_core::dlt::{ControlType, DltTimeStamp, Endianness, Message, MessageType, PayloadContent};
+use std::collections::HashMap;
+
+/// Uptime timestamps are counted in units of 0.1 milliseconds.
+const UPTIME_UNIT_US: u64 = 100;
+/// Tolerated decrease of the uptime within a lifecycle (1 second), since the messages of
+/// different applications are buffered independently on the ECU.
+const UPTIME_TOLERANCE: u32 = 10_000;
+/// Service id of the `get_software_version` control message, which the DLT daemon responds
+/// to on its start.
+const SERVICE_ID_GET_SOFTWARE_VERSION: u32 = 0x13;
+/// Software version responses within the first 10 seconds of uptime are startup messages.
+const STARTUP_UPTIME: u32 = 100_000;
+
+/// Lifecycle information attached to a DLT message.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LifecycleInfo {
+    /// Number of the lifecycle, unique across all ECUs of the parser (starting with 1).
+    pub id: u32,
+    /// Absolute time of the message computed from the lifecycle start and the uptime.
+    ///
+    /// Is `None` if the message has no uptime or no lifecycle start is known yet, e.g. if no
+    /// message of the lifecycle had a storage header.
+    pub absolute_time: Option<DltTimeStamp>,
+}
+
+#[derive(Debug)]
+struct EcuLifecycle {
+    id: u32,
+    /// Estimated start of the lifecycle in microseconds since the epoch.
+    start_us: Option<u64>,
+    /// Highest uptime seen in the lifecycle.
+    max_uptime: u32,
+    /// Whether a startup message was already received in the lifecycle.
+    startup_seen: bool,
+}
+
+/// Tracks the lifecycles of all ECUs of a DLT trace.
+#[derive(Debug, Default)]
+pub struct LifecycleDetector {
+    ecus: HashMap<String, EcuLifecycle>,
+    count: u32,
+}
+
+impl LifecycleDetector {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Assigns the message to a lifecycle of its ECU, starting a new one if needed.
+    ///
+    /// The message has to be passed as it was parsed: a storage header added afterwards
+    /// (e.g. with the time of receiving) would distort the start of the lifecycle.
+    pub fn process(&mut self, message: &Message) -> LifecycleInfo {
+        let ecu_id = message
+            .header
+            .ecu_id
+            .as_deref()
+            .or_else(|| message.storage_header.as_ref().map(|sh| sh.ecu_id.as_str()))
+            .unwrap_or_default();
+        let uptime = message.header.timestamp;
+        let startup = uptime.is_some_and(|uptime| is_startup_message(message, uptime));
+        let restarted = match (self.ecus.get(ecu_id), uptime) {
+            (None, _) => true,
+            (Some(lifecycle), Some(uptime)) => {
+                uptime.saturating_add(UPTIME_TOLERANCE) < lifecycle.max_uptime
+                    || (startup && lifecycle.startup_seen)
+            }
+            (Some(_), None) => false,
+        };
+        if restarted {
+            self.count += 1;
+            self.ecus.insert(
+                ecu_id.to_owned(),
+                EcuLifecycle {
+                    id: self.count,
+                    start_us: None,
+                    max_uptime: 0,
+                    startup_seen: false,
+                },
+            );
+        }
+        let lifecycle = self
+            .ecus
+            .get_mut(ecu_id)
+            .expect("lifecycle of ECU is present");
+        let Some(uptime) = uptime else {
+            return LifecycleInfo {
+                id: lifecycle.id,
+                absolute_time: None,
+            };
+        };
+        let uptime_us = u64::from(uptime) * UPTIME_UNIT_US;
+        lifecycle.max_uptime = lifecycle.max_uptime.max(uptime);
+        lifecycle.startup_seen |= startup;
+        if let Some(start) = message
+            .storage_header
+            .as_ref()
+            .map(|sh| to_micros(&sh.timestamp))
+            .and_then(|storage_us| storage_us.checked_sub(uptime_us))
+        {
+            lifecycle.start_us = Some(lifecycle.start_us.map_or(start, |s| s.min(start)));
+        }
+        LifecycleInfo {
+            id: lifecycle.id,
+            absolute_time: lifecycle
+                .start_us
+                .and_then(|start| from_micros(start + uptime_us)),
+        }
+    }
+}
+
+/// Checks whether the message is the software version response of a starting DLT daemon.
+fn is_startup_message(message: &Message, uptime: u32) -> bool {
+    let is_response = message
+        .extended_header
+        .as_ref()
+        .is_some_and(|eh| matches!(eh.message_type, MessageType::Control(ControlType::Response)));
+    is_response
+        && service_id(message) == Some(SERVICE_ID_GET_SOFTWARE_VERSION)
+        && uptime < STARTUP_UPTIME
+}
+
+/// Returns the service id of a control message. The payload of control messages is split
+/// into its first byte and the rest while parsing, but the service id consists of the first
+/// 4 bytes in the endianness of the message.
+fn service_id(message: &Message) -> Option<u32> {
+    let PayloadContent::ControlMsg(ctrl_id, payload) = &message.payload else {
+        return None;
+    };
+    let [b1, b2, b3] = *payload.first_chunk::<3>()?;
+    let bytes = [ctrl_id.value(), b1, b2, b3];
+    Some(match message.header.endianness {
+        Endianness::Big => u32::from_be_bytes(bytes),
+        Endianness::Little => u32::from_le_bytes(bytes),
+    })
+}
+
+fn to_micros(timestamp: &DltTimeStamp) -> u64 {
+    u64::from(timestamp.seconds) * 1_000_000 + u64::from(timestamp.microseconds)
+}
+
+fn from_micros(micros: u64) -> Option<DltTimeStamp> {
+    Some(DltTimeStamp {
+        seconds: u32::try_from(micros / 1_000_000).ok()?,
+        microseconds: (micros % 1_000_000) as u32,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{LogMessage, dlt::FormattableMessage};
+    use dlt_core::dlt::{ExtendedHeader, LogLevel, StandardHeader, StorageHeader};
+
+    fn message(ecu: &str, storage_us: u64, uptime: u32) -> Message {
+        Message {
+            storage_header: Some(StorageHeader {
+                timestamp: from_micros(storage_us).unwrap(),
+                ecu_id: ecu.to_string(),
+            }),
+            header: StandardHeader {
+                version: 1,
+                endianness: Endianness::Big,
+                has_extended_header: true,
+                message_counter: 0,
+                ecu_id: Some(ecu.to_string()),
+                session_id: None,
+                timestamp: Some(uptime),
+                payload_length: 0,
+            },
+            extended_header: Some(ExtendedHeader {
+                verbose: true,
+                argument_count: 0,
+                message_type: MessageType::Log(LogLevel::Info),
+                application_id: String::from("APP"),
+                context_id: String::from("CTX"),
+            }),
+            payload: PayloadContent::Verbose(vec![]),
+        }
+    }
+
+    fn startup_message(ecu: &str, storage_us: u64, uptime: u32) -> Message {
+        let mut msg = message(ecu, storage_us, uptime);
+        if let Some(eh) = msg.extended_header.as_mut() {
+            eh.message_type = MessageType::Control(ControlType::Response);
+        }
+        let service_id = match msg.header.endianness {
+            Endianness::Big => SERVICE_ID_GET_SOFTWARE_VERSION.to_be_bytes(),
+            Endianness::Little => SERVICE_ID_GET_SOFTWARE_VERSION.to_le_bytes(),
+        };
+        // Service id followed by the status of the response.
+        let mut payload = service_id[1..].to_vec();
+        payload.push(0x00);
+        msg.payload = PayloadContent::ControlMsg(ControlType::from_value(service_id[0]), payload);
+        msg
+    }
+
+    #[test]
+    fn reset_when_uptime_goes_backwards() {
+        let mut detector = LifecycleDetector::new();
+        assert_eq!(detector.process(&message("ECU1", 10_000_000, 50_000)).id, 1);
+        assert_eq!(detector.process(&message("ECU1", 11_000_000, 60_000)).id, 1);
+        // Slightly out of order messages stay in the lifecycle.
+        assert_eq!(detector.process(&message("ECU1", 11_000_000, 55_000)).id, 1);
+        // Reset of the ECU.
+        assert_eq!(detector.process(&message("ECU1", 12_000_000, 100)).id, 2);
+        assert_eq!(detector.process(&message("ECU1", 12_100_000, 1_000)).id, 2);
+    }
+
+    #[test]
+    fn lifecycles_per_ecu() {
+        let mut detector = LifecycleDetector::new();
+        assert_eq!(detector.process(&message("ECU1", 10_000_000, 50_000)).id, 1);
+        assert_eq!(detector.process(&message("ECU2", 10_000_000, 10)).id, 2);
+        assert_eq!(detector.process(&message("ECU1", 10_100_000, 51_000)).id, 1);
+        assert_eq!(detector.process(&message("ECU2", 10_100_000, 1_010)).id, 2);
+    }
+
+    #[test]
+    fn reset_on_startup_message() {
+        let mut detector = LifecycleDetector::new();
+        assert_eq!(
+            detector.process(&startup_message("ECU1", 1_000_000, 10)).id,
+            1
+        );
+        assert_eq!(detector.process(&message("ECU1", 1_500_000, 5_000)).id, 1);
+        // The daemon restarted before the uptime exceeded the previous one.
+        assert_eq!(
+            detector
+                .process(&startup_message("ECU1", 3_000_000, 20_000))
+                .id,
+            2
+        );
+        // Periodic software version messages don't start a new lifecycle.
+        assert_eq!(
+            detector
+                .process(&startup_message("ECU1", 60_000_000, 600_000))
+                .id,
+            2
+        );
+    }
+
+    #[test]
+    fn service_id_in_endianness_of_message() {
+        let msg = startup_message("ECU1", 1_000_000, 10);
+        assert_eq!(service_id(&msg), Some(SERVICE_ID_GET_SOFTWARE_VERSION));
+        let mut msg = message("ECU1", 1_000_000, 10);
+        msg.header.endianness = Endianness::Little;
+        msg.payload =
+            PayloadContent::ControlMsg(ControlType::from_value(0x13), vec![0x00, 0x00, 0x00, 0x00]);
+        assert_eq!(service_id(&msg), Some(SERVICE_ID_GET_SOFTWARE_VERSION));
+        // The same bytes in a big endian message are another service.
+        msg.header.endianness = Endianness::Big;
+        assert_eq!(service_id(&msg), Some(0x1300_0000));
+    }
+
+    #[test]
+    fn no_start_without_storage_header() {
+        let mut detector = LifecycleDetector::new();
+        let mut msg = message("ECU1", 110_500_000, 100_000);
+        msg.storage_header = None;
+        let info = detector.process(&msg);
+        assert_eq!((info.id, info.absolute_time), (1, None));
+        // The start is estimated once messages with storage header arrive.
+        let info = detector.process(&message("ECU1", 111_000_000, 110_000));
+        assert_eq!(info.absolute_time.map(|t| to_micros(&t)), Some(111_000_000));
+    }
+
+    #[test]
+    fn absolute_time() {
+        let mut detector = LifecycleDetector::new();
+        // Started at 100 s, the first message was delayed by 0.5 s.
+        let info = detector.process(&message("ECU1", 110_500_000, 100_000));
+        assert_eq!(info.absolute_time.map(|t| to_micros(&t)), Some(110_500_000));
+        // Without delay, the start is corrected.
+        let info = detector.process(&message("ECU1", 111_000_000, 110_000));
+        assert_eq!(info.absolute_time.map(|t| to_micros(&t)), Some(111_000_000));
+        // Later delayed messages are aligned to the corrected start.
+        let info = detector.process(&message("ECU1", 113_000_000, 120_000));
+        assert_eq!(info.absolute_time.map(|t| to_micros(&t)), Some(112_000_000));
+    }
+
+    #[test]
+    fn same_columns_without_detection() {
+        use crate::dlt::fmt::{DLT_COLUMN_SENTINAL, FormattableMessage};
+        let columns = |lifecycle: Option<LifecycleInfo>| {
+            let mut msg = FormattableMessage::from(message("ECU1", 110_500_000, 100_000));
+            msg.lifecycle = lifecycle;
+            msg.to_string().split(DLT_COLUMN_SENTINAL).count()
+        };
+        let info = LifecycleDetector::new().process(&message("ECU1", 110_500_000, 100_000));
+        assert_eq!(columns(None), columns(Some(info)));
+    }
+
+    #[test]
+    fn message_timestamp() {
+        let formattable = |message, lifecycle| FormattableMessage {
+            message,
+            fibex_dlt_metadata: None,
+            arxml_metadata: None,
+            fibex_someip_metadata: None,
+            dbc_metadata: None,
+            flexray_metadata: None,
+            options: None,
+            lifecycle,
+        };
+        let msg = message("ECU1", 10_500_000, 50_000);
+        assert_eq!(formattable(msg.clone(), None).timestamp(), Some(10_500));
+        let lifecycle = LifecycleInfo {
+            id: 1,
+            absolute_time: from_micros(9_000_000),
+        };
+        assert_eq!(formattable(msg, Some(lifecycle)).timestamp(), Some(9_000));
+        // Without storage header the ECU time is used.
+        let mut msg = message("ECU1", 10_500_000, 50_000);
+        msg.storage_header = None;
+        assert_eq!(formattable(msg, None).timestamp(), Some(5_000));
+    }
+}
@@ -1,9 +1,13 @@
+pub mod arxml;
 pub mod attachment;
+pub mod flexray;
 pub mod fmt;
+pub mod lifecycle;
+pub mod stats;
 
 use crate::{
-    Error, LogMessage, ParseYield, SingleParser, dlt::fmt::FormattableMessage,
-    someip::FibexMetadata as FibexSomeipMetadata,
+    Error, LogMessage, ParseYield, SingleParser, can::dbc::DbcMetadata,
+    dlt::fmt::FormattableMessage, someip::FibexMetadata as FibexSomeipMetadata,
 };
 use byteorder::{BigEndian, WriteBytesExt};
 use dlt_core::{
@@ -15,10 +19,18 @@ pub use dlt_core::{
     fibex::{FibexConfig, FibexMetadata as FibexDltMetadata, gather_fibex_data},
     filtering::{DltFilterConfig, ProcessedDltFilterConfig},
 };
+use log::error;
 use serde::Serialize;
 use std::{io::Write, ops::Range};
 
-use self::{attachment::FtScanner, fmt::FormatOptions};
+use self::{
+    arxml::ArxmlMetadata,
+    attachment::FtScanner,
+    flexray::FlexrayMetadata,
+    fmt::FormatOptions,
+    lifecycle::LifecycleDetector,
+    stats::{DltStatistics, SharedDltStatistics},
+};
 
 /// The most likely minimal bytes count needed to parse a DLT message.
 const MIN_MSG_LEN: usize = 20;
@@ -30,6 +42,19 @@ impl LogMessage for FormattableMessage<'_> {
         writer.write_all(&bytes)?;
         Ok(len)
     }
+
+    /// Returns the absolute time of the lifecycle if it's detected, otherwise the time of the
+    /// storage header. Messages without storage header get the ECU time, which is relative
+    /// to the start of the ECU.
+    fn timestamp(&self) -> Option<u64> {
+        self.lifecycle
+            .as_ref()
+            .and_then(|lifecycle| lifecycle.absolute_time.as_ref())
+            .or(self.message.storage_header.as_ref().map(|sh| &sh.timestamp))
+            .map(|ts| u64::from(ts.seconds) * 1000 + u64::from(ts.microseconds) / 1000)
+            // The ECU time is measured in 0.1 milliseconds.
+            .or_else(|| self.message.header.timestamp.map(|ts| u64::from(ts) / 10))
+    }
 }
 
 #[derive(Debug, Serialize)]
@@ -78,6 +103,14 @@ pub struct DltParser<'m> {
     pub with_storage_header: bool,
     ft_scanner: FtScanner,
     fibex_someip_metadata: Option<&'m FibexSomeipMetadata>,
+    dbc_metadata: Option<&'m DbcMetadata>,
+    flexray_metadata: Option<&'m FlexrayMetadata>,
+    arxml_metadata: Option<&'m ArxmlMetadata>,
+    lifecycles: Option<LifecycleDetector>,
+    statistics: Option<SharedDltStatistics>,
+    /// Statistics of the current segment, merged into the shared statistics once the
+    /// segment is parsed.
+    segment_statistics: DltStatistics,
     offset: usize,
 }
 
@@ -119,9 +152,49 @@ impl<'m> DltParser<'m> {
             fmt_options,
             ft_scanner: FtScanner::new(),
             fibex_someip_metadata,
+            dbc_metadata: None,
+            flexray_metadata: None,
+            arxml_metadata: None,
+            lifecycles: None,
+            statistics: None,
+            segment_statistics: DltStatistics::new(),
             offset: 0,
         }
     }
+
+    /// Uses the ARXML metadata to decode non-verbose messages which aren't described by the
+    /// FIBEX metadata.
+    pub fn with_arxml_metadata(mut self, arxml_metadata: Option<&'m ArxmlMetadata>) -> Self {
+        self.arxml_metadata = arxml_metadata;
+        self
+    }
+
+    /// Uses the DBC metadata to decode the payloads of CAN network traces.
+    pub fn with_dbc_metadata(mut self, dbc_metadata: Option<&'m DbcMetadata>) -> Self {
+        self.dbc_metadata = dbc_metadata;
+        self
+    }
+
+    /// Uses the FlexRay frames of the FIBEX files to decode the payloads of FlexRay network
+    /// traces.
+    pub fn with_flexray_metadata(mut self, flexray_metadata: Option<&'m FlexrayMetadata>) -> Self {
+        self.flexray_metadata = flexray_metadata;
+        self
+    }
+
+    /// Enables the detection of ECU lifecycles, adding the lifecycle and the absolute time
+    /// of each message to its columns.
+    pub fn with_lifecycle_detection(mut self) -> Self {
+        self.lifecycles = Some(LifecycleDetector::new());
+        self
+    }
+
+    /// Collects the statistics of all parsed messages, which passed the filter, into the
+    /// shared statistics while parsing.
+    pub fn with_statistics(mut self, statistics: SharedDltStatistics) -> Self {
+        self.statistics = Some(statistics);
+        self
+    }
 }
 
 impl From<DltParseError> for Error {
@@ -154,6 +227,15 @@ impl<'m> SingleParser<FormattableMessage<'m>> for DltParser<'m> {
             }
             (rest, dlt_core::parse::ParsedMessage::Item(i)) => {
                 let attachment = self.ft_scanner.process(&i);
+                if self.statistics.is_some() {
+                    self.segment_statistics.collect(&i);
+                }
+                // Lifecycles are detected before a storage header is added, which only
+                // holds the time of receiving the message.
+                let lifecycle = self
+                    .lifecycles
+                    .as_mut()
+                    .map(|detector| detector.process(&i));
                 let msg_with_storage_header = if i.storage_header.is_some() {
                     i
                 } else {
@@ -163,8 +245,12 @@ impl<'m> SingleParser<FormattableMessage<'m>> for DltParser<'m> {
                 let msg = FormattableMessage {
                     message: msg_with_storage_header,
                     fibex_dlt_metadata: self.fibex_dlt_metadata,
+                    arxml_metadata: self.arxml_metadata,
                     options: self.fmt_options,
                     fibex_someip_metadata: self.fibex_someip_metadata,
+                    dbc_metadata: self.dbc_metadata,
+                    flexray_metadata: self.flexray_metadata,
+                    lifecycle,
                 };
                 let consumed = input.len() - rest.len();
                 self.offset += consumed;
@@ -181,6 +267,24 @@ impl<'m> SingleParser<FormattableMessage<'m>> for DltParser<'m> {
             }
         }
     }
+
+    fn finish_items(&mut self) {
+        let Some(statistics) = self.statistics.as_ref() else {
+            return;
+        };
+        if self.segment_statistics.messages() == 0 {
+            return;
+        }
+        // The statistics are plain counters, which stay usable after a panic of another
+        // holder of the lock. The poisoning is cleared, so it's reported only once.
+        let mut shared = statistics.lock().unwrap_or_else(|err| {
+            error!("DLT statistics were poisoned: {err}");
+            statistics.clear_poison();
+            err.into_inner()
+        });
+        shared.merge(&self.segment_statistics);
+        self.segment_statistics = DltStatistics::new();
+    }
 }
 
 impl SingleParser<RangeMessage> for DltRangeParser {
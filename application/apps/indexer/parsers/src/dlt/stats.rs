@@ -0,0 +1,280 @@
+//! # Statistics of DLT messages
+//!
+//! Counts the messages per application, context and ECU id, split by their log level.
+//! Unlike the statistics of `dlt-core`, which are read from files with storage headers only,
+//! the statistics are collected from the parsed messages. This allows to collect them from
+//! any source the parser is used with (e.g. DLT embedded in PCAP files or streamed DLT)
+//! and to update them while the messages arrive.
+use dlt_core::{
+    dlt::{LogLevel, Message, MessageType},
+    statistics::common::{LevelDistribution, StatisticInfo},
+};
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+};
+
+/// Statistics shared between a parser collecting them and their readers.
+pub type SharedDltStatistics = Arc<Mutex<DltStatistics>>;
+
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+struct LevelCounts {
+    non_log: usize,
+    log_fatal: usize,
+    log_error: usize,
+    log_warning: usize,
+    log_info: usize,
+    log_debug: usize,
+    log_verbose: usize,
+    log_invalid: usize,
+}
+
+impl LevelCounts {
+    fn count(&mut self, level: Option<&LogLevel>) {
+        match level {
+            None => self.non_log += 1,
+            Some(LogLevel::Fatal) => self.log_fatal += 1,
+            Some(LogLevel::Error) => self.log_error += 1,
+            Some(LogLevel::Warn) => self.log_warning += 1,
+            Some(LogLevel::Info) => self.log_info += 1,
+            Some(LogLevel::Debug) => self.log_debug += 1,
+            Some(LogLevel::Verbose) => self.log_verbose += 1,
+            Some(_) => self.log_invalid += 1,
+        }
+    }
+
+    fn merge(&mut self, other: &LevelCounts) {
+        self.non_log += other.non_log;
+        self.log_fatal += other.log_fatal;
+        self.log_error += other.log_error;
+        self.log_warning += other.log_warning;
+        self.log_info += other.log_info;
+        self.log_debug += other.log_debug;
+        self.log_verbose += other.log_verbose;
+        self.log_invalid += other.log_invalid;
+    }
+}
+
+impl From<&LevelCounts> for LevelDistribution {
+    fn from(counts: &LevelCounts) -> Self {
+        LevelDistribution {
+            non_log: counts.non_log,
+            log_fatal: counts.log_fatal,
+            log_error: counts.log_error,
+            log_warning: counts.log_warning,
+            log_info: counts.log_info,
+            log_debug: counts.log_debug,
+            log_verbose: counts.log_verbose,
+            log_invalid: counts.log_invalid,
+        }
+    }
+}
+
+/// Collects the statistics of DLT messages.
+#[derive(Debug, Default, Clone)]
+pub struct DltStatistics {
+    app_ids: HashMap<String, LevelCounts>,
+    context_ids: HashMap<String, LevelCounts>,
+    ecu_ids: HashMap<String, LevelCounts>,
+    contained_non_verbose: bool,
+    messages: usize,
+}
+
+impl DltStatistics {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates empty statistics which can be shared with a parser.
+    pub fn shared() -> SharedDltStatistics {
+        Arc::new(Mutex::new(Self::new()))
+    }
+
+    /// Adds the message to the statistics.
+    pub fn collect(&mut self, message: &Message) {
+        let level = match message.extended_header.as_ref().map(|eh| &eh.message_type) {
+            Some(MessageType::Log(level)) => Some(level),
+            _ => None,
+        };
+        let ecu_id = message
+            .header
+            .ecu_id
+            .as_deref()
+            .or_else(|| message.storage_header.as_ref().map(|sh| sh.ecu_id.as_str()))
+            .unwrap_or_default();
+        count(&mut self.ecu_ids, ecu_id, level);
+        match &message.extended_header {
+            Some(eh) => {
+                count(&mut self.app_ids, &eh.application_id, level);
+                count(&mut self.context_ids, &eh.context_id, level);
+                self.contained_non_verbose |= !eh.verbose;
+            }
+            // Messages without extended header are always non-verbose.
+            None => self.contained_non_verbose = true,
+        }
+        self.messages += 1;
+    }
+
+    /// Adds the statistics of another source.
+    pub fn merge(&mut self, other: &DltStatistics) {
+        merge(&mut self.app_ids, &other.app_ids);
+        merge(&mut self.context_ids, &other.context_ids);
+        merge(&mut self.ecu_ids, &other.ecu_ids);
+        self.contained_non_verbose |= other.contained_non_verbose;
+        self.messages += other.messages;
+    }
+
+    /// Returns the count of collected messages.
+    pub fn messages(&self) -> usize {
+        self.messages
+    }
+
+    /// Returns the collected statistics, sorted by their ids.
+    pub fn info(&self) -> StatisticInfo {
+        StatisticInfo {
+            app_ids: to_sorted(&self.app_ids),
+            context_ids: to_sorted(&self.context_ids),
+            ecu_ids: to_sorted(&self.ecu_ids),
+            contained_non_verbose: self.contained_non_verbose,
+        }
+    }
+}
+
+fn count(ids: &mut HashMap<String, LevelCounts>, id: &str, level: Option<&LogLevel>) {
+    if let Some(counts) = ids.get_mut(id) {
+        counts.count(level);
+    } else {
+        ids.entry(id.to_owned()).or_default().count(level);
+    }
+}
+
+fn merge(ids: &mut HashMap<String, LevelCounts>, other: &HashMap<String, LevelCounts>) {
+    for (id, counts) in other {
+        ids.entry(id.clone()).or_default().merge(counts);
+    }
+}
+
+fn to_sorted(ids: &HashMap<String, LevelCounts>) -> Vec<(String, LevelDistribution)> {
+    let mut sorted: Vec<(String, LevelDistribution)> = ids
+        .iter()
+        .map(|(id, counts)| (id.clone(), counts.into()))
+        .collect();
+    sorted.sort_by(|(a, _), (b, _)| a.cmp(b));
+    sorted
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use dlt_core::dlt::{ControlType, Endianness, ExtendedHeader, PayloadContent, StandardHeader};
+
+    fn message(ecu: &str, app: &str, ctx: &str, message_type: MessageType) -> Message {
+        Message {
+            storage_header: None,
+            header: StandardHeader {
+                version: 1,
+                endianness: Endianness::Big,
+                has_extended_header: true,
+                message_counter: 0,
+                ecu_id: Some(ecu.to_string()),
+                session_id: None,
+                timestamp: None,
+                payload_length: 0,
+            },
+            extended_header: Some(ExtendedHeader {
+                verbose: true,
+                argument_count: 0,
+                message_type,
+                application_id: app.to_string(),
+                context_id: ctx.to_string(),
+            }),
+            payload: PayloadContent::Verbose(vec![]),
+        }
+    }
+
+    fn counts(ids: &[(String, LevelDistribution)], id: &str) -> (usize, usize, usize) {
+        ids.iter()
+            .find(|(i, _)| i == id)
+            .map(|(_, d)| (d.non_log, d.log_error, d.log_info))
+            .unwrap_or_default()
+    }
+
+    #[test]
+    fn collect_levels_per_id() {
+        let mut stats = DltStatistics::new();
+        stats.collect(&message(
+            "ECU1",
+            "APP1",
+            "CTX1",
+            MessageType::Log(LogLevel::Info),
+        ));
+        stats.collect(&message(
+            "ECU1",
+            "APP1",
+            "CTX2",
+            MessageType::Log(LogLevel::Error),
+        ));
+        stats.collect(&message(
+            "ECU2",
+            "APP2",
+            "CTX1",
+            MessageType::Control(ControlType::Response),
+        ));
+        let info = stats.info();
+        assert_eq!(stats.messages(), 3);
+        assert_eq!(counts(&info.ecu_ids, "ECU1"), (0, 1, 1));
+        assert_eq!(counts(&info.ecu_ids, "ECU2"), (1, 0, 0));
+        assert_eq!(counts(&info.app_ids, "APP1"), (0, 1, 1));
+        assert_eq!(counts(&info.context_ids, "CTX1"), (1, 0, 1));
+        assert_eq!(
+            info.app_ids
+                .iter()
+                .map(|(id, _)| id.as_str())
+                .collect::<Vec<_>>(),
+            vec!["APP1", "APP2"]
+        );
+        assert!(!info.contained_non_verbose);
+    }
+
+    #[test]
+    fn merge_statistics() {
+        let mut first = DltStatistics::new();
+        first.collect(&message(
+            "ECU1",
+            "APP1",
+            "CTX1",
+            MessageType::Log(LogLevel::Info),
+        ));
+        let mut second = DltStatistics::new();
+        let mut non_verbose = message("ECU1", "APP1", "CTX1", MessageType::Log(LogLevel::Info));
+        non_verbose.extended_header = None;
+        second.collect(&non_verbose);
+        first.merge(&second);
+        let info = first.info();
+        assert_eq!(first.messages(), 2);
+        assert_eq!(counts(&info.ecu_ids, "ECU1"), (1, 0, 1));
+        assert_eq!(counts(&info.app_ids, "APP1"), (0, 0, 1));
+        assert!(info.contained_non_verbose);
+    }
+
+    #[test]
+    fn merge_per_segment() {
+        use crate::{Parser, dlt::DltParser};
+        let statistics = DltStatistics::shared();
+        let mut parser =
+            DltParser::new(None, None, None, None, false).with_statistics(statistics.clone());
+        let bytes = [LogLevel::Info, LogLevel::Warn]
+            .into_iter()
+            .flat_map(|level| {
+                let mut msg = message("ECU1", "APP1", "CTX1", MessageType::Log(level));
+                // Messages without payload are too short without the session id.
+                msg.header.session_id = Some(1);
+                msg.as_bytes()
+            })
+            .collect::<Vec<u8>>();
+        assert_eq!(parser.parse(&bytes, None).unwrap().count(), 2);
+        assert_eq!(statistics.lock().unwrap().messages(), 0);
+        parser.finish_segment();
+        assert_eq!(statistics.lock().unwrap().messages(), 2);
+    }
+}
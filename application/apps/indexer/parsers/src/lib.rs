@@ -1,4 +1,5 @@
 #![deny(unused_crate_dependencies)]
+pub mod can;
 pub mod dlt;
 pub mod someip;
 pub mod text;
@@ -58,6 +59,24 @@ pub trait Parser<T> {
         input: &[u8],
         timestamp: Option<u64>,
     ) -> Result<impl Iterator<Item = (usize, Option<ParseYield<T>>)>, Error>;
+
+    /// Parses the bytes left at the end of the input, which [`Parser::parse()`] considers
+    /// incomplete, once no more bytes can be loaded (e.g. the last line of a text file
+    /// without a trailing newline).
+    ///
+    /// Returns `None` if the remaining bytes don't form an item, which is the default.
+    fn parse_rest(
+        &mut self,
+        _input: &[u8],
+        _timestamp: Option<u64>,
+    ) -> Option<(usize, Option<ParseYield<T>>)> {
+        None
+    }
+
+    /// Called once the items parsed from the currently loaded bytes are taken, allowing
+    /// parsers to publish data collected while parsing (e.g. statistics) once per segment
+    /// instead of once per item. Does nothing by default.
+    fn finish_segment(&mut self) {}
 }
 
 #[derive(Debug, Clone, Serialize)]
@@ -95,6 +114,13 @@ pub trait LogMessage: Display + Serialize {
     /// Serializes a message directly into a Writer
     /// returns the size of the serialized message
     fn to_writer<W: Write>(&self, writer: &mut W) -> Result<usize, std::io::Error>;
+
+    /// Returns the time of the message in milliseconds if the format carries it (e.g. the
+    /// storage header of a DLT message or the time of a network packet). Messages without
+    /// time return `None`, which is the default.
+    fn timestamp(&self) -> Option<u64> {
+        None
+    }
 }
 
 #[derive(Debug)]
@@ -139,6 +165,20 @@ pub trait SingleParser<T> {
         input: &[u8],
         timestamp: Option<u64>,
     ) -> Result<(usize, Option<ParseYield<T>>), Error>;
+
+    /// Parses the bytes left at the end of the input once no more bytes can be loaded.
+    /// See [`Parser::parse_rest()`].
+    fn parse_rest_item(
+        &mut self,
+        _input: &[u8],
+        _timestamp: Option<u64>,
+    ) -> Option<(usize, Option<ParseYield<T>>)> {
+        None
+    }
+
+    /// Called once the items of the currently loaded bytes are parsed.
+    /// See [`Parser::finish_segment()`].
+    fn finish_items(&mut self) {}
 }
 
 /// This blanket implementation repeatedly applies [`SingleParser::parse_item()`] function,
@@ -170,4 +210,16 @@ where
 
         Ok(iter)
     }
+
+    fn parse_rest(
+        &mut self,
+        input: &[u8],
+        timestamp: Option<u64>,
+    ) -> Option<(usize, Option<ParseYield<T>>)> {
+        self.parse_rest_item(input, timestamp)
+    }
+
+    fn finish_segment(&mut self) {
+        self.finish_items();
+    }
 }
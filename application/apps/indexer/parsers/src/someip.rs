@@ -295,8 +295,12 @@ impl SingleParser<SomeipLogMessage> for SomeipParser {
         input: &[u8],
         timestamp: Option<u64>,
     ) -> Result<(usize, Option<ParseYield<SomeipLogMessage>>), Error> {
-        SomeipParser::parse_message(self.fibex_metadata.as_ref(), input, timestamp)
-            .map(|(rest, message)| (rest, Some(ParseYield::from(message))))
+        SomeipParser::parse_message(self.fibex_metadata.as_ref(), input, timestamp).map(
+            |(rest, mut message)| {
+                message.timestamp = timestamp;
+                (rest, Some(ParseYield::from(message)))
+            },
+        )
     }
 }
 
@@ -487,12 +491,19 @@ fn rpc_message_string(
 pub struct SomeipLogMessage {
     description: String,
     bytes: Vec<u8>,
+    /// Time of the network packet of the message.
+    #[serde(skip)]
+    timestamp: Option<u64>,
 }
 
 impl SomeipLogMessage {
     /// Creates a new log message for the given values.
     pub fn from(description: String, bytes: Vec<u8>) -> Self {
-        SomeipLogMessage { description, bytes }
+        SomeipLogMessage {
+            description,
+            bytes,
+            timestamp: None,
+        }
     }
 }
 
@@ -501,6 +512,10 @@ impl LogMessage for SomeipLogMessage {
         writer.write_all(&self.bytes)?;
         Ok(self.bytes.len())
     }
+
+    fn timestamp(&self) -> Option<u64> {
+        self.timestamp
+    }
 }
 
 impl Display for SomeipLogMessage {
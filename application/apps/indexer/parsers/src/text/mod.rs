@@ -2,6 +2,8 @@ use crate::{Error, LogMessage, ParseYield, SingleParser};
 use serde::Serialize;
 use std::{fmt, io::Write};
 
+pub mod timestamp;
+
 pub struct StringTokenizer {}
 
 #[derive(Debug, PartialEq, Eq, Serialize)]
@@ -0,0 +1,343 @@
+use crate::Error;
+use chrono::{
+    DateTime, Datelike, FixedOffset, NaiveDate, NaiveDateTime, TimeZone, Utc,
+    format::{Item, ParseErrorKind, Parsed, StrftimeItems, parse_and_remainder},
+};
+use chrono_tz::Tz;
+use lazy_static::lazy_static;
+use regex::{Captures, Regex};
+use std::time::SystemTime;
+
+/// Count of bytes from the start of a line which are searched for a timestamp.
+const SEARCH_LIMIT: usize = 128;
+
+const MONTHS: [&str; 12] = [
+    "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec",
+];
+
+lazy_static! {
+    /// ISO 8601 like timestamps, e.g. `2024-01-15T14:32:05.123+01:00` or
+    /// `2024/01/15 14:32:05,123`.
+    static ref ISO: Regex = Regex::new(
+        r"\b(\d{4})[-/](\d{2})[-/](\d{2})[T ](\d{2}):(\d{2}):(\d{2})(?:[.,](\d{1,9}))?(Z|[+-]\d{2}:?\d{2})?"
+    )
+    .unwrap();
+    /// Syslog timestamps without year, e.g. `Jan 15 14:32:05`.
+    static ref SYSLOG: Regex = Regex::new(
+        r"\b(Jan|Feb|Mar|Apr|May|Jun|Jul|Aug|Sep|Oct|Nov|Dec) +(\d{1,2}) (\d{2}):(\d{2}):(\d{2})(?:[.,](\d{1,9}))?"
+    )
+    .unwrap();
+    /// Timestamps of Android logcat without year, e.g. `01-15 14:32:05.123`.
+    static ref MONTH_DAY: Regex =
+        Regex::new(r"\b(\d{2})-(\d{2}) (\d{2}):(\d{2}):(\d{2})(?:[.,](\d{1,9}))?").unwrap();
+}
+
+/// Point in time found in a line, either with or without time zone.
+enum Moment {
+    Local(NaiveDateTime),
+    Fixed(DateTime<FixedOffset>),
+}
+
+/// Detects a timestamp in a common format at the beginning of the line and returns it in
+/// milliseconds since the UNIX epoch. Timestamps without time zone are taken as UTC;
+/// timestamps without year get the given year.
+pub fn detect_timestamp(line: &str, year: i32) -> Option<u64> {
+    match detect(line, year)? {
+        Moment::Local(datetime) => millis(datetime.and_utc()),
+        Moment::Fixed(datetime) => millis(datetime),
+    }
+}
+
+fn detect(line: &str, year: i32) -> Option<Moment> {
+    let line = head(line);
+    if let Some(caps) = ISO.captures(line) {
+        let datetime = datetime(
+            number(&caps, 1)?,
+            number(&caps, 2)?,
+            number(&caps, 3)?,
+            &caps,
+            4,
+        )?;
+        Some(match caps.get(8).map(|offset| offset.as_str()) {
+            None => Moment::Local(datetime),
+            Some("Z") => Moment::Fixed(datetime.and_utc().fixed_offset()),
+            Some(offset) => Moment::Fixed(
+                parse_offset(offset)?
+                    .from_local_datetime(&datetime)
+                    .single()?,
+            ),
+        })
+    } else if let Some(caps) = SYSLOG.captures(line) {
+        let month = MONTHS.iter().position(|m| *m == &caps[1])? as u32 + 1;
+        datetime(year, month, number(&caps, 2)?, &caps, 3).map(Moment::Local)
+    } else if let Some(caps) = MONTH_DAY.captures(line) {
+        datetime(year, number(&caps, 1)?, number(&caps, 2)?, &caps, 3).map(Moment::Local)
+    } else {
+        None
+    }
+}
+
+/// Extracts timestamps of lines of a text log, either by the given format or by detecting
+/// common formats (see [`detect_timestamp`]).
+#[derive(Debug, Clone)]
+pub struct TimestampExtractor {
+    /// Format of timestamps like `%Y-%m-%d %H:%M:%S%.3f` (see `chrono::format::strftime`).
+    format: Option<String>,
+    /// Time zone of timestamps without offset; UTC is used if it isn't defined.
+    tz: Option<Tz>,
+    /// Year of timestamps without year.
+    year: i32,
+    /// `true` if the year was defined explicitly.
+    fixed_year: bool,
+}
+
+impl TimestampExtractor {
+    /// Creates an extractor; the current year is used if `year` isn't defined.
+    pub fn new(format: Option<String>, tz: Option<&str>, year: Option<i32>) -> Result<Self, Error> {
+        if let Some(format) = format.as_ref().filter(|format| {
+            format.is_empty() || StrftimeItems::new(format).any(|item| matches!(item, Item::Error))
+        }) {
+            return Err(Error::Parse(format!("Invalid timestamp format: {format}")));
+        }
+        let tz = tz
+            .map(|tz| {
+                tz.parse::<Tz>()
+                    .map_err(|_| Error::Parse(format!("Unknown time zone: {tz}")))
+            })
+            .transpose()?;
+        Ok(Self {
+            format,
+            tz,
+            year: year.unwrap_or_else(|| Utc::now().year()),
+            fixed_year: year.is_some(),
+        })
+    }
+
+    /// Returns the extractor completing timestamps without year by the given year (e.g. the
+    /// year the source file was modified) instead of the current year. A year defined
+    /// explicitly is kept.
+    pub fn with_default_year(&self, year: i32) -> Self {
+        let mut extractor = self.clone();
+        if !self.fixed_year {
+            extractor.year = year;
+        }
+        extractor
+    }
+
+    /// Returns the timestamp at the beginning of the line in milliseconds since the UNIX epoch.
+    pub fn extract(&self, line: &str) -> Option<u64> {
+        let moment = match self.format.as_ref() {
+            Some(format) => self.parse(line, format)?,
+            None => detect(line, self.year)?,
+        };
+        match moment {
+            Moment::Fixed(datetime) => millis(datetime),
+            Moment::Local(datetime) => match self.tz {
+                Some(tz) => millis(tz.from_local_datetime(&datetime).earliest()?),
+                None => millis(datetime.and_utc()),
+            },
+        }
+    }
+
+    /// Parses the first timestamp of the given format which starts at the beginning of a word.
+    fn parse(&self, line: &str, format: &str) -> Option<Moment> {
+        let line = head(line);
+        let mut previous: Option<char> = None;
+        for (start, c) in line.char_indices() {
+            if previous.is_some_and(char::is_alphanumeric) && c.is_alphanumeric() {
+                previous = Some(c);
+                continue;
+            }
+            previous = Some(c);
+            let mut parsed = Parsed::new();
+            if parse_and_remainder(&mut parsed, &line[start..], StrftimeItems::new(format)).is_err()
+            {
+                continue;
+            }
+            if let Some(moment) = self.resolve(parsed) {
+                return Some(moment);
+            }
+        }
+        None
+    }
+
+    fn resolve(&self, mut parsed: Parsed) -> Option<Moment> {
+        if let Ok(datetime) = parsed.to_datetime() {
+            return Some(Moment::Fixed(datetime));
+        }
+        match parsed.to_naive_datetime_with_offset(0) {
+            Ok(datetime) => return Some(Moment::Local(datetime)),
+            Err(err) if err.kind() == ParseErrorKind::NotEnough => {}
+            Err(_) => return None,
+        }
+        // The format has no year.
+        parsed.set_year(self.year as i64).ok()?;
+        if let Ok(datetime) = parsed.to_datetime() {
+            return Some(Moment::Fixed(datetime));
+        }
+        parsed
+            .to_naive_datetime_with_offset(0)
+            .ok()
+            .map(Moment::Local)
+    }
+}
+
+fn millis<T: TimeZone>(datetime: DateTime<T>) -> Option<u64> {
+    u64::try_from(datetime.timestamp_millis()).ok()
+}
+
+/// Returns the year of the given time (UTC), e.g. to complete timestamps of a file without
+/// year by the time the file was modified.
+pub fn year_of(time: SystemTime) -> i32 {
+    DateTime::<Utc>::from(time).year()
+}
+
+/// Returns the beginning of the line without splitting characters.
+fn head(line: &str) -> &str {
+    if line.len() <= SEARCH_LIMIT {
+        return line;
+    }
+    let mut end = SEARCH_LIMIT;
+    while !line.is_char_boundary(end) {
+        end -= 1;
+    }
+    &line[..end]
+}
+
+fn number<T: std::str::FromStr>(caps: &Captures, index: usize) -> Option<T> {
+    caps.get(index)?.as_str().parse().ok()
+}
+
+/// Builds the date and time; the groups of hours, minutes, seconds and the optional
+/// fraction of seconds start at `time`.
+fn datetime(
+    year: i32,
+    month: u32,
+    day: u32,
+    caps: &Captures,
+    time: usize,
+) -> Option<NaiveDateTime> {
+    let millis = caps
+        .get(time + 3)
+        .map(|fraction| {
+            let digits = &fraction.as_str()[..fraction.len().min(3)];
+            // Pads the fraction to milliseconds, e.g. `.5` is 500 ms.
+            digits
+                .parse::<u32>()
+                .map(|ms| ms * 10u32.pow(3 - digits.len() as u32))
+        })
+        .transpose()
+        .ok()?
+        .unwrap_or(0);
+    NaiveDate::from_ymd_opt(year, month, day)?.and_hms_milli_opt(
+        number(caps, time)?,
+        number(caps, time + 1)?,
+        number(caps, time + 2)?,
+        millis,
+    )
+}
+
+/// Parses offsets like `+01:00`, `-0530`.
+fn parse_offset(offset: &str) -> Option<FixedOffset> {
+    let sign = if offset.starts_with('-') { -1 } else { 1 };
+    let digits = offset[1..].replace(':', "");
+    let hours: i32 = digits.get(..2)?.parse().ok()?;
+    let minutes: i32 = digits.get(2..4)?.parse().ok()?;
+    FixedOffset::east_opt(sign * (hours * 3600 + minutes * 60))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn detect_formats() {
+        assert_eq!(
+            detect_timestamp("2024-01-15T14:32:05.123Z INFO started", 2000),
+            Some(1705329125123)
+        );
+        assert_eq!(
+            detect_timestamp("[2024-01-15 15:32:05,5+01:00] started", 2000),
+            Some(1705329125500)
+        );
+        assert_eq!(
+            detect_timestamp("Jan 15 14:32:05 host app[42]: started", 2024),
+            Some(1705329125000)
+        );
+        assert_eq!(
+            detect_timestamp("01-15 14:32:05.123  1234  1234 I app: started", 2024),
+            Some(1705329125123)
+        );
+        assert_eq!(detect_timestamp("started without timestamp", 2024), None);
+        assert_eq!(
+            detect_timestamp("2024-13-45 14:32:05 invalid date", 2024),
+            None
+        );
+        assert_eq!(
+            year_of(SystemTime::UNIX_EPOCH + std::time::Duration::from_millis(1705329125123)),
+            2024
+        );
+    }
+
+    #[test]
+    fn extract_by_format() {
+        let extractor =
+            TimestampExtractor::new(Some(String::from("%d.%m.%Y %H:%M:%S%.3f")), None, None)
+                .unwrap();
+        assert_eq!(
+            extractor.extract("[main] 15.01.2024 14:32:05.123 started"),
+            Some(1705329125123)
+        );
+        assert_eq!(extractor.extract("[main] 2024-01-15 started"), None);
+        // Timestamps are found at the beginning of words only.
+        assert_eq!(extractor.extract("id115.01.2024 14:32:05.123"), None);
+
+        let extractor =
+            TimestampExtractor::new(Some(String::from("%b %d %H:%M:%S")), None, Some(2024))
+                .unwrap();
+        assert_eq!(
+            extractor.extract("Jan 15 14:32:05 host app[42]: started"),
+            Some(1705329125000)
+        );
+
+        let extractor =
+            TimestampExtractor::new(Some(String::from("%Y-%m-%dT%H:%M:%S%z")), None, None).unwrap();
+        assert_eq!(
+            extractor.extract("2024-01-15T15:32:05+0100 started"),
+            Some(1705329125000)
+        );
+    }
+
+    #[test]
+    fn extract_with_fallbacks() {
+        let extractor = TimestampExtractor::new(None, Some("Europe/Berlin"), Some(2024)).unwrap();
+        // Local times are taken in the given time zone.
+        assert_eq!(
+            extractor.extract("01-15 15:32:05.123  1234  1234 I app: started"),
+            Some(1705329125123)
+        );
+        // Explicit offsets win over the time zone.
+        assert_eq!(
+            extractor.extract("2024-01-15T14:32:05.123Z INFO started"),
+            Some(1705329125123)
+        );
+        assert!(TimestampExtractor::new(Some(String::from("%Y-%Q")), None, None).is_err());
+        assert!(TimestampExtractor::new(None, Some("Mars/Olympus"), None).is_err());
+    }
+
+    #[test]
+    fn default_year() {
+        let line = "Jan 15 14:32:05 host app[42]: started";
+        let extractor = TimestampExtractor::new(None, None, None).unwrap();
+        assert_eq!(
+            extractor.with_default_year(2024).extract(line),
+            Some(1705329125000)
+        );
+        // The year defined explicitly is kept.
+        let extractor = TimestampExtractor::new(None, None, Some(2024)).unwrap();
+        assert_eq!(
+            extractor.with_default_year(2023).extract(line),
+            Some(1705329125000)
+        );
+    }
+}
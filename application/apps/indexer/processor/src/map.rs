@@ -196,6 +196,43 @@ impl SearchMap {
         Ok(&self.matches[*range.start() as usize..=*range.end() as usize])
     }
 
+    /// Returns the ranges of rows around the matches within the given range of search results,
+    /// like the context of `grep -C`. Each window covers `before` rows before a match and
+    /// `after` rows after it; overlapping and adjacent windows are merged.
+    ///
+    /// # Parameters
+    ///
+    /// * `range` - The range of positions in the search results.
+    /// * `before` - The count of rows before each match.
+    /// * `after` - The count of rows after each match.
+    /// * `stream_len` - The count of rows in the stream; windows don't exceed it.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Vec<RangeInclusive<u64>>)` - Ordered ranges of rows in the stream.
+    /// * `Err(MapError::OutOfRange)` - If the range exceeds the available matches.
+    pub fn context(
+        &self,
+        range: &RangeInclusive<u64>,
+        before: u64,
+        after: u64,
+        stream_len: u64,
+    ) -> Result<Vec<RangeInclusive<u64>>, MapError> {
+        let last = stream_len.saturating_sub(1);
+        let mut windows: Vec<RangeInclusive<u64>> = Vec::new();
+        for filter_match in self.indexes(range)? {
+            let start = filter_match.index.saturating_sub(before);
+            let end = filter_match.index.saturating_add(after).min(last);
+            match windows.last_mut() {
+                Some(window) if *window.end() + 1 >= start => {
+                    *window = *window.start()..=end.max(*window.end());
+                }
+                _ => windows.push(start..=end),
+            }
+        }
+        Ok(windows)
+    }
+
     /// Returns information about all matches in the search results occurring after the specified position.
     ///
     /// # Parameters
@@ -506,3 +543,36 @@ fn test_scaled_map() {
     assert_eq!(scaled[8], vec![(0, 1), (1, 2), (2, 1), (3, 1)]);
     assert_eq!(scaled[9], vec![(2, 1), (3, 1)]);
 }
+
+#[test]
+fn test_context() {
+    let mut example_map: SearchMap = SearchMap::new();
+    example_map.set(
+        Some(vec![
+            stypes::FilterMatch::new(1, vec![0]),
+            stypes::FilterMatch::new(4, vec![0]),
+            stypes::FilterMatch::new(10, vec![1]),
+            stypes::FilterMatch::new(13, vec![0]),
+            stypes::FilterMatch::new(19, vec![0]),
+        ]),
+        None,
+    );
+    assert_eq!(
+        example_map.context(&(0..=4), 2, 1, 20).unwrap(),
+        vec![0..=5, 8..=14, 17..=19]
+    );
+    assert_eq!(
+        example_map.context(&(2..=3), 0, 1, 20).unwrap(),
+        vec![10..=11, 13..=14]
+    );
+    // Adjacent windows are merged
+    assert_eq!(
+        example_map.context(&(2..=3), 1, 1, 20).unwrap(),
+        vec![9..=14]
+    );
+    assert_eq!(
+        example_map.context(&(1..=1), 0, 0, 20).unwrap(),
+        vec![4..=4]
+    );
+    assert!(example_map.context(&(0..=5), 1, 1, 20).is_err());
+}
@@ -2,3 +2,5 @@ pub mod error;
 pub mod extractor;
 pub mod filter;
 pub mod searchers;
+pub mod sequences;
+pub mod units;
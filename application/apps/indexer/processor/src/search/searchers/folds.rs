@@ -0,0 +1,161 @@
+//! Detection of repeated rows for folding of noisy logs (e.g. heartbeats or retry storms).
+//! A row repeats the previous one if both rows are identical or, comparing templates, if
+//! they differ only in tokens with digits, which are most likely parameters (see
+//! [`super::templates`]).
+use crate::search::error::SearchError;
+use std::{
+    borrow::Cow,
+    ops::{Range, RangeInclusive},
+    path::{Path, PathBuf},
+};
+use tokio_util::sync::CancellationToken;
+use uuid::Uuid;
+
+use super::{
+    BaseSearcher, SearchState,
+    templates::{WILDCARD, is_separator},
+};
+
+pub type FoldsResults = Result<(Range<usize>, Vec<RangeInclusive<u64>>), SearchError>;
+
+#[derive(Debug)]
+pub struct FoldsState {
+    pub file_path: PathBuf,
+    pub uuid: Uuid,
+    /// Compare templates of rows instead of the whole rows
+    by_template: bool,
+    /// Compared form of the last collected row
+    previous: Option<String>,
+    /// Ranges of rows repeating their previous rows
+    repeats: Vec<RangeInclusive<u64>>,
+}
+
+pub type FoldsHolder = BaseSearcher<FoldsState>;
+
+impl FoldsHolder {
+    /// Creates a holder comparing the rows of the file from the beginning.
+    pub fn folding(path: &Path, uuid: Uuid, by_template: bool) -> Self {
+        let mut holder = Self::new(path, uuid, 0, 0);
+        holder.search_state.by_template = by_template;
+        holder
+    }
+}
+
+impl SearchState for FoldsState {
+    type SearchResultType = FoldsResults;
+    fn new(path: &Path, uuid: Uuid) -> Self {
+        Self {
+            file_path: PathBuf::from(path),
+            uuid,
+            by_template: false,
+            previous: None,
+            repeats: vec![],
+        }
+    }
+    fn get_terms(&self) -> Vec<String> {
+        // All rows are compared
+        vec![String::from("^")]
+    }
+}
+
+/// Returns the form of the row to compare with the previous one.
+fn compared(line: &str, by_template: bool) -> Cow<'_, str> {
+    if !by_template {
+        return Cow::Borrowed(line);
+    }
+    Cow::Owned(
+        line.split(is_separator)
+            .filter(|token| !token.is_empty())
+            .map(|token| {
+                if token.chars().any(|c| c.is_ascii_digit()) {
+                    WILDCARD
+                } else {
+                    token
+                }
+            })
+            .collect::<Vec<&str>>()
+            .join(" "),
+    )
+}
+
+fn collect(row: u64, line: &str, state: &mut FoldsState) {
+    let line = compared(line.trim_end_matches(['\n', '\r']), state.by_template);
+    if state.previous.as_deref() == Some(line.as_ref()) {
+        match state.repeats.last_mut() {
+            Some(repeats) if *repeats.end() + 1 == row => {
+                *repeats = *repeats.start()..=row;
+            }
+            _ => state.repeats.push(row..=row),
+        }
+    } else {
+        state.previous = Some(line.into_owned());
+    }
+}
+
+/// Compares the rows added to the file since the previous call. Returns the range of
+/// compared rows and the ranges of rows repeating their previous rows; the first row
+/// of a range might repeat the last row compared by the previous call.
+pub fn search(
+    base_searcher: &mut FoldsHolder,
+    rows_count: u64,
+    read_bytes: u64,
+    cancellation: CancellationToken,
+) -> FoldsResults {
+    if rows_count <= base_searcher.lines_read {
+        return Ok((0..0, vec![]));
+    }
+    let processed = base_searcher.search(rows_count, read_bytes, cancellation, collect)?;
+    Ok((
+        processed,
+        std::mem::take(&mut base_searcher.search_state.repeats),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+
+    fn repeats(rows: &[&str], by_template: bool) -> (Range<usize>, Vec<RangeInclusive<u64>>) {
+        let mut file = tempfile::NamedTempFile::new().expect("temp file is created");
+        for row in rows {
+            writeln!(file, "{row}").expect("row is written");
+        }
+        let len = file.as_file().metadata().expect("file has metadata").len();
+        let mut holder = FoldsHolder::folding(file.path(), Uuid::new_v4(), by_template);
+        search(
+            &mut holder,
+            rows.len() as u64,
+            len,
+            CancellationToken::new(),
+        )
+        .expect("rows are compared")
+    }
+
+    #[test]
+    fn repeated_rows() {
+        let rows = [
+            "heartbeat",
+            "heartbeat",
+            "heartbeat",
+            "retry 1 of 5",
+            "retry 2 of 5",
+            "heartbeat",
+            "heartbeat",
+        ];
+        assert_eq!(repeats(&rows, false), (0..7, vec![1..=2, 6..=6]));
+        assert_eq!(repeats(&rows, true), (0..7, vec![1..=2, 4..=4, 6..=6]));
+    }
+
+    #[test]
+    fn dlt_columns_are_tokens() {
+        assert_eq!(
+            compared("ECU1\u{4}APP\u{4}retry\u{5}1", true),
+            compared("ECU1\u{4}APP\u{4}retry\u{5}2", true)
+        );
+        assert_ne!(
+            compared("ECU1\u{4}APP\u{4}retry\u{5}1", false),
+            compared("ECU1\u{4}APP\u{4}retry\u{5}2", false)
+        );
+    }
+}
@@ -11,8 +11,10 @@ use text_grep::buffer::CancellableBufReader;
 use tokio_util::sync::CancellationToken;
 use uuid::Uuid;
 
+pub mod folds;
 pub mod linear;
 pub mod regular;
+pub mod templates;
 #[cfg(test)]
 pub mod tests_linear;
 #[cfg(test)]
@@ -46,6 +48,17 @@ impl<State: SearchState> BaseSearcher<State> {
             search_state,
         }
     }
+    /// Returns the count of lines processed by the searcher.
+    pub fn lines_read(&self) -> u64 {
+        self.lines_read
+    }
+
+    /// Sets the position of the processed lines in the file, e.g. after the file was replaced
+    /// by a copy with a different content of these lines.
+    pub fn set_bytes_read(&mut self, bytes_read: u64) {
+        self.bytes_read = bytes_read;
+    }
+
     /// execute a search for the given input path and filters
     /// return the file that contains the search results along with the
     /// map of found matches. Format of map is an array of matches:
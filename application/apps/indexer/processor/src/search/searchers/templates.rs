@@ -0,0 +1,321 @@
+//! Mining of message templates with the Drain algorithm (He et al., "Drain: An Online Log
+//! Parsing Approach with Fixed Depth Tree"). Rows are split into tokens and grouped by
+//! their count of tokens and the first tokens; within a group a row joins the most similar
+//! template, where tokens differing between rows become parameters.
+use crate::search::{error::SearchError, filter::SearchFilter};
+use parsers::dlt::fmt::{DLT_ARGUMENT_SENTINAL, DLT_COLUMN_SENTINAL};
+use std::{
+    collections::HashMap,
+    ops::Range,
+    path::{Path, PathBuf},
+};
+use tokio_util::sync::CancellationToken;
+use uuid::Uuid;
+
+use super::{BaseSearcher, SearchState};
+
+/// Placeholder of a parameter in a template
+pub const WILDCARD: &str = "<*>";
+/// Count of leading tokens used to find the group of a row
+const PREFIX_DEPTH: usize = 2;
+/// Minimal share of equal tokens to add a row to a template
+const SIMILARITY_THRESHOLD: f64 = 0.5;
+/// Maximal count of children of a node of the tree; further tokens share the wildcard node
+const MAX_CHILDREN: usize = 100;
+/// Class of characters separating tokens (see [`is_separator`]) within a regex
+const SEPARATORS: &str = r"\s\x04\x05";
+
+/// Template of messages with statistics of the rows matching it
+#[derive(Debug)]
+struct Template {
+    id: u32,
+    tokens: Vec<String>,
+    count: u64,
+    first: u64,
+    last: u64,
+    example: String,
+    hidden: bool,
+}
+
+impl Template {
+    /// Returns the share of tokens matching the template and the count of its parameters.
+    /// Parameters match any token.
+    fn similarity(&self, tokens: &[&str]) -> (f64, usize) {
+        let mut equal = 0;
+        let mut params = 0;
+        for (template, token) in self.tokens.iter().zip(tokens.iter()) {
+            if template == WILDCARD {
+                params += 1;
+                equal += 1;
+            } else if template == token {
+                equal += 1;
+            }
+        }
+        (equal as f64 / tokens.len() as f64, params)
+    }
+
+    fn add(&mut self, row: u64, tokens: &[&str]) {
+        for (template, token) in self.tokens.iter_mut().zip(tokens.iter()) {
+            if template != token {
+                *template = WILDCARD.to_owned();
+            }
+        }
+        self.count += 1;
+        self.last = row;
+    }
+
+    /// Returns a filter matching the rows of the template
+    fn as_filter(&self) -> SearchFilter {
+        let tokens = self
+            .tokens
+            .iter()
+            .map(|token| {
+                if token == WILDCARD {
+                    format!("[^{SEPARATORS}]+")
+                } else {
+                    regex::escape(token)
+                }
+            })
+            .collect::<Vec<String>>()
+            .join(&format!("[{SEPARATORS}]+"));
+        SearchFilter::plain(&format!("^[{SEPARATORS}]*{tokens}[{SEPARATORS}]*$")).regex(true)
+    }
+}
+
+#[derive(Debug, Default)]
+struct Node {
+    children: HashMap<String, Node>,
+    /// Indexes of templates in the leaf
+    templates: Vec<usize>,
+}
+
+#[derive(Debug)]
+pub struct TemplatesState {
+    pub file_path: PathBuf,
+    pub uuid: Uuid,
+    /// Roots of the tree by count of tokens
+    roots: HashMap<usize, Node>,
+    templates: Vec<Template>,
+}
+
+pub type TemplatesHolder = BaseSearcher<TemplatesState>;
+
+impl TemplatesHolder {
+    /// Returns the visible templates ordered by the count of rows.
+    pub fn templates(&self) -> Vec<stypes::LogTemplate> {
+        let mut templates = self
+            .search_state
+            .templates
+            .iter()
+            .filter(|template| !template.hidden)
+            .map(|template| stypes::LogTemplate {
+                id: template.id,
+                template: template.tokens.join(" "),
+                filter: template.as_filter().value,
+                count: template.count,
+                first: template.first,
+                last: template.last,
+                example: template.example.clone(),
+            })
+            .collect::<Vec<stypes::LogTemplate>>();
+        templates.sort_by(|a, b| b.count.cmp(&a.count).then(a.id.cmp(&b.id)));
+        templates
+    }
+
+    /// Returns a regex filter matching the rows of the template.
+    pub fn filter(&self, id: u32) -> Option<SearchFilter> {
+        self.search_state
+            .templates
+            .get(id as usize)
+            .map(Template::as_filter)
+    }
+
+    /// Hides templates from the list of templates. Only the list is affected: rows of hidden
+    /// templates stay in the stream and in search results. These rows are still assigned to
+    /// the hidden templates while mining, so hidden templates don't appear again.
+    pub fn hide(&mut self, ids: &[u32]) {
+        for id in ids {
+            if let Some(template) = self.search_state.templates.get_mut(*id as usize) {
+                template.hidden = true;
+            }
+        }
+    }
+
+    /// Makes all templates visible.
+    pub fn show_all(&mut self) {
+        self.search_state
+            .templates
+            .iter_mut()
+            .for_each(|template| template.hidden = false);
+    }
+}
+
+impl SearchState for TemplatesState {
+    type SearchResultType = Result<Range<usize>, SearchError>;
+    fn new(path: &Path, uuid: Uuid) -> Self {
+        Self {
+            file_path: PathBuf::from(path),
+            uuid,
+            roots: HashMap::new(),
+            templates: vec![],
+        }
+    }
+    fn get_terms(&self) -> Vec<String> {
+        // All rows are mined
+        vec![String::from("^")]
+    }
+}
+
+pub(super) fn is_separator(c: char) -> bool {
+    c.is_whitespace() || c == DLT_COLUMN_SENTINAL || c == DLT_ARGUMENT_SENTINAL
+}
+
+fn collect(row: u64, line: &str, state: &mut TemplatesState) {
+    let tokens = line
+        .split(is_separator)
+        .filter(|token| !token.is_empty())
+        .collect::<Vec<&str>>();
+    if tokens.is_empty() {
+        return;
+    }
+    let mut node = state.roots.entry(tokens.len()).or_default();
+    for token in tokens.iter().take(PREFIX_DEPTH) {
+        // Tokens with digits are most likely parameters
+        let key = if token.chars().any(|c| c.is_ascii_digit()) {
+            WILDCARD
+        } else {
+            token
+        };
+        let key = if node.children.contains_key(key) || node.children.len() < MAX_CHILDREN {
+            key
+        } else {
+            WILDCARD
+        };
+        node = node.children.entry(key.to_owned()).or_default();
+    }
+    let best = node
+        .templates
+        .iter()
+        .map(|index| (*index, state.templates[*index].similarity(&tokens)))
+        .filter(|(_, (similarity, _))| *similarity >= SIMILARITY_THRESHOLD)
+        .max_by(|(_, (a, a_params)), (_, (b, b_params))| {
+            a.total_cmp(b).then(a_params.cmp(b_params))
+        })
+        .map(|(index, _)| index);
+    if let Some(index) = best {
+        state.templates[index].add(row, &tokens);
+    } else {
+        let id = state.templates.len();
+        state.templates.push(Template {
+            id: id as u32,
+            tokens: tokens.iter().map(|token| token.to_string()).collect(),
+            count: 1,
+            first: row,
+            last: row,
+            example: line.to_owned(),
+            hidden: false,
+        });
+        node.templates.push(id);
+    }
+}
+
+/// Mines templates of the rows added to the file since the previous call.
+pub fn mine(
+    base_searcher: &mut TemplatesHolder,
+    rows_count: u64,
+    read_bytes: u64,
+    cancellation: CancellationToken,
+) -> Result<Range<usize>, SearchError> {
+    // Mining is requested on demand, so there might be no new rows since the previous call
+    if rows_count <= base_searcher.lines_read {
+        return Ok(0..0);
+    }
+    base_searcher.search(rows_count, read_bytes, cancellation, collect)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+
+    #[test]
+    fn templates_of_rows() {
+        let rows = [
+            "connected to 10.0.0.1 in 12 ms",
+            "user login alice ok",
+            "connected to 10.0.0.7 in 3 ms",
+            "user login bob ok",
+            "connection lost",
+            "user login carol failed",
+            "connected to 10.0.0.9 in 41 ms",
+        ];
+        let mut file = tempfile::NamedTempFile::new().expect("temp file is created");
+        for row in rows {
+            writeln!(file, "{row}").expect("row is written");
+        }
+        let len = file.as_file().metadata().expect("file has metadata").len();
+        let mut holder = TemplatesHolder::new(file.path(), Uuid::new_v4(), 0, 0);
+        mine(
+            &mut holder,
+            rows.len() as u64,
+            len,
+            CancellationToken::new(),
+        )
+        .expect("templates are mined");
+        // Nothing is mined without new rows
+        assert_eq!(
+            mine(
+                &mut holder,
+                rows.len() as u64,
+                len,
+                CancellationToken::new(),
+            )
+            .expect("mining succeeds"),
+            0..0
+        );
+        let templates = holder
+            .templates()
+            .into_iter()
+            .map(|t| (t.template, t.count, t.first, t.last))
+            .collect::<Vec<(String, u64, u64, u64)>>();
+        assert_eq!(
+            templates,
+            vec![
+                (String::from("connected to <*> in <*> ms"), 3, 0, 6),
+                (String::from("user login <*> <*>"), 3, 1, 5),
+                (String::from("connection lost"), 1, 4, 4),
+            ]
+        );
+        let filter = regex::Regex::new(&holder.filter(0).expect("template exists").value)
+            .expect("filter is valid");
+        assert!(filter.is_match("connected to 10.0.0.2 in 7 ms"));
+        assert!(!filter.is_match("connected to 10.0.0.2 in 7 ms twice"));
+
+        holder.hide(&[0]);
+        assert_eq!(holder.templates().len(), 2);
+        holder.show_all();
+        assert_eq!(holder.templates().len(), 3);
+    }
+
+    #[test]
+    fn dlt_columns_are_tokens() {
+        let mut state = TemplatesState::new(Path::new(""), Uuid::new_v4());
+        collect(0, "ECU1\u{4}APP\u{4}started\u{5}1", &mut state);
+        collect(1, "ECU1\u{4}APP\u{4}started\u{5}2", &mut state);
+        assert_eq!(state.templates.len(), 1);
+        assert_eq!(state.templates[0].tokens.join(" "), "ECU1 APP started <*>");
+    }
+
+    #[test]
+    fn parameters_match_any_token() {
+        let mut state = TemplatesState::new(Path::new(""), Uuid::new_v4());
+        collect(0, "job 1 b c d", &mut state);
+        collect(1, "job 2 x c d", &mut state);
+        assert_eq!(state.templates[0].tokens.join(" "), "job <*> <*> c d");
+        // Only "job" is equal, but the parameters match as well
+        collect(2, "job 3 y z e", &mut state);
+        assert_eq!(state.templates.len(), 1);
+        assert_eq!(state.templates[0].tokens.join(" "), "job <*> <*> <*> <*>");
+        assert_eq!(state.templates[0].count, 3);
+    }
+}
@@ -95,3 +95,94 @@ fn test_value_search2() -> Result<(), std::io::Error> {
     assert_eq!(control_1, sum_1);
     Ok(())
 }
+
+#[test]
+fn test_value_search_named_groups_and_units() -> Result<(), std::io::Error> {
+    let rows = [
+        "cpu=12% mem=340MB latency=0x1F",
+        "no values here",
+        "cpu=15% mem=1.5GB latency=1.2s",
+        "cpu=high mem=2KiB latency=12ms",
+    ];
+    let terms = extracted(
+        &rows.join("\n"),
+        vec![
+            String::from(r"cpu=(?P<cpu>\S+) mem=(?P<mem>\S+)"),
+            String::from(r"latency=(\S+)"),
+        ],
+    )?;
+    assert_eq!(terms.get(&0), Some(&vec![(0, 12.0), (2, 15.0)]));
+    assert_eq!(
+        terms.get(&1),
+        Some(&vec![(0, 340e6), (2, 1.5e9), (3, 2048.0)])
+    );
+    assert_eq!(
+        terms.get(&2),
+        Some(&vec![(0, 31.0), (2, 1200.0), (3, 12.0)])
+    );
+    Ok(())
+}
+
+#[test]
+fn test_value_search_derived_series() -> Result<(), std::io::Error> {
+    let rows = [
+        "2024-01-15 10:00:00.000 sent=100",
+        "2024-01-15 10:00:00.500 error",
+        "2024-01-15 10:00:02.000 sent=400",
+        "2024-01-15 10:00:02.000 sent=450",
+        "2024-01-15 10:00:03.000 sent=550 error",
+    ];
+    let terms = extracted(
+        &rows.join("\n"),
+        vec![
+            String::from(r"sent=(?P<sent_delta>\d+)"),
+            String::from(r"sent=(?P<sent_rate>\d+)"),
+            String::from("error"),
+        ],
+    )?;
+    assert_eq!(
+        terms.get(&0),
+        Some(&vec![(2, 300.0), (3, 50.0), (4, 100.0)])
+    );
+    // Samples with the same timestamp are merged into the next one.
+    assert_eq!(terms.get(&1), Some(&vec![(2, 150.0), (4, 150.0)]));
+    assert_eq!(terms.get(&2), Some(&vec![(1, 1.0), (4, 2.0)]));
+    Ok(())
+}
+
+#[test]
+fn test_value_series() {
+    let mut holder: BaseSearcher<ValueSearchState> =
+        ValueSearchHolder::new(std::path::Path::new("unused"), Uuid::new_v4(), 0, 0);
+    holder
+        .setup(vec![
+            String::from(r"cpu=(?P<cpu>\d+) mem=(?P<mem_delta>\d+)"),
+            String::from(r"temp=(\d+)"),
+            String::from("error"),
+        ])
+        .expect("setup failed");
+    let series = holder
+        .series()
+        .into_iter()
+        .map(|s| (s.id, s.term, s.name, s.kind))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        series,
+        vec![
+            (
+                0,
+                0,
+                Some(String::from("cpu")),
+                stypes::ValueSeriesKind::Value
+            ),
+            (
+                1,
+                0,
+                Some(String::from("mem_delta")),
+                stypes::ValueSeriesKind::Delta
+            ),
+            (2, 1, None, stypes::ValueSeriesKind::Value),
+            (3, 2, None, stypes::ValueSeriesKind::Count),
+        ]
+    );
+}
@@ -1,5 +1,6 @@
-use crate::search::error::SearchError;
-use regex::Regex;
+use crate::search::{error::SearchError, units::parse_value};
+use parsers::text::timestamp::TimestampExtractor;
+use regex::{Captures, Regex};
 use std::{
     collections::HashMap,
     ops::Range,
@@ -13,6 +14,11 @@ use super::{BaseSearcher, SearchState};
 
 pub type OperationResults = Result<(Range<usize>, HashMap<u8, Vec<(u64, f64)>>), SearchError>;
 
+/// Suffix of a capture group name producing the differences between extracted values
+pub const DELTA_SUFFIX: &str = "_delta";
+/// Suffix of a capture group name producing the change of extracted values per second
+pub const RATE_SUFFIX: &str = "_rate";
+
 #[derive(Debug)]
 struct Results {
     indexes: Option<HashMap<u8, Vec<(u64, f64)>>>,
@@ -30,6 +36,34 @@ pub fn as_regex(filter: &str) -> String {
     format!("(?i){filter}(?-i)")
 }
 
+/// Replaces named capture groups with non-capturing groups. Terms are combined into one
+/// regex to find matching rows, where the same group name in different terms would
+/// conflict.
+fn without_names(filter: &str) -> String {
+    let mut output = String::with_capacity(filter.len());
+    let mut rest = filter;
+    while let Some(c) = rest.chars().next() {
+        if c == '\\' {
+            let escaped = rest.chars().nth(1).map_or(1, |c| 1 + c.len_utf8());
+            output.push_str(&rest[..escaped]);
+            rest = &rest[escaped..];
+            continue;
+        }
+        if let Some(named) = rest
+            .strip_prefix("(?P<")
+            .or_else(|| rest.strip_prefix("(?<"))
+            && let Some(end) = named.find('>')
+        {
+            output.push_str("(?:");
+            rest = &named[end + 1..];
+            continue;
+        }
+        output.push(c);
+        rest = &rest[c.len_utf8()..];
+    }
+    output
+}
+
 /// Validate search condition. Checks possibility to convert
 /// given string into RegEx
 ///
@@ -45,12 +79,151 @@ fn is_valid(filter: &str) -> bool {
     Regex::from_str(&as_regex(filter)).is_ok()
 }
 
+#[derive(Debug)]
+enum Source {
+    /// Capture group with the given index
+    Group(usize),
+    /// Named capture group
+    Name(String),
+    /// Matches of the term
+    Matches,
+}
+
+/// Series of values produced by a term along with the state needed to derive values.
+#[derive(Debug)]
+struct Series {
+    id: u8,
+    name: Option<String>,
+    source: Source,
+    kind: stypes::ValueSeriesKind,
+    /// Previous value with its timestamp (for rates)
+    last: Option<(f64, Option<u64>)>,
+    count: u64,
+}
+
+impl Series {
+    fn new(id: u8, name: Option<String>, source: Source, kind: stypes::ValueSeriesKind) -> Self {
+        Self {
+            id,
+            name,
+            source,
+            kind,
+            last: None,
+            count: 0,
+        }
+    }
+
+    /// Returns the value of the series for the given match or the captured text if it
+    /// cannot be parsed.
+    fn value(
+        &mut self,
+        caps: &Captures,
+        timestamp: impl FnOnce() -> Option<u64>,
+    ) -> Result<Option<f64>, String> {
+        let captured = match &self.source {
+            Source::Group(index) => caps.get(*index),
+            Source::Name(name) => caps.name(name),
+            Source::Matches => {
+                self.count += 1;
+                return Ok(Some(self.count as f64));
+            }
+        };
+        let Some(captured) = captured else {
+            return Ok(None);
+        };
+        let value = parse_value(captured.as_str()).ok_or_else(|| captured.as_str().to_owned())?;
+        Ok(match self.kind {
+            stypes::ValueSeriesKind::Value | stypes::ValueSeriesKind::Count => Some(value),
+            stypes::ValueSeriesKind::Delta => self
+                .last
+                .replace((value, None))
+                .map(|(last, _)| value - last),
+            stypes::ValueSeriesKind::Rate => {
+                let Some(timestamp) = timestamp() else {
+                    return Ok(None);
+                };
+                match self.last {
+                    Some((last, Some(last_timestamp))) if timestamp > last_timestamp => {
+                        self.last = Some((value, Some(timestamp)));
+                        Some((value - last) * 1000.0 / (timestamp - last_timestamp) as f64)
+                    }
+                    // Samples logged within the same millisecond are merged into the next one
+                    Some((_, Some(last_timestamp))) if timestamp == last_timestamp => None,
+                    _ => {
+                        self.last = Some((value, Some(timestamp)));
+                        None
+                    }
+                }
+            }
+        })
+    }
+}
+
+#[derive(Debug)]
+struct Term {
+    matcher: Regex,
+    series: Vec<Series>,
+}
+
+/// Creates the series of a term. The first series gets the index of the term as ID, which
+/// keeps results of terms producing a single series keyed by the index of the term. Further
+/// series get IDs starting with `next_id`.
+fn series_of(matcher: &Regex, term: usize, next_id: usize) -> Result<Vec<Series>, SearchError> {
+    let id = |offset: usize| {
+        let id = if offset == 0 {
+            term
+        } else {
+            next_id + offset - 1
+        };
+        u8::try_from(id).map_err(|_| SearchError::Input(String::from("Too many series of values")))
+    };
+    let names = matcher.capture_names().flatten().collect::<Vec<&str>>();
+    if !names.is_empty() {
+        return names
+            .into_iter()
+            .enumerate()
+            .map(|(offset, name)| {
+                let kind = if name.ends_with(DELTA_SUFFIX) {
+                    stypes::ValueSeriesKind::Delta
+                } else if name.ends_with(RATE_SUFFIX) {
+                    stypes::ValueSeriesKind::Rate
+                } else {
+                    stypes::ValueSeriesKind::Value
+                };
+                Ok(Series::new(
+                    id(offset)?,
+                    Some(name.to_owned()),
+                    Source::Name(name.to_owned()),
+                    kind,
+                ))
+            })
+            .collect();
+    }
+    Ok(vec![if matcher.captures_len() > 1 {
+        Series::new(
+            id(0)?,
+            None,
+            Source::Group(1),
+            stypes::ValueSeriesKind::Value,
+        )
+    } else {
+        Series::new(
+            id(0)?,
+            None,
+            Source::Matches,
+            stypes::ValueSeriesKind::Count,
+        )
+    }])
+}
+
 #[derive(Debug)]
 pub struct ValueSearchState {
     pub file_path: PathBuf,
     pub uuid: Uuid,
     terms: Vec<String>,
-    matchers: Vec<Regex>,
+    matchers: Vec<Term>,
+    /// Extracts timestamps of rows for series of rates
+    timestamps: Option<TimestampExtractor>,
     results: Results,
     errors: HashMap<u64, Vec<(u8, String)>>,
 }
@@ -58,6 +231,16 @@ pub struct ValueSearchState {
 pub type ValueSearchHolder = BaseSearcher<ValueSearchState>;
 
 impl ValueSearchHolder {
+    /// Sets up terms of the search. Each term produces one or more series of values:
+    /// - a term with named capture groups produces a series per group; groups with names
+    ///   ending with `_delta` produce the differences between consecutive values and groups
+    ///   ending with `_rate` the change of values per second;
+    /// - a term with unnamed capture groups produces the values of the first group;
+    /// - a term without capture groups produces the running count of its matches.
+    ///
+    /// Captured values are parsed considering units (see [`parse_value`]). The first series
+    /// of a term is keyed by the index of the term in results, further series of terms get
+    /// the following keys (see [`Self::series`]).
     pub fn setup(&mut self, terms: Vec<String>) -> Result<(), SearchError> {
         let invalid = terms
             .iter()
@@ -68,16 +251,61 @@ impl ValueSearchHolder {
         if !invalid.is_empty() {
             Err(SearchError::Input(format!("Invalid filters: {invalid}")))?;
         }
-        let mut matchers = vec![];
-        for filter in terms.iter() {
-            matchers.push(Regex::from_str(&as_regex(filter)).map_err(|err| {
+        let mut matchers: Vec<Term> = vec![];
+        // IDs of further series of terms follow the IDs of the first series of all terms
+        let mut next_id = terms.len();
+        for (term, filter) in terms.iter().enumerate() {
+            let matcher = Regex::from_str(&as_regex(filter)).map_err(|err| {
                 SearchError::Regex(format!("Failed to create regex for {filter}: {err}"))
-            })?);
+            })?;
+            let series = series_of(&matcher, term, next_id)?;
+            next_id += series.len() - 1;
+            matchers.push(Term { matcher, series });
         }
+        let rates = matchers
+            .iter()
+            .flat_map(|term| term.series.iter())
+            .any(|series| series.kind == stypes::ValueSeriesKind::Rate);
+        self.search_state.timestamps = if rates {
+            Some(
+                TimestampExtractor::new(None, None, None)
+                    .map_err(|err| SearchError::Config(err.to_string()))?,
+            )
+        } else {
+            None
+        };
         self.search_state.terms = terms;
         self.search_state.matchers = matchers;
         Ok(())
     }
+
+    /// Replaces the extractor of timestamps used for series of rates. By default
+    /// timestamps are detected automatically.
+    pub fn set_timestamp_extractor(&mut self, extractor: TimestampExtractor) {
+        if let Some(timestamps) = self.search_state.timestamps.as_mut() {
+            *timestamps = extractor;
+        }
+    }
+
+    /// Returns the descriptions of the series produced by the terms.
+    pub fn series(&self) -> Vec<stypes::ValueSeries> {
+        self.search_state
+            .matchers
+            .iter()
+            .enumerate()
+            .flat_map(|(term, matcher)| {
+                matcher
+                    .series
+                    .iter()
+                    .map(move |series| stypes::ValueSeries {
+                        id: series.id,
+                        term: term as u8,
+                        name: series.name.clone(),
+                        kind: series.kind.clone(),
+                    })
+            })
+            .collect()
+    }
 }
 
 impl SearchState for ValueSearchState {
@@ -88,35 +316,49 @@ impl SearchState for ValueSearchState {
             uuid,
             terms: vec![],
             matchers: vec![],
+            timestamps: None,
             results: Results::new(),
             errors: HashMap::new(),
         }
     }
     fn get_terms(&self) -> Vec<String> {
-        self.terms.iter().map(|f| as_regex(f)).collect()
+        self.terms
+            .iter()
+            .map(|f| as_regex(&without_names(f)))
+            .collect()
     }
 }
 
 fn collect(row: u64, line: &str, state: &mut ValueSearchState) {
-    for (term_index, re) in state.matchers.iter().enumerate() {
-        if let Some(caps) = re.captures(line)
-            && let Some(value) = caps.get(1)
-        {
-            let value_str = value.as_str().to_owned();
-            if let Ok(value_i64) = value_str.parse::<f64>() {
-                if let Some(indexes) = state.results.indexes.as_mut() {
-                    if let Some(matches) = indexes.get_mut(&(term_index as u8)) {
-                        matches.push((row, value_i64));
-                    } else {
-                        indexes.insert(term_index as u8, vec![(row, value_i64)]);
+    let ValueSearchState {
+        matchers,
+        timestamps,
+        results,
+        errors,
+        ..
+    } = state;
+    // Timestamp of the row is extracted once and only if a series of rates needs it
+    let mut timestamp: Option<Option<u64>> = None;
+    for term in matchers.iter_mut() {
+        let Some(caps) = term.matcher.captures(line) else {
+            continue;
+        };
+        for series in term.series.iter_mut() {
+            let value = series.value(&caps, || {
+                *timestamp.get_or_insert_with(|| {
+                    timestamps
+                        .as_ref()
+                        .and_then(|extractor| extractor.extract(line))
+                })
+            });
+            match value {
+                Ok(Some(value)) => {
+                    if let Some(indexes) = results.indexes.as_mut() {
+                        indexes.entry(series.id).or_default().push((row, value));
                     }
                 }
-            } else if let Some(errors) = state.errors.get_mut(&row) {
-                errors.push((term_index as u8, value_str));
-            } else {
-                state
-                    .errors
-                    .insert(row, vec![(term_index as u8, value_str)]);
+                Ok(None) => {}
+                Err(value_str) => errors.entry(row).or_default().push((series.id, value_str)),
             }
         }
     }
@@ -141,3 +383,33 @@ pub fn search(
             )))?,
     ))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn names_of_groups_are_removed() {
+        assert_eq!(
+            without_names(r"cpu=(?P<cpu>\d+)% mem=(?<mem>\d+)"),
+            r"cpu=(?:\d+)% mem=(?:\d+)"
+        );
+        assert_eq!(without_names(r"\(?P<x>(\d)"), r"\(?P<x>(\d)");
+    }
+
+    #[test]
+    fn first_series_are_keyed_by_terms() {
+        let ids = |filter: &str, term: usize, next_id: usize| {
+            let matcher = Regex::from_str(&as_regex(filter)).expect("valid regex");
+            series_of(&matcher, term, next_id)
+                .expect("series are created")
+                .iter()
+                .map(|series| series.id)
+                .collect::<Vec<u8>>()
+        };
+        // Two terms: the further series of the first term follow the keys of both terms
+        assert_eq!(ids(r"cpu=(?P<cpu>\d+) mem=(?P<mem>\d+)", 0, 2), vec![0, 2]);
+        assert_eq!(ids(r"temp=(\d+)", 1, 3), vec![1]);
+        assert_eq!(ids(r"restart", 1, 3), vec![1]);
+    }
+}
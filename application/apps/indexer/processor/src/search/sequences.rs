@@ -0,0 +1,362 @@
+use crate::search::{
+    error::SearchError,
+    filter::{self, SearchFilter},
+};
+use regex::Regex;
+use stypes::{SearchCondition, SequenceInstance, SequenceRule, SequenceViolation};
+
+/// Max count of pending instances of a rule. A log with many rows matching the first step
+/// of a rule but no following steps would keep all of them otherwise.
+pub const MAX_PENDING_INSTANCES: usize = 1000;
+
+fn as_regex(condition: &SearchCondition) -> Result<Regex, SearchError> {
+    let filter = SearchFilter::new(
+        condition.value.clone(),
+        condition.is_regex,
+        condition.ignore_case,
+        condition.is_word,
+    );
+    Regex::new(&filter::as_regex(&filter))
+        .map_err(|err| SearchError::Regex(format!("Invalid condition \"{}\": {err}", filter.value)))
+}
+
+#[derive(Debug)]
+struct Step {
+    regex: Regex,
+    max_rows: Option<u64>,
+    max_ms: Option<u64>,
+}
+
+#[derive(Debug)]
+struct Rule {
+    steps: Vec<Step>,
+    forbidden: Vec<Regex>,
+}
+
+/// An instance of a rule waiting for its next step.
+#[derive(Debug)]
+struct Pending {
+    rule: usize,
+    /// Rows of the matched steps; the next step is `steps.len()`.
+    steps: Vec<u64>,
+    /// Timestamp of the last matched step if it's known.
+    timestamp: Option<u64>,
+}
+
+impl Pending {
+    fn close(self, end: u64, violation: Option<SequenceViolation>) -> SequenceInstance {
+        SequenceInstance {
+            rule: self.rule as u32,
+            rows: stypes::Range {
+                start: self.steps[0],
+                end,
+            },
+            steps: self.steps,
+            violation,
+        }
+    }
+}
+
+/// Checks the rows of a log against sequence rules: ordered steps following each other
+/// within max gaps (in rows or in ms) without forbidden rows in between. Each row matching
+/// the first step of a rule starts a new instance of the rule; a row can advance several
+/// instances, but each of them by one step only. A row matching a forbidden condition
+/// violates all pending instances of the rule before steps are checked.
+///
+/// Gaps in time are checked only if timestamps of both rows are known. At most
+/// [`MAX_PENDING_INSTANCES`] instances of a rule are pending; once a new instance exceeds
+/// the limit, the oldest pending instance of the rule is closed as overflowed.
+#[derive(Debug)]
+pub struct SequenceChecker {
+    rules: Vec<Rule>,
+    /// Pending instances ordered by their first rows
+    pending: Vec<Pending>,
+    /// Count of pending instances of each rule
+    counts: Vec<usize>,
+    closed: Vec<SequenceInstance>,
+    last_row: Option<u64>,
+}
+
+impl SequenceChecker {
+    pub fn new(rules: Vec<SequenceRule>) -> Result<Self, SearchError> {
+        if rules.is_empty() {
+            return Err(SearchError::Config(String::from(
+                "No sequence rules defined",
+            )));
+        }
+        let rules = rules
+            .into_iter()
+            .enumerate()
+            .map(|(i, rule)| {
+                if rule.steps.is_empty() {
+                    return Err(SearchError::Config(format!(
+                        "No steps defined in sequence rule {i}"
+                    )));
+                }
+                Ok(Rule {
+                    steps: rule
+                        .steps
+                        .iter()
+                        .map(|step| {
+                            Ok(Step {
+                                regex: as_regex(&step.condition)?,
+                                max_rows: step.max_rows,
+                                max_ms: step.max_ms,
+                            })
+                        })
+                        .collect::<Result<Vec<Step>, SearchError>>()?,
+                    forbidden: rule
+                        .forbidden
+                        .iter()
+                        .map(as_regex)
+                        .collect::<Result<Vec<Regex>, SearchError>>()?,
+                })
+            })
+            .collect::<Result<Vec<Rule>, SearchError>>()?;
+        Ok(Self {
+            counts: vec![0; rules.len()],
+            rules,
+            pending: Vec::new(),
+            closed: Vec::new(),
+            last_row: None,
+        })
+    }
+
+    /// Returns `true` if any of the steps has a max gap in time.
+    pub fn needs_timestamps(&self) -> bool {
+        self.rules
+            .iter()
+            .any(|rule| rule.steps.iter().any(|step| step.max_ms.is_some()))
+    }
+
+    /// Checks the next row. Rows are expected in ascending order.
+    pub fn check(&mut self, row: u64, timestamp: Option<u64>, line: &str) {
+        self.last_row = Some(row);
+        let forbidden: Vec<Option<u32>> = self
+            .rules
+            .iter()
+            .map(|rule| {
+                rule.forbidden
+                    .iter()
+                    .position(|regex| regex.is_match(line))
+                    .map(|i| i as u32)
+            })
+            .collect();
+        let mut pending = Vec::with_capacity(self.pending.len());
+        for mut instance in std::mem::take(&mut self.pending) {
+            let next = instance.steps.len();
+            let step = &self.rules[instance.rule].steps[next];
+            let last = instance.steps[next - 1];
+            let timeout = step.max_rows.is_some_and(|max| row - last > max)
+                || step
+                    .max_ms
+                    .zip(instance.timestamp)
+                    .zip(timestamp)
+                    .is_some_and(|((max, from), to)| to.saturating_sub(from) > max);
+            if timeout {
+                self.counts[instance.rule] -= 1;
+                self.closed
+                    .push(instance.close(row, Some(SequenceViolation::Timeout(next as u32))));
+            } else if let Some(condition) = forbidden[instance.rule] {
+                self.counts[instance.rule] -= 1;
+                self.closed
+                    .push(instance.close(row, Some(SequenceViolation::Forbidden(condition))));
+            } else if step.regex.is_match(line) {
+                instance.steps.push(row);
+                instance.timestamp = timestamp;
+                if instance.steps.len() == self.rules[instance.rule].steps.len() {
+                    self.counts[instance.rule] -= 1;
+                    self.closed.push(instance.close(row, None));
+                } else {
+                    pending.push(instance);
+                }
+            } else {
+                pending.push(instance);
+            }
+        }
+        self.pending = pending;
+        for (i, rule) in self.rules.iter().enumerate() {
+            if !rule.steps[0].regex.is_match(line) {
+                continue;
+            }
+            let instance = Pending {
+                rule: i,
+                steps: vec![row],
+                timestamp,
+            };
+            if rule.steps.len() == 1 {
+                self.closed.push(instance.close(row, None));
+                continue;
+            }
+            if self.counts[i] == MAX_PENDING_INSTANCES {
+                // Pending instances are ordered, the first one of the rule is the oldest.
+                if let Some(oldest) = self.pending.iter().position(|pending| pending.rule == i) {
+                    let oldest = self.pending.remove(oldest);
+                    let next = oldest.steps.len() as u32;
+                    self.closed
+                        .push(oldest.close(row, Some(SequenceViolation::Overflow(next))));
+                    self.counts[i] -= 1;
+                }
+            }
+            self.counts[i] += 1;
+            self.pending.push(instance);
+        }
+    }
+
+    /// Closes pending instances as unfinished and returns all instances ordered by their
+    /// first rows.
+    pub fn finish(mut self) -> Vec<SequenceInstance> {
+        let end = self.last_row.unwrap_or_default();
+        self.counts.fill(0);
+        for instance in std::mem::take(&mut self.pending) {
+            let next = instance.steps.len() as u32;
+            self.closed
+                .push(instance.close(end, Some(SequenceViolation::Unfinished(next))));
+        }
+        self.closed
+            .sort_by_key(|instance| (instance.rows.start, instance.rule));
+        self.closed
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use stypes::SequenceStep;
+
+    fn condition(value: &str) -> SearchCondition {
+        SearchCondition {
+            value: value.to_owned(),
+            is_regex: false,
+            ignore_case: false,
+            is_word: false,
+        }
+    }
+
+    fn step(value: &str, max_rows: Option<u64>, max_ms: Option<u64>) -> SequenceStep {
+        SequenceStep {
+            condition: condition(value),
+            max_rows,
+            max_ms,
+        }
+    }
+
+    fn check(rules: Vec<SequenceRule>, rows: &[(u64, &str)]) -> Vec<SequenceInstance> {
+        let mut checker = SequenceChecker::new(rules).expect("rules are valid");
+        for (row, (timestamp, line)) in rows.iter().enumerate() {
+            checker.check(row as u64, Some(*timestamp), line);
+        }
+        checker.finish()
+    }
+
+    fn summary(instances: &[SequenceInstance]) -> Vec<(Vec<u64>, u64, Option<SequenceViolation>)> {
+        instances
+            .iter()
+            .map(|i| (i.steps.clone(), i.rows.end, i.violation.clone()))
+            .collect()
+    }
+
+    #[test]
+    fn sequences() {
+        // A is followed by B within 500 ms, and C never happens in between
+        let rule = SequenceRule {
+            steps: vec![step("A", None, None), step("B", None, Some(500))],
+            forbidden: vec![condition("C")],
+        };
+        let instances = check(
+            vec![rule],
+            &[
+                (1_000, "A"),
+                (1_200, "x"),
+                (1_400, "B"),
+                (2_000, "A"),
+                (2_100, "C"),
+                (2_200, "B"),
+                (3_000, "A"),
+                (3_600, "B"),
+                (4_000, "A"),
+            ],
+        );
+        assert_eq!(
+            summary(&instances),
+            vec![
+                (vec![0, 2], 2, None),
+                (vec![3], 4, Some(SequenceViolation::Forbidden(0))),
+                (vec![6], 7, Some(SequenceViolation::Timeout(1))),
+                (vec![8], 8, Some(SequenceViolation::Unfinished(1))),
+            ]
+        );
+    }
+
+    #[test]
+    fn gaps_in_rows() {
+        let rule = SequenceRule {
+            steps: vec![
+                step("A", None, None),
+                step("B", Some(2), None),
+                step("C", Some(1), None),
+            ],
+            forbidden: vec![],
+        };
+        let instances = check(
+            vec![rule],
+            &[(0, "A"), (0, "x"), (0, "B A"), (0, "C"), (0, "x"), (0, "B")],
+        );
+        // B follows the second A only after 3 rows
+        assert_eq!(
+            summary(&instances),
+            vec![
+                (vec![0, 2, 3], 3, None),
+                (vec![2], 5, Some(SequenceViolation::Timeout(1))),
+            ]
+        );
+        let invalid = |rules| SequenceChecker::new(rules).is_err();
+        assert!(invalid(vec![]));
+        assert!(invalid(vec![SequenceRule {
+            steps: vec![],
+            forbidden: vec![]
+        }]));
+        assert!(invalid(vec![SequenceRule {
+            steps: vec![SequenceStep {
+                condition: SearchCondition {
+                    value: String::from("(unclosed"),
+                    is_regex: true,
+                    ignore_case: false,
+                    is_word: false,
+                },
+                max_rows: None,
+                max_ms: None,
+            }],
+            forbidden: vec![]
+        }]));
+    }
+
+    #[test]
+    fn pending_limit() {
+        let rule = SequenceRule {
+            steps: vec![step("A", None, None), step("B", None, None)],
+            forbidden: vec![],
+        };
+        let extra = 500;
+        let mut checker = SequenceChecker::new(vec![rule]).expect("rules are valid");
+        for row in 0..(MAX_PENDING_INSTANCES + extra) as u64 {
+            checker.check(row, None, "A");
+        }
+        let last = (MAX_PENDING_INSTANCES + extra) as u64;
+        checker.check(last, None, "B");
+        assert_eq!(checker.pending.len(), 0);
+        let instances = checker.finish();
+        assert_eq!(instances.len(), MAX_PENDING_INSTANCES + extra);
+        // The oldest instances are dropped by the new ones.
+        for (row, instance) in instances.iter().enumerate().take(extra) {
+            assert_eq!(instance.steps, vec![row as u64]);
+            assert_eq!(instance.rows.end, (MAX_PENDING_INSTANCES + row) as u64);
+            assert_eq!(instance.violation, Some(SequenceViolation::Overflow(1)));
+        }
+        assert!(
+            instances[extra..]
+                .iter()
+                .all(|instance| instance.violation.is_none() && instance.steps[1] == last)
+        );
+    }
+}
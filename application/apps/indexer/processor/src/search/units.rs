@@ -0,0 +1,71 @@
+/// Parses a value printed in logs into a number. Supported are:
+/// - plain numbers: `12`, `-0.5`, `1e3`
+/// - hexadecimal numbers: `0x1F`
+/// - percents: `12%` (the sign is dropped)
+/// - durations, converted into milliseconds: `ns`, `us`/`µs`, `ms`, `s`/`sec`, `m`/`min`, `h`
+/// - sizes, converted into bytes: `B`, decimal `KB`, `MB`, `GB`, `TB` and binary `KiB`,
+///   `MiB`, `GiB`, `TiB`
+///
+/// Units are case insensitive and can be separated from the number by whitespaces.
+///
+/// # Returns
+///
+/// `None` if the value isn't a number or has an unknown unit
+pub fn parse_value(input: &str) -> Option<f64> {
+    let input = input.trim();
+    if let Some(hex) = input
+        .strip_prefix("0x")
+        .or_else(|| input.strip_prefix("0X"))
+    {
+        return u64::from_str_radix(hex, 16).ok().map(|value| value as f64);
+    }
+    let split = input
+        .find(|c: char| !(c.is_ascii_digit() || matches!(c, '.' | '-' | '+' | 'e' | 'E')))
+        .unwrap_or(input.len());
+    let (number, unit) = input.split_at(split);
+    Some(number.parse::<f64>().ok()? * multiplier(unit.trim())?)
+}
+
+fn multiplier(unit: &str) -> Option<f64> {
+    const KIB: f64 = 1024.0;
+    Some(match unit.to_lowercase().as_str() {
+        "" | "%" | "b" | "ms" => 1.0,
+        "ns" => 1e-6,
+        "us" | "µs" | "μs" => 1e-3,
+        "s" | "sec" => 1e3,
+        "m" | "min" => 60e3,
+        "h" => 3600e3,
+        "kb" => 1e3,
+        "mb" => 1e6,
+        "gb" => 1e9,
+        "tb" => 1e12,
+        "kib" => KIB,
+        "mib" => KIB.powi(2),
+        "gib" => KIB.powi(3),
+        "tib" => KIB.powi(4),
+        _ => return None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn values_with_units() {
+        assert_eq!(parse_value("12"), Some(12.0));
+        assert_eq!(parse_value(" -0.5 "), Some(-0.5));
+        assert_eq!(parse_value("1e3"), Some(1000.0));
+        assert_eq!(parse_value("0x1F"), Some(31.0));
+        assert_eq!(parse_value("12%"), Some(12.0));
+        assert_eq!(parse_value("12ms"), Some(12.0));
+        assert_eq!(parse_value("1.2s"), Some(1200.0));
+        assert_eq!(parse_value("1.5 min"), Some(90_000.0));
+        assert_eq!(parse_value("250us"), Some(0.25));
+        assert_eq!(parse_value("340MB"), Some(340e6));
+        assert_eq!(parse_value("2KiB"), Some(2048.0));
+        assert_eq!(parse_value("71.5C"), None);
+        assert_eq!(parse_value("0xZZ"), None);
+        assert_eq!(parse_value("abc"), None);
+    }
+}
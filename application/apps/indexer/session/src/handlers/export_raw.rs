@@ -3,6 +3,7 @@ use indexer_base::config::IndexSection;
 use log::debug;
 use parsers::{
     LogMessage, Parser,
+    can::{CanParser, dbc::DbcMetadata},
     dlt::{DltParser, fmt::FormatOptions},
     someip::SomeipParser,
     text::StringTokenizer,
@@ -12,15 +13,14 @@ use processor::export::{ExportError, export_raw};
 use sources::{
     ByteSource,
     binary::{
+        blf::BlfByteSource,
+        compressed::DecompressedFile,
         pcap::{legacy::PcapLegacyByteSource, ng::PcapngByteSource},
         raw::BinaryByteSource,
     },
     producer::MessageProducer,
 };
-use std::{
-    fs::File,
-    path::{Path, PathBuf},
-};
+use std::path::{Path, PathBuf};
 use tokio_util::sync::CancellationToken;
 
 pub async fn execute_export(
@@ -82,7 +82,8 @@ async fn assing_source(
     read_to_end: bool,
     cancel: &CancellationToken,
 ) -> Result<Option<usize>, stypes::NativeError> {
-    let reader = File::open(src).map_err(|e| stypes::NativeError {
+    // Raw data of compressed files and archive entries is exported decompressed.
+    let reader = DecompressedFile::open(src).map_err(|e| stypes::NativeError {
         severity: stypes::Severity::ERROR,
         kind: stypes::NativeErrorKind::Io,
         message: Some(format!("Fail open file {}: {}", src.to_string_lossy(), e)),
@@ -121,6 +122,17 @@ async fn assing_source(
             )
             .await
         }
+        stypes::FileFormat::Blf => {
+            export(
+                dest,
+                parser,
+                BlfByteSource::new(reader),
+                sections,
+                read_to_end,
+                cancel,
+            )
+            .await
+        }
     }
 }
 
@@ -168,6 +180,13 @@ async fn export<S: ByteSource>(
             let producer = MessageProducer::new(StringTokenizer {}, source);
             export_runner(producer, dest, sections, read_to_end, true, cancel).await
         }
+        stypes::ParserType::Can(settings) => {
+            let dbc_metadata = settings.dbc_file_paths.as_ref().and_then(|paths| {
+                DbcMetadata::from_dbc_files(paths.iter().map(PathBuf::from).collect())
+            });
+            let producer = MessageProducer::new(CanParser::new(dbc_metadata), source);
+            export_runner(producer, dest, sections, read_to_end, false, cancel).await
+        }
     }
 }
 
@@ -0,0 +1,60 @@
+use crate::{
+    operations::{OperationAPI, OperationResult},
+    state::SessionStateAPI,
+};
+use log::debug;
+use processor::search::searchers;
+use tokio::task;
+
+/// Compares all rows of the session file to find repeated rows after switching to a folding
+/// mode. Rows added later are compared by the state on updates of the session file.
+pub async fn fold(operation_api: &OperationAPI, state: SessionStateAPI) -> OperationResult<()> {
+    debug!("RUST: Folding of rows is requested");
+    let (rows, read_bytes) = state.get_stream_len().await?;
+    let mut holder = state.get_folds_holder(operation_api.id()).await?;
+    let cancel = operation_api.cancellation_token();
+    let folded = task::spawn_blocking(move || {
+        let result = searchers::folds::search(&mut holder, rows, read_bytes, cancel);
+        (holder, result)
+    })
+    .await;
+    let (holder, result) = match folded {
+        Ok(folded) => folded,
+        Err(err) => {
+            state.set_folds_holder(operation_api.id(), None).await?;
+            return Err(stypes::NativeError {
+                severity: stypes::Severity::ERROR,
+                kind: stypes::NativeErrorKind::ComputationFailed,
+                message: Some(format!("Fail to fold rows: {err}")),
+            });
+        }
+    };
+    if operation_api.cancellation_token().is_cancelled() {
+        // Rows aren't folded until the mode is set again.
+        state.set_folds_holder(operation_api.id(), None).await?;
+        return Ok(None);
+    }
+    match result {
+        Ok((processed, repeats)) => {
+            state
+                .set_folds_holder(
+                    operation_api.id(),
+                    Some((
+                        holder,
+                        processed.start as u64..processed.end as u64,
+                        repeats,
+                    )),
+                )
+                .await?;
+            Ok(Some(()))
+        }
+        Err(err) => {
+            state.set_folds_holder(operation_api.id(), None).await?;
+            Err(stypes::NativeError {
+                severity: stypes::Severity::ERROR,
+                kind: stypes::NativeErrorKind::OperationSearch,
+                message: Some(format!("Fail to fold rows: {err}")),
+            })
+        }
+    }
+}
@@ -1,7 +1,11 @@
 pub mod export_raw;
 pub mod extract;
+pub mod folds;
 pub mod observe;
 mod observing;
 pub mod search;
 pub mod search_values;
+pub mod sequences;
 pub mod sleep;
+pub mod templates;
+pub mod timestamps;
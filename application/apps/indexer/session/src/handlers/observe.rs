@@ -15,6 +15,11 @@ pub async fn start_observing(
     if let stypes::ParserType::Dlt(ref mut settings) = options.parser {
         settings.load_fibex_metadata();
     };
+    let filter = options
+        .network_filter
+        .as_ref()
+        .map(observing::source_filter)
+        .transpose()?;
     if let Err(err) = state.add_executed_observe(options.clone()).await {
         error!("Fail to store observe options: {err:?}");
     }
@@ -40,6 +45,8 @@ pub async fn start_observing(
                         state,
                         &[(uuid.clone(), file_origin.clone(), filename.clone())],
                         &options.parser,
+                        filter.as_ref(),
+                        None,
                     )
                     .await
                 }
@@ -51,6 +58,7 @@ pub async fn start_observing(
                         file_origin,
                         filename,
                         &options.parser,
+                        filter.as_ref(),
                     )
                     .await
                 }
@@ -64,7 +72,15 @@ pub async fn start_observing(
                     message: Some(String::from("No files are defined for Concat operation")),
                 })
             } else {
-                observing::concat::concat_files(operation_api, state, files, &options.parser).await
+                observing::concat::concat_files(
+                    operation_api,
+                    state,
+                    files,
+                    &options.parser,
+                    filter.as_ref(),
+                    options.order_by_time.as_ref(),
+                )
+                .await
             }
         }
         stypes::ObserveOrigin::Stream(uuid, transport) => {
@@ -78,5 +94,15 @@ pub async fn start_observing(
             )
             .await
         }
+        stypes::ObserveOrigin::Folder(_, watch) => {
+            observing::folder::observe_folder(
+                operation_api,
+                state,
+                watch,
+                &options.parser,
+                filter.as_ref(),
+            )
+            .await
+        }
     }
 }
@@ -1,12 +1,19 @@
 use crate::{
     operations::{OperationAPI, OperationResult},
     state::SessionStateAPI,
+    unbound::commands::chronology::files_chronology,
 };
-use sources::binary::{
-    pcap::{legacy::PcapLegacyByteSource, ng::PcapngByteSource},
-    raw::BinaryByteSource,
+use sources::{
+    SourceFilter,
+    binary::{
+        blf::BlfByteSource,
+        compressed::DecompressedFile,
+        pcap::{legacy::PcapLegacyByteSource, ng::PcapngByteSource},
+        raw::BinaryByteSource,
+    },
 };
-use std::{fs::File, path::PathBuf};
+use std::path::PathBuf;
+use tokio::task;
 
 #[allow(clippy::type_complexity)]
 pub async fn concat_files(
@@ -14,7 +21,13 @@ pub async fn concat_files(
     state: SessionStateAPI,
     files: &[(String, stypes::FileFormat, PathBuf)],
     parser: &stypes::ParserType,
+    filter: Option<&SourceFilter>,
+    order: Option<&stypes::TimestampSettings>,
 ) -> OperationResult<()> {
+    let files = match order {
+        Some(settings) => order_by_time(&operation_api, files, settings).await?,
+        None => files.to_vec(),
+    };
     for file in files.iter() {
         let (uuid, _file_type, _filename) = file;
         state.add_source(uuid).await?;
@@ -30,7 +43,8 @@ pub async fn concat_files(
                 uuid,
             )),
         })?;
-        let input_file = File::open(filename).map_err(|e| stypes::NativeError {
+        // Compressed files and archive entries are decompressed while reading.
+        let input_file = DecompressedFile::open(filename).map_err(|e| stypes::NativeError {
             severity: stypes::Severity::ERROR,
             kind: stypes::NativeErrorKind::Io,
             message: Some(format!(
@@ -47,6 +61,7 @@ pub async fn concat_files(
                     BinaryByteSource::new(input_file),
                     source_id,
                     parser,
+                    filter,
                     None,
                     None,
                 )
@@ -59,6 +74,7 @@ pub async fn concat_files(
                     PcapLegacyByteSource::new(input_file)?,
                     source_id,
                     parser,
+                    filter,
                     None,
                     None,
                 )
@@ -71,6 +87,20 @@ pub async fn concat_files(
                     PcapngByteSource::new(input_file)?,
                     source_id,
                     parser,
+                    filter,
+                    None,
+                    None,
+                )
+                .await?
+            }
+            stypes::FileFormat::Blf => {
+                super::run_source(
+                    operation_api.clone(),
+                    state.clone(),
+                    BlfByteSource::new(input_file),
+                    source_id,
+                    parser,
+                    filter,
                     None,
                     None,
                 )
@@ -83,6 +113,7 @@ pub async fn concat_files(
                     BinaryByteSource::new(input_file),
                     source_id,
                     parser,
+                    filter,
                     None,
                     None,
                 )
@@ -92,3 +123,56 @@ pub async fn concat_files(
     }
     Ok(Some(()))
 }
+
+/// Orders the files chronologically by the timestamps of their first messages and reports
+/// overlapping files as warnings. The files keep their order if the operation is cancelled.
+async fn order_by_time(
+    operation_api: &OperationAPI,
+    files: &[(String, stypes::FileFormat, PathBuf)],
+    settings: &stypes::TimestampSettings,
+) -> Result<Vec<(String, stypes::FileFormat, PathBuf)>, stypes::NativeError> {
+    let paths = files
+        .iter()
+        .map(|(_, _, filename)| filename.to_string_lossy().to_string())
+        .collect();
+    let settings = settings.clone();
+    let cancel = operation_api.cancellation_token();
+    let chronology =
+        match task::spawn_blocking(move || files_chronology(paths, Some(&settings), &cancel)).await
+        {
+            Ok(chronology) => chronology,
+            Err(err) => Err(err.to_string()),
+        }
+        .map_err(|err| stypes::NativeError {
+            severity: stypes::Severity::ERROR,
+            kind: stypes::NativeErrorKind::Io,
+            message: Some(format!("Fail to order files by time: {err}")),
+        })?;
+    let Some(chronology) = chronology else {
+        return Ok(files.to_vec());
+    };
+    for overlap in chronology.overlaps.iter() {
+        operation_api.emit(stypes::CallbackEvent::Progress {
+            uuid: operation_api.id(),
+            progress: stypes::Progress::Notification(stypes::Notification {
+                severity: stypes::Severity::WARNING,
+                content: format!(
+                    "Files {} and {} overlap by {} ms",
+                    overlap.earlier, overlap.later, overlap.duration
+                ),
+                line: None,
+            }),
+        });
+    }
+    let mut unordered = files.to_vec();
+    Ok(chronology
+        .files
+        .iter()
+        .filter_map(|range| {
+            unordered
+                .iter()
+                .position(|(_, _, filename)| filename.to_string_lossy() == range.path)
+                .map(|pos| unordered.remove(pos))
+        })
+        .collect())
+}
@@ -1,17 +1,29 @@
 use crate::{
     operations::{OperationAPI, OperationResult},
-    state::SessionStateAPI,
+    state::{SessionStateAPI, copy_linked},
     tail,
 };
-use sources::binary::{
-    pcap::{legacy::PcapLegacyByteSource, ng::PcapngByteSource},
-    raw::BinaryByteSource,
+use log::{debug, warn};
+use sources::{
+    SourceFilter,
+    binary::{
+        blf::BlfByteSource,
+        compressed::{Container, DecompressedFile, ReadProgress},
+        pcap::{legacy::PcapLegacyByteSource, ng::PcapngByteSource},
+        raw::BinaryByteSource,
+    },
 };
 use std::{fs::File, path::Path};
 use tokio::{
     join, select,
     sync::mpsc::{Receiver, Sender, channel},
+    task,
+    time::{Duration, interval},
 };
+use tokio_util::sync::CancellationToken;
+
+/// Interval of progress reports while reading compressed files.
+const PROGRESS_INTERVAL_MS: u64 = 500;
 
 #[allow(clippy::type_complexity)]
 pub async fn observe_file(
@@ -21,15 +33,30 @@ pub async fn observe_file(
     file_format: &stypes::FileFormat,
     filename: &Path,
     parser: &stypes::ParserType,
+    filter: Option<&SourceFilter>,
 ) -> OperationResult<()> {
     let source_id = state.add_source(uuid).await?;
-    let (tx_tail, mut rx_tail): (
-        Sender<Result<(), tail::Error>>,
-        Receiver<Result<(), tail::Error>>,
+    if !Container::detect(filename)?.is_plain() {
+        return observe_decompressed_file(
+            operation_api,
+            state,
+            source_id,
+            file_format,
+            filename,
+            parser,
+            filter,
+        )
+        .await;
+    }
+    let (tx_tail, rx_tail): (
+        Sender<Result<tail::Update, tail::Error>>,
+        Receiver<Result<tail::Update, tail::Error>>,
     ) = channel(1);
     match file_format {
         stypes::FileFormat::Binary => {
             let source = BinaryByteSource::new(input_file(filename)?);
+            let follower =
+                tail::Follower::new(rx_tail, || Ok(BinaryByteSource::new(input_file(filename)?)));
             let (_, listening) = join!(
                 tail::track(filename, tx_tail, operation_api.cancellation_token()),
                 super::run_source(
@@ -38,14 +65,18 @@ pub async fn observe_file(
                     source,
                     source_id,
                     parser,
+                    filter,
                     None,
-                    Some(rx_tail)
+                    Some(follower)
                 )
             );
             listening
         }
         stypes::FileFormat::PcapLegacy => {
             let source = PcapLegacyByteSource::new(input_file(filename)?)?;
+            let follower = tail::Follower::new(rx_tail, || {
+                Ok(PcapLegacyByteSource::new(input_file(filename)?)?)
+            });
             let (_, listening) = join!(
                 tail::track(filename, tx_tail, operation_api.cancellation_token()),
                 super::run_source(
@@ -54,14 +85,18 @@ pub async fn observe_file(
                     source,
                     source_id,
                     parser,
+                    filter,
                     None,
-                    Some(rx_tail)
+                    Some(follower)
                 )
             );
             listening
         }
         stypes::FileFormat::PcapNG => {
             let source = PcapngByteSource::new(input_file(filename)?)?;
+            let follower = tail::Follower::new(rx_tail, || {
+                Ok(PcapngByteSource::new(input_file(filename)?)?)
+            });
             let (_, listening) = join!(
                 tail::track(filename, tx_tail, operation_api.cancellation_token()),
                 super::run_source(
@@ -70,51 +105,370 @@ pub async fn observe_file(
                     source,
                     source_id,
                     parser,
+                    filter,
                     None,
-                    Some(rx_tail)
+                    Some(follower)
+                )
+            );
+            listening
+        }
+        stypes::FileFormat::Blf => {
+            let source = BlfByteSource::new(input_file(filename)?);
+            let follower =
+                tail::Follower::new(rx_tail, || Ok(BlfByteSource::new(input_file(filename)?)));
+            let (_, listening) = join!(
+                tail::track(filename, tx_tail, operation_api.cancellation_token()),
+                super::run_source(
+                    operation_api,
+                    state,
+                    source,
+                    source_id,
+                    parser,
+                    filter,
+                    None,
+                    Some(follower)
                 )
             );
             listening
         }
         stypes::FileFormat::Text => {
+            // The handle keeps the content of the linked file readable after it's rotated.
+            let linked = input_file(filename)?;
             state.set_session_file(Some(filename.to_path_buf())).await?;
+            let mut triggered = super::Triggered::new(state.get_triggers().await?);
             // Grab main file content
-            state.update_session(source_id).await?;
+            let stop =
+                super::update_linked(&operation_api, &state, source_id, &mut triggered).await?;
             operation_api.processing();
             // Confirm: main file content has been read
             state.file_read().await?;
+            if stop {
+                debug!("observe is stopped by a trigger rule");
+                operation_api.cancel();
+                return Ok(None);
+            }
             // Switching to tail
             let cancel = operation_api.cancellation_token();
             let (result, tracker) = join!(
                 async {
-                    let result = select! {
-                        res = async move {
-                            while let Some(update) = rx_tail.recv().await {
-                                update.map_err(|err| stypes::NativeError {
-                                    severity: stypes::Severity::ERROR,
-                                    kind: stypes::NativeErrorKind::Interrupted,
-                                    message: Some(err.to_string()),
-                                })?;
-                                state.update_session(source_id).await?;
-                            }
-                            Ok(())
-                        } => res,
-                        _ = cancel.cancelled() => Ok(())
-                    };
-                    result
+                    select! {
+                        res = follow_linked_file(
+                            operation_api.clone(),
+                            state,
+                            source_id,
+                            filename,
+                            parser,
+                            linked,
+                            rx_tail,
+                            triggered,
+                        ) => res,
+                        _ = cancel.cancelled() => Ok(None)
+                    }
                 },
                 tail::track(filename, tx_tail, operation_api.cancellation_token()),
             );
-            result
-                .and_then(|_| {
-                    tracker.map_err(|e| stypes::NativeError {
+            result.and_then(|result| {
+                tracker
+                    .map_err(|e| stypes::NativeError {
                         severity: stypes::Severity::ERROR,
                         kind: stypes::NativeErrorKind::Interrupted,
                         message: Some(format!("Tailing error: {e}")),
                     })
-                })
-                .map(|_| None)
+                    .map(|_| result)
+            })
+        }
+    }
+}
+
+/// Follows a text file linked with the session. Appended content is read from the linked file
+/// directly. Once the file is truncated or rotated, the content of the session is moved into a
+/// generated session file and the content of the new file is parsed and written into the
+/// session as a new segment of the same source. The rows of a truncated file are kept as
+/// empty lines, because their content isn't available anymore. Trigger rules are checked on
+/// appended rows.
+#[allow(clippy::too_many_arguments)]
+async fn follow_linked_file(
+    operation_api: OperationAPI,
+    state: SessionStateAPI,
+    source_id: u16,
+    filename: &Path,
+    parser: &stypes::ParserType,
+    linked: File,
+    mut rx_tail: Receiver<Result<tail::Update, tail::Error>>,
+    mut triggered: super::Triggered,
+) -> OperationResult<()> {
+    while let Some(update) = rx_tail.recv().await {
+        let update = update.map_err(|err| stypes::NativeError {
+            severity: stypes::Severity::ERROR,
+            kind: stypes::NativeErrorKind::Interrupted,
+            message: Some(err.to_string()),
+        })?;
+        if update == tail::Update::Appended {
+            if super::update_linked(&operation_api, &state, source_id, &mut triggered).await? {
+                debug!("observe is stopped by a trigger rule");
+                operation_api.cancel();
+                return Ok(None);
+            }
+            continue;
+        }
+        let truncated = update == tail::Update::Truncated;
+        let (rows, bytes) = state.get_stream_len().await?;
+        let copy = task::spawn_blocking(move || copy_linked(linked, rows, bytes, truncated))
+            .await
+            .map_err(|err| stypes::NativeError {
+                severity: stypes::Severity::ERROR,
+                kind: stypes::NativeErrorKind::Io,
+                message: Some(format!("Fail to copy linked file: {err}")),
+            })??;
+        state.unlink_session_file(source_id, copy).await?;
+        let (pos, _) = state.get_stream_len().await?;
+        operation_api.emit(stypes::CallbackEvent::FileRotated {
+            source_id,
+            pos,
+            truncated,
+        });
+        let source = BinaryByteSource::new(input_file(filename)?);
+        let follower =
+            tail::Follower::new(rx_tail, || Ok(BinaryByteSource::new(input_file(filename)?)));
+        return super::run_source(
+            operation_api,
+            state,
+            source,
+            source_id,
+            parser,
+            None,
+            None,
+            Some(follower),
+        )
+        .await;
+    }
+    Ok(None)
+}
+
+/// Tails the file from its start until `shutdown` is cancelled or the file is rotated; the
+/// token is cancelled once tailing ends. Unlike [`observe_file`], the file isn't linked with
+/// the session, text files are parsed like binary files. If the file is truncated, its new
+/// content continues as a new segment of the source. If it's rotated, the rest of the renamed
+/// file is read and tailing ends, because the new file is picked up by the caller.
+#[allow(clippy::type_complexity)]
+pub async fn tail_file(
+    operation_api: OperationAPI,
+    state: SessionStateAPI,
+    source_id: u16,
+    file_format: &stypes::FileFormat,
+    filename: &Path,
+    parser: &stypes::ParserType,
+    filter: Option<&SourceFilter>,
+    shutdown: CancellationToken,
+) -> OperationResult<()> {
+    let (tx_tail, mut rx_tail): (
+        Sender<Result<tail::Update, tail::Error>>,
+        Receiver<Result<tail::Update, tail::Error>>,
+    ) = channel(1);
+    let (tx_follow, rx_follow) = channel(1);
+    let tracking = async {
+        let forwarding = async {
+            while let Some(update) = rx_tail.recv().await {
+                if matches!(update, Ok(tail::Update::Rotated)) {
+                    shutdown.cancel();
+                } else if tx_follow.send(update).await.is_err() {
+                    break;
+                }
+            }
+            // Follower is done as soon as updates aren't forwarded anymore.
+            drop(tx_follow);
+        };
+        let (tracked, _) = join!(tail::track(filename, tx_tail, shutdown.clone()), forwarding);
+        match tracked {
+            // Sending updates fails if listening is done already.
+            Err(err) if !shutdown.is_cancelled() => {
+                warn!("Fail to track {}: {err}", filename.display());
+            }
+            _ => {}
+        }
+        shutdown.cancel();
+    };
+    let listening = async {
+        match file_format {
+            stypes::FileFormat::Binary | stypes::FileFormat::Text => {
+                let source = BinaryByteSource::new(input_file(filename)?);
+                let follower = tail::Follower::new(rx_follow, || {
+                    Ok(BinaryByteSource::new(input_file(filename)?))
+                });
+                super::run_source(
+                    operation_api,
+                    state,
+                    source,
+                    source_id,
+                    parser,
+                    filter,
+                    None,
+                    Some(follower),
+                )
+                .await
+            }
+            stypes::FileFormat::PcapLegacy => {
+                let source = PcapLegacyByteSource::new(input_file(filename)?)?;
+                let follower = tail::Follower::new(rx_follow, || {
+                    Ok(PcapLegacyByteSource::new(input_file(filename)?)?)
+                });
+                super::run_source(
+                    operation_api,
+                    state,
+                    source,
+                    source_id,
+                    parser,
+                    filter,
+                    None,
+                    Some(follower),
+                )
+                .await
+            }
+            stypes::FileFormat::PcapNG => {
+                let source = PcapngByteSource::new(input_file(filename)?)?;
+                let follower = tail::Follower::new(rx_follow, || {
+                    Ok(PcapngByteSource::new(input_file(filename)?)?)
+                });
+                super::run_source(
+                    operation_api,
+                    state,
+                    source,
+                    source_id,
+                    parser,
+                    filter,
+                    None,
+                    Some(follower),
+                )
+                .await
+            }
+            stypes::FileFormat::Blf => {
+                let source = BlfByteSource::new(input_file(filename)?);
+                let follower = tail::Follower::new(rx_follow, || {
+                    Ok(BlfByteSource::new(input_file(filename)?))
+                });
+                super::run_source(
+                    operation_api,
+                    state,
+                    source,
+                    source_id,
+                    parser,
+                    filter,
+                    None,
+                    Some(follower),
+                )
+                .await
+            }
+        }
+    };
+    let (_, listening) = join!(tracking, async {
+        let result = listening.await;
+        // Tracking isn't needed anymore once listening is done, e.g. because it failed.
+        shutdown.cancel();
+        result
+    });
+    listening
+}
+
+/// Observes a compressed file or an entry of an archive. The data is decompressed while it's
+/// read, so text files are parsed like binary files instead of being linked with the session.
+/// Tailing isn't supported.
+async fn observe_decompressed_file(
+    operation_api: OperationAPI,
+    state: SessionStateAPI,
+    source_id: u16,
+    file_format: &stypes::FileFormat,
+    filename: &Path,
+    parser: &stypes::ParserType,
+    filter: Option<&SourceFilter>,
+) -> OperationResult<()> {
+    let input = DecompressedFile::open(filename)?;
+    let progress = input.progress();
+    let listening = async {
+        match file_format {
+            stypes::FileFormat::Binary | stypes::FileFormat::Text => {
+                let source = BinaryByteSource::new(input);
+                super::run_source(
+                    operation_api.clone(),
+                    state,
+                    source,
+                    source_id,
+                    parser,
+                    filter,
+                    None,
+                    None,
+                )
+                .await
+            }
+            stypes::FileFormat::PcapLegacy => {
+                let source = PcapLegacyByteSource::new(input)?;
+                super::run_source(
+                    operation_api.clone(),
+                    state,
+                    source,
+                    source_id,
+                    parser,
+                    filter,
+                    None,
+                    None,
+                )
+                .await
+            }
+            stypes::FileFormat::PcapNG => {
+                let source = PcapngByteSource::new(input)?;
+                super::run_source(
+                    operation_api.clone(),
+                    state,
+                    source,
+                    source_id,
+                    parser,
+                    filter,
+                    None,
+                    None,
+                )
+                .await
+            }
+            stypes::FileFormat::Blf => {
+                let source = BlfByteSource::new(input);
+                super::run_source(
+                    operation_api.clone(),
+                    state,
+                    source,
+                    source_id,
+                    parser,
+                    filter,
+                    None,
+                    None,
+                )
+                .await
+            }
+        }
+    };
+    select! {
+        result = listening => result,
+        _ = report_progress(&operation_api, progress) => Ok(None),
+    }
+}
+
+/// Reports the progress of reading the compressed file periodically. The progress is based
+/// on the compressed bytes, the future never completes.
+async fn report_progress(operation_api: &OperationAPI, progress: ReadProgress) {
+    let mut ticker = interval(Duration::from_millis(PROGRESS_INTERVAL_MS));
+    let mut reported = None;
+    loop {
+        ticker.tick().await;
+        let read = progress.read();
+        if reported == Some(read) {
+            continue;
         }
+        reported = Some(read);
+        operation_api.emit(stypes::CallbackEvent::Progress {
+            uuid: operation_api.id(),
+            progress: stypes::Progress::Ticks(stypes::Ticks {
+                count: read,
+                state: Some(String::from("Decompressing")),
+                total: Some(progress.total()),
+            }),
+        });
     }
 }
 
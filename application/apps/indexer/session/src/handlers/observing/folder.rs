@@ -0,0 +1,352 @@
+use crate::{
+    operations::{OperationAPI, OperationResult},
+    state::SessionStateAPI,
+    tail,
+};
+use log::warn;
+use sources::{SourceFilter, binary::compressed::Container};
+use std::{
+    cmp::Ordering, collections::HashSet, fs, io, iter::Peekable, path::PathBuf, str::Chars,
+    time::SystemTime,
+};
+use tokio::{
+    join, select,
+    sync::mpsc::UnboundedReceiver,
+    time::{Duration, Interval, MissedTickBehavior, interval},
+};
+use tokio_util::sync::CancellationToken;
+
+/// Interval of checking the folder for new files. Notifications of the file system only speed
+/// up detecting new files, because they aren't available everywhere.
+const SCANNING_INTERVAL_MS: u64 = 1000;
+
+/// Identity of a file. Files keep their identity while they are renamed by a logger, so
+/// rotated files aren't read twice.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+enum FileKey {
+    Id(u128),
+    Path(PathBuf),
+}
+
+#[derive(Debug)]
+struct MatchedFile {
+    path: PathBuf,
+    name: String,
+    key: FileKey,
+    modified: Option<SystemTime>,
+}
+
+impl MatchedFile {
+    /// The file is registered as a source with its path as alias.
+    fn alias(&self) -> String {
+        self.path.to_string_lossy().to_string()
+    }
+}
+
+/// Observes a folder with rotating log files. Existing files are concatenated from the oldest
+/// to the newest one, then the newest file is tailed. Once a newer file appears (or the tailed
+/// file is rotated), the rest of the tailed file is read and the newer file is tailed instead.
+/// Each file gets its own source.
+///
+/// Rotation by renaming files is recognized by the identity of the files. Files which are
+/// copied and truncated by the logger are new files and their content is read again.
+pub async fn observe_folder(
+    operation_api: OperationAPI,
+    state: SessionStateAPI,
+    watch: &stypes::FolderWatch,
+    parser: &stypes::ParserType,
+    filter: Option<&SourceFilter>,
+) -> OperationResult<()> {
+    let cancel = operation_api.cancellation_token();
+    let pattern = watch.pattern.clone();
+    // The watcher stops watching when it's dropped.
+    let (_watcher, mut rx_notification) = tail::watch_folder(&watch.path, move |path| {
+        path.file_name()
+            .is_some_and(|name| matches_pattern(&pattern, &name.to_string_lossy()))
+    })
+    .unzip();
+    let mut ticker = interval(Duration::from_millis(SCANNING_INTERVAL_MS));
+    ticker.set_missed_tick_behavior(MissedTickBehavior::Delay);
+    let mut observed: HashSet<FileKey> = HashSet::new();
+    loop {
+        let files = matching_files(watch).map_err(|err| stypes::NativeError {
+            severity: stypes::Severity::ERROR,
+            kind: stypes::NativeErrorKind::Io,
+            message: Some(format!(
+                "Fail to read folder {}: {err}",
+                watch.path.to_string_lossy()
+            )),
+        })?;
+        let Some((newest, older)) = files.split_last() else {
+            if !changed(&cancel, &mut ticker, &mut rx_notification).await {
+                break;
+            }
+            continue;
+        };
+        let pending = older
+            .iter()
+            .filter(|file| observed.insert(file.key.clone()))
+            .map(|file| (file.alias(), watch.format.clone(), file.path.clone()))
+            .collect::<Vec<(String, stypes::FileFormat, PathBuf)>>();
+        if !pending.is_empty() {
+            super::concat::concat_files(
+                operation_api.clone(),
+                state.clone(),
+                &pending,
+                parser,
+                filter,
+                None,
+            )
+            .await?;
+        }
+        if cancel.is_cancelled() {
+            break;
+        }
+        if !observed.insert(newest.key.clone()) {
+            // The newest file was read already, waiting for a new one.
+            if !changed(&cancel, &mut ticker, &mut rx_notification).await {
+                break;
+            }
+            continue;
+        }
+        if !Container::detect(&newest.path)?.is_plain() {
+            // Compressed files are rotated already, they don't grow anymore.
+            super::concat::concat_files(
+                operation_api.clone(),
+                state.clone(),
+                &[(newest.alias(), watch.format.clone(), newest.path.clone())],
+                parser,
+                filter,
+                None,
+            )
+            .await?;
+            continue;
+        }
+        let source_id = state.add_source(&newest.alias()).await?;
+        let shutdown = cancel.child_token();
+        let (tailing, _) = join!(
+            super::file::tail_file(
+                operation_api.clone(),
+                state.clone(),
+                source_id,
+                &watch.format,
+                &newest.path,
+                parser,
+                filter,
+                shutdown.clone(),
+            ),
+            async {
+                // Tailing is stopped as soon as a newer file appears.
+                while changed(&shutdown, &mut ticker, &mut rx_notification).await {
+                    match matching_files(watch) {
+                        Ok(files) if files.last().is_some_and(|file| file.key != newest.key) => {
+                            shutdown.cancel();
+                        }
+                        Ok(_) => {}
+                        Err(err) => {
+                            warn!("Fail to read folder {}: {err}", watch.path.display());
+                        }
+                    }
+                }
+            }
+        );
+        tailing?;
+        if cancel.is_cancelled() {
+            break;
+        }
+    }
+    Ok(None)
+}
+
+/// Waits for the next check of the folder. Returns `false` if waiting was cancelled.
+async fn changed(
+    cancel: &CancellationToken,
+    ticker: &mut Interval,
+    rx_notification: &mut Option<UnboundedReceiver<()>>,
+) -> bool {
+    select! {
+        _ = cancel.cancelled() => return false,
+        _ = ticker.tick() => {},
+        Some(()) = async {
+            match rx_notification.as_mut() {
+                Some(rx_notification) => rx_notification.recv().await,
+                None => None,
+            }
+        } => {},
+    }
+    // A single check covers all changes notified so far.
+    if let Some(rx_notification) = rx_notification.as_mut() {
+        while rx_notification.try_recv().is_ok() {}
+    }
+    true
+}
+
+/// Returns the files of the folder matching the pattern, sorted from the oldest file to the
+/// newest one.
+fn matching_files(watch: &stypes::FolderWatch) -> io::Result<Vec<MatchedFile>> {
+    let mut files = Vec::new();
+    for entry in fs::read_dir(&watch.path)? {
+        let entry = entry?;
+        let name = entry.file_name().to_string_lossy().to_string();
+        if !matches_pattern(&watch.pattern, &name) {
+            continue;
+        }
+        let path = entry.path();
+        // Files can be removed by a logger at any time.
+        let md = match fs::metadata(&path) {
+            Ok(md) => md,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => continue,
+            Err(err) => return Err(err),
+        };
+        if !md.is_file() {
+            continue;
+        }
+        files.push(MatchedFile {
+            key: tail::file_id(&md).map_or_else(|| FileKey::Path(path.clone()), FileKey::Id),
+            modified: md.modified().ok(),
+            path,
+            name,
+        });
+    }
+    match watch.order {
+        stypes::FolderSortOrder::Name => files.sort_by(|a, b| natural_cmp(&a.name, &b.name)),
+        stypes::FolderSortOrder::Modified => files.sort_by(|a, b| {
+            a.modified
+                .cmp(&b.modified)
+                .then_with(|| natural_cmp(&a.name, &b.name))
+        }),
+    }
+    Ok(files)
+}
+
+/// Matches the name of a file with a glob pattern supporting `*` (any sequence of characters)
+/// and `?` (any single character). An empty pattern matches all files.
+fn matches_pattern(pattern: &str, name: &str) -> bool {
+    if pattern.is_empty() {
+        return true;
+    }
+    let pattern: Vec<char> = pattern.chars().collect();
+    let name: Vec<char> = name.chars().collect();
+    let (mut p, mut n) = (0, 0);
+    // Position of the last `*` in the pattern and the position in the name it continues from.
+    let mut star: Option<(usize, usize)> = None;
+    while n < name.len() {
+        match pattern.get(p) {
+            Some('*') => {
+                star = Some((p, n));
+                p += 1;
+            }
+            Some(c) if *c == '?' || *c == name[n] => {
+                p += 1;
+                n += 1;
+            }
+            _ => match star {
+                Some((star_p, star_n)) => {
+                    // `*` takes one more character.
+                    star = Some((star_p, star_n + 1));
+                    p = star_p + 1;
+                    n = star_n + 1;
+                }
+                None => return false,
+            },
+        }
+    }
+    pattern[p..].iter().all(|c| *c == '*')
+}
+
+/// Compares names of files, numbers within the names are compared by their value.
+fn natural_cmp(a: &str, b: &str) -> Ordering {
+    let (mut a, mut b) = (a.chars().peekable(), b.chars().peekable());
+    loop {
+        let ordering = match (a.peek().copied(), b.peek().copied()) {
+            (None, None) => return Ordering::Equal,
+            (None, Some(_)) => return Ordering::Less,
+            (Some(_), None) => return Ordering::Greater,
+            (Some(ca), Some(cb)) if ca.is_ascii_digit() && cb.is_ascii_digit() => {
+                let (na, nb) = (take_number(&mut a), take_number(&mut b));
+                let (va, vb) = (na.trim_start_matches('0'), nb.trim_start_matches('0'));
+                va.len()
+                    .cmp(&vb.len())
+                    .then_with(|| va.cmp(vb))
+                    .then_with(|| na.len().cmp(&nb.len()))
+            }
+            (Some(ca), Some(cb)) => {
+                a.next();
+                b.next();
+                ca.cmp(&cb)
+            }
+        };
+        if ordering != Ordering::Equal {
+            return ordering;
+        }
+    }
+}
+
+fn take_number(chars: &mut Peekable<Chars<'_>>) -> String {
+    let mut number = String::new();
+    while let Some(digit) = chars.next_if(char::is_ascii_digit) {
+        number.push(digit);
+    }
+    number
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn match_patterns() {
+        assert!(matches_pattern("*.log", "app.log"));
+        assert!(matches_pattern("app*.log", "app.log"));
+        assert!(matches_pattern("app*.log", "app-2024-01-01.log"));
+        assert!(matches_pattern("app.log.?", "app.log.1"));
+        assert!(matches_pattern("*a*b*", "xxaxxbxx"));
+        assert!(matches_pattern("", "app.log"));
+        assert!(!matches_pattern("*.log", "app.log.1"));
+        assert!(!matches_pattern("app.log.?", "app.log.10"));
+        assert!(!matches_pattern("app*.log", "system.log"));
+    }
+
+    #[test]
+    fn natural_order() {
+        let mut names = vec![
+            "app-10.log",
+            "app-9.log",
+            "app-1.log",
+            "app-09.log",
+            "app.log",
+        ];
+        names.sort_by(|a, b| natural_cmp(a, b));
+        assert_eq!(
+            names,
+            vec![
+                "app-1.log",
+                "app-9.log",
+                "app-09.log",
+                "app-10.log",
+                "app.log"
+            ]
+        );
+    }
+
+    #[test]
+    fn sorted_files() {
+        let dir = tempfile::tempdir().unwrap();
+        for name in ["app-2.log", "app-10.log", "app-1.log", "other.txt"] {
+            fs::write(dir.path().join(name), name).unwrap();
+        }
+        fs::create_dir(dir.path().join("app-3.log")).unwrap();
+        let watch = stypes::FolderWatch {
+            path: dir.path().to_path_buf(),
+            pattern: String::from("app-*.log"),
+            order: stypes::FolderSortOrder::Name,
+            format: stypes::FileFormat::Text,
+        };
+        let names = matching_files(&watch)
+            .unwrap()
+            .into_iter()
+            .map(|file| file.name)
+            .collect::<Vec<String>>();
+        assert_eq!(names, vec!["app-1.log", "app-2.log", "app-10.log"]);
+    }
+}
@@ -1,31 +1,35 @@
-use std::path::PathBuf;
+use std::{collections::HashMap, ops::Range, path::PathBuf};
 
 use crate::{
     operations::{OperationAPI, OperationResult},
-    state::SessionStateAPI,
+    state::{SessionStateAPI, triggers::SharedTriggers},
     tail,
 };
-use log::trace;
+use log::{error, trace, warn};
 use parsers::{
     LogMessage, MessageStreamItem, ParseYield, Parser,
-    dlt::{DltParser, fmt::FormatOptions},
+    can::{CanParser, dbc::DbcMetadata},
+    dlt::{DltParser, arxml::ArxmlMetadata, flexray::FlexrayMetadata, fmt::FormatOptions},
     someip::{FibexMetadata as FibexSomeipMetadata, SomeipParser},
     text::StringTokenizer,
 };
 use plugins_host::PluginsParser;
+use processor::grabber::LineRange;
 use sources::{
-    ByteSource,
+    ByteSource, NetworkFlow, SourceFilter, TransportProtocol,
     producer::MessageProducer,
     sde::{SdeMsg, SdeReceiver},
 };
 use tokio::{
     select,
-    sync::mpsc::Receiver,
     time::{Duration, timeout},
 };
 
 enum Next<'a, T: LogMessage> {
-    Items(&'a mut Vec<(usize, MessageStreamItem<T>)>),
+    Items(
+        &'a mut Vec<(usize, MessageStreamItem<T>)>,
+        Option<NetworkFlow>,
+    ),
     Timeout,
     Waiting,
     Sde(SdeMsg),
@@ -33,6 +37,7 @@ enum Next<'a, T: LogMessage> {
 
 pub mod concat;
 pub mod file;
+pub mod folder;
 pub mod stream;
 
 pub const FLUSH_TIMEOUT_IN_MS: u128 = 500;
@@ -43,8 +48,9 @@ pub async fn run_source<S: ByteSource>(
     source: S,
     source_id: u16,
     parser: &stypes::ParserType,
+    filter: Option<&SourceFilter>,
     rx_sde: Option<SdeReceiver>,
-    rx_tail: Option<Receiver<Result<(), tail::Error>>>,
+    follower: Option<tail::Follower<'_, S>>,
 ) -> OperationResult<()> {
     let cancel = operation_api.cancellation_token();
 
@@ -56,8 +62,9 @@ pub async fn run_source<S: ByteSource>(
         source,
         source_id,
         parser,
+        filter,
         rx_sde,
-        rx_tail,
+        follower,
     )
     .await;
 
@@ -75,8 +82,9 @@ async fn run_source_intern<S: ByteSource>(
     source: S,
     source_id: u16,
     parser: &stypes::ParserType,
+    filter: Option<&SourceFilter>,
     rx_sde: Option<SdeReceiver>,
-    rx_tail: Option<Receiver<Result<(), tail::Error>>>,
+    follower: Option<tail::Follower<'_, S>>,
 ) -> OperationResult<()> {
     match parser {
         stypes::ParserType::Plugin(settings) => {
@@ -87,7 +95,16 @@ async fn run_source_intern<S: ByteSource>(
             )
             .await?;
             let producer = MessageProducer::new(parser, source);
-            run_producer(operation_api, state, source_id, producer, rx_tail, rx_sde).await
+            run_producer(
+                operation_api,
+                state,
+                source_id,
+                producer,
+                filter,
+                follower,
+                rx_sde,
+            )
+            .await
         }
         stypes::ParserType::SomeIp(settings) => {
             let someip_parser = match &settings.fibex_file_paths {
@@ -97,26 +114,85 @@ async fn run_source_intern<S: ByteSource>(
                 None => SomeipParser::new(),
             };
             let producer = MessageProducer::new(someip_parser, source);
-            run_producer(operation_api, state, source_id, producer, rx_tail, rx_sde).await
+            run_producer(
+                operation_api,
+                state,
+                source_id,
+                producer,
+                filter,
+                follower,
+                rx_sde,
+            )
+            .await
         }
         stypes::ParserType::Text(()) => {
             let producer = MessageProducer::new(StringTokenizer {}, source);
-            run_producer(operation_api, state, source_id, producer, rx_tail, rx_sde).await
+            run_producer(
+                operation_api,
+                state,
+                source_id,
+                producer,
+                filter,
+                follower,
+                rx_sde,
+            )
+            .await
+        }
+        stypes::ParserType::Can(settings) => {
+            let dbc_metadata = settings.dbc_file_paths.as_ref().and_then(|paths| {
+                DbcMetadata::from_dbc_files(paths.iter().map(PathBuf::from).collect())
+            });
+            let producer = MessageProducer::new(CanParser::new(dbc_metadata), source);
+            run_producer(
+                operation_api,
+                state,
+                source_id,
+                producer,
+                filter,
+                follower,
+                rx_sde,
+            )
+            .await
         }
         stypes::ParserType::Dlt(settings) => {
             let fmt_options = Some(FormatOptions::from(settings.tz.as_ref()));
             let someip_metadata = settings.fibex_file_paths.as_ref().and_then(|paths| {
                 FibexSomeipMetadata::from_fibex_files(paths.iter().map(PathBuf::from).collect())
             });
-            let dlt_parser = DltParser::new(
+            let flexray_metadata = settings.fibex_file_paths.as_ref().and_then(|paths| {
+                FlexrayMetadata::from_fibex_files(paths.iter().map(PathBuf::from).collect())
+            });
+            let dbc_metadata = settings.dbc_file_paths.as_ref().and_then(|paths| {
+                DbcMetadata::from_dbc_files(paths.iter().map(PathBuf::from).collect())
+            });
+            let arxml_metadata = settings.arxml_file_paths.as_ref().and_then(|paths| {
+                ArxmlMetadata::from_arxml_files(paths.iter().map(PathBuf::from).collect())
+            });
+            let mut dlt_parser = DltParser::new(
                 settings.filter_config.as_ref().map(|f| f.into()),
                 settings.fibex_metadata.as_ref(),
                 fmt_options.as_ref(),
                 someip_metadata.as_ref(),
                 settings.with_storage_header,
-            );
+            )
+            .with_arxml_metadata(arxml_metadata.as_ref())
+            .with_dbc_metadata(dbc_metadata.as_ref())
+            .with_flexray_metadata(flexray_metadata.as_ref());
+            if settings.detect_lifecycles {
+                dlt_parser = dlt_parser.with_lifecycle_detection();
+            }
+            dlt_parser = dlt_parser.with_statistics(state.get_dlt_statistics().await?);
             let producer = MessageProducer::new(dlt_parser, source);
-            run_producer(operation_api, state, source_id, producer, rx_tail, rx_sde).await
+            run_producer(
+                operation_api,
+                state,
+                source_id,
+                producer,
+                filter,
+                follower,
+                rx_sde,
+            )
+            .await
         }
     }
 }
@@ -126,20 +202,31 @@ async fn run_producer<T: LogMessage, P: Parser<T>, S: ByteSource>(
     state: SessionStateAPI,
     source_id: u16,
     mut producer: MessageProducer<T, P, S>,
-    mut rx_tail: Option<Receiver<Result<(), tail::Error>>>,
+    filter: Option<&SourceFilter>,
+    mut follower: Option<tail::Follower<'_, S>>,
     mut rx_sde: Option<SdeReceiver>,
 ) -> OperationResult<()> {
     use log::debug;
+    if let Some(filter) = filter {
+        producer = producer.with_filter(filter.clone());
+    }
     state.set_session_file(None).await?;
     operation_api.processing();
     let cancel = operation_api.cancellation_token();
     let cancel_on_tail = cancel.clone();
+    // Network traces get a separate source for each flow.
+    let mut flows: HashMap<NetworkFlow, u16> = HashMap::new();
+    let mut triggered = Triggered::new(state.get_triggers().await?);
+    // The end of the source is reached again each time a tailed file grows.
+    let mut file_read = false;
+    // A rotated file is read up to its end before the new file is picked up.
+    let mut rotated = false;
     while let Some(next) = select! {
         next_from_stream = async {
-            match timeout(Duration::from_millis(FLUSH_TIMEOUT_IN_MS as u64), producer.read_next_segment()).await {
+            match timeout(Duration::from_millis(FLUSH_TIMEOUT_IN_MS as u64), producer.read_next_flow_segment()).await {
                 Ok(items) => {
-                    if let Some(items) = items {
-                        Some(Next::Items(items))
+                    if let Some((items, flow)) = items {
+                        Some(Next::Items(items, flow))
                     } else {
                         Some(Next::Waiting)
                     }
@@ -158,24 +245,32 @@ async fn run_producer<T: LogMessage, P: Parser<T>, S: ByteSource>(
         _ = cancel.cancelled() => None,
     } {
         match next {
-            Next::Items(items) => {
+            Next::Items(items, flow) => {
+                let source_id = match flow {
+                    Some(flow) if has_messages(items) => {
+                        flow_source_id(&state, &mut flows, flow).await?
+                    }
+                    _ => source_id,
+                };
                 // Iterating over references is more efficient than using `drain(..)`, even though
                 // we clone the attachments below. With ownership, `mem_copy()` would still be called
                 // to move the item into the attachment vector. Cloning avoids the overhead of
                 // `drain(..)`, especially since `items` is cleared on each iteration anyway.
+                let mut stop = false;
+                triggered.refresh();
                 for (_, item) in items {
                     match item {
                         MessageStreamItem::Item(ParseYield::Message(item)) => {
-                            state
-                                .write_session_file(source_id, format!("{item}\n"))
+                            stop = triggered
+                                .write(&state, source_id, format!("{item}\n"), item.timestamp())
                                 .await?;
                         }
                         MessageStreamItem::Item(ParseYield::MessageAndAttachment((
                             item,
                             attachment,
                         ))) => {
-                            state
-                                .write_session_file(source_id, format!("{item}\n"))
+                            stop = triggered
+                                .write(&state, source_id, format!("{item}\n"), item.timestamp())
                                 .await?;
                             state.add_attachment(attachment.to_owned())?;
                         }
@@ -185,7 +280,10 @@ async fn run_producer<T: LogMessage, P: Parser<T>, S: ByteSource>(
                         MessageStreamItem::Done => {
                             trace!("observe, message stream is done");
                             state.flush_session_file().await?;
-                            state.file_read().await?;
+                            if !file_read {
+                                state.file_read().await?;
+                                file_read = true;
+                            }
                         }
                         // MessageStreamItem::FileRead => {
                         //     state.file_read().await?;
@@ -200,6 +298,24 @@ async fn run_producer<T: LogMessage, P: Parser<T>, S: ByteSource>(
                             trace!("observe: empty message");
                         }
                     }
+                    if stop {
+                        break;
+                    }
+                }
+                if triggered.has_fired() {
+                    // Rows of fired rules have to be available for the client.
+                    state.flush_session_file().await?;
+                }
+                for (rule, request) in triggered.apply(&operation_api, &state).await? {
+                    if let Err(err) = producer.sde_income(request).await {
+                        warn!("Fail to send request of trigger rule {rule} to source: {err}");
+                    }
+                }
+                if stop {
+                    debug!("observe is stopped by a trigger rule");
+                    state.flush_session_file().await?;
+                    operation_api.cancel();
+                    break;
                 }
             }
             Next::Timeout => {
@@ -208,21 +324,57 @@ async fn run_producer<T: LogMessage, P: Parser<T>, S: ByteSource>(
                 }
             }
             Next::Waiting => {
-                if let Some(mut rx_tail) = rx_tail.take() {
-                    if select! {
-                        next_from_stream = rx_tail.recv() => {
-                            if let Some(result) = next_from_stream {
-                                result.is_err()
-                            } else {
-                                true
-                            }
-                        },
-                        _ = cancel_on_tail.cancelled() => true,
-                    } {
-                        break;
-                    }
-                } else {
+                let Some(tailing) = follower.as_mut() else {
                     break;
+                };
+                if rotated {
+                    // The rest of the rotated file is read, the new file continues as a new
+                    // segment of the same source.
+                    rotated = false;
+                    producer.replace_source(tailing.reopen()?);
+                    let (pos, _) = state.get_stream_len().await?;
+                    operation_api.emit(stypes::CallbackEvent::FileRotated {
+                        source_id,
+                        pos,
+                        truncated: false,
+                    });
+                    continue;
+                }
+                let update = select! {
+                    update = tailing.next() => update,
+                    _ = cancel_on_tail.cancelled() => break,
+                };
+                match update {
+                    Some(Ok(tail::Update::Appended)) => producer.resume(),
+                    Some(Ok(tail::Update::Rotated)) => {
+                        // Data could be appended to the renamed file before it was rotated.
+                        rotated = true;
+                        producer.resume();
+                    }
+                    Some(Ok(tail::Update::Truncated)) => {
+                        // The new content of the file continues as a new segment of the
+                        // same source.
+                        producer.replace_source(tailing.reopen()?);
+                        let (pos, _) = state.get_stream_len().await?;
+                        operation_api.emit(stypes::CallbackEvent::FileRotated {
+                            source_id,
+                            pos,
+                            truncated: true,
+                        });
+                    }
+                    Some(Err(err)) => {
+                        return Err(stypes::NativeError {
+                            severity: stypes::Severity::ERROR,
+                            kind: stypes::NativeErrorKind::Interrupted,
+                            message: Some(format!("Tailing error: {err}")),
+                        });
+                    }
+                    None => {
+                        // Tracking is done, but data could be appended after the last
+                        // update. The rest of the source is read before finishing.
+                        producer.resume();
+                        follower = None;
+                    }
                 }
             }
             Next::Sde((msg, tx_response)) => {
@@ -236,3 +388,215 @@ async fn run_producer<T: LogMessage, P: Parser<T>, S: ByteSource>(
     debug!("listen done");
     Ok(None)
 }
+
+/// Count of rows of a linked file grabbed at once to check the trigger rules.
+const TRIGGERS_CHECK_ROWS: u64 = 10_000;
+
+/// Checks the trigger rules of the session on messages written into the session file or
+/// rows added from a linked file.
+struct Triggered {
+    triggers: SharedTriggers,
+    /// `true` if rules are set. It's updated for each segment of messages, so messages
+    /// aren't checked one by one if there are no rules.
+    active: bool,
+    /// Fired rules with the rows that fired them, which aren't applied yet
+    fired: Vec<(u32, u64, Vec<stypes::TriggerAction>)>,
+    /// The row after which observing is stopped
+    stop_at: Option<u64>,
+}
+
+impl Triggered {
+    fn new(triggers: SharedTriggers) -> Self {
+        Self {
+            triggers,
+            active: false,
+            fired: Vec::new(),
+            stop_at: None,
+        }
+    }
+
+    /// Updates whether rules are set, since rules can be changed while observing.
+    fn refresh(&mut self) {
+        self.active = match self.triggers.lock() {
+            Ok(triggers) => !triggers.is_empty(),
+            Err(err) => {
+                error!("Fail to check trigger rules: {err}");
+                false
+            }
+        };
+    }
+
+    /// Returns the rules fired by the message. The rules are locked only if any are set.
+    fn check(&self, msg: &str) -> Vec<(u32, Vec<stypes::TriggerAction>)> {
+        if !self.active {
+            return Vec::new();
+        }
+        match self.triggers.lock() {
+            Ok(mut triggers) => triggers.check(msg),
+            Err(err) => {
+                error!("Fail to check trigger rules: {err}");
+                Vec::new()
+            }
+        }
+    }
+
+    /// Keeps the rules fired by the row to apply them later.
+    fn fire(&mut self, row: u64, fired: Vec<(u32, Vec<stypes::TriggerAction>)>) {
+        for (rule, actions) in fired {
+            for action in actions.iter() {
+                if let stypes::TriggerAction::StopAfter(count) = action {
+                    let stop_at = row.saturating_add(*count);
+                    self.stop_at = Some(self.stop_at.map_or(stop_at, |s| s.min(stop_at)));
+                }
+            }
+            self.fired.push((rule, row, actions));
+        }
+    }
+
+    fn has_fired(&self) -> bool {
+        !self.fired.is_empty()
+    }
+
+    /// Returns `true` if observing has to be stopped after the given row.
+    fn stops_after(&self, row: u64) -> bool {
+        self.stop_at.is_some_and(|stop_at| row >= stop_at)
+    }
+
+    /// Writes the message with its timestamp into the session file and checks the trigger
+    /// rules. Returns `true` if observing has to be stopped.
+    async fn write(
+        &mut self,
+        state: &SessionStateAPI,
+        source_id: u16,
+        msg: String,
+        timestamp: Option<u64>,
+    ) -> Result<bool, stypes::NativeError> {
+        let fired = self.check(msg.trim_end_matches('\n'));
+        let row = state.write_session_file(source_id, msg, timestamp).await?;
+        self.fire(row, fired);
+        Ok(self.stops_after(row))
+    }
+
+    /// Checks the trigger rules on rows added to the session from a linked file. Returns
+    /// `true` if observing has to be stopped. Rows following the row of a `StopAfter`
+    /// action are already in the session at this point, so only further reading stops.
+    async fn check_rows(
+        &mut self,
+        state: &SessionStateAPI,
+        rows: Range<u64>,
+    ) -> Result<bool, stypes::NativeError> {
+        self.refresh();
+        if !self.active || rows.is_empty() {
+            return Ok(false);
+        }
+        let mut start = rows.start;
+        while start < rows.end {
+            let end = rows.end.min(start.saturating_add(TRIGGERS_CHECK_ROWS));
+            let grabbed = state.grab(LineRange::from(start..=end - 1)).await?;
+            for element in grabbed {
+                let fired = self.check(&element.content);
+                self.fire(element.pos as u64, fired);
+            }
+            start = end;
+        }
+        Ok(self.stops_after(rows.end - 1))
+    }
+
+    /// Executes the actions of fired rules. The session file has to be flushed before, so
+    /// the rows of fired rules are available for the client. Returns the requests of
+    /// `SendToSource` actions, since only the caller has access to the source.
+    async fn apply(
+        &mut self,
+        operation_api: &OperationAPI,
+        state: &SessionStateAPI,
+    ) -> Result<Vec<(u32, stypes::SdeRequest)>, stypes::NativeError> {
+        let mut requests = Vec::new();
+        for (rule, row, actions) in self.fired.drain(..) {
+            for action in actions {
+                match action {
+                    stypes::TriggerAction::Notify => {
+                        operation_api.emit(stypes::CallbackEvent::TriggerFired { rule, row });
+                    }
+                    stypes::TriggerAction::Bookmark => state.add_bookmark(row).await?,
+                    stypes::TriggerAction::StopAfter(_) => {}
+                    stypes::TriggerAction::SendToSource(request) => {
+                        requests.push((rule, request));
+                    }
+                }
+            }
+        }
+        Ok(requests)
+    }
+}
+
+/// Reads rows appended to a linked file into the session and checks the trigger rules on
+/// them. Returns `true` if observing has to be stopped.
+async fn update_linked(
+    operation_api: &OperationAPI,
+    state: &SessionStateAPI,
+    source_id: u16,
+    triggered: &mut Triggered,
+) -> Result<bool, stypes::NativeError> {
+    let (before, _) = state.get_stream_len().await?;
+    state.update_session(source_id).await?;
+    let (after, _) = state.get_stream_len().await?;
+    let stop = triggered.check_rows(state, before..after).await?;
+    for (rule, _) in triggered.apply(operation_api, state).await? {
+        warn!("Request of trigger rule {rule} isn't sent: file sources don't take requests");
+    }
+    Ok(stop)
+}
+
+/// Converts the filter of the observe options into the filter applied by the sources.
+pub fn source_filter(filter: &stypes::NetworkFilter) -> Result<SourceFilter, stypes::NativeError> {
+    let invalid = |err: stypes::NetError| stypes::NativeError {
+        severity: stypes::Severity::ERROR,
+        kind: stypes::NativeErrorKind::Configuration,
+        message: Some(format!("Invalid network filter: {err}")),
+    };
+    let mut source_filter = SourceFilter::new();
+    if let Some(transport) = &filter.transport {
+        source_filter = source_filter.with_transport(match transport {
+            stypes::NetworkTransport::Tcp => TransportProtocol::TCP,
+            stypes::NetworkTransport::Udp => TransportProtocol::UDP,
+        });
+    }
+    if let Some(ip) = filter.source_addr().map_err(invalid)? {
+        source_filter = source_filter.with_source_ip(ip);
+    }
+    if let Some(port) = filter.source_port {
+        source_filter = source_filter.with_source_port(port);
+    }
+    if let Some(ip) = filter.destination_addr().map_err(invalid)? {
+        source_filter = source_filter.with_destination_ip(ip);
+    }
+    if let Some(port) = filter.destination_port {
+        source_filter = source_filter.with_destination_port(port);
+    }
+    Ok(source_filter)
+}
+
+fn has_messages<T: LogMessage>(items: &[(usize, MessageStreamItem<T>)]) -> bool {
+    items.iter().any(|(_, item)| {
+        matches!(
+            item,
+            MessageStreamItem::Item(ParseYield::Message(_) | ParseYield::MessageAndAttachment(_))
+        )
+    })
+}
+
+/// Returns the source id of the network flow. The flow is registered as a source with a
+/// friendly alias (e.g. `192.168.0.10:3490 -> 192.168.0.20:3490 (UDP)`) when it delivers
+/// messages for the first time.
+async fn flow_source_id(
+    state: &SessionStateAPI,
+    flows: &mut HashMap<NetworkFlow, u16>,
+    flow: NetworkFlow,
+) -> Result<u16, stypes::NativeError> {
+    if let Some(source_id) = flows.get(&flow) {
+        return Ok(*source_id);
+    }
+    let source_id = state.add_source(&flow.to_string()).await?;
+    flows.insert(flow, source_id);
+    Ok(source_id)
+}
@@ -35,6 +35,7 @@ pub async fn observe_stream(
                 udp_source,
                 source_id,
                 parser,
+                None,
                 rx_sde,
                 None,
             )
@@ -54,6 +55,7 @@ pub async fn observe_stream(
                 tcp_source,
                 source_id,
                 parser,
+                None,
                 rx_sde,
                 None,
             )
@@ -71,11 +73,38 @@ pub async fn observe_stream(
                 serial_source,
                 source_id,
                 parser,
+                None,
+                rx_sde,
+                None,
+            )
+            .await
+        }
+        #[cfg(target_os = "linux")]
+        stypes::Transport::SocketCan(settings) => {
+            let can_source = sources::socket::can::SocketCanSource::new(&settings.interface)
+                .map_err(|e| stypes::NativeError {
+                    severity: stypes::Severity::ERROR,
+                    kind: stypes::NativeErrorKind::Interrupted,
+                    message: Some(format!("{e}")),
+                })?;
+            observing::run_source(
+                operation_api,
+                state,
+                can_source,
+                source_id,
+                parser,
+                None,
                 rx_sde,
                 None,
             )
             .await
         }
+        #[cfg(not(target_os = "linux"))]
+        stypes::Transport::SocketCan(_) => Err(stypes::NativeError {
+            severity: stypes::Severity::ERROR,
+            kind: stypes::NativeErrorKind::Configuration,
+            message: Some(String::from("SocketCAN is only supported on Linux")),
+        }),
         stypes::Transport::Process(settings) => {
             let process_source = ProcessSource::new(
                 settings.command.clone(),
@@ -94,6 +123,7 @@ pub async fn observe_stream(
                 process_source,
                 source_id,
                 parser,
+                None,
                 rx_sde,
                 None,
             )
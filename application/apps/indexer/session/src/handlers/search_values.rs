@@ -42,6 +42,11 @@ pub async fn execute_value_search(
             .await?;
         return Err(err);
     }
+    // Rates of values are measured with timestamps configured for the session
+    if let Some(cursor) = state.get_timestamps_cursor().await? {
+        holder.set_timestamp_extractor(cursor.extractor);
+    }
+    let series = holder.series();
     if filters.is_empty() {
         debug!("RUST: Search values are dropped. Filters are empty");
         state
@@ -117,6 +122,7 @@ pub async fn execute_value_search(
                     state
                         .set_search_values_holder(Some(holder), operation_api.id())
                         .await?;
+                    state.set_search_values_series(series).await?;
                     state.set_search_values(values).await?;
                     Ok(Some(()))
                 }
@@ -0,0 +1,86 @@
+use super::timestamps;
+use crate::{
+    operations::{OperationAPI, OperationResult},
+    state::{SessionStateAPI, timestamps::CANCELLATION_CHECK_ROWS},
+};
+use log::debug;
+use processor::search::sequences::SequenceChecker;
+use std::{
+    fs::File,
+    io::{BufRead, BufReader},
+    path::PathBuf,
+};
+use tokio::task;
+use tokio_util::sync::CancellationToken;
+
+/// Checks the rows of the session file against sequence rules and returns the found
+/// instances of the rules. If any step has a max gap in time, the index of timestamps is
+/// updated first, so rows need the timestamps of their messages or extraction of timestamps
+/// has to be set up.
+pub async fn check(
+    operation_api: &OperationAPI,
+    state: SessionStateAPI,
+    rules: Vec<stypes::SequenceRule>,
+) -> OperationResult<stypes::ResultSequences> {
+    debug!("RUST: Checking of sequences is requested");
+    let checker = SequenceChecker::new(rules).map_err(|err| stypes::NativeError {
+        severity: stypes::Severity::ERROR,
+        kind: stypes::NativeErrorKind::Configuration,
+        message: Some(err.to_string()),
+    })?;
+    let (points, indexed) = if checker.needs_timestamps() {
+        timestamps::update_index(operation_api, &state).await?;
+        if operation_api.cancellation_token().is_cancelled() {
+            return Ok(None);
+        }
+        state.get_timestamp_points().await?
+    } else {
+        (Vec::new(), 0)
+    };
+    let (rows, _) = state.get_stream_len().await?;
+    let filename = state.get_session_file().await?;
+    let cancel = operation_api.cancellation_token();
+    task::spawn_blocking(move || check_rows(filename, checker, &points, indexed, rows, cancel))
+        .await
+        .map_err(|err| stypes::NativeError {
+            severity: stypes::Severity::ERROR,
+            kind: stypes::NativeErrorKind::ComputationFailed,
+            message: Some(format!("Fail to check sequences: {err}")),
+        })?
+}
+
+/// Feeds the rows of the session file to the checker. Timestamps are taken from the
+/// change points of the index, so rows beyond the indexed ones have no timestamp.
+fn check_rows(
+    filename: PathBuf,
+    mut checker: SequenceChecker,
+    points: &[(u64, u64)],
+    indexed: u64,
+    rows: u64,
+    cancel: CancellationToken,
+) -> OperationResult<stypes::ResultSequences> {
+    let mut reader = BufReader::new(File::open(&filename)?);
+    let mut line = Vec::new();
+    // Count of points starting at or before the current row
+    let mut passed = 0;
+    for row in 0..rows {
+        if row % CANCELLATION_CHECK_ROWS == 0 && cancel.is_cancelled() {
+            return Ok(None);
+        }
+        line.clear();
+        if reader.read_until(b'\n', &mut line)? == 0 {
+            break;
+        }
+        while passed < points.len() && points[passed].0 <= row {
+            passed += 1;
+        }
+        let timestamp = if row < indexed {
+            passed.checked_sub(1).map(|point| points[point].1)
+        } else {
+            None
+        };
+        let line = String::from_utf8_lossy(&line);
+        checker.check(row, timestamp, line.trim_end_matches(['\n', '\r']));
+    }
+    Ok(Some(stypes::ResultSequences(checker.finish())))
+}
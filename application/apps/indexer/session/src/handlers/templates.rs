@@ -0,0 +1,55 @@
+use crate::{
+    operations::{OperationAPI, OperationResult},
+    state::SessionStateAPI,
+};
+use log::debug;
+use processor::search::searchers;
+use tokio::task;
+
+/// Mines templates of messages from the rows added to the session file since the previous
+/// mining and returns all visible templates.
+pub async fn mine(
+    operation_api: &OperationAPI,
+    state: SessionStateAPI,
+) -> OperationResult<stypes::LogTemplateList> {
+    debug!("RUST: Mining of templates is requested");
+    let (rows, read_bytes) = state.get_stream_len().await?;
+    let mut holder = state.get_templates_holder(operation_api.id()).await?;
+    let cancel = operation_api.cancellation_token();
+    let mined = task::spawn_blocking(move || {
+        let result = searchers::templates::mine(&mut holder, rows, read_bytes, cancel);
+        (holder, result)
+    })
+    .await;
+    let (holder, result) = match mined {
+        Ok(mined) => mined,
+        Err(err) => {
+            state.set_templates_holder(None).await?;
+            return Err(stypes::NativeError {
+                severity: stypes::Severity::ERROR,
+                kind: stypes::NativeErrorKind::ComputationFailed,
+                message: Some(format!("Fail to mine templates: {err}")),
+            });
+        }
+    };
+    if operation_api.cancellation_token().is_cancelled() {
+        // Rows read before the cancellation are lost, so mining has to start over.
+        state.set_templates_holder(None).await?;
+        return Ok(None);
+    }
+    match result {
+        Ok(_) => {
+            let templates = holder.templates();
+            state.set_templates_holder(Some(holder)).await?;
+            Ok(Some(stypes::LogTemplateList(templates)))
+        }
+        Err(err) => {
+            state.set_templates_holder(None).await?;
+            Err(stypes::NativeError {
+                severity: stypes::Severity::ERROR,
+                kind: stypes::NativeErrorKind::OperationSearch,
+                message: Some(format!("Fail to mine templates: {err}")),
+            })
+        }
+    }
+}
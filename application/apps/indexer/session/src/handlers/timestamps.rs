@@ -0,0 +1,77 @@
+use crate::{
+    operations::{OperationAPI, OperationResult},
+    state::{SessionStateAPI, timestamps::index_rows},
+};
+use std::ops::RangeInclusive;
+use tokio::task;
+
+/// Returns the row with the timestamp nearest to the given one (ms since the UNIX epoch).
+pub async fn nearest(
+    operation_api: &OperationAPI,
+    state: SessionStateAPI,
+    timestamp: u64,
+) -> OperationResult<stypes::ResultNearestTimestamp> {
+    update_index(operation_api, &state).await?;
+    Ok(Some(state.get_nearest_timestamp(timestamp).await?))
+}
+
+/// Returns the range of rows with timestamps within the given period.
+pub async fn range(
+    operation_api: &OperationAPI,
+    state: SessionStateAPI,
+    period: RangeInclusive<u64>,
+) -> OperationResult<stypes::ResultTimeRange> {
+    update_index(operation_api, &state).await?;
+    Ok(Some(state.get_time_range(period).await?))
+}
+
+/// Returns the counts of search matches of each filter within periods of `bucket` ms.
+pub async fn distribution(
+    operation_api: &OperationAPI,
+    state: SessionStateAPI,
+    bucket: u64,
+) -> OperationResult<stypes::ResultTimeDistribution> {
+    update_index(operation_api, &state).await?;
+    Ok(Some(state.get_time_distribution(bucket).await?))
+}
+
+/// Extends the index of timestamps by the rows added to the session file since the last
+/// update. Nothing is to do if the rows have the timestamps of their messages.
+pub(super) async fn update_index(
+    operation_api: &OperationAPI,
+    state: &SessionStateAPI,
+) -> Result<(), stypes::NativeError> {
+    let cancel = operation_api.cancellation_token();
+    // The session file can grow while rows are indexed; new rows are indexed by the state
+    // once the index covers all previous rows.
+    while let Some(cursor) = state.get_timestamps_cursor().await? {
+        let (rows, _) = state.get_stream_len().await?;
+        if rows <= cursor.rows || cancel.is_cancelled() {
+            return Ok(());
+        }
+        let filename = state.get_session_file().await?;
+        let cancel = cancel.clone();
+        let update = task::spawn_blocking(move || index_rows(&filename, cursor, rows, &cancel))
+            .await
+            .map_err(|err| stypes::NativeError {
+                severity: stypes::Severity::ERROR,
+                kind: stypes::NativeErrorKind::ComputationFailed,
+                message: Some(format!("Fail to index timestamps: {err}")),
+            })??;
+        let Some(update) = update else {
+            return Ok(());
+        };
+        state.update_timestamps(update).await?;
+    }
+    if state.is_timestamps_available().await? {
+        Ok(())
+    } else {
+        Err(stypes::NativeError {
+            severity: stypes::Severity::ERROR,
+            kind: stypes::NativeErrorKind::Configuration,
+            message: Some(String::from(
+                "Rows have no timestamps and extraction of timestamps isn't set up",
+            )),
+        })
+    }
+}
@@ -109,6 +109,18 @@ pub enum OperationKind {
         out_path: PathBuf,
         ranges: Vec<std::ops::RangeInclusive<u64>>,
     },
+    /// Exports the rows with search matches and the rows around them (see `Export` for
+    /// `columns`, `spliter` and `delimiter`).
+    ExportSearchContext {
+        out_path: PathBuf,
+        /// The count of rows before each match.
+        before: u64,
+        /// The count of rows after each match.
+        after: u64,
+        columns: Vec<usize>,
+        spliter: Option<String>,
+        delimiter: Option<String>,
+    },
     Extract {
         filters: Vec<SearchFilter>,
     },
@@ -127,6 +139,18 @@ pub enum OperationKind {
         source_id: String,
     },
     GetNearestPosition(u64),
+    /// Finds the row with the timestamp nearest to the given one (ms since the UNIX epoch).
+    GetNearestTimestamp(u64),
+    /// Finds the rows with timestamps within the given period (ms since the UNIX epoch).
+    GetTimeRange(RangeInclusive<u64>),
+    /// Counts search matches within periods of the given length (ms).
+    GetTimeDistribution(u64),
+    /// Mines templates of messages from the rows added since the previous mining.
+    MineTemplates,
+    /// Finds the repeated rows of the session after switching to a folding mode.
+    FoldRows,
+    /// Checks the rows of the session against sequence rules.
+    CheckSequences(Vec<stypes::SequenceRule>),
     Cancel {
         target: Uuid,
     },
@@ -145,6 +169,7 @@ impl std::fmt::Display for OperationKind {
                 OperationKind::SearchValues { .. } => "Searching values",
                 OperationKind::Export { .. } => "Exporting",
                 OperationKind::ExportRaw { .. } => "Exporting as Raw",
+                OperationKind::ExportSearchContext { .. } => "Exporting search context",
                 OperationKind::Extract { .. } => "Extracting",
                 OperationKind::Map { .. } => "Mapping",
                 OperationKind::Values { .. } => "Values",
@@ -152,6 +177,12 @@ impl std::fmt::Display for OperationKind {
                 OperationKind::Sleep(_, _) => "Sleeping",
                 OperationKind::Cancel { .. } => "Canceling",
                 OperationKind::GetNearestPosition(_) => "Getting nearest position",
+                OperationKind::GetNearestTimestamp(_) => "Getting nearest timestamp",
+                OperationKind::GetTimeRange(_) => "Getting time range",
+                OperationKind::GetTimeDistribution(_) => "Getting time distribution",
+                OperationKind::MineTemplates => "Mining templates",
+                OperationKind::FoldRows => "Folding rows",
+                OperationKind::CheckSequences(_) => "Checking sequences",
                 OperationKind::End => "End",
             }
         )
@@ -271,6 +302,19 @@ impl OperationAPI {
         self.cancellation_token.clone()
     }
 
+    /// Cancels the operation from within in the same way as it's cancelled by the client.
+    /// The cancellation is requested in a separate task, because the tracker waits for the
+    /// operation to be done.
+    pub fn cancel(&self) {
+        let tracker_api = self.tracker_api.clone();
+        let id = self.id();
+        spawn(async move {
+            if let Err(err) = tracker_api.cancel_operation(id).await {
+                error!("Failed to cancel operation {id}; error: {err:?}");
+            }
+        });
+    }
+
     pub async fn execute(
         &self,
         operation: Operation,
@@ -368,6 +412,35 @@ impl OperationAPI {
                     )
                     .await;
                 }
+                OperationKind::ExportSearchContext {
+                    out_path,
+                    before,
+                    after,
+                    columns,
+                    spliter,
+                    delimiter,
+                } => {
+                    let exported = match state.get_search_context(before, after).await {
+                        Ok(ranges) => {
+                            state
+                                .export_session(
+                                    out_path,
+                                    ranges,
+                                    columns,
+                                    spliter,
+                                    delimiter,
+                                    api.cancellation_token(),
+                                )
+                                .await
+                        }
+                        Err(err) => Err(err),
+                    };
+                    api.finish(
+                        exported.map(|done| Some(stypes::ResultBool(done))),
+                        operation_str,
+                    )
+                    .await;
+                }
                 OperationKind::Extract { filters } => {
                     let session_file = if let Some(session_file) = session_file {
                         session_file
@@ -477,6 +550,42 @@ impl OperationAPI {
                         }
                     }
                 }
+                OperationKind::GetNearestTimestamp(timestamp) => {
+                    api.finish(
+                        handlers::timestamps::nearest(&api, state, timestamp).await,
+                        operation_str,
+                    )
+                    .await;
+                }
+                OperationKind::GetTimeRange(period) => {
+                    api.finish(
+                        handlers::timestamps::range(&api, state, period).await,
+                        operation_str,
+                    )
+                    .await;
+                }
+                OperationKind::GetTimeDistribution(bucket) => {
+                    api.finish(
+                        handlers::timestamps::distribution(&api, state, bucket).await,
+                        operation_str,
+                    )
+                    .await;
+                }
+                OperationKind::MineTemplates => {
+                    api.finish(handlers::templates::mine(&api, state).await, operation_str)
+                        .await;
+                }
+                OperationKind::FoldRows => {
+                    api.finish(handlers::folds::fold(&api, state).await, operation_str)
+                        .await;
+                }
+                OperationKind::CheckSequences(rules) => {
+                    api.finish(
+                        handlers::sequences::check(&api, state, rules).await,
+                        operation_str,
+                    )
+                    .await;
+                }
                 _ => {
                     // OperationKind::End is processing in the loop directly
                 }
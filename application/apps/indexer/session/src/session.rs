@@ -2,12 +2,13 @@ use crate::{
     operations,
     operations::Operation,
     state,
-    state::{IndexesMode, SessionStateAPI},
+    state::{IndexesMode, SessionStateAPI, triggers::Triggers},
     tracker,
     tracker::OperationTrackerAPI,
 };
 use futures::Future;
 use log::{debug, error, warn};
+use parsers::text::timestamp::TimestampExtractor;
 use processor::{grabber::LineRange, search::filter::SearchFilter};
 use std::{ops::RangeInclusive, path::PathBuf};
 use tokio::{
@@ -105,7 +106,7 @@ impl Session {
                             &tx_operations,
                             &destroying,
                             "state",
-                            state::run(rx_state_api, tx_callback_events_state)
+                            state::run(state_api.clone(), rx_state_api, tx_callback_events_state)
                         ),
                         Self::run(
                             &tx_operations,
@@ -172,15 +173,29 @@ impl Session {
             .map_err(stypes::ComputationError::NativeError)
     }
 
+    /// Sets the mode of the indexed map. In the folding modes the rows are compared by an
+    /// operation started here, the indexed map is updated once they are folded.
     pub async fn set_indexing_mode(&self, mode: u8) -> Result<(), stypes::ComputationError> {
-        self.state
+        let fold = self
+            .state
             .set_indexing_mode(match mode {
                 0u8 => IndexesMode::Regular,
                 1u8 => IndexesMode::Breadcrumbs,
+                2u8 => IndexesMode::Folding,
+                3u8 => IndexesMode::FoldingTemplates,
                 _ => return Err(stypes::ComputationError::InvalidData),
             })
             .await
-            .map_err(stypes::ComputationError::NativeError)
+            .map_err(stypes::ComputationError::NativeError)?;
+        if fold {
+            self.tx_operations
+                .send(Operation::new(
+                    Uuid::new_v4(),
+                    operations::OperationKind::FoldRows,
+                ))
+                .map_err(|e| stypes::ComputationError::Communication(e.to_string()))?;
+        }
+        Ok(())
     }
 
     pub async fn get_indexed_len(&self) -> Result<usize, stypes::ComputationError> {
@@ -234,6 +249,26 @@ impl Session {
             .map_err(stypes::ComputationError::NativeError)
     }
 
+    /// Shows the rows hidden by the fold of the given row in the folding modes.
+    pub async fn expand_fold(&self, row: u64) -> Result<(), stypes::ComputationError> {
+        self.state
+            .expand_fold(row)
+            .await
+            .map_err(stypes::ComputationError::NativeError)
+    }
+
+    /// Returns the folds shown within the given range of rows in the folding modes.
+    pub async fn get_folds(
+        &self,
+        range: RangeInclusive<u64>,
+    ) -> Result<stypes::FoldList, stypes::ComputationError> {
+        self.state
+            .get_folds(range)
+            .await
+            .map(stypes::FoldList)
+            .map_err(stypes::ComputationError::NativeError)
+    }
+
     /// Calls "nested" search functionality.
     /// A "nested" search refers to filtering matches within the primary search results.
     ///
@@ -280,6 +315,22 @@ impl Session {
             .map_err(stypes::ComputationError::NativeError)
     }
 
+    /// Grabs the rows with the matches within the range of search results and `before`/`after`
+    /// rows around each match, like `grep -C`. Overlapping windows are merged; rows of
+    /// matches can be recognized by their nature.
+    pub async fn grab_search_context(
+        &self,
+        range: LineRange,
+        before: u64,
+        after: u64,
+    ) -> Result<stypes::GrabbedElementList, stypes::ComputationError> {
+        self.state
+            .grab_search_context(range, before, after)
+            .await
+            .map(|els| els.into())
+            .map_err(stypes::ComputationError::NativeError)
+    }
+
     pub async fn grab_ranges(
         &self,
         ranges: Vec<RangeInclusive<u64>>,
@@ -394,6 +445,24 @@ impl Session {
             .map_err(stypes::ComputationError::NativeError)
     }
 
+    /// Returns the statistics of the DLT messages of all DLT sources observed in the session.
+    /// The statistics are updated incrementally while messages arrive, so it can be requested
+    /// repeatedly for running sources (e.g. streams or tailed files).
+    pub async fn get_dlt_stats(
+        &self,
+    ) -> Result<stypes::DltStatisticInfo, stypes::ComputationError> {
+        let statistics = self
+            .state
+            .get_dlt_statistics()
+            .await
+            .map_err(stypes::ComputationError::NativeError)?;
+        let info = statistics
+            .lock()
+            .map_err(|e| stypes::ComputationError::IoOperation(e.to_string()))?
+            .info();
+        Ok(info.into())
+    }
+
     /// Exports data to the specified output path with the given parameters. This method is used to export
     /// only into text format. For exporting into raw format is using method `export_raw`
     ///
@@ -449,6 +518,35 @@ impl Session {
             .map_err(|e| stypes::ComputationError::Communication(e.to_string()))
     }
 
+    /// Exports the rows with search matches and `before`/`after` rows around each match into
+    /// text format. Overlapping windows are merged. See [`Session::export`] for `columns`,
+    /// `spliter` and `delimiter`.
+    #[allow(clippy::too_many_arguments)]
+    pub fn export_search_context(
+        &self,
+        operation_id: Uuid,
+        out_path: PathBuf,
+        before: u64,
+        after: u64,
+        columns: Vec<usize>,
+        spliter: Option<String>,
+        delimiter: Option<String>,
+    ) -> Result<(), stypes::ComputationError> {
+        self.tx_operations
+            .send(Operation::new(
+                operation_id,
+                operations::OperationKind::ExportSearchContext {
+                    out_path,
+                    before,
+                    after,
+                    columns,
+                    spliter,
+                    delimiter,
+                },
+            ))
+            .map_err(|e| stypes::ComputationError::Communication(e.to_string()))
+    }
+
     pub async fn is_raw_export_available(&self) -> Result<bool, stypes::ComputationError> {
         self.state
             .is_raw_export_available()
@@ -543,6 +641,127 @@ impl Session {
             .map_err(|e| stypes::ComputationError::Communication(e.to_string()))
     }
 
+    /// Sets up extracting timestamps from the rows of the session; `None` turns it off.
+    /// The index of timestamps is built on demand by the time based lookups.
+    pub async fn set_timestamp_settings(
+        &self,
+        settings: Option<stypes::TimestampSettings>,
+    ) -> Result<(), stypes::ComputationError> {
+        let extractor = settings
+            .map(|settings| {
+                TimestampExtractor::new(settings.format, settings.tz.as_deref(), settings.year)
+                    .map_err(|err| stypes::ComputationError::InvalidArgs(err.to_string()))
+            })
+            .transpose()?;
+        self.state
+            .set_timestamp_extractor(extractor)
+            .await
+            .map_err(stypes::ComputationError::NativeError)
+    }
+
+    /// Sets the trigger rules checked on each log entry written into the session while
+    /// sources are observed. The rules replace previous rules; an empty list turns them off.
+    pub async fn set_triggers(
+        &self,
+        rules: Vec<stypes::TriggerRule>,
+    ) -> Result<(), stypes::ComputationError> {
+        let rules = Triggers::new(rules).map_err(stypes::ComputationError::InvalidArgs)?;
+        let triggers = self
+            .state
+            .get_triggers()
+            .await
+            .map_err(stypes::ComputationError::NativeError)?;
+        *triggers
+            .lock()
+            .map_err(|e| stypes::ComputationError::IoOperation(e.to_string()))? = rules;
+        Ok(())
+    }
+
+    pub fn get_nearest_to_timestamp(
+        &self,
+        operation_id: Uuid,
+        timestamp: u64,
+    ) -> Result<(), stypes::ComputationError> {
+        self.tx_operations
+            .send(Operation::new(
+                operation_id,
+                operations::OperationKind::GetNearestTimestamp(timestamp),
+            ))
+            .map_err(|e| stypes::ComputationError::Communication(e.to_string()))
+    }
+
+    pub fn get_time_range(
+        &self,
+        operation_id: Uuid,
+        period: RangeInclusive<u64>,
+    ) -> Result<(), stypes::ComputationError> {
+        self.tx_operations
+            .send(Operation::new(
+                operation_id,
+                operations::OperationKind::GetTimeRange(period),
+            ))
+            .map_err(|e| stypes::ComputationError::Communication(e.to_string()))
+    }
+
+    /// Counts the search matches of each filter within periods of `bucket` ms using the
+    /// timestamps of rows; the result is delivered with `OperationDone`.
+    pub fn get_time_distribution(
+        &self,
+        operation_id: Uuid,
+        bucket: u64,
+    ) -> Result<(), stypes::ComputationError> {
+        if bucket == 0 {
+            return Err(stypes::ComputationError::InvalidArgs(String::from(
+                "Length of a period should be positive",
+            )));
+        }
+        self.tx_operations
+            .send(Operation::new(
+                operation_id,
+                operations::OperationKind::GetTimeDistribution(bucket),
+            ))
+            .map_err(|e| stypes::ComputationError::Communication(e.to_string()))
+    }
+
+    /// Mines templates of messages (constant tokens with parameters) from the rows of the
+    /// session. Mining continues from the rows mined by the previous call; the visible
+    /// templates are delivered with `OperationDone`.
+    pub fn mine_templates(&self, operation_id: Uuid) -> Result<(), stypes::ComputationError> {
+        self.tx_operations
+            .send(Operation::new(
+                operation_id,
+                operations::OperationKind::MineTemplates,
+            ))
+            .map_err(|e| stypes::ComputationError::Communication(e.to_string()))
+    }
+
+    /// Checks the rows of the session against sequence rules; found instances of the rules,
+    /// violated or not, are delivered with `OperationDone`.
+    pub fn check_sequences(
+        &self,
+        operation_id: Uuid,
+        rules: Vec<stypes::SequenceRule>,
+    ) -> Result<(), stypes::ComputationError> {
+        self.tx_operations
+            .send(Operation::new(
+                operation_id,
+                operations::OperationKind::CheckSequences(rules),
+            ))
+            .map_err(|e| stypes::ComputationError::Communication(e.to_string()))
+    }
+
+    /// Hides the given templates from the results of mining; `None` shows all templates.
+    /// Only the list of templates is affected, rows of hidden templates aren't hidden.
+    pub async fn hide_templates(
+        &self,
+        ids: Option<Vec<u32>>,
+    ) -> Result<(), stypes::ComputationError> {
+        self.state
+            .hide_templates(ids)
+            .await
+            .map_err(stypes::ComputationError::NativeError)
+    }
+
     pub async fn get_attachments(
         &self,
     ) -> Result<stypes::AttachmentList, stypes::ComputationError> {
@@ -561,6 +780,30 @@ impl Session {
             .map_err(stypes::ComputationError::NativeError)
     }
 
+    /// Returns descriptions of the datasets produced by the last search of values.
+    pub async fn get_values_series(
+        &self,
+    ) -> Result<stypes::ValueSeriesList, stypes::ComputationError> {
+        self.state
+            .get_search_values_series()
+            .await
+            .map(stypes::ValueSeriesList)
+            .map_err(stypes::ComputationError::NativeError)
+    }
+
+    /// Returns statistics of the datasets of the last search of values over the whole
+    /// session or over the given range of rows.
+    pub async fn get_values_statistics(
+        &self,
+        range: Option<RangeInclusive<u64>>,
+    ) -> Result<stypes::ValueStatisticsList, stypes::ComputationError> {
+        self.state
+            .get_search_values_statistics(range)
+            .await
+            .map(stypes::ValueStatisticsList)
+            .map_err(stypes::ComputationError::NativeError)
+    }
+
     /// Used for debug goals
     pub fn sleep(
         &self,
@@ -1,22 +1,34 @@
 use super::values::graph::CandlePoint;
 use crate::{
     state::{
-        indexes::controller::Mode as IndexesMode, observed::Observed,
-        session_file::SessionFileOrigin, values::ValuesError,
+        indexes::controller::Mode as IndexesMode,
+        observed::Observed,
+        session_file::{LinkedCopy, SessionFileOrigin},
+        timestamps::{TimestampsCursor, TimestampsUpdate},
+        triggers::SharedTriggers,
+        values::ValuesError,
     },
     tracker::OperationTrackerAPI,
 };
 use log::error;
-use parsers;
+use parsers::{self, dlt::stats::SharedDltStatistics, text::timestamp::TimestampExtractor};
 use processor::{
     grabber::LineRange,
     map::{FiltersStats, ScaledDistribution},
     search::{
         filter::SearchFilter,
-        searchers::{regular::RegularSearchHolder, values::ValueSearchHolder},
+        searchers::{
+            folds::FoldsHolder, regular::RegularSearchHolder, templates::TemplatesHolder,
+            values::ValueSearchHolder,
+        },
     },
 };
-use std::{collections::HashMap, fmt::Display, ops::RangeInclusive, path::PathBuf};
+use std::{
+    collections::HashMap,
+    fmt::Display,
+    ops::{Range, RangeInclusive},
+    path::PathBuf,
+};
 use stypes::GrabbedElement;
 use tokio::sync::{
     mpsc::{UnboundedReceiver, UnboundedSender, unbounded_channel},
@@ -37,10 +49,18 @@ pub enum Api {
         (
             u16,
             String,
-            oneshot::Sender<Result<(), stypes::NativeError>>,
+            Option<u64>,
+            oneshot::Sender<Result<u64, stypes::NativeError>>,
         ),
     ),
     FlushSessionFile(oneshot::Sender<Result<(), stypes::NativeError>>),
+    UnlinkSessionFile(
+        (
+            u16,
+            LinkedCopy,
+            oneshot::Sender<Result<(), stypes::NativeError>>,
+        ),
+    ),
     GetSessionFileOrigin(oneshot::Sender<Result<Option<SessionFileOrigin>, stypes::NativeError>>),
     UpdateSession((u16, oneshot::Sender<Result<bool, stypes::NativeError>>)),
     AddSource((String, oneshot::Sender<u16>)),
@@ -50,6 +70,12 @@ pub enum Api {
     AddExecutedObserve((stypes::ObserveOptions, oneshot::Sender<()>)),
     GetExecutedHolder(oneshot::Sender<Observed>),
     IsRawExportAvailable(oneshot::Sender<bool>),
+    /// Returns the DLT statistics of the session, which are updated while DLT messages are
+    /// parsed. The statistics accumulate the messages of all DLT sources of the session.
+    GetDltStatistics(oneshot::Sender<SharedDltStatistics>),
+    /// Returns the trigger rules of the session, which are checked while sources are
+    /// observed.
+    GetTriggers(oneshot::Sender<SharedTriggers>),
     /// Export operation containing parameters for exporting data.
     ///
     /// # Fields
@@ -98,7 +124,7 @@ pub enum Api {
     SetIndexingMode(
         (
             IndexesMode,
-            oneshot::Sender<Result<(), stypes::NativeError>>,
+            oneshot::Sender<Result<bool, stypes::NativeError>>,
         ),
     ),
     GetIndexedMapLen(oneshot::Sender<usize>),
@@ -118,12 +144,32 @@ pub enum Api {
         above: bool,
         tx_response: oneshot::Sender<Result<(), stypes::NativeError>>,
     },
+    ExpandFold((u64, oneshot::Sender<Result<(), stypes::NativeError>>)),
+    GetFolds((RangeInclusive<u64>, oneshot::Sender<Vec<stypes::Fold>>)),
     GrabSearch(
         (
             LineRange,
             oneshot::Sender<Result<Vec<GrabbedElement>, stypes::NativeError>>,
         ),
     ),
+    /// Grabs the rows around the matches within the range of search results.
+    GrabSearchContext(
+        (
+            LineRange,
+            u64,
+            u64,
+            oneshot::Sender<Result<Vec<GrabbedElement>, stypes::NativeError>>,
+        ),
+    ),
+    /// Returns the ranges of rows around all search matches.
+    #[allow(clippy::type_complexity)]
+    GetSearchContext(
+        (
+            u64,
+            u64,
+            oneshot::Sender<Result<Vec<RangeInclusive<u64>>, stypes::NativeError>>,
+        ),
+    ),
     #[allow(clippy::type_complexity)]
     SearchNestedMatch(
         (
@@ -157,6 +203,27 @@ pub enum Api {
     ),
     DropSearch(oneshot::Sender<bool>),
     GetNearestPosition((u64, oneshot::Sender<stypes::ResultNearestPosition>)),
+    SetTimestampExtractor((Option<TimestampExtractor>, oneshot::Sender<()>)),
+    GetTimestampsCursor(oneshot::Sender<Option<TimestampsCursor>>),
+    IsTimestampsAvailable(oneshot::Sender<bool>),
+    UpdateTimestamps(
+        (
+            TimestampsUpdate,
+            oneshot::Sender<Result<(), stypes::NativeError>>,
+        ),
+    ),
+    /// Result of indexing the timestamps of new rows, which the state started in the
+    /// background on an update of the session file.
+    SetIndexedTimestamps(Result<Option<TimestampsUpdate>, stypes::NativeError>),
+    GetTimestampPoints(oneshot::Sender<(Vec<(u64, u64)>, u64)>),
+    GetNearestTimestamp((u64, oneshot::Sender<stypes::ResultNearestTimestamp>)),
+    GetTimeRange(
+        (
+            RangeInclusive<u64>,
+            oneshot::Sender<stypes::ResultTimeRange>,
+        ),
+    ),
+    GetTimeDistribution((u64, oneshot::Sender<stypes::ResultTimeDistribution>)),
     GetScaledMap((u16, Option<(u64, u64)>, oneshot::Sender<ScaledDistribution>)),
     SetMatches(
         (
@@ -179,6 +246,42 @@ pub enum Api {
         ),
     ),
     SetSearchValues(HashMap<u8, Vec<(u64, f64)>>, oneshot::Sender<()>),
+    GetTemplatesHolder(
+        (
+            Uuid,
+            oneshot::Sender<Result<TemplatesHolder, stypes::NativeError>>,
+        ),
+    ),
+    SetTemplatesHolder(
+        (
+            Option<TemplatesHolder>,
+            oneshot::Sender<Result<(), stypes::NativeError>>,
+        ),
+    ),
+    GetFoldsHolder(
+        (
+            Uuid,
+            oneshot::Sender<Result<FoldsHolder, stypes::NativeError>>,
+        ),
+    ),
+    /// Puts back the holder of the folding operation with the given id along with the
+    /// processed rows and the ranges of repeated rows, or `None` if folding failed.
+    #[allow(clippy::type_complexity)]
+    SetFoldsHolder(
+        (
+            Uuid,
+            Option<(FoldsHolder, Range<u64>, Vec<RangeInclusive<u64>>)>,
+            oneshot::Sender<Result<(), stypes::NativeError>>,
+        ),
+    ),
+    /// Hides the given templates from the list of templates or shows all templates if `None`.
+    /// Rows of hidden templates stay in the stream.
+    HideTemplates(
+        (
+            Option<Vec<u32>>,
+            oneshot::Sender<Result<(), stypes::NativeError>>,
+        ),
+    ),
     #[allow(clippy::type_complexity)]
     GetSearchValues(
         (
@@ -188,6 +291,15 @@ pub enum Api {
         ),
     ),
     DropSearchValues(oneshot::Sender<bool>),
+    SetSearchValuesSeries(Vec<stypes::ValueSeries>, oneshot::Sender<()>),
+    GetSearchValuesSeries(oneshot::Sender<Vec<stypes::ValueSeries>>),
+    #[allow(clippy::type_complexity)]
+    GetSearchValuesStatistics(
+        (
+            Option<RangeInclusive<u64>>,
+            oneshot::Sender<Result<Vec<stypes::ValueStatistics>, ValuesError>>,
+        ),
+    ),
     GetIndexedRanges(oneshot::Sender<Vec<RangeInclusive<u64>>>),
     CloseSession(oneshot::Sender<()>),
     SetDebugMode((bool, oneshot::Sender<()>)),
@@ -210,6 +322,7 @@ impl Display for Api {
                 Self::GetSessionFile(_) => "GetSessionFile",
                 Self::WriteSessionFile(_) => "WriteSessionFile",
                 Self::FlushSessionFile(_) => "FlushSessionFile",
+                Self::UnlinkSessionFile(_) => "UnlinkSessionFile",
                 Self::GetSessionFileOrigin(_) => "GetSessionFileOrigin",
                 Self::UpdateSession(_) => "UpdateSession",
                 Self::AddSource(_) => "AddSource",
@@ -218,6 +331,8 @@ impl Display for Api {
                 Self::AddExecutedObserve(_) => "AddExecutedObserve",
                 Self::GetExecutedHolder(_) => "GetExecutedHolder",
                 Self::IsRawExportAvailable(_) => "IsRawExportAvailable",
+                Self::GetDltStatistics(_) => "GetDltStatistics",
+                Self::GetTriggers(_) => "GetTriggers",
                 Self::ExportSession { .. } => "ExportSession",
                 Self::FileRead(_) => "FileRead",
                 Self::Grab(_) => "Grab",
@@ -227,6 +342,8 @@ impl Display for Api {
                 Self::SetSearchHolder(_) => "SetSearchHolder",
                 Self::DropSearch(_) => "DropSearch",
                 Self::GrabSearch(_) => "GrabSearch",
+                Self::GrabSearchContext(_) => "GrabSearchContext",
+                Self::GetSearchContext(_) => "GetSearchContext",
                 Self::SearchNestedMatch(_) => "SearchNestedMatch",
                 Self::GrabIndexed(_) => "GrabIndexed",
                 Self::SetIndexingMode(_) => "SetIndexingMode",
@@ -236,15 +353,34 @@ impl Display for Api {
                 Self::SetBookmarks(_) => "SetBookmarks",
                 Self::RemoveBookmark(_) => "RemoveBookmark",
                 Self::ExpandBreadcrumbs { .. } => "ExpandBreadcrumbs",
+                Self::ExpandFold(_) => "ExpandFold",
+                Self::GetFolds(_) => "GetFolds",
                 Self::GrabRanges(_) => "GrabRanges",
                 Self::GetNearestPosition(_) => "GetNearestPosition",
+                Self::SetTimestampExtractor(_) => "SetTimestampExtractor",
+                Self::GetTimestampsCursor(_) => "GetTimestampsCursor",
+                Self::IsTimestampsAvailable(_) => "IsTimestampsAvailable",
+                Self::UpdateTimestamps(_) => "UpdateTimestamps",
+                Self::SetIndexedTimestamps(_) => "SetIndexedTimestamps",
+                Self::GetTimestampPoints(_) => "GetTimestampPoints",
+                Self::GetNearestTimestamp(_) => "GetNearestTimestamp",
+                Self::GetTimeRange(_) => "GetTimeRange",
+                Self::GetTimeDistribution(_) => "GetTimeDistribution",
                 Self::GetScaledMap(_) => "GetScaledMap",
                 Self::SetMatches(_) => "SetMatches",
                 Self::GetSearchValuesHolder(_) => "GetSearchValuesHolder",
                 Self::SetSearchValuesHolder(_) => "SetSearchValuesHolder",
                 Self::SetSearchValues(_, _) => "SetSearchValues",
+                Self::GetTemplatesHolder(_) => "GetTemplatesHolder",
+                Self::SetTemplatesHolder(_) => "SetTemplatesHolder",
+                Self::GetFoldsHolder(_) => "GetFoldsHolder",
+                Self::SetFoldsHolder(_) => "SetFoldsHolder",
+                Self::HideTemplates(_) => "HideTemplates",
                 Self::GetSearchValues(_) => "GetSearchValues",
                 Self::DropSearchValues(_) => "DropSearchValues",
+                Self::SetSearchValuesSeries(_, _) => "SetSearchValuesSeries",
+                Self::GetSearchValuesSeries(_) => "GetSearchValuesSeries",
+                Self::GetSearchValuesStatistics(_) => "GetSearchValuesStatistics",
                 Self::GetIndexedRanges(_) => "GetIndexedRanges",
                 Self::CloseSession(_) => "CloseSession",
                 Self::SetDebugMode(_) => "SetDebugMode",
@@ -309,7 +445,9 @@ impl SessionStateAPI {
             .await?
     }
 
-    pub async fn set_indexing_mode(&self, mode: IndexesMode) -> Result<(), stypes::NativeError> {
+    /// Sets the mode of the indexed map. Returns `true` if the rows have to be folded by
+    /// an operation.
+    pub async fn set_indexing_mode(&self, mode: IndexesMode) -> Result<bool, stypes::NativeError> {
         let (tx, rx) = oneshot::channel();
         self.exec_operation(Api::SetIndexingMode((mode, tx)), rx)
             .await?
@@ -365,6 +503,19 @@ impl SessionStateAPI {
         .await?
     }
 
+    pub async fn expand_fold(&self, row: u64) -> Result<(), stypes::NativeError> {
+        let (tx, rx) = oneshot::channel();
+        self.exec_operation(Api::ExpandFold((row, tx)), rx).await?
+    }
+
+    pub async fn get_folds(
+        &self,
+        range: RangeInclusive<u64>,
+    ) -> Result<Vec<stypes::Fold>, stypes::NativeError> {
+        let (tx, rx) = oneshot::channel();
+        self.exec_operation(Api::GetFolds((range, tx)), rx).await
+    }
+
     pub async fn grab_search(
         &self,
         range: LineRange,
@@ -374,6 +525,31 @@ impl SessionStateAPI {
             .await?
     }
 
+    /// Grabs the rows with the matches within the range of search results and `before`/`after`
+    /// rows around each match. Overlapping windows are merged.
+    pub async fn grab_search_context(
+        &self,
+        range: LineRange,
+        before: u64,
+        after: u64,
+    ) -> Result<Vec<GrabbedElement>, stypes::NativeError> {
+        let (tx, rx) = oneshot::channel();
+        self.exec_operation(Api::GrabSearchContext((range, before, after, tx)), rx)
+            .await?
+    }
+
+    /// Returns the ranges of rows with all search matches and `before`/`after` rows around
+    /// each match.
+    pub async fn get_search_context(
+        &self,
+        before: u64,
+        after: u64,
+    ) -> Result<Vec<RangeInclusive<u64>>, stypes::NativeError> {
+        let (tx, rx) = oneshot::channel();
+        self.exec_operation(Api::GetSearchContext((before, after, tx)), rx)
+            .await?
+    }
+
     pub async fn search_nested_match(
         &self,
         filter: SearchFilter,
@@ -413,6 +589,73 @@ impl SessionStateAPI {
             .await
     }
 
+    pub async fn set_timestamp_extractor(
+        &self,
+        extractor: Option<TimestampExtractor>,
+    ) -> Result<(), stypes::NativeError> {
+        let (tx, rx) = oneshot::channel();
+        self.exec_operation(Api::SetTimestampExtractor((extractor, tx)), rx)
+            .await
+    }
+
+    pub async fn get_timestamps_cursor(
+        &self,
+    ) -> Result<Option<TimestampsCursor>, stypes::NativeError> {
+        let (tx, rx) = oneshot::channel();
+        self.exec_operation(Api::GetTimestampsCursor(tx), rx).await
+    }
+
+    /// Returns `true` if rows have timestamps, either extracted from their text or taken
+    /// from their messages.
+    pub async fn is_timestamps_available(&self) -> Result<bool, stypes::NativeError> {
+        let (tx, rx) = oneshot::channel();
+        self.exec_operation(Api::IsTimestampsAvailable(tx), rx)
+            .await
+    }
+
+    pub async fn update_timestamps(
+        &self,
+        update: TimestampsUpdate,
+    ) -> Result<(), stypes::NativeError> {
+        let (tx, rx) = oneshot::channel();
+        self.exec_operation(Api::UpdateTimestamps((update, tx)), rx)
+            .await?
+    }
+
+    pub async fn get_timestamp_points(
+        &self,
+    ) -> Result<(Vec<(u64, u64)>, u64), stypes::NativeError> {
+        let (tx, rx) = oneshot::channel();
+        self.exec_operation(Api::GetTimestampPoints(tx), rx).await
+    }
+
+    pub async fn get_nearest_timestamp(
+        &self,
+        timestamp: u64,
+    ) -> Result<stypes::ResultNearestTimestamp, stypes::NativeError> {
+        let (tx, rx) = oneshot::channel();
+        self.exec_operation(Api::GetNearestTimestamp((timestamp, tx)), rx)
+            .await
+    }
+
+    pub async fn get_time_range(
+        &self,
+        period: RangeInclusive<u64>,
+    ) -> Result<stypes::ResultTimeRange, stypes::NativeError> {
+        let (tx, rx) = oneshot::channel();
+        self.exec_operation(Api::GetTimeRange((period, tx)), rx)
+            .await
+    }
+
+    pub async fn get_time_distribution(
+        &self,
+        bucket: u64,
+    ) -> Result<stypes::ResultTimeDistribution, stypes::NativeError> {
+        let (tx, rx) = oneshot::channel();
+        self.exec_operation(Api::GetTimeDistribution((bucket, tx)), rx)
+            .await
+    }
+
     pub async fn get_scaled_map(
         &self,
         dataset_len: u16,
@@ -437,13 +680,17 @@ impl SessionStateAPI {
         self.exec_operation(Api::GetSessionFile(tx), rx).await?
     }
 
+    /// Writes the message into the session file and returns the position of its first row.
+    /// The timestamp of the message (if its format carries one) is added to the index of
+    /// timestamps.
     pub async fn write_session_file(
         &self,
         source_id: u16,
         msg: String,
-    ) -> Result<(), stypes::NativeError> {
+        timestamp: Option<u64>,
+    ) -> Result<u64, stypes::NativeError> {
         let (tx, rx) = oneshot::channel();
-        self.exec_operation(Api::WriteSessionFile((source_id, msg, tx)), rx)
+        self.exec_operation(Api::WriteSessionFile((source_id, msg, timestamp, tx)), rx)
             .await?
     }
 
@@ -452,6 +699,19 @@ impl SessionStateAPI {
         self.exec_operation(Api::FlushSessionFile(tx), rx).await?
     }
 
+    /// Switches the session from the linked file to the generated file with its copy, so the
+    /// session can continue with writing content after the linked file was replaced.
+    /// See [`super::session_file::SessionFile::unlink()`].
+    pub async fn unlink_session_file(
+        &self,
+        source_id: u16,
+        copy: LinkedCopy,
+    ) -> Result<(), stypes::NativeError> {
+        let (tx, rx) = oneshot::channel();
+        self.exec_operation(Api::UnlinkSessionFile((source_id, copy, tx)), rx)
+            .await?
+    }
+
     pub async fn get_session_file_origin(
         &self,
     ) -> Result<Option<SessionFileOrigin>, stypes::NativeError> {
@@ -506,6 +766,17 @@ impl SessionStateAPI {
             .await
     }
 
+    pub async fn get_dlt_statistics(&self) -> Result<SharedDltStatistics, stypes::NativeError> {
+        let (tx_response, rx) = oneshot::channel();
+        self.exec_operation(Api::GetDltStatistics(tx_response), rx)
+            .await
+    }
+
+    pub async fn get_triggers(&self) -> Result<SharedTriggers, stypes::NativeError> {
+        let (tx_response, rx) = oneshot::channel();
+        self.exec_operation(Api::GetTriggers(tx_response), rx).await
+    }
+
     /// Exports data to the specified output path with the given parameters. This method is used to export
     /// only into text format.
     ///
@@ -652,6 +923,73 @@ impl SessionStateAPI {
         self.exec_operation(Api::DropSearchValues(tx), rx).await
     }
 
+    pub async fn set_search_values_series(
+        &self,
+        series: Vec<stypes::ValueSeries>,
+    ) -> Result<(), stypes::NativeError> {
+        let (tx, rx) = oneshot::channel();
+        self.exec_operation(Api::SetSearchValuesSeries(series, tx), rx)
+            .await
+    }
+
+    pub async fn get_search_values_series(
+        &self,
+    ) -> Result<Vec<stypes::ValueSeries>, stypes::NativeError> {
+        let (tx, rx) = oneshot::channel();
+        self.exec_operation(Api::GetSearchValuesSeries(tx), rx)
+            .await
+    }
+
+    pub async fn get_search_values_statistics(
+        &self,
+        frame: Option<RangeInclusive<u64>>,
+    ) -> Result<Vec<stypes::ValueStatistics>, stypes::NativeError> {
+        let (tx, rx) = oneshot::channel();
+        self.exec_operation(Api::GetSearchValuesStatistics((frame, tx)), rx)
+            .await?
+            .map_err(|e| e.into())
+    }
+
+    pub async fn get_templates_holder(
+        &self,
+        uuid: Uuid,
+    ) -> Result<TemplatesHolder, stypes::NativeError> {
+        let (tx, rx) = oneshot::channel();
+        self.exec_operation(Api::GetTemplatesHolder((uuid, tx)), rx)
+            .await?
+    }
+
+    pub async fn set_templates_holder(
+        &self,
+        holder: Option<TemplatesHolder>,
+    ) -> Result<(), stypes::NativeError> {
+        let (tx, rx) = oneshot::channel();
+        self.exec_operation(Api::SetTemplatesHolder((holder, tx)), rx)
+            .await?
+    }
+
+    pub async fn get_folds_holder(&self, uuid: Uuid) -> Result<FoldsHolder, stypes::NativeError> {
+        let (tx, rx) = oneshot::channel();
+        self.exec_operation(Api::GetFoldsHolder((uuid, tx)), rx)
+            .await?
+    }
+
+    pub async fn set_folds_holder(
+        &self,
+        uuid: Uuid,
+        folded: Option<(FoldsHolder, Range<u64>, Vec<RangeInclusive<u64>>)>,
+    ) -> Result<(), stypes::NativeError> {
+        let (tx, rx) = oneshot::channel();
+        self.exec_operation(Api::SetFoldsHolder((uuid, folded, tx)), rx)
+            .await?
+    }
+
+    pub async fn hide_templates(&self, ids: Option<Vec<u32>>) -> Result<(), stypes::NativeError> {
+        let (tx, rx) = oneshot::channel();
+        self.exec_operation(Api::HideTemplates((ids, tx)), rx)
+            .await?
+    }
+
     pub async fn get_indexed_ranges(
         &self,
     ) -> Result<Vec<RangeInclusive<u64>>, stypes::NativeError> {
@@ -688,6 +1026,19 @@ impl SessionStateAPI {
         })
     }
 
+    pub fn set_indexed_timestamps(
+        &self,
+        result: Result<Option<TimestampsUpdate>, stypes::NativeError>,
+    ) -> Result<(), stypes::NativeError> {
+        self.tx_api
+            .send(Api::SetIndexedTimestamps(result))
+            .map_err(|e| {
+                stypes::NativeError::channel(&format!(
+                    "fail to send to Api::SetIndexedTimestamps; error: {e}",
+                ))
+            })
+    }
+
     pub fn add_attachment(&self, origin: parsers::Attachment) -> Result<(), stypes::NativeError> {
         self.tx_api.send(Api::AddAttachment(origin)).map_err(|e| {
             stypes::NativeError::channel(
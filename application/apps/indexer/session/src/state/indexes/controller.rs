@@ -1,6 +1,6 @@
 use super::{frame::Frame, map::Map, nature::Nature};
 use log::error;
-use std::ops::RangeInclusive;
+use std::ops::{Range, RangeInclusive};
 use tokio::sync::mpsc::UnboundedSender;
 
 const MIN_BREADCRUMBS_DISTANCE: u64 = 4;
@@ -11,6 +11,10 @@ const MIN_BREADCRUMBS_OFFSET: u64 = 2;
 pub enum Mode {
     Regular = 0u8,
     Breadcrumbs = 1u8,
+    /// Consecutive identical rows are folded into one row
+    Folding = 2u8,
+    /// Consecutive rows with the same template are folded into one row
+    FoldingTemplates = 3u8,
 }
 
 #[derive(Debug)]
@@ -39,6 +43,9 @@ impl Controller {
                 self.map.clean(Nature::BREADCRUMB_SEPORATOR);
                 self.map.clean(Nature::EXPANDED);
             }
+            Mode::Folding | Mode::FoldingTemplates => {
+                self.map.folds_clean();
+            }
             Mode::Regular => {
                 // Nothing to do
             }
@@ -52,6 +59,33 @@ impl Controller {
         Ok(())
     }
 
+    pub(crate) fn mode(&self) -> &Mode {
+        &self.mode
+    }
+
+    pub(crate) fn is_folding(&self) -> bool {
+        matches!(self.mode, Mode::Folding | Mode::FoldingTemplates)
+    }
+
+    /// Adds rows of the stream in the folding modes; `repeats` are the ranges of rows
+    /// repeating their previous rows.
+    pub(crate) fn append_folds(
+        &mut self,
+        rows: Range<u64>,
+        repeats: &[RangeInclusive<u64>],
+    ) -> Result<(), stypes::NativeError> {
+        if !self.is_folding() {
+            return Err(stypes::NativeError {
+                severity: stypes::Severity::ERROR,
+                kind: stypes::NativeErrorKind::Grabber,
+                message: Some(format!("Rows cannot be folded in {:?} mode", self.mode)),
+            });
+        }
+        self.map.folds_append(rows, repeats);
+        self.notify();
+        Ok(())
+    }
+
     pub(crate) fn add_bookmark(&mut self, row: u64) -> Result<(), stypes::NativeError> {
         if matches!(self.mode, Mode::Breadcrumbs) {
             self.map.breadcrumbs_insert_and_update(
@@ -62,6 +96,7 @@ impl Controller {
             )?;
         } else {
             self.map.insert(&[row], Nature::BOOKMARK);
+            self.map.folds_split(&[row]);
         }
         self.notify();
         Ok(())
@@ -92,6 +127,7 @@ impl Controller {
         } else {
             self.map.remove(&rows, Nature::BOOKMARK);
             self.map.insert(&rows, Nature::BOOKMARK);
+            self.map.folds_split(&rows);
         }
         self.notify();
         Ok(())
@@ -133,6 +169,7 @@ impl Controller {
         );
         let collected = matches.iter().map(|f| f.index).collect::<Vec<u64>>();
         self.map.insert(&collected, Nature::SEARCH);
+        self.map.folds_split(&collected);
         if matches!(self.mode, Mode::Breadcrumbs) {
             self.map
                 .breadcrumbs_build(MIN_BREADCRUMBS_DISTANCE, MIN_BREADCRUMBS_OFFSET)?;
@@ -153,10 +190,9 @@ impl Controller {
                 2,
             )?
         } else {
-            self.map.insert(
-                &matches.iter().map(|f| f.index).collect::<Vec<u64>>(),
-                Nature::SEARCH,
-            );
+            let collected = matches.iter().map(|f| f.index).collect::<Vec<u64>>();
+            self.map.insert(&collected, Nature::SEARCH);
+            self.map.folds_split(&collected);
         }
         self.notify();
         Ok(())
@@ -184,6 +220,16 @@ impl Controller {
         Ok(())
     }
 
+    pub(crate) fn folds_expand(&mut self, row: u64) -> Result<(), stypes::NativeError> {
+        self.map.folds_expand(row)?;
+        self.notify();
+        Ok(())
+    }
+
+    pub(crate) fn get_folds(&self, range: RangeInclusive<u64>) -> Vec<(u64, u64)> {
+        self.map.folds_within(range)
+    }
+
     pub(crate) fn frame(
         &mut self,
         range: &mut RangeInclusive<u64>,
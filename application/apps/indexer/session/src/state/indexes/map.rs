@@ -1,7 +1,11 @@
 use super::{frame::Frame, keys::Keys, nature::Nature};
 use log::error;
 use rustc_hash::FxHashMap;
-use std::{cmp, ops::RangeInclusive};
+use std::{
+    cmp,
+    collections::BTreeMap,
+    ops::{Range, RangeInclusive},
+};
 
 // This trigger is used to choose a way to remove key from a map.
 // If count of keys, which should be removed grander than TRIGGER,
@@ -45,6 +49,8 @@ const RANGES_LIMIT: usize = 10000; // based on bench test < 100K
 pub struct Map {
     indexes: FxHashMap<u64, Nature>,
     keys: Keys,
+    /// Last rows of the folds by the rows shown instead of them (folding modes).
+    folds: BTreeMap<u64, u64>,
     pub stream_len: u64,
 }
 
@@ -53,6 +59,7 @@ impl Map {
         Self {
             indexes: FxHashMap::default(),
             keys: Keys::new(),
+            folds: BTreeMap::new(),
             stream_len: 0,
         }
     }
@@ -675,6 +682,110 @@ impl Map {
         Ok(result)
     }
 
+    /// Adds the given rows of the stream in the folding modes. Rows repeating their previous
+    /// rows are hidden, while the first row of each run of repeated rows is shown as
+    /// `Nature::FOLDED`. A run might continue the fold of previously added rows. Search
+    /// results and bookmarks within a run stay visible and split its fold (see
+    /// [`Self::folds_split`]).
+    pub fn folds_append(&mut self, rows: Range<u64>, repeats: &[RangeInclusive<u64>]) {
+        let mut shown: Vec<u64> = vec![];
+        let mut next = rows.start;
+        for repeated in repeats.iter() {
+            let start = *repeated.start();
+            if start == 0 {
+                // The first row cannot repeat anything
+                continue;
+            }
+            shown.extend(next..start);
+            let head = match self.folds.range(..start).next_back() {
+                Some((head, last)) if last + 1 == start => *head,
+                _ => start - 1,
+            };
+            self.folds.insert(head, *repeated.end());
+            self.insert(&[head], Nature::FOLDED);
+            next = repeated.end() + 1;
+        }
+        shown.extend(next..rows.end);
+        self.insert(&shown, Nature::UNFOLDED);
+        let pinned = repeats
+            .iter()
+            .flat_map(|repeated| repeated.clone())
+            .filter(|row| {
+                self.indexes
+                    .get(row)
+                    .is_some_and(|nature| nature.is_search() || nature.is_bookmark())
+            })
+            .collect::<Vec<u64>>();
+        self.folds_split(&pinned);
+    }
+
+    /// Ends the folds hiding any of the given rows before these rows, so they are shown
+    /// (e.g. search results or bookmarks). The rest of a fold is folded into the shown row.
+    pub fn folds_split(&mut self, rows: &[u64]) {
+        if self.folds.is_empty() {
+            return;
+        }
+        let mut shown: Vec<u64> = vec![];
+        for row in rows.iter() {
+            let Some((head, last)) = self
+                .folds
+                .range(..*row)
+                .next_back()
+                .map(|(head, last)| (*head, *last))
+            else {
+                continue;
+            };
+            if last < *row {
+                continue;
+            }
+            if head + 1 < *row {
+                self.folds.insert(head, row - 1);
+            } else {
+                self.folds.remove(&head);
+                self.remove(&[head], Nature::FOLDED);
+            }
+            if last > *row {
+                self.folds.insert(*row, last);
+                self.insert(&[*row], Nature::FOLDED);
+            }
+            shown.push(*row);
+        }
+        self.insert(&shown, Nature::UNFOLDED);
+    }
+
+    /// Shows the rows hidden by the fold of the given row.
+    pub fn folds_expand(&mut self, row: u64) -> Result<(), stypes::NativeError> {
+        let last = self.folds.remove(&row).ok_or(stypes::NativeError {
+            severity: stypes::Severity::ERROR,
+            kind: stypes::NativeErrorKind::Grabber,
+            message: Some(format!("Index {row} isn't Nature::FOLDED.")),
+        })?;
+        self.remove(&[row], Nature::FOLDED);
+        self.insert_range(
+            RangeInclusive::new(row + 1, last),
+            Nature::UNFOLDED.union(Nature::EXPANDED),
+        );
+        Ok(())
+    }
+
+    /// Returns the folds shown within the given range of rows as `(row, last row)`.
+    pub fn folds_within(&self, range: RangeInclusive<u64>) -> Vec<(u64, u64)> {
+        self.folds
+            .range(range)
+            .map(|(row, last)| (*row, *last))
+            .collect()
+    }
+
+    /// Removes all rows added in the folding modes, keeping other natures of the rows.
+    pub fn folds_clean(&mut self) {
+        self.folds.clear();
+        self.clean(
+            Nature::FOLDED
+                .union(Nature::UNFOLDED)
+                .union(Nature::EXPANDED),
+        );
+    }
+
     pub fn clean(&mut self, nature: Nature) {
         let mut to_be_removed: Vec<u64> = vec![];
         self.indexes.iter_mut().for_each(|(position, index)| {
@@ -701,6 +812,7 @@ impl Map {
         if self.stream_len == 0 {
             self.indexes.clear();
             self.keys.clear();
+            self.folds.clear();
             return Ok(());
         }
         if update_breadcrumbs {
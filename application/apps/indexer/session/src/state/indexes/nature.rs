@@ -3,6 +3,10 @@ pub struct Nature(u8);
 impl Nature {
     pub const SEARCH: Nature = Nature(1);
     pub const BOOKMARK: Nature = Nature(1 << 1);
+    /// Row shown instead of the following rows repeating it (folding modes).
+    pub const FOLDED: Nature = Nature(1 << 2);
+    /// Row shown in the folding modes.
+    pub const UNFOLDED: Nature = Nature(1 << 3);
     pub const EXPANDED: Nature = Nature(1 << 5);
     pub const BREADCRUMB: Nature = Nature(1 << 6);
     pub const BREADCRUMB_SEPORATOR: Nature = Nature(1 << 7);
@@ -15,7 +19,7 @@ impl Nature {
 impl TryFrom<u8> for Nature {
     type Error = stypes::NativeError;
     fn try_from(n: u8) -> Result<Self, Self::Error> {
-        if 0b00111100 & n > 0 {
+        if 0b00110000 & n > 0 {
             return Err(stypes::NativeError {
                 severity: stypes::Severity::ERROR,
                 kind: stypes::NativeErrorKind::Grabber,
@@ -109,6 +113,14 @@ impl Nature {
         self.contains(&Nature::BREADCRUMB_SEPORATOR)
     }
 
+    pub fn is_folded(&self) -> bool {
+        self.contains(&Nature::FOLDED)
+    }
+
+    pub fn is_unfolded(&self) -> bool {
+        self.contains(&Nature::UNFOLDED)
+    }
+
     pub fn is_expanded(&self) -> bool {
         self.contains(&Nature::EXPANDED)
     }
@@ -190,6 +202,14 @@ fn test_nature() {
     assert!(!n.is_bookmark());
     assert!(n.is_breadcrumb());
     assert!(!n.is_seporator());
+    let n = Nature::FOLDED.union(Nature::UNFOLDED);
+    assert!(n.is_folded());
+    assert!(n.is_unfolded());
+    assert!(!n.is_search());
+    assert!(!n.is_breadcrumb());
+    let b = Nature::try_from(n.bits()).unwrap();
+    assert!(b.is_folded());
+    assert!(b.is_unfolded());
     let n = Nature::BREADCRUMB_SEPORATOR;
     assert!(!n.is_search());
     assert!(!n.is_bookmark());
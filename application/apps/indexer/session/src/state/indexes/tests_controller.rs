@@ -3,7 +3,7 @@ use super::{
     frame::Frame,
     nature::Nature,
 };
-use std::ops::RangeInclusive;
+use std::ops::{Range, RangeInclusive};
 
 lazy_static::lazy_static! {
     // If you need to execute some specific test, just define a name
@@ -65,6 +65,15 @@ impl ControllerTest {
             Action::AppendSearch(matches) => {
                 self.controller.append_search_results(&matches).unwrap();
             }
+            Action::AppendFolds((rows, repeats)) => {
+                self.controller.append_folds(rows, &repeats).unwrap();
+            }
+            Action::ExpandFold(row) => {
+                self.controller.folds_expand(row).unwrap();
+            }
+            Action::CheckFolds((range, folds)) => {
+                assert_eq!(self.controller.get_folds(range), folds);
+            }
             Action::AddBookmark(position) => {
                 self.controller.add_bookmark(position).unwrap();
             }
@@ -122,7 +131,13 @@ enum Action {
     SetMode(Mode),
     // Extending breadcrumbs
     ExpandBreadcrumbs((u64, u64, bool)),
-    // Insert bookmarks into position (used ONLY in breadcrumbs mode)
+    // Add rows in folding modes (rows, repeating rows)
+    AppendFolds((Range<u64>, Vec<RangeInclusive<u64>>)),
+    // Expanding fold
+    ExpandFold(u64),
+    // Checks folds within range (row, last row)
+    CheckFolds((RangeInclusive<u64>, Vec<(u64, u64)>)),
+    // Insert bookmarks into position (used in breadcrumbs and folding modes)
     AddBookmark(u64),
     // Remove bookmarks from position (used ONLY in breadcrumbs mode)
     RemoveBookmark(u64),
@@ -143,6 +158,10 @@ impl std::fmt::Display for Action {
                 Action::SetMode(mode) => format!("BuildBreadcrumbs - {mode:?}"),
                 Action::ExpandBreadcrumbs((sep, offset, above)) =>
                     format!("ExpandBreadcrumbs - sep={sep}; offset={offset}; above={above}"),
+                Action::AppendFolds((rows, repeats)) =>
+                    format!("AppendFolds - rows={rows:?}; repeats={repeats:?}"),
+                Action::ExpandFold(row) => format!("ExpandFold - {row}"),
+                Action::CheckFolds((range, _)) => format!("CheckFolds - {range:?}"),
                 Action::AddBookmark(pos) => format!("AddBookmark - {pos}"),
                 Action::RemoveBookmark(pos) => format!("RemoveBookmark - {pos}"),
                 Action::Frame((range, _)) => format!("Frame - {range:?}"),
@@ -1779,6 +1798,85 @@ fn test() {
                 )),
             ],
         ),
+        ControllerTest::create(
+            "024",
+            vec![
+                Action::StreamLen(10),
+                Action::Search(vec![stypes::FilterMatch::new(3, vec![])]),
+                Action::SetMode(Mode::Folding),
+                Action::AppendFolds((0..6, vec![2..=3, 5..=5])),
+                Action::AppendFolds((6..10, vec![6..=7])),
+                Action::CheckLen(6),
+                // The search result ends the fold, so it isn't hidden by it.
+                Action::CheckFolds((0..=9, vec![(1, 2), (4, 7)])),
+                Action::Frame((
+                    None,
+                    vec![
+                        (0, Nature::UNFOLDED),
+                        (1, Nature::UNFOLDED.union(Nature::FOLDED)),
+                        (3, Nature::SEARCH.union(Nature::UNFOLDED)),
+                        (4, Nature::UNFOLDED.union(Nature::FOLDED)),
+                        (8, Nature::UNFOLDED),
+                        (9, Nature::UNFOLDED),
+                    ],
+                )),
+                Action::ExpandFold(1),
+                Action::CheckLen(7),
+                Action::CheckFolds((0..=9, vec![(4, 7)])),
+                Action::Frame((
+                    None,
+                    vec![
+                        (0, Nature::UNFOLDED),
+                        (1, Nature::UNFOLDED),
+                        (2, Nature::UNFOLDED.union(Nature::EXPANDED)),
+                        (3, Nature::SEARCH.union(Nature::UNFOLDED)),
+                        (4, Nature::UNFOLDED.union(Nature::FOLDED)),
+                        (8, Nature::UNFOLDED),
+                        (9, Nature::UNFOLDED),
+                    ],
+                )),
+                Action::SetMode(Mode::Regular),
+                Action::CheckLen(1),
+                Action::CheckFolds((0..=9, vec![])),
+                Action::Frame((None, vec![(3, Nature::SEARCH)])),
+            ],
+        ),
+        ControllerTest::create(
+            "025",
+            vec![
+                Action::StreamLen(10),
+                Action::SetMode(Mode::Folding),
+                Action::AppendFolds((0..10, vec![1..=5])),
+                Action::CheckLen(5),
+                Action::CheckFolds((0..=9, vec![(0, 5)])),
+                // Search results and bookmarks added later split the folds hiding them.
+                Action::AppendSearch(vec![stypes::FilterMatch::new(3, vec![])]),
+                Action::CheckFolds((0..=9, vec![(0, 2), (3, 5)])),
+                Action::AddBookmark(1),
+                Action::CheckLen(7),
+                Action::CheckFolds((0..=9, vec![(1, 2), (3, 5)])),
+                Action::Frame((
+                    None,
+                    vec![
+                        (0, Nature::UNFOLDED),
+                        (
+                            1,
+                            Nature::BOOKMARK
+                                .union(Nature::UNFOLDED)
+                                .union(Nature::FOLDED),
+                        ),
+                        (
+                            3,
+                            Nature::SEARCH.union(Nature::UNFOLDED).union(Nature::FOLDED),
+                        ),
+                        (6, Nature::UNFOLDED),
+                        (7, Nature::UNFOLDED),
+                        (8, Nature::UNFOLDED),
+                        (9, Nature::UNFOLDED),
+                    ],
+                )),
+            ],
+        ),
     ];
     tests.iter_mut().for_each(|test| {
         while test.next() {}
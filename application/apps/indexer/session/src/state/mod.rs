@@ -1,12 +1,16 @@
 use log::{debug, error};
-use parsers;
+use parsers::{
+    self,
+    dlt::stats::{DltStatistics, SharedDltStatistics},
+};
 use processor::{
     grabber::LineRange,
     map::SearchMap,
     search::{
         filter::SearchFilter,
         searchers::{
-            linear::LineSearcher, regular::RegularSearchHolder, values::ValueSearchHolder,
+            folds::FoldsHolder, linear::LineSearcher, regular::RegularSearchHolder,
+            templates::TemplatesHolder, values::ValueSearchHolder,
         },
     },
 };
@@ -14,10 +18,13 @@ use std::{
     collections::HashMap,
     fs::File,
     io::{BufWriter, Write},
-    ops::RangeInclusive,
+    ops::{Range, RangeInclusive},
     path::PathBuf,
 };
-use tokio::sync::mpsc::{UnboundedReceiver, UnboundedSender};
+use tokio::{
+    sync::mpsc::{UnboundedReceiver, UnboundedSender},
+    task,
+};
 use tokio_util::sync::CancellationToken;
 use uuid::Uuid;
 
@@ -28,6 +35,8 @@ mod observed;
 mod searchers;
 mod session_file;
 mod source_ids;
+pub(crate) mod timestamps;
+pub(crate) mod triggers;
 pub(crate) mod values;
 
 pub use api::{Api, SessionStateAPI};
@@ -40,8 +49,10 @@ pub use indexes::{
 };
 use observed::Observed;
 use searchers::{SearcherState, Searchers};
-pub use session_file::{SessionFile, SessionFileOrigin, SessionFileState};
+pub use session_file::{LinkedCopy, SessionFile, SessionFileOrigin, SessionFileState, copy_linked};
 use stypes::{FilterMatch, GrabbedElement};
+use timestamps::{Timestamps, TimestampsCursor, TimestampsUpdate};
+use triggers::{SharedTriggers, Triggers};
 pub use values::{Values, ValuesError};
 
 #[derive(Debug)]
@@ -57,15 +68,27 @@ pub struct SessionState {
     pub search_map: SearchMap,
     pub indexes: Indexes,
     pub values: Values,
+    pub timestamps: Timestamps,
     pub searchers: Searchers,
     pub attachments: Attachments,
+    pub dlt_statistics: SharedDltStatistics,
+    pub triggers: SharedTriggers,
     pub cancelling_operations: HashMap<Uuid, bool>,
+    /// The operation folding all rows of the session file, if it's running
+    pub folding: Option<Uuid>,
+    /// Whether timestamps of new rows are indexed in the background
+    pub indexing_timestamps: bool,
+    /// Used to send the results of background tasks back to the state
+    pub state_api: SessionStateAPI,
     pub status: Status,
     pub debug: bool,
 }
 
 impl SessionState {
-    fn new(tx_callback_events: UnboundedSender<stypes::CallbackEvent>) -> Self {
+    fn new(
+        state_api: SessionStateAPI,
+        tx_callback_events: UnboundedSender<stypes::CallbackEvent>,
+    ) -> Self {
         Self {
             session_file: SessionFile::new(),
             observed: Observed::new(),
@@ -73,12 +96,20 @@ impl SessionState {
             searchers: Searchers {
                 regular: SearcherState::NotInited,
                 values: SearcherState::NotInited,
+                templates: SearcherState::NotInited,
+                folds: SearcherState::NotInited,
             },
             attachments: Attachments::new(),
+            dlt_statistics: DltStatistics::shared(),
+            triggers: Triggers::shared(),
             indexes: Indexes::new(Some(tx_callback_events.clone())),
             values: Values::new(Some(tx_callback_events)),
+            timestamps: Timestamps::persistent(),
             status: Status::Open,
             cancelling_operations: HashMap::new(),
+            folding: None,
+            indexing_timestamps: false,
+            state_api,
             debug: false,
         }
     }
@@ -160,6 +191,39 @@ impl SessionState {
         Ok(elements)
     }
 
+    /// Returns the ranges of rows with the search matches and `before`/`after` rows around
+    /// each match. Without `range` all matches are considered.
+    fn handle_get_search_context(
+        &mut self,
+        range: Option<RangeInclusive<u64>>,
+        before: u64,
+        after: u64,
+    ) -> Result<Vec<RangeInclusive<u64>>, stypes::NativeError> {
+        let range = match range {
+            Some(range) => range,
+            None if self.search_map.is_empty() => return Ok(Vec::new()),
+            None => 0..=self.search_map.len() as u64 - 1,
+        };
+        let stream_len = self.session_file.len();
+        self.search_map
+            .context(&range, before, after, stream_len)
+            .map_err(|e| stypes::NativeError {
+                severity: stypes::Severity::ERROR,
+                kind: stypes::NativeErrorKind::Grabber,
+                message: Some(format!("{e}")),
+            })
+    }
+
+    fn handle_grab_search_context(
+        &mut self,
+        range: LineRange,
+        before: u64,
+        after: u64,
+    ) -> Result<Vec<GrabbedElement>, stypes::NativeError> {
+        let ranges = self.handle_get_search_context(Some(range.range), before, after)?;
+        self.handle_grab_ranges(ranges)
+    }
+
     /// Handles "nested" search functionality.
     /// A "nested" search refers to filtering matches within the primary search results.
     ///
@@ -264,7 +328,12 @@ impl SessionState {
         state_cancellation_token: CancellationToken,
         tx_callback_events: UnboundedSender<stypes::CallbackEvent>,
         msg: String,
-    ) -> Result<(), stypes::NativeError> {
+        timestamp: Option<u64>,
+    ) -> Result<u64, stypes::NativeError> {
+        let row = self.session_file.next_row();
+        if let Some(timestamp) = timestamp {
+            self.timestamps.add_message(row, timestamp);
+        }
         if matches!(
             self.session_file
                 .write(source_id, state_cancellation_token.clone(), msg)?,
@@ -273,6 +342,27 @@ impl SessionState {
             self.update_searchers(state_cancellation_token, tx_callback_events)
                 .await?;
         }
+        Ok(row)
+    }
+
+    async fn handle_unlink_session_file(
+        &mut self,
+        source_id: u16,
+        copy: LinkedCopy,
+        state_cancellation_token: CancellationToken,
+        tx_callback_events: UnboundedSender<stypes::CallbackEvent>,
+    ) -> Result<(), stypes::NativeError> {
+        let changes = self.session_file.unlink(source_id, copy)?;
+        let filename = self.session_file.filename()?;
+        self.attachments.set_dest_path(filename);
+        self.searchers.regular.relink(&self.session_file);
+        self.searchers.values.relink(&self.session_file);
+        self.searchers.templates.relink(&self.session_file);
+        self.searchers.folds.relink(&self.session_file);
+        if matches!(changes, SessionFileState::Changed) {
+            self.update_searchers(state_cancellation_token, tx_callback_events)
+                .await?;
+        }
         Ok(())
     }
 
@@ -320,6 +410,9 @@ impl SessionState {
         let bytes = self.session_file.read_bytes();
         self.search_map.set_stream_len(rows);
         self.indexes.set_stream_len(rows)?;
+        if self.timestamps.set_stream_len(rows)? {
+            self.update_timestamps(rows, &state_cancellation_token);
+        }
         tx_callback_events.send(stypes::CallbackEvent::StreamUpdated(rows))?;
         match self
             .searchers
@@ -329,6 +422,7 @@ impl SessionState {
             Some(Ok((_processed, mut matches, stats))) => {
                 self.indexes.append_search_results(&matches)?;
                 let updates: stypes::FilterMatchList = (&matches).into();
+                let distribution = self.timestamps.distribution_update(&matches);
                 let found = self.search_map.append(&mut matches) as u64;
                 self.search_map.append_stats(stats);
                 tx_callback_events.send(stypes::CallbackEvent::search_results(
@@ -336,6 +430,10 @@ impl SessionState {
                     self.search_map.get_stats(),
                 ))?;
                 tx_callback_events.send(stypes::CallbackEvent::SearchMapUpdated(Some(updates)))?;
+                if let Some(distribution) = distribution {
+                    tx_callback_events
+                        .send(stypes::CallbackEvent::TimeDistributionUpdated(distribution))?;
+                }
             }
             Some(Err(err)) => error!("Fail to append search: {err}"),
             None => (),
@@ -343,7 +441,7 @@ impl SessionState {
         match self
             .searchers
             .values
-            .search(rows, bytes, state_cancellation_token)
+            .search(rows, bytes, state_cancellation_token.clone())
         {
             Some(Ok((_processed, values))) => {
                 self.values.append_values(values);
@@ -351,9 +449,189 @@ impl SessionState {
             Some(Err(err)) => error!("Fail to update search values: {err}"),
             None => (),
         }
+        match self
+            .searchers
+            .folds
+            .search(rows, bytes, state_cancellation_token)
+        {
+            Some(Ok((processed, repeats))) => {
+                self.indexes
+                    .append_folds(processed.start as u64..processed.end as u64, &repeats)?;
+            }
+            Some(Err(err)) => error!("Fail to update folds: {err}"),
+            None => (),
+        }
         Ok(())
     }
 
+    /// Extracts the timestamps of new rows of the session file. Only called while all
+    /// previous rows are indexed, otherwise the index is extended by the operations which
+    /// need it.
+    ///
+    /// Reading the rows blocks, therefore they are indexed in the background and the result
+    /// is sent back to the state (see [`Self::handle_set_indexed_timestamps`]). Rows added
+    /// meanwhile are indexed once the result arrives.
+    fn update_timestamps(&mut self, rows: u64, cancel: &CancellationToken) {
+        if self.indexing_timestamps {
+            return;
+        }
+        let Some(cursor) = self.handle_get_timestamps_cursor() else {
+            return;
+        };
+        let filename = match self.session_file.filename() {
+            Ok(filename) => filename,
+            Err(err) => {
+                error!("Fail to update timestamps: {err:?}");
+                return;
+            }
+        };
+        self.indexing_timestamps = true;
+        let state_api = self.state_api.clone();
+        let cancel = cancel.clone();
+        tokio::spawn(async move {
+            let result = task::spawn_blocking(move || {
+                timestamps::index_rows(&filename, cursor, rows, &cancel)
+            })
+            .await
+            .unwrap_or_else(|err| {
+                Err(stypes::NativeError {
+                    severity: stypes::Severity::ERROR,
+                    kind: stypes::NativeErrorKind::ComputationFailed,
+                    message: Some(format!("Fail to index timestamps: {err}")),
+                })
+            });
+            if let Err(err) = state_api.set_indexed_timestamps(result) {
+                error!("Fail to send indexed timestamps: {err:?}");
+            }
+        });
+    }
+
+    /// Extends the index by the timestamps indexed in the background, adds the search
+    /// matches of the indexed rows to the time distribution and continues with the rows
+    /// added meanwhile. Rows aren't indexed further if the settings changed meanwhile.
+    fn handle_set_indexed_timestamps(
+        &mut self,
+        result: Result<Option<TimestampsUpdate>, stypes::NativeError>,
+        cancel: &CancellationToken,
+        tx_callback_events: UnboundedSender<stypes::CallbackEvent>,
+    ) -> Result<(), stypes::NativeError> {
+        self.indexing_timestamps = false;
+        // Indexing was cancelled
+        let Some(update) = result? else {
+            return Ok(());
+        };
+        let generation = update.generation;
+        let indexed = self.timestamps.cursor().map_or(0, |cursor| cursor.rows);
+        self.timestamps.update(update)?;
+        // Matches of rows without timestamps weren't counted while searching.
+        let matches = &self.search_map.matches;
+        let from = matches.partition_point(|matched| matched.index < indexed);
+        if let Some(distribution) = self.timestamps.distribution_update(&matches[from..]) {
+            tx_callback_events
+                .send(stypes::CallbackEvent::TimeDistributionUpdated(distribution))?;
+        }
+        let rows = self.session_file.len();
+        if self
+            .timestamps
+            .cursor()
+            .is_some_and(|cursor| cursor.generation == generation && cursor.rows < rows)
+        {
+            self.update_timestamps(rows, cancel);
+        }
+        Ok(())
+    }
+
+    /// Sets the mode of the indexed map. Returns `true` if the mode is switched to a folding
+    /// mode, so all rows of the session file have to be compared by an operation (see
+    /// [`Self::handle_get_folds_holder`]) to find repeated rows. Rows added later are
+    /// compared on updates of the file.
+    fn handle_set_indexing_mode(&mut self, mode: IndexesMode) -> Result<bool, stypes::NativeError> {
+        if self.indexes.mode() == &mode {
+            return Ok(false);
+        }
+        // Results of a running folding operation are outdated now.
+        self.folding = None;
+        self.searchers.folds.not_inited();
+        self.indexes.set_mode(mode)?;
+        Ok(self.indexes.is_folding())
+    }
+
+    /// Returns a new holder to fold all rows of the session file in the current folding
+    /// mode. Only the results of the latest operation are accepted.
+    fn handle_get_folds_holder(&mut self, uuid: Uuid) -> Result<FoldsHolder, stypes::NativeError> {
+        let by_template = match self.indexes.mode() {
+            IndexesMode::Folding => false,
+            IndexesMode::FoldingTemplates => true,
+            IndexesMode::Regular | IndexesMode::Breadcrumbs => {
+                return Err(stypes::NativeError {
+                    severity: stypes::Severity::WARNING,
+                    kind: stypes::NativeErrorKind::Configuration,
+                    message: Some(String::from("Rows are folded only in the folding modes")),
+                });
+            }
+        };
+        let filename = self.session_file.filename()?;
+        self.folding = Some(uuid);
+        self.searchers.folds.in_use();
+        Ok(FoldsHolder::folding(&filename, uuid, by_template))
+    }
+
+    /// Puts back the holder of the folding operation and adds its results to the indexed
+    /// map. Results of outdated operations are dropped.
+    fn handle_set_folds_holder(
+        &mut self,
+        uuid: Uuid,
+        folded: Option<(FoldsHolder, Range<u64>, Vec<RangeInclusive<u64>>)>,
+    ) -> Result<(), stypes::NativeError> {
+        if self.folding != Some(uuid) {
+            debug!("Results of outdated folding operation {uuid} are dropped");
+            return Ok(());
+        }
+        self.folding = None;
+        let Some((mut holder, processed, repeats)) = folded else {
+            self.searchers.folds.not_inited();
+            return Ok(());
+        };
+        // The session file might be unlinked while the holder was in use.
+        self.session_file.relink(&mut holder);
+        self.indexes.append_folds(processed, &repeats)?;
+        self.searchers.folds.set(holder);
+        Ok(())
+    }
+
+    /// Returns the cursor to continue indexing timestamps with the source files of the rows
+    /// which aren't indexed yet.
+    fn handle_get_timestamps_cursor(&self) -> Option<TimestampsCursor> {
+        let mut cursor = self.timestamps.cursor()?;
+        let sources = &self.session_file.sources;
+        cursor.sources = sources
+            .map
+            .iter()
+            .filter(|(range, _)| *range.end() >= cursor.rows)
+            .filter_map(|(range, id)| {
+                let alias = sources.sources.get(id)?;
+                let filename = self.observed.get_source_file(alias)?;
+                Some((range.clone(), filename))
+            })
+            .collect();
+        Some(cursor)
+    }
+
+    /// Returns the folds shown within the given range of rows with the timestamps of their
+    /// first and last rows if they are indexed.
+    fn handle_get_folds(&self, range: RangeInclusive<u64>) -> Vec<stypes::Fold> {
+        self.indexes
+            .get_folds(range)
+            .into_iter()
+            .map(|(row, last)| stypes::Fold {
+                row,
+                count: last - row + 1,
+                first: self.timestamps.at(row),
+                last: self.timestamps.at(last),
+            })
+            .collect()
+    }
+
     /// Exports data to the specified output path with the given parameters. This method is used to export
     /// only into text format.
     ///
@@ -494,6 +772,39 @@ impl SessionState {
         }
     }
 
+    fn handle_get_templates_holder(
+        &mut self,
+        uuid: Uuid,
+    ) -> Result<TemplatesHolder, stypes::NativeError> {
+        match std::mem::replace(&mut self.searchers.templates, SearcherState::InUse) {
+            SearcherState::Available(holder) => Ok(holder),
+            SearcherState::InUse => Err(stypes::NativeError::channel("Templates holder is in use")),
+            SearcherState::NotInited => {
+                let filename = self.session_file.filename().inspect_err(|_| {
+                    self.searchers.templates.not_inited();
+                })?;
+                Ok(TemplatesHolder::new(&filename, uuid, 0, 0))
+            }
+        }
+    }
+
+    fn handle_hide_templates(&mut self, ids: Option<Vec<u32>>) -> Result<(), stypes::NativeError> {
+        let SearcherState::Available(holder) = &mut self.searchers.templates else {
+            return Err(stypes::NativeError {
+                severity: stypes::Severity::WARNING,
+                kind: stypes::NativeErrorKind::Configuration,
+                message: Some(String::from(
+                    "Templates aren't mined or mining is in progress",
+                )),
+            });
+        };
+        match ids {
+            Some(ids) => holder.hide(&ids),
+            None => holder.show_all(),
+        }
+        Ok(())
+    }
+
     fn handle_add_attachment(
         &mut self,
         origin: parsers::Attachment,
@@ -509,10 +820,11 @@ impl SessionState {
 }
 
 pub async fn run(
+    state_api: SessionStateAPI,
     mut rx_api: UnboundedReceiver<Api>,
     tx_callback_events: UnboundedSender<stypes::CallbackEvent>,
 ) -> Result<(), stypes::NativeError> {
-    let mut state = SessionState::new(tx_callback_events.clone());
+    let mut state = SessionState::new(state_api, tx_callback_events.clone());
     let state_cancellation_token = CancellationToken::new();
     debug!("task is started");
     while let Some(msg) = rx_api.recv().await {
@@ -535,7 +847,7 @@ pub async fn run(
                         stypes::NativeError::channel("Failed to respond to Api::GetSessionFile")
                     })?;
             }
-            Api::WriteSessionFile((source_id, msg, tx_response)) => {
+            Api::WriteSessionFile((source_id, msg, timestamp, tx_response)) => {
                 tx_response
                     .send(
                         state
@@ -544,6 +856,7 @@ pub async fn run(
                                 state_cancellation_token.clone(),
                                 tx_callback_events.clone(),
                                 msg,
+                                timestamp,
                             )
                             .await,
                     )
@@ -562,6 +875,19 @@ pub async fn run(
                     stypes::NativeError::channel("Failed to respond to Api::FlushSessionFile")
                 })?;
             }
+            Api::UnlinkSessionFile((source_id, copy, tx_response)) => {
+                let res = state
+                    .handle_unlink_session_file(
+                        source_id,
+                        copy,
+                        state_cancellation_token.clone(),
+                        tx_callback_events.clone(),
+                    )
+                    .await;
+                tx_response.send(res).map_err(|_| {
+                    stypes::NativeError::channel("Failed to respond to Api::UnlinkSessionFile")
+                })?;
+            }
             Api::GetSessionFileOrigin(tx_response) => {
                 tx_response
                     .send(Ok(state.session_file.filename.clone()))
@@ -626,6 +952,18 @@ pub async fn run(
                         )
                     })?;
             }
+            Api::GetDltStatistics(tx_response) => {
+                tx_response
+                    .send(state.dlt_statistics.clone())
+                    .map_err(|_| {
+                        stypes::NativeError::channel("Failed to respond to Api::GetDltStatistics")
+                    })?;
+            }
+            Api::GetTriggers(tx_response) => {
+                tx_response.send(state.triggers.clone()).map_err(|_| {
+                    stypes::NativeError::channel("Failed to respond to Api::GetTriggers")
+                })?;
+            }
             Api::ExportSession {
                 out_path,
                 ranges,
@@ -656,7 +994,7 @@ pub async fn run(
             }
             Api::SetIndexingMode((mode, tx_response)) => {
                 tx_response
-                    .send(state.indexes.set_mode(mode))
+                    .send(state.handle_set_indexing_mode(mode))
                     .map_err(|_| {
                         stypes::NativeError::channel("Failed to respond to Api::SetIndexingMode")
                     })?;
@@ -706,6 +1044,20 @@ pub async fn run(
                         stypes::NativeError::channel("Failed to respond to Api::ExpandBreadcrumbs")
                     })?;
             }
+            Api::ExpandFold((row, tx_response)) => {
+                tx_response
+                    .send(state.indexes.folds_expand(row))
+                    .map_err(|_| {
+                        stypes::NativeError::channel("Failed to respond to Api::ExpandFold")
+                    })?;
+            }
+            Api::GetFolds((range, tx_response)) => {
+                tx_response
+                    .send(state.handle_get_folds(range))
+                    .map_err(|_| {
+                        stypes::NativeError::channel("Failed to respond to Api::GetFolds")
+                    })?;
+            }
             Api::GrabSearch((range, tx_response)) => {
                 tx_response
                     .send(state.handle_grab_search(range))
@@ -713,6 +1065,20 @@ pub async fn run(
                         stypes::NativeError::channel("Failed to respond to Api::GrabSearch")
                     })?;
             }
+            Api::GrabSearchContext((range, before, after, tx_response)) => {
+                tx_response
+                    .send(state.handle_grab_search_context(range, before, after))
+                    .map_err(|_| {
+                        stypes::NativeError::channel("Failed to respond to Api::GrabSearchContext")
+                    })?;
+            }
+            Api::GetSearchContext((before, after, tx_response)) => {
+                tx_response
+                    .send(state.handle_get_search_context(None, before, after))
+                    .map_err(|_| {
+                        stypes::NativeError::channel("Failed to respond to Api::GetSearchContext")
+                    })?;
+            }
             Api::SearchNestedMatch((filter, from, rev, tx_response)) => {
                 tx_response
                     .send(state.handle_search_nested_match(filter, from, rev))
@@ -736,6 +1102,85 @@ pub async fn run(
                         stypes::NativeError::channel("Failed to respond to Api::GetNearestPosition")
                     })?;
             }
+            Api::SetTimestampExtractor((extractor, tx_response)) => {
+                state.timestamps.set_extractor(extractor);
+                tx_response.send(()).map_err(|_| {
+                    stypes::NativeError::channel("Failed to respond to Api::SetTimestampExtractor")
+                })?;
+            }
+            Api::GetTimestampsCursor(tx_response) => {
+                tx_response
+                    .send(state.handle_get_timestamps_cursor())
+                    .map_err(|_| {
+                        stypes::NativeError::channel(
+                            "Failed to respond to Api::GetTimestampsCursor",
+                        )
+                    })?;
+            }
+            Api::IsTimestampsAvailable(tx_response) => {
+                tx_response
+                    .send(state.timestamps.is_available())
+                    .map_err(|_| {
+                        stypes::NativeError::channel(
+                            "Failed to respond to Api::IsTimestampsAvailable",
+                        )
+                    })?;
+            }
+            Api::UpdateTimestamps((update, tx_response)) => {
+                tx_response
+                    .send(state.timestamps.update(update))
+                    .map_err(|_| {
+                        stypes::NativeError::channel("Failed to respond to Api::UpdateTimestamps")
+                    })?;
+            }
+            Api::SetIndexedTimestamps(result) => {
+                if let Err(err) = state.handle_set_indexed_timestamps(
+                    result,
+                    &state_cancellation_token,
+                    tx_callback_events.clone(),
+                ) {
+                    error!("Fail to update timestamps: {err:?}");
+                }
+            }
+            Api::GetTimestampPoints(tx_response) => {
+                tx_response.send(state.timestamps.points()).map_err(|_| {
+                    stypes::NativeError::channel("Failed to respond to Api::GetTimestampPoints")
+                })?;
+            }
+            Api::GetNearestTimestamp((timestamp, tx_response)) => {
+                tx_response
+                    .send(stypes::ResultNearestTimestamp(
+                        state.timestamps.nearest(timestamp),
+                    ))
+                    .map_err(|_| {
+                        stypes::NativeError::channel(
+                            "Failed to respond to Api::GetNearestTimestamp",
+                        )
+                    })?;
+            }
+            Api::GetTimeRange((period, tx_response)) => {
+                tx_response
+                    .send(stypes::ResultTimeRange(
+                        state.timestamps.range(period).map(|rows| rows.into()),
+                    ))
+                    .map_err(|_| {
+                        stypes::NativeError::channel("Failed to respond to Api::GetTimeRange")
+                    })?;
+            }
+            Api::GetTimeDistribution((bucket, tx_response)) => {
+                state.timestamps.set_distribution_bucket(bucket);
+                tx_response
+                    .send(stypes::ResultTimeDistribution(
+                        state
+                            .timestamps
+                            .distribution(&state.search_map.matches, bucket),
+                    ))
+                    .map_err(|_| {
+                        stypes::NativeError::channel(
+                            "Failed to respond to Api::GetTimeDistribution",
+                        )
+                    })?;
+            }
             Api::GetScaledMap((len, range, tx_response)) => {
                 tx_response
                     .send(state.search_map.scaled(len, range))
@@ -770,7 +1215,9 @@ pub async fn run(
             }
             Api::SetSearchHolder((mut holder, _uuid_for_debug, tx_response)) => {
                 let result = if state.searchers.regular.is_using() {
-                    if let Some(holder) = holder.take() {
+                    if let Some(mut holder) = holder.take() {
+                        // The session file might be unlinked while the holder was in use.
+                        state.session_file.relink(&mut holder);
                         state.searchers.regular.set(holder);
                     } else {
                         state.searchers.regular.not_inited();
@@ -827,7 +1274,9 @@ pub async fn run(
             }
             Api::SetSearchValuesHolder((mut holder, _uuid_for_debug, tx_response)) => {
                 let result = if state.searchers.values.is_using() {
-                    if let Some(holder) = holder.take() {
+                    if let Some(mut holder) = holder.take() {
+                        // The session file might be unlinked while the holder was in use.
+                        state.session_file.relink(&mut holder);
                         state.searchers.values.set(holder);
                     } else {
                         state.searchers.values.not_inited();
@@ -842,6 +1291,53 @@ pub async fn run(
                     stypes::NativeError::channel("Failed to respond to Api::SetSearchValuesHolder")
                 })?;
             }
+            Api::GetTemplatesHolder((uuid, tx_response)) => {
+                tx_response
+                    .send(state.handle_get_templates_holder(uuid))
+                    .map_err(|_| {
+                        stypes::NativeError::channel("Failed to respond to Api::GetTemplatesHolder")
+                    })?;
+            }
+            Api::SetTemplatesHolder((holder, tx_response)) => {
+                let result = if state.searchers.templates.is_using() {
+                    if let Some(mut holder) = holder {
+                        // The session file might be unlinked while the holder was in use.
+                        state.session_file.relink(&mut holder);
+                        state.searchers.templates.set(holder);
+                    } else {
+                        state.searchers.templates.not_inited();
+                    }
+                    Ok(())
+                } else {
+                    Err(stypes::NativeError::channel(
+                        "Cannot set templates holder - it wasn't in use",
+                    ))
+                };
+                tx_response.send(result).map_err(|_| {
+                    stypes::NativeError::channel("Failed to respond to Api::SetTemplatesHolder")
+                })?;
+            }
+            Api::GetFoldsHolder((uuid, tx_response)) => {
+                tx_response
+                    .send(state.handle_get_folds_holder(uuid))
+                    .map_err(|_| {
+                        stypes::NativeError::channel("Failed to respond to Api::GetFoldsHolder")
+                    })?;
+            }
+            Api::SetFoldsHolder((uuid, folded, tx_response)) => {
+                tx_response
+                    .send(state.handle_set_folds_holder(uuid, folded))
+                    .map_err(|_| {
+                        stypes::NativeError::channel("Failed to respond to Api::SetFoldsHolder")
+                    })?;
+            }
+            Api::HideTemplates((ids, tx_response)) => {
+                tx_response
+                    .send(state.handle_hide_templates(ids))
+                    .map_err(|_| {
+                        stypes::NativeError::channel("Failed to respond to Api::HideTemplates")
+                    })?;
+            }
             Api::GetSearchValues((frame, width, tx_response)) => {
                 tx_response
                     .send(state.values.get(frame, width))
@@ -869,6 +1365,26 @@ pub async fn run(
                     stypes::NativeError::channel("Failed to respond to Api::DropSearchValues")
                 })?;
             }
+            Api::SetSearchValuesSeries(series, tx_response) => {
+                state.values.set_series(series);
+                tx_response.send(()).map_err(|_| {
+                    stypes::NativeError::channel("Failed to respond to Api::SetSearchValuesSeries")
+                })?;
+            }
+            Api::GetSearchValuesSeries(tx_response) => {
+                tx_response.send(state.values.series()).map_err(|_| {
+                    stypes::NativeError::channel("Failed to respond to Api::GetSearchValuesSeries")
+                })?;
+            }
+            Api::GetSearchValuesStatistics((frame, tx_response)) => {
+                tx_response
+                    .send(state.values.get_statistics(frame))
+                    .map_err(|_| {
+                        stypes::NativeError::channel(
+                            "Failed to respond to Api::GetSearchValuesStatistics",
+                        )
+                    })?;
+            }
             Api::GetIndexedRanges(tx_response) => {
                 tx_response
                     .send(state.indexes.get_all_as_ranges())
@@ -16,8 +16,11 @@ impl Observed {
 
     pub fn is_file_based_export_possible(&self) -> bool {
         !self.executed.iter().any(|opt| {
-            matches!(opt.origin, stypes::ObserveOrigin::Stream(..))
-                || matches!(opt.parser, stypes::ParserType::Plugin(..))
+            // Files of a folder are rotated, so they can't be read again.
+            matches!(
+                opt.origin,
+                stypes::ObserveOrigin::Stream(..) | stypes::ObserveOrigin::Folder(..)
+            ) || matches!(opt.parser, stypes::ParserType::Plugin(..))
         })
     }
 
@@ -41,6 +44,25 @@ impl Observed {
         });
         files
     }
+
+    /// Returns the file of the source with the given alias if the source is a file.
+    pub fn get_source_file(&self, alias: &str) -> Option<PathBuf> {
+        self.executed.iter().find_map(|opt| match &opt.origin {
+            stypes::ObserveOrigin::File(uuid, _, filename) if uuid == alias => {
+                Some(filename.clone())
+            }
+            stypes::ObserveOrigin::Concat(list) => list
+                .iter()
+                .find(|(uuid, _, _)| uuid == alias)
+                .map(|(_, _, filename)| filename.clone()),
+            // Files of a folder are registered with their paths as aliases.
+            stypes::ObserveOrigin::Folder(..) => {
+                let filename = PathBuf::from(alias);
+                filename.is_file().then_some(filename)
+            }
+            _ => None,
+        })
+    }
 }
 
 impl Default for Observed {
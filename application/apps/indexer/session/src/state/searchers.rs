@@ -1,6 +1,9 @@
+use super::SessionFile;
 use processor::search::searchers::{
     self, BaseSearcher, SearchState,
+    folds::{FoldsResults, FoldsState},
     regular::{self, RegularSearchState},
+    templates::TemplatesState,
     values::{OperationResults, ValueSearchState},
 };
 use tokio_util::sync::CancellationToken;
@@ -13,6 +16,14 @@ pub enum SearcherState<State: SearchState> {
 }
 
 impl<State: SearchState> SearcherState<State> {
+    /// Points an available searcher to the current session file.
+    /// See [`SessionFile::relink()`].
+    pub fn relink(&mut self, session_file: &SessionFile) {
+        if let Self::Available(searcher) = self {
+            session_file.relink(searcher);
+        }
+    }
+
     pub fn is_using(&self) -> bool {
         matches!(self, SearcherState::<_>::InUse)
     }
@@ -65,8 +76,29 @@ impl SearcherState<RegularSearchState> {
     }
 }
 
+impl SearcherState<FoldsState> {
+    pub fn search(
+        &mut self,
+        rows_count: u64,
+        read_bytes: u64,
+        cancel_token: CancellationToken,
+    ) -> Option<FoldsResults> {
+        match self {
+            Self::Available(h) => Some(searchers::folds::search(
+                h,
+                rows_count,
+                read_bytes,
+                cancel_token,
+            )),
+            _ => None,
+        }
+    }
+}
+
 #[derive(Debug)]
 pub struct Searchers {
     pub regular: SearcherState<RegularSearchState>,
     pub values: SearcherState<ValueSearchState>,
+    pub templates: SearcherState<TemplatesState>,
+    pub folds: SearcherState<FoldsState>,
 }
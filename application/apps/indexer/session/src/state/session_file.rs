@@ -3,11 +3,12 @@ use crate::paths;
 use log::debug;
 use processor::{
     grabber::{Grabber, LineRange},
+    search::searchers::{BaseSearcher, SearchState},
     text_source::TextFileSource,
 };
 use std::{
     fs::File,
-    io::{BufWriter, Write},
+    io::{self, BufWriter, Read, Seek, SeekFrom, Write},
     path::PathBuf,
     time::Instant,
 };
@@ -52,6 +53,10 @@ pub struct SessionFile {
     pub writer: Option<BufWriter<File>>,
     pub last_message_timestamp: Instant,
     pub sources: SourceIDs,
+    /// Count of rows written, but not indexed by the grabber yet
+    pending: u64,
+    /// Count of the first rows, which content was lost because the linked file was truncated.
+    blank: u64,
 }
 
 impl SessionFile {
@@ -62,6 +67,8 @@ impl SessionFile {
             writer: None,
             last_message_timestamp: Instant::now(),
             sources: SourceIDs::new(),
+            pending: 0,
+            blank: 0,
         }
     }
 
@@ -71,20 +78,8 @@ impl SessionFile {
                 self.filename = Some(SessionFileOrigin::Linked(filename.clone()));
                 filename
             } else {
-                let streams = paths::get_streams_dir()?;
-                let filename = streams.join(format!("{}.{SESSION_FILE_EXTENSION}", Uuid::new_v4()));
-                debug!("Session file setup: {}", filename.to_string_lossy());
-                self.writer = Some(BufWriter::new(File::create(&filename).map_err(|e| {
-                    stypes::NativeError {
-                        severity: stypes::Severity::ERROR,
-                        kind: stypes::NativeErrorKind::Io,
-                        message: Some(format!(
-                            "Fail to create session writer for {}: {}",
-                            filename.to_string_lossy(),
-                            e
-                        )),
-                    }
-                })?));
+                let (filename, writer) = create_session_file()?;
+                self.writer = Some(writer);
                 self.filename = Some(SessionFileOrigin::Generated(filename.clone()));
                 filename
             };
@@ -95,6 +90,49 @@ impl SessionFile {
         }
     }
 
+    /// Turns a session linked with a file into a session with the generated file of `copy`.
+    /// It's used when the linked file is replaced (e.g. rotated by a logger) or truncated and
+    /// the content of the new file has to be written into the session.
+    pub fn unlink(
+        &mut self,
+        source_id: u16,
+        copy: LinkedCopy,
+    ) -> Result<SessionFileState, stypes::NativeError> {
+        if !matches!(self.filename, Some(SessionFileOrigin::Linked(_))) {
+            return Err(stypes::NativeError {
+                severity: stypes::Severity::ERROR,
+                kind: stypes::NativeErrorKind::Grabber,
+                message: Some(String::from("Session file isn't linked, cannot unlink")),
+            });
+        }
+        let prev = self.len();
+        self.grabber = Some(Box::new(copy.grabber));
+        self.writer = Some(copy.writer);
+        self.filename = Some(SessionFileOrigin::Generated(copy.filename));
+        self.blank = copy.blank;
+        // Content appended to the linked file before it was replaced.
+        let current = self.len();
+        Ok(if current > prev {
+            self.sources.add_range(prev..=current - 1, source_id);
+            SessionFileState::Changed
+        } else {
+            SessionFileState::NoChanges
+        })
+    }
+
+    /// Points the searcher to the current session file. The searcher has to be created for
+    /// this session file or for the linked file it was copied from.
+    pub fn relink<State: SearchState>(&self, searcher: &mut BaseSearcher<State>) {
+        let Ok(filename) = self.filename() else {
+            return;
+        };
+        searcher.file_path = filename;
+        // Rows of a truncated linked file are empty lines of one byte.
+        if searcher.lines_read() <= self.blank {
+            searcher.set_bytes_read(searcher.lines_read());
+        }
+    }
+
     #[allow(clippy::len_without_is_empty)]
     pub fn len(&mut self) -> u64 {
         if let Some(ref grabber) = self.grabber {
@@ -108,6 +146,12 @@ impl SessionFile {
         }
     }
 
+    /// Returns the position of the next row written into the session file, considering
+    /// rows, which aren't flushed yet.
+    pub fn next_row(&mut self) -> u64 {
+        self.len() + self.pending
+    }
+
     /// Returns amount of bytes, which was processed by grabber
     pub fn read_bytes(&mut self) -> u64 {
         if let Some(ref grabber) = self.grabber {
@@ -136,6 +180,7 @@ impl SessionFile {
         }
         if let Some(writer) = &mut self.writer {
             writer.write_all(msg.as_bytes())?;
+            self.pending += msg.bytes().filter(|b| *b == b'\n').count() as u64;
             self.sources.source_update(source_id);
             if self.last_message_timestamp.elapsed().as_millis() > FLUSH_DATA_IN_MS {
                 self.flush(state_cancellation_token, true)
@@ -193,6 +238,7 @@ impl SessionFile {
             self.sources.add_range(range, source_id);
         }
         let current = grabber.log_entry_count().unwrap_or(0) as u64;
+        self.pending = self.pending.saturating_sub(current.saturating_sub(prev));
         Ok(if prev != current {
             SessionFileState::Changed
         } else {
@@ -335,6 +381,79 @@ impl SessionFile {
     }
 }
 
+/// Content of a linked session file copied into a generated session file.
+/// See [`copy_linked()`].
+#[derive(Debug)]
+pub struct LinkedCopy {
+    filename: PathBuf,
+    writer: BufWriter<File>,
+    grabber: Grabber,
+    blank: u64,
+}
+
+/// Copies the content of a linked session file into a new generated session file. Copying
+/// blocks, therefore it's done by the observing operation before the session is unlinked.
+///
+/// `linked` has to be a handle of the linked file opened before it was replaced, because the
+/// handle keeps the original content readable after the file was renamed. `rows` and `bytes`
+/// are the rows and bytes of the linked file read by the session. If the file was truncated,
+/// its content is lost and each of the read rows is kept as an empty line, so the positions
+/// of the session (e.g. search results and bookmarks) stay valid.
+pub fn copy_linked(
+    mut linked: File,
+    rows: u64,
+    bytes: u64,
+    truncated: bool,
+) -> Result<LinkedCopy, stypes::NativeError> {
+    let truncated = truncated || linked.metadata()?.len() < bytes;
+    let (filename, mut writer) = create_session_file()?;
+    let blank = if truncated {
+        for _ in 0..rows {
+            writer.write_all(b"\n")?;
+        }
+        rows
+    } else {
+        linked.seek(SeekFrom::Start(0))?;
+        let copied = io::copy(&mut linked, &mut writer)?;
+        // The content written into the session has to start on a new line.
+        if copied > 0 {
+            let mut last = [0u8; 1];
+            linked.seek(SeekFrom::Start(copied - 1))?;
+            linked.read_exact(&mut last)?;
+            if last[0] != b'\n' {
+                writer.write_all(b"\n")?;
+            }
+        }
+        0
+    };
+    writer.flush()?;
+    let mut grabber = Grabber::lazy(TextFileSource::new(&filename))?;
+    grabber.update_from_file(None)?;
+    Ok(LinkedCopy {
+        filename,
+        writer,
+        grabber,
+        blank,
+    })
+}
+
+/// Creates a new file in the streams folder for the content of a session.
+fn create_session_file() -> Result<(PathBuf, BufWriter<File>), stypes::NativeError> {
+    let streams = paths::get_streams_dir()?;
+    let filename = streams.join(format!("{}.{SESSION_FILE_EXTENSION}", Uuid::new_v4()));
+    debug!("Session file setup: {}", filename.to_string_lossy());
+    let writer = BufWriter::new(File::create(&filename).map_err(|e| stypes::NativeError {
+        severity: stypes::Severity::ERROR,
+        kind: stypes::NativeErrorKind::Io,
+        message: Some(format!(
+            "Fail to create session writer for {}: {}",
+            filename.to_string_lossy(),
+            e
+        )),
+    })?);
+    Ok((filename, writer))
+}
+
 impl Default for SessionFile {
     fn default() -> Self {
         Self::new()
@@ -25,6 +25,8 @@ pub struct SourceIDs {
     pub sources: HashMap<u16, String>,
     pub map: Vec<(RangeInclusive<u64>, u16)>,
     pub recent: Option<u16>,
+    /// Count of log entries by source, updated with each added range.
+    counts: HashMap<u16, u64>,
 }
 
 impl SourceIDs {
@@ -33,6 +35,7 @@ impl SourceIDs {
             sources: HashMap::new(),
             map: vec![],
             recent: None,
+            counts: HashMap::new(),
         }
     }
 
@@ -81,11 +84,20 @@ impl SourceIDs {
             .map(|(id, alias)| stypes::SourceDefinition {
                 id: *id,
                 alias: alias.to_string(),
+                count: self.count(*id),
             })
             .collect::<Vec<stypes::SourceDefinition>>()
     }
 
+    /// Returns the count of log entries of the source in the session.
+    pub fn count(&self, source_id: u16) -> u64 {
+        self.counts.get(&source_id).copied().unwrap_or(0)
+    }
+
     pub fn add_range(&mut self, range: RangeInclusive<u64>, source_id: u16) {
+        if !range.is_empty() {
+            *self.counts.entry(source_id).or_default() += range.end() - range.start() + 1;
+        }
         self.map.push((range, source_id));
     }
 
@@ -109,3 +121,21 @@ impl Default for SourceIDs {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn count_entries_of_sources() {
+        let mut sources = SourceIDs::new();
+        let first = sources.add_source(String::from("first"));
+        let second = sources.add_source(String::from("second"));
+        sources.add_range(0..=9, first);
+        sources.add_range(10..=14, second);
+        sources.add_range(15..=19, first);
+        assert_eq!(sources.count(first), 15);
+        assert_eq!(sources.count(second), 5);
+        assert_eq!(sources.count(2), 0);
+    }
+}
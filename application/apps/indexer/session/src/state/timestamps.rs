@@ -0,0 +1,682 @@
+use crate::paths;
+use log::{debug, error};
+use parsers::text::timestamp::{TimestampExtractor, year_of};
+use std::{
+    collections::{BTreeMap, HashMap},
+    fs::File,
+    io::{BufRead, BufReader, BufWriter, Seek, SeekFrom, Write},
+    ops::RangeInclusive,
+    path::{Path, PathBuf},
+};
+use tokio_util::sync::CancellationToken;
+use uuid::Uuid;
+
+pub const TIMESTAMPS_FILE_EXTENSION: &str = "timestamps";
+
+/// Count of rows between checks of cancellation while indexing.
+pub const CANCELLATION_CHECK_ROWS: u64 = 10_000;
+
+/// State of the index needed to continue indexing the rows of the session file.
+#[derive(Debug, Clone)]
+pub struct TimestampsCursor {
+    pub extractor: TimestampExtractor,
+    /// Version of the settings the index belongs to.
+    pub generation: u64,
+    /// Count of indexed rows.
+    pub rows: u64,
+    /// Count of indexed bytes of the session file.
+    pub bytes: u64,
+    /// Timestamp of the last indexed row.
+    pub last: Option<u64>,
+    /// Source files of the rows starting from `rows` in ascending order. Timestamps without
+    /// year get the year their file was modified.
+    pub sources: Vec<(RangeInclusive<u64>, PathBuf)>,
+}
+
+/// Timestamps of newly indexed rows.
+#[derive(Debug)]
+pub struct TimestampsUpdate {
+    pub generation: u64,
+    /// Count of indexed rows including the new ones.
+    pub rows: u64,
+    /// Count of indexed bytes including the new rows.
+    pub bytes: u64,
+    /// Rows starting with a new timestamp as `(row, timestamp)`.
+    pub points: Vec<(u64, u64)>,
+}
+
+/// File with the points of the index next to the session file. Each point is written as
+/// the row and the timestamp (both `u64`, little endian).
+#[derive(Debug)]
+struct IndexFile {
+    filename: PathBuf,
+    writer: BufWriter<File>,
+}
+
+impl IndexFile {
+    fn create() -> Result<Self, stypes::NativeError> {
+        let filename = paths::get_streams_dir()?
+            .join(format!("{}.{TIMESTAMPS_FILE_EXTENSION}", Uuid::new_v4()));
+        debug!("Timestamps file setup: {}", filename.to_string_lossy());
+        let writer = BufWriter::new(File::create(&filename).map_err(|e| stypes::NativeError {
+            severity: stypes::Severity::ERROR,
+            kind: stypes::NativeErrorKind::Io,
+            message: Some(format!(
+                "Fail to create timestamps file {}: {e}",
+                filename.to_string_lossy(),
+            )),
+        })?);
+        Ok(Self { filename, writer })
+    }
+
+    fn write(&mut self, points: &[(u64, u64)]) -> Result<(), stypes::NativeError> {
+        for (row, ts) in points {
+            self.writer.write_all(&row.to_le_bytes())?;
+            self.writer.write_all(&ts.to_le_bytes())?;
+        }
+        self.writer.flush()?;
+        Ok(())
+    }
+}
+
+impl Drop for IndexFile {
+    fn drop(&mut self) {
+        if let Err(err) = std::fs::remove_file(&self.filename) {
+            error!(
+                "Fail to remove timestamps file {}: {err}",
+                self.filename.to_string_lossy()
+            );
+        }
+    }
+}
+
+/// Rows where the timestamp changes as `(row, timestamp)`.
+#[derive(Debug)]
+struct Index {
+    points: Vec<(u64, u64)>,
+    /// Count of rows covered by the index.
+    rows: u64,
+    /// `true` while timestamps don't decrease, which allows a binary search.
+    monotonic: bool,
+    /// Count of points written into the file.
+    written: usize,
+    file: Option<IndexFile>,
+}
+
+impl Default for Index {
+    fn default() -> Self {
+        Self {
+            points: Vec::new(),
+            rows: 0,
+            monotonic: true,
+            written: 0,
+            file: None,
+        }
+    }
+}
+
+impl Index {
+    /// Adds the timestamp of the row unless it's the timestamp of the previous point.
+    fn push(&mut self, row: u64, ts: u64) {
+        if let Some((_, last)) = self.points.last() {
+            if *last == ts {
+                return;
+            }
+            if ts < *last {
+                self.monotonic = false;
+            }
+        }
+        self.points.push((row, ts));
+    }
+
+    /// Writes the points added since the last call into the file of the index. The file is
+    /// created with the first point.
+    fn persist(&mut self) -> Result<(), stypes::NativeError> {
+        if self.written == self.points.len() {
+            return Ok(());
+        }
+        if self.file.is_none() {
+            self.file = Some(IndexFile::create()?);
+        }
+        if let Some(file) = self.file.as_mut() {
+            file.write(&self.points[self.written..])?;
+        }
+        self.written = self.points.len();
+        Ok(())
+    }
+
+    fn nearest(&self, timestamp: u64) -> Option<stypes::NearestTimestamp> {
+        let (row, ts) = if self.monotonic {
+            let after = self.points.partition_point(|(_, ts)| *ts < timestamp);
+            [after.checked_sub(1), Some(after)]
+                .into_iter()
+                .flatten()
+                .filter_map(|index| self.points.get(index))
+                .min_by_key(|(_, ts)| ts.abs_diff(timestamp))?
+        } else {
+            self.points
+                .iter()
+                .min_by_key(|(_, ts)| ts.abs_diff(timestamp))?
+        };
+        Some(stypes::NearestTimestamp {
+            position: *row,
+            timestamp: *ts,
+        })
+    }
+
+    fn range(&self, period: RangeInclusive<u64>) -> Option<RangeInclusive<u64>> {
+        let (first, last) = if self.monotonic {
+            let first = self.points.partition_point(|(_, ts)| ts < period.start());
+            let end = self.points.partition_point(|(_, ts)| ts <= period.end());
+            (first, end.checked_sub(1)?)
+        } else {
+            let mut within = self
+                .points
+                .iter()
+                .enumerate()
+                .filter(|(_, (_, ts))| period.contains(ts))
+                .map(|(index, _)| index);
+            let first = within.next()?;
+            (first, within.next_back().unwrap_or(first))
+        };
+        if first > last {
+            return None;
+        }
+        // The last point covers all rows until the next point.
+        let end = self
+            .points
+            .get(last + 1)
+            .map_or(self.rows, |(row, _)| *row)
+            .checked_sub(1)?;
+        Some(self.points[first].0..=end)
+    }
+
+    fn at(&self, row: u64) -> Option<u64> {
+        if row >= self.rows {
+            return None;
+        }
+        let after = self.points.partition_point(|(point, _)| *point <= row);
+        after
+            .checked_sub(1)
+            .and_then(|index| self.points.get(index))
+            .map(|(_, ts)| *ts)
+    }
+
+    fn distribution(
+        &self,
+        matches: &[stypes::FilterMatch],
+        bucket: u64,
+    ) -> Vec<stypes::TimeBucket> {
+        let mut buckets: BTreeMap<u64, BTreeMap<u8, u32>> = BTreeMap::new();
+        // Index of the first point after the row of the match
+        let mut next = matches.first().map_or(0, |first| {
+            self.points.partition_point(|(row, _)| *row <= first.index)
+        });
+        for matched in matches.iter().take_while(|m| m.index < self.rows) {
+            while next < self.points.len() && self.points[next].0 <= matched.index {
+                next += 1;
+            }
+            let Some((_, ts)) = next.checked_sub(1).map(|point| self.points[point]) else {
+                continue;
+            };
+            let counts = buckets.entry(ts - ts % bucket).or_default();
+            for filter in matched.filters.iter() {
+                *counts.entry(*filter).or_default() += 1;
+            }
+        }
+        buckets
+            .into_iter()
+            .map(|(timestamp, counts)| stypes::TimeBucket {
+                timestamp,
+                matches: counts.into_iter().collect(),
+            })
+            .collect()
+    }
+}
+
+/// Timestamps of the rows of the session file. Rows without own timestamp (e.g. lines of a
+/// stack trace) get the timestamp of the previous row, so only rows where the timestamp
+/// changes are stored.
+///
+/// Rows get the timestamps of their messages if the format carries them (e.g. the storage
+/// header of DLT messages or the time of network packets). Once an extractor is set, the
+/// timestamps are extracted from the text of the rows instead.
+#[derive(Debug, Default)]
+pub struct Timestamps {
+    extractor: Option<TimestampExtractor>,
+    generation: u64,
+    /// Count of bytes of the session file indexed by the extractor.
+    bytes: u64,
+    /// Timestamps extracted from the text of the rows.
+    extracted: Index,
+    /// Timestamps of the messages.
+    messages: Index,
+    /// `true` if the indexes are written into files next to the session file.
+    persistent: bool,
+    /// Length of the periods of the distribution last requested by the client, which is
+    /// updated with new search matches.
+    bucket: Option<u64>,
+}
+
+impl Timestamps {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates timestamps which write the indexes into files next to the session file. A
+    /// file is created with the first point of its index and removed with the index.
+    pub fn persistent() -> Self {
+        Self {
+            persistent: true,
+            ..Self::new()
+        }
+    }
+
+    /// Sets the extractor of timestamps and drops the index built with the previous one.
+    /// Without extractor the timestamps of the messages are used.
+    pub fn set_extractor(&mut self, extractor: Option<TimestampExtractor>) {
+        self.extractor = extractor;
+        self.generation += 1;
+        self.extracted = Index::default();
+        self.bytes = 0;
+    }
+
+    /// Returns the index used for queries.
+    fn index(&self) -> &Index {
+        if self.extractor.is_some() {
+            &self.extracted
+        } else {
+            &self.messages
+        }
+    }
+
+    /// Returns `true` if rows have timestamps, either extracted from their text or taken from
+    /// their messages.
+    pub fn is_available(&self) -> bool {
+        self.extractor.is_some() || !self.messages.points.is_empty()
+    }
+
+    /// Returns the cursor to continue indexing or `None` if extraction isn't set up.
+    pub fn cursor(&self) -> Option<TimestampsCursor> {
+        self.extractor.as_ref().map(|extractor| TimestampsCursor {
+            extractor: extractor.clone(),
+            generation: self.generation,
+            rows: self.extracted.rows,
+            bytes: self.bytes,
+            last: self.extracted.points.last().map(|(_, ts)| *ts),
+            sources: Vec::new(),
+        })
+    }
+
+    /// Adds the timestamp of the message starting at the given row.
+    pub fn add_message(&mut self, row: u64, timestamp: u64) {
+        self.messages.push(row, timestamp);
+    }
+
+    /// Sets the count of rows of the session file, which are covered by the timestamps of
+    /// their messages then. Returns `true` if the extracted timestamps cover all previous
+    /// rows, so the new rows can be indexed right away.
+    pub fn set_stream_len(&mut self, rows: u64) -> Result<bool, stypes::NativeError> {
+        let extend = self.extractor.is_some() && self.extracted.rows >= self.messages.rows;
+        self.messages.rows = rows;
+        if self.persistent {
+            self.messages.persist()?;
+        }
+        Ok(extend)
+    }
+
+    /// Extends the index. Updates of outdated settings or of rows which are indexed already
+    /// (two concurrent updates) are ignored.
+    pub fn update(&mut self, update: TimestampsUpdate) -> Result<(), stypes::NativeError> {
+        if update.generation != self.generation || update.rows <= self.extracted.rows {
+            return Ok(());
+        }
+        for (row, ts) in update.points {
+            if row >= self.extracted.rows {
+                self.extracted.push(row, ts);
+            }
+        }
+        self.extracted.rows = update.rows;
+        self.bytes = update.bytes;
+        if self.persistent {
+            self.extracted.persist()?;
+        }
+        Ok(())
+    }
+
+    /// Returns the first row of the timestamp nearest to the given one.
+    pub fn nearest(&self, timestamp: u64) -> Option<stypes::NearestTimestamp> {
+        self.index().nearest(timestamp)
+    }
+
+    /// Returns the rows with timestamps within the given period. If timestamps aren't
+    /// monotonic, the range spans from the first to the last row within the period.
+    pub fn range(&self, period: RangeInclusive<u64>) -> Option<RangeInclusive<u64>> {
+        self.index().range(period)
+    }
+
+    /// Returns the timestamp of the given row if the row is indexed.
+    pub fn at(&self, row: u64) -> Option<u64> {
+        self.index().at(row)
+    }
+
+    /// Returns the rows starting with a new timestamp as `(row, timestamp)` and the count of
+    /// indexed rows.
+    pub fn points(&self) -> (Vec<(u64, u64)>, u64) {
+        let index = self.index();
+        (index.points.clone(), index.rows)
+    }
+
+    /// Counts the matches of each filter within periods of `bucket` ms. Matches are expected
+    /// in ascending order of rows; matches in rows before the first timestamp or in rows
+    /// which aren't indexed yet are skipped.
+    pub fn distribution(
+        &self,
+        matches: &[stypes::FilterMatch],
+        bucket: u64,
+    ) -> Vec<stypes::TimeBucket> {
+        self.index().distribution(matches, bucket)
+    }
+
+    /// Sets the length of the periods of the distribution requested by the client.
+    pub fn set_distribution_bucket(&mut self, bucket: u64) {
+        self.bucket = Some(bucket);
+    }
+
+    /// Returns the counts of new matches to add to the distribution requested by the client.
+    /// Returns `None` if the distribution wasn't requested or no match is counted.
+    pub fn distribution_update(
+        &self,
+        matches: &[stypes::FilterMatch],
+    ) -> Option<Vec<stypes::TimeBucket>> {
+        self.bucket
+            .map(|bucket| self.distribution(matches, bucket))
+            .filter(|buckets| !buckets.is_empty())
+    }
+}
+
+/// Returns the extractors of the source files of the cursor. Timestamps without year (if the
+/// year isn't defined explicitly) are completed by the year the file was modified.
+fn source_extractors(cursor: &TimestampsCursor) -> Vec<(RangeInclusive<u64>, TimestampExtractor)> {
+    let mut years: HashMap<&PathBuf, Option<i32>> = HashMap::new();
+    cursor
+        .sources
+        .iter()
+        .filter_map(|(range, filename)| {
+            let year = *years.entry(filename).or_insert_with(|| {
+                std::fs::metadata(filename)
+                    .and_then(|md| md.modified())
+                    .map(year_of)
+                    .ok()
+            });
+            year.map(|year| (range.clone(), cursor.extractor.with_default_year(year)))
+        })
+        .collect()
+}
+
+/// Reads the rows of the session file from the cursor up to `rows` and extracts their
+/// timestamps. Returns `None` if indexing was cancelled.
+pub fn index_rows(
+    filename: &Path,
+    cursor: TimestampsCursor,
+    rows: u64,
+    cancel: &CancellationToken,
+) -> Result<Option<TimestampsUpdate>, stypes::NativeError> {
+    let mut file = File::open(filename)?;
+    file.seek(SeekFrom::Start(cursor.bytes))?;
+    let mut reader = BufReader::new(file);
+    let mut line = Vec::new();
+    let mut bytes = cursor.bytes;
+    let mut indexed = cursor.rows;
+    let mut last = cursor.last;
+    let mut points = Vec::new();
+    let extractors = source_extractors(&cursor);
+    // Index of the first source which doesn't end before the current row
+    let mut source = 0;
+    for row in cursor.rows..rows {
+        if row % CANCELLATION_CHECK_ROWS == 0 && cancel.is_cancelled() {
+            return Ok(None);
+        }
+        line.clear();
+        let read = reader.read_until(b'\n', &mut line)?;
+        if read == 0 {
+            break;
+        }
+        bytes += read as u64;
+        indexed += 1;
+        while source < extractors.len() && *extractors[source].0.end() < row {
+            source += 1;
+        }
+        let extractor = extractors
+            .get(source)
+            .filter(|(range, _)| range.contains(&row))
+            .map_or(&cursor.extractor, |(_, extractor)| extractor);
+        let Some(ts) = extractor.extract(&String::from_utf8_lossy(&line)) else {
+            continue;
+        };
+        if last != Some(ts) {
+            points.push((row, ts));
+            last = Some(ts);
+        }
+    }
+    Ok(Some(TimestampsUpdate {
+        generation: cursor.generation,
+        rows: indexed,
+        bytes,
+        points,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn indexed(points: Vec<(u64, u64)>, rows: u64) -> Timestamps {
+        let mut timestamps = Timestamps::new();
+        timestamps.set_extractor(Some(TimestampExtractor::new(None, None, None).unwrap()));
+        timestamps
+            .update(TimestampsUpdate {
+                generation: timestamps.generation,
+                rows,
+                bytes: 0,
+                points,
+            })
+            .unwrap();
+        timestamps
+    }
+
+    #[test]
+    fn nearest_timestamp() {
+        let timestamps = indexed(vec![(1, 100), (3, 100), (4, 200), (7, 400)], 10);
+        // Repeated timestamps aren't stored.
+        assert_eq!(
+            timestamps.extracted.points,
+            vec![(1, 100), (4, 200), (7, 400)]
+        );
+        let nearest = |ts| timestamps.nearest(ts).map(|n| (n.position, n.timestamp));
+        assert_eq!(nearest(0), Some((1, 100)));
+        assert_eq!(nearest(140), Some((1, 100)));
+        assert_eq!(nearest(160), Some((4, 200)));
+        assert_eq!(nearest(1000), Some((7, 400)));
+        assert!(indexed(vec![], 10).nearest(100).is_none());
+
+        let timestamps = indexed(vec![(0, 300), (2, 100), (5, 200)], 10);
+        assert!(!timestamps.extracted.monotonic);
+        assert_eq!(timestamps.nearest(180).map(|n| n.position), Some(5));
+    }
+
+    #[test]
+    fn time_range() {
+        let timestamps = indexed(vec![(1, 100), (4, 200), (7, 400)], 10);
+        assert_eq!(timestamps.range(100..=200), Some(1..=6));
+        assert_eq!(timestamps.range(150..=450), Some(4..=9));
+        assert_eq!(timestamps.range(0..=50), None);
+        assert_eq!(timestamps.range(250..=350), None);
+
+        let timestamps = indexed(vec![(0, 300), (2, 100), (5, 200)], 10);
+        assert_eq!(timestamps.range(100..=200), Some(2..=9));
+        assert_eq!(timestamps.range(250..=300), Some(0..=1));
+    }
+
+    #[test]
+    fn outdated_updates() {
+        let mut timestamps = indexed(vec![(0, 100)], 2);
+        let generation = timestamps.generation;
+        timestamps
+            .update(TimestampsUpdate {
+                generation,
+                rows: 2,
+                bytes: 0,
+                points: vec![(1, 50)],
+            })
+            .unwrap();
+        assert_eq!(timestamps.extracted.points, vec![(0, 100)]);
+        timestamps.set_extractor(None);
+        timestamps
+            .update(TimestampsUpdate {
+                generation,
+                rows: 4,
+                bytes: 0,
+                points: vec![(3, 50)],
+            })
+            .unwrap();
+        assert!(timestamps.extracted.points.is_empty());
+        assert!(timestamps.cursor().is_none());
+    }
+
+    #[test]
+    fn persistent_index() {
+        let mut timestamps = Timestamps::persistent();
+        timestamps.set_extractor(Some(TimestampExtractor::new(None, None, None).unwrap()));
+        let update = |timestamps: &mut Timestamps, rows, points| {
+            let generation = timestamps.generation;
+            timestamps
+                .update(TimestampsUpdate {
+                    generation,
+                    rows,
+                    bytes: 0,
+                    points,
+                })
+                .unwrap();
+        };
+        update(&mut timestamps, 2, vec![]);
+        assert!(timestamps.extracted.file.is_none());
+        update(&mut timestamps, 4, vec![(2, 100), (3, 100)]);
+        update(&mut timestamps, 6, vec![(5, 200)]);
+        let filename = timestamps.extracted.file.as_ref().unwrap().filename.clone();
+        assert_eq!(
+            std::fs::read(&filename).unwrap(),
+            [2u64, 100, 5, 200]
+                .iter()
+                .flat_map(|n| n.to_le_bytes())
+                .collect::<Vec<u8>>()
+        );
+        // The file is removed with the index.
+        timestamps.set_extractor(None);
+        assert!(!filename.exists());
+    }
+
+    #[test]
+    fn distribution_over_time() {
+        let timestamps = indexed(vec![(1, 1_100), (4, 1_900), (7, 3_500)], 9);
+        let matches = [
+            (0, vec![0]),
+            (2, vec![0, 1]),
+            (5, vec![1]),
+            (7, vec![0]),
+            (9, vec![0]),
+        ]
+        .into_iter()
+        .map(|(index, filters)| stypes::FilterMatch { index, filters })
+        .collect::<Vec<stypes::FilterMatch>>();
+        let distribution = timestamps
+            .distribution(&matches, 1_000)
+            .into_iter()
+            .map(|bucket| (bucket.timestamp, bucket.matches))
+            .collect::<Vec<(u64, Vec<(u8, u32)>)>>();
+        // Row 0 is before the first timestamp and row 9 isn't indexed.
+        assert_eq!(
+            distribution,
+            vec![(1_000, vec![(0, 1), (1, 2)]), (3_000, vec![(0, 1)])]
+        );
+        assert!(indexed(vec![], 9).distribution(&matches, 1_000).is_empty());
+    }
+
+    #[test]
+    fn message_timestamps() {
+        let mut timestamps = Timestamps::new();
+        assert!(!timestamps.is_available());
+        timestamps.add_message(0, 100);
+        timestamps.add_message(2, 100);
+        timestamps.add_message(3, 300);
+        assert!(!timestamps.set_stream_len(5).unwrap());
+        assert!(timestamps.is_available());
+        assert!(timestamps.cursor().is_none());
+        assert_eq!(timestamps.points(), (vec![(0, 100), (3, 300)], 5));
+        assert_eq!(timestamps.at(4), Some(300));
+        assert_eq!(timestamps.range(0..=200), Some(0..=2));
+        // Extracted timestamps take precedence over the ones of messages.
+        timestamps.set_extractor(Some(TimestampExtractor::new(None, None, None).unwrap()));
+        assert_eq!(timestamps.at(4), None);
+        assert!(!timestamps.set_stream_len(6).unwrap());
+        let generation = timestamps.generation;
+        timestamps
+            .update(TimestampsUpdate {
+                generation,
+                rows: 6,
+                bytes: 0,
+                points: vec![(1, 500)],
+            })
+            .unwrap();
+        assert_eq!(timestamps.at(4), Some(500));
+        // New rows are indexed right away once all rows are indexed.
+        assert!(timestamps.set_stream_len(8).unwrap());
+        timestamps.set_extractor(None);
+        assert_eq!(timestamps.at(4), Some(300));
+    }
+
+    #[test]
+    fn distribution_updates() {
+        let mut timestamps = indexed(vec![(1, 1_100), (4, 1_900), (7, 3_500)], 9);
+        let matches = [(5, vec![1]), (7, vec![0]), (8, vec![0])]
+            .into_iter()
+            .map(|(index, filters)| stypes::FilterMatch { index, filters })
+            .collect::<Vec<stypes::FilterMatch>>();
+        assert!(timestamps.distribution_update(&matches).is_none());
+        timestamps.set_distribution_bucket(1_000);
+        let update = timestamps
+            .distribution_update(&matches)
+            .unwrap()
+            .into_iter()
+            .map(|bucket| (bucket.timestamp, bucket.matches))
+            .collect::<Vec<(u64, Vec<(u8, u32)>)>>();
+        assert_eq!(update, vec![(1_000, vec![(1, 1)]), (3_000, vec![(0, 2)])]);
+        assert!(timestamps.distribution_update(&matches[..0]).is_none());
+    }
+
+    #[test]
+    fn extract_rows() {
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        file.write_all(b"2024-01-01 10:00:00.000 a\ntrace\n2024-01-01 10:00:01.000 b\n")
+            .unwrap();
+        let mut timestamps = Timestamps::new();
+        timestamps.set_extractor(Some(TimestampExtractor::new(None, None, None).unwrap()));
+        let cancel = CancellationToken::new();
+        let update = index_rows(file.path(), timestamps.cursor().unwrap(), 2, &cancel)
+            .unwrap()
+            .unwrap();
+        assert_eq!((update.rows, update.bytes), (2, 32));
+        timestamps.update(update).unwrap();
+        let update = index_rows(file.path(), timestamps.cursor().unwrap(), 3, &cancel)
+            .unwrap()
+            .unwrap();
+        timestamps.update(update).unwrap();
+        let (points, rows) = timestamps.points();
+        assert_eq!(rows, 3);
+        assert_eq!(
+            points.iter().map(|(row, _)| *row).collect::<Vec<u64>>(),
+            [0, 2]
+        );
+        assert_eq!(points[1].1 - points[0].1, 1_000);
+    }
+}
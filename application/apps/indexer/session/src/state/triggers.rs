@@ -0,0 +1,219 @@
+use processor::search::{
+    filter::{self, SearchFilter},
+    searchers::values,
+    units::parse_value,
+};
+use regex::Regex;
+use std::sync::{Arc, Mutex};
+use stypes::{TriggerAction, TriggerCondition, TriggerRule};
+
+pub type SharedTriggers = Arc<Mutex<Triggers>>;
+
+#[derive(Debug)]
+enum Matcher {
+    Filter(Regex),
+    Threshold {
+        regex: Regex,
+        above: Option<f64>,
+        below: Option<f64>,
+        /// The value is out of the range, so the rule doesn't fire until the value is back
+        exceeded: bool,
+    },
+}
+
+impl Matcher {
+    fn new(condition: TriggerCondition) -> Result<Self, String> {
+        Ok(match condition {
+            TriggerCondition::Filter(condition) => {
+                let filter = SearchFilter::new(
+                    condition.value,
+                    condition.is_regex,
+                    condition.ignore_case,
+                    condition.is_word,
+                );
+                Matcher::Filter(
+                    Regex::new(&filter::as_regex(&filter))
+                        .map_err(|err| format!("Invalid filter \"{}\": {err}", filter.value))?,
+                )
+            }
+            TriggerCondition::Threshold {
+                filter,
+                above,
+                below,
+            } => {
+                if above.is_none() && below.is_none() {
+                    return Err(format!("No thresholds defined for \"{filter}\""));
+                }
+                let regex = Regex::new(&values::as_regex(&filter))
+                    .map_err(|err| format!("Invalid filter \"{filter}\": {err}"))?;
+                if regex.captures_len() < 2 {
+                    return Err(format!("No capture group in \"{filter}\""));
+                }
+                Matcher::Threshold {
+                    regex,
+                    above,
+                    below,
+                    exceeded: false,
+                }
+            }
+        })
+    }
+
+    fn fires(&mut self, msg: &str) -> bool {
+        match self {
+            Matcher::Filter(regex) => regex.is_match(msg),
+            Matcher::Threshold {
+                regex,
+                above,
+                below,
+                exceeded,
+            } => {
+                let Some(value) = regex
+                    .captures(msg)
+                    .and_then(|caps| caps.get(1))
+                    .and_then(|captured| parse_value(captured.as_str()))
+                else {
+                    return false;
+                };
+                let out = above.is_some_and(|above| value > above)
+                    || below.is_some_and(|below| value < below);
+                let fires = out && !*exceeded;
+                *exceeded = out;
+                fires
+            }
+        }
+    }
+}
+
+#[derive(Debug)]
+struct Rule {
+    matcher: Matcher,
+    actions: Vec<TriggerAction>,
+}
+
+/// Trigger rules checked on each log entry written into the session while a source is
+/// observed.
+#[derive(Debug, Default)]
+pub struct Triggers {
+    rules: Vec<Rule>,
+}
+
+impl Triggers {
+    pub fn shared() -> SharedTriggers {
+        Arc::new(Mutex::new(Triggers::default()))
+    }
+
+    /// Compiles the rules. Returns a description of the error if a rule is invalid.
+    pub fn new(rules: Vec<TriggerRule>) -> Result<Self, String> {
+        Ok(Self {
+            rules: rules
+                .into_iter()
+                .map(|rule| {
+                    Matcher::new(rule.condition).map(|matcher| Rule {
+                        matcher,
+                        actions: rule.actions,
+                    })
+                })
+                .collect::<Result<Vec<Rule>, String>>()?,
+        })
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rules.is_empty()
+    }
+
+    /// Checks the log entry and returns the positions of fired rules with their actions.
+    pub fn check(&mut self, msg: &str) -> Vec<(u32, Vec<TriggerAction>)> {
+        self.rules
+            .iter_mut()
+            .enumerate()
+            .filter_map(|(i, rule)| {
+                rule.matcher
+                    .fires(msg)
+                    .then(|| (i as u32, rule.actions.clone()))
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn filter(value: &str) -> TriggerCondition {
+        TriggerCondition::Filter(stypes::SearchCondition {
+            value: value.to_owned(),
+            is_regex: false,
+            ignore_case: true,
+            is_word: false,
+        })
+    }
+
+    fn fired(triggers: &mut Triggers, msg: &str) -> Vec<u32> {
+        triggers
+            .check(msg)
+            .into_iter()
+            .map(|(rule, _)| rule)
+            .collect()
+    }
+
+    #[test]
+    fn rules() {
+        let mut triggers = Triggers::new(vec![
+            TriggerRule {
+                condition: filter("error"),
+                actions: vec![TriggerAction::Notify],
+            },
+            TriggerRule {
+                condition: TriggerCondition::Threshold {
+                    filter: String::from(r"temp=(\d+)"),
+                    above: Some(80.0),
+                    below: None,
+                },
+                actions: vec![TriggerAction::Bookmark, TriggerAction::StopAfter(10)],
+            },
+        ])
+        .expect("rules are valid");
+        assert!(fired(&mut triggers, "all fine").is_empty());
+        assert_eq!(fired(&mut triggers, "ERROR: temp=90"), vec![0, 1]);
+        // The value is still out of the range
+        assert!(fired(&mut triggers, "temp=95").is_empty());
+        assert!(fired(&mut triggers, "temp=70").is_empty());
+        assert_eq!(fired(&mut triggers, "temp=81"), vec![1]);
+        assert!(matches!(
+            triggers.check("temp=70 error").as_slice(),
+            [(0, actions)] if matches!(actions.as_slice(), [TriggerAction::Notify])
+        ));
+    }
+
+    #[test]
+    fn invalid_rules() {
+        let threshold = |filter: &str, above: Option<f64>| {
+            Triggers::new(vec![TriggerRule {
+                condition: TriggerCondition::Threshold {
+                    filter: filter.to_owned(),
+                    above,
+                    below: None,
+                },
+                actions: vec![],
+            }])
+        };
+        assert!(threshold(r"temp=(\d+)", Some(1.0)).is_ok());
+        assert!(threshold(r"temp=\d+", Some(1.0)).is_err());
+        assert!(threshold(r"temp=(\d+)", None).is_err());
+        assert!(threshold(r"temp=(\d+", Some(1.0)).is_err());
+        let filter = TriggerCondition::Filter(stypes::SearchCondition {
+            value: String::from("(unclosed"),
+            is_regex: true,
+            ignore_case: false,
+            is_word: false,
+        });
+        assert!(
+            Triggers::new(vec![TriggerRule {
+                condition: filter,
+                actions: vec![],
+            }])
+            .is_err()
+        );
+    }
+}
@@ -10,7 +10,7 @@ pub type Point2D = (f64, f64);
 pub struct CandlePoint {
     pub(crate) row: u64,
     min_max_y: Option<(f64, f64)>,
-    y_value: f64,
+    pub(crate) y_value: f64,
 }
 
 impl From<CandlePoint> for stypes::Point {
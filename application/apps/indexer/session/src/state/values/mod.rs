@@ -4,8 +4,10 @@ use thiserror::Error;
 use tokio::sync::mpsc::UnboundedSender;
 
 pub mod graph;
+mod stats;
 
 use graph::{CandlePoint, Point2D, candled_graph};
+use stats::Summary;
 
 #[derive(Error, Debug)]
 pub enum ValuesError {
@@ -29,6 +31,10 @@ pub struct Values {
     /// maps the dataset id to (min_y, max_y, list of data-points)
     values: HashMap<u8, (f64, f64, Vec<CandlePoint>)>,
     errors: HashMap<u64, Vec<(u8, String)>>,
+    /// Descriptions of the datasets
+    series: Vec<stypes::ValueSeries>,
+    /// Statistics of the datasets over the whole session
+    summaries: HashMap<u8, Summary>,
     tx_callback_events: Option<UnboundedSender<stypes::CallbackEvent>>,
 }
 
@@ -37,14 +43,18 @@ impl Values {
         Values {
             values: HashMap::new(),
             errors: HashMap::new(),
+            series: Vec::new(),
+            summaries: HashMap::new(),
             tx_callback_events,
         }
     }
 
-    /// Drops all values and errors
+    /// Drops all values, errors and descriptions of datasets
     pub(crate) fn drop(&mut self) {
         self.errors.clear();
         self.values.clear();
+        self.series.clear();
+        self.summaries.clear();
         self.notify(true);
     }
 
@@ -53,6 +63,8 @@ impl Values {
         for (value_set_id, vs) in values {
             let min = Values::min(&vs);
             let max = Values::max(&vs);
+            self.summaries
+                .insert(value_set_id, Summary::new(vs.iter().map(|(_, v)| *v)));
             let v: Vec<CandlePoint> = vec![];
             let candle_points = vs.iter().fold(v, |mut acc, p| {
                 acc.push((*p).into());
@@ -63,11 +75,24 @@ impl Values {
         self.notify(false);
     }
 
+    /// Sets descriptions of datasets
+    pub(crate) fn set_series(&mut self, series: Vec<stypes::ValueSeries>) {
+        self.series = series;
+    }
+
+    pub(crate) fn series(&self) -> Vec<stypes::ValueSeries> {
+        self.series.clone()
+    }
+
     /// Append new chunk of data to existed
     pub(crate) fn append_values(&mut self, values: HashMap<u8, Vec<(u64, f64)>>) {
         for (value_set_id, vs) in values {
             let upd_min = Values::min(&vs);
             let upd_max = Values::max(&vs);
+            self.summaries
+                .entry(value_set_id)
+                .or_default()
+                .append(vs.iter().map(|(_, v)| *v));
             if let Some((min, max, values)) = self.values.get_mut(&value_set_id) {
                 for v in vs {
                     values.push(v.into())
@@ -131,6 +156,41 @@ impl Values {
         Ok(datasets)
     }
 
+    /// Returns statistics of the datasets, optionally limited to the rows of the frame.
+    /// Datasets without values (in the frame) are skipped.
+    pub(crate) fn get_statistics(
+        &self,
+        frame: Option<RangeInclusive<u64>>,
+    ) -> Result<Vec<stypes::ValueStatistics>, ValuesError> {
+        let mut statistics: Vec<stypes::ValueStatistics> = match frame {
+            None => self
+                .summaries
+                .iter()
+                .filter_map(|(id, summary)| summary.statistics(*id))
+                .collect(),
+            Some(frame) => {
+                if frame.end() < frame.start() {
+                    return Err(ValuesError::InvalidFrame(format!(
+                        "[{}, {}]",
+                        frame.start(),
+                        frame.end()
+                    )));
+                }
+                self.values
+                    .iter()
+                    .filter_map(|(id, (_, _, points))| {
+                        let from = points.partition_point(|point| point.row_before(&frame));
+                        let to = points.partition_point(|point| !point.row_after(&frame));
+                        Summary::new(points[from..to.max(from)].iter().map(|p| p.y_value))
+                            .statistics(*id)
+                    })
+                    .collect()
+            }
+        };
+        statistics.sort_by_key(|stats| stats.id);
+        Ok(statistics)
+    }
+
     #[allow(clippy::type_complexity)]
     fn get_fragment(
         &self,
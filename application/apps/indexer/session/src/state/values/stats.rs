@@ -0,0 +1,119 @@
+/// Statistics of a series of values which are updated as new values arrive.
+///
+/// Count, mean and variance are tracked with Welford's algorithm; the values are kept
+/// sorted to get the median and percentiles without sorting on each request.
+#[derive(Debug, Default)]
+pub struct Summary {
+    count: u64,
+    mean: f64,
+    /// Sum of squares of differences from the mean
+    m2: f64,
+    /// Values in ascending order
+    sorted: Vec<f64>,
+}
+
+impl Summary {
+    pub fn new(values: impl Iterator<Item = f64>) -> Self {
+        let mut summary = Summary::default();
+        summary.append(values);
+        summary
+    }
+
+    /// Adds values to the summary
+    pub fn append(&mut self, values: impl Iterator<Item = f64>) {
+        let mut chunk: Vec<f64> = values.filter(|v| !v.is_nan()).collect();
+        if chunk.is_empty() {
+            return;
+        }
+        for value in chunk.iter() {
+            self.count += 1;
+            let delta = value - self.mean;
+            self.mean += delta / self.count as f64;
+            self.m2 += delta * (value - self.mean);
+        }
+        chunk.sort_by(f64::total_cmp);
+        if self.sorted.last().is_none_or(|last| *last <= chunk[0]) {
+            self.sorted.append(&mut chunk);
+        } else {
+            self.sorted = merge(&self.sorted, &chunk);
+        }
+    }
+
+    /// Returns the statistics of the series with the given ID or `None` if there are no
+    /// values
+    pub fn statistics(&self, id: u8) -> Option<stypes::ValueStatistics> {
+        let (Some(min), Some(max)) = (self.sorted.first(), self.sorted.last()) else {
+            return None;
+        };
+        Some(stypes::ValueStatistics {
+            id,
+            count: self.count,
+            min: *min,
+            max: *max,
+            mean: self.mean,
+            median: self.percentile(0.5),
+            p90: self.percentile(0.9),
+            p99: self.percentile(0.99),
+            stddev: (self.m2 / self.count as f64).sqrt(),
+        })
+    }
+
+    /// Returns the percentile interpolating linearly between the closest ranks
+    fn percentile(&self, p: f64) -> f64 {
+        let rank = p * (self.sorted.len() - 1) as f64;
+        let lower = rank.floor() as usize;
+        let upper = rank.ceil() as usize;
+        self.sorted[lower] + (self.sorted[upper] - self.sorted[lower]) * (rank - lower as f64)
+    }
+}
+
+fn merge(left: &[f64], right: &[f64]) -> Vec<f64> {
+    let mut merged = Vec::with_capacity(left.len() + right.len());
+    let (mut l, mut r) = (0, 0);
+    while l < left.len() && r < right.len() {
+        if left[l] <= right[r] {
+            merged.push(left[l]);
+            l += 1;
+        } else {
+            merged.push(right[r]);
+            r += 1;
+        }
+    }
+    merged.extend_from_slice(&left[l..]);
+    merged.extend_from_slice(&right[r..]);
+    merged
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn statistics_of_appended_values() {
+        let mut summary = Summary::new([4.0, 2.0, 8.0].into_iter());
+        summary.append([6.0, 10.0].into_iter());
+        summary.append([1.0, 3.0, 5.0, 7.0, 9.0].into_iter());
+        let stats = summary.statistics(3).expect("statistics are available");
+        assert_eq!(stats.id, 3);
+        assert_eq!(stats.count, 10);
+        assert_eq!(stats.min, 1.0);
+        assert_eq!(stats.max, 10.0);
+        assert_eq!(stats.mean, 5.5);
+        assert_eq!(stats.median, 5.5);
+        assert!((stats.p90 - 9.1).abs() < 1e-9);
+        assert!((stats.p99 - 9.91).abs() < 1e-9);
+        assert!((stats.stddev - 8.25f64.sqrt()).abs() < 1e-9);
+        // Statistics don't depend on the order of appending
+        let once = Summary::new((1..=10).map(|v| v as f64))
+            .statistics(3)
+            .expect("statistics are available");
+        assert_eq!(once.median, stats.median);
+        assert!((once.stddev - stats.stddev).abs() < 1e-9);
+    }
+
+    #[test]
+    fn no_statistics_without_values() {
+        assert!(Summary::new(std::iter::empty()).statistics(0).is_none());
+        assert!(Summary::new([f64::NAN].into_iter()).statistics(0).is_none());
+    }
+}
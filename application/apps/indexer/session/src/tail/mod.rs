@@ -1,12 +1,20 @@
-use log::error;
-use std::path::Path;
+use log::warn;
+use notify::{Event, RecommendedWatcher, RecursiveMode, Watcher};
+use std::{
+    fs::Metadata,
+    io::ErrorKind,
+    path::{Path, PathBuf},
+};
 use thiserror::Error as ThisError;
 use tokio::{
-    sync::mpsc::Sender,
-    time::{Duration, timeout},
+    select,
+    sync::mpsc::{Receiver, Sender, UnboundedReceiver, unbounded_channel},
+    time::{Duration, MissedTickBehavior, interval},
 };
 use tokio_util::sync::CancellationToken;
 
+/// Interval of checking the file. Notifications of the file system only speed up detecting
+/// changes, because they aren't available everywhere (e.g. on network shares).
 const TRACKING_INTERVAL_MS: u64 = 1000;
 
 #[derive(ThisError, Debug)]
@@ -17,37 +25,242 @@ pub enum Error {
     Channel(String),
 }
 
+/// Change of a tracked file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Update {
+    /// Data was appended to the file.
+    Appended,
+    /// The file was truncated, its content starts over.
+    Truncated,
+    /// The file was replaced by a new file with the same path (e.g. rotated by a logger).
+    Rotated,
+}
+
+/// Identity and size of the tracked file.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct FileState {
+    id: Option<u128>,
+    size: u64,
+}
+
+impl FileState {
+    fn new(md: &Metadata) -> Self {
+        Self {
+            id: file_id(md),
+            size: md.len(),
+        }
+    }
+
+    /// Returns the change since the previous state of the file.
+    fn changes(&self, previous: &FileState) -> Option<Update> {
+        if self.id != previous.id {
+            Some(Update::Rotated)
+        } else if self.size < previous.size {
+            Some(Update::Truncated)
+        } else if self.size > previous.size {
+            Some(Update::Appended)
+        } else {
+            None
+        }
+    }
+}
+
+/// Returns the identity of the file, which doesn't change when the file is renamed.
+#[cfg(unix)]
+pub(crate) fn file_id(md: &Metadata) -> Option<u128> {
+    use std::os::unix::fs::MetadataExt;
+    Some(((md.dev() as u128) << 64) | md.ino() as u128)
+}
+
+#[cfg(not(unix))]
+pub(crate) fn file_id(md: &Metadata) -> Option<u128> {
+    md.created()
+        .ok()
+        .and_then(|created| created.duration_since(std::time::UNIX_EPOCH).ok())
+        .map(|created| created.as_nanos())
+}
+
+/// Returns the state of the file or `None` if the file doesn't exist, which happens between
+/// renaming a file and creating the new one while it's rotated.
+async fn file_state(path: &Path) -> Result<Option<FileState>, Error> {
+    match tokio::fs::metadata(path).await {
+        Ok(md) => Ok(Some(FileState::new(&md))),
+        Err(err) if err.kind() == ErrorKind::NotFound => Ok(None),
+        Err(err) => Err(Error::Io(err.to_string())),
+    }
+}
+
+/// Watches the folder of the file to get notified about changes of the file, including
+/// creating it again after it was rotated. Returns `None` if notifications aren't available.
+fn watch(path: &Path) -> Option<(RecommendedWatcher, UnboundedReceiver<()>)> {
+    let file_name = path.file_name()?.to_owned();
+    let folder = match path.parent() {
+        Some(folder) if !folder.as_os_str().is_empty() => folder.to_path_buf(),
+        _ => PathBuf::from("."),
+    };
+    watch_folder(&folder, move |path| {
+        path.file_name() == Some(file_name.as_os_str())
+    })
+}
+
+/// Watches the folder (without subfolders) and sends a notification each time an entry
+/// accepted by the filter was changed. Returns `None` if notifications aren't available.
+pub(crate) fn watch_folder(
+    folder: &Path,
+    filter: impl Fn(&Path) -> bool + Send + 'static,
+) -> Option<(RecommendedWatcher, UnboundedReceiver<()>)> {
+    let (tx_notification, rx_notification) = unbounded_channel();
+    let mut watcher = notify::recommended_watcher(move |event: notify::Result<Event>| {
+        let Ok(event) = event else {
+            return;
+        };
+        if event.paths.iter().any(|path| filter(path)) {
+            // The receiver is gone only when tracking is done.
+            let _ = tx_notification.send(());
+        }
+    })
+    .map_err(|err| warn!("Fail to create watcher of {}: {err}", folder.display()))
+    .ok()?;
+    watcher
+        .watch(folder, RecursiveMode::NonRecursive)
+        .map_err(|err| warn!("Fail to watch {}: {err}", folder.display()))
+        .ok()?;
+    Some((watcher, rx_notification))
+}
+
+/// Tracks the file and sends an update each time data was appended to it, it was truncated
+/// or it was rotated.
 pub async fn track(
     path: &Path,
-    tx_update: Sender<Result<(), Error>>,
+    tx_update: Sender<Result<Update, Error>>,
     shutdown: CancellationToken,
 ) -> Result<(), Error> {
-    use tokio::fs;
-    let mut size = fs::metadata(path)
-        .await
-        .map(|md| md.len())
-        .map_err(|e| Error::Io(e.to_string()))?;
+    let mut state = file_state(path)
+        .await?
+        .ok_or_else(|| Error::Io(format!("File {} doesn't exist", path.display())))?;
+    // The watcher stops watching when it's dropped.
+    let (_watcher, mut rx_notification) = watch(path).unzip();
+    let mut ticker = interval(Duration::from_millis(TRACKING_INTERVAL_MS));
+    ticker.set_missed_tick_behavior(MissedTickBehavior::Delay);
     loop {
-        match timeout(
-            Duration::from_millis(TRACKING_INTERVAL_MS),
-            shutdown.cancelled(),
-        )
-        .await
-        {
-            Ok(_) => break,
-            Err(_) => {
-                let updated = fs::metadata(path)
-                    .await
-                    .map(|md| md.len())
-                    .map_err(|e| Error::Io(e.to_string()))?;
-                if updated != size {
-                    size = updated;
-                    if let Err(err) = tx_update.send(Ok(())).await {
-                        return Err(Error::Channel(format!("Fail to send update signal: {err}")));
-                    }
+        select! {
+            _ = shutdown.cancelled() => break,
+            _ = ticker.tick() => {},
+            Some(()) = async {
+                match rx_notification.as_mut() {
+                    Some(rx_notification) => rx_notification.recv().await,
+                    None => None,
                 }
-            }
+            } => {},
+        }
+        let Some(current) = file_state(path).await? else {
+            continue;
         };
+        if let Some(update) = current.changes(&state) {
+            state = current;
+            if let Err(err) = tx_update.send(Ok(update)).await {
+                return Err(Error::Channel(format!("Fail to send update signal: {err}")));
+            }
+        }
     }
     Ok(())
 }
+
+/// Follows a tracked file for a byte source: receives the updates of the tracker and
+/// reopens the source when the file was truncated or rotated.
+pub struct Follower<'a, S> {
+    rx_update: Receiver<Result<Update, Error>>,
+    reopen: Box<dyn Fn() -> Result<S, stypes::NativeError> + Send + Sync + 'a>,
+}
+
+impl<'a, S> Follower<'a, S> {
+    pub fn new(
+        rx_update: Receiver<Result<Update, Error>>,
+        reopen: impl Fn() -> Result<S, stypes::NativeError> + Send + Sync + 'a,
+    ) -> Self {
+        Self {
+            rx_update,
+            reopen: Box::new(reopen),
+        }
+    }
+
+    /// Waits for the next update of the file. Returns `None` when tracking is done.
+    pub async fn next(&mut self) -> Option<Result<Update, Error>> {
+        self.rx_update.recv().await
+    }
+
+    /// Opens the source from the start of the file again.
+    pub fn reopen(&self) -> Result<S, stypes::NativeError> {
+        (self.reopen)()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{fs, io::Write};
+    use tokio::{sync::mpsc::channel, time::timeout};
+
+    async fn next_update(rx_update: &mut Receiver<Result<Update, Error>>) -> Update {
+        timeout(Duration::from_secs(5), rx_update.recv())
+            .await
+            .expect("update in time")
+            .expect("open channel")
+            .expect("tracking without errors")
+    }
+
+    #[tokio::test]
+    async fn track_changes() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("app.log");
+        fs::write(&path, "first\n").unwrap();
+        let (tx_update, mut rx_update) = channel(1);
+        let shutdown = CancellationToken::new();
+        let tracking = tokio::spawn({
+            let path = path.clone();
+            let shutdown = shutdown.clone();
+            async move { track(&path, tx_update, shutdown).await }
+        });
+        // Gives the tracker the time to read the initial state.
+        tokio::time::sleep(Duration::from_millis(100)).await;
+
+        let mut file = fs::OpenOptions::new().append(true).open(&path).unwrap();
+        file.write_all(b"second\n").unwrap();
+        assert_eq!(next_update(&mut rx_update).await, Update::Appended);
+
+        file.set_len(0).unwrap();
+        assert_eq!(next_update(&mut rx_update).await, Update::Truncated);
+
+        fs::rename(&path, dir.path().join("app.log.1")).unwrap();
+        fs::write(&path, "third\n").unwrap();
+        assert_eq!(next_update(&mut rx_update).await, Update::Rotated);
+
+        shutdown.cancel();
+        assert!(tracking.await.unwrap().is_ok());
+    }
+
+    #[test]
+    fn compare_states() {
+        let state = FileState {
+            id: Some(1),
+            size: 10,
+        };
+        assert_eq!(state.changes(&state), None);
+        assert_eq!(
+            FileState { size: 20, ..state }.changes(&state),
+            Some(Update::Appended)
+        );
+        assert_eq!(
+            FileState { size: 0, ..state }.changes(&state),
+            Some(Update::Truncated)
+        );
+        assert_eq!(
+            FileState {
+                id: Some(2),
+                size: 20
+            }
+            .changes(&state),
+            Some(Update::Rotated)
+        );
+    }
+}
@@ -114,6 +114,54 @@ impl UnboundSessionAPI {
             .await
     }
 
+    pub async fn detect_file_format(
+        &self,
+        id: u64,
+        file_path: String,
+    ) -> Result<stypes::CommandOutcome<stypes::FormatDetection>, stypes::ComputationError> {
+        let (tx_results, rx_results) = oneshot::channel();
+        self.process_command(
+            id,
+            rx_results,
+            Command::DetectFileFormat(file_path, tx_results),
+        )
+        .await
+    }
+
+    pub async fn get_files_chronology(
+        &self,
+        id: u64,
+        files: Vec<String>,
+    ) -> Result<stypes::CommandOutcome<stypes::FilesChronology>, stypes::ComputationError> {
+        let (tx_results, rx_results) = oneshot::channel();
+        self.process_command(
+            id,
+            rx_results,
+            Command::GetFilesChronology(files, tx_results),
+        )
+        .await
+    }
+
+    pub async fn compare_files(
+        &self,
+        id: u64,
+        options: stypes::ComparisonOptions,
+    ) -> Result<stypes::CommandOutcome<stypes::FilesComparison>, stypes::ComputationError> {
+        let (tx_results, rx_results) = oneshot::channel();
+        self.process_command(id, rx_results, Command::CompareFiles(options, tx_results))
+            .await
+    }
+
+    pub async fn grep(
+        &self,
+        id: u64,
+        options: stypes::GrepOptions,
+    ) -> Result<stypes::CommandOutcome<stypes::GrepResult>, stypes::ComputationError> {
+        let (tx_results, rx_results) = oneshot::channel();
+        self.process_command(id, rx_results, Command::Grep(options, tx_results))
+            .await
+    }
+
     pub async fn spawn_process(
         &self,
         id: u64,
@@ -0,0 +1,473 @@
+use super::dlt::{DLT_STORAGE_HEADER_MAGIC, PCAP_MAGICS, PCAPNG_MAGIC};
+use crate::unbound::signal::Signal;
+use log::warn;
+use parsers::text::timestamp::{TimestampExtractor, year_of};
+use sources::binary::compressed::DecompressedFile;
+use std::{
+    fs::File,
+    io::{Read, Seek, SeekFrom},
+    path::Path,
+};
+use stypes::{FileTimeRange, FilesChronology, FilesOverlap};
+use tokio_util::sync::CancellationToken;
+
+/// Count of bytes at the start and at the end of a file which are inspected for timestamps.
+const WINDOW_SIZE: usize = 64 * 1024;
+const STORAGE_HEADER_LEN: usize = 16;
+const PCAP_HEADER_LEN: usize = 24;
+const PCAP_RECORD_HEADER_LEN: usize = 16;
+/// Max size of a captured packet, larger lengths of records are corrupted data.
+const PCAP_MAX_PACKET_LEN: u32 = 256 * 1024;
+/// Byte order magic of the section header block of PCAPNG files.
+const PCAPNG_BYTE_ORDER_MAGIC: u32 = 0x1A2B3C4D;
+const PCAPNG_ENHANCED_PACKET_BLOCK: u32 = 6;
+const PCAPNG_MIN_BLOCK_LEN: usize = 12;
+
+/// The beginning and the end of a file.
+struct Sample {
+    head: Vec<u8>,
+    tail: Vec<u8>,
+    /// The tail starts with the start of the file.
+    whole: bool,
+}
+
+/// Reads the timestamps of the first and the last message of each file and orders the files
+/// chronologically. Timestamps are taken from DLT storage headers, PCAP/PCAPNG packets and
+/// common timestamp formats at the beginning of text lines. Overlapping files are reported.
+pub async fn order_by_time(
+    files: Vec<String>,
+    signal: Signal,
+) -> Result<stypes::CommandOutcome<FilesChronology>, stypes::ComputationError> {
+    match files_chronology(files, None, &signal.token())
+        .map_err(stypes::ComputationError::IoOperation)?
+    {
+        Some(chronology) => Ok(stypes::CommandOutcome::Finished(chronology)),
+        None => Ok(stypes::CommandOutcome::Cancelled),
+    }
+}
+
+/// Orders the files chronologically, see [`order_by_time`]. Timestamps of text files are
+/// extracted with the given settings or detected if they aren't defined.
+///
+/// Returns `None` if the operation was cancelled.
+pub fn files_chronology(
+    files: Vec<String>,
+    settings: Option<&stypes::TimestampSettings>,
+    cancel: &CancellationToken,
+) -> Result<Option<FilesChronology>, String> {
+    let mut ranges = Vec::with_capacity(files.len());
+    for path in files {
+        let Some(sample) =
+            read_sample(Path::new(&path), cancel).map_err(|err| format!("{path}: {err}"))?
+        else {
+            return Ok(None);
+        };
+        // Timestamps without year are completed by the year the file was modified.
+        let year = settings
+            .and_then(|settings| settings.year)
+            .unwrap_or_else(|| {
+                std::fs::metadata(&path)
+                    .and_then(|md| md.modified())
+                    .map(year_of)
+                    .unwrap_or(1970)
+            });
+        let extractor = TimestampExtractor::new(
+            settings.and_then(|settings| settings.format.clone()),
+            settings.and_then(|settings| settings.tz.as_deref()),
+            Some(year),
+        )
+        .map_err(|err| err.to_string())?;
+        let (first, last) = time_range(&sample, &extractor);
+        ranges.push(FileTimeRange { path, first, last });
+    }
+    let chronology = chronology(ranges);
+    for overlap in chronology.overlaps.iter() {
+        warn!(
+            "Files {} and {} overlap by {} ms",
+            overlap.earlier, overlap.later, overlap.duration
+        );
+    }
+    Ok(Some(chronology))
+}
+
+/// Orders files by their first timestamps and finds overlapping files.
+fn chronology(mut files: Vec<FileTimeRange>) -> FilesChronology {
+    // The sort is stable, files without timestamps keep their order.
+    files.sort_by_key(|file| (file.first.is_none(), file.first));
+    let mut overlaps = Vec::new();
+    for (i, earlier) in files.iter().enumerate() {
+        let Some(end) = earlier.last else {
+            continue;
+        };
+        for later in files[i + 1..].iter() {
+            if let Some(start) = later.first.filter(|start| *start < end) {
+                overlaps.push(FilesOverlap {
+                    earlier: earlier.path.clone(),
+                    later: later.path.clone(),
+                    duration: end - start,
+                });
+            }
+        }
+    }
+    FilesChronology { files, overlaps }
+}
+
+/// Reads the beginning and the end of the file. Compressed files and entries of archives are
+/// decompressed completely to get their end.
+///
+/// Returns `None` if the operation was cancelled.
+fn read_sample(path: &Path, cancel: &CancellationToken) -> std::io::Result<Option<Sample>> {
+    let mut input = DecompressedFile::open(path).map_err(std::io::Error::other)?;
+    let mut head = Vec::with_capacity(WINDOW_SIZE);
+    (&mut input)
+        .take(WINDOW_SIZE as u64)
+        .read_to_end(&mut head)?;
+    if input.container().is_plain() {
+        let mut file = File::open(path)?;
+        let len = file.metadata()?.len();
+        let start = len.saturating_sub(WINDOW_SIZE as u64);
+        file.seek(SeekFrom::Start(start))?;
+        let mut tail = Vec::with_capacity(WINDOW_SIZE);
+        file.read_to_end(&mut tail)?;
+        return Ok(Some(Sample {
+            head,
+            tail,
+            whole: start == 0,
+        }));
+    }
+    let mut tail = head.clone();
+    let mut whole = true;
+    let mut buffer = vec![0u8; WINDOW_SIZE];
+    loop {
+        if cancel.is_cancelled() {
+            return Ok(None);
+        }
+        let read = input.read(&mut buffer)?;
+        if read == 0 {
+            break;
+        }
+        tail.extend_from_slice(&buffer[..read]);
+        if tail.len() > WINDOW_SIZE * 2 {
+            tail.drain(..tail.len() - WINDOW_SIZE);
+            whole = false;
+        }
+    }
+    if tail.len() > WINDOW_SIZE {
+        tail.drain(..tail.len() - WINDOW_SIZE);
+        whole = false;
+    }
+    Ok(Some(Sample { head, tail, whole }))
+}
+
+/// Returns the timestamps of the first and the last message in milliseconds.
+fn time_range(sample: &Sample, extractor: &TimestampExtractor) -> (Option<u64>, Option<u64>) {
+    let head = sample.head.as_slice();
+    if head.starts_with(&PCAPNG_MAGIC) {
+        let big_endian = read_u32(head, 8, true) == Some(PCAPNG_BYTE_ORDER_MAGIC);
+        (
+            pcapng_first(head, big_endian),
+            pcapng_last(&sample.tail, big_endian),
+        )
+    } else if let Some(index) = PCAP_MAGICS.iter().position(|magic| head.starts_with(magic)) {
+        // Magic numbers are ordered by big/little endian and micro/nanosecond resolution.
+        let (big_endian, nanos) = (index % 2 == 0, index >= 2);
+        // The global header precedes the records if the tail is the whole file.
+        let skip = if sample.whole { PCAP_HEADER_LEN } else { 0 };
+        let last = sample
+            .tail
+            .get(skip..)
+            .and_then(|records| {
+                records_end(records, |data, pos| pcap_record_len(data, pos, big_endian))
+            })
+            .and_then(|pos| pcap_time(&sample.tail, skip + pos, big_endian, nanos));
+        (pcap_time(head, PCAP_HEADER_LEN, big_endian, nanos), last)
+    } else if head.starts_with(&DLT_STORAGE_HEADER_MAGIC) {
+        let last = records_end(&sample.tail, storage_header_record_len)
+            .and_then(|pos| storage_header_time(&sample.tail, pos));
+        (storage_header_time(head, 0), last)
+    } else {
+        (text_first(head, extractor), text_last(sample, extractor))
+    }
+}
+
+/// Finds the first position in the data which starts a chain of records reaching exactly the
+/// end of the data and returns the position of the last record of this chain. Even if the
+/// chain starts within a record by chance, it runs into the actual records before the end.
+fn records_end<F: Fn(&[u8], usize) -> Option<usize>>(data: &[u8], record_len: F) -> Option<usize> {
+    (0..data.len()).find_map(|start| {
+        let mut pos = start;
+        loop {
+            let len = record_len(data, pos)?;
+            if pos + len == data.len() {
+                return Some(pos);
+            }
+            pos += len;
+            if pos > data.len() {
+                return None;
+            }
+        }
+    })
+}
+
+fn read_u32(data: &[u8], pos: usize, big_endian: bool) -> Option<u32> {
+    let bytes: [u8; 4] = data.get(pos..pos + 4)?.try_into().ok()?;
+    Some(if big_endian {
+        u32::from_be_bytes(bytes)
+    } else {
+        u32::from_le_bytes(bytes)
+    })
+}
+
+fn storage_header_record_len(data: &[u8], pos: usize) -> Option<usize> {
+    if !data.get(pos..)?.starts_with(&DLT_STORAGE_HEADER_MAGIC) {
+        return None;
+    }
+    // The length of the message is a part of the standard header, which follows the
+    // storage header.
+    let offset = pos + STORAGE_HEADER_LEN + 2;
+    let len = u16::from_be_bytes(data.get(offset..offset + 2)?.try_into().ok()?) as usize;
+    (len >= 4).then_some(STORAGE_HEADER_LEN + len)
+}
+
+fn storage_header_time(data: &[u8], pos: usize) -> Option<u64> {
+    if !data.get(pos..)?.starts_with(&DLT_STORAGE_HEADER_MAGIC) {
+        return None;
+    }
+    let seconds = read_u32(data, pos + 4, false)? as u64;
+    let micros = read_u32(data, pos + 8, false)? as u64;
+    Some(seconds * 1000 + micros / 1000)
+}
+
+fn pcap_record_len(data: &[u8], pos: usize, big_endian: bool) -> Option<usize> {
+    let captured = read_u32(data, pos + 8, big_endian)?;
+    let original = read_u32(data, pos + 12, big_endian)?;
+    (captured <= PCAP_MAX_PACKET_LEN && captured <= original)
+        .then_some(PCAP_RECORD_HEADER_LEN + captured as usize)
+}
+
+fn pcap_time(data: &[u8], pos: usize, big_endian: bool, nanos: bool) -> Option<u64> {
+    let seconds = read_u32(data, pos, big_endian)? as u64;
+    let fraction = read_u32(data, pos + 4, big_endian)? as u64;
+    Some(
+        seconds * 1000
+            + if nanos {
+                fraction / 1_000_000
+            } else {
+                fraction / 1000
+            },
+    )
+}
+
+/// Returns the time of the enhanced packet block at the position; timestamps are taken as
+/// microseconds like the PCAPNG source does.
+fn pcapng_time(data: &[u8], pos: usize, big_endian: bool) -> Option<u64> {
+    let high = read_u32(data, pos + 12, big_endian)? as u64;
+    let low = read_u32(data, pos + 16, big_endian)? as u64;
+    Some(((high << 32) | low) / 1000)
+}
+
+/// Walks through the blocks from the start to the first enhanced packet block.
+fn pcapng_first(data: &[u8], big_endian: bool) -> Option<u64> {
+    let mut pos = 0;
+    loop {
+        let block_type = read_u32(data, pos, big_endian)?;
+        let len = read_u32(data, pos + 4, big_endian)? as usize;
+        if len < PCAPNG_MIN_BLOCK_LEN {
+            return None;
+        }
+        if block_type == PCAPNG_ENHANCED_PACKET_BLOCK {
+            return pcapng_time(data, pos, big_endian);
+        }
+        pos += len;
+    }
+}
+
+/// Walks back through the blocks from the end to the last enhanced packet block. The length
+/// of a block is repeated at its end.
+fn pcapng_last(data: &[u8], big_endian: bool) -> Option<u64> {
+    let mut end = data.len();
+    loop {
+        let len = read_u32(data, end.checked_sub(4)?, big_endian)? as usize;
+        let pos = end
+            .checked_sub(len)
+            .filter(|_| len >= PCAPNG_MIN_BLOCK_LEN)?;
+        if read_u32(data, pos + 4, big_endian)? as usize != len {
+            return None;
+        }
+        if read_u32(data, pos, big_endian)? == PCAPNG_ENHANCED_PACKET_BLOCK {
+            return pcapng_time(data, pos, big_endian);
+        }
+        end = pos;
+    }
+}
+
+fn text_first(head: &[u8], extractor: &TimestampExtractor) -> Option<u64> {
+    String::from_utf8_lossy(head)
+        .lines()
+        .find_map(|line| extractor.extract(line))
+}
+
+fn text_last(sample: &Sample, extractor: &TimestampExtractor) -> Option<u64> {
+    let tail = String::from_utf8_lossy(&sample.tail);
+    let mut lines = tail.lines();
+    if !sample.whole {
+        // The first line could be cut.
+        lines.next();
+    }
+    lines.rev().find_map(|line| extractor.extract(line))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn extractor(format: Option<&str>) -> TimestampExtractor {
+        TimestampExtractor::new(format.map(String::from), None, Some(2024)).unwrap()
+    }
+
+    fn range(path: &str, first: Option<u64>, last: Option<u64>) -> FileTimeRange {
+        FileTimeRange {
+            path: path.to_owned(),
+            first,
+            last,
+        }
+    }
+
+    fn dlt_message(seconds: u32, payload_len: u16) -> Vec<u8> {
+        let mut msg = DLT_STORAGE_HEADER_MAGIC.to_vec();
+        msg.extend_from_slice(&seconds.to_le_bytes());
+        msg.extend_from_slice(&500_000u32.to_le_bytes());
+        msg.extend_from_slice(b"ECU1");
+        msg.extend_from_slice(&[0x21, 0x00]);
+        msg.extend_from_slice(&(4 + payload_len).to_be_bytes());
+        msg.extend(std::iter::repeat_n(0x44, payload_len as usize));
+        msg
+    }
+
+    #[test]
+    fn order_and_overlaps() {
+        let chronology = chronology(vec![
+            range("c.log", Some(300), Some(400)),
+            range("unknown.log", None, None),
+            range("a.log", Some(100), Some(200)),
+            range("b.log", Some(150), Some(320)),
+        ]);
+        assert_eq!(
+            chronology
+                .files
+                .iter()
+                .map(|file| file.path.as_str())
+                .collect::<Vec<&str>>(),
+            vec!["a.log", "b.log", "c.log", "unknown.log"]
+        );
+        assert_eq!(
+            chronology.overlaps,
+            vec![
+                FilesOverlap {
+                    earlier: String::from("a.log"),
+                    later: String::from("b.log"),
+                    duration: 50,
+                },
+                FilesOverlap {
+                    earlier: String::from("b.log"),
+                    later: String::from("c.log"),
+                    duration: 20,
+                }
+            ]
+        );
+    }
+
+    #[test]
+    fn dlt_time_range() {
+        let mut content = Vec::new();
+        for seconds in 0..5000 {
+            // Payloads contain the magic of storage headers to mislead the search.
+            let mut msg = dlt_message(1000 + seconds, 8);
+            msg.splice(24..28, DLT_STORAGE_HEADER_MAGIC);
+            content.extend(msg);
+        }
+        let start = content.len() - WINDOW_SIZE;
+        let sample = Sample {
+            head: content[..WINDOW_SIZE].to_vec(),
+            tail: content[start..].to_vec(),
+            whole: false,
+        };
+        assert_eq!(
+            time_range(&sample, &extractor(None)),
+            (Some(1_000_500), Some(5_999_500))
+        );
+    }
+
+    #[test]
+    fn packets_time_range() {
+        let mut pcap = PCAP_MAGICS[1].to_vec();
+        pcap.extend_from_slice(&[0u8; PCAP_HEADER_LEN - 4]);
+        for seconds in [10u32, 20, 30] {
+            pcap.extend_from_slice(&seconds.to_le_bytes());
+            pcap.extend_from_slice(&250_000u32.to_le_bytes());
+            pcap.extend_from_slice(&4u32.to_le_bytes());
+            pcap.extend_from_slice(&4u32.to_le_bytes());
+            pcap.extend_from_slice(&[0xAA; 4]);
+        }
+        let mut pcapng = Vec::new();
+        let mut block = |block_type: u32, body: &[u8]| {
+            let len = (body.len() + 12) as u32;
+            pcapng.extend_from_slice(&block_type.to_le_bytes());
+            pcapng.extend_from_slice(&len.to_le_bytes());
+            pcapng.extend_from_slice(body);
+            pcapng.extend_from_slice(&len.to_le_bytes());
+        };
+        let mut shb = PCAPNG_BYTE_ORDER_MAGIC.to_le_bytes().to_vec();
+        shb.extend_from_slice(&[1, 0, 0, 0, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF]);
+        block(u32::from_le_bytes(PCAPNG_MAGIC), &shb);
+        block(1, &[1, 0, 0, 0, 0, 0, 0, 0]);
+        for micros in [1_500_000u64, 2_500_000] {
+            let mut epb = 0u32.to_le_bytes().to_vec();
+            epb.extend_from_slice(&((micros >> 32) as u32).to_le_bytes());
+            epb.extend_from_slice(&(micros as u32).to_le_bytes());
+            epb.extend_from_slice(&[0u8; 8]);
+            block(PCAPNG_ENHANCED_PACKET_BLOCK, &epb);
+        }
+        for (content, range) in [
+            (pcap, (Some(10_250), Some(30_250))),
+            (pcapng, (Some(1_500), Some(2_500))),
+        ] {
+            let sample = Sample {
+                head: content.clone(),
+                tail: content,
+                whole: true,
+            };
+            assert_eq!(time_range(&sample, &extractor(None)), range);
+        }
+    }
+
+    #[test]
+    fn text_time_range() {
+        let content = b"Jan 15 14:32:05 host app: first\nno timestamp\n\
+            Jan 15 14:35:00 host app: last\ncontinued\n";
+        let sample = Sample {
+            head: content.to_vec(),
+            tail: content.to_vec(),
+            whole: true,
+        };
+        assert_eq!(
+            time_range(&sample, &extractor(None)),
+            (Some(1_705_329_125_000), Some(1_705_329_300_000))
+        );
+    }
+
+    #[test]
+    fn text_time_range_by_format() {
+        let content = b"[15.01.2024 14:32:05] first\n[15.01.2024 14:35:00] last\n";
+        let sample = Sample {
+            head: content.to_vec(),
+            tail: content.to_vec(),
+            whole: true,
+        };
+        assert_eq!(time_range(&sample, &extractor(None)), (None, None));
+        assert_eq!(
+            time_range(&sample, &extractor(Some("%d.%m.%Y %H:%M:%S"))),
+            (Some(1_705_329_125_000), Some(1_705_329_300_000))
+        );
+    }
+}
@@ -0,0 +1,671 @@
+use crate::unbound::signal::Signal;
+use parsers::dlt::fmt::{DLT_ARGUMENT_SENTINAL, DLT_COLUMN_SENTINAL};
+use regex::{NoExpand, Regex};
+use std::{
+    borrow::Cow,
+    collections::HashMap,
+    fs::File,
+    io::{BufRead, BufReader},
+    ops::{Index, IndexMut, Range},
+};
+use stypes::{ComparedLine, ComparisonOptions, DiffHunk, FilesComparison};
+
+/// Count of rows read between checks of the cancellation.
+const CANCELLATION_CHECK_INTERVAL: u64 = 10_000;
+/// Max count of ranges of rows reported for a line.
+const MAX_RANGES: usize = 1000;
+/// Max count of lines of each list of the comparison.
+const MAX_LINES: usize = 1000;
+/// Max count of reported hunks of the alignment.
+const MAX_HUNKS: usize = 1000;
+/// Max count of edits searched to align a region of both files. Regions which differ more
+/// are reported as a single hunk, which keeps the alignment of very different files fast.
+const MAX_EDITS: usize = 1000;
+
+/// Default rules of normalization. Timestamps and IDs are masked before numbers, otherwise
+/// their parts would be masked as separate numbers.
+const DEFAULT_RULES: &[(&str, &str)] = &[
+    (
+        r"\d{4}-\d{2}-\d{2}[T ]\d{1,2}:\d{2}:\d{2}(?:[.,]\d+)?(?:Z|[+-]\d{2}:?\d{2})?",
+        "<TS>",
+    ),
+    (r"\b\d{1,2}:\d{2}:\d{2}(?:[.,]\d+)?", "<TS>"),
+    (
+        r"\b[0-9a-fA-F]{8}-[0-9a-fA-F]{4}-[0-9a-fA-F]{4}-[0-9a-fA-F]{4}-[0-9a-fA-F]{12}\b",
+        "<ID>",
+    ),
+    (r"\b0[xX][0-9a-fA-F]+\b", "<HEX>"),
+    (r"\b[0-9a-fA-F]{8,}\b", "<HEX>"),
+    (r"\b\d+(?:\.\d+)?", "<NUM>"),
+];
+
+/// Masks variable parts of lines.
+struct Normalizer {
+    rules: Vec<(Regex, String)>,
+}
+
+impl Normalizer {
+    fn new(options: &ComparisonOptions) -> Result<Self, stypes::ComputationError> {
+        let defaults = DEFAULT_RULES
+            .iter()
+            .filter(|_| options.defaults)
+            .map(|(pattern, mask)| (pattern.to_string(), mask.to_string()));
+        let rules = options
+            .rules
+            .iter()
+            .map(|rule| (rule.pattern.clone(), rule.mask.clone()))
+            .chain(defaults)
+            .map(|(pattern, mask)| {
+                Regex::new(&pattern).map(|re| (re, mask)).map_err(|err| {
+                    stypes::ComputationError::InvalidArgs(format!(
+                        "Invalid mask rule \"{pattern}\": {err}"
+                    ))
+                })
+            })
+            .collect::<Result<Vec<(Regex, String)>, stypes::ComputationError>>()?;
+        Ok(Self { rules })
+    }
+
+    fn normalize(&self, line: &str) -> String {
+        // Columns and arguments of DLT messages are compared as words
+        let mut line = line.replace([DLT_COLUMN_SENTINAL, DLT_ARGUMENT_SENTINAL], " ");
+        for (re, mask) in self.rules.iter() {
+            if let Cow::Owned(masked) = re.replace_all(&line, NoExpand(mask)) {
+                line = masked;
+            }
+        }
+        line
+    }
+}
+
+/// Rows of a file with the same normalized content.
+#[derive(Debug, Default)]
+struct Occurrences {
+    count: u64,
+    rows: Vec<stypes::Range>,
+}
+
+impl Occurrences {
+    fn add(&mut self, row: u64) {
+        self.count += 1;
+        if let Some(last) = self.rows.last_mut().filter(|last| last.end + 1 == row) {
+            last.end = row;
+        } else if self.rows.len() < MAX_RANGES {
+            self.rows.push(stypes::Range {
+                start: row,
+                end: row,
+            });
+        }
+    }
+}
+
+/// Rows of a session file as the ids of their normalized content.
+struct Side {
+    rows: Vec<u32>,
+    /// Occurrences of each normalized content by its id.
+    occurrences: HashMap<u32, Occurrences>,
+}
+
+/// Compares two session files. Lines of both files are normalized with the mask rules. The
+/// files are aligned in order by the longest common subsequence of their normalized lines
+/// to find the regions which differ. Besides, lines found on one side only or a different
+/// number of times are reported with the rows they were found in.
+pub async fn compare_files(
+    options: ComparisonOptions,
+    signal: Signal,
+) -> Result<stypes::CommandOutcome<FilesComparison>, stypes::ComputationError> {
+    let normalizer = Normalizer::new(&options)?;
+    let mut lines = HashMap::new();
+    let Some(left) = read(&options.left, &normalizer, &mut lines, &signal)? else {
+        return Ok(stypes::CommandOutcome::Cancelled);
+    };
+    let Some(right) = read(&options.right, &normalizer, &mut lines, &signal)? else {
+        return Ok(stypes::CommandOutcome::Cancelled);
+    };
+    let mut alignment = Alignment::new(&left.rows, &right.rows, &signal);
+    alignment.conquer(0..left.rows.len(), 0..right.rows.len());
+    if alignment.cancelled {
+        return Ok(stypes::CommandOutcome::Cancelled);
+    }
+    let (hunks, truncated) = (alignment.hunks, alignment.truncated);
+    let mut comparison = compare(lines, left, right);
+    comparison.hunks = hunks;
+    comparison.truncated |= truncated;
+    Ok(stypes::CommandOutcome::Finished(comparison))
+}
+
+/// Reads the rows of a session file. Normalized contents get their ids from `lines`, which
+/// is shared by both sides. Returns `None` if the command was cancelled.
+fn read(
+    path: &str,
+    normalizer: &Normalizer,
+    lines: &mut HashMap<String, u32>,
+    signal: &Signal,
+) -> Result<Option<Side>, stypes::ComputationError> {
+    let io_err =
+        |err: std::io::Error| stypes::ComputationError::IoOperation(format!("{path}: {err}"));
+    let mut reader = BufReader::new(File::open(path).map_err(io_err)?);
+    let mut side = Side {
+        rows: Vec::new(),
+        occurrences: HashMap::new(),
+    };
+    let mut buffer = Vec::new();
+    let mut row = 0;
+    loop {
+        buffer.clear();
+        if reader.read_until(b'\n', &mut buffer).map_err(io_err)? == 0 {
+            break;
+        }
+        if row % CANCELLATION_CHECK_INTERVAL == 0 && signal.is_cancelling() {
+            return Ok(None);
+        }
+        let line = String::from_utf8_lossy(&buffer);
+        let next = lines.len() as u32;
+        let id = *lines
+            .entry(normalizer.normalize(line.trim_end_matches(['\n', '\r'])))
+            .or_insert(next);
+        side.rows.push(id);
+        side.occurrences.entry(id).or_default().add(row);
+        row += 1;
+    }
+    Ok(Some(side))
+}
+
+/// Diagonals of the furthest paths of Myers' algorithm indexed by `k` in `-d..=d`.
+struct Diagonals {
+    offset: isize,
+    x: Vec<usize>,
+}
+
+impl Diagonals {
+    fn new(max_d: usize) -> Self {
+        Self {
+            offset: max_d as isize + 1,
+            x: vec![0; max_d * 2 + 3],
+        }
+    }
+}
+
+impl Index<isize> for Diagonals {
+    type Output = usize;
+
+    fn index(&self, k: isize) -> &usize {
+        &self.x[(k + self.offset) as usize]
+    }
+}
+
+impl IndexMut<isize> for Diagonals {
+    fn index_mut(&mut self, k: isize) -> &mut usize {
+        &mut self.x[(k + self.offset) as usize]
+    }
+}
+
+fn common_prefix(left: &[u32], right: &[u32]) -> usize {
+    left.iter().zip(right).take_while(|(l, r)| l == r).count()
+}
+
+fn common_suffix(left: &[u32], right: &[u32]) -> usize {
+    left.iter()
+        .rev()
+        .zip(right.iter().rev())
+        .take_while(|(l, r)| l == r)
+        .count()
+}
+
+/// Alignment of the rows of both sides by the linear space variant of Myers' algorithm,
+/// which finds the longest common subsequence. Regions which don't belong to the common
+/// subsequence are collected as hunks.
+struct Alignment<'a> {
+    left: &'a [u32],
+    right: &'a [u32],
+    forward: Diagonals,
+    backward: Diagonals,
+    hunks: Vec<DiffHunk>,
+    /// `true` if hunks were dropped because of `MAX_HUNKS`.
+    truncated: bool,
+    cancelled: bool,
+    signal: &'a Signal,
+}
+
+impl<'a> Alignment<'a> {
+    fn new(left: &'a [u32], right: &'a [u32], signal: &'a Signal) -> Self {
+        Self {
+            left,
+            right,
+            forward: Diagonals::new(MAX_EDITS),
+            backward: Diagonals::new(MAX_EDITS),
+            hunks: Vec::new(),
+            truncated: false,
+            cancelled: false,
+            signal,
+        }
+    }
+
+    /// Aligns the given regions of both sides by splitting them at the middle snake of
+    /// their shortest edit script.
+    fn conquer(&mut self, mut left: Range<usize>, mut right: Range<usize>) {
+        let prefix = common_prefix(&self.left[left.clone()], &self.right[right.clone()]);
+        left.start += prefix;
+        right.start += prefix;
+        let suffix = common_suffix(&self.left[left.clone()], &self.right[right.clone()]);
+        left.end -= suffix;
+        right.end -= suffix;
+        if (left.is_empty() && right.is_empty()) || self.truncated || self.cancelled {
+            return;
+        }
+        if self.signal.is_cancelling() {
+            self.cancelled = true;
+            return;
+        }
+        if left.is_empty() || right.is_empty() {
+            self.differ(left, right);
+        } else if let Some((x, y)) = self.middle_snake(left.clone(), right.clone()) {
+            self.conquer(left.start..x, right.start..y);
+            self.conquer(x..left.end, y..right.end);
+        } else {
+            self.differ(left, right);
+        }
+    }
+
+    /// Returns the start of the middle snake of the shortest edit script of the regions or
+    /// `None` if the regions need more than `MAX_EDITS` edits.
+    fn middle_snake(&mut self, left: Range<usize>, right: Range<usize>) -> Option<(usize, usize)> {
+        let n = left.len();
+        let m = right.len();
+        let delta = n as isize - m as isize;
+        let odd = delta & 1 == 1;
+        self.forward[1] = 0;
+        self.backward[1] = 0;
+        let max_d = ((n + m).div_ceil(2) + 1).min(MAX_EDITS) as isize;
+        for d in 0..max_d {
+            for k in (-d..=d).rev().step_by(2) {
+                let mut x = if k == -d || (k != d && self.forward[k - 1] < self.forward[k + 1]) {
+                    self.forward[k + 1]
+                } else {
+                    self.forward[k - 1] + 1
+                };
+                let y = (x as isize - k) as usize;
+                let (x0, y0) = (x, y);
+                if x < n && y < m {
+                    x += common_prefix(
+                        &self.left[left.start + x..left.end],
+                        &self.right[right.start + y..right.end],
+                    );
+                }
+                self.forward[k] = x;
+                if odd
+                    && (k - delta).abs() < d
+                    && self.forward[k] + self.backward[-(k - delta)] >= n
+                {
+                    return Some((left.start + x0, right.start + y0));
+                }
+            }
+            for k in (-d..=d).rev().step_by(2) {
+                let mut x = if k == -d || (k != d && self.backward[k - 1] < self.backward[k + 1]) {
+                    self.backward[k + 1]
+                } else {
+                    self.backward[k - 1] + 1
+                };
+                let mut y = (x as isize - k) as usize;
+                if x < n && y < m {
+                    let advance = common_suffix(
+                        &self.left[left.start..left.end - x],
+                        &self.right[right.start..right.end - y],
+                    );
+                    x += advance;
+                    y += advance;
+                }
+                self.backward[k] = x;
+                if !odd
+                    && (k - delta).abs() <= d
+                    && self.backward[k] + self.forward[-(k - delta)] >= n
+                {
+                    return Some((left.end - x, right.end - y));
+                }
+            }
+        }
+        None
+    }
+
+    /// Adds rows which differ to the hunks. Adjacent regions are joined.
+    fn differ(&mut self, left: Range<usize>, right: Range<usize>) {
+        if let Some(last) = self.hunks.last_mut().filter(|last| {
+            last.left_start + last.left_len == left.start as u64
+                && last.right_start + last.right_len == right.start as u64
+        }) {
+            last.left_len += left.len() as u64;
+            last.right_len += right.len() as u64;
+        } else if self.hunks.len() < MAX_HUNKS {
+            self.hunks.push(DiffHunk {
+                left_start: left.start as u64,
+                left_len: left.len() as u64,
+                right_start: right.start as u64,
+                right_len: right.len() as u64,
+            });
+        } else {
+            self.truncated = true;
+        }
+    }
+}
+
+fn compare(lines: HashMap<String, u32>, left: Side, right: Side) -> FilesComparison {
+    let mut comparison = FilesComparison {
+        left_only: Vec::new(),
+        right_only: Vec::new(),
+        changed: Vec::new(),
+        common: 0,
+        hunks: Vec::new(),
+        truncated: false,
+    };
+    let (mut left, mut right) = (left.occurrences, right.occurrences);
+    for (line, id) in lines {
+        match (left.remove(&id), right.remove(&id)) {
+            (Some(left), None) => {
+                comparison
+                    .left_only
+                    .push(compared(line, left, Occurrences::default()))
+            }
+            (None, Some(right)) => {
+                comparison
+                    .right_only
+                    .push(compared(line, Occurrences::default(), right))
+            }
+            (Some(left), Some(right)) if left.count != right.count => {
+                comparison.changed.push(compared(line, left, right))
+            }
+            (Some(_), Some(_)) => comparison.common += 1,
+            (None, None) => {}
+        }
+    }
+    for lines in [
+        &mut comparison.left_only,
+        &mut comparison.right_only,
+        &mut comparison.changed,
+    ] {
+        lines.sort_by(|a, b| {
+            b.left
+                .abs_diff(b.right)
+                .cmp(&a.left.abs_diff(a.right))
+                .then_with(|| a.line.cmp(&b.line))
+        });
+        if lines.len() > MAX_LINES {
+            lines.truncate(MAX_LINES);
+            comparison.truncated = true;
+        }
+    }
+    comparison
+}
+
+fn compared(line: String, left: Occurrences, right: Occurrences) -> ComparedLine {
+    ComparedLine {
+        line,
+        left: left.count,
+        right: right.count,
+        left_rows: left.rows,
+        right_rows: right.rows,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+
+    fn session_file(rows: &[&str]) -> tempfile::NamedTempFile {
+        let mut file = tempfile::NamedTempFile::new().expect("temp file is created");
+        for row in rows {
+            writeln!(file, "{row}").expect("row is written");
+        }
+        file
+    }
+
+    fn options(
+        left: &tempfile::NamedTempFile,
+        right: &tempfile::NamedTempFile,
+    ) -> ComparisonOptions {
+        ComparisonOptions {
+            left: left.path().to_string_lossy().to_string(),
+            right: right.path().to_string_lossy().to_string(),
+            rules: Vec::new(),
+            defaults: true,
+        }
+    }
+
+    fn ranges(rows: &[stypes::Range]) -> Vec<(u64, u64)> {
+        rows.iter().map(|range| (range.start, range.end)).collect()
+    }
+
+    #[test]
+    fn normalization() {
+        let normalizer = Normalizer::new(&ComparisonOptions {
+            left: String::new(),
+            right: String::new(),
+            rules: vec![stypes::MaskRule {
+                pattern: String::from(r"user \w+"),
+                mask: String::from("user $name"),
+            }],
+            defaults: true,
+        })
+        .expect("rules are valid");
+        assert_eq!(
+            normalizer.normalize(
+                "2024-03-01T10:00:00.123Z user bob sent 0x1F to deadbeef01 in 12.5 ms at 10:00:01"
+            ),
+            "<TS> user $name sent <HEX> to <HEX> in <NUM> ms at <TS>"
+        );
+        assert_eq!(
+            normalizer.normalize("ECU1\u{4}APP\u{4}id 550e8400-e29b-41d4-a716-446655440000"),
+            "ECU1 APP id <ID>"
+        );
+        let invalid = Normalizer::new(&ComparisonOptions {
+            left: String::new(),
+            right: String::new(),
+            rules: vec![stypes::MaskRule {
+                pattern: String::from("("),
+                mask: String::new(),
+            }],
+            defaults: false,
+        });
+        assert!(invalid.is_err());
+    }
+
+    #[tokio::test]
+    async fn differences_of_files() {
+        let left = session_file(&[
+            "10:00:00 connected to 10.0.0.1",
+            "10:00:01 request 1 ok",
+            "10:00:02 request 2 ok",
+            "10:00:03 request 3 ok",
+            "10:00:04 cache miss",
+            "10:00:05 disconnected",
+        ]);
+        let right = session_file(&[
+            "11:00:00 connected to 10.0.0.2",
+            "11:00:01 request 1 ok",
+            "11:00:02 request 2 failed",
+            "11:00:03 watchdog timeout",
+            "11:00:04 disconnected",
+        ]);
+        let signal = Signal::new(String::from("compare"));
+        let comparison = match compare_files(options(&left, &right), signal)
+            .await
+            .expect("files are compared")
+        {
+            stypes::CommandOutcome::Finished(comparison) => comparison,
+            stypes::CommandOutcome::Cancelled => panic!("comparison isn't cancelled"),
+        };
+        assert_eq!(comparison.common, 2);
+        let lines = |lines: &[ComparedLine]| {
+            lines
+                .iter()
+                .map(|line| {
+                    (
+                        line.line.clone(),
+                        line.left,
+                        line.right,
+                        ranges(&line.left_rows),
+                        ranges(&line.right_rows),
+                    )
+                })
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            lines(&comparison.left_only),
+            vec![(String::from("<TS> cache miss"), 1, 0, vec![(4, 4)], vec![])]
+        );
+        assert_eq!(
+            lines(&comparison.right_only),
+            vec![
+                (
+                    String::from("<TS> request <NUM> failed"),
+                    0,
+                    1,
+                    vec![],
+                    vec![(2, 2)]
+                ),
+                (
+                    String::from("<TS> watchdog timeout"),
+                    0,
+                    1,
+                    vec![],
+                    vec![(3, 3)]
+                ),
+            ]
+        );
+        assert_eq!(
+            lines(&comparison.changed),
+            vec![(
+                String::from("<TS> request <NUM> ok"),
+                3,
+                1,
+                vec![(1, 3)],
+                vec![(1, 1)]
+            )]
+        );
+        // The files differ after the first request until the disconnection.
+        assert_eq!(
+            comparison.hunks,
+            vec![DiffHunk {
+                left_start: 2,
+                left_len: 3,
+                right_start: 2,
+                right_len: 2,
+            }]
+        );
+        assert!(!comparison.truncated);
+    }
+
+    /// Returns the length of the longest common subsequence.
+    fn lcs(left: &[u32], right: &[u32]) -> usize {
+        let mut lengths = vec![vec![0; right.len() + 1]; left.len() + 1];
+        for (i, l) in left.iter().enumerate() {
+            for (j, r) in right.iter().enumerate() {
+                lengths[i + 1][j + 1] = if l == r {
+                    lengths[i][j] + 1
+                } else {
+                    lengths[i][j + 1].max(lengths[i + 1][j])
+                };
+            }
+        }
+        lengths[left.len()][right.len()]
+    }
+
+    #[test]
+    fn alignment() {
+        let signal = Signal::new(String::from("compare"));
+        let align = |left: &[u32], right: &[u32]| {
+            let mut alignment = Alignment::new(left, right, &signal);
+            alignment.conquer(0..left.len(), 0..right.len());
+            alignment.hunks
+        };
+        // Pseudo-random rows of a few distinct lines
+        let mut seed: u32 = 7;
+        let mut rows = |len: usize| {
+            (0..len)
+                .map(|_| {
+                    seed = seed.wrapping_mul(1_103_515_245).wrapping_add(12_345);
+                    (seed >> 16) % 4
+                })
+                .collect::<Vec<u32>>()
+        };
+        for len in 0..40 {
+            let (left, right) = (rows(len), rows(40 - len));
+            let hunks = align(&left, &right);
+            // Rows outside the hunks are equal and form the longest common subsequence.
+            let outside = |rows: &[u32], hunks: Vec<(u64, u64)>| {
+                (0..rows.len() as u64)
+                    .filter(|row| {
+                        !hunks
+                            .iter()
+                            .any(|(start, len)| (*start..start + len).contains(row))
+                    })
+                    .map(|row| rows[row as usize])
+                    .collect::<Vec<u32>>()
+            };
+            let common = outside(
+                &left,
+                hunks.iter().map(|h| (h.left_start, h.left_len)).collect(),
+            );
+            assert_eq!(
+                common,
+                outside(
+                    &right,
+                    hunks.iter().map(|h| (h.right_start, h.right_len)).collect()
+                )
+            );
+            assert_eq!(common.len(), lcs(&left, &right));
+        }
+        // Regions which need too many edits are reported as a whole.
+        let left = (0..3000).collect::<Vec<u32>>();
+        let right = (3000..6000).collect::<Vec<u32>>();
+        assert_eq!(
+            align(&left, &right),
+            vec![DiffHunk {
+                left_start: 0,
+                left_len: 3000,
+                right_start: 0,
+                right_len: 3000,
+            }]
+        );
+    }
+
+    #[tokio::test]
+    async fn truncated_comparison() {
+        let rows = (0..=MAX_LINES)
+            .map(|row| format!("line {row}"))
+            .collect::<Vec<String>>();
+        let left = session_file(&rows.iter().map(String::as_str).collect::<Vec<&str>>());
+        let right = session_file(&[]);
+        let mut options = options(&left, &right);
+        options.defaults = false;
+        let signal = Signal::new(String::from("compare"));
+        let stypes::CommandOutcome::Finished(comparison) = compare_files(options, signal)
+            .await
+            .expect("files are compared")
+        else {
+            panic!("comparison isn't cancelled");
+        };
+        assert!(comparison.truncated);
+        assert_eq!(comparison.left_only.len(), MAX_LINES);
+        assert_eq!(
+            comparison.hunks,
+            vec![DiffHunk {
+                left_start: 0,
+                left_len: MAX_LINES as u64 + 1,
+                right_start: 0,
+                right_len: 0,
+            }]
+        );
+    }
+
+    #[tokio::test]
+    async fn cancelled_comparison() {
+        let file = session_file(&["row"]);
+        let signal = Signal::new(String::from("compare"));
+        signal.invoke();
+        let outcome = compare_files(options(&file, &file), signal)
+            .await
+            .expect("comparison doesn't fail");
+        assert!(matches!(outcome, stypes::CommandOutcome::Cancelled));
+    }
+}
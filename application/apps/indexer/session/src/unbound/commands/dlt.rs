@@ -6,39 +6,173 @@ use dlt_core::{
         common::{StatisticInfo, StatisticInfoCollector},
     },
 };
-use std::fs::File;
+use parsers::{
+    MessageStreamItem,
+    dlt::{DltParser, stats::DltStatistics},
+};
+use sources::{
+    ByteSource,
+    binary::{
+        pcap::{legacy::PcapLegacyByteSource, ng::PcapngByteSource},
+        raw::BinaryByteSource,
+    },
+    producer::MessageProducer,
+};
+use std::{
+    fs::File,
+    io::{BufReader, Read},
+};
+
+/// Block type of the section header block, which starts every PCAPNG file.
+pub(super) const PCAPNG_MAGIC: [u8; 4] = [0x0A, 0x0D, 0x0D, 0x0A];
+/// Magic numbers of PCAP files in both byte orders with micro- and nanosecond resolution.
+pub(super) const PCAP_MAGICS: [[u8; 4]; 4] = [
+    [0xA1, 0xB2, 0xC3, 0xD4],
+    [0xD4, 0xC3, 0xB2, 0xA1],
+    [0xA1, 0xB2, 0x3C, 0x4D],
+    [0x4D, 0x3C, 0xB2, 0xA1],
+];
+/// Pattern of the storage header of DLT files.
+pub(super) const DLT_STORAGE_HEADER_MAGIC: [u8; 4] = [0x44, 0x4C, 0x54, 0x01];
+
+/// Container of the DLT messages in a file.
+#[derive(Debug, PartialEq)]
+pub(super) enum DltContainer {
+    /// DLT messages with storage headers.
+    StorageHeader,
+    /// DLT messages without storage headers (e.g. recorded from a stream).
+    Raw,
+    /// DLT messages embedded into the packets of a PCAPNG file.
+    PcapNG,
+    /// DLT messages embedded into the packets of a PCAP file.
+    PcapLegacy,
+}
+
+impl DltContainer {
+    pub(super) fn detect(file_path: &str) -> Result<Self, std::io::Error> {
+        let mut magic = [0u8; 4];
+        let mut file = File::open(file_path)?;
+        let mut read = 0;
+        while read < magic.len() {
+            match file.read(&mut magic[read..])? {
+                0 => break,
+                n => read += n,
+            }
+        }
+        Ok(Self::from_magic(&magic[..read]))
+    }
+
+    fn from_magic(magic: &[u8]) -> Self {
+        if magic == PCAPNG_MAGIC {
+            DltContainer::PcapNG
+        } else if PCAP_MAGICS.iter().any(|m| m == magic) {
+            DltContainer::PcapLegacy
+        } else if magic == DLT_STORAGE_HEADER_MAGIC {
+            DltContainer::StorageHeader
+        } else {
+            DltContainer::Raw
+        }
+    }
+}
 
-pub fn stats(
+/// Collects the statistics of the DLT messages in the given files. Besides DLT files
+/// with storage headers, files without storage headers and PCAP/PCAPNG files with
+/// embedded DLT messages are supported.
+pub async fn stats(
     file_paths: Vec<String>,
-    _signal: Signal,
+    signal: Signal,
 ) -> Result<stypes::CommandOutcome<stypes::DltStatisticInfo>, stypes::ComputationError> {
     let mut stat = StatisticInfo::new();
-    let mut error: Option<String> = None;
-    file_paths.iter().for_each(|file_path| {
-        if error.is_some() {
-            return;
+    for file_path in file_paths.iter() {
+        if signal.is_cancelling() {
+            return Ok(stypes::CommandOutcome::Cancelled);
         }
-        match File::open(file_path) {
-            Ok(file) => {
+        let container = DltContainer::detect(file_path)
+            .map_err(|err| stypes::ComputationError::IoOperation(err.to_string()))?;
+        let file = File::open(file_path)
+            .map_err(|err| stypes::ComputationError::IoOperation(err.to_string()))?;
+        let collected = match container {
+            DltContainer::StorageHeader => {
                 let mut reader = DltMessageReader::new(file, true);
                 let mut collector = StatisticInfoCollector::default();
-
-                match collect_statistics(&mut reader, &mut collector) {
-                    Ok(()) => {
-                        stat.merge(collector.collect());
-                    }
-                    Err(err) => {
-                        error = Some(err.to_string());
-                    }
-                }
+                collect_statistics(&mut reader, &mut collector)
+                    .map_err(|err| stypes::ComputationError::IoOperation(err.to_string()))?;
+                Some(collector.collect())
+            }
+            DltContainer::Raw => {
+                let source = BinaryByteSource::new(BufReader::new(file));
+                collect_from_source(source, false, &signal).await
+            }
+            DltContainer::PcapNG => {
+                let source = PcapngByteSource::new(file)
+                    .map_err(|err| stypes::ComputationError::IoOperation(err.to_string()))?;
+                collect_from_source(source, false, &signal).await
             }
-            Err(err) => {
-                error = Some(err.to_string());
+            DltContainer::PcapLegacy => {
+                let source = PcapLegacyByteSource::new(file)
+                    .map_err(|err| stypes::ComputationError::IoOperation(err.to_string()))?;
+                collect_from_source(source, false, &signal).await
             }
+        };
+        match collected {
+            Some(collected) => stat.merge(collected),
+            None => return Ok(stypes::CommandOutcome::Cancelled),
         }
-    });
-    if let Some(err) = error {
-        return Err(stypes::ComputationError::IoOperation(err));
     }
     Ok(stypes::CommandOutcome::Finished(stat.into()))
 }
+
+/// Parses all DLT messages of the source to collect their statistics.
+///
+/// Returns `None` if the operation was cancelled.
+async fn collect_from_source<S: ByteSource>(
+    source: S,
+    with_storage_header: bool,
+    signal: &Signal,
+) -> Option<StatisticInfo> {
+    let statistics = DltStatistics::shared();
+    let parser = DltParser::new(None, None, None, None, with_storage_header)
+        .with_statistics(statistics.clone());
+    let mut producer = MessageProducer::new(parser, source);
+    'outer: while let Some(items) = producer.read_next_segment().await {
+        if signal.is_cancelling() {
+            return None;
+        }
+        for (_, item) in items {
+            if matches!(item, MessageStreamItem::Done) {
+                break 'outer;
+            }
+        }
+    }
+    let info = statistics.lock().map_or_else(
+        |err| err.into_inner().info(),
+        |statistics| statistics.info(),
+    );
+    Some(info)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn detect_container() {
+        assert_eq!(
+            DltContainer::from_magic(&[0x0A, 0x0D, 0x0D, 0x0A]),
+            DltContainer::PcapNG
+        );
+        assert_eq!(
+            DltContainer::from_magic(&[0xD4, 0xC3, 0xB2, 0xA1]),
+            DltContainer::PcapLegacy
+        );
+        assert_eq!(
+            DltContainer::from_magic(b"DLT\x01"),
+            DltContainer::StorageHeader
+        );
+        assert_eq!(
+            DltContainer::from_magic(&[0x3D, 0x01, 0x00, 0x2A]),
+            DltContainer::Raw
+        );
+        assert_eq!(DltContainer::from_magic(&[]), DltContainer::Raw);
+    }
+}
@@ -0,0 +1,462 @@
+use super::dlt::{DLT_STORAGE_HEADER_MAGIC, PCAP_MAGICS, PCAPNG_MAGIC};
+use crate::unbound::signal::Signal;
+use sources::{
+    ByteSource, Error as SourceError,
+    binary::{
+        compressed::DecompressedFile,
+        pcap::{legacy::PcapLegacyByteSource, ng::PcapngByteSource},
+    },
+};
+use std::{io::Read, path::Path};
+use stypes::{DetectedParser, FileFormat, FormatDetection, TextEncoding};
+
+/// Count of bytes from the start of the file used to detect its format.
+const SAMPLE_SIZE: usize = 64 * 1024;
+/// Max count of loads of network packets used to detect the format of their payload.
+const MAX_PACKET_LOADS: usize = 256;
+/// Count of consecutive messages needed to accept files without magic numbers.
+const MIN_MESSAGES: usize = 3;
+/// File signature of the Vector binary logging format.
+const BLF_MAGIC: [u8; 4] = *b"LOGG";
+const STORAGE_HEADER_LEN: usize = 16;
+const SOMEIP_HEADER_LEN: usize = 16;
+/// Max ratio of control characters in text content.
+const MAX_CONTROL_RATIO: f64 = 0.01;
+
+/// Suggests the format, parser and settings of the given file by inspecting its magic
+/// numbers and a sample of its content. Compressed files and entries of archives are
+/// inspected after decompression.
+pub async fn detect(
+    file_path: String,
+    signal: Signal,
+) -> Result<stypes::CommandOutcome<FormatDetection>, stypes::ComputationError> {
+    let path = Path::new(&file_path);
+    let mut file = DecompressedFile::open(path).map_err(into_computation_err)?;
+    let compressed = !file.container().is_plain();
+    let sample = read_sample(&mut file)
+        .map_err(|err| stypes::ComputationError::IoOperation(err.to_string()))?;
+    if signal.is_cancelling() {
+        return Ok(stypes::CommandOutcome::Cancelled);
+    }
+    let detection = if sample.starts_with(&PCAPNG_MAGIC) {
+        let source =
+            PcapngByteSource::new(DecompressedFile::open(path).map_err(into_computation_err)?)
+                .map_err(into_computation_err)?;
+        detect_packets(FileFormat::PcapNG, source, &signal).await?
+    } else if PCAP_MAGICS.iter().any(|magic| sample.starts_with(magic)) {
+        let source =
+            PcapLegacyByteSource::new(DecompressedFile::open(path).map_err(into_computation_err)?)
+                .map_err(into_computation_err)?;
+        detect_packets(FileFormat::PcapLegacy, source, &signal).await?
+    } else {
+        Some(detect_content(&sample))
+    };
+    Ok(match detection {
+        Some(detection) => stypes::CommandOutcome::Finished(FormatDetection {
+            compressed,
+            ..detection
+        }),
+        None => stypes::CommandOutcome::Cancelled,
+    })
+}
+
+fn into_computation_err(err: SourceError) -> stypes::ComputationError {
+    stypes::ComputationError::IoOperation(err.to_string())
+}
+
+fn read_sample<R: Read>(reader: &mut R) -> std::io::Result<Vec<u8>> {
+    let mut sample = Vec::with_capacity(SAMPLE_SIZE);
+    reader.take(SAMPLE_SIZE as u64).read_to_end(&mut sample)?;
+    Ok(sample)
+}
+
+/// Detects the parser of the payload of network packets.
+///
+/// Returns `None` if the operation was cancelled.
+async fn detect_packets<S: ByteSource>(
+    format: FileFormat,
+    mut source: S,
+    signal: &Signal,
+) -> Result<Option<FormatDetection>, stypes::ComputationError> {
+    let mut dlt = Messages::default();
+    let mut someip = Messages::default();
+    let mut inspected = 0;
+    for _ in 0..MAX_PACKET_LOADS {
+        if signal.is_cancelling() {
+            return Ok(None);
+        }
+        if inspected >= SAMPLE_SIZE
+            || source
+                .load(None)
+                .await
+                .map_err(into_computation_err)?
+                .is_none()
+        {
+            break;
+        }
+        // Payloads are inspected one by one, because data of different flows doesn't
+        // continue each other.
+        let payload = source.current_slice();
+        dlt.merge(count_messages(payload, |data| dlt_message(data, false)));
+        someip.merge(count_messages(payload, someip_message));
+        inspected += payload.len();
+        let len = source.len();
+        source.consume(len);
+    }
+    let (parser, messages) = if dlt.count >= someip.count {
+        (DetectedParser::Dlt, dlt)
+    } else {
+        (DetectedParser::SomeIp, someip)
+    };
+    Ok(Some(if messages.count == 0 {
+        // The format of the file is known even if its payload isn't.
+        detection(format, None, 50)
+    } else {
+        FormatDetection {
+            with_storage_header: (parser == DetectedParser::Dlt).then_some(false),
+            ..detection(format, Some(parser), messages.confidence())
+        }
+    }))
+}
+
+/// Detects the format and parser of a file without a container of network packets.
+fn detect_content(sample: &[u8]) -> FormatDetection {
+    if sample.is_empty() {
+        return detection(FileFormat::Text, None, 0);
+    }
+    if sample.starts_with(&BLF_MAGIC) {
+        return detection(FileFormat::Blf, Some(DetectedParser::Can), 95);
+    }
+    if sample.starts_with(&DLT_STORAGE_HEADER_MAGIC) {
+        let messages = count_messages(sample, |data| dlt_message(data, true));
+        return FormatDetection {
+            with_storage_header: Some(true),
+            // The magic number alone is a good hint already.
+            ..detection(
+                FileFormat::Binary,
+                Some(DetectedParser::Dlt),
+                messages.confidence().max(60),
+            )
+        };
+    }
+    let dlt = count_messages(sample, |data| dlt_message(data, false));
+    let someip = count_messages(sample, someip_message);
+    if dlt.is_stream() && dlt.count >= someip.count {
+        return FormatDetection {
+            with_storage_header: Some(false),
+            ..detection(
+                FileFormat::Binary,
+                Some(DetectedParser::Dlt),
+                dlt.confidence(),
+            )
+        };
+    }
+    if someip.is_stream() {
+        return detection(
+            FileFormat::Binary,
+            Some(DetectedParser::SomeIp),
+            someip.confidence(),
+        );
+    }
+    match detect_text(sample) {
+        Some((encoding, confidence)) => FormatDetection {
+            encoding: Some(encoding),
+            ..detection(FileFormat::Text, Some(DetectedParser::Text), confidence)
+        },
+        None => detection(FileFormat::Binary, None, 10),
+    }
+}
+
+fn detection(
+    format: FileFormat,
+    parser: Option<DetectedParser>,
+    confidence: u8,
+) -> FormatDetection {
+    FormatDetection {
+        format,
+        parser,
+        with_storage_header: None,
+        encoding: None,
+        compressed: false,
+        confidence,
+    }
+}
+
+/// Consecutive messages found from the start of data.
+#[derive(Debug, Default)]
+struct Messages {
+    count: usize,
+    /// Whether the messages are followed by something else than a truncated message.
+    broken: bool,
+}
+
+impl Messages {
+    fn merge(&mut self, other: Messages) {
+        self.count += other.count;
+        self.broken |= other.broken;
+    }
+
+    /// Whether the data looks like a stream of messages.
+    fn is_stream(&self) -> bool {
+        self.count >= MIN_MESSAGES || (self.count > 0 && !self.broken)
+    }
+
+    fn confidence(&self) -> u8 {
+        let confidence = (20 + 15 * self.count).min(95) as u8;
+        if self.broken {
+            confidence / 2
+        } else {
+            confidence
+        }
+    }
+}
+
+/// Result of inspecting the data at the position of the next message.
+#[derive(Debug, PartialEq)]
+enum Step {
+    /// A valid message with the given length.
+    Message(usize),
+    /// The data ends before the message does.
+    Truncated,
+    Invalid,
+}
+
+fn count_messages<F: Fn(&[u8]) -> Step>(data: &[u8], next: F) -> Messages {
+    let mut messages = Messages::default();
+    let mut offset = 0;
+    while offset < data.len() {
+        match next(&data[offset..]) {
+            Step::Message(len) => {
+                messages.count += 1;
+                offset += len;
+            }
+            Step::Truncated => break,
+            Step::Invalid => {
+                messages.broken = true;
+                break;
+            }
+        }
+    }
+    messages
+}
+
+/// Checks the headers of the DLT message at the start of the data.
+fn dlt_message(data: &[u8], with_storage_header: bool) -> Step {
+    let offset = if with_storage_header {
+        if !data.starts_with(&DLT_STORAGE_HEADER_MAGIC) {
+            return if DLT_STORAGE_HEADER_MAGIC.starts_with(data) {
+                Step::Truncated
+            } else {
+                Step::Invalid
+            };
+        }
+        STORAGE_HEADER_LEN
+    } else {
+        0
+    };
+    let Some(header) = data.get(offset..offset + 4) else {
+        return Step::Truncated;
+    };
+    let htyp = header[0];
+    // Version of the protocol.
+    if htyp >> 5 != 1 {
+        return Step::Invalid;
+    }
+    let with_extended_header = htyp & 0x01 != 0;
+    let optional = [0x04, 0x08, 0x10]
+        .iter()
+        .filter(|flag| htyp & **flag != 0)
+        .count();
+    let extended_header_offset = 4 + 4 * optional;
+    let len = u16::from_be_bytes([header[2], header[3]]) as usize;
+    let min_len = extended_header_offset + if with_extended_header { 10 } else { 0 };
+    if len < min_len {
+        return Step::Invalid;
+    }
+    if with_extended_header {
+        let Some(msin) = data.get(offset + extended_header_offset) else {
+            return Step::Truncated;
+        };
+        let message_type = (msin >> 1) & 0x07;
+        let message_type_info = msin >> 4;
+        let max_type_info = match message_type {
+            0 => 6,
+            1 => 5,
+            2 => 4,
+            3 => 2,
+            _ => return Step::Invalid,
+        };
+        if !(1..=max_type_info).contains(&message_type_info) {
+            return Step::Invalid;
+        }
+    }
+    if data.len() < offset + len {
+        Step::Truncated
+    } else {
+        Step::Message(offset + len)
+    }
+}
+
+/// Checks the header of the SOME/IP message at the start of the data.
+fn someip_message(data: &[u8]) -> Step {
+    if data.len() < SOMEIP_HEADER_LEN {
+        return Step::Truncated;
+    }
+    // Length of the message from the request id on.
+    let len = u32::from_be_bytes([data[4], data[5], data[6], data[7]]) as usize;
+    let protocol_version = data[12];
+    // The flag of segmented messages is ignored.
+    let message_type = data[14] & !0x20;
+    let return_code = data[15];
+    if len < 8
+        || protocol_version != 1
+        || ![0x00, 0x01, 0x02, 0x80, 0x81].contains(&message_type)
+        || return_code > 0x5E
+    {
+        return Step::Invalid;
+    }
+    if data.len() < 8 + len {
+        Step::Truncated
+    } else {
+        Step::Message(8 + len)
+    }
+}
+
+/// Detects the encoding of text content with the confidence in it.
+fn detect_text(sample: &[u8]) -> Option<(TextEncoding, u8)> {
+    if sample.starts_with(&[0xEF, 0xBB, 0xBF]) {
+        return Some((TextEncoding::Utf8, 95));
+    }
+    if sample.starts_with(&[0xFF, 0xFE]) {
+        return Some((TextEncoding::Utf16Le, 95));
+    }
+    if sample.starts_with(&[0xFE, 0xFF]) {
+        return Some((TextEncoding::Utf16Be, 95));
+    }
+    // Text in UTF-16 without BOM is recognized by the high zero bytes of ASCII characters.
+    let pairs = sample.len() / 2;
+    if pairs > 0 {
+        let zeros_at = |parity: usize| {
+            sample
+                .iter()
+                .skip(parity)
+                .step_by(2)
+                .filter(|byte| **byte == 0)
+                .count()
+        };
+        let (even, odd) = (zeros_at(0), zeros_at(1));
+        if odd * 2 > pairs && even * 20 < pairs {
+            return Some((TextEncoding::Utf16Le, 70));
+        }
+        if even * 2 > pairs && odd * 20 < pairs {
+            return Some((TextEncoding::Utf16Be, 70));
+        }
+    }
+    let text = match std::str::from_utf8(sample) {
+        Ok(text) => Some(text),
+        // The sample may end within a character.
+        Err(err) if err.error_len().is_none() => {
+            std::str::from_utf8(&sample[..err.valid_up_to()]).ok()
+        }
+        Err(_) => None,
+    };
+    if let Some(text) = text {
+        let controls = text.chars().filter(|c| is_control(*c)).count();
+        return ((controls as f64) < text.chars().count() as f64 * MAX_CONTROL_RATIO)
+            .then_some((TextEncoding::Utf8, 85));
+    }
+    let controls = sample
+        .iter()
+        .filter(|byte| byte.is_ascii() && is_control(**byte as char))
+        .count();
+    ((controls as f64) < sample.len() as f64 * MAX_CONTROL_RATIO)
+        .then_some((TextEncoding::Latin1, 60))
+}
+
+/// Control characters which aren't expected in text. Escape is allowed for ANSI colors.
+fn is_control(c: char) -> bool {
+    c.is_control() && !matches!(c, '\t' | '\n' | '\r' | '\x0C' | '\x1B') && (c as u32) < 0x80
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// DLT message with an extended header and the given payload.
+    fn dlt(payload: &[u8]) -> Vec<u8> {
+        let len = (4 + 10 + payload.len()) as u16;
+        let mut msg = vec![0x21, 0x00];
+        msg.extend_from_slice(&len.to_be_bytes());
+        // Verbose log message of level info without arguments.
+        msg.extend_from_slice(&[0x41, 0x00]);
+        msg.extend_from_slice(b"APP1CTX1");
+        msg.extend_from_slice(payload);
+        msg
+    }
+
+    fn someip(payload: &[u8]) -> Vec<u8> {
+        let mut msg = vec![0x12, 0x34, 0x80, 0x01];
+        msg.extend_from_slice(&((8 + payload.len()) as u32).to_be_bytes());
+        msg.extend_from_slice(&[0x00, 0x01, 0x00, 0x01, 0x01, 0x01, 0x02, 0x00]);
+        msg.extend_from_slice(payload);
+        msg
+    }
+
+    #[test]
+    fn detect_dlt() {
+        let mut with_storage_header = Vec::new();
+        let mut without_storage_header = Vec::new();
+        for _ in 0..5 {
+            with_storage_header.extend_from_slice(b"DLT\x01");
+            with_storage_header.extend_from_slice(&[0; 8]);
+            with_storage_header.extend_from_slice(b"ECU1");
+            with_storage_header.extend_from_slice(&dlt(b"payload"));
+            without_storage_header.extend_from_slice(&dlt(b"payload"));
+        }
+        let detection = detect_content(&with_storage_header);
+        assert_eq!(detection.parser, Some(DetectedParser::Dlt));
+        assert_eq!(detection.with_storage_header, Some(true));
+        assert_eq!(detection.confidence, 95);
+        // Truncated messages at the end of the sample are fine.
+        let detection = detect_content(&without_storage_header[..without_storage_header.len() - 3]);
+        assert!(matches!(detection.format, FileFormat::Binary));
+        assert_eq!(detection.parser, Some(DetectedParser::Dlt));
+        assert_eq!(detection.with_storage_header, Some(false));
+        assert_eq!(detection.confidence, 80);
+    }
+
+    #[test]
+    fn detect_someip() {
+        let stream: Vec<u8> = (0..4).flat_map(|_| someip(&[1, 2, 3, 4])).collect();
+        let detection = detect_content(&stream);
+        assert_eq!(detection.parser, Some(DetectedParser::SomeIp));
+        assert_eq!(detection.with_storage_header, None);
+        assert_eq!(detection.confidence, 80);
+    }
+
+    #[test]
+    fn detect_text_encodings() {
+        let text = "2024-01-01 12:00:00 INFO started\n2024-01-01 12:00:01 WARN über\n";
+        let detection = detect_content(text.as_bytes());
+        assert!(matches!(detection.format, FileFormat::Text));
+        assert_eq!(detection.encoding, Some(TextEncoding::Utf8));
+        let utf16: Vec<u8> = text.encode_utf16().flat_map(|c| c.to_le_bytes()).collect();
+        assert_eq!(detect_content(&utf16).encoding, Some(TextEncoding::Utf16Le));
+        let utf16: Vec<u8> = text.encode_utf16().flat_map(|c| c.to_be_bytes()).collect();
+        assert_eq!(detect_content(&utf16).encoding, Some(TextEncoding::Utf16Be));
+        let latin1: Vec<u8> = text.chars().map(|c| c as u8).collect();
+        assert_eq!(detect_content(&latin1).encoding, Some(TextEncoding::Latin1));
+    }
+
+    #[test]
+    fn detect_unknown() {
+        let binary: Vec<u8> = (0..=255u8).cycle().take(4096).collect();
+        let detection = detect_content(&binary);
+        assert!(matches!(detection.format, FileFormat::Binary));
+        assert_eq!(detection.parser, None);
+        assert_eq!(
+            detect_content(b"LOGG\x90\x00").parser,
+            Some(DetectedParser::Can)
+        );
+    }
+}
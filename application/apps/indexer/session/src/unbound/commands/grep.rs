@@ -0,0 +1,359 @@
+use super::format;
+use crate::unbound::signal::Signal;
+use file_tools::is_binary;
+use parsers::{
+    MessageStreamItem, ParseYield,
+    dlt::{
+        DltParser,
+        fmt::{DLT_ARGUMENT_SENTINAL, DLT_COLUMN_SENTINAL},
+    },
+};
+use processor::search::filter::{self, SearchFilter};
+use regex::Regex;
+use sources::{
+    ByteSource,
+    binary::{
+        pcap::{legacy::PcapLegacyByteSource, ng::PcapngByteSource},
+        raw::BinaryByteSource,
+    },
+    producer::MessageProducer,
+};
+use std::{fs::File, io::BufReader, path::Path};
+use stypes::{DetectedParser, FileFormat, GrepMatch, GrepOptions, GrepResult};
+use text_grep::{GrepError, find_matches};
+use walkdir::WalkDir;
+
+/// Collects the requested page of matches.
+struct Page {
+    /// Count of matches left to skip.
+    offset: u64,
+    limit: u64,
+    result: GrepResult,
+}
+
+impl Page {
+    fn new(offset: u64, limit: u64) -> Self {
+        Self {
+            offset,
+            limit,
+            result: GrepResult {
+                matches: Vec::new(),
+                more: false,
+                skipped: Vec::new(),
+            },
+        }
+    }
+
+    /// Adds a match. Returns `false` if the page is complete, so the search can be stopped.
+    fn add(&mut self, file: &str, line: u64, patterns: Vec<usize>, content: &str) -> bool {
+        if self.offset > 0 {
+            self.offset -= 1;
+            return true;
+        }
+        if self.result.matches.len() as u64 >= self.limit {
+            self.result.more = true;
+            return false;
+        }
+        self.result.matches.push(GrepMatch {
+            file: file.to_owned(),
+            line,
+            patterns: patterns.into_iter().map(|i| i as u32).collect(),
+            content: content.to_owned(),
+        });
+        true
+    }
+
+    fn is_complete(&self) -> bool {
+        self.result.more
+    }
+
+    fn skip(&mut self, file: &str, reason: impl std::fmt::Display) {
+        self.result.skipped.push(format!("{file}: {reason}"));
+    }
+}
+
+/// Searches the lines matching at least one of the conditions in the given files and
+/// folders (scanned recursively) without opening sessions. Text files are searched line by
+/// line. Binary files recognized as DLT (with or without storage headers, as well as
+/// PCAP/PCAPNG files) are decoded and searched message by message. Other binary files and
+/// files which can't be read are reported as skipped instead of failing the whole search.
+pub async fn grep(
+    options: GrepOptions,
+    signal: Signal,
+) -> Result<stypes::CommandOutcome<GrepResult>, stypes::ComputationError> {
+    if options.patterns.is_empty() {
+        return Err(stypes::ComputationError::InvalidArgs(String::from(
+            "No conditions to search for",
+        )));
+    }
+    let patterns = options
+        .patterns
+        .into_iter()
+        .map(|condition| {
+            let filter = SearchFilter::new(
+                condition.value,
+                condition.is_regex,
+                condition.ignore_case,
+                condition.is_word,
+            );
+            Regex::new(&filter::as_regex(&filter)).map_err(|err| {
+                stypes::ComputationError::InvalidArgs(format!(
+                    "Invalid condition \"{}\": {err}",
+                    filter.value
+                ))
+            })
+        })
+        .collect::<Result<Vec<Regex>, stypes::ComputationError>>()?;
+    let mut page = Page::new(options.offset, options.limit);
+    for path in options.paths.iter() {
+        let path = Path::new(path);
+        let files = if path.is_dir() {
+            let mut files = Vec::new();
+            for entry in WalkDir::new(path).sort_by_file_name() {
+                match entry {
+                    Ok(entry) if entry.file_type().is_file() => files.push(entry.into_path()),
+                    Ok(_) => {}
+                    Err(err) => page.skip(&path.to_string_lossy(), err),
+                }
+            }
+            files
+        } else {
+            vec![path.to_path_buf()]
+        };
+        for file in files {
+            if signal.is_cancelling() {
+                return Ok(stypes::CommandOutcome::Cancelled);
+            }
+            if !grep_file(&file, &patterns, &mut page, &signal).await {
+                return Ok(stypes::CommandOutcome::Cancelled);
+            }
+            if page.is_complete() {
+                return Ok(stypes::CommandOutcome::Finished(page.result));
+            }
+        }
+    }
+    Ok(stypes::CommandOutcome::Finished(page.result))
+}
+
+/// Searches a single file. Returns `false` if the command was cancelled.
+async fn grep_file(file: &Path, patterns: &[Regex], page: &mut Page, signal: &Signal) -> bool {
+    let name = file.to_string_lossy().to_string();
+    match is_binary(name.clone()) {
+        Ok(true) => {}
+        Ok(false) => {
+            return match find_matches(patterns, file, &signal.token(), |matched| {
+                page.add(&name, matched.line, matched.patterns, &matched.content)
+            }) {
+                Ok(()) => true,
+                Err(GrepError::OperationCancelled) => false,
+                Err(err) => {
+                    page.skip(&name, err);
+                    true
+                }
+            };
+        }
+        Err(err) => {
+            page.skip(&name, err);
+            return true;
+        }
+    }
+    // Binary files are decoded only if they are recognized as DLT, other content would be
+    // decoded as garbage.
+    let detection = match format::detect(name.clone(), signal.clone()).await {
+        Ok(stypes::CommandOutcome::Finished(detection)) => detection,
+        Ok(stypes::CommandOutcome::Cancelled) => return false,
+        Err(err) => {
+            page.skip(&name, err);
+            return true;
+        }
+    };
+    if detection.compressed {
+        page.skip(&name, "compressed files aren't searched");
+        return true;
+    }
+    if detection.parser != Some(DetectedParser::Dlt) {
+        page.skip(&name, "binary file isn't recognized as DLT");
+        return true;
+    }
+    let file = match File::open(file) {
+        Ok(file) => file,
+        Err(err) => {
+            page.skip(&name, err);
+            return true;
+        }
+    };
+    let grepped = match detection.format {
+        FileFormat::PcapNG => match PcapngByteSource::new(file) {
+            Ok(source) => grep_messages(source, false, &name, patterns, page, signal).await,
+            Err(err) => {
+                page.skip(&name, err);
+                Some(())
+            }
+        },
+        FileFormat::PcapLegacy => match PcapLegacyByteSource::new(file) {
+            Ok(source) => grep_messages(source, false, &name, patterns, page, signal).await,
+            Err(err) => {
+                page.skip(&name, err);
+                Some(())
+            }
+        },
+        _ => {
+            let source = BinaryByteSource::new(BufReader::new(file));
+            let with_storage_header = detection.with_storage_header.unwrap_or(false);
+            grep_messages(source, with_storage_header, &name, patterns, page, signal).await
+        }
+    };
+    grepped.is_some()
+}
+
+/// Decodes the DLT messages of the source and searches them. The number of a message in
+/// the source is used as its line.
+///
+/// Returns `None` if the operation was cancelled.
+async fn grep_messages<S: ByteSource>(
+    source: S,
+    with_storage_header: bool,
+    name: &str,
+    patterns: &[Regex],
+    page: &mut Page,
+    signal: &Signal,
+) -> Option<()> {
+    let parser = DltParser::new(None, None, None, None, with_storage_header);
+    let mut producer = MessageProducer::new(parser, source);
+    let mut line = 0;
+    while let Some(items) = producer.read_next_segment().await {
+        if signal.is_cancelling() {
+            return None;
+        }
+        for (_, item) in items {
+            let msg = match item {
+                MessageStreamItem::Item(ParseYield::Message(msg))
+                | MessageStreamItem::Item(ParseYield::MessageAndAttachment((msg, _))) => msg,
+                MessageStreamItem::Done => return Some(()),
+                _ => continue,
+            };
+            let content = msg
+                .to_string()
+                .replace([DLT_COLUMN_SENTINAL, DLT_ARGUMENT_SENTINAL], " ");
+            let matched: Vec<usize> = patterns
+                .iter()
+                .enumerate()
+                .filter_map(|(i, pattern)| pattern.is_match(&content).then_some(i))
+                .collect();
+            if !matched.is_empty() && !page.add(name, line, matched, &content) {
+                return Some(());
+            }
+            line += 1;
+        }
+    }
+    Some(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{fs, io::Write};
+
+    fn condition(value: &str) -> stypes::SearchCondition {
+        stypes::SearchCondition {
+            value: value.to_owned(),
+            is_regex: false,
+            ignore_case: true,
+            is_word: false,
+        }
+    }
+
+    async fn search(paths: &[&Path], offset: u64, limit: u64) -> GrepResult {
+        let options = GrepOptions {
+            paths: paths
+                .iter()
+                .map(|path| path.to_string_lossy().to_string())
+                .collect(),
+            patterns: vec![condition("error"), condition("warn")],
+            offset,
+            limit,
+        };
+        match grep(options, Signal::new(String::from("grep"))).await {
+            Ok(stypes::CommandOutcome::Finished(result)) => result,
+            _ => panic!("search should be finished"),
+        }
+    }
+
+    #[tokio::test]
+    async fn grep_folder() {
+        let dir = tempfile::tempdir().expect("temp dir is created");
+        fs::create_dir(dir.path().join("nested")).expect("folder is created");
+        for (name, rows) in [
+            ("a.log", vec!["ok", "ERROR: one", "warn and error"]),
+            ("nested/b.log", vec!["warning", "ok"]),
+        ] {
+            let mut file = File::create(dir.path().join(name)).expect("file is created");
+            for row in rows {
+                writeln!(file, "{row}").expect("row is written");
+            }
+        }
+        let missing = dir.path().join("missing.log");
+
+        let result = search(&[dir.path(), &missing], 0, 10).await;
+        let found: Vec<(u64, Vec<u32>, &str)> = result
+            .matches
+            .iter()
+            .map(|m| (m.line, m.patterns.clone(), m.content.as_str()))
+            .collect();
+        assert_eq!(
+            found,
+            vec![
+                (1, vec![0], "ERROR: one"),
+                (2, vec![0, 1], "warn and error"),
+                (0, vec![1], "warning"),
+            ]
+        );
+        assert!(result.matches[2].file.ends_with("b.log"));
+        assert!(!result.more);
+        assert_eq!(result.skipped.len(), 1);
+
+        let result = search(&[dir.path()], 1, 1).await;
+        assert_eq!(result.matches.len(), 1);
+        assert_eq!(result.matches[0].content, "warn and error");
+        assert!(result.more);
+
+        let result = search(&[dir.path()], 2, 1).await;
+        assert_eq!(result.matches.len(), 1);
+        assert!(!result.more);
+    }
+
+    #[tokio::test]
+    async fn grep_skips_binary_files() {
+        let dir = tempfile::tempdir().expect("temp dir is created");
+        fs::write(dir.path().join("a.log"), "error\n").expect("file is created");
+        // A BLF file with a string looking like a match isn't decoded as DLT.
+        let mut blf = b"LOGG".to_vec();
+        blf.extend([0xFF; 64]);
+        blf.extend(b"error");
+        fs::write(dir.path().join("b.blf"), blf).expect("file is created");
+
+        let result = search(&[dir.path()], 0, 10).await;
+        assert_eq!(result.matches.len(), 1);
+        assert!(result.matches[0].file.ends_with("a.log"));
+        assert_eq!(result.skipped.len(), 1);
+        assert!(result.skipped[0].contains("b.blf"));
+    }
+
+    #[tokio::test]
+    async fn grep_cancelled() {
+        let dir = tempfile::tempdir().expect("temp dir is created");
+        File::create(dir.path().join("a.log")).expect("file is created");
+        let signal = Signal::new(String::from("grep"));
+        signal.invoke();
+        let options = GrepOptions {
+            paths: vec![dir.path().to_string_lossy().to_string()],
+            patterns: vec![condition("error")],
+            offset: 0,
+            limit: 10,
+        };
+        assert!(matches!(
+            grep(options, signal).await,
+            Ok(stypes::CommandOutcome::Cancelled)
+        ));
+    }
+}
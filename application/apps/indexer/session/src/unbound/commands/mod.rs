@@ -1,8 +1,12 @@
 mod cancel_test;
 mod checksum;
+pub(crate) mod chronology;
+mod compare;
 mod dlt;
 mod file;
 mod folder;
+mod format;
+mod grep;
 pub mod plugins;
 mod process;
 mod regex;
@@ -78,6 +82,32 @@ pub enum Command {
         String,
         oneshot::Sender<Result<stypes::CommandOutcome<bool>, stypes::ComputationError>>,
     ),
+    /// Suggests format, parser and settings of a file based on its content.
+    DetectFileFormat(
+        String,
+        oneshot::Sender<
+            Result<stypes::CommandOutcome<stypes::FormatDetection>, stypes::ComputationError>,
+        >,
+    ),
+    GetFilesChronology(
+        Vec<String>,
+        oneshot::Sender<
+            Result<stypes::CommandOutcome<stypes::FilesChronology>, stypes::ComputationError>,
+        >,
+    ),
+    CompareFiles(
+        stypes::ComparisonOptions,
+        oneshot::Sender<
+            Result<stypes::CommandOutcome<stypes::FilesComparison>, stypes::ComputationError>,
+        >,
+    ),
+    /// Searches lines across files and folders without opening sessions.
+    Grep(
+        stypes::GrepOptions,
+        oneshot::Sender<
+            Result<stypes::CommandOutcome<stypes::GrepResult>, stypes::ComputationError>,
+        >,
+    ),
     CancelTest(
         i64,
         i64,
@@ -164,6 +194,10 @@ impl std::fmt::Display for Command {
                 Command::GetSomeipStatistic(_, _) => "Getting someip statistic",
                 Command::GetRegexError(_, _) => "Checking regex",
                 Command::IsFileBinary(_, _) => "Checking if file is binary",
+                Command::DetectFileFormat(_, _) => "Detecting file format",
+                Command::GetFilesChronology(_, _) => "Ordering files by time",
+                Command::CompareFiles(_, _) => "Comparing files",
+                Command::Grep(_, _) => "Searching in files",
                 Command::InstalledPluginsList(..) => "Getting installed plugins",
                 Command::InvalidPluginsList(..) => "Getting invalid plugins",
                 Command::InstalledPluginsPaths(..) => "Getting installed plugins paths",
@@ -201,7 +235,7 @@ pub async fn process(command: Command, signal: Signal, plugins_manager: &RwLock<
             tx.send(regex::get_filter_error(filter, signal)).is_err()
         }
         Command::Checksum(file, tx) => tx.send(checksum::checksum(&file, signal)).is_err(),
-        Command::GetDltStats(files, tx) => tx.send(dlt::stats(files, signal)).is_err(),
+        Command::GetDltStats(files, tx) => tx.send(dlt::stats(files, signal).await).is_err(),
         Command::GetSomeipStatistic(files, tx) => {
             tx.send(get_someip_statistic(files, signal)).is_err()
         }
@@ -209,6 +243,16 @@ pub async fn process(command: Command, signal: Signal, plugins_manager: &RwLock<
         Command::GetContextEnvvars(tx) => tx.send(shells::get_context_envvars(signal)).is_err(),
         Command::SerialPortsList(tx) => tx.send(serial::available_ports(signal)).is_err(),
         Command::IsFileBinary(file_path, tx) => tx.send(file::is_file_binary(file_path)).is_err(),
+        Command::DetectFileFormat(file_path, tx) => {
+            tx.send(format::detect(file_path, signal).await).is_err()
+        }
+        Command::GetFilesChronology(files, tx) => tx
+            .send(chronology::order_by_time(files, signal).await)
+            .is_err(),
+        Command::CompareFiles(options, tx) => tx
+            .send(compare::compare_files(options, signal).await)
+            .is_err(),
+        Command::Grep(options, tx) => tx.send(grep::grep(options, signal).await).is_err(),
         Command::CancelTest(a, b, tx) => tx
             .send(cancel_test::cancel_test(a, b, signal).await)
             .is_err(),
@@ -261,6 +305,10 @@ pub fn err(command: Command, err: stypes::ComputationError) {
         Command::GetContextEnvvars(tx) => tx.send(Err(err)).is_err(),
         Command::SerialPortsList(tx) => tx.send(Err(err)).is_err(),
         Command::IsFileBinary(_filepath, tx) => tx.send(Err(err)).is_err(),
+        Command::DetectFileFormat(_filepath, tx) => tx.send(Err(err)).is_err(),
+        Command::GetFilesChronology(_files, tx) => tx.send(Err(err)).is_err(),
+        Command::CompareFiles(_options, tx) => tx.send(Err(err)).is_err(),
+        Command::Grep(_options, tx) => tx.send(Err(err)).is_err(),
         Command::CancelTest(_a, _b, tx) => tx.send(Err(err)).is_err(),
         Command::InstalledPluginsList(tx) => tx.send(Err(err)).is_err(),
         Command::InvalidPluginsList(tx) => tx.send(Err(err)).is_err(),
@@ -1 +1,2 @@
+mod observe_tests;
 mod snapshot_tests;
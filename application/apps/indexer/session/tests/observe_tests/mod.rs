@@ -0,0 +1,329 @@
+//! Tests running observe operations on a session and checking the produced stream.
+
+use processor::grabber::LineRange;
+use session::session::Session;
+use std::{fs, time::Duration};
+use tokio::time::timeout;
+use uuid::Uuid;
+
+const SOMEIP_PCAPNG: &str = "../../../developing/resources/someip.pcapng";
+const SOMEIP_FIBEX: &str = "../../../developing/resources/someip.xml";
+
+/// Observes the SomeIP network trace with the given filter and returns the count of rows in
+/// the session stream or the error of the observe operation.
+async fn observe_someip_pcapng(
+    network_filter: Option<stypes::NetworkFilter>,
+) -> Result<u64, stypes::NativeError> {
+    let uuid = Uuid::new_v4();
+    let (session, mut receiver) = Session::new(uuid).await.expect("Session should be created");
+    let parser = stypes::ParserType::SomeIp(stypes::SomeIpParserSettings {
+        fibex_file_paths: Some(vec![String::from(SOMEIP_FIBEX)]),
+    });
+    session
+        .observe(
+            uuid,
+            stypes::ObserveOptions {
+                origin: stypes::ObserveOrigin::File(
+                    uuid.to_string(),
+                    stypes::FileFormat::PcapNG,
+                    SOMEIP_PCAPNG.into(),
+                ),
+                parser,
+                network_filter,
+                order_by_time: None,
+            },
+        )
+        .unwrap();
+    while let Some(feedback) = receiver.recv().await {
+        match feedback {
+            stypes::CallbackEvent::FileRead | stypes::CallbackEvent::SessionDestroyed => break,
+            stypes::CallbackEvent::SessionError(err) => panic!("Received session error: {err:#?}"),
+            stypes::CallbackEvent::OperationError { error, .. } => return Err(error),
+            _ => {}
+        }
+    }
+    let (rows, _) = session.get_state().get_stream_len().await?;
+    Ok(rows)
+}
+
+#[tokio::test]
+async fn observe_pcapng_with_network_filter() {
+    let all = observe_someip_pcapng(None)
+        .await
+        .expect("Observe without filter");
+    assert!(all > 0);
+
+    let by_port = observe_someip_pcapng(Some(stypes::NetworkFilter {
+        destination_port: Some(30001),
+        ..Default::default()
+    }))
+    .await
+    .expect("Observe with port filter");
+    assert!(by_port > 0);
+    assert!(by_port < all);
+
+    let by_address = observe_someip_pcapng(Some(stypes::NetworkFilter {
+        source_ip: Some(String::from("192.168.178.58")),
+        source_port: Some(40000),
+        ..Default::default()
+    }))
+    .await
+    .expect("Observe with address filter");
+    assert_eq!(by_address, all);
+
+    let tcp = observe_someip_pcapng(Some(stypes::NetworkFilter {
+        transport: Some(stypes::NetworkTransport::Tcp),
+        ..Default::default()
+    }))
+    .await
+    .expect("Observe with transport filter");
+    assert_eq!(tcp, 0);
+}
+
+#[tokio::test]
+async fn observe_pcapng_with_invalid_network_filter() {
+    let result = observe_someip_pcapng(Some(stypes::NetworkFilter {
+        source_ip: Some(String::from("not an ip")),
+        ..Default::default()
+    }))
+    .await;
+    assert!(matches!(
+        result,
+        Err(stypes::NativeError {
+            kind: stypes::NativeErrorKind::Configuration,
+            ..
+        })
+    ));
+}
+
+#[tokio::test]
+async fn observe_truncated_text_file() {
+    let dir = tempfile::tempdir().unwrap();
+    let filename = dir.path().join("app.log");
+    fs::write(&filename, "a\nb\nc\n").unwrap();
+    let uuid = Uuid::new_v4();
+    let (session, mut receiver) = Session::new(uuid).await.expect("Session should be created");
+    session
+        .observe(
+            uuid,
+            stypes::ObserveOptions::file(
+                filename.clone(),
+                stypes::FileFormat::Text,
+                stypes::ParserType::Text(()),
+            ),
+        )
+        .unwrap();
+    let wait = async {
+        while let Some(feedback) = receiver.recv().await {
+            match feedback {
+                stypes::CallbackEvent::FileRead => {
+                    // The file is truncated and gets new content.
+                    fs::write(&filename, "d\ne\n").unwrap();
+                }
+                stypes::CallbackEvent::FileRotated { pos, truncated, .. } => {
+                    assert!(truncated);
+                    assert_eq!(pos, 3);
+                }
+                stypes::CallbackEvent::StreamUpdated(5) => break,
+                stypes::CallbackEvent::OperationError { error, .. } => {
+                    panic!("Received operation error: {error:#?}")
+                }
+                _ => {}
+            }
+        }
+    };
+    timeout(Duration::from_secs(10), wait)
+        .await
+        .expect("Content of the truncated file should be observed");
+    let rows = session
+        .grab(LineRange::from(0..=4))
+        .await
+        .expect("Rows should be grabbed")
+        .0
+        .into_iter()
+        .map(|row| row.content)
+        .collect::<Vec<String>>();
+    // The content of the rows read before the truncation isn't available anymore.
+    assert_eq!(rows, vec!["", "", "", "d", "e"]);
+}
+
+#[tokio::test]
+async fn observe_concat_ordered_by_time() {
+    let dir = tempfile::tempdir().unwrap();
+    let newer = dir.path().join("a.log");
+    let older = dir.path().join("b.log");
+    fs::write(
+        &newer,
+        "[15.01.2024 14:40:00] third\n[15.01.2024 14:50:00] fourth\n",
+    )
+    .unwrap();
+    fs::write(
+        &older,
+        "[15.01.2024 14:30:00] first\n[15.01.2024 14:45:00] second\n",
+    )
+    .unwrap();
+    let uuid = Uuid::new_v4();
+    let (session, mut receiver) = Session::new(uuid).await.expect("Session should be created");
+    session
+        .observe(
+            uuid,
+            stypes::ObserveOptions {
+                origin: stypes::ObserveOrigin::Concat(
+                    [&newer, &older]
+                        .into_iter()
+                        .map(|filename| {
+                            (
+                                Uuid::new_v4().to_string(),
+                                stypes::FileFormat::Text,
+                                filename.clone(),
+                            )
+                        })
+                        .collect(),
+                ),
+                parser: stypes::ParserType::Text(()),
+                network_filter: None,
+                order_by_time: Some(stypes::TimestampSettings {
+                    format: Some(String::from("%d.%m.%Y %H:%M:%S")),
+                    tz: None,
+                    year: None,
+                }),
+            },
+        )
+        .unwrap();
+    let mut warnings = Vec::new();
+    while let Some(feedback) = receiver.recv().await {
+        match feedback {
+            stypes::CallbackEvent::OperationDone(..) => break,
+            stypes::CallbackEvent::Progress {
+                progress: stypes::Progress::Notification(notification),
+                ..
+            } => warnings.push(notification.content),
+            stypes::CallbackEvent::OperationError { error, .. } => {
+                panic!("Received operation error: {error:#?}")
+            }
+            _ => {}
+        }
+    }
+    // The older file ends after the newer file starts.
+    assert_eq!(warnings.len(), 1);
+    let rows = session
+        .grab(LineRange::from(0..=3))
+        .await
+        .expect("Rows should be grabbed")
+        .0
+        .into_iter()
+        .map(|row| row.content)
+        .collect::<Vec<String>>();
+    assert_eq!(
+        rows,
+        vec![
+            "[15.01.2024 14:30:00] first",
+            "[15.01.2024 14:45:00] second",
+            "[15.01.2024 14:40:00] third",
+            "[15.01.2024 14:50:00] fourth",
+        ]
+    );
+}
+
+fn trigger_rule(value: &str, actions: Vec<stypes::TriggerAction>) -> stypes::TriggerRule {
+    stypes::TriggerRule {
+        condition: stypes::TriggerCondition::Filter(stypes::SearchCondition {
+            value: value.to_owned(),
+            is_regex: false,
+            ignore_case: false,
+            is_word: false,
+        }),
+        actions,
+    }
+}
+
+/// Observes the file with trigger rules notifying on `error`, bookmarking `warn` and
+/// stopping after `stop` and its next row. Returns the rows of the notifications, the
+/// bookmarked rows and the rows in the session after observing is done.
+async fn observe_with_triggers(
+    content: &str,
+    file_format: stypes::FileFormat,
+) -> (Vec<u64>, Vec<u64>, usize) {
+    let dir = tempfile::tempdir().unwrap();
+    let filename = dir.path().join("app.log");
+    fs::write(&filename, content).unwrap();
+    let uuid = Uuid::new_v4();
+    let (session, mut receiver) = Session::new(uuid).await.expect("Session should be created");
+    session
+        .set_triggers(vec![
+            trigger_rule("error", vec![stypes::TriggerAction::Notify]),
+            trigger_rule("warn", vec![stypes::TriggerAction::Bookmark]),
+            trigger_rule("stop", vec![stypes::TriggerAction::StopAfter(1)]),
+        ])
+        .await
+        .expect("Trigger rules should be set");
+    session
+        .observe(
+            uuid,
+            stypes::ObserveOptions::file(
+                filename.clone(),
+                file_format,
+                stypes::ParserType::Text(()),
+            ),
+        )
+        .unwrap();
+    let mut notified = Vec::new();
+    let wait = async {
+        while let Some(feedback) = receiver.recv().await {
+            match feedback {
+                stypes::CallbackEvent::TriggerFired { rule, row } => {
+                    assert_eq!(rule, 0);
+                    notified.push(row);
+                }
+                // Observing of a tailed file ends only because of the rule.
+                stypes::CallbackEvent::OperationDone(..) => break,
+                stypes::CallbackEvent::OperationError { error, .. } => {
+                    panic!("Received operation error: {error:#?}")
+                }
+                _ => {}
+            }
+        }
+    };
+    timeout(Duration::from_secs(10), wait)
+        .await
+        .expect("Observing should be stopped by the trigger rule");
+    let bookmarked = session
+        .get_indexed_ranges()
+        .await
+        .expect("Indexed ranges should be available")
+        .0
+        .into_iter()
+        .flat_map(|range| range.start..=range.end)
+        .collect();
+    let rows = session
+        .get_stream_len()
+        .await
+        .expect("Stream length should be available");
+    (notified, bookmarked, rows)
+}
+
+#[tokio::test]
+async fn observe_with_triggers_parsed_file() {
+    let (notified, bookmarked, rows) = observe_with_triggers(
+        "a\nerror b\nwarn c\nd\nstop e\nf\ng\nerror h\n",
+        stypes::FileFormat::Binary,
+    )
+    .await;
+    assert_eq!(notified, vec![1]);
+    assert_eq!(bookmarked, vec![2]);
+    // Parsing stops right after the row following the row of the rule.
+    assert_eq!(rows, 6);
+}
+
+#[tokio::test]
+async fn observe_with_triggers_linked_file() {
+    let (notified, bookmarked, rows) = observe_with_triggers(
+        "a\nerror b\nwarn c\nd\nstop e\nf\ng\nerror h\n",
+        stypes::FileFormat::Text,
+    )
+    .await;
+    // The linked file is read as a whole, the rules are checked on all its rows.
+    assert_eq!(notified, vec![1, 7]);
+    assert_eq!(bookmarked, vec![2]);
+    assert_eq!(rows, 8);
+}
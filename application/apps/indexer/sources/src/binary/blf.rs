@@ -0,0 +1,351 @@
+use crate::{ByteSource, Error as SourceError, ReloadInfo, SourceFilter};
+use flate2::read::ZlibDecoder;
+use log::{debug, trace};
+use parsers::can::blf::{BASE_HEADER_LEN, FILE_SIGNATURE, LOG_CONTAINER, ObjectHeader};
+use std::io::Read;
+use tokio::task::{JoinHandle, spawn_blocking};
+
+/// Length of the header of a log container following the base header of the object.
+const CONTAINER_HEADER_LEN: usize = 16;
+/// Log container content isn't compressed.
+const NO_COMPRESSION: u16 = 0;
+/// Log container content is compressed with zlib.
+const ZLIB_DEFLATE: u16 = 2;
+
+/// Byte source for files in the Vector binary logging format (BLF).
+///
+/// The file header is skipped and log containers are unpacked. The source delivers the
+/// contained objects as one continuous stream, since objects may be split across the
+/// boundaries of log containers.
+///
+/// The reader is read on the blocking thread pool. Bytes of an object which isn't written
+/// completely yet are kept until the rest of the object can be read.
+pub struct BlfByteSource<R: Read> {
+    /// `None` while a read is running on the blocking thread pool.
+    reader: Option<R>,
+    /// Pending read, kept across calls to keep loading cancel safe.
+    reading: Option<JoinHandle<PendingRead<R>>>,
+    /// Bytes of the file header or of the next object read so far.
+    partial: Vec<u8>,
+    header_skipped: bool,
+    buffer: Vec<u8>,
+    consumed: usize,
+}
+
+/// Reader and read bytes returned from the blocking thread pool.
+type PendingRead<R> = (R, Vec<u8>, std::io::Result<usize>);
+
+impl<R: Read + Send + 'static> BlfByteSource<R> {
+    pub fn new(reader: R) -> Self {
+        Self {
+            reader: Some(reader),
+            reading: None,
+            partial: Vec::new(),
+            header_skipped: false,
+            buffer: Vec::new(),
+            consumed: 0,
+        }
+    }
+
+    /// Reads until at least `len` bytes are available in `partial`.
+    ///
+    /// Returns `false` if the reader ends before; the read bytes are kept for the next try.
+    async fn fill_partial(&mut self, len: usize) -> Result<bool, SourceError> {
+        let reading = match self.reading.as_mut() {
+            Some(reading) => reading,
+            None => {
+                if self.partial.len() >= len {
+                    return Ok(true);
+                }
+                let Some(mut reader) = self.reader.take() else {
+                    return Err(SourceError::Unrecoverable(
+                        "BLF reader isn't available".into(),
+                    ));
+                };
+                let mut partial = std::mem::take(&mut self.partial);
+                let missing = (len - partial.len()) as u64;
+                self.reading.insert(spawn_blocking(move || {
+                    let result = (&mut reader).take(missing).read_to_end(&mut partial);
+                    (reader, partial, result)
+                }))
+            }
+        };
+        // Awaiting the handle is cancel safe, an interrupted read is resumed on the next call.
+        let result = reading.await;
+        self.reading = None;
+        let (reader, partial, result) = result
+            .map_err(|e| SourceError::Unrecoverable(format!("Fail to read BLF file: {e}")))?;
+        self.reader = Some(reader);
+        self.partial = partial;
+        result.map_err(SourceError::Io)?;
+        Ok(self.partial.len() >= len)
+    }
+
+    /// Takes the first `len` bytes of `partial`.
+    fn take_partial(&mut self, len: usize) -> Vec<u8> {
+        let rest = self.partial.split_off(len);
+        std::mem::replace(&mut self.partial, rest)
+    }
+
+    /// Skips the file header and returns the amount of skipped bytes.
+    async fn skip_file_header(&mut self) -> Result<usize, SourceError> {
+        const START_LEN: usize = 8;
+        if !self.fill_partial(START_LEN).await? {
+            return Err(SourceError::Setup(
+                "Could not read BLF file header: file is too short".into(),
+            ));
+        }
+        let size = match parsers::can::blf::file_header_size(&self.partial[..START_LEN]) {
+            Some(Ok(size)) if size >= START_LEN => size,
+            _ => {
+                return Err(SourceError::Setup(format!(
+                    "BLF file signature {FILE_SIGNATURE:?} not found"
+                )));
+            }
+        };
+        if !self.fill_partial(size).await? {
+            return Err(SourceError::Setup("BLF file header is incomplete".into()));
+        }
+        self.take_partial(size);
+        Ok(size)
+    }
+
+    /// Reads the next top level object including its padding.
+    ///
+    /// Returns `None` if the end of the file is reached. The bytes of an incomplete object
+    /// are kept, so the object is read completely once the file grows.
+    async fn read_object(&mut self) -> Result<Option<(ObjectHeader, Vec<u8>)>, SourceError> {
+        if !self.fill_partial(BASE_HEADER_LEN).await? {
+            if !self.partial.is_empty() {
+                debug!("BLF file ends within an object header");
+            }
+            return Ok(None);
+        }
+        let header = ObjectHeader::parse(&self.partial[..BASE_HEADER_LEN])
+            .map_err(|e| SourceError::Unrecoverable(format!("Invalid BLF object: {e}")))?;
+        let size = header.padded_size().max(BASE_HEADER_LEN);
+        if !self.fill_partial(size).await? {
+            debug!("BLF file ends within an object");
+            return Ok(None);
+        }
+        Ok(Some((header, self.take_partial(size))))
+    }
+
+    /// Appends the content of the log container to the buffer and returns the amount of
+    /// appended bytes. Containers with unknown compression are skipped.
+    fn unpack_container(
+        &mut self,
+        header: &ObjectHeader,
+        object: &[u8],
+    ) -> Result<usize, SourceError> {
+        let content_start = BASE_HEADER_LEN + CONTAINER_HEADER_LEN;
+        if (header.object_size as usize) < content_start {
+            return Err(SourceError::Unrecoverable(
+                "BLF log container is too small".into(),
+            ));
+        }
+        let method = u16::from_le_bytes([object[BASE_HEADER_LEN], object[BASE_HEADER_LEN + 1]]);
+        let content = &object[content_start..header.object_size as usize];
+        match method {
+            NO_COMPRESSION => {
+                self.buffer.extend_from_slice(content);
+                Ok(content.len())
+            }
+            ZLIB_DEFLATE => ZlibDecoder::new(content)
+                .read_to_end(&mut self.buffer)
+                .map_err(|e| {
+                    SourceError::Unrecoverable(format!("Fail to unpack BLF log container: {e}"))
+                }),
+            _ => {
+                debug!("Skipping BLF log container with unknown compression {method}");
+                Ok(0)
+            }
+        }
+    }
+}
+
+impl<R: Read + Send + Sync + 'static> ByteSource for BlfByteSource<R> {
+    async fn load(&mut self, _: Option<&SourceFilter>) -> Result<Option<ReloadInfo>, SourceError> {
+        // Drop consumed bytes before loading more data.
+        self.buffer.drain(..self.consumed);
+        self.consumed = 0;
+        let mut skipped = 0;
+        if !self.header_skipped {
+            skipped += self.skip_file_header().await?;
+            self.header_skipped = true;
+        }
+        loop {
+            let Some((header, object)) = self.read_object().await? else {
+                trace!("BLF file, EOF");
+                return Ok(None);
+            };
+            let newly_loaded = if header.object_type == LOG_CONTAINER {
+                let loaded = self.unpack_container(&header, &object)?;
+                skipped += if loaded > 0 {
+                    BASE_HEADER_LEN + CONTAINER_HEADER_LEN
+                } else {
+                    object.len()
+                };
+                loaded
+            } else {
+                self.buffer.extend_from_slice(&object);
+                object.len()
+            };
+            if newly_loaded > 0 {
+                trace!("BLF file, loaded {newly_loaded} bytes");
+                return Ok(Some(ReloadInfo::new(
+                    newly_loaded,
+                    self.len(),
+                    skipped,
+                    None,
+                )));
+            }
+        }
+    }
+
+    fn current_slice(&self) -> &[u8] {
+        &self.buffer[self.consumed..]
+    }
+
+    fn consume(&mut self, offset: usize) {
+        self.consumed = (self.consumed + offset).min(self.buffer.len());
+    }
+
+    fn len(&self) -> usize {
+        self.buffer.len() - self.consumed
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::general_source_reload_test;
+    use flate2::{Compression, write::ZlibEncoder};
+    use parsers::can::blf::{CAN_MESSAGE, OBJECT_SIGNATURE};
+    use std::{
+        io::{Cursor, Write},
+        sync::{Arc, Mutex},
+    };
+
+    fn object(object_type: u32, data: &[u8]) -> Vec<u8> {
+        let size = BASE_HEADER_LEN + data.len();
+        let mut obj = Vec::new();
+        obj.extend_from_slice(OBJECT_SIGNATURE);
+        obj.extend_from_slice(&(BASE_HEADER_LEN as u16).to_le_bytes());
+        obj.extend_from_slice(&1u16.to_le_bytes());
+        obj.extend_from_slice(&(size as u32).to_le_bytes());
+        obj.extend_from_slice(&object_type.to_le_bytes());
+        obj.extend_from_slice(data);
+        obj.resize(size + size % 4, 0);
+        obj
+    }
+
+    fn container(content: &[u8], compressed: bool) -> Vec<u8> {
+        let mut data = Vec::new();
+        let method = if compressed {
+            ZLIB_DEFLATE
+        } else {
+            NO_COMPRESSION
+        };
+        data.extend_from_slice(&method.to_le_bytes());
+        data.extend_from_slice(&[0; 6]);
+        data.extend_from_slice(&(content.len() as u32).to_le_bytes());
+        data.extend_from_slice(&[0; 4]);
+        if compressed {
+            let mut encoder = ZlibEncoder::new(Vec::new(), Compression::default());
+            encoder.write_all(content).unwrap();
+            data.extend_from_slice(&encoder.finish().unwrap());
+        } else {
+            data.extend_from_slice(content);
+        }
+        object(LOG_CONTAINER, &data)
+    }
+
+    fn blf_file(containers: &[Vec<u8>]) -> Vec<u8> {
+        let mut file = Vec::new();
+        file.extend_from_slice(FILE_SIGNATURE);
+        file.extend_from_slice(&144u32.to_le_bytes());
+        file.resize(144, 0);
+        containers.iter().for_each(|c| file.extend_from_slice(c));
+        file
+    }
+
+    fn inner_objects() -> Vec<u8> {
+        let mut objects = Vec::new();
+        for id in 0..10u32 {
+            let mut msg = vec![1, 0, 0, 2];
+            msg.extend_from_slice(&id.to_le_bytes());
+            msg.extend_from_slice(&[0xAB; 8]);
+            objects.extend_from_slice(&object(CAN_MESSAGE, &msg));
+        }
+        objects
+    }
+
+    #[tokio::test]
+    async fn unpack_containers() {
+        let objects = inner_objects();
+        // Split the objects in the middle of one object.
+        let (first, second) = objects.split_at(objects.len() / 2 + 3);
+        let file = blf_file(&[container(first, true), container(second, false)]);
+        let mut source = BlfByteSource::new(Cursor::new(file));
+        let mut received = Vec::new();
+        while let Some(info) = source.load(None).await.unwrap() {
+            assert_eq!(info.available_bytes, source.current_slice().len());
+            received.extend_from_slice(source.current_slice());
+            source.consume(info.available_bytes);
+        }
+        assert_eq!(received, objects);
+    }
+
+    /// Reader delivering the shared content, which may grow between reads.
+    struct GrowingReader {
+        content: Arc<Mutex<Vec<u8>>>,
+        pos: usize,
+    }
+
+    impl Read for GrowingReader {
+        fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+            let content = self.content.lock().unwrap();
+            let len = buf.len().min(content.len() - self.pos);
+            buf[..len].copy_from_slice(&content[self.pos..self.pos + len]);
+            self.pos += len;
+            Ok(len)
+        }
+    }
+
+    #[tokio::test]
+    async fn partial_object() {
+        let objects = inner_objects();
+        let file = blf_file(&[container(&objects, false)]);
+        let (written, rest) = file.split_at(file.len() - 20);
+        let content = Arc::new(Mutex::new(written.to_vec()));
+        let mut source = BlfByteSource::new(GrowingReader {
+            content: content.clone(),
+            pos: 0,
+        });
+        assert!(source.load(None).await.unwrap().is_none());
+        content.lock().unwrap().extend_from_slice(rest);
+        let info = source
+            .load(None)
+            .await
+            .unwrap()
+            .expect("object is complete");
+        assert_eq!(info.available_bytes, objects.len());
+        assert_eq!(source.current_slice(), objects);
+    }
+
+    #[tokio::test]
+    async fn invalid_file() {
+        let mut source = BlfByteSource::new(Cursor::new(b"no blf file".to_vec()));
+        assert!(source.load(None).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_general_source_reload() {
+        let objects = inner_objects();
+        let file = blf_file(&[container(&objects, true), container(&objects, true)]);
+        let mut source = BlfByteSource::new(Cursor::new(file));
+
+        general_source_reload_test(&mut source).await;
+    }
+}
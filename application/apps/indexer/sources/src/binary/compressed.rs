@@ -0,0 +1,498 @@
+//! # Compressed and archived files
+//!
+//! Logs are often shipped compressed (gzip, zstd, xz) or bundled in archives (zip, tar or
+//! compressed tar). [`DecompressedFile`] opens such files as a plain stream of the contained
+//! data, so it can be used as input of every binary byte source.
+//!
+//! Compressed files are decompressed while reading. Entries of archives are addressed by
+//! appending their path within the archive to the path of the archive file, e.g.
+//! `/logs/bundle.tar.gz/ecu1/trace.dlt`. Without an entry path, the first regular file of
+//! the archive is opened.
+//!
+//! Since the size of the decompressed data is unknown in advance, the progress of reading is
+//! measured in bytes read from the file on disk (see [`ReadProgress`]).
+use crate::Error as SourceError;
+use flate2::read::{DeflateDecoder, MultiGzDecoder};
+use std::{
+    fs::File,
+    io::{self, Read, Seek, SeekFrom},
+    path::{Component, Path, PathBuf},
+    sync::{
+        Arc, Mutex,
+        atomic::{AtomicU64, Ordering},
+    },
+};
+
+const GZIP_MAGIC: &[u8] = &[0x1F, 0x8B];
+const ZSTD_MAGIC: &[u8] = &[0x28, 0xB5, 0x2F, 0xFD];
+const XZ_MAGIC: &[u8] = &[0xFD, 0x37, 0x7A, 0x58, 0x5A, 0x00];
+/// Signature of the local file header, which starts every non-empty zip archive.
+const ZIP_MAGIC: &[u8] = &[0x50, 0x4B, 0x03, 0x04];
+/// Magic of POSIX (`ustar\0`) and GNU (`ustar `) tar headers.
+const TAR_MAGIC: &[u8] = b"ustar";
+const TAR_MAGIC_OFFSET: usize = 257;
+const TAR_HEADER_LEN: usize = 512;
+
+/// Compression of a file or of a tar archive.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Compression {
+    Gzip,
+    Zstd,
+    Xz,
+}
+
+impl Compression {
+    fn detect(magic: &[u8]) -> Option<Self> {
+        if magic.starts_with(GZIP_MAGIC) {
+            Some(Compression::Gzip)
+        } else if magic.starts_with(ZSTD_MAGIC) {
+            Some(Compression::Zstd)
+        } else if magic.starts_with(XZ_MAGIC) {
+            Some(Compression::Xz)
+        } else {
+            None
+        }
+    }
+
+    fn decoder<R: Read + Send + 'static>(&self, reader: R) -> io::Result<Box<dyn Read + Send>> {
+        Ok(match self {
+            // Files can consist of multiple concatenated gzip members or xz streams.
+            Compression::Gzip => Box::new(MultiGzDecoder::new(reader)),
+            Compression::Zstd => Box::new(zstd::stream::read::Decoder::new(reader)?),
+            Compression::Xz => Box::new(xz2::read::XzDecoder::new_multi_decoder(reader)),
+        })
+    }
+}
+
+/// Describes how the data is stored in a file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Container {
+    /// Uncompressed data.
+    Plain,
+    /// A compressed file.
+    Compressed(Compression),
+    Zip,
+    /// A tar archive, which might be compressed as a whole.
+    Tar(Option<Compression>),
+}
+
+impl Container {
+    /// Detects the container of the file by its content. For paths of archive entries, the
+    /// container of the archive is returned.
+    pub fn detect(path: &Path) -> Result<Self, SourceError> {
+        let (file_path, _) = split_entry_path(path)?;
+        Self::detect_file(&file_path)
+    }
+
+    fn detect_file(path: &Path) -> Result<Self, SourceError> {
+        let prefix = read_prefix(open_file(path)?, TAR_HEADER_LEN)?;
+        if let Some(compression) = Compression::detect(&prefix) {
+            let decoder = compression
+                .decoder(open_file(path)?)
+                .map_err(SourceError::Io)?;
+            return Ok(if is_tar(&read_prefix(decoder, TAR_HEADER_LEN)?) {
+                Container::Tar(Some(compression))
+            } else {
+                Container::Compressed(compression)
+            });
+        }
+        Ok(if prefix.starts_with(ZIP_MAGIC) {
+            Container::Zip
+        } else if is_tar(&prefix) {
+            Container::Tar(None)
+        } else {
+            Container::Plain
+        })
+    }
+
+    pub fn is_plain(&self) -> bool {
+        matches!(self, Container::Plain)
+    }
+}
+
+/// Progress of reading a file, measured in bytes read from the file on disk.
+#[derive(Debug, Clone)]
+pub struct ReadProgress {
+    read: Arc<AtomicU64>,
+    total: u64,
+}
+
+impl ReadProgress {
+    /// Count of bytes read so far.
+    pub fn read(&self) -> u64 {
+        self.read.load(Ordering::Relaxed)
+    }
+
+    /// Count of bytes to read in total (e.g. the size of the compressed file).
+    pub fn total(&self) -> u64 {
+        self.total
+    }
+}
+
+/// Counts the bytes read from the inner reader.
+struct CountingReader<R: Read> {
+    inner: R,
+    read: Arc<AtomicU64>,
+}
+
+impl<R: Read> Read for CountingReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let read = self.inner.read(buf)?;
+        self.read.fetch_add(read as u64, Ordering::Relaxed);
+        Ok(read)
+    }
+}
+
+/// A file, compressed file or archive entry opened as a stream of its (decompressed) data.
+pub struct DecompressedFile {
+    /// The mutex makes the reader `Sync` as required by the byte sources. It's only accessed
+    /// mutably, so it's never actually locked.
+    reader: Mutex<Box<dyn Read + Send>>,
+    container: Container,
+    progress: ReadProgress,
+}
+
+impl DecompressedFile {
+    /// Opens the file or the archive entry addressed by the path (see module documentation).
+    pub fn open(path: &Path) -> Result<Self, SourceError> {
+        let (file_path, entry) = split_entry_path(path)?;
+        let container = Container::detect_file(&file_path)?;
+        let file = open_file(&file_path)?;
+        let len = file.metadata().map_err(SourceError::Io)?.len();
+        let read = Arc::new(AtomicU64::new(0));
+        let (reader, total) = match (container, entry) {
+            (Container::Zip, entry) => open_zip_entry(file, entry.as_deref(), &read)?,
+            (Container::Tar(compression), entry) => (
+                open_tar_entry(&file_path, compression, entry.as_deref(), &read)?,
+                len,
+            ),
+            (_, Some(entry)) => {
+                return Err(SourceError::Setup(format!(
+                    "Cannot open entry {entry}: {} isn't an archive",
+                    file_path.display()
+                )));
+            }
+            (Container::Compressed(compression), None) => (
+                compression
+                    .decoder(counting(file, &read))
+                    .map_err(SourceError::Io)?,
+                len,
+            ),
+            (Container::Plain, None) => {
+                let reader: Box<dyn Read + Send> = Box::new(counting(file, &read));
+                (reader, len)
+            }
+        };
+        debug!("opened {} ({container:?})", path.display());
+        Ok(Self {
+            reader: Mutex::new(reader),
+            container,
+            progress: ReadProgress { read, total },
+        })
+    }
+
+    pub fn container(&self) -> Container {
+        self.container
+    }
+
+    pub fn progress(&self) -> ReadProgress {
+        self.progress.clone()
+    }
+}
+
+impl Read for DecompressedFile {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        match self.reader.get_mut() {
+            Ok(reader) => reader.read(buf),
+            Err(poisoned) => poisoned.into_inner().read(buf),
+        }
+    }
+}
+
+/// Lists the paths of the regular files in the archive.
+pub fn entries(path: &Path) -> Result<Vec<String>, SourceError> {
+    match Container::detect_file(path)? {
+        Container::Zip => {
+            let mut archive = zip_archive(open_file(path)?)?;
+            let mut entries = Vec::new();
+            for index in 0..archive.len() {
+                let zip_file = archive.by_index_raw(index).map_err(zip_error)?;
+                if zip_file.is_file() {
+                    entries.push(entry_name(Path::new(zip_file.name())));
+                }
+            }
+            Ok(entries)
+        }
+        Container::Tar(compression) => {
+            let read = Arc::new(AtomicU64::new(0));
+            let mut archive = tar::Archive::new(open_stream(path, compression, &read)?);
+            let mut entries = Vec::new();
+            for tar_entry in archive.entries().map_err(SourceError::Io)? {
+                let tar_entry = tar_entry.map_err(SourceError::Io)?;
+                if tar_entry.header().entry_type().is_file() {
+                    entries.push(entry_name(&tar_entry.path().map_err(SourceError::Io)?));
+                }
+            }
+            Ok(entries)
+        }
+        _ => Err(SourceError::Setup(format!(
+            "{} isn't an archive",
+            path.display()
+        ))),
+    }
+}
+
+/// Splits the path into the path of the file on disk and the path of an archive entry, if
+/// the path addresses an entry.
+fn split_entry_path(path: &Path) -> Result<(PathBuf, Option<String>), SourceError> {
+    if path.is_file() {
+        return Ok((path.to_path_buf(), None));
+    }
+    for archive in path.ancestors().skip(1) {
+        if archive.is_file() {
+            let entry = path.strip_prefix(archive).unwrap_or(path);
+            return Ok((archive.to_path_buf(), Some(entry_name(entry))));
+        }
+    }
+    Err(SourceError::Io(io::Error::new(
+        io::ErrorKind::NotFound,
+        format!("{} doesn't exist", path.display()),
+    )))
+}
+
+/// Normalizes the path of an entry to `/` separated components (e.g. `./logs/a.dlt` to
+/// `logs/a.dlt`).
+fn entry_name(path: &Path) -> String {
+    path.components()
+        .filter_map(|component| match component {
+            Component::Normal(name) => Some(name.to_string_lossy()),
+            _ => None,
+        })
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+fn missing_entry(entry: Option<&str>) -> SourceError {
+    SourceError::Setup(match entry {
+        Some(entry) => format!("Archive doesn't contain entry {entry}"),
+        None => String::from("Archive doesn't contain any file"),
+    })
+}
+
+fn zip_error(err: zip::result::ZipError) -> SourceError {
+    SourceError::Setup(format!("Invalid zip archive: {err}"))
+}
+
+fn zip_archive(file: File) -> Result<zip::ZipArchive<File>, SourceError> {
+    zip::ZipArchive::new(file).map_err(zip_error)
+}
+
+/// Opens the data of the zip entry. Returns the reader along with the compressed size of the
+/// entry.
+fn open_zip_entry(
+    mut file: File,
+    entry: Option<&str>,
+    read: &Arc<AtomicU64>,
+) -> Result<(Box<dyn Read + Send>, u64), SourceError> {
+    let mut archive = zip_archive(file.try_clone().map_err(SourceError::Io)?)?;
+    let mut found = None;
+    for index in 0..archive.len() {
+        let zip_file = archive.by_index_raw(index).map_err(zip_error)?;
+        if zip_file.is_file()
+            && entry.is_none_or(|entry| entry_name(Path::new(zip_file.name())) == entry)
+        {
+            if zip_file.encrypted() {
+                return Err(SourceError::Setup(format!(
+                    "Encrypted zip entry {} isn't supported",
+                    zip_file.name()
+                )));
+            }
+            found = Some((
+                zip_file.data_start(),
+                zip_file.compressed_size(),
+                zip_file.compression(),
+            ));
+            break;
+        }
+    }
+    let (start, len, compression) = found.ok_or_else(|| missing_entry(entry))?;
+    // The entry is read directly from the file to own the reader.
+    file.seek(SeekFrom::Start(start)).map_err(SourceError::Io)?;
+    let data = counting(file, read).take(len);
+    let reader: Box<dyn Read + Send> = match compression {
+        zip::CompressionMethod::Stored => Box::new(data),
+        zip::CompressionMethod::Deflated => Box::new(DeflateDecoder::new(data)),
+        other => {
+            return Err(SourceError::Setup(format!(
+                "Compression {other:?} of zip entries isn't supported"
+            )));
+        }
+    };
+    Ok((reader, len))
+}
+
+/// Opens the data of the tar entry.
+///
+/// The position of the entry is looked up first. Afterwards, the stream is reopened and
+/// read up to the entry, since the entries of a tar archive borrow the archive.
+fn open_tar_entry(
+    path: &Path,
+    compression: Option<Compression>,
+    entry: Option<&str>,
+    read: &Arc<AtomicU64>,
+) -> Result<Box<dyn Read + Send>, SourceError> {
+    let (position, size) = {
+        let lookup = Arc::new(AtomicU64::new(0));
+        let mut archive = tar::Archive::new(open_stream(path, compression, &lookup)?);
+        let mut found = None;
+        for tar_entry in archive.entries().map_err(SourceError::Io)? {
+            let tar_entry = tar_entry.map_err(SourceError::Io)?;
+            if !tar_entry.header().entry_type().is_file() {
+                continue;
+            }
+            let name = entry_name(&tar_entry.path().map_err(SourceError::Io)?);
+            if entry.is_none_or(|entry| name == entry) {
+                found = Some((tar_entry.raw_file_position(), tar_entry.size()));
+                break;
+            }
+        }
+        found.ok_or_else(|| missing_entry(entry))?
+    };
+    let mut stream = open_stream(path, compression, read)?;
+    io::copy(&mut (&mut stream).take(position), &mut io::sink()).map_err(SourceError::Io)?;
+    Ok(Box::new(stream.take(size)))
+}
+
+/// Opens the file as a stream of its decompressed data.
+fn open_stream(
+    path: &Path,
+    compression: Option<Compression>,
+    read: &Arc<AtomicU64>,
+) -> Result<Box<dyn Read + Send>, SourceError> {
+    let file = counting(open_file(path)?, read);
+    match compression {
+        Some(compression) => compression.decoder(file).map_err(SourceError::Io),
+        None => Ok(Box::new(file)),
+    }
+}
+
+fn open_file(path: &Path) -> Result<File, SourceError> {
+    File::open(path).map_err(SourceError::Io)
+}
+
+fn counting<R: Read>(inner: R, read: &Arc<AtomicU64>) -> CountingReader<R> {
+    CountingReader {
+        inner,
+        read: read.clone(),
+    }
+}
+
+fn read_prefix<R: Read>(reader: R, len: usize) -> Result<Vec<u8>, SourceError> {
+    let mut prefix = Vec::with_capacity(len);
+    reader
+        .take(len as u64)
+        .read_to_end(&mut prefix)
+        .map_err(SourceError::Io)?;
+    Ok(prefix)
+}
+
+fn is_tar(header: &[u8]) -> bool {
+    header
+        .get(TAR_MAGIC_OFFSET..TAR_MAGIC_OFFSET + TAR_MAGIC.len())
+        .is_some_and(|magic| magic == TAR_MAGIC)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use flate2::{Compression as Level, write::GzEncoder};
+    use std::io::Write;
+
+    fn gzip(data: &[u8]) -> Vec<u8> {
+        let mut encoder = GzEncoder::new(Vec::new(), Level::default());
+        encoder.write_all(data).expect("gzip data");
+        encoder.finish().expect("finish gzip")
+    }
+
+    fn tar(entries: &[(&str, &[u8])]) -> Vec<u8> {
+        let mut builder = tar::Builder::new(Vec::new());
+        builder.append_dir("./logs", ".").expect("append directory");
+        for (name, data) in entries {
+            let mut header = tar::Header::new_gnu();
+            header.set_size(data.len() as u64);
+            header.set_mode(0o644);
+            builder
+                .append_data(&mut header, name, *data)
+                .expect("append entry");
+        }
+        builder.into_inner().expect("finish tar")
+    }
+
+    fn read_all(path: &Path) -> Result<(Vec<u8>, DecompressedFile), SourceError> {
+        let mut file = DecompressedFile::open(path)?;
+        let mut data = Vec::new();
+        file.read_to_end(&mut data).map_err(SourceError::Io)?;
+        Ok((data, file))
+    }
+
+    #[test]
+    fn read_gzip_file() {
+        let dir = tempfile::tempdir().expect("temp dir");
+        let path = dir.path().join("trace.dlt.gz");
+        let compressed = gzip(b"first message\nsecond message\n");
+        std::fs::write(&path, &compressed).expect("write file");
+        let (data, file) = read_all(&path).expect("read file");
+        assert_eq!(data, b"first message\nsecond message\n");
+        assert_eq!(file.container(), Container::Compressed(Compression::Gzip));
+        assert_eq!(file.progress().read(), compressed.len() as u64);
+        assert_eq!(file.progress().total(), compressed.len() as u64);
+        // Entries can be opened in archives only.
+        assert!(DecompressedFile::open(&path.join("trace.dlt")).is_err());
+    }
+
+    #[test]
+    fn read_entries_of_compressed_tar() {
+        let dir = tempfile::tempdir().expect("temp dir");
+        let path = dir.path().join("bundle.tar.gz");
+        let archive = tar(&[("logs/a.log", b"aaa"), ("logs/b.log", b"bbbb")]);
+        std::fs::write(&path, gzip(&archive)).expect("write file");
+        assert_eq!(
+            Container::detect(&path).expect("detect"),
+            Container::Tar(Some(Compression::Gzip))
+        );
+        assert_eq!(
+            entries(&path).expect("list entries"),
+            vec![String::from("logs/a.log"), String::from("logs/b.log")]
+        );
+        let (data, _) = read_all(&path.join("logs/b.log")).expect("read entry");
+        assert_eq!(data, b"bbbb");
+        // Without entry path, the first file is read.
+        let (data, _) = read_all(&path).expect("read first entry");
+        assert_eq!(data, b"aaa");
+        assert!(DecompressedFile::open(&path.join("logs/c.log")).is_err());
+    }
+
+    #[test]
+    fn read_entries_of_zip() {
+        let dir = tempfile::tempdir().expect("temp dir");
+        let path = dir.path().join("bundle.zip");
+        let mut writer = zip::ZipWriter::new(File::create(&path).expect("create file"));
+        for (name, method) in [
+            ("stored.log", zip::CompressionMethod::Stored),
+            ("deflated.log", zip::CompressionMethod::Deflated),
+        ] {
+            let options = zip::write::SimpleFileOptions::default().compression_method(method);
+            writer.start_file(name, options).expect("start entry");
+            writer
+                .write_all(name.repeat(100).as_bytes())
+                .expect("write entry");
+        }
+        writer.finish().expect("finish zip");
+        assert_eq!(Container::detect(&path).expect("detect"), Container::Zip);
+        for name in ["stored.log", "deflated.log"] {
+            let (data, file) = read_all(&path.join(name)).expect("read entry");
+            assert_eq!(data, name.repeat(100).as_bytes());
+            assert_eq!(file.progress().read(), file.progress().total());
+        }
+    }
+}
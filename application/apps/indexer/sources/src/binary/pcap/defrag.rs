@@ -0,0 +1,174 @@
+//! # Reassembly of fragmented IPv4 packets
+//!
+//! Large UDP datagrams (e.g. SOME/IP messages) are split into multiple IPv4 fragments, of
+//! which only the first one carries the UDP header. The fragments are collected per
+//! datagram until all of them were received.
+use log::{debug, trace};
+use std::{collections::HashMap, net::Ipv4Addr};
+
+/// Maximum count of incomplete datagrams. If it's exceeded, the oldest datagram is dropped.
+const MAX_INCOMPLETE_DATAGRAMS: usize = 256;
+
+/// Identifies the fragments of one datagram.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct FragmentKey {
+    pub source: Ipv4Addr,
+    pub destination: Ipv4Addr,
+    pub protocol: u8,
+    pub identification: u16,
+}
+
+/// A fragment of an IPv4 packet.
+#[derive(Debug)]
+pub struct Fragment<'a> {
+    /// Offset of the fragment data in bytes.
+    pub offset: usize,
+    pub more_fragments: bool,
+    pub payload: &'a [u8],
+}
+
+#[derive(Debug, Default)]
+struct Datagram {
+    fragments: Vec<(usize, Vec<u8>)>,
+    /// Total length of the datagram, known once the last fragment was received.
+    len: Option<usize>,
+    /// Number of the fragment which started the datagram.
+    since: u64,
+}
+
+impl Datagram {
+    fn bytes(&self) -> usize {
+        self.fragments.iter().map(|(_, data)| data.len()).sum()
+    }
+
+    /// Assembles the payload if all fragments were received.
+    fn assemble(&mut self) -> Option<Vec<u8>> {
+        let len = self.len?;
+        self.fragments.sort_by_key(|(offset, _)| *offset);
+        let mut covered = 0;
+        for (offset, data) in self.fragments.iter() {
+            if *offset > covered {
+                return None;
+            }
+            covered = covered.max(offset + data.len());
+        }
+        if covered < len {
+            return None;
+        }
+        let mut payload = vec![0u8; len];
+        for (offset, data) in self.fragments.iter() {
+            let end = (offset + data.len()).min(len);
+            if *offset < end {
+                payload[*offset..end].copy_from_slice(&data[..end - offset]);
+            }
+        }
+        Some(payload)
+    }
+}
+
+/// Collects the fragments of IPv4 datagrams.
+#[derive(Debug, Default)]
+pub struct Ipv4Defragmenter {
+    datagrams: HashMap<FragmentKey, Datagram>,
+    fragments: u64,
+}
+
+impl Ipv4Defragmenter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds the fragment and returns the payload of the datagram once it's complete. The
+    /// count of bytes of dropped incomplete datagrams is returned as well.
+    pub fn push(&mut self, key: FragmentKey, fragment: Fragment) -> (Option<Vec<u8>>, usize) {
+        self.fragments += 1;
+        let mut dropped = 0;
+        if !self.datagrams.contains_key(&key) && self.datagrams.len() >= MAX_INCOMPLETE_DATAGRAMS {
+            dropped += self.drop_oldest();
+        }
+        let since = self.fragments;
+        let datagram = self.datagrams.entry(key).or_insert_with(|| Datagram {
+            since,
+            ..Default::default()
+        });
+        if !fragment.more_fragments {
+            datagram.len = Some(fragment.offset + fragment.payload.len());
+        }
+        datagram
+            .fragments
+            .push((fragment.offset, fragment.payload.to_vec()));
+        let payload = datagram.assemble();
+        if payload.is_some() {
+            trace!("datagram {key:?} reassembled");
+            self.datagrams.remove(&key);
+        }
+        (payload, dropped)
+    }
+
+    fn drop_oldest(&mut self) -> usize {
+        let Some(key) = self
+            .datagrams
+            .iter()
+            .min_by_key(|(_, datagram)| datagram.since)
+            .map(|(key, _)| *key)
+        else {
+            return 0;
+        };
+        debug!("drop incomplete datagram {key:?}");
+        self.datagrams
+            .remove(&key)
+            .map(|datagram| datagram.bytes())
+            .unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(identification: u16) -> FragmentKey {
+        FragmentKey {
+            source: Ipv4Addr::new(10, 0, 0, 1),
+            destination: Ipv4Addr::new(10, 0, 0, 2),
+            protocol: 17,
+            identification,
+        }
+    }
+
+    fn fragment(offset: usize, more_fragments: bool, payload: &[u8]) -> Fragment<'_> {
+        Fragment {
+            offset,
+            more_fragments,
+            payload,
+        }
+    }
+
+    #[test]
+    fn reassemble_out_of_order_fragments() {
+        let mut defrag = Ipv4Defragmenter::new();
+        assert_eq!(defrag.push(key(1), fragment(16, false, b"cc")), (None, 0));
+        assert_eq!(
+            defrag.push(key(1), fragment(0, true, b"aaaaaaaa")),
+            (None, 0)
+        );
+        // Fragments of other datagrams are kept apart.
+        assert_eq!(
+            defrag.push(key(2), fragment(0, true, b"xxxxxxxx")),
+            (None, 0)
+        );
+        let (payload, _) = defrag.push(key(1), fragment(8, true, b"bbbbbbbb"));
+        assert_eq!(payload.as_deref(), Some(&b"aaaaaaaabbbbbbbbcc"[..]));
+    }
+
+    #[test]
+    fn drop_oldest_incomplete_datagram() {
+        let mut defrag = Ipv4Defragmenter::new();
+        for id in 0..MAX_INCOMPLETE_DATAGRAMS as u16 {
+            defrag.push(key(id), fragment(0, true, b"12345678"));
+        }
+        let (_, dropped) = defrag.push(key(u16::MAX), fragment(0, true, b"12345678"));
+        assert_eq!(dropped, 8);
+        // The first datagram is gone, its last fragment alone is incomplete.
+        assert_eq!(defrag.push(key(0), fragment(8, false, b"end")), (None, 8));
+    }
+}
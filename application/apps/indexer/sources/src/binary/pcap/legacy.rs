@@ -1,17 +1,23 @@
 use crate::{
-    ByteSource, Error as SourceError, ReloadInfo, SourceFilter, TransportProtocol,
-    binary::pcap::debug_block,
+    ByteSource, Error as SourceError, NetworkFlow, ReloadInfo, SourceFilter,
+    binary::pcap::{PacketDecoder, debug_block},
 };
 use bufread::DeqBuffer;
-use etherparse::{SlicedPacket, TransportSlice};
 use log::{debug, error, trace};
-use pcap_parser::{LegacyPcapReader, PcapBlockOwned, PcapError, traits::PcapReaderIterator};
+use pcap_parser::{
+    LegacyPcapReader, Linktype, PcapBlockOwned, PcapError, traits::PcapReaderIterator,
+};
 use std::io::Read;
 
 pub struct PcapLegacyByteSource<R: Read> {
     pcap_reader: LegacyPcapReader<R>,
     buffer: DeqBuffer,
-    last_know_timestamp: Option<u64>,
+    decoder: PacketDecoder,
+    /// Link type of the captured frames.
+    link_type: Linktype,
+    /// Indicates that the fractions of the timestamps of packets are nanoseconds instead of
+    /// microseconds.
+    nanosecond_precision: bool,
     total: usize,
 }
 
@@ -21,7 +27,9 @@ impl<R: Read> PcapLegacyByteSource<R> {
             pcap_reader: LegacyPcapReader::new(65536, reader)
                 .map_err(|e| SourceError::Setup(format!("{e}")))?,
             buffer: DeqBuffer::new(8192),
-            last_know_timestamp: None,
+            decoder: PacketDecoder::new(),
+            link_type: Linktype::ETHERNET,
+            nanosecond_precision: false,
             total: 0,
         })
     }
@@ -32,10 +40,19 @@ impl<R: Read + Send + Sync> ByteSource for PcapLegacyByteSource<R> {
         &mut self,
         filter: Option<&SourceFilter>,
     ) -> Result<Option<ReloadInfo>, SourceError> {
-        let raw_data;
-        let mut consumed;
         let mut skipped = 0usize;
         loop {
+            // Reassembled data of previous packets is written before reading new packets.
+            let written = self.decoder.write_into(&mut self.buffer);
+            skipped += written.discarded;
+            if written.bytes > 0 {
+                return Ok(Some(ReloadInfo::new(
+                    written.bytes,
+                    self.buffer.read_available(),
+                    skipped,
+                    written.timestamp,
+                )));
+            }
             match self.pcap_reader.next() {
                 Ok((bytes_read, block)) => {
                     self.total += bytes_read;
@@ -43,27 +60,52 @@ impl<R: Read + Send + Sync> ByteSource for PcapLegacyByteSource<R> {
                         "PcapByteSource::reload, bytes_read: {} (total: {})",
                         bytes_read, self.total
                     );
-                    consumed = bytes_read;
+                    let consumed = bytes_read;
                     match block {
-                        PcapBlockOwned::LegacyHeader(ref _hdr) => {
-                            self.pcap_reader.consume(consumed);
-                            continue;
+                        PcapBlockOwned::LegacyHeader(ref hdr) => {
+                            self.link_type = hdr.network;
+                            self.nanosecond_precision = hdr.is_nanosecond_precision();
                         }
                         PcapBlockOwned::Legacy(ref b) => {
-                            raw_data = &b.data[..b.origlen as usize];
-                            break;
+                            let raw_data = &b.data[..b.origlen as usize];
+                            skipped += consumed - raw_data.len();
+                            let fraction_ms = if self.nanosecond_precision {
+                                b.ts_usec as u64 / 1_000_000
+                            } else {
+                                b.ts_usec as u64 / 1000
+                            };
+                            let timestamp = b.ts_sec as u64 * 1000 + fraction_ms;
+                            skipped += self.decoder.push_frame(
+                                self.link_type,
+                                raw_data,
+                                Some(timestamp),
+                                filter,
+                            );
                         }
                         other_type => {
                             debug_block(other_type);
                             skipped += consumed;
                             debug!("skipped in total {skipped} bytes");
-                            self.pcap_reader.consume(consumed);
-                            continue;
                         }
                     }
+                    // bytes are copied into the streams and can be dropped by pcap reader
+                    trace!("consume {consumed} processed bytes");
+                    self.pcap_reader.consume(consumed);
                 }
                 Err(PcapError::Eof) => {
                     debug!("reloading from pcap file, EOF");
+                    // Incomplete streams are delivered with their gaps skipped.
+                    skipped += self.decoder.finish();
+                    let written = self.decoder.write_into(&mut self.buffer);
+                    skipped += written.discarded;
+                    if written.bytes > 0 {
+                        return Ok(Some(ReloadInfo::new(
+                            written.bytes,
+                            self.buffer.read_available(),
+                            skipped,
+                            written.timestamp,
+                        )));
+                    }
                     return Ok(None);
                 }
                 Err(PcapError::Incomplete(size)) => {
@@ -80,66 +122,6 @@ impl<R: Read + Send + Sync> ByteSource for PcapLegacyByteSource<R> {
                 }
             }
         }
-        let res = match SlicedPacket::from_ethernet(raw_data) {
-            Ok(value) => {
-                let payload = match &value.transport {
-                    Some(TransportSlice::Icmpv4(slice)) => slice.payload(),
-                    Some(TransportSlice::Icmpv6(slice)) => slice.payload(),
-                    Some(TransportSlice::Udp(slice)) => slice.payload(),
-                    Some(TransportSlice::Tcp(slice)) => slice.payload(),
-                    None => {
-                        return Err(SourceError::Unrecoverable(format!(
-                            "ethernet frame with unknown payload: {raw_data:02X?}"
-                        )));
-                    }
-                };
-                skipped += consumed - payload.len();
-                match (value.transport, filter) {
-                    (
-                        Some(actual),
-                        Some(SourceFilter {
-                            transport: Some(wanted),
-                        }),
-                    ) => {
-                        let actual_tp: TransportProtocol = actual.into();
-                        let received_bytes = self.buffer.write_from(payload);
-                        let available_bytes = self.buffer.read_available();
-                        if actual_tp == *wanted {
-                            Ok(Some(ReloadInfo::new(
-                                received_bytes,
-                                available_bytes,
-                                skipped,
-                                self.last_know_timestamp,
-                            )))
-                        } else {
-                            Ok(Some(ReloadInfo::new(
-                                0,
-                                0,
-                                payload.len() + skipped,
-                                self.last_know_timestamp,
-                            )))
-                        }
-                    }
-                    _ => {
-                        let copied = self.buffer.write_from(payload);
-                        let available_bytes = self.buffer.read_available();
-                        Ok(Some(ReloadInfo::new(
-                            copied,
-                            available_bytes,
-                            skipped,
-                            self.last_know_timestamp,
-                        )))
-                    }
-                }
-            }
-            Err(e) => Err(SourceError::Unrecoverable(format!(
-                "error trying to extract data from ethernet frame: {e}"
-            ))),
-        };
-        // bytes are copied into buffer and can be dropped by pcap reader
-        trace!("consume {consumed} processed bytes");
-        self.pcap_reader.consume(consumed);
-        res
     }
 
     fn current_slice(&self) -> &[u8] {
@@ -153,6 +135,10 @@ impl<R: Read + Send + Sync> ByteSource for PcapLegacyByteSource<R> {
     fn len(&self) -> usize {
         self.buffer.read_available()
     }
+
+    fn current_flow(&self) -> Option<NetworkFlow> {
+        self.decoder.current_flow()
+    }
 }
 
 #[cfg(test)]
@@ -1,7 +1,252 @@
-use pcap_parser::PcapBlockOwned;
+use crate::{NetworkFlow, SourceFilter, TransportProtocol};
+use bufread::DeqBuffer;
+use etherparse::{EtherType, IpNumber, NetSlice, SlicedPacket, TcpSlice, TransportSlice, UdpSlice};
+use log::{debug, trace};
+use pcap_parser::{Linktype, PcapBlockOwned};
+use std::net::{IpAddr, Ipv4Addr, SocketAddr};
 
+use self::{
+    defrag::{Fragment, FragmentKey, Ipv4Defragmenter},
+    reassembly::{FlowKey, PacketStreams, TcpSegment, Written},
+};
+
+mod defrag;
 pub mod legacy;
 pub mod ng;
+mod reassembly;
+
+/// Offset of the protocol type in the header of Linux cooked captures (SLL).
+const SLL_PROTOCOL_OFFSET: usize = 14;
+const SLL_HEADER_LEN: usize = 16;
+/// Offset of the protocol type in the header of Linux cooked captures v2 (SLL2).
+const SLL2_PROTOCOL_OFFSET: usize = 0;
+const SLL2_HEADER_LEN: usize = 20;
+/// Length of the address family header of BSD loopback captures.
+const NULL_HEADER_LEN: usize = 4;
+
+/// Decodes captured frames and collects the transport payloads of the relevant packets in
+/// streams.
+#[derive(Debug, Default)]
+struct PacketDecoder {
+    streams: PacketStreams,
+    fragments: Ipv4Defragmenter,
+}
+
+impl PacketDecoder {
+    fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds the transport payload of a captured frame to its stream and returns the count of
+    /// skipped bytes (headers, filtered out or unsupported packets, retransmitted data and
+    /// gaps). The timestamp (ms) of the frame is delivered along with its payload.
+    fn push_frame(
+        &mut self,
+        link_type: Linktype,
+        raw_data: &[u8],
+        timestamp: Option<u64>,
+        filter: Option<&SourceFilter>,
+    ) -> usize {
+        let packet = match slice_frame(link_type, raw_data) {
+            Ok(Some(packet)) => packet,
+            Ok(None) => {
+                trace!("skip frame of unsupported link type {link_type:?}");
+                return raw_data.len();
+            }
+            Err(err) => {
+                debug!("skip frame which can't be decoded: {err}");
+                return raw_data.len();
+            }
+        };
+        let (source, destination) = ip_addrs(&packet.net);
+        if let Some(transport) = &packet.transport {
+            let headers = raw_data.len() - transport_payload(transport).len();
+            return headers
+                + self.push_transport(transport, source, destination, timestamp, filter);
+        }
+        let Some(NetSlice::Ipv4(ip)) = &packet.net else {
+            trace!("skip packet without transport payload");
+            return raw_data.len();
+        };
+        if !ip.is_payload_fragmented() {
+            trace!("skip packet without transport payload");
+            return raw_data.len();
+        }
+        let header = ip.header();
+        let payload = ip.payload().payload;
+        let key = FragmentKey {
+            source: header.source_addr(),
+            destination: header.destination_addr(),
+            protocol: header.protocol().0,
+            identification: header.identification(),
+        };
+        let fragment = Fragment {
+            offset: usize::from(header.fragments_offset().value()) * 8,
+            more_fragments: header.more_fragments(),
+            payload,
+        };
+        let headers = raw_data.len() - payload.len();
+        let (datagram, dropped) = self.fragments.push(key, fragment);
+        let Some(datagram) = datagram else {
+            return headers + dropped;
+        };
+        let transport = match header.protocol() {
+            IpNumber::TCP => TcpSlice::from_slice(&datagram)
+                .ok()
+                .map(TransportSlice::Tcp),
+            IpNumber::UDP => UdpSlice::from_slice(&datagram)
+                .ok()
+                .map(TransportSlice::Udp),
+            _ => None,
+        };
+        match transport {
+            Some(transport) => {
+                let transport_header = datagram.len() - transport_payload(&transport).len();
+                headers
+                    + dropped
+                    + transport_header
+                    + self.push_transport(&transport, source, destination, timestamp, filter)
+            }
+            None => headers + dropped + datagram.len(),
+        }
+    }
+
+    /// Adds the payload to its stream if it passes the filter and returns the count of
+    /// skipped payload bytes.
+    fn push_transport(
+        &mut self,
+        transport: &TransportSlice,
+        source: Option<IpAddr>,
+        destination: Option<IpAddr>,
+        timestamp: Option<u64>,
+        filter: Option<&SourceFilter>,
+    ) -> usize {
+        let payload = transport_payload(transport);
+        let (source_port, destination_port) = match transport {
+            TransportSlice::Tcp(tcp) => (Some(tcp.source_port()), Some(tcp.destination_port())),
+            TransportSlice::Udp(udp) => (Some(udp.source_port()), Some(udp.destination_port())),
+            _ => (None, None),
+        };
+        if let Some(filter) = filter {
+            let protocol: TransportProtocol = transport.clone().into();
+            if !filter.matches(
+                &protocol,
+                (source, source_port),
+                (destination, destination_port),
+            ) {
+                return payload.len();
+            }
+        }
+        match transport {
+            TransportSlice::Tcp(tcp) => {
+                let unspecified = IpAddr::from(Ipv4Addr::UNSPECIFIED);
+                let key = FlowKey {
+                    source: SocketAddr::new(source.unwrap_or(unspecified), tcp.source_port()),
+                    destination: SocketAddr::new(
+                        destination.unwrap_or(unspecified),
+                        tcp.destination_port(),
+                    ),
+                };
+                let segment = TcpSegment {
+                    sequence_number: tcp.sequence_number(),
+                    syn: tcp.syn(),
+                    closing: tcp.fin() || tcp.rst(),
+                    payload,
+                };
+                self.streams.push_tcp(key, segment, timestamp)
+            }
+            TransportSlice::Udp(udp) => {
+                let key = source
+                    .zip(destination)
+                    .map(|(source, destination)| FlowKey {
+                        source: SocketAddr::new(source, udp.source_port()),
+                        destination: SocketAddr::new(destination, udp.destination_port()),
+                    });
+                self.streams.push_datagram(key, payload, timestamp);
+                0
+            }
+            _ => {
+                self.streams.push_datagram(None, payload, timestamp);
+                0
+            }
+        }
+    }
+
+    /// Closes all streams at the end of the capture. Returns the count of skipped bytes.
+    fn finish(&mut self) -> usize {
+        self.streams.finish()
+    }
+
+    /// Writes the available data of a stream into the buffer (see
+    /// [`PacketStreams::write_into`]).
+    fn write_into(&mut self, buffer: &mut DeqBuffer) -> Written {
+        self.streams.write_into(buffer)
+    }
+
+    /// Returns the flow of the data written into the buffer.
+    fn current_flow(&self) -> Option<NetworkFlow> {
+        self.streams.current_flow()
+    }
+}
+
+/// Slices the frame according to the link type of its interface. Returns `None` for
+/// unsupported link types.
+fn slice_frame(link_type: Linktype, data: &[u8]) -> Result<Option<SlicedPacket<'_>>, String> {
+    let packet = match link_type {
+        // VLAN tags are handled while slicing ethernet frames.
+        Linktype::ETHERNET => SlicedPacket::from_ethernet(data),
+        Linktype::RAW | Linktype::IPV4 | Linktype::IPV6 => SlicedPacket::from_ip(data),
+        Linktype::NULL | Linktype::LOOP => match data.get(NULL_HEADER_LEN..) {
+            Some(data) => SlicedPacket::from_ip(data),
+            None => return Ok(None),
+        },
+        Linktype::LINUX_SLL => return slice_cooked(data, SLL_PROTOCOL_OFFSET, SLL_HEADER_LEN),
+        Linktype::LINUX_SLL2 => return slice_cooked(data, SLL2_PROTOCOL_OFFSET, SLL2_HEADER_LEN),
+        _ => return Ok(None),
+    };
+    packet.map(Some).map_err(|e| e.to_string())
+}
+
+/// Slices a frame of a Linux cooked capture by the protocol type in its header.
+fn slice_cooked(
+    data: &[u8],
+    protocol_offset: usize,
+    header_len: usize,
+) -> Result<Option<SlicedPacket<'_>>, String> {
+    let (Some(protocol), Some(payload)) = (
+        data.get(protocol_offset..protocol_offset + 2),
+        data.get(header_len..),
+    ) else {
+        return Err(format!("cooked capture header is truncated: {data:02X?}"));
+    };
+    let ether_type = EtherType(u16::from_be_bytes([protocol[0], protocol[1]]));
+    SlicedPacket::from_ether_type(ether_type, payload)
+        .map(Some)
+        .map_err(|e| e.to_string())
+}
+
+fn ip_addrs(net: &Option<NetSlice>) -> (Option<IpAddr>, Option<IpAddr>) {
+    match net {
+        Some(NetSlice::Ipv4(ip)) => (
+            Some(ip.header().source_addr().into()),
+            Some(ip.header().destination_addr().into()),
+        ),
+        Some(NetSlice::Ipv6(ip)) => (
+            Some(ip.header().source_addr().into()),
+            Some(ip.header().destination_addr().into()),
+        ),
+        _ => (None, None),
+    }
+}
+
+fn transport_payload<'a>(transport: &TransportSlice<'a>) -> &'a [u8] {
+    match transport {
+        TransportSlice::Icmpv4(slice) => slice.payload(),
+        TransportSlice::Icmpv6(slice) => slice.payload(),
+        TransportSlice::Udp(slice) => slice.payload(),
+        TransportSlice::Tcp(slice) => slice.payload(),
+    }
+}
 
 fn debug_block(b: PcapBlockOwned) {
     match b {
@@ -41,3 +286,48 @@ fn debug_block(b: PcapBlockOwned) {
         _ => trace!("unknown block"),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SLL2_UDP_FRAME: &[u8] = &[
+        // Linux cooked capture v2 header
+        0x08, 0x00, // protocol type: IPv4
+        0x00, 0x00, // reserved
+        0x00, 0x00, 0x00, 0x02, // interface index
+        0x00, 0x01, // ARPHRD type
+        0x00, // packet type
+        0x06, // address length
+        0xb8, 0x27, 0xeb, 0x1d, 0x24, 0xc9, 0x00, 0x00, // address
+        // IPv4 header
+        0x45, 0x00, 0x00, 0x20, 0x00, 0x01, 0x00, 0x00, 0x40, 0x11, 0x00, 0x00, //
+        0x0a, 0x00, 0x00, 0x01, // source
+        0x0a, 0x00, 0x00, 0x02, // destination
+        // UDP header
+        0x0d, 0x80, 0x0d, 0x81, 0x00, 0x0c, 0x00, 0x00, //
+        // UDP payload
+        0x01, 0x02, 0x03, 0x04,
+    ];
+
+    #[test]
+    fn filter_flows_of_linux_cooked_capture() {
+        let mut decoder = PacketDecoder::new();
+        let mut buffer = DeqBuffer::new(1024);
+        let filter = SourceFilter::new()
+            .with_transport(TransportProtocol::UDP)
+            .with_destination_port(3457);
+        assert_eq!(
+            decoder.push_frame(Linktype::LINUX_SLL2, SLL2_UDP_FRAME, None, Some(&filter)),
+            SLL2_UDP_FRAME.len() - 4
+        );
+        assert_eq!(decoder.write_into(&mut buffer).bytes, 4);
+        assert_eq!(buffer.read_slice(), &[0x01, 0x02, 0x03, 0x04]);
+
+        let filter = SourceFilter::new().with_source_ip(IpAddr::from([10, 0, 0, 2]));
+        assert_eq!(
+            decoder.push_frame(Linktype::LINUX_SLL2, SLL2_UDP_FRAME, None, Some(&filter)),
+            SLL2_UDP_FRAME.len()
+        );
+    }
+}
@@ -1,17 +1,18 @@
 use crate::{
-    ByteSource, Error as SourceError, ReloadInfo, SourceFilter, TransportProtocol,
-    binary::pcap::debug_block,
+    ByteSource, Error as SourceError, NetworkFlow, ReloadInfo, SourceFilter,
+    binary::pcap::{PacketDecoder, debug_block},
 };
 use bufread::DeqBuffer;
-use etherparse::{SlicedPacket, TransportSlice};
 use log::{debug, error, trace};
-use pcap_parser::{PcapBlockOwned, PcapError, PcapNGReader, traits::PcapReaderIterator};
+use pcap_parser::{Linktype, PcapBlockOwned, PcapError, PcapNGReader, traits::PcapReaderIterator};
 use std::io::Read;
 
 pub struct PcapngByteSource<R: Read> {
     pcapng_reader: PcapNGReader<R>,
     buffer: DeqBuffer,
-    last_know_timestamp: Option<u64>,
+    decoder: PacketDecoder,
+    /// Link types of the interfaces of the current section.
+    interfaces: Vec<Linktype>,
     total: usize,
 }
 
@@ -21,21 +22,39 @@ impl<R: Read> PcapngByteSource<R> {
             pcapng_reader: PcapNGReader::new(65536, reader)
                 .map_err(|e| SourceError::Setup(format!("{e}")))?,
             buffer: DeqBuffer::new(8192),
-            last_know_timestamp: None,
+            decoder: PacketDecoder::new(),
+            interfaces: Vec::new(),
             total: 0,
         })
     }
 }
 
+/// Link type of the interface, ethernet if the interface isn't described.
+fn interface_link_type(interfaces: &[Linktype], interface: usize) -> Linktype {
+    interfaces
+        .get(interface)
+        .copied()
+        .unwrap_or(Linktype::ETHERNET)
+}
+
 impl<R: Read + Send + Sync> ByteSource for PcapngByteSource<R> {
     async fn load(
         &mut self,
         filter: Option<&SourceFilter>,
     ) -> Result<Option<ReloadInfo>, SourceError> {
-        let raw_data;
-        let mut consumed;
         let mut skipped = 0usize;
         loop {
+            // Reassembled data of previous packets is written before reading new packets.
+            let written = self.decoder.write_into(&mut self.buffer);
+            skipped += written.discarded;
+            if written.bytes > 0 {
+                return Ok(Some(ReloadInfo::new(
+                    written.bytes,
+                    self.buffer.read_available(),
+                    skipped,
+                    written.timestamp,
+                )));
+            }
             match self.pcapng_reader.next() {
                 Ok((bytes_read, block)) => {
                     self.total += bytes_read;
@@ -43,31 +62,65 @@ impl<R: Read + Send + Sync> ByteSource for PcapngByteSource<R> {
                         "PcapngByteSource::reload, bytes_read: {bytes_read} (total: {})",
                         self.total
                     );
-                    consumed = bytes_read;
+                    let consumed = bytes_read;
                     match block {
                         PcapBlockOwned::NG(pcap_parser::Block::EnhancedPacket(ref epb)) => {
                             trace!("Enhanced package");
                             let ts_us: u64 = ((epb.ts_high as u64) << 32) | epb.ts_low as u64;
-                            self.last_know_timestamp = Some(ts_us / 1000);
-                            raw_data = &epb.data[..epb.caplen as usize];
-                            break;
+                            let raw_data = &epb.data[..epb.caplen as usize];
+                            skipped += consumed - raw_data.len();
+                            let link_type =
+                                interface_link_type(&self.interfaces, epb.if_id as usize);
+                            skipped += self.decoder.push_frame(
+                                link_type,
+                                raw_data,
+                                Some(ts_us / 1000),
+                                filter,
+                            );
                         }
                         PcapBlockOwned::NG(pcap_parser::Block::SimplePacket(ref spb)) => {
                             trace!("SimplePacket");
-                            raw_data = &spb.data[..spb.origlen as usize];
-                            break;
+                            let raw_data = &spb.data[..spb.origlen as usize];
+                            skipped += consumed - raw_data.len();
+                            // Simple packets are captured on the first interface and have
+                            // no timestamp.
+                            let link_type = interface_link_type(&self.interfaces, 0);
+                            skipped += self.decoder.push_frame(link_type, raw_data, None, filter);
+                        }
+                        PcapBlockOwned::NG(pcap_parser::Block::SectionHeader(_)) => {
+                            trace!("NG SectionHeader");
+                            self.interfaces.clear();
+                            skipped += consumed;
+                        }
+                        PcapBlockOwned::NG(pcap_parser::Block::InterfaceDescription(ref idb)) => {
+                            trace!("NG InterfaceDescription: {:?}", idb.linktype);
+                            self.interfaces.push(idb.linktype);
+                            skipped += consumed;
                         }
                         other_type => {
                             debug_block(other_type);
                             skipped += consumed;
                             debug!("skipped in total {skipped} bytes");
-                            self.pcapng_reader.consume(consumed);
-                            continue;
                         }
                     }
+                    // bytes are copied into the streams and can be dropped by pcap reader
+                    trace!("consume {consumed} processed bytes");
+                    self.pcapng_reader.consume(consumed);
                 }
                 Err(PcapError::Eof) => {
                     debug!("reloading from pcap file, EOF");
+                    // Incomplete streams are delivered with their gaps skipped.
+                    skipped += self.decoder.finish();
+                    let written = self.decoder.write_into(&mut self.buffer);
+                    skipped += written.discarded;
+                    if written.bytes > 0 {
+                        return Ok(Some(ReloadInfo::new(
+                            written.bytes,
+                            self.buffer.read_available(),
+                            skipped,
+                            written.timestamp,
+                        )));
+                    }
                     return Ok(None);
                 }
                 Err(PcapError::Incomplete(size)) => {
@@ -84,66 +137,6 @@ impl<R: Read + Send + Sync> ByteSource for PcapngByteSource<R> {
                 }
             }
         }
-        let res = match SlicedPacket::from_ethernet(raw_data) {
-            Ok(value) => {
-                let payload = match &value.transport {
-                    Some(TransportSlice::Icmpv4(slice)) => slice.payload(),
-                    Some(TransportSlice::Icmpv6(slice)) => slice.payload(),
-                    Some(TransportSlice::Udp(slice)) => slice.payload(),
-                    Some(TransportSlice::Tcp(slice)) => slice.payload(),
-                    None => {
-                        return Err(SourceError::Unrecoverable(format!(
-                            "ethernet frame with unknown payload: {raw_data:02X?}"
-                        )));
-                    }
-                };
-                skipped += consumed - payload.len();
-                match (value.transport, filter) {
-                    (
-                        Some(actual),
-                        Some(SourceFilter {
-                            transport: Some(wanted),
-                        }),
-                    ) => {
-                        let actual_tp: TransportProtocol = actual.into();
-                        let received_bytes = self.buffer.write_from(payload);
-                        let available_bytes = self.buffer.read_available();
-                        if actual_tp == *wanted {
-                            Ok(Some(ReloadInfo::new(
-                                received_bytes,
-                                available_bytes,
-                                skipped,
-                                self.last_know_timestamp,
-                            )))
-                        } else {
-                            Ok(Some(ReloadInfo::new(
-                                0,
-                                0,
-                                payload.len() + skipped,
-                                self.last_know_timestamp,
-                            )))
-                        }
-                    }
-                    _ => {
-                        let copied = self.buffer.write_from(payload);
-                        let available_bytes = self.buffer.read_available();
-                        Ok(Some(ReloadInfo::new(
-                            copied,
-                            available_bytes,
-                            skipped,
-                            self.last_know_timestamp,
-                        )))
-                    }
-                }
-            }
-            Err(e) => Err(SourceError::Unrecoverable(format!(
-                "error trying to extract data from ethernet frame: {e}"
-            ))),
-        };
-        // bytes are copied into buffer and can be dropped by pcap reader
-        trace!("consume {consumed} processed bytes");
-        self.pcapng_reader.consume(consumed);
-        res
     }
 
     fn current_slice(&self) -> &[u8] {
@@ -157,6 +150,10 @@ impl<R: Read + Send + Sync> ByteSource for PcapngByteSource<R> {
     fn len(&self) -> usize {
         self.buffer.read_available()
     }
+
+    fn current_flow(&self) -> Option<NetworkFlow> {
+        self.decoder.current_flow()
+    }
 }
 
 #[cfg(test)]
@@ -0,0 +1,575 @@
+//! # Reassembly of TCP streams
+//!
+//! The payloads of captured packets can't be written into the buffer of a source as they
+//! are, since TCP segments may be retransmitted, arrive out of order or be interleaved with
+//! the segments of other connections. [`PacketStreams`] reassembles each TCP connection into
+//! a contiguous byte stream and feeds the buffer with the data of one stream at a time, so
+//! messages split over multiple segments reach the parser in one piece.
+//!
+//! The payloads of other transport protocols (e.g. UDP) contain complete messages and are
+//! collected per flow. Keeping the flows apart allows to tell the flow of the written data
+//! (see [`PacketStreams::current_flow`]).
+//!
+//! The data is delivered in the order of the packets it was captured with, along with the
+//! timestamps of these packets. The data of another stream is written only after the
+//! buffer was consumed completely. Remaining bytes which can't be continued anymore are
+//! discarded instead of being mixed with the data of the next stream.
+use crate::{NetworkFlow, TransportProtocol};
+use bufread::DeqBuffer;
+use log::{debug, trace};
+use std::{
+    collections::{HashMap, VecDeque},
+    net::SocketAddr,
+};
+
+/// Maximum amount of out of order data held back per connection while waiting for missing
+/// segments. If it's exceeded, the missing data is skipped.
+const MAX_OUT_OF_ORDER_BYTES: usize = 1024 * 1024;
+/// Maximum amount of reassembled data of other streams held back while waiting for the rest
+/// of a message in the current stream. If it's exceeded, the incomplete message is discarded
+/// and the source switches to the next stream.
+const MAX_PENDING_BYTES: usize = 4 * 1024 * 1024;
+
+/// Direction of a TCP connection or of a UDP flow.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct FlowKey {
+    pub source: SocketAddr,
+    pub destination: SocketAddr,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum StreamId {
+    /// Datagrams of a UDP flow or, without a key, of other protocols.
+    Datagrams(Option<FlowKey>),
+    Tcp(FlowKey),
+}
+
+impl StreamId {
+    fn flow(&self) -> Option<NetworkFlow> {
+        let (transport, key) = match self {
+            StreamId::Datagrams(key) => (TransportProtocol::UDP, (*key)?),
+            StreamId::Tcp(key) => (TransportProtocol::TCP, *key),
+        };
+        Some(NetworkFlow {
+            transport,
+            source: key.source,
+            destination: key.destination,
+        })
+    }
+}
+
+/// A TCP segment of a captured packet.
+#[derive(Debug)]
+pub struct TcpSegment<'a> {
+    pub sequence_number: u32,
+    pub syn: bool,
+    /// Indicates that the connection is closed (FIN or RST flag).
+    pub closing: bool,
+    pub payload: &'a [u8],
+}
+
+#[derive(Debug, Default)]
+struct TcpFlow {
+    /// Sequence number of the next expected byte.
+    next_seq: Option<u32>,
+    /// Segments received ahead of the next expected byte.
+    out_of_order: Vec<(u32, Vec<u8>)>,
+    out_of_order_bytes: usize,
+}
+
+impl TcpFlow {
+    /// Appends the in-order data of the segment to `ready` and returns the count of skipped
+    /// bytes (retransmitted data and gaps).
+    fn push(&mut self, segment: &TcpSegment, ready: &mut Vec<u8>) -> usize {
+        let mut seq = segment.sequence_number;
+        if segment.syn {
+            // The SYN flag occupies one sequence number.
+            seq = seq.wrapping_add(1);
+            self.next_seq = Some(seq);
+        }
+        if segment.payload.is_empty() {
+            return 0;
+        }
+        let next_seq = *self.next_seq.get_or_insert(seq);
+        let mut skipped = 0;
+        match distance(next_seq, seq) {
+            ..=0 => {
+                // Retransmitted data, possibly overlapping with new data.
+                let overlap = next_seq.wrapping_sub(seq) as usize;
+                if overlap >= segment.payload.len() {
+                    trace!(
+                        "drop retransmitted segment ({} bytes)",
+                        segment.payload.len()
+                    );
+                    return segment.payload.len();
+                }
+                skipped += overlap;
+                self.append(&segment.payload[overlap..], ready);
+            }
+            _ => {
+                trace!("hold back out of order segment (seq: {seq})");
+                self.out_of_order_bytes += segment.payload.len();
+                self.out_of_order.push((seq, segment.payload.to_vec()));
+            }
+        }
+        skipped += self.drain_out_of_order(ready);
+        while self.out_of_order_bytes > MAX_OUT_OF_ORDER_BYTES {
+            skipped += self.skip_gap(ready);
+        }
+        skipped
+    }
+
+    fn append(&mut self, data: &[u8], ready: &mut Vec<u8>) {
+        ready.extend_from_slice(data);
+        self.next_seq = self
+            .next_seq
+            .map(|next_seq| next_seq.wrapping_add(data.len() as u32));
+    }
+
+    /// Appends all held back segments which continue the stream.
+    fn drain_out_of_order(&mut self, ready: &mut Vec<u8>) -> usize {
+        let mut skipped = 0;
+        while let Some(next_seq) = self.next_seq {
+            let Some(pos) = self
+                .out_of_order
+                .iter()
+                .position(|(seq, _)| distance(next_seq, *seq) <= 0)
+            else {
+                break;
+            };
+            let (seq, data) = self.out_of_order.swap_remove(pos);
+            self.out_of_order_bytes -= data.len();
+            let overlap = next_seq.wrapping_sub(seq) as usize;
+            if overlap >= data.len() {
+                skipped += data.len();
+            } else {
+                skipped += overlap;
+                self.append(&data[overlap..], ready);
+            }
+        }
+        skipped
+    }
+
+    /// Skips the missing data in front of the earliest held back segment and returns the
+    /// count of skipped bytes including the bytes of the gap.
+    fn skip_gap(&mut self, ready: &mut Vec<u8>) -> usize {
+        let Some(next_seq) = self.next_seq else {
+            return 0;
+        };
+        let Some(earliest) = self
+            .out_of_order
+            .iter()
+            .map(|(seq, _)| *seq)
+            .min_by_key(|seq| distance(next_seq, *seq))
+        else {
+            return 0;
+        };
+        let gap = earliest.wrapping_sub(next_seq) as usize;
+        debug!("skip gap of {gap} bytes in TCP stream");
+        self.next_seq = Some(earliest);
+        gap + self.drain_out_of_order(ready)
+    }
+
+    /// Skips all gaps, appending the held back segments.
+    fn flush(&mut self, ready: &mut Vec<u8>) -> usize {
+        let mut skipped = 0;
+        while !self.out_of_order.is_empty() {
+            skipped += self.skip_gap(ready);
+        }
+        skipped
+    }
+}
+
+/// Distance from `from` to `to` in the sequence number space, considering wrap arounds.
+fn distance(from: u32, to: u32) -> i32 {
+    to.wrapping_sub(from) as i32
+}
+
+/// Data of a captured packet ready to be written into the buffer of the source.
+#[derive(Debug)]
+struct Chunk {
+    /// Number of the packet the data was captured with.
+    packet: u64,
+    /// Timestamp of the packet in milliseconds.
+    timestamp: Option<u64>,
+    data: Vec<u8>,
+}
+
+#[derive(Debug, Default)]
+struct Stream {
+    /// Data ready to be written into the buffer of the source, in order of the packets.
+    ready: VecDeque<Chunk>,
+    tcp: Option<TcpFlow>,
+    closed: bool,
+}
+
+impl Stream {
+    fn push(&mut self, packet: u64, timestamp: Option<u64>, data: Vec<u8>) {
+        if !data.is_empty() {
+            self.ready.push_back(Chunk {
+                packet,
+                timestamp,
+                data,
+            });
+        }
+    }
+
+    /// Number of the packet of the oldest ready data.
+    fn next_packet(&self) -> Option<u64> {
+        self.ready.front().map(|chunk| chunk.packet)
+    }
+
+    fn pending(&self) -> usize {
+        self.ready.iter().map(|chunk| chunk.data.len()).sum()
+    }
+
+    /// Indicates whether the written data can be continued by data which isn't ready yet.
+    /// Only open TCP connections continue their data, datagrams contain complete messages.
+    fn continues(&self) -> bool {
+        self.tcp.is_some() && !self.closed
+    }
+}
+
+/// Result of writing the data of a stream into the buffer.
+#[derive(Debug, Default, PartialEq)]
+pub struct Written {
+    /// Count of written bytes.
+    pub bytes: usize,
+    /// Count of remaining bytes of the previous stream, which were discarded from the buffer
+    /// because they can't be continued anymore.
+    pub discarded: usize,
+    /// Timestamp (ms) of the latest packet of the written data.
+    pub timestamp: Option<u64>,
+}
+
+/// Reassembles the streams of captured packets and feeds the data of one stream at a time
+/// into the buffer of a source.
+#[derive(Debug, Default)]
+pub struct PacketStreams {
+    streams: HashMap<StreamId, Stream>,
+    current: Option<StreamId>,
+    /// Count of received packets, used to deliver the data in order of the packets.
+    packets: u64,
+}
+
+impl PacketStreams {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds the payload of a packet which isn't part of a TCP connection. The key identifies
+    /// the flow of UDP datagrams.
+    pub fn push_datagram(&mut self, key: Option<FlowKey>, payload: &[u8], timestamp: Option<u64>) {
+        self.packets += 1;
+        self.streams
+            .entry(StreamId::Datagrams(key))
+            .or_default()
+            .push(self.packets, timestamp, payload.to_vec());
+    }
+
+    /// Adds a TCP segment to the stream of its connection and returns the count of skipped
+    /// bytes (retransmitted data and gaps).
+    pub fn push_tcp(&mut self, key: FlowKey, segment: TcpSegment, timestamp: Option<u64>) -> usize {
+        self.packets += 1;
+        let stream = self.streams.entry(StreamId::Tcp(key)).or_default();
+        if segment.syn && stream.closed {
+            trace!("new TCP connection {key:?}");
+            stream.tcp = None;
+            stream.closed = false;
+        }
+        let mut data = Vec::new();
+        let skipped = stream
+            .tcp
+            .get_or_insert_with(TcpFlow::default)
+            .push(&segment, &mut data);
+        stream.push(self.packets, timestamp, data);
+        if segment.closing {
+            stream.closed = true;
+        }
+        skipped
+    }
+
+    /// Closes all streams at the end of the capture, skipping the gaps of incomplete TCP
+    /// streams. Returns the count of skipped bytes.
+    pub fn finish(&mut self) -> usize {
+        let mut skipped = 0;
+        for stream in self.streams.values_mut() {
+            if let Some(tcp) = stream.tcp.as_mut() {
+                let mut data = Vec::new();
+                skipped += tcp.flush(&mut data);
+                stream.push(self.packets, None, data);
+            }
+            stream.closed = true;
+        }
+        skipped
+    }
+
+    /// Writes the available data of a stream into the buffer.
+    ///
+    /// The data is written in the order of the packets. Remaining bytes in the buffer belong
+    /// to the current stream: an open TCP connection continues them, so its data is written
+    /// before the data of other streams. Otherwise the remaining bytes are discarded before
+    /// the data of another stream is written.
+    pub fn write_into(&mut self, buffer: &mut DeqBuffer) -> Written {
+        let mut written = Written::default();
+        let Some(id) = self.next_stream(buffer) else {
+            return written;
+        };
+        if self.current != Some(id) {
+            written.discarded = buffer.read_available();
+            if written.discarded > 0 {
+                debug!(
+                    "discard {} bytes of incomplete data of the previous stream",
+                    written.discarded
+                );
+                buffer.read_done(written.discarded);
+            }
+            self.current = Some(id);
+        }
+        // Data of other streams captured before the next data of the current stream.
+        let others = self
+            .streams
+            .iter()
+            .filter(|(other, _)| **other != id)
+            .filter_map(|(_, stream)| stream.next_packet())
+            .min();
+        let Some(stream) = self.streams.get_mut(&id) else {
+            return written;
+        };
+        while let Some(chunk) = stream.ready.front_mut() {
+            if written.bytes > 0 && others.is_some_and(|others| others < chunk.packet) {
+                break;
+            }
+            if buffer.write_available() < chunk.data.len() {
+                buffer.flush();
+            }
+            let bytes = buffer.write_from(&chunk.data);
+            if bytes > 0 {
+                written.bytes += bytes;
+                written.timestamp = chunk.timestamp.or(written.timestamp);
+            }
+            if bytes < chunk.data.len() {
+                chunk.data.drain(..bytes);
+                break;
+            }
+            stream.ready.pop_front();
+        }
+        if stream.ready.is_empty() && stream.closed && stream.tcp.is_some() {
+            // Connection is done, forget its state.
+            self.streams.remove(&id);
+        }
+        written
+    }
+
+    /// Returns the flow of the current stream, which is the flow of the data in the buffer.
+    /// It's `None` for datagrams of protocols other than UDP.
+    pub fn current_flow(&self) -> Option<NetworkFlow> {
+        self.current.as_ref().and_then(StreamId::flow)
+    }
+
+    /// Returns the stream to write data from: the current stream if it continues the
+    /// remaining bytes in the buffer, otherwise the stream with the oldest data.
+    fn next_stream(&self, buffer: &DeqBuffer) -> Option<StreamId> {
+        let oldest = self
+            .streams
+            .iter()
+            .filter_map(|(id, stream)| stream.next_packet().map(|packet| (packet, *id)))
+            .min_by_key(|(packet, _)| *packet)
+            .map(|(_, id)| id);
+        if buffer.read_available() == 0 {
+            return oldest;
+        }
+        let Some(current) = self.current.and_then(|id| self.streams.get(&id)) else {
+            return oldest;
+        };
+        if !current.continues() {
+            return oldest;
+        }
+        if !current.ready.is_empty() {
+            return self.current;
+        }
+        let pending: usize = self.streams.values().map(Stream::pending).sum();
+        if pending > MAX_PENDING_BYTES {
+            debug!("switch stream with incomplete data ({pending} bytes pending)");
+            return oldest;
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(port: u16) -> FlowKey {
+        FlowKey {
+            source: SocketAddr::from(([10, 0, 0, 1], port)),
+            destination: SocketAddr::from(([10, 0, 0, 2], 3490)),
+        }
+    }
+
+    fn segment(sequence_number: u32, payload: &[u8]) -> TcpSegment<'_> {
+        TcpSegment {
+            sequence_number,
+            syn: false,
+            closing: false,
+            payload,
+        }
+    }
+
+    fn read_all(streams: &mut PacketStreams, buffer: &mut DeqBuffer) -> Vec<u8> {
+        let mut data = Vec::new();
+        while streams.write_into(buffer).bytes > 0 {
+            data.extend_from_slice(buffer.read_slice());
+            let len = buffer.read_available();
+            buffer.read_done(len);
+        }
+        data
+    }
+
+    #[test]
+    fn reorder_and_drop_retransmissions() {
+        let mut streams = PacketStreams::new();
+        let mut buffer = DeqBuffer::new(1024);
+        assert_eq!(streams.push_tcp(key(1), segment(100, b"abc"), None), 0);
+        assert_eq!(streams.push_tcp(key(1), segment(106, b"ghi"), None), 0);
+        assert_eq!(streams.push_tcp(key(1), segment(103, b"def"), None), 0);
+        // Full and partial retransmissions.
+        assert_eq!(streams.push_tcp(key(1), segment(103, b"def"), None), 3);
+        assert_eq!(streams.push_tcp(key(1), segment(107, b"hijk"), None), 2);
+        assert_eq!(read_all(&mut streams, &mut buffer), b"abcdefghijk");
+    }
+
+    #[test]
+    fn wrapping_sequence_numbers() {
+        let mut streams = PacketStreams::new();
+        let mut buffer = DeqBuffer::new(1024);
+        streams.push_tcp(key(1), segment(u32::MAX - 1, b"ab"), None);
+        streams.push_tcp(key(1), segment(2, b"ef"), None);
+        streams.push_tcp(key(1), segment(0, b"cd"), None);
+        assert_eq!(read_all(&mut streams, &mut buffer), b"abcdef");
+    }
+
+    #[test]
+    fn skip_gaps_at_the_end() {
+        let mut streams = PacketStreams::new();
+        let mut buffer = DeqBuffer::new(1024);
+        streams.push_tcp(key(1), segment(0, b"ab"), None);
+        streams.push_tcp(key(1), segment(5, b"fg"), None);
+        assert_eq!(read_all(&mut streams, &mut buffer), b"ab");
+        assert_eq!(streams.finish(), 3);
+        assert_eq!(read_all(&mut streams, &mut buffer), b"fg");
+    }
+
+    #[test]
+    fn keep_stream_until_buffer_is_consumed() {
+        let mut streams = PacketStreams::new();
+        let mut buffer = DeqBuffer::new(1024);
+        streams.push_tcp(key(1), segment(0, b"A1"), None);
+        assert_eq!(streams.write_into(&mut buffer).bytes, 2);
+        // The other connection has to wait for the rest of the current message.
+        streams.push_tcp(key(2), segment(0, b"B1B2"), None);
+        streams.push_datagram(Some(key(3)), b"U1", None);
+        assert_eq!(streams.write_into(&mut buffer), Written::default());
+        streams.push_tcp(key(1), segment(2, b"A2"), None);
+        assert_eq!(streams.write_into(&mut buffer).bytes, 2);
+        assert_eq!(buffer.read_slice(), b"A1A2");
+        buffer.read_done(4);
+        assert_eq!(read_all(&mut streams, &mut buffer), b"B1B2U1");
+    }
+
+    #[test]
+    fn discard_incomplete_data_of_closed_connection() {
+        let mut streams = PacketStreams::new();
+        let mut buffer = DeqBuffer::new(1024);
+        streams.push_tcp(key(1), segment(0, b"A1"), None);
+        assert_eq!(streams.write_into(&mut buffer).bytes, 2);
+        streams.push_tcp(key(2), segment(0, b"B1"), None);
+        streams.push_tcp(
+            key(1),
+            TcpSegment {
+                sequence_number: 2,
+                syn: false,
+                closing: true,
+                payload: &[],
+            },
+            None,
+        );
+        // The rest of the message can't arrive anymore.
+        let written = streams.write_into(&mut buffer);
+        assert_eq!((written.bytes, written.discarded), (2, 2));
+        assert_eq!(buffer.read_slice(), b"B1");
+        assert_eq!(
+            streams.current_flow().map(|flow| flow.source.port()),
+            Some(2)
+        );
+    }
+
+    #[test]
+    fn syn_sets_initial_sequence_number() {
+        let mut streams = PacketStreams::new();
+        let mut buffer = DeqBuffer::new(1024);
+        streams.push_tcp(
+            key(1),
+            TcpSegment {
+                sequence_number: 1000,
+                syn: true,
+                closing: false,
+                payload: &[],
+            },
+            None,
+        );
+        streams.push_tcp(key(1), segment(1003, b"cd"), None);
+        streams.push_tcp(key(1), segment(1001, b"ab"), None);
+        assert_eq!(read_all(&mut streams, &mut buffer), b"abcd");
+    }
+
+    #[test]
+    fn deliver_datagrams_in_capture_order() {
+        let mut streams = PacketStreams::new();
+        let mut buffer = DeqBuffer::new(1024);
+        streams.push_datagram(Some(key(1)), b"U1", Some(1));
+        streams.push_datagram(Some(key(2)), b"U2", Some(2));
+        streams.push_datagram(Some(key(1)), b"U3", Some(3));
+        assert_eq!(
+            streams.write_into(&mut buffer),
+            Written {
+                bytes: 2,
+                discarded: 0,
+                timestamp: Some(1),
+            }
+        );
+        assert_eq!(buffer.read_slice(), b"U1");
+        assert_eq!(
+            streams.current_flow().map(|flow| flow.to_string()),
+            Some(String::from("10.0.0.1:1 -> 10.0.0.2:3490 (UDP)"))
+        );
+        buffer.read_done(2);
+        assert_eq!(streams.write_into(&mut buffer).timestamp, Some(2));
+        assert_eq!(buffer.read_slice(), b"U2");
+        assert_eq!(
+            streams.current_flow().map(|flow| flow.source.port()),
+            Some(2)
+        );
+        // Remaining bytes of a datagram are discarded instead of being attributed to the
+        // next flow.
+        buffer.read_done(1);
+        assert_eq!(
+            streams.write_into(&mut buffer),
+            Written {
+                bytes: 2,
+                discarded: 1,
+                timestamp: Some(3),
+            }
+        );
+        assert_eq!(buffer.read_slice(), b"U3");
+        assert_eq!(
+            streams.current_flow().map(|flow| flow.source.port()),
+            Some(1)
+        );
+        streams.push_datagram(None, b"I1", None);
+        buffer.read_done(2);
+        assert_eq!(streams.write_into(&mut buffer).bytes, 2);
+        assert_eq!(streams.current_flow(), None);
+    }
+}
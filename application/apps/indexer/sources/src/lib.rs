@@ -1,6 +1,10 @@
 // Rust can't currently distinguish between dev and none-dev dependencies at the moment. There is
 // an open issue for this case: "https://github.com/rust-lang/rust/issues/129637"
 
+use std::{
+    fmt,
+    net::{IpAddr, SocketAddr},
+};
 use thiserror::Error;
 
 #[macro_use]
@@ -19,7 +23,7 @@ pub mod sde;
 pub mod serial;
 pub mod socket;
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum TransportProtocol {
     TCP,
     UDP,
@@ -36,9 +40,85 @@ impl From<etherparse::TransportSlice<'_>> for TransportProtocol {
     }
 }
 
-#[derive(Debug)]
+/// A flow of packets in a network trace, identified by its endpoints and transport protocol.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct NetworkFlow {
+    pub transport: TransportProtocol,
+    pub source: SocketAddr,
+    pub destination: SocketAddr,
+}
+
+impl fmt::Display for NetworkFlow {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let transport = match self.transport {
+            TransportProtocol::TCP => "TCP",
+            TransportProtocol::UDP => "UDP",
+            TransportProtocol::Unknown => "Unknown",
+        };
+        write!(f, "{} -> {} ({transport})", self.source, self.destination)
+    }
+}
+
+/// Filter applied by sources on network traces to pick the relevant packets. Each defined
+/// criterion has to match, undefined criteria match any packet.
+#[derive(Debug, Default, Clone)]
 pub struct SourceFilter {
     transport: Option<TransportProtocol>,
+    source_ip: Option<IpAddr>,
+    destination_ip: Option<IpAddr>,
+    source_port: Option<u16>,
+    destination_port: Option<u16>,
+}
+
+impl SourceFilter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_transport(mut self, transport: TransportProtocol) -> Self {
+        self.transport = Some(transport);
+        self
+    }
+
+    pub fn with_source_ip(mut self, ip: IpAddr) -> Self {
+        self.source_ip = Some(ip);
+        self
+    }
+
+    pub fn with_destination_ip(mut self, ip: IpAddr) -> Self {
+        self.destination_ip = Some(ip);
+        self
+    }
+
+    pub fn with_source_port(mut self, port: u16) -> Self {
+        self.source_port = Some(port);
+        self
+    }
+
+    pub fn with_destination_port(mut self, port: u16) -> Self {
+        self.destination_port = Some(port);
+        self
+    }
+
+    /// Checks whether a packet passes the filter. Addresses and ports are `None` for packets
+    /// without them (e.g. ports of ICMP packets), which don't match a filter on them.
+    pub fn matches(
+        &self,
+        transport: &TransportProtocol,
+        source: (Option<IpAddr>, Option<u16>),
+        destination: (Option<IpAddr>, Option<u16>),
+    ) -> bool {
+        fn matches<T: PartialEq>(wanted: &Option<T>, actual: &Option<T>) -> bool {
+            wanted.is_none() || wanted == actual
+        }
+        self.transport
+            .as_ref()
+            .is_none_or(|wanted| wanted == transport)
+            && matches(&self.source_ip, &source.0)
+            && matches(&self.source_port, &source.1)
+            && matches(&self.destination_ip, &destination.0)
+            && matches(&self.destination_port, &destination.1)
+    }
 }
 
 #[derive(Debug)]
@@ -133,6 +213,12 @@ pub trait ByteSource: Send {
     /// This function must be **Cancel-Safe**
     async fn load(&mut self, filter: Option<&SourceFilter>) -> Result<Option<ReloadInfo>, Error>;
 
+    /// Sources delivering the data of multiple network flows (e.g. network traces) return
+    /// the flow the currently loaded data belongs to. Other sources return `None`.
+    fn current_flow(&self) -> Option<NetworkFlow> {
+        None
+    }
+
     /// In case the ByteSource is some kind of connection that does not end,
     /// cancel can be implemented that will give the ByteSource the chance to perform some
     /// cleanup before the ByteSource is discarded
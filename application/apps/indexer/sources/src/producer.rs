@@ -1,7 +1,7 @@
 #[cfg(test)]
 mod tests;
 
-use crate::{ByteSource, ReloadInfo, SourceFilter};
+use crate::{ByteSource, NetworkFlow, ReloadInfo, SourceFilter};
 use log::warn;
 use parsers::{Error as ParserError, LogMessage, MessageStreamItem, Parser};
 use std::marker::PhantomData;
@@ -43,6 +43,13 @@ impl<T: LogMessage, P: Parser<T>, D: ByteSource> MessageProducer<T, P, D> {
         }
     }
 
+    /// Applies the filter when loading data from the byte source (e.g. to pick the relevant
+    /// flows of a network trace).
+    pub fn with_filter(mut self, filter: SourceFilter) -> Self {
+        self.filter = Some(filter);
+        self
+    }
+
     /// Loads the next segment of bytes, parses them, and returns the items in a mutable vector.
     /// The caller can choose whether to consume the items or not.
     ///
@@ -136,6 +143,7 @@ impl<T: LogMessage, P: Parser<T>, D: ByteSource> MessageProducer<T, P, D> {
                     }
 
                     self.byte_source.consume(total_consumed);
+                    self.parser.finish_segment();
                     return Some(&mut self.buffer);
                 }
                 Err(ParserError::Incomplete) => {
@@ -146,6 +154,19 @@ impl<T: LogMessage, P: Parser<T>, D: ByteSource> MessageProducer<T, P, D> {
                         trace!("New bytes has been loaded, trying parsing again.");
                         available += newly_loaded;
                         skipped_bytes += skipped;
+                    } else if let Some((consumed, item)) = self
+                        .parser
+                        .parse_rest(self.byte_source.current_slice(), self.last_seen_ts)
+                    {
+                        trace!("No bytes has been loaded, parsed the rest of {consumed} bytes");
+                        let total_used_bytes = consumed + skipped_bytes;
+                        self.buffer.push((
+                            total_used_bytes,
+                            item.map_or(MessageStreamItem::Skipped, MessageStreamItem::Item),
+                        ));
+                        self.byte_source.consume(consumed);
+                        self.parser.finish_segment();
+                        return Some(&mut self.buffer);
                     } else {
                         trace!("No bytes has been loaded, drop one byte if available or load");
 
@@ -216,6 +237,31 @@ impl<T: LogMessage, P: Parser<T>, D: ByteSource> MessageProducer<T, P, D> {
         }
     }
 
+    /// Loads and parses the next segment like [`Self::read_next_segment()`] and returns the
+    /// parsed items along with the network flow they were parsed from (see
+    /// [`ByteSource::current_flow()`]).
+    pub async fn read_next_flow_segment(
+        &mut self,
+    ) -> Option<(&mut Vec<(usize, MessageStreamItem<T>)>, Option<NetworkFlow>)> {
+        self.read_next_segment().await?;
+        let flow = self.byte_source.current_flow();
+        Some((&mut self.buffer, flow))
+    }
+
+    /// Continues producing items after the end of the byte source was reached, e.g. because
+    /// more data was appended to a tailed file.
+    pub fn resume(&mut self) {
+        self.done = false;
+    }
+
+    /// Replaces the byte source and continues producing items from the new one, e.g. after a
+    /// tailed file was truncated or rotated. Items are parsed with the same parser, therefore
+    /// the state of the parser (e.g. detected lifecycles) is kept.
+    pub fn replace_source(&mut self, source: D) {
+        self.byte_source = source;
+        self.done = false;
+    }
+
     /// Calls load on the underline byte source filling it with more bytes.
     /// Returning information about the state of the byte counts, Or None if
     /// the reload call fails.
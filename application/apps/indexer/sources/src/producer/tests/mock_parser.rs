@@ -36,6 +36,10 @@ impl LogMessage for MockMessage {
 pub struct MockParser {
     /// The seeds that will be used to return values on [`Parser::parse()`] calls
     seeds: VecDeque<Result<Vec<MockParseSeed>, Error>>,
+    /// The seed that will be used to return value on [`Parser::parse_rest()`] call
+    rest: Option<MockParseSeed>,
+    /// The count of [`Parser::finish_segment()`] calls
+    pub finished_segments: usize,
 }
 
 impl MockParser {
@@ -43,8 +47,16 @@ impl MockParser {
     pub fn new(seeds: impl Into<VecDeque<Result<Vec<MockParseSeed>, Error>>>) -> Self {
         Self {
             seeds: seeds.into(),
+            rest: None,
+            finished_segments: 0,
         }
     }
+
+    /// * `rest`: Seed item which will be used to produce return-value on [`Parser::parse_rest()`] call
+    pub fn with_rest(mut self, rest: MockParseSeed) -> Self {
+        self.rest = Some(rest);
+        self
+    }
 }
 
 #[derive(Debug)]
@@ -85,6 +97,20 @@ where
             .into_iter()
             .map(|seed| (seed.cosumed, seed.parse_yeild)))
     }
+
+    fn parse_rest(
+        &mut self,
+        _input: &[u8],
+        _timestamp: Option<u64>,
+    ) -> Option<(usize, Option<ParseYield<MockMessage>>)> {
+        self.rest
+            .take()
+            .map(|seed| (seed.cosumed, seed.parse_yeild))
+    }
+
+    fn finish_segment(&mut self) {
+        self.finished_segments += 1;
+    }
 }
 
 #[test]
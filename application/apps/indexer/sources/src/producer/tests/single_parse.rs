@@ -147,6 +147,58 @@ async fn parse_incomplete() {
     assert!(next.is_none());
 }
 
+#[tokio::test]
+async fn parse_rest_at_end() {
+    let parser = MockParser::new([Err(ParseError::Incomplete)]).with_rest(MockParseSeed::new(
+        10,
+        Some(ParseYield::Message(MockMessage::from(1))),
+    ));
+    let source = MockByteSource::new(
+        0,
+        [Ok(Some(MockReloadSeed::new(10, 0))), Ok(None), Ok(None)],
+    );
+
+    let mut producer = MessageProducer::new(parser, source);
+
+    // Incomplete bytes should be parsed as the rest once no more bytes can be loaded
+    let next = producer.read_next_segment().await.unwrap();
+    assert_eq!(next.len(), 1);
+    assert!(matches!(
+        next[0],
+        (
+            10,
+            MessageStreamItem::Item(ParseYield::Message(MockMessage { content: 1 }))
+        )
+    ));
+
+    let next = producer.read_next_segment().await.unwrap();
+    assert_eq!(next.len(), 1);
+    assert!(matches!(next[0], (0, MessageStreamItem::Done)));
+}
+
+#[tokio::test]
+async fn finish_segment_after_items() {
+    let parser = MockParser::new([
+        Ok(vec![
+            MockParseSeed::new(5, Some(ParseYield::Message(MockMessage::from(1)))),
+            MockParseSeed::new(5, Some(ParseYield::Message(MockMessage::from(2)))),
+        ]),
+        Err(ParseError::Incomplete),
+    ]);
+    let source = MockByteSource::new(0, [Ok(Some(MockReloadSeed::new(10, 0))), Ok(None)]);
+
+    let mut producer = MessageProducer::new(parser, source);
+
+    // Segment is finished once for all its items
+    let next = producer.read_next_segment().await.unwrap();
+    assert_eq!(next.len(), 2);
+    assert_eq!(producer.parser.finished_segments, 1);
+
+    let next = producer.read_next_segment().await.unwrap();
+    assert!(matches!(next[0], (0, MessageStreamItem::Done)));
+    assert_eq!(producer.parser.finished_segments, 1);
+}
+
 #[tokio::test]
 async fn parse_incomplete_with_err_reload() {
     let parser = MockParser::new([Err(ParseError::Incomplete)]);
@@ -518,3 +570,33 @@ async fn success_parsi_err_success_drain_bytes() {
     let next = producer.read_next_segment().await;
     assert!(next.is_none());
 }
+
+#[tokio::test]
+async fn replace_source_after_done() {
+    let parser = MockParser::new([Ok(vec![MockParseSeed::new(
+        5,
+        Some(ParseYield::Message(MockMessage::from(1))),
+    )])]);
+    let source = MockByteSource::new(0, [Ok(None)]);
+
+    let mut producer = MessageProducer::new(parser, source);
+
+    let next = producer.read_next_segment().await.unwrap();
+    assert!(matches!(next[0], (0, MessageStreamItem::Done)));
+    assert!(producer.read_next_segment().await.is_none());
+
+    // Items of the new source are delivered after the previous source is done.
+    producer.replace_source(MockByteSource::new(
+        0,
+        [Ok(Some(MockReloadSeed::new(5, 0))), Ok(None)],
+    ));
+    let next = producer.read_next_segment().await.unwrap();
+    assert_eq!(next.len(), 1);
+    assert!(matches!(
+        next[0],
+        (
+            5,
+            MessageStreamItem::Item(ParseYield::Message(MockMessage { content: 1 }))
+        )
+    ));
+}
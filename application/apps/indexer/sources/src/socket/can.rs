@@ -0,0 +1,408 @@
+use bufread::DeqBuffer;
+use log::trace;
+use parsers::can::{CanFrame, FrameKind, candump};
+use std::{
+    ffi::CString,
+    io, mem,
+    os::fd::{AsRawFd, FromRawFd, OwnedFd},
+    time::{SystemTime, UNIX_EPOCH},
+};
+use thiserror::Error;
+use tokio::io::unix::AsyncFd;
+
+use super::MAX_BUFF_SIZE;
+use crate::{
+    ByteSource, Error as SourceError, ReloadInfo, SourceFilter,
+    socket::{BuffCapacityState, handle_buff_capacity},
+};
+
+/// Maximum length of a frame written as `candump -l` line.
+const MAX_LINE_LEN: usize = 256;
+
+#[derive(Error, Debug)]
+pub enum SocketCanSourceError {
+    #[error("IO Error: {0}")]
+    Io(#[from] io::Error),
+    #[error("Invalid interface name: {0}")]
+    Interface(String),
+}
+
+/// Byte source reading frames from a SocketCAN interface.
+///
+/// Received frames are delivered as lines in the log format of `candump -l`, which makes
+/// them readable by the CAN parser and keeps exported raw data usable with the SocketCAN
+/// utilities.
+pub struct SocketCanSource {
+    socket: AsyncFd<OwnedFd>,
+    interface: String,
+    buffer: DeqBuffer,
+    line: String,
+}
+
+impl SocketCanSource {
+    /// Opens a raw CAN socket bound to the given interface with CAN FD frames enabled.
+    pub fn new(interface: &str) -> Result<Self, SocketCanSourceError> {
+        Self::from_socket(open_socket(interface)?, interface)
+    }
+
+    /// Creates a source reading from an already opened non-blocking socket, which has to
+    /// deliver one `struct can_frame` or `struct canfd_frame` with each read.
+    fn from_socket(socket: OwnedFd, interface: &str) -> Result<Self, SocketCanSourceError> {
+        Ok(Self {
+            socket: AsyncFd::new(socket)?,
+            interface: interface.to_owned(),
+            buffer: DeqBuffer::new(MAX_BUFF_SIZE),
+            line: String::new(),
+        })
+    }
+}
+
+impl ByteSource for SocketCanSource {
+    async fn load(
+        &mut self,
+        _filter: Option<&SourceFilter>,
+    ) -> Result<Option<ReloadInfo>, SourceError> {
+        // If buffer is almost full then skip loading and return the available bytes.
+        match handle_buff_capacity(&mut self.buffer) {
+            BuffCapacityState::CanLoad => {}
+            BuffCapacityState::AlmostFull => {
+                let available_bytes = self.len();
+                return Ok(Some(ReloadInfo::new(0, available_bytes, 0, None)));
+            }
+        }
+        let Self {
+            socket,
+            interface,
+            buffer,
+            line,
+        } = self;
+        loop {
+            let mut guard = socket
+                .readable()
+                .await
+                .map_err(|e| SourceError::Setup(format!("{e}")))?;
+            // Frames are only taken from the socket here, which makes loading cancel safe.
+            let loaded = read_available_frames(socket.get_ref(), interface, buffer, line)
+                .map_err(|e| SourceError::Unrecoverable(format!("{e}")))?;
+            if loaded == 0 {
+                guard.clear_ready();
+                continue;
+            }
+            trace!("SocketCAN: loaded {loaded} bytes");
+            return Ok(Some(ReloadInfo::new(
+                loaded,
+                buffer.read_available(),
+                0,
+                None,
+            )));
+        }
+    }
+
+    fn current_slice(&self) -> &[u8] {
+        self.buffer.read_slice()
+    }
+
+    fn consume(&mut self, offset: usize) {
+        self.buffer.read_done(offset);
+    }
+
+    fn len(&self) -> usize {
+        self.buffer.read_available()
+    }
+}
+
+/// Reads all frames which are currently available without blocking, writes them as lines
+/// into the buffer and returns the amount of loaded bytes.
+fn read_available_frames(
+    socket: &OwnedFd,
+    interface: &str,
+    buffer: &mut DeqBuffer,
+    line: &mut String,
+) -> io::Result<usize> {
+    let mut loaded = 0;
+    let mut raw = [0u8; libc::CANFD_MTU];
+    while buffer.write_available() >= MAX_LINE_LEN {
+        let size = match read_frame(socket, &mut raw) {
+            Ok(size) => size,
+            Err(e) if e.kind() == io::ErrorKind::WouldBlock => break,
+            Err(e) => return Err(e),
+        };
+        let Some(frame) = to_can_frame(interface, &raw[..size]) else {
+            trace!("SocketCAN: ignoring frame with {size} bytes");
+            continue;
+        };
+        line.clear();
+        candump::write_line(&frame, line);
+        loaded += buffer.write_from(line.as_bytes());
+    }
+    Ok(loaded)
+}
+
+/// Converts the content of `struct can_frame` or `struct canfd_frame`.
+fn to_can_frame(interface: &str, raw: &[u8]) -> Option<CanFrame> {
+    let fd = match raw.len() {
+        libc::CAN_MTU => false,
+        libc::CANFD_MTU => true,
+        _ => return None,
+    };
+    let can_id = u32::from_ne_bytes(raw[..4].try_into().ok()?);
+    let len = (raw[4] as usize).min(raw.len() - 8);
+    let flags = raw[5];
+    let extended = can_id & libc::CAN_EFF_FLAG != 0;
+    let kind = if can_id & libc::CAN_ERR_FLAG != 0 {
+        FrameKind::Error
+    } else if can_id & libc::CAN_RTR_FLAG != 0 {
+        FrameKind::Remote
+    } else if fd {
+        FrameKind::Fd {
+            brs: flags & libc::CANFD_BRS as u8 != 0,
+            esi: flags & libc::CANFD_ESI as u8 != 0,
+        }
+    } else {
+        FrameKind::Data
+    };
+    Some(CanFrame {
+        timestamp: SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default(),
+        channel: interface.to_owned(),
+        id: if extended || kind == FrameKind::Error {
+            can_id & libc::CAN_EFF_MASK
+        } else {
+            can_id & libc::CAN_SFF_MASK
+        },
+        extended: extended || kind == FrameKind::Error,
+        kind,
+        direction: None,
+        dlc: if fd {
+            parsers::can::dlc_from_len(len)
+        } else {
+            raw[4]
+        },
+        data: if kind == FrameKind::Remote {
+            Vec::new()
+        } else {
+            raw[8..8 + len].to_vec()
+        },
+    })
+}
+
+/// Opens a non-blocking raw CAN socket bound to the given interface.
+fn open_socket(interface: &str) -> Result<OwnedFd, SocketCanSourceError> {
+    let name = CString::new(interface)
+        .map_err(|_| SocketCanSourceError::Interface(interface.to_owned()))?;
+    // SAFETY: The name is a valid null terminated string.
+    let index = unsafe { libc::if_nametoindex(name.as_ptr()) };
+    if index == 0 {
+        return Err(SocketCanSourceError::Interface(format!(
+            "{interface}: {}",
+            io::Error::last_os_error()
+        )));
+    }
+    // SAFETY: Plain system call, the returned descriptor is checked below.
+    let fd = unsafe {
+        libc::socket(
+            libc::PF_CAN,
+            libc::SOCK_RAW | libc::SOCK_NONBLOCK | libc::SOCK_CLOEXEC,
+            libc::CAN_RAW,
+        )
+    };
+    if fd < 0 {
+        return Err(io::Error::last_os_error().into());
+    }
+    // SAFETY: The descriptor is valid and owned by nobody else.
+    let socket = unsafe { OwnedFd::from_raw_fd(fd) };
+    let enable: libc::c_int = 1;
+    // SAFETY: The option value points to a valid integer of the given size.
+    let res = unsafe {
+        libc::setsockopt(
+            socket.as_raw_fd(),
+            libc::SOL_CAN_RAW,
+            libc::CAN_RAW_FD_FRAMES,
+            &enable as *const libc::c_int as *const libc::c_void,
+            mem::size_of::<libc::c_int>() as libc::socklen_t,
+        )
+    };
+    if res < 0 {
+        return Err(io::Error::last_os_error().into());
+    }
+    // SAFETY: `sockaddr_can` is a plain C struct for which zeroed memory is valid.
+    let mut addr: libc::sockaddr_can = unsafe { mem::zeroed() };
+    addr.can_family = libc::AF_CAN as libc::sa_family_t;
+    addr.can_ifindex = index as libc::c_int;
+    // SAFETY: The address points to a valid `sockaddr_can` of the given size.
+    let res = unsafe {
+        libc::bind(
+            socket.as_raw_fd(),
+            &addr as *const libc::sockaddr_can as *const libc::sockaddr,
+            mem::size_of::<libc::sockaddr_can>() as libc::socklen_t,
+        )
+    };
+    if res < 0 {
+        return Err(io::Error::last_os_error().into());
+    }
+    Ok(socket)
+}
+
+/// Reads a single frame from the socket and returns its size.
+fn read_frame(socket: &OwnedFd, frame: &mut [u8]) -> io::Result<usize> {
+    // SAFETY: The buffer is valid for writes of its length.
+    let size = unsafe {
+        libc::read(
+            socket.as_raw_fd(),
+            frame.as_mut_ptr() as *mut libc::c_void,
+            frame.len(),
+        )
+    };
+    if size < 0 {
+        Err(io::Error::last_os_error())
+    } else {
+        Ok(size as usize)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::general_source_reload_test;
+    use std::time::Duration;
+
+    /// Name of the virtual CAN interface used for testing. It can be set up with:
+    /// ```sh
+    /// sudo ip link add dev vcan0 type vcan && sudo ip link set up vcan0
+    /// ```
+    const TEST_INTERFACE: &str = "vcan0";
+
+    /// Creates a source on a local stand-in for a CAN socket, which replays the frames
+    /// written to the returned socket.
+    fn replay_source() -> (SocketCanSource, OwnedFd) {
+        let mut fds = [0; 2];
+        // SAFETY: The array provides space for both descriptors.
+        let res = unsafe {
+            libc::socketpair(
+                libc::AF_UNIX,
+                libc::SOCK_SEQPACKET | libc::SOCK_NONBLOCK | libc::SOCK_CLOEXEC,
+                0,
+                fds.as_mut_ptr(),
+            )
+        };
+        assert_eq!(res, 0, "{}", io::Error::last_os_error());
+        // SAFETY: Both descriptors are valid and owned by nobody else.
+        let (receiver, sender) =
+            unsafe { (OwnedFd::from_raw_fd(fds[0]), OwnedFd::from_raw_fd(fds[1])) };
+        let source = SocketCanSource::from_socket(receiver, "replay").expect("source");
+        (source, sender)
+    }
+
+    /// Writes classic CAN frames with the given identifiers to the socket.
+    fn send_frames(socket: &OwnedFd, ids: &[u32]) {
+        for id in ids {
+            let mut frame = [0u8; libc::CAN_MTU];
+            frame[..4].copy_from_slice(&id.to_ne_bytes());
+            frame[4] = 2;
+            frame[8] = 0xDE;
+            frame[9] = 0xAD;
+            // SAFETY: The buffer is valid for reads of its length.
+            let res = unsafe {
+                libc::write(
+                    socket.as_raw_fd(),
+                    frame.as_ptr() as *const libc::c_void,
+                    frame.len(),
+                )
+            };
+            assert_eq!(res, libc::CAN_MTU as isize);
+        }
+    }
+
+    /// Loads from the source until the given count of lines is received.
+    async fn receive_lines(source: &mut SocketCanSource, count: usize) -> Vec<CanFrame> {
+        let mut lines = String::new();
+        while lines.lines().count() < count {
+            let info = tokio::time::timeout(Duration::from_secs(1), source.load(None))
+                .await
+                .expect("frames are received")
+                .unwrap()
+                .unwrap();
+            lines.push_str(&String::from_utf8_lossy(source.current_slice()));
+            source.consume(info.available_bytes);
+        }
+        lines
+            .lines()
+            .map(|line| candump::parse_line(line).expect("valid line"))
+            .collect()
+    }
+
+    #[test]
+    fn convert_frames() {
+        let mut raw = [0u8; libc::CANFD_MTU];
+        raw[..4].copy_from_slice(&(0x1234_5678 | libc::CAN_EFF_FLAG).to_ne_bytes());
+        raw[4] = 12;
+        raw[5] = libc::CANFD_BRS as u8;
+        raw[8..20].copy_from_slice(&[0xAA; 12]);
+        let frame = to_can_frame("can0", &raw).expect("fd frame");
+        assert!(frame.extended);
+        assert_eq!(frame.id, 0x1234_5678);
+        assert_eq!(frame.dlc, 9);
+        assert_eq!(
+            frame.kind,
+            FrameKind::Fd {
+                brs: true,
+                esi: false
+            }
+        );
+
+        let mut raw = [0u8; libc::CAN_MTU];
+        raw[..4].copy_from_slice(&(0x123 | libc::CAN_RTR_FLAG).to_ne_bytes());
+        raw[4] = 4;
+        let frame = to_can_frame("can0", &raw).expect("remote frame");
+        assert_eq!(frame.kind, FrameKind::Remote);
+        assert_eq!(frame.dlc, 4);
+        assert!(frame.data.is_empty());
+
+        assert!(to_can_frame("can0", &raw[..10]).is_none());
+    }
+
+    #[tokio::test]
+    async fn receive_frames() {
+        let (mut source, sender) = replay_source();
+        send_frames(&sender, &[0x100, 0x200]);
+        let frames = receive_lines(&mut source, 2).await;
+        assert_eq!(
+            frames.iter().map(|f| f.id).collect::<Vec<_>>(),
+            vec![0x100, 0x200]
+        );
+        assert_eq!(frames[0].channel, "replay");
+        assert_eq!(frames[0].data, vec![0xDE, 0xAD]);
+    }
+
+    #[tokio::test]
+    async fn test_general_source_reload() {
+        let (mut source, sender) = replay_source();
+        let sender = tokio::spawn(async move {
+            for id in 0..20 {
+                send_frames(&sender, &[id]);
+                tokio::time::sleep(Duration::from_millis(10)).await;
+            }
+        });
+
+        general_source_reload_test(&mut source).await;
+        sender.abort();
+    }
+
+    #[tokio::test]
+    async fn receive_from_vcan() {
+        let mut source = match SocketCanSource::new(TEST_INTERFACE) {
+            Ok(source) => source,
+            Err(err) => {
+                println!("Skipping SocketCAN test, {TEST_INTERFACE} isn't available: {err}");
+                return;
+            }
+        };
+        let sender = open_socket(TEST_INTERFACE).expect("open sending socket");
+        send_frames(&sender, &[0x100, 0x1F33_4455 | libc::CAN_EFF_FLAG]);
+        let frames = receive_lines(&mut source, 2).await;
+        assert_eq!(frames[0].id, 0x100);
+        assert!(frames[1].extended);
+        assert_eq!(frames[1].channel, TEST_INTERFACE);
+    }
+}
@@ -1,5 +1,7 @@
 use bufread::DeqBuffer;
 
+#[cfg(target_os = "linux")]
+pub mod can;
 pub mod tcp;
 pub mod udp;
 
@@ -3,6 +3,7 @@ import type { AttachmentInfo } from "./attachment";
 import type { FilterMatchList } from "./miscellaneous";
 import type { NativeError } from "./error";
 import type { Progress } from "./progress";
+import type { TimeBucket } from "./operations";
 
 /**
  * Represents events sent to the client.
@@ -29,7 +30,27 @@ len: number,
 /**
  * The description of the attachment.
  */
-attachment: AttachmentInfo, } } | { "Progress": { 
+attachment: AttachmentInfo, } } | { "FileRotated": { 
+/**
+ * The identifier of the source the file belongs to.
+ */
+source_id: number, 
+/**
+ * The position of the first log entry of the new segment.
+ */
+pos: number, 
+/**
+ * `true` if the file was truncated, `false` if it was replaced by a new file.
+ */
+truncated: boolean, } } | { "TimeDistributionUpdated": Array<TimeBucket> } | { "TriggerFired": { 
+/**
+ * The position of the rule in the list of trigger rules.
+ */
+rule: number, 
+/**
+ * The position of the log entry which fired the rule.
+ */
+row: number, } } | { "Progress": { 
 /**
  * The unique identifier of the operation.
  */
@@ -1,4 +1,7 @@
 // This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { FileFormat } from "./observe";
+import type { Range } from "./miscellaneous";
+import type { SearchCondition } from "./server";
 
 /**
  * Represents the result of a command execution.
@@ -14,6 +17,20 @@ export type CommandOutcomeBool = { "Finished": boolean } | "Cancelled";
  */
 export type CommandOutcomeDltStatisticInfoResult = { "Finished": DltStatisticInfo } | "Cancelled";
 
+/**
+ * Represents the result of a command execution.
+ * At the core level, this type is used for all commands invoked within an `UnboundSession`.
+ * It is only used to indicate the successful completion or interruption of a command.
+ */
+export type CommandOutcomeFilesChronologyResult = { "Finished": FilesChronology } | "Cancelled";
+
+/**
+ * Represents the result of a command execution.
+ * At the core level, this type is used for all commands invoked within an `UnboundSession`.
+ * It is only used to indicate the successful completion or interruption of a command.
+ */
+export type CommandOutcomeFilesComparisonResult = { "Finished": FilesComparison } | "Cancelled";
+
 /**
  * Represents the result of a command execution.
  * At the core level, this type is used for all commands invoked within an `UnboundSession`.
@@ -21,6 +38,20 @@ export type CommandOutcomeDltStatisticInfoResult = { "Finished": DltStatisticInf
  */
 export type CommandOutcomeFoldersScanningResult = { "Finished": FoldersScanningResult } | "Cancelled";
 
+/**
+ * Represents the result of a command execution.
+ * At the core level, this type is used for all commands invoked within an `UnboundSession`.
+ * It is only used to indicate the successful completion or interruption of a command.
+ */
+export type CommandOutcomeFormatDetectionResult = { "Finished": FormatDetection } | "Cancelled";
+
+/**
+ * Represents the result of a command execution.
+ * At the core level, this type is used for all commands invoked within an `UnboundSession`.
+ * It is only used to indicate the successful completion or interruption of a command.
+ */
+export type CommandOutcomeGrepResult = { "Finished": GrepResult } | "Cancelled";
+
 /**
  * Represents the result of a command execution.
  * At the core level, this type is used for all commands invoked within an `UnboundSession`.
@@ -63,10 +94,198 @@ export type CommandOutcomeVoid = "Finished" | "Cancelled";
  */
 export type CommandOutcomei64 = { "Finished": number } | "Cancelled";
 
+/**
+ * A normalized line with its occurrences on both sides of a comparison.
+ */
+export type ComparedLine = { 
+/**
+ * The line after applying the mask rules.
+ */
+line: string, 
+/**
+ * Count of the lines on the left side.
+ */
+left: number, 
+/**
+ * Count of the lines on the right side.
+ */
+right: number, 
+/**
+ * Inclusive ranges of rows on the left side, which can be passed to `grab_ranges` of
+ * the left session. Might be truncated for frequent lines.
+ */
+left_rows: Array<Range>, 
+/**
+ * Inclusive ranges of rows on the right side, which can be passed to `grab_ranges` of
+ * the right session. Might be truncated for frequent lines.
+ */
+right_rows: Array<Range>, };
+
+/**
+ * Options of the comparison of two session files.
+ */
+export type ComparisonOptions = { 
+/**
+ * Path to the session file of the left side.
+ */
+left: string, 
+/**
+ * Path to the session file of the right side.
+ */
+right: string, 
+/**
+ * Rules applied to each line in the given order.
+ */
+rules: Array<MaskRule>, 
+/**
+ * Applies the default rules (masking timestamps, hex IDs and numbers) after `rules`.
+ */
+defaults: boolean, };
+
+/**
+ * The parser suggested for the content of a file.
+ */
+export type DetectedParser = "Dlt" | "SomeIp" | "Can" | "Text";
+
+/**
+ * A region in which two aligned session files differ: `left_len` rows of the left side
+ * starting at `left_start` are replaced by `right_len` rows of the right side starting at
+ * `right_start`. A length of `0` means the rows of the other side were added.
+ */
+export type DiffHunk = { 
+/**
+ * First row of the region on the left side.
+ */
+left_start: number, 
+/**
+ * Count of rows of the region on the left side.
+ */
+left_len: number, 
+/**
+ * First row of the region on the right side.
+ */
+right_start: number, 
+/**
+ * Count of rows of the region on the right side.
+ */
+right_len: number, };
+
 export type DltLevelDistribution = { non_log: number, log_fatal: number, log_error: number, log_warning: number, log_info: number, log_debug: number, log_verbose: number, log_invalid: number, };
 
 export type DltStatisticInfo = { app_ids: Array<[string, DltLevelDistribution]>, context_ids: Array<[string, DltLevelDistribution]>, ecu_ids: Array<[string, DltLevelDistribution]>, contained_non_verbose: boolean, };
 
+/**
+ * The time range of the messages of a file. Timestamps are in milliseconds since the UNIX
+ * epoch and are `None` if no timestamps were found.
+ */
+export type FileTimeRange = { 
+/**
+ * Path to the file.
+ */
+path: string, 
+/**
+ * Timestamp of the first message.
+ */
+first: number | null, 
+/**
+ * Timestamp of the last message.
+ */
+last: number | null, };
+
+/**
+ * Files ordered chronologically by the timestamps of their first messages. Files without
+ * timestamps keep their order and follow the files with timestamps.
+ */
+export type FilesChronology = { 
+/**
+ * Files in chronological order.
+ */
+files: Array<FileTimeRange>, 
+/**
+ * Overlapping files, which should be reported to the user as warnings.
+ */
+overlaps: Array<FilesOverlap>, };
+
+/**
+ * Differences between two session files. The files are aligned in order by the longest
+ * common subsequence of their normalized lines, which gives the regions in which they
+ * differ. Besides, the lists of lines compare the normalized contents regardless of their
+ * order; they are ordered by the difference of counts.
+ */
+export type FilesComparison = { 
+/**
+ * Lines found on the left side only.
+ */
+left_only: Array<ComparedLine>, 
+/**
+ * Lines found on the right side only.
+ */
+right_only: Array<ComparedLine>, 
+/**
+ * Lines found on both sides, but a different number of times.
+ */
+changed: Array<ComparedLine>, 
+/**
+ * Count of distinct lines found on both sides the same number of times.
+ */
+common: number, 
+/**
+ * Regions in which the aligned files differ in ascending order of rows.
+ */
+hunks: Array<DiffHunk>, 
+/**
+ * `true` if lists or hunks were cut to their max length (1000 entries). Lists keep the
+ * lines with the largest differences, hunks keep the first regions.
+ */
+truncated: boolean, };
+
+/**
+ * Two files whose time ranges overlap. Concatenating them doesn't give a continuous
+ * timeline.
+ */
+export type FilesOverlap = { 
+/**
+ * Path to the file which starts earlier.
+ */
+earlier: string, 
+/**
+ * Path to the file which starts before the earlier file ends.
+ */
+later: string, 
+/**
+ * Duration of the overlap in milliseconds.
+ */
+duration: number, };
+
+/**
+ * A suggestion on how to open a file, based on its magic numbers and a sample of its content.
+ */
+export type FormatDetection = { 
+/**
+ * The suggested format of the file.
+ */
+format: FileFormat, 
+/**
+ * The suggested parser, if the content could be recognized.
+ */
+parser: DetectedParser | null, 
+/**
+ * Whether DLT messages are preceded by storage headers. Set only for the DLT parser.
+ */
+with_storage_header: boolean | null, 
+/**
+ * The encoding of text content. Set only for the text parser.
+ */
+encoding: TextEncoding | null, 
+/**
+ * Whether the file is compressed or an entry of an archive.
+ */
+compressed: boolean, 
+/**
+ * The confidence in the suggestion in percent.
+ */
+confidence: number, };
+
 /**
  * Represents a folder entity in the file system.
  */
@@ -131,6 +350,80 @@ list: Array<FolderEntity>,
  */
 max_len_reached: boolean, };
 
+/**
+ * A line matched by the search across files.
+ */
+export type GrepMatch = { 
+/**
+ * Path to the file.
+ */
+file: string, 
+/**
+ * Zero-based number of the line. For DLT files it's the number of the message.
+ */
+line: number, 
+/**
+ * Positions of the matched conditions in `GrepOptions::patterns`.
+ */
+patterns: Array<number>, 
+/**
+ * Content of the line.
+ */
+content: string, };
+
+/**
+ * Options of the search of lines across files without opening sessions.
+ */
+export type GrepOptions = { 
+/**
+ * Paths to files or folders. Folders are scanned recursively.
+ */
+paths: Array<string>, 
+/**
+ * Conditions of the search; a line matches if it matches at least one of them.
+ */
+patterns: Array<SearchCondition>, 
+/**
+ * Count of matches to skip.
+ */
+offset: number, 
+/**
+ * Max count of matches to return.
+ */
+limit: number, };
+
+/**
+ * A page of the lines matched by the search across files.
+ */
+export type GrepResult = { 
+/**
+ * Matched lines in the order of files and lines.
+ */
+matches: Array<GrepMatch>, 
+/**
+ * `true` if there are more matches after this page.
+ */
+more: boolean, 
+/**
+ * Files which couldn't be searched, with the reason. Binary files are searched only if
+ * they are recognized as DLT.
+ */
+skipped: Array<string>, };
+
+/**
+ * A rule of normalization of lines: all matches of the regex are replaced by the mask, so
+ * lines which differ only in variable parts (timestamps, numbers, IDs) become equal.
+ */
+export type MaskRule = { 
+/**
+ * Regular expression of the masked parts.
+ */
+pattern: string, 
+/**
+ * Replacement of the masked parts, e.g. `<NUM>`.
+ */
+mask: string, };
+
 export type Profile = { 
 /**
  * Suggested name of shell. For unix based systems it will be name of executable file,
@@ -168,3 +461,8 @@ export type ProfileList = Array<Profile>;
  * or identifier of a serial port available on the system.
  */
 export type SerialPortsList = Array<string>;
+
+/**
+ * The encoding of text content.
+ */
+export type TextEncoding = "Utf8" | "Utf16Le" | "Utf16Be" | "Latin1";
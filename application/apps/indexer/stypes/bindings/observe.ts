@@ -5,9 +5,20 @@
  */
 import { DltFilterConfig } from './dlt';
 import type { PluginParserSettings } from './plugins';
+import type { TimestampSettings } from './miscellaneous';
 
 // This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
 
+/**
+ * Settings for the CAN parser.
+ */
+export type CanParserSettings = {
+    /**
+     * Paths to DBC files used to decode the payload of frames into messages and signals.
+     */
+    dbc_file_paths: Array<string> | null;
+};
+
 /**
  * Settings for the DLT parser.
  */
@@ -17,9 +28,20 @@ export type DltParserSettings = {
      */
     filter_config: DltFilterConfig;
     /**
-     * Paths to FIBEX files for additional interpretation of `payload` content.
+     * Paths to FIBEX files for additional interpretation of `payload` content. FlexRay
+     * network trace payloads are decoded with the FlexRay frames of these files.
      */
     fibex_file_paths: Array<string> | null;
+    /**
+     * Paths to AUTOSAR ARXML files describing non-verbose messages. Used for messages which
+     * aren't described by the FIBEX files.
+     */
+    arxml_file_paths: Array<string> | null;
+    /**
+     * Paths to DBC files used to decode CAN network trace payloads into
+     * messages and signals.
+     */
+    dbc_file_paths: Array<string> | null;
     /**
      * Indicates whether the source contains a `StorageHeader`. Set to `true` if applicable.
      */
@@ -28,12 +50,44 @@ export type DltParserSettings = {
      * Timezone for timestamp adjustment. If specified, timestamps are converted to this timezone.
      */
     tz: string | null;
+    /**
+     * Enables the detection of ECU lifecycles. Adds the lifecycle and the absolute time,
+     * computed from the ECU uptime and the lifecycle start, as columns.
+     */
+    detect_lifecycles: boolean;
 };
 
 /**
  * Supported file formats for observation.
  */
-export type FileFormat = 'PcapNG' | 'PcapLegacy' | 'Text' | 'Binary';
+export type FileFormat = 'PcapNG' | 'PcapLegacy' | 'Text' | 'Binary' | 'Blf';
+
+/**
+ * Order of the files in a watched folder, from the oldest file to the newest one.
+ */
+export type FolderSortOrder = 'Name' | 'Modified';
+
+/**
+ * Folder with rotating log files observed as a single source of data.
+ */
+export type FolderWatch = {
+    /**
+     * Path to the watched folder. Subfolders aren't included.
+     */
+    path: string;
+    /**
+     * Glob pattern for the names of the files, e.g. `app*.log`. Supports `*` and `?`.
+     */
+    pattern: string;
+    /**
+     * Order in which existing files are concatenated. The last file is the newest one.
+     */
+    order: FolderSortOrder;
+    /**
+     * Format of the files.
+     */
+    format: FileFormat;
+};
 
 /**
  * Multicast configuration information.
@@ -43,6 +97,38 @@ export type FileFormat = 'PcapNG' | 'PcapLegacy' | 'Text' | 'Binary';
  */
 export type MulticastInfo = { multiaddr: string; interface: string | null };
 
+/**
+ * Filter of the packets of network traces (PCAP and PCAPNG files). Each defined criterion
+ * has to match, undefined criteria match any packet.
+ */
+export type NetworkFilter = {
+    /**
+     * Transport protocol of the packets.
+     */
+    transport: NetworkTransport | null;
+    /**
+     * IP address of the sender, e.g. `192.168.0.1`.
+     */
+    source_ip: string | null;
+    /**
+     * Port of the sender.
+     */
+    source_port: number | null;
+    /**
+     * IP address of the receiver.
+     */
+    destination_ip: string | null;
+    /**
+     * Port of the receiver.
+     */
+    destination_port: number | null;
+};
+
+/**
+ * Transport protocol of the packets picked from network traces.
+ */
+export type NetworkTransport = 'Tcp' | 'Udp';
+
 /**
  * Options for observing data within a session.
  */
@@ -55,6 +141,16 @@ export type ObserveOptions = {
      * The parser configuration to be applied.
      */
     parser: ParserType;
+    /**
+     * Filter of the packets of network traces. Applied to PCAP and PCAPNG files only.
+     */
+    network_filter: NetworkFilter | null;
+    /**
+     * Orders concatenated files chronologically by the timestamps of their first messages;
+     * overlapping files are reported as warnings. Timestamps of text files are extracted
+     * with the given settings. Applied to `ObserveOrigin::Concat` only.
+     */
+    order_by_time: TimestampSettings | null;
 };
 
 /**
@@ -63,7 +159,8 @@ export type ObserveOptions = {
 export type ObserveOrigin =
     | { File: [string, FileFormat, string] }
     | { Concat: Array<[string, FileFormat, string]> }
-    | { Stream: [string, Transport] };
+    | { Stream: [string, Transport] }
+    | { Folder: [string, FolderWatch] };
 
 /**
  * Specifies the parser to be used for processing session data.
@@ -72,7 +169,8 @@ export type ParserType =
     | { Dlt: DltParserSettings }
     | { SomeIp: SomeIpParserSettings }
     | { Text: null }
-    | { Plugin: PluginParserSettings };
+    | { Plugin: PluginParserSettings }
+    | { Can: CanParserSettings };
 
 /**
  * Configuration for executing terminal commands.
@@ -130,6 +228,16 @@ export type SerialTransportConfig = {
     exclusive: boolean;
 };
 
+/**
+ * Configuration for SocketCAN connections.
+ */
+export type SocketCanTransportConfig = {
+    /**
+     * The name of the CAN interface (e.g. `can0` or `vcan0`).
+     */
+    interface: string;
+};
+
 /**
  * Settings for the SomeIp parser.
  */
@@ -157,7 +265,8 @@ export type Transport =
     | { Process: ProcessTransportConfig }
     | { TCP: TCPTransportConfig }
     | { UDP: UDPTransportConfig }
-    | { Serial: SerialTransportConfig };
+    | { Serial: SerialTransportConfig }
+    | { SocketCan: SocketCanTransportConfig };
 
 /**
  * Configuration for UDP connections.
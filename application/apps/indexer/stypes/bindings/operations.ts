@@ -1,4 +1,5 @@
 // This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { Range } from "./miscellaneous";
 
 /**
  * Used to delivery results of extracting values. That's used in the scope
@@ -16,8 +17,41 @@ index: number,
  */
 values: Array<[number, Array<string>]>, };
 
+/**
+ * Template of messages mined from the rows of a session
+ */
+export type LogTemplate = { id: number, 
+/**
+ * Tokens of the template separated by spaces; parameters are replaced with `<*>`
+ */
+template: string, 
+/**
+ * Regular expression matching the rows of the template
+ */
+filter: string, 
+/**
+ * Count of rows matching the template
+ */
+count: number, 
+/**
+ * First row matching the template
+ */
+first: number, 
+/**
+ * Last row matching the template
+ */
+last: number, 
+/**
+ * The first row matching the template
+ */
+example: string, };
+
+export type LogTemplateList = Array<LogTemplate>;
+
 export type NearestPosition = { index: number, position: number, };
 
+export type NearestTimestamp = { position: number, timestamp: number, };
+
 /**
  *(row_number, min_value_in_range, max_value_in_range, value)
  * value - can be last value in range or some kind of average
@@ -33,6 +67,8 @@ export type ResultExtractedMatchValues = Array<ExtractedMatchValue>;
 
 export type ResultNearestPosition = NearestPosition | null;
 
+export type ResultNearestTimestamp = NearestTimestamp | null;
+
 /**
  * Scaled chart data
  */
@@ -40,9 +76,117 @@ export type ResultScaledDistribution = Array<Array<[number, number]>>;
 
 export type ResultSearchValues = Map<number, Point[]>;
 
+/**
+ * Satisfied and violated instances of sequence rules ordered by their first rows
+ */
+export type ResultSequences = Array<SequenceInstance>;
+
 /**
  * Used only for debug session lifecycle
  */
 export type ResultSleep = { sleep_well: boolean, };
 
+/**
+ * Distribution of search matches over time. Only periods with matches are included.
+ */
+export type ResultTimeDistribution = Array<TimeBucket>;
+
+/**
+ * Range of positions in original stream/file covering a period of time
+ */
+export type ResultTimeRange = Range | null;
+
 export type ResultU64 = number;
+
+/**
+ * An instance of a sequence rule started by a log entry matching its first step
+ */
+export type SequenceInstance = { 
+/**
+ * Index of the rule
+ */
+rule: number, 
+/**
+ * Rows of the matched steps
+ */
+steps: Array<number>, 
+/**
+ * Rows from the first step to the last step or to the row the violation was detected in
+ */
+rows: Range, 
+/**
+ * The reason of the violation or `None` if the instance is satisfied
+ */
+violation: SequenceViolation | null, };
+
+/**
+ * Reason of the violation of a sequence rule
+ */
+export type SequenceViolation = { "Timeout": number } | { "Forbidden": number } | { "Unfinished": number } | { "Overflow": number };
+
+/**
+ * Count of matches of search filters within a period of time
+ */
+export type TimeBucket = { 
+/**
+ * Start of the period in ms since the UNIX epoch
+ */
+timestamp: number, 
+/**
+ * Pairs of the index of a filter and the count of its matches
+ */
+matches: Array<[number, number]>, };
+
+/**
+ * Describes a series of search values. A search values term produces one series per
+ * named capture group, the series of its first capture group or, if it has no capture
+ * groups, the running count of its matches.
+ */
+export type ValueSeries = { 
+/**
+ * Key of the series in `ResultSearchValues`. The first series of a term is keyed by the
+ * index of the term, further series of terms get the keys following the count of terms.
+ */
+id: number, 
+/**
+ * Index of the term producing the series
+ */
+term: number, 
+/**
+ * Name of the capture group
+ */
+name: string | null, kind: ValueSeriesKind, };
+
+/**
+ * Kind of values of a series of search values
+ */
+export type ValueSeriesKind = "Value" | "Delta" | "Rate" | "Count";
+
+export type ValueSeriesList = Array<ValueSeries>;
+
+/**
+ * Statistics of the values of a series of search values
+ */
+export type ValueStatistics = { 
+/**
+ * Key of the series in `ResultSearchValues`
+ */
+id: number, 
+/**
+ * Count of values
+ */
+count: number, min: number, max: number, mean: number, median: number, 
+/**
+ * 90th percentile
+ */
+p90: number, 
+/**
+ * 99th percentile
+ */
+p99: number, 
+/**
+ * Population standard deviation
+ */
+stddev: number, };
+
+export type ValueStatisticsList = Array<ValueStatistics>;
@@ -0,0 +1,57 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { CallbackEvent } from "./callback";
+import type { ComparisonOptions, GrepOptions } from "./command";
+import type { ComputationError } from "./error";
+import type { ObserveOptions } from "./observe";
+import type { Range } from "./miscellaneous";
+import type { Ranges } from "./miscellaneous";
+
+/**
+ * Condition of a search.
+ */
+export type SearchCondition = { value: string, is_regex: boolean, ignore_case: boolean, is_word: boolean, };
+
+/**
+ * Command sent to the server.
+ */
+export type ServerCommand = "CreateSession" | { "DestroySession": string } | { "Session": [string, SessionRequest] } | { "Unbound": UnboundRequest };
+
+/**
+ * Message sent by the server.
+ */
+export type ServerMessage = { "Response": ServerResponse } | { "Event": [string, CallbackEvent] };
+
+/**
+ * Request sent to the server. The `id` is chosen by the client and is used to match the
+ * response with the request.
+ */
+export type ServerRequest = { id: number, command: ServerCommand, };
+
+/**
+ * Response to a request.
+ */
+export type ServerResponse = { id: number, result: ServerResult, };
+
+/**
+ * Result of a request.
+ */
+export type ServerResult = "Done" | { "Session": string } | { "Operation": string } | { "Number": number } | { "Encoded": Array<number> } | { "Error": ComputationError };
+
+/**
+ * Request to an existing session.
+ */
+export type SessionRequest = { "Observe": ObserveOptions } | { "Search": Array<SearchCondition> } | "DropSearch" | "GetStreamLen" | "GetSearchResultLen" | { "Grab": Range } | { "GrabSearch": Range } | { "GrabRanges": Ranges } | { "GrabSearchContext": { range: Range, before: number, after: number, } } | { "Export": { out_path: string, ranges: Array<Range>, 
+/**
+ * Columns to export; all columns are exported if empty.
+ */
+columns: Array<number>, spliter: string | null, delimiter: string | null, } } | { "ExportRaw": { out_path: string, ranges: Array<Range>, } } | { "ExportSearchContext": { out_path: string, before: number, after: number, 
+/**
+ * Columns to export; all columns are exported if empty.
+ */
+columns: Array<number>, spliter: string | null, delimiter: string | null, } } | { "AddBookmark": number } | { "RemoveBookmark": number } | { "SetBookmarks": Array<number> } | { "Abort": string } | "Subscribe" | "Unsubscribe";
+
+/**
+ * Command of the unbound session. Responds with `ServerResult::Encoded` containing
+ * `CommandOutcome` of the type the command returns in `UnboundSessionAPI`.
+ */
+export type UnboundRequest = { "ListFolderContent": { paths: Array<string>, depth: number, max_len: number, include_files: boolean, include_folders: boolean, } } | { "IsFileBinary": string } | { "DetectFileFormat": string } | { "GetFilesChronology": Array<string> } | { "CompareFiles": ComparisonOptions } | { "Grep": GrepOptions } | { "GetFileChecksum": string } | { "GetDltStats": Array<string> } | { "GetSomeipStatistic": Array<string> } | "GetSerialPortsList" | { "GetRegexError": SearchCondition } | { "Cancel": number };
@@ -14,6 +14,9 @@ impl std::fmt::Display for CallbackEvent {
     /// - `SearchMapUpdated` - Indicates that the search map has been updated.
     /// - `SearchValuesUpdated` - Indicates that search values have been updated.
     /// - `AttachmentsUpdated: {len}` - Displays the size of the updated attachment.
+    /// - `FileRotated: {source_id} at {pos}` - Displays the source and the position of the new segment.
+    /// - `TimeDistributionUpdated` - Indicates that the distribution of matches over time has been updated.
+    /// - `TriggerFired: {rule} at {row}` - Displays the rule and the position of the log entry.
     /// - `Progress` - Indicates progress for an operation.
     /// - `SessionError: {err:?}` - Displays details of a session error.
     /// - `OperationError: {uuid}: {error:?}` - Displays the UUID of the operation and the error details.
@@ -32,6 +35,13 @@ impl std::fmt::Display for CallbackEvent {
             Self::AttachmentsUpdated { len, attachment: _ } => {
                 write!(f, "AttachmentsUpdated: {len}")
             }
+            Self::FileRotated {
+                source_id,
+                pos,
+                truncated: _,
+            } => write!(f, "FileRotated: {source_id} at {pos}"),
+            Self::TimeDistributionUpdated(_) => write!(f, "TimeDistributionUpdated"),
+            Self::TriggerFired { rule, row } => write!(f, "TriggerFired: {rule} at {row}"),
             Self::Progress {
                 uuid: _,
                 progress: _,
@@ -88,6 +88,33 @@ pub enum CallbackEvent {
         attachment: AttachmentInfo,
     },
 
+    /// Triggered when a tailed file was truncated or replaced by a new file (e.g. rotated by
+    /// a logger). The content of the new file follows as a new segment of the same source.
+    FileRotated {
+        /// The identifier of the source the file belongs to.
+        source_id: u16,
+        /// The position of the first log entry of the new segment.
+        #[cfg_attr(all(test, feature = "test_and_gen"), ts(type = "number"))]
+        pos: u64,
+        /// `true` if the file was truncated, `false` if it was replaced by a new file.
+        truncated: bool,
+    },
+
+    /// Triggered after `SearchMapUpdated` once the distribution of search matches over time
+    /// was requested. Contains the counts of the new matches to add to the distribution,
+    /// in periods of the last requested length.
+    /// - `Vec<TimeBucket>`: The counts of the new matches of each filter.
+    TimeDistributionUpdated(Vec<TimeBucket>),
+
+    /// Triggered when a trigger rule with the `TriggerAction::Notify` action fires.
+    TriggerFired {
+        /// The position of the rule in the list of trigger rules.
+        rule: u32,
+        /// The position of the log entry which fired the rule.
+        #[cfg_attr(all(test, feature = "test_and_gen"), ts(type = "number"))]
+        row: u64,
+    },
+
     /// Triggered when progress is made during an operation.
     Progress {
         /// The unique identifier of the operation.
@@ -32,6 +32,9 @@ impl Arbitrary for CallbackEvent {
     /// - `SearchMapUpdated` with an optional `FilterMatchList`.
     /// - `SearchValuesUpdated` with a map of random values, converting `f32` to `f64`.
     /// - `AttachmentsUpdated` with random attachment information.
+    /// - `FileRotated` with random source, position and kind of the change.
+    /// - `TimeDistributionUpdated` with random periods of time.
+    /// - `TriggerFired` with random rule and position.
     /// - `Progress` with a random `Uuid` and `Progress` instance.
     /// - `SessionError` with a random `NativeError`.
     /// - `OperationError` with random `Uuid` and `NativeError`.
@@ -67,6 +70,19 @@ impl Arbitrary for CallbackEvent {
                     attachment,
                 }
             }),
+            (any::<u16>(), any::<u32>(), any::<bool>()).prop_map(|(source_id, pos, truncated)| {
+                CallbackEvent::FileRotated {
+                    source_id,
+                    pos: pos as u64,
+                    truncated,
+                }
+            }),
+            prop::collection::vec(any::<TimeBucket>(), 0..10)
+                .prop_map(CallbackEvent::TimeDistributionUpdated),
+            (any::<u32>(), any::<u32>()).prop_map(|(rule, row)| CallbackEvent::TriggerFired {
+                rule,
+                row: row as u64,
+            }),
             (Just(Uuid::new_v4()), any::<Progress>(),)
                 .prop_map(|(uuid, progress)| CallbackEvent::Progress { uuid, progress }),
             any::<NativeError>().prop_map(CallbackEvent::SessionError),
@@ -0,0 +1,61 @@
+#[cfg(feature = "nodejs")]
+mod nodejs;
+#[cfg(test)]
+mod proptest;
+
+use crate::*;
+
+/// The time range of the messages of a file. Timestamps are in milliseconds since the UNIX
+/// epoch and are `None` if no timestamps were found.
+#[derive(Clone, Serialize, Deserialize, Debug, PartialEq)]
+#[extend::encode_decode]
+#[cfg_attr(
+    all(test, feature = "test_and_gen"),
+    derive(TS),
+    ts(export, export_to = "command.ts")
+)]
+pub struct FileTimeRange {
+    /// Path to the file.
+    pub path: String,
+    /// Timestamp of the first message.
+    #[cfg_attr(all(test, feature = "test_and_gen"), ts(type = "number | null"))]
+    pub first: Option<u64>,
+    /// Timestamp of the last message.
+    #[cfg_attr(all(test, feature = "test_and_gen"), ts(type = "number | null"))]
+    pub last: Option<u64>,
+}
+
+/// Two files whose time ranges overlap. Concatenating them doesn't give a continuous
+/// timeline.
+#[derive(Clone, Serialize, Deserialize, Debug, PartialEq)]
+#[extend::encode_decode]
+#[cfg_attr(
+    all(test, feature = "test_and_gen"),
+    derive(TS),
+    ts(export, export_to = "command.ts")
+)]
+pub struct FilesOverlap {
+    /// Path to the file which starts earlier.
+    pub earlier: String,
+    /// Path to the file which starts before the earlier file ends.
+    pub later: String,
+    /// Duration of the overlap in milliseconds.
+    #[cfg_attr(all(test, feature = "test_and_gen"), ts(type = "number"))]
+    pub duration: u64,
+}
+
+/// Files ordered chronologically by the timestamps of their first messages. Files without
+/// timestamps keep their order and follow the files with timestamps.
+#[derive(Clone, Serialize, Deserialize, Debug, PartialEq)]
+#[extend::encode_decode]
+#[cfg_attr(
+    all(test, feature = "test_and_gen"),
+    derive(TS),
+    ts(export, export_to = "command.ts")
+)]
+pub struct FilesChronology {
+    /// Files in chronological order.
+    pub files: Vec<FileTimeRange>,
+    /// Overlapping files, which should be reported to the user as warnings.
+    pub overlaps: Vec<FilesOverlap>,
+}
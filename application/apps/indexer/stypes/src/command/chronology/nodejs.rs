@@ -0,0 +1,5 @@
+use crate::*;
+
+try_into_js!(FileTimeRange);
+try_into_js!(FilesOverlap);
+try_into_js!(FilesChronology);
@@ -0,0 +1,49 @@
+use crate::*;
+
+impl Arbitrary for FileTimeRange {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        (any::<String>(), any::<Option<u32>>(), any::<Option<u32>>())
+            .prop_map(|(path, first, last)| FileTimeRange {
+                path,
+                first: first.map(|v| v as u64),
+                last: last.map(|v| v as u64),
+            })
+            .boxed()
+    }
+}
+
+impl Arbitrary for FilesOverlap {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        (any::<String>(), any::<String>(), any::<u32>())
+            .prop_map(|(earlier, later, duration)| FilesOverlap {
+                earlier,
+                later,
+                duration: duration as u64,
+            })
+            .boxed()
+    }
+}
+
+impl Arbitrary for FilesChronology {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        (
+            prop::collection::vec(any::<FileTimeRange>(), 0..10),
+            prop::collection::vec(any::<FilesOverlap>(), 0..10),
+        )
+            .prop_map(|(files, overlaps)| FilesChronology { files, overlaps })
+            .boxed()
+    }
+}
+
+test_msg!(FileTimeRange, TESTS_USECASE_COUNT);
+test_msg!(FilesOverlap, TESTS_USECASE_COUNT);
+test_msg!(FilesChronology, TESTS_USECASE_COUNT);
@@ -0,0 +1,119 @@
+#[cfg(feature = "nodejs")]
+mod nodejs;
+#[cfg(test)]
+mod proptest;
+
+use crate::*;
+
+/// A rule of normalization of lines: all matches of the regex are replaced by the mask, so
+/// lines which differ only in variable parts (timestamps, numbers, IDs) become equal.
+#[derive(Clone, Serialize, Deserialize, Debug, PartialEq)]
+#[extend::encode_decode]
+#[cfg_attr(
+    all(test, feature = "test_and_gen"),
+    derive(TS),
+    ts(export, export_to = "command.ts")
+)]
+pub struct MaskRule {
+    /// Regular expression of the masked parts.
+    pub pattern: String,
+    /// Replacement of the masked parts, e.g. `<NUM>`.
+    pub mask: String,
+}
+
+/// Options of the comparison of two session files.
+#[derive(Clone, Serialize, Deserialize, Debug, PartialEq)]
+#[extend::encode_decode]
+#[cfg_attr(
+    all(test, feature = "test_and_gen"),
+    derive(TS),
+    ts(export, export_to = "command.ts")
+)]
+pub struct ComparisonOptions {
+    /// Path to the session file of the left side.
+    pub left: String,
+    /// Path to the session file of the right side.
+    pub right: String,
+    /// Rules applied to each line in the given order.
+    pub rules: Vec<MaskRule>,
+    /// Applies the default rules (masking timestamps, hex IDs and numbers) after `rules`.
+    pub defaults: bool,
+}
+
+/// A normalized line with its occurrences on both sides of a comparison.
+#[derive(Clone, Serialize, Deserialize, Debug)]
+#[extend::encode_decode]
+#[cfg_attr(
+    all(test, feature = "test_and_gen"),
+    derive(TS),
+    ts(export, export_to = "command.ts")
+)]
+pub struct ComparedLine {
+    /// The line after applying the mask rules.
+    pub line: String,
+    /// Count of the lines on the left side.
+    #[cfg_attr(all(test, feature = "test_and_gen"), ts(type = "number"))]
+    pub left: u64,
+    /// Count of the lines on the right side.
+    #[cfg_attr(all(test, feature = "test_and_gen"), ts(type = "number"))]
+    pub right: u64,
+    /// Inclusive ranges of rows on the left side, which can be passed to `grab_ranges` of
+    /// the left session. Might be truncated for frequent lines.
+    pub left_rows: Vec<Range>,
+    /// Inclusive ranges of rows on the right side, which can be passed to `grab_ranges` of
+    /// the right session. Might be truncated for frequent lines.
+    pub right_rows: Vec<Range>,
+}
+
+/// A region in which two aligned session files differ: `left_len` rows of the left side
+/// starting at `left_start` are replaced by `right_len` rows of the right side starting at
+/// `right_start`. A length of `0` means the rows of the other side were added.
+#[derive(Clone, Serialize, Deserialize, Debug, PartialEq)]
+#[extend::encode_decode]
+#[cfg_attr(
+    all(test, feature = "test_and_gen"),
+    derive(TS),
+    ts(export, export_to = "command.ts")
+)]
+pub struct DiffHunk {
+    /// First row of the region on the left side.
+    #[cfg_attr(all(test, feature = "test_and_gen"), ts(type = "number"))]
+    pub left_start: u64,
+    /// Count of rows of the region on the left side.
+    #[cfg_attr(all(test, feature = "test_and_gen"), ts(type = "number"))]
+    pub left_len: u64,
+    /// First row of the region on the right side.
+    #[cfg_attr(all(test, feature = "test_and_gen"), ts(type = "number"))]
+    pub right_start: u64,
+    /// Count of rows of the region on the right side.
+    #[cfg_attr(all(test, feature = "test_and_gen"), ts(type = "number"))]
+    pub right_len: u64,
+}
+
+/// Differences between two session files. The files are aligned in order by the longest
+/// common subsequence of their normalized lines, which gives the regions in which they
+/// differ. Besides, the lists of lines compare the normalized contents regardless of their
+/// order; they are ordered by the difference of counts.
+#[derive(Clone, Serialize, Deserialize, Debug)]
+#[extend::encode_decode]
+#[cfg_attr(
+    all(test, feature = "test_and_gen"),
+    derive(TS),
+    ts(export, export_to = "command.ts")
+)]
+pub struct FilesComparison {
+    /// Lines found on the left side only.
+    pub left_only: Vec<ComparedLine>,
+    /// Lines found on the right side only.
+    pub right_only: Vec<ComparedLine>,
+    /// Lines found on both sides, but a different number of times.
+    pub changed: Vec<ComparedLine>,
+    /// Count of distinct lines found on both sides the same number of times.
+    #[cfg_attr(all(test, feature = "test_and_gen"), ts(type = "number"))]
+    pub common: u64,
+    /// Regions in which the aligned files differ in ascending order of rows.
+    pub hunks: Vec<DiffHunk>,
+    /// `true` if lists or hunks were cut to their max length (1000 entries). Lists keep the
+    /// lines with the largest differences, hunks keep the first regions.
+    pub truncated: bool,
+}
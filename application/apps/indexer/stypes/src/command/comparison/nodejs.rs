@@ -0,0 +1,7 @@
+use crate::*;
+
+try_into_js!(MaskRule);
+try_into_js!(ComparisonOptions);
+try_into_js!(ComparedLine);
+try_into_js!(DiffHunk);
+try_into_js!(FilesComparison);
@@ -0,0 +1,105 @@
+use crate::*;
+
+impl Arbitrary for MaskRule {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        (any::<String>(), any::<String>())
+            .prop_map(|(pattern, mask)| MaskRule { pattern, mask })
+            .boxed()
+    }
+}
+
+impl Arbitrary for ComparisonOptions {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        (
+            any::<String>(),
+            any::<String>(),
+            prop::collection::vec(any::<MaskRule>(), 0..10),
+            any::<bool>(),
+        )
+            .prop_map(|(left, right, rules, defaults)| ComparisonOptions {
+                left,
+                right,
+                rules,
+                defaults,
+            })
+            .boxed()
+    }
+}
+
+impl Arbitrary for ComparedLine {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        (
+            any::<String>(),
+            any::<u32>(),
+            any::<u32>(),
+            prop::collection::vec(any::<Range>(), 0..10),
+            prop::collection::vec(any::<Range>(), 0..10),
+        )
+            .prop_map(|(line, left, right, left_rows, right_rows)| ComparedLine {
+                line,
+                left: left as u64,
+                right: right as u64,
+                left_rows,
+                right_rows,
+            })
+            .boxed()
+    }
+}
+
+impl Arbitrary for DiffHunk {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        (any::<u32>(), any::<u32>(), any::<u32>(), any::<u32>())
+            .prop_map(|(left_start, left_len, right_start, right_len)| DiffHunk {
+                left_start: left_start as u64,
+                left_len: left_len as u64,
+                right_start: right_start as u64,
+                right_len: right_len as u64,
+            })
+            .boxed()
+    }
+}
+
+impl Arbitrary for FilesComparison {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        (
+            prop::collection::vec(any::<ComparedLine>(), 0..10),
+            prop::collection::vec(any::<ComparedLine>(), 0..10),
+            prop::collection::vec(any::<ComparedLine>(), 0..10),
+            any::<u32>(),
+            prop::collection::vec(any::<DiffHunk>(), 0..10),
+            any::<bool>(),
+        )
+            .prop_map(
+                |(left_only, right_only, changed, common, hunks, truncated)| FilesComparison {
+                    left_only,
+                    right_only,
+                    changed,
+                    common: common as u64,
+                    hunks,
+                    truncated,
+                },
+            )
+            .boxed()
+    }
+}
+
+test_msg!(MaskRule, TESTS_USECASE_COUNT);
+test_msg!(ComparisonOptions, TESTS_USECASE_COUNT);
+test_msg!(ComparedLine, TESTS_USECASE_COUNT);
+test_msg!(DiffHunk, TESTS_USECASE_COUNT);
+test_msg!(FilesComparison, TESTS_USECASE_COUNT);
@@ -0,0 +1,60 @@
+#[cfg(feature = "nodejs")]
+mod nodejs;
+#[cfg(test)]
+mod proptest;
+
+use crate::*;
+
+/// The parser suggested for the content of a file.
+#[derive(Clone, Serialize, Deserialize, Debug, PartialEq)]
+#[extend::encode_decode]
+#[cfg_attr(
+    all(test, feature = "test_and_gen"),
+    derive(TS),
+    ts(export, export_to = "command.ts")
+)]
+pub enum DetectedParser {
+    Dlt,
+    SomeIp,
+    Can,
+    Text,
+}
+
+/// The encoding of text content.
+#[derive(Clone, Serialize, Deserialize, Debug, PartialEq)]
+#[extend::encode_decode]
+#[cfg_attr(
+    all(test, feature = "test_and_gen"),
+    derive(TS),
+    ts(export, export_to = "command.ts")
+)]
+pub enum TextEncoding {
+    Utf8,
+    Utf16Le,
+    Utf16Be,
+    /// Single byte encoding, which isn't valid UTF-8.
+    Latin1,
+}
+
+/// A suggestion on how to open a file, based on its magic numbers and a sample of its content.
+#[derive(Clone, Serialize, Deserialize, Debug)]
+#[extend::encode_decode]
+#[cfg_attr(
+    all(test, feature = "test_and_gen"),
+    derive(TS),
+    ts(export, export_to = "command.ts")
+)]
+pub struct FormatDetection {
+    /// The suggested format of the file.
+    pub format: FileFormat,
+    /// The suggested parser, if the content could be recognized.
+    pub parser: Option<DetectedParser>,
+    /// Whether DLT messages are preceded by storage headers. Set only for the DLT parser.
+    pub with_storage_header: Option<bool>,
+    /// The encoding of text content. Set only for the text parser.
+    pub encoding: Option<TextEncoding>,
+    /// Whether the file is compressed or an entry of an archive.
+    pub compressed: bool,
+    /// The confidence in the suggestion in percent.
+    pub confidence: u8,
+}
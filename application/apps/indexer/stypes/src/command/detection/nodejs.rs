@@ -0,0 +1,5 @@
+use crate::*;
+
+try_into_js!(DetectedParser);
+try_into_js!(TextEncoding);
+try_into_js!(FormatDetection);
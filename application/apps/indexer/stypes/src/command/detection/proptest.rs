@@ -0,0 +1,64 @@
+use crate::*;
+
+impl Arbitrary for DetectedParser {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        prop_oneof![
+            Just(DetectedParser::Dlt),
+            Just(DetectedParser::SomeIp),
+            Just(DetectedParser::Can),
+            Just(DetectedParser::Text),
+        ]
+        .boxed()
+    }
+}
+
+impl Arbitrary for TextEncoding {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        prop_oneof![
+            Just(TextEncoding::Utf8),
+            Just(TextEncoding::Utf16Le),
+            Just(TextEncoding::Utf16Be),
+            Just(TextEncoding::Latin1),
+        ]
+        .boxed()
+    }
+}
+
+impl Arbitrary for FormatDetection {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        (
+            any::<FileFormat>(),
+            any::<Option<DetectedParser>>(),
+            any::<Option<bool>>(),
+            any::<Option<TextEncoding>>(),
+            any::<bool>(),
+            0..=100u8,
+        )
+            .prop_map(
+                |(format, parser, with_storage_header, encoding, compressed, confidence)| {
+                    FormatDetection {
+                        format,
+                        parser,
+                        with_storage_header,
+                        encoding,
+                        compressed,
+                        confidence,
+                    }
+                },
+            )
+            .boxed()
+    }
+}
+
+test_msg!(DetectedParser, TESTS_USECASE_COUNT);
+test_msg!(TextEncoding, TESTS_USECASE_COUNT);
+test_msg!(FormatDetection, TESTS_USECASE_COUNT);
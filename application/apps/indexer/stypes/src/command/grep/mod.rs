@@ -0,0 +1,65 @@
+#[cfg(feature = "nodejs")]
+mod nodejs;
+#[cfg(test)]
+mod proptest;
+
+use crate::*;
+
+/// Options of the search of lines across files without opening sessions.
+#[derive(Clone, Serialize, Deserialize, Debug)]
+#[extend::encode_decode]
+#[cfg_attr(
+    all(test, feature = "test_and_gen"),
+    derive(TS),
+    ts(export, export_to = "command.ts")
+)]
+pub struct GrepOptions {
+    /// Paths to files or folders. Folders are scanned recursively.
+    pub paths: Vec<String>,
+    /// Conditions of the search; a line matches if it matches at least one of them.
+    pub patterns: Vec<SearchCondition>,
+    /// Count of matches to skip.
+    #[cfg_attr(all(test, feature = "test_and_gen"), ts(type = "number"))]
+    pub offset: u64,
+    /// Max count of matches to return.
+    #[cfg_attr(all(test, feature = "test_and_gen"), ts(type = "number"))]
+    pub limit: u64,
+}
+
+/// A line matched by the search across files.
+#[derive(Clone, Serialize, Deserialize, Debug)]
+#[extend::encode_decode]
+#[cfg_attr(
+    all(test, feature = "test_and_gen"),
+    derive(TS),
+    ts(export, export_to = "command.ts")
+)]
+pub struct GrepMatch {
+    /// Path to the file.
+    pub file: String,
+    /// Zero-based number of the line. For DLT files it's the number of the message.
+    #[cfg_attr(all(test, feature = "test_and_gen"), ts(type = "number"))]
+    pub line: u64,
+    /// Positions of the matched conditions in `GrepOptions::patterns`.
+    pub patterns: Vec<u32>,
+    /// Content of the line.
+    pub content: String,
+}
+
+/// A page of the lines matched by the search across files.
+#[derive(Clone, Serialize, Deserialize, Debug)]
+#[extend::encode_decode]
+#[cfg_attr(
+    all(test, feature = "test_and_gen"),
+    derive(TS),
+    ts(export, export_to = "command.ts")
+)]
+pub struct GrepResult {
+    /// Matched lines in the order of files and lines.
+    pub matches: Vec<GrepMatch>,
+    /// `true` if there are more matches after this page.
+    pub more: bool,
+    /// Files which couldn't be searched, with the reason. Binary files are searched only if
+    /// they are recognized as DLT.
+    pub skipped: Vec<String>,
+}
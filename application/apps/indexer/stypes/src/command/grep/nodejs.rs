@@ -0,0 +1,5 @@
+use crate::*;
+
+try_into_js!(GrepOptions);
+try_into_js!(GrepMatch);
+try_into_js!(GrepResult);
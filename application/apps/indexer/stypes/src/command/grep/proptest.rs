@@ -0,0 +1,66 @@
+use crate::*;
+
+impl Arbitrary for GrepOptions {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        (
+            prop::collection::vec(any::<String>(), 0..10),
+            prop::collection::vec(any::<SearchCondition>(), 0..10),
+            any::<u32>(),
+            any::<u32>(),
+        )
+            .prop_map(|(paths, patterns, offset, limit)| GrepOptions {
+                paths,
+                patterns,
+                offset: offset as u64,
+                limit: limit as u64,
+            })
+            .boxed()
+    }
+}
+
+impl Arbitrary for GrepMatch {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        (
+            any::<String>(),
+            any::<u32>(),
+            prop::collection::vec(any::<u32>(), 0..10),
+            any::<String>(),
+        )
+            .prop_map(|(file, line, patterns, content)| GrepMatch {
+                file,
+                line: line as u64,
+                patterns,
+                content,
+            })
+            .boxed()
+    }
+}
+
+impl Arbitrary for GrepResult {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        (
+            prop::collection::vec(any::<GrepMatch>(), 0..10),
+            any::<bool>(),
+            prop::collection::vec(any::<String>(), 0..10),
+        )
+            .prop_map(|(matches, more, skipped)| GrepResult {
+                matches,
+                more,
+                skipped,
+            })
+            .boxed()
+    }
+}
+
+test_msg!(GrepOptions, TESTS_USECASE_COUNT);
+test_msg!(GrepMatch, TESTS_USECASE_COUNT);
+test_msg!(GrepResult, TESTS_USECASE_COUNT);
@@ -7,13 +7,21 @@ mod proptest;
 #[cfg(test)]
 mod ts;
 
+mod chronology;
+mod comparison;
+mod detection;
 mod dltstat;
 mod folders;
+mod grep;
 mod profiles;
 mod serial;
 
+pub use chronology::*;
+pub use comparison::*;
+pub use detection::*;
 pub use dltstat::*;
 pub use folders::*;
+pub use grep::*;
 pub use profiles::*;
 pub use serial::*;
 
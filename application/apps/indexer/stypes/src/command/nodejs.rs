@@ -5,6 +5,10 @@ try_into_js!(CommandOutcome<SerialPortsList>);
 try_into_js!(CommandOutcome<ProfileList>);
 try_into_js!(CommandOutcome<MapKeyValue>);
 try_into_js!(CommandOutcome<DltStatisticInfo>);
+try_into_js!(CommandOutcome<FormatDetection>);
+try_into_js!(CommandOutcome<FilesChronology>);
+try_into_js!(CommandOutcome<FilesComparison>);
+try_into_js!(CommandOutcome<GrepResult>);
 try_into_js!(CommandOutcome<()>);
 try_into_js!(CommandOutcome<i64>);
 try_into_js!(CommandOutcome<Option<String>>);
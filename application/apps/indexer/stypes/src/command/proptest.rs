@@ -95,6 +95,82 @@ impl Arbitrary for CommandOutcome<DltStatisticInfo> {
         .boxed()
     }
 }
+impl Arbitrary for CommandOutcome<FormatDetection> {
+    /// Implements the `Arbitrary` trait for `CommandOutcome<FormatDetection>` to generate random instances.
+    ///
+    /// # Details
+    /// - Generates either:
+    ///   - `CommandOutcome::Finished` with a random `FormatDetection`.
+    ///   - `CommandOutcome::Cancelled`.
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        prop_oneof![
+            any::<FormatDetection>().prop_map(CommandOutcome::Finished),
+            Just(CommandOutcome::Cancelled),
+        ]
+        .boxed()
+    }
+}
+
+impl Arbitrary for CommandOutcome<FilesChronology> {
+    /// Implements the `Arbitrary` trait for `CommandOutcome<FilesChronology>` to generate random instances.
+    ///
+    /// # Details
+    /// - Generates either:
+    ///   - `CommandOutcome::Finished` with a random `FilesChronology`.
+    ///   - `CommandOutcome::Cancelled`.
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        prop_oneof![
+            any::<FilesChronology>().prop_map(CommandOutcome::Finished),
+            Just(CommandOutcome::Cancelled),
+        ]
+        .boxed()
+    }
+}
+
+impl Arbitrary for CommandOutcome<FilesComparison> {
+    /// Implements the `Arbitrary` trait for `CommandOutcome<FilesComparison>` to generate random instances.
+    ///
+    /// # Details
+    /// - Generates either:
+    ///   - `CommandOutcome::Finished` with a random `FilesComparison`.
+    ///   - `CommandOutcome::Cancelled`.
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        prop_oneof![
+            any::<FilesComparison>().prop_map(CommandOutcome::Finished),
+            Just(CommandOutcome::Cancelled),
+        ]
+        .boxed()
+    }
+}
+
+impl Arbitrary for CommandOutcome<GrepResult> {
+    /// Implements the `Arbitrary` trait for `CommandOutcome<GrepResult>` to generate random instances.
+    ///
+    /// # Details
+    /// - Generates either:
+    ///   - `CommandOutcome::Finished` with a random `GrepResult`.
+    ///   - `CommandOutcome::Cancelled`.
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        prop_oneof![
+            any::<GrepResult>().prop_map(CommandOutcome::Finished),
+            Just(CommandOutcome::Cancelled),
+        ]
+        .boxed()
+    }
+}
+
 impl Arbitrary for CommandOutcome<()> {
     /// Implements the `Arbitrary` trait for `CommandOutcome<()>` to generate random instances.
     ///
@@ -192,4 +268,8 @@ test_msg!(CommandOutcome<SerialPortsList>, TESTS_USECASE_COUNT);
 test_msg!(CommandOutcome<String>, TESTS_USECASE_COUNT);
 test_msg!(CommandOutcome<FoldersScanningResult>, TESTS_USECASE_COUNT);
 test_msg!(CommandOutcome<DltStatisticInfo>, TESTS_USECASE_COUNT);
+test_msg!(CommandOutcome<FormatDetection>, TESTS_USECASE_COUNT);
+test_msg!(CommandOutcome<FilesChronology>, TESTS_USECASE_COUNT);
+test_msg!(CommandOutcome<FilesComparison>, TESTS_USECASE_COUNT);
+test_msg!(CommandOutcome<GrepResult>, TESTS_USECASE_COUNT);
 test_msg!(CommandOutcome<ProfileList>, TESTS_USECASE_COUNT);
@@ -16,6 +16,70 @@ pub enum CommandOutcomeDltStatisticInfoResult {
     Cancelled,
 }
 
+/// Represents the result of a command execution.
+/// At the core level, this type is used for all commands invoked within an `UnboundSession`.
+/// It is only used to indicate the successful completion or interruption of a command.
+#[derive(Clone, Serialize, Deserialize, Debug)]
+#[cfg_attr(
+    all(test, feature = "test_and_gen"),
+    derive(TS),
+    ts(export, export_to = "command.ts")
+)]
+pub enum CommandOutcomeFormatDetectionResult {
+    /// Indicates that the command was successfully completed.
+    Finished(FormatDetection),
+    /// Indicates that the command execution was interrupted.
+    Cancelled,
+}
+
+/// Represents the result of a command execution.
+/// At the core level, this type is used for all commands invoked within an `UnboundSession`.
+/// It is only used to indicate the successful completion or interruption of a command.
+#[derive(Clone, Serialize, Deserialize, Debug)]
+#[cfg_attr(
+    all(test, feature = "test_and_gen"),
+    derive(TS),
+    ts(export, export_to = "command.ts")
+)]
+pub enum CommandOutcomeFilesChronologyResult {
+    /// Indicates that the command was successfully completed.
+    Finished(FilesChronology),
+    /// Indicates that the command execution was interrupted.
+    Cancelled,
+}
+
+/// Represents the result of a command execution.
+/// At the core level, this type is used for all commands invoked within an `UnboundSession`.
+/// It is only used to indicate the successful completion or interruption of a command.
+#[derive(Clone, Serialize, Deserialize, Debug)]
+#[cfg_attr(
+    all(test, feature = "test_and_gen"),
+    derive(TS),
+    ts(export, export_to = "command.ts")
+)]
+pub enum CommandOutcomeFilesComparisonResult {
+    /// Indicates that the command was successfully completed.
+    Finished(FilesComparison),
+    /// Indicates that the command execution was interrupted.
+    Cancelled,
+}
+
+/// Represents the result of a command execution.
+/// At the core level, this type is used for all commands invoked within an `UnboundSession`.
+/// It is only used to indicate the successful completion or interruption of a command.
+#[derive(Clone, Serialize, Deserialize, Debug)]
+#[cfg_attr(
+    all(test, feature = "test_and_gen"),
+    derive(TS),
+    ts(export, export_to = "command.ts")
+)]
+pub enum CommandOutcomeGrepResult {
+    /// Indicates that the command was successfully completed.
+    Finished(GrepResult),
+    /// Indicates that the command execution was interrupted.
+    Cancelled,
+}
+
 /// Represents the result of a command execution.
 /// At the core level, this type is used for all commands invoked within an `UnboundSession`.
 /// It is only used to indicate the successful completion or interruption of a command.
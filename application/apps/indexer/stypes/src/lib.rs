@@ -102,6 +102,7 @@ mod observe;
 mod operations;
 mod plugins;
 mod progress;
+mod server;
 
 pub use attachment::*;
 pub use callback::*;
@@ -113,6 +114,7 @@ pub use observe::*;
 pub use operations::*;
 pub use plugins::*;
 pub use progress::*;
+pub use server::*;
 
 pub(crate) use serde::{Deserialize, Serialize, de::DeserializeOwned};
 pub(crate) use std::{collections::HashMap, path::PathBuf};
@@ -57,6 +57,9 @@ pub struct SourceDefinition {
     pub id: u16,
     /// The user-friendly name of the source for display purposes.
     pub alias: String,
+    /// The number of log entries of the source in the session.
+    #[cfg_attr(all(test, feature = "test_and_gen"), ts(type = "number"))]
+    pub count: u64,
 }
 
 /// A list of data sources.
@@ -120,6 +123,8 @@ pub struct GrabbedElement {
     /// The nature of the log entry, represented as a bitmask. Possible values include:
     /// - `SEARCH`: Nature = Nature(1)
     /// - `BOOKMARK`: Nature = Nature(1 << 1)
+    /// - `FOLDED`: Nature = Nature(1 << 2)
+    /// - `UNFOLDED`: Nature = Nature(1 << 3)
     /// - `EXPANDED`: Nature = Nature(1 << 5)
     /// - `BREADCRUMB`: Nature = Nature(1 << 6)
     /// - `BREADCRUMB_SEPARATOR`: Nature = Nature(1 << 7)
@@ -151,6 +156,39 @@ pub struct GrabbedElementList(pub Vec<GrabbedElement>);
 )]
 pub struct AroundIndexes(pub (Option<u64>, Option<u64>));
 
+/// Consecutive repeated log entries folded into one entry of the indexed map.
+#[derive(Clone, Serialize, Deserialize, Debug)]
+#[extend::encode_decode]
+#[cfg_attr(
+    all(test, feature = "test_and_gen"),
+    derive(TS),
+    ts(export, export_to = "miscellaneous.ts")
+)]
+pub struct Fold {
+    /// The log entry shown instead of the folded ones.
+    #[cfg_attr(all(test, feature = "test_and_gen"), ts(type = "number"))]
+    pub row: u64,
+    /// The count of folded log entries including the shown one.
+    #[cfg_attr(all(test, feature = "test_and_gen"), ts(type = "number"))]
+    pub count: u64,
+    /// The timestamp of the first log entry if timestamps are indexed.
+    #[cfg_attr(all(test, feature = "test_and_gen"), ts(type = "number | null"))]
+    pub first: Option<u64>,
+    /// The timestamp of the last log entry if timestamps are indexed.
+    #[cfg_attr(all(test, feature = "test_and_gen"), ts(type = "number | null"))]
+    pub last: Option<u64>,
+}
+
+/// A list of folds.
+#[derive(Clone, Serialize, Deserialize, Debug)]
+#[extend::encode_decode]
+#[cfg_attr(
+    all(test, feature = "test_and_gen"),
+    derive(TS),
+    ts(export, export_to = "miscellaneous.ts")
+)]
+pub struct FoldList(pub Vec<Fold>);
+
 /// Describes a match for a search condition.
 #[derive(Clone, Serialize, Deserialize, Debug)]
 #[extend::encode_decode]
@@ -177,3 +215,139 @@ pub struct FilterMatch {
     ts(export, export_to = "miscellaneous.ts")
 )]
 pub struct FilterMatchList(pub Vec<FilterMatch>);
+
+/// Settings of extracting timestamps from the log entries of a text session.
+#[derive(Clone, Serialize, Deserialize, Debug)]
+#[extend::encode_decode]
+#[cfg_attr(
+    all(test, feature = "test_and_gen"),
+    derive(TS),
+    ts(export, export_to = "miscellaneous.ts")
+)]
+pub struct TimestampSettings {
+    /// The format of timestamps, e.g. `%Y-%m-%d %H:%M:%S%.3f`. Common formats are
+    /// detected if it isn't defined.
+    pub format: Option<String>,
+    /// The time zone of timestamps without offset, e.g. `Europe/Berlin`. UTC is used
+    /// if it isn't defined.
+    pub tz: Option<String>,
+    /// The year of timestamps without year. If it isn't defined, the year the source file was
+    /// modified is used (the current year for streams).
+    pub year: Option<i32>,
+}
+
+/// Condition of a trigger rule, which is checked for each log entry written into the session.
+#[derive(Clone, Serialize, Deserialize, Debug)]
+#[extend::encode_decode]
+#[cfg_attr(
+    all(test, feature = "test_and_gen"),
+    derive(TS),
+    ts(export, export_to = "miscellaneous.ts")
+)]
+pub enum TriggerCondition {
+    /// The log entry matches the search condition.
+    Filter(SearchCondition),
+    /// A value extracted from the log entry leaves the range defined by the thresholds. The
+    /// value is taken from the first capture group of the regex like in the extraction of
+    /// search values. The rule fires once the value leaves the range and again only after
+    /// the value was back in the range.
+    Threshold {
+        /// The regex extracting the value.
+        filter: String,
+        /// The rule fires if the value is greater than this threshold.
+        above: Option<f64>,
+        /// The rule fires if the value is less than this threshold.
+        below: Option<f64>,
+    },
+}
+
+/// Action executed when a trigger rule fires.
+#[derive(Clone, Serialize, Deserialize, Debug)]
+#[extend::encode_decode]
+#[cfg_attr(
+    all(test, feature = "test_and_gen"),
+    derive(TS),
+    ts(export, export_to = "miscellaneous.ts")
+)]
+pub enum TriggerAction {
+    /// Emits `CallbackEvent::TriggerFired`.
+    Notify,
+    /// Bookmarks the log entry.
+    Bookmark,
+    /// Stops the observe operation after the given number of further log entries.
+    #[cfg_attr(all(test, feature = "test_and_gen"), ts(type = "number"))]
+    StopAfter(u64),
+    /// Sends the request to the source, if the source supports feedback.
+    SendToSource(SdeRequest),
+}
+
+/// A rule checked on each log entry while a source is observed.
+#[derive(Clone, Serialize, Deserialize, Debug)]
+#[extend::encode_decode]
+#[cfg_attr(
+    all(test, feature = "test_and_gen"),
+    derive(TS),
+    ts(export, export_to = "miscellaneous.ts")
+)]
+pub struct TriggerRule {
+    /// The condition firing the rule.
+    pub condition: TriggerCondition,
+    /// The actions executed when the rule fires.
+    pub actions: Vec<TriggerAction>,
+}
+
+/// A list of trigger rules. Rules are identified by their positions in the list.
+#[derive(Clone, Serialize, Deserialize, Debug)]
+#[extend::encode_decode]
+#[cfg_attr(
+    all(test, feature = "test_and_gen"),
+    derive(TS),
+    ts(export, export_to = "miscellaneous.ts")
+)]
+pub struct TriggerRules(pub Vec<TriggerRule>);
+
+/// A step of a sequence rule.
+#[derive(Clone, Serialize, Deserialize, Debug)]
+#[extend::encode_decode]
+#[cfg_attr(
+    all(test, feature = "test_and_gen"),
+    derive(TS),
+    ts(export, export_to = "miscellaneous.ts")
+)]
+pub struct SequenceStep {
+    /// The condition the log entry of the step matches.
+    pub condition: SearchCondition,
+    /// Max count of rows from the previous step. Not checked for the first step.
+    #[cfg_attr(all(test, feature = "test_and_gen"), ts(type = "number | null"))]
+    pub max_rows: Option<u64>,
+    /// Max time in ms from the previous step. Not checked for the first step and for log
+    /// entries without timestamps.
+    #[cfg_attr(all(test, feature = "test_and_gen"), ts(type = "number | null"))]
+    pub max_ms: Option<u64>,
+}
+
+/// Ordered steps which have to follow each other within their max gaps. Each log entry
+/// matching the first step starts a new instance of the rule.
+#[derive(Clone, Serialize, Deserialize, Debug)]
+#[extend::encode_decode]
+#[cfg_attr(
+    all(test, feature = "test_and_gen"),
+    derive(TS),
+    ts(export, export_to = "miscellaneous.ts")
+)]
+pub struct SequenceRule {
+    /// The steps in the expected order.
+    pub steps: Vec<SequenceStep>,
+    /// Conditions of log entries which must not occur between the first and the last step.
+    pub forbidden: Vec<SearchCondition>,
+}
+
+/// A list of sequence rules. Rules are identified by their positions in the list.
+#[derive(Clone, Serialize, Deserialize, Debug)]
+#[extend::encode_decode]
+#[cfg_attr(
+    all(test, feature = "test_and_gen"),
+    derive(TS),
+    ts(export, export_to = "miscellaneous.ts")
+)]
+pub struct SequenceRules(pub Vec<SequenceRule>);
@@ -8,5 +8,15 @@ try_into_js!(Sources);
 try_into_js!(SdeRequest);
 try_into_js!(SdeResponse);
 try_into_js!(AroundIndexes);
+try_into_js!(Fold);
+try_into_js!(FoldList);
 try_into_js!(FilterMatch);
 try_into_js!(FilterMatchList);
+try_into_js!(TimestampSettings);
+try_into_js!(TriggerCondition);
+try_into_js!(TriggerAction);
+try_into_js!(TriggerRule);
+try_into_js!(TriggerRules);
+try_into_js!(SequenceStep);
+try_into_js!(SequenceRule);
+try_into_js!(SequenceRules);
@@ -39,13 +39,18 @@ impl Arbitrary for SourceDefinition {
     /// Implements the `Arbitrary` trait for `SourceDefinition` to generate random instances.
     ///
     /// # Details
-    /// - Generates random `id` (`u16`) and `alias` (`String`) values.
+    /// - Generates random `id` (`u16`), `alias` (`String`) and `count` (`u32`, mapped to
+    ///   `u64`) values.
     type Parameters = ();
     type Strategy = BoxedStrategy<Self>;
 
     fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
-        (any::<u16>(), any::<String>())
-            .prop_map(|(id, alias)| SourceDefinition { id, alias })
+        (any::<u16>(), any::<String>(), any::<u32>())
+            .prop_map(|(id, alias, count)| SourceDefinition {
+                id,
+                alias,
+                count: count as u64,
+            })
             .boxed()
     }
 }
@@ -156,6 +161,40 @@ impl Arbitrary for AroundIndexes {
     }
 }
 
+impl Arbitrary for Fold {
+    /// Implements the `Arbitrary` trait for `Fold` to generate random instances.
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        (
+            any::<u32>(),
+            any::<u32>(),
+            prop::option::of(any::<u32>()),
+            prop::option::of(any::<u32>()),
+        )
+            .prop_map(|(row, count, first, last)| Fold {
+                row: row as u64,
+                count: count as u64,
+                first: first.map(|v| v as u64),
+                last: last.map(|v| v as u64),
+            })
+            .boxed()
+    }
+}
+
+impl Arbitrary for FoldList {
+    /// Implements the `Arbitrary` trait for `FoldList` to generate random instances.
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        prop::collection::vec(any::<Fold>(), 0..10)
+            .prop_map(FoldList)
+            .boxed()
+    }
+}
+
 impl Arbitrary for FilterMatch {
     /// Implements the `Arbitrary` trait for `FilterMatch` to generate random instances.
     ///
@@ -191,6 +230,135 @@ impl Arbitrary for FilterMatchList {
     }
 }
 
+impl Arbitrary for TimestampSettings {
+    /// Implements the `Arbitrary` trait for `TimestampSettings` to generate random instances.
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        (
+            prop::option::of(any::<String>()),
+            prop::option::of(any::<String>()),
+            prop::option::of(any::<i32>()),
+        )
+            .prop_map(|(format, tz, year)| TimestampSettings { format, tz, year })
+            .boxed()
+    }
+}
+
+impl Arbitrary for TriggerCondition {
+    /// Implements the `Arbitrary` trait for `TriggerCondition` to generate random instances.
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        prop_oneof![
+            any::<SearchCondition>().prop_map(TriggerCondition::Filter),
+            (
+                any::<String>(),
+                prop::option::of(any::<f32>()),
+                prop::option::of(any::<f32>()),
+            )
+                .prop_map(|(filter, above, below)| TriggerCondition::Threshold {
+                    filter,
+                    above: above.map(|v| v as f64),
+                    below: below.map(|v| v as f64),
+                }),
+        ]
+        .boxed()
+    }
+}
+
+impl Arbitrary for TriggerAction {
+    /// Implements the `Arbitrary` trait for `TriggerAction` to generate random instances.
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        prop_oneof![
+            Just(TriggerAction::Notify),
+            Just(TriggerAction::Bookmark),
+            any::<u32>().prop_map(|n| TriggerAction::StopAfter(n as u64)),
+            any::<SdeRequest>().prop_map(TriggerAction::SendToSource),
+        ]
+        .boxed()
+    }
+}
+
+impl Arbitrary for TriggerRule {
+    /// Implements the `Arbitrary` trait for `TriggerRule` to generate random instances.
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        (
+            any::<TriggerCondition>(),
+            prop::collection::vec(any::<TriggerAction>(), 0..5),
+        )
+            .prop_map(|(condition, actions)| TriggerRule { condition, actions })
+            .boxed()
+    }
+}
+
+impl Arbitrary for TriggerRules {
+    /// Implements the `Arbitrary` trait for `TriggerRules` to generate random instances.
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        prop::collection::vec(any::<TriggerRule>(), 0..5)
+            .prop_map(TriggerRules)
+            .boxed()
+    }
+}
+
+impl Arbitrary for SequenceStep {
+    /// Implements the `Arbitrary` trait for `SequenceStep` to generate random instances.
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        (
+            any::<SearchCondition>(),
+            prop::option::of(any::<u32>()),
+            prop::option::of(any::<u32>()),
+        )
+            .prop_map(|(condition, max_rows, max_ms)| SequenceStep {
+                condition,
+                max_rows: max_rows.map(|v| v as u64),
+                max_ms: max_ms.map(|v| v as u64),
+            })
+            .boxed()
+    }
+}
+
+impl Arbitrary for SequenceRule {
+    /// Implements the `Arbitrary` trait for `SequenceRule` to generate random instances.
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        (
+            prop::collection::vec(any::<SequenceStep>(), 0..5),
+            prop::collection::vec(any::<SearchCondition>(), 0..5),
+        )
+            .prop_map(|(steps, forbidden)| SequenceRule { steps, forbidden })
+            .boxed()
+    }
+}
+
+impl Arbitrary for SequenceRules {
+    /// Implements the `Arbitrary` trait for `SequenceRules` to generate random instances.
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        prop::collection::vec(any::<SequenceRule>(), 0..5)
+            .prop_map(SequenceRules)
+            .boxed()
+    }
+}
+
 test_msg!(SourceDefinition, TESTS_USECASE_COUNT);
 test_msg!(Sources, TESTS_USECASE_COUNT);
 test_msg!(SdeRequest, TESTS_USECASE_COUNT);
@@ -198,5 +366,15 @@ test_msg!(SdeResponse, TESTS_USECASE_COUNT);
 test_msg!(GrabbedElement, TESTS_USECASE_COUNT);
 test_msg!(GrabbedElementList, TESTS_USECASE_COUNT);
 test_msg!(AroundIndexes, TESTS_USECASE_COUNT);
+test_msg!(Fold, TESTS_USECASE_COUNT);
+test_msg!(FoldList, TESTS_USECASE_COUNT);
 test_msg!(FilterMatch, TESTS_USECASE_COUNT);
 test_msg!(FilterMatchList, TESTS_USECASE_COUNT);
+test_msg!(TimestampSettings, TESTS_USECASE_COUNT);
+test_msg!(TriggerCondition, TESTS_USECASE_COUNT);
+test_msg!(TriggerAction, TESTS_USECASE_COUNT);
+test_msg!(TriggerRule, TESTS_USECASE_COUNT);
+test_msg!(TriggerRules, TESTS_USECASE_COUNT);
+test_msg!(SequenceStep, TESTS_USECASE_COUNT);
+test_msg!(SequenceRule, TESTS_USECASE_COUNT);
+test_msg!(SequenceRules, TESTS_USECASE_COUNT);
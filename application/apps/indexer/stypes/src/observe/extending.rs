@@ -17,6 +17,8 @@ impl ObserveOptions {
         ObserveOptions {
             origin: ObserveOrigin::File(Uuid::new_v4().to_string(), file_origin, filename),
             parser,
+            network_filter: None,
+            order_by_time: None,
         }
     }
 }
@@ -27,15 +29,21 @@ impl Default for DltParserSettings {
     /// # Defaults
     /// - `filter_config`: `None`
     /// - `fibex_file_paths`: `None`
+    /// - `arxml_file_paths`: `None`
+    /// - `dbc_file_paths`: `None`
     /// - `with_storage_header`: `true`
     /// - `tz`: `None`
+    /// - `detect_lifecycles`: `false`
     /// - `fibex_metadata`: `None`
     fn default() -> Self {
         Self {
             filter_config: None,
             fibex_file_paths: None,
+            arxml_file_paths: None,
+            dbc_file_paths: None,
             with_storage_header: true,
             tz: None,
+            detect_lifecycles: false,
             fibex_metadata: None,
         }
     }
@@ -57,8 +65,11 @@ impl DltParserSettings {
         Self {
             filter_config,
             fibex_file_paths,
+            arxml_file_paths: None,
+            dbc_file_paths: None,
             with_storage_header: true,
             tz: None,
+            detect_lifecycles: false,
             fibex_metadata: None,
         }
     }
@@ -94,6 +105,35 @@ pub enum NetError {
     Io(#[from] std::io::Error),
 }
 
+impl NetworkFilter {
+    /// Parses the IP address of the sender.
+    ///
+    /// # Returns
+    /// - `Ok(None)` if no address is defined.
+    /// - `Err(NetError::Configuration)` if the address cannot be parsed.
+    pub fn source_addr(&self) -> Result<Option<IpAddr>, NetError> {
+        parse_ip(self.source_ip.as_deref())
+    }
+
+    /// Parses the IP address of the receiver.
+    ///
+    /// # Returns
+    /// - `Ok(None)` if no address is defined.
+    /// - `Err(NetError::Configuration)` if the address cannot be parsed.
+    pub fn destination_addr(&self) -> Result<Option<IpAddr>, NetError> {
+        parse_ip(self.destination_ip.as_deref())
+    }
+}
+
+fn parse_ip(addr: Option<&str>) -> Result<Option<IpAddr>, NetError> {
+    addr.map(|addr| {
+        addr.parse().map_err(|e| {
+            NetError::Configuration(format!("Could not parse IP address \"{addr}\": {e}"))
+        })
+    })
+    .transpose()
+}
+
 impl MulticastInfo {
     /// Parses the multicast address into an `IpAddr`.
     ///
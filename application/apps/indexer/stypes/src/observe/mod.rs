@@ -58,6 +58,8 @@ pub enum ParserType {
     Text(()),
     /// Parser using plugins system.
     Plugin(PluginParserSettings),
+    /// CAN parser for `candump -l`, ASC and BLF logs or SocketCAN streams.
+    Can(CanParserSettings),
 }
 
 /// Settings for the DLT parser.
@@ -72,12 +74,22 @@ pub struct DltParserSettings {
     /// Configuration for filtering DLT messages.
     #[cfg_attr(all(test, feature = "test_and_gen"), ts(type = "DltFilterConfig"))]
     pub filter_config: Option<DltFilterConfig>,
-    /// Paths to FIBEX files for additional interpretation of `payload` content.
+    /// Paths to FIBEX files for additional interpretation of `payload` content. FlexRay
+    /// network trace payloads are decoded with the FlexRay frames of these files.
     pub fibex_file_paths: Option<Vec<String>>,
+    /// Paths to AUTOSAR ARXML files describing non-verbose messages. Used for messages which
+    /// aren't described by the FIBEX files.
+    pub arxml_file_paths: Option<Vec<String>>,
+    /// Paths to DBC files used to decode CAN network trace payloads into
+    /// messages and signals.
+    pub dbc_file_paths: Option<Vec<String>>,
     /// Indicates whether the source contains a `StorageHeader`. Set to `true` if applicable.
     pub with_storage_header: bool,
     /// Timezone for timestamp adjustment. If specified, timestamps are converted to this timezone.
     pub tz: Option<String>,
+    /// Enables the detection of ECU lifecycles. Adds the lifecycle and the absolute time,
+    /// computed from the ECU uptime and the lifecycle start, as columns.
+    pub detect_lifecycles: bool,
     /// Internal field that stores FIBEX schema metadata. Not exposed to the client.
     #[serde(skip)]
     pub fibex_metadata: Option<dlt_core::fibex::FibexMetadata>,
@@ -96,6 +108,19 @@ pub struct SomeIpParserSettings {
     pub fibex_file_paths: Option<Vec<String>>,
 }
 
+/// Settings for the CAN parser.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[extend::encode_decode]
+#[cfg_attr(
+    all(test, feature = "test_and_gen"),
+    derive(TS),
+    ts(export, export_to = "observe.ts")
+)]
+pub struct CanParserSettings {
+    /// Paths to DBC files used to decode the payload of frames into messages and signals.
+    pub dbc_file_paths: Option<Vec<String>>,
+}
+
 /// Describes the transport source for a session.
 #[derive(Debug, Serialize, Deserialize, Clone)]
 #[extend::encode_decode]
@@ -113,6 +138,8 @@ pub enum Transport {
     UDP(UDPTransportConfig),
     /// Serial port connection.
     Serial(SerialTransportConfig),
+    /// SocketCAN interface (Linux only).
+    SocketCan(SocketCanTransportConfig),
 }
 
 /// Configuration for executing terminal commands.
@@ -173,6 +200,19 @@ pub struct TCPTransportConfig {
     pub bind_addr: String,
 }
 
+/// Configuration for SocketCAN connections.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[extend::encode_decode]
+#[cfg_attr(
+    all(test, feature = "test_and_gen"),
+    derive(TS),
+    ts(export, export_to = "observe.ts")
+)]
+pub struct SocketCanTransportConfig {
+    /// The name of the CAN interface (e.g. `can0` or `vcan0`).
+    pub interface: String,
+}
+
 /// Configuration for UDP connections.
 #[derive(Clone, Debug, Serialize, Deserialize)]
 #[extend::encode_decode]
@@ -201,6 +241,78 @@ pub enum FileFormat {
     PcapLegacy,
     Text,
     Binary,
+    /// Vector binary logging format (BLF).
+    Blf,
+}
+
+/// Transport protocol of the packets picked from network traces.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[extend::encode_decode]
+#[cfg_attr(
+    all(test, feature = "test_and_gen"),
+    derive(TS),
+    ts(export, export_to = "observe.ts")
+)]
+pub enum NetworkTransport {
+    Tcp,
+    Udp,
+}
+
+/// Filter of the packets of network traces (PCAP and PCAPNG files). Each defined criterion
+/// has to match, undefined criteria match any packet.
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[extend::encode_decode]
+#[cfg_attr(
+    all(test, feature = "test_and_gen"),
+    derive(TS),
+    ts(export, export_to = "observe.ts")
+)]
+pub struct NetworkFilter {
+    /// Transport protocol of the packets.
+    pub transport: Option<NetworkTransport>,
+    /// IP address of the sender, e.g. `192.168.0.1`.
+    pub source_ip: Option<String>,
+    /// Port of the sender.
+    pub source_port: Option<u16>,
+    /// IP address of the receiver.
+    pub destination_ip: Option<String>,
+    /// Port of the receiver.
+    pub destination_port: Option<u16>,
+}
+
+/// Order of the files in a watched folder, from the oldest file to the newest one.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[extend::encode_decode]
+#[cfg_attr(
+    all(test, feature = "test_and_gen"),
+    derive(TS),
+    ts(export, export_to = "observe.ts")
+)]
+pub enum FolderSortOrder {
+    /// Files are sorted by name; numbers within names are compared by value
+    /// (e.g. `app-9.log` comes before `app-10.log`).
+    Name,
+    /// Files are sorted by the time of the last modification.
+    Modified,
+}
+
+/// Folder with rotating log files observed as a single source of data.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[extend::encode_decode]
+#[cfg_attr(
+    all(test, feature = "test_and_gen"),
+    derive(TS),
+    ts(export, export_to = "observe.ts")
+)]
+pub struct FolderWatch {
+    /// Path to the watched folder. Subfolders aren't included.
+    pub path: PathBuf,
+    /// Glob pattern for the names of the files, e.g. `app*.log`. Supports `*` and `?`.
+    pub pattern: String,
+    /// Order in which existing files are concatenated. The last file is the newest one.
+    pub order: FolderSortOrder,
+    /// Format of the files.
+    pub format: FileFormat,
 }
 
 /// Describes the source of data for observation.
@@ -218,6 +330,9 @@ pub enum ObserveOrigin {
     Concat(Vec<(String, FileFormat, PathBuf)>),
     /// The source is a stream.
     Stream(String, Transport),
+    /// The source is a folder: existing files are concatenated, then the newest file is
+    /// tailed while new files are picked up as they appear.
+    Folder(String, FolderWatch),
 }
 
 /// Options for observing data within a session.
@@ -233,4 +348,10 @@ pub struct ObserveOptions {
     pub origin: ObserveOrigin,
     /// The parser configuration to be applied.
     pub parser: ParserType,
+    /// Filter of the packets of network traces. Applied to PCAP and PCAPNG files only.
+    pub network_filter: Option<NetworkFilter>,
+    /// Orders concatenated files chronologically by the timestamps of their first messages;
+    /// overlapping files are reported as warnings. Timestamps of text files are extracted
+    /// with the given settings. Applied to `ObserveOrigin::Concat` only.
+    pub order_by_time: Option<TimestampSettings>,
 }
@@ -4,11 +4,17 @@ try_into_js!(MulticastInfo);
 try_into_js!(ParserType);
 try_into_js!(DltParserSettings);
 try_into_js!(SomeIpParserSettings);
+try_into_js!(CanParserSettings);
 try_into_js!(Transport);
 try_into_js!(ProcessTransportConfig);
 try_into_js!(SerialTransportConfig);
+try_into_js!(SocketCanTransportConfig);
 try_into_js!(TCPTransportConfig);
 try_into_js!(UDPTransportConfig);
 try_into_js!(FileFormat);
+try_into_js!(NetworkTransport);
+try_into_js!(NetworkFilter);
+try_into_js!(FolderSortOrder);
+try_into_js!(FolderWatch);
 try_into_js!(ObserveOrigin);
 try_into_js!(ObserveOptions);
@@ -35,7 +35,8 @@ impl Arbitrary for ParserType {
             any::<DltParserSettings>().prop_map(ParserType::Dlt),
             any::<SomeIpParserSettings>().prop_map(ParserType::SomeIp),
             Just(ParserType::Text(())),
-            any::<PluginParserSettings>().prop_map(ParserType::Plugin)
+            any::<PluginParserSettings>().prop_map(ParserType::Plugin),
+            any::<CanParserSettings>().prop_map(ParserType::Can)
         ]
         .boxed()
     }
@@ -81,17 +82,32 @@ impl Arbitrary for DltParserSettings {
         (
             any::<Option<DltFilterConfigWrapper>>().prop_map(|wrp| wrp.map(|wrp| wrp.0)),
             any::<Option<Vec<String>>>(),
+            any::<Option<Vec<String>>>(),
+            any::<Option<Vec<String>>>(),
             any::<bool>(),
             any::<Option<String>>(),
+            any::<bool>(),
             Just(None), // fibex_metadata is skipped
         )
             .prop_map(
-                |(filter_config, fibex_file_paths, with_storage_header, tz, fibex_metadata)| {
+                |(
+                    filter_config,
+                    fibex_file_paths,
+                    arxml_file_paths,
+                    dbc_file_paths,
+                    with_storage_header,
+                    tz,
+                    detect_lifecycles,
+                    fibex_metadata,
+                )| {
                     DltParserSettings {
                         filter_config,
                         fibex_file_paths,
+                        arxml_file_paths,
+                        dbc_file_paths,
                         with_storage_header,
                         tz,
+                        detect_lifecycles,
                         fibex_metadata,
                     }
                 },
@@ -111,6 +127,17 @@ impl Arbitrary for SomeIpParserSettings {
     }
 }
 
+impl Arbitrary for CanParserSettings {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        any::<Option<Vec<String>>>()
+            .prop_map(|dbc_file_paths| CanParserSettings { dbc_file_paths })
+            .boxed()
+    }
+}
+
 impl Arbitrary for Transport {
     type Parameters = ();
     type Strategy = BoxedStrategy<Self>;
@@ -121,6 +148,7 @@ impl Arbitrary for Transport {
             any::<TCPTransportConfig>().prop_map(Transport::TCP),
             any::<UDPTransportConfig>().prop_map(Transport::UDP),
             any::<SerialTransportConfig>().prop_map(Transport::Serial),
+            any::<SocketCanTransportConfig>().prop_map(Transport::SocketCan),
         ]
         .boxed()
     }
@@ -183,6 +211,17 @@ impl Arbitrary for SerialTransportConfig {
     }
 }
 
+impl Arbitrary for SocketCanTransportConfig {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        any::<String>()
+            .prop_map(|interface| SocketCanTransportConfig { interface })
+            .boxed()
+    }
+}
+
 impl Arbitrary for TCPTransportConfig {
     type Parameters = ();
     type Strategy = BoxedStrategy<Self>;
@@ -222,6 +261,7 @@ impl Arbitrary for FileFormat {
             FileFormat::PcapLegacy => {}
             FileFormat::Text => {}
             FileFormat::Binary => {}
+            FileFormat::Blf => {}
         };
 
         prop_oneof![
@@ -229,11 +269,78 @@ impl Arbitrary for FileFormat {
             Just(FileFormat::PcapLegacy),
             Just(FileFormat::Text),
             Just(FileFormat::Binary),
+            Just(FileFormat::Blf),
         ]
         .boxed()
     }
 }
 
+impl Arbitrary for FolderSortOrder {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        prop_oneof![Just(FolderSortOrder::Name), Just(FolderSortOrder::Modified),].boxed()
+    }
+}
+
+impl Arbitrary for FolderWatch {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        (
+            any::<PathBuf>(),
+            any::<String>(),
+            any::<FolderSortOrder>(),
+            any::<FileFormat>(),
+        )
+            .prop_map(|(path, pattern, order, format)| FolderWatch {
+                path,
+                pattern,
+                order,
+                format,
+            })
+            .boxed()
+    }
+}
+
+impl Arbitrary for NetworkTransport {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        prop_oneof![Just(NetworkTransport::Tcp), Just(NetworkTransport::Udp),].boxed()
+    }
+}
+
+impl Arbitrary for NetworkFilter {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        (
+            any::<Option<NetworkTransport>>(),
+            any::<Option<String>>(),
+            any::<Option<u16>>(),
+            any::<Option<String>>(),
+            any::<Option<u16>>(),
+        )
+            .prop_map(
+                |(transport, source_ip, source_port, destination_ip, destination_port)| {
+                    NetworkFilter {
+                        transport,
+                        source_ip,
+                        source_port,
+                        destination_ip,
+                        destination_port,
+                    }
+                },
+            )
+            .boxed()
+    }
+}
+
 impl Arbitrary for ObserveOrigin {
     type Parameters = ();
     type Strategy = BoxedStrategy<Self>;
@@ -249,6 +356,8 @@ impl Arbitrary for ObserveOrigin {
             .prop_map(ObserveOrigin::Concat),
             (any::<String>(), any::<Transport>(),)
                 .prop_map(|(stream, transport)| ObserveOrigin::Stream(stream, transport)),
+            (any::<String>(), any::<FolderWatch>(),)
+                .prop_map(|(uuid, watch)| ObserveOrigin::Folder(uuid, watch)),
         ]
         .boxed()
     }
@@ -259,8 +368,20 @@ impl Arbitrary for ObserveOptions {
     type Strategy = BoxedStrategy<Self>;
 
     fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
-        (any::<ObserveOrigin>(), any::<ParserType>())
-            .prop_map(|(origin, parser)| ObserveOptions { origin, parser })
+        (
+            any::<ObserveOrigin>(),
+            any::<ParserType>(),
+            any::<Option<NetworkFilter>>(),
+            any::<Option<TimestampSettings>>(),
+        )
+            .prop_map(
+                |(origin, parser, network_filter, order_by_time)| ObserveOptions {
+                    origin,
+                    parser,
+                    network_filter,
+                    order_by_time,
+                },
+            )
             .boxed()
     }
 }
@@ -268,13 +389,19 @@ impl Arbitrary for ObserveOptions {
 test_msg!(ObserveOptions, TESTS_USECASE_COUNT);
 test_msg!(ObserveOrigin, TESTS_USECASE_COUNT);
 test_msg!(FileFormat, TESTS_USECASE_COUNT);
+test_msg!(NetworkTransport, TESTS_USECASE_COUNT);
+test_msg!(NetworkFilter, TESTS_USECASE_COUNT);
+test_msg!(FolderSortOrder, TESTS_USECASE_COUNT);
+test_msg!(FolderWatch, TESTS_USECASE_COUNT);
 test_msg!(UDPTransportConfig, TESTS_USECASE_COUNT);
 test_msg!(TCPTransportConfig, TESTS_USECASE_COUNT);
 test_msg!(SerialTransportConfig, TESTS_USECASE_COUNT);
+test_msg!(SocketCanTransportConfig, TESTS_USECASE_COUNT);
 test_msg!(ProcessTransportConfig, TESTS_USECASE_COUNT);
 test_msg!(Transport, TESTS_USECASE_COUNT);
 test_msg!(SomeIpParserSettings, TESTS_USECASE_COUNT);
 test_msg!(DltParserSettings, TESTS_USECASE_COUNT);
+test_msg!(CanParserSettings, TESTS_USECASE_COUNT);
 test_msg!(ParserType, TESTS_USECASE_COUNT);
 test_msg!(UdpConnectionInfo, TESTS_USECASE_COUNT);
 test_msg!(MulticastInfo, TESTS_USECASE_COUNT);
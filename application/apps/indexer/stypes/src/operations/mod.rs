@@ -28,6 +28,115 @@ pub struct NearestPosition {
 )]
 pub struct ResultNearestPosition(pub Option<NearestPosition>);
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[extend::encode_decode]
+#[cfg_attr(
+    all(test, feature = "test_and_gen"),
+    derive(TS),
+    ts(export, export_to = "operations.ts")
+)]
+pub struct NearestTimestamp {
+    #[cfg_attr(all(test, feature = "test_and_gen"), ts(type = "number"))]
+    pub position: u64, // Position in original stream/file
+    #[cfg_attr(all(test, feature = "test_and_gen"), ts(type = "number"))]
+    pub timestamp: u64, // Timestamp of the position in ms since the UNIX epoch
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[extend::encode_decode]
+#[cfg_attr(
+    all(test, feature = "test_and_gen"),
+    derive(TS),
+    ts(export, export_to = "operations.ts")
+)]
+pub struct ResultNearestTimestamp(pub Option<NearestTimestamp>);
+
+/// Range of positions in original stream/file covering a period of time
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[extend::encode_decode]
+#[cfg_attr(
+    all(test, feature = "test_and_gen"),
+    derive(TS),
+    ts(export, export_to = "operations.ts")
+)]
+pub struct ResultTimeRange(pub Option<Range>);
+
+/// Count of matches of search filters within a period of time
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[extend::encode_decode]
+#[cfg_attr(
+    all(test, feature = "test_and_gen"),
+    derive(TS),
+    ts(export, export_to = "operations.ts")
+)]
+pub struct TimeBucket {
+    /// Start of the period in ms since the UNIX epoch
+    #[cfg_attr(all(test, feature = "test_and_gen"), ts(type = "number"))]
+    pub timestamp: u64,
+    /// Pairs of the index of a filter and the count of its matches
+    pub matches: Vec<(u8, u32)>,
+}
+
+/// Distribution of search matches over time. Only periods with matches are included.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[extend::encode_decode]
+#[cfg_attr(
+    all(test, feature = "test_and_gen"),
+    derive(TS),
+    ts(export, export_to = "operations.ts")
+)]
+pub struct ResultTimeDistribution(pub Vec<TimeBucket>);
+
+/// Reason of the violation of a sequence rule
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[extend::encode_decode]
+#[cfg_attr(
+    all(test, feature = "test_and_gen"),
+    derive(TS),
+    ts(export, export_to = "operations.ts")
+)]
+pub enum SequenceViolation {
+    /// The step with the given index didn't follow the previous one within the max gap
+    Timeout(u32),
+    /// A log entry matched the forbidden condition with the given index
+    Forbidden(u32),
+    /// The log ended before the step with the given index
+    Unfinished(u32),
+    /// The instance waiting for the step with the given index was dropped, because too many
+    /// instances of the rule were pending
+    Overflow(u32),
+}
+
+/// An instance of a sequence rule started by a log entry matching its first step
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[extend::encode_decode]
+#[cfg_attr(
+    all(test, feature = "test_and_gen"),
+    derive(TS),
+    ts(export, export_to = "operations.ts")
+)]
+pub struct SequenceInstance {
+    /// Index of the rule
+    pub rule: u32,
+    /// Rows of the matched steps
+    #[cfg_attr(all(test, feature = "test_and_gen"), ts(type = "Array<number>"))]
+    pub steps: Vec<u64>,
+    /// Rows from the first step to the last step or to the row the violation was detected in
+    pub rows: Range,
+    /// The reason of the violation or `None` if the instance is satisfied
+    pub violation: Option<SequenceViolation>,
+}
+
+/// Satisfied and violated instances of sequence rules ordered by their first rows
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[extend::encode_decode]
+#[cfg_attr(
+    all(test, feature = "test_and_gen"),
+    derive(TS),
+    ts(export, export_to = "operations.ts")
+)]
+pub struct ResultSequences(pub Vec<SequenceInstance>);
+
 ///(row_number, min_value_in_range, max_value_in_range, value)
 /// value - can be last value in range or some kind of average
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -55,6 +164,126 @@ pub struct Point {
 #[cfg_attr(all(test, feature = "test_and_gen"), ts(type = "Map<number, Point[]>"))]
 pub struct ResultSearchValues(pub HashMap<u8, Vec<Point>>);
 
+/// Kind of values of a series of search values
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[extend::encode_decode]
+#[cfg_attr(
+    all(test, feature = "test_and_gen"),
+    derive(TS),
+    ts(export, export_to = "operations.ts")
+)]
+pub enum ValueSeriesKind {
+    /// Extracted values
+    Value,
+    /// Difference between an extracted value and the previous one
+    Delta,
+    /// Change of extracted values per second
+    Rate,
+    /// Running count of matches
+    Count,
+}
+
+/// Describes a series of search values. A search values term produces one series per
+/// named capture group, the series of its first capture group or, if it has no capture
+/// groups, the running count of its matches.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[extend::encode_decode]
+#[cfg_attr(
+    all(test, feature = "test_and_gen"),
+    derive(TS),
+    ts(export, export_to = "operations.ts")
+)]
+pub struct ValueSeries {
+    /// Key of the series in `ResultSearchValues`. The first series of a term is keyed by the
+    /// index of the term, further series of terms get the keys following the count of terms.
+    pub id: u8,
+    /// Index of the term producing the series
+    pub term: u8,
+    /// Name of the capture group
+    pub name: Option<String>,
+    pub kind: ValueSeriesKind,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[extend::encode_decode]
+#[cfg_attr(
+    all(test, feature = "test_and_gen"),
+    derive(TS),
+    ts(export, export_to = "operations.ts")
+)]
+pub struct ValueSeriesList(pub Vec<ValueSeries>);
+
+/// Statistics of the values of a series of search values
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[extend::encode_decode]
+#[cfg_attr(
+    all(test, feature = "test_and_gen"),
+    derive(TS),
+    ts(export, export_to = "operations.ts")
+)]
+pub struct ValueStatistics {
+    /// Key of the series in `ResultSearchValues`
+    pub id: u8,
+    /// Count of values
+    #[cfg_attr(all(test, feature = "test_and_gen"), ts(type = "number"))]
+    pub count: u64,
+    pub min: f64,
+    pub max: f64,
+    pub mean: f64,
+    pub median: f64,
+    /// 90th percentile
+    pub p90: f64,
+    /// 99th percentile
+    pub p99: f64,
+    /// Population standard deviation
+    pub stddev: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[extend::encode_decode]
+#[cfg_attr(
+    all(test, feature = "test_and_gen"),
+    derive(TS),
+    ts(export, export_to = "operations.ts")
+)]
+pub struct ValueStatisticsList(pub Vec<ValueStatistics>);
+
+/// Template of messages mined from the rows of a session
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[extend::encode_decode]
+#[cfg_attr(
+    all(test, feature = "test_and_gen"),
+    derive(TS),
+    ts(export, export_to = "operations.ts")
+)]
+pub struct LogTemplate {
+    pub id: u32,
+    /// Tokens of the template separated by spaces; parameters are replaced with `<*>`
+    pub template: String,
+    /// Regular expression matching the rows of the template
+    pub filter: String,
+    /// Count of rows matching the template
+    #[cfg_attr(all(test, feature = "test_and_gen"), ts(type = "number"))]
+    pub count: u64,
+    /// First row matching the template
+    #[cfg_attr(all(test, feature = "test_and_gen"), ts(type = "number"))]
+    pub first: u64,
+    /// Last row matching the template
+    #[cfg_attr(all(test, feature = "test_and_gen"), ts(type = "number"))]
+    pub last: u64,
+    /// The first row matching the template
+    pub example: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[extend::encode_decode]
+#[cfg_attr(
+    all(test, feature = "test_and_gen"),
+    derive(TS),
+    ts(export, export_to = "operations.ts")
+)]
+pub struct LogTemplateList(pub Vec<LogTemplate>);
+
 /// Scaled chart data
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[extend::encode_decode]
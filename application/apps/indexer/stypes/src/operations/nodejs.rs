@@ -2,8 +2,23 @@ use crate::*;
 
 try_into_js!(NearestPosition);
 try_into_js!(ResultNearestPosition);
+try_into_js!(NearestTimestamp);
+try_into_js!(ResultNearestTimestamp);
+try_into_js!(ResultTimeRange);
+try_into_js!(TimeBucket);
+try_into_js!(ResultTimeDistribution);
+try_into_js!(SequenceViolation);
+try_into_js!(SequenceInstance);
+try_into_js!(ResultSequences);
 try_into_js!(Point);
 try_into_js!(ResultSearchValues);
+try_into_js!(ValueSeriesKind);
+try_into_js!(ValueSeries);
+try_into_js!(ValueSeriesList);
+try_into_js!(ValueStatistics);
+try_into_js!(ValueStatisticsList);
+try_into_js!(LogTemplate);
+try_into_js!(LogTemplateList);
 try_into_js!(ResultScaledDistribution);
 try_into_js!(ExtractedMatchValue);
 try_into_js!(ResultExtractedMatchValues);
@@ -29,6 +29,133 @@ impl Arbitrary for ResultNearestPosition {
     }
 }
 
+impl Arbitrary for NearestTimestamp {
+    /// Implements the `Arbitrary` trait for `NearestTimestamp` to generate random values for
+    /// property-based testing using the `proptest` framework.
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        (
+            any::<u32>().prop_map(|n| n as u64),
+            any::<u32>().prop_map(|n| n as u64),
+        )
+            .prop_map(|(position, timestamp)| NearestTimestamp {
+                position,
+                timestamp,
+            })
+            .boxed()
+    }
+}
+
+impl Arbitrary for ResultNearestTimestamp {
+    /// Implements the `Arbitrary` trait for `ResultNearestTimestamp` to generate random values for
+    /// property-based testing using the `proptest` framework.
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        prop::option::of(NearestTimestamp::arbitrary())
+            .prop_map(ResultNearestTimestamp)
+            .boxed()
+    }
+}
+
+impl Arbitrary for ResultTimeRange {
+    /// Implements the `Arbitrary` trait for `ResultTimeRange` to generate random values for
+    /// property-based testing using the `proptest` framework.
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        prop::option::of(Range::arbitrary())
+            .prop_map(ResultTimeRange)
+            .boxed()
+    }
+}
+
+impl Arbitrary for TimeBucket {
+    /// Implements the `Arbitrary` trait for `TimeBucket` to generate random values for
+    /// property-based testing using the `proptest` framework.
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        (
+            any::<u32>().prop_map(|n| n as u64),
+            prop::collection::vec((any::<u8>(), any::<u32>()), 0..10),
+        )
+            .prop_map(|(timestamp, matches)| TimeBucket { timestamp, matches })
+            .boxed()
+    }
+}
+
+impl Arbitrary for ResultTimeDistribution {
+    /// Implements the `Arbitrary` trait for `ResultTimeDistribution` to generate random values
+    /// for property-based testing using the `proptest` framework.
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        prop::collection::vec(TimeBucket::arbitrary(), 0..10)
+            .prop_map(ResultTimeDistribution)
+            .boxed()
+    }
+}
+
+impl Arbitrary for SequenceViolation {
+    /// Implements the `Arbitrary` trait for `SequenceViolation` to generate random values
+    /// for property-based testing using the `proptest` framework.
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        prop_oneof![
+            any::<u32>().prop_map(SequenceViolation::Timeout),
+            any::<u32>().prop_map(SequenceViolation::Forbidden),
+            any::<u32>().prop_map(SequenceViolation::Unfinished),
+            any::<u32>().prop_map(SequenceViolation::Overflow),
+        ]
+        .boxed()
+    }
+}
+
+impl Arbitrary for SequenceInstance {
+    /// Implements the `Arbitrary` trait for `SequenceInstance` to generate random values
+    /// for property-based testing using the `proptest` framework.
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        (
+            any::<u32>(),
+            prop::collection::vec(any::<u32>().prop_map(|n| n as u64), 0..10),
+            any::<Range>(),
+            prop::option::of(any::<SequenceViolation>()),
+        )
+            .prop_map(|(rule, steps, rows, violation)| SequenceInstance {
+                rule,
+                steps,
+                rows,
+                violation,
+            })
+            .boxed()
+    }
+}
+
+impl Arbitrary for ResultSequences {
+    /// Implements the `Arbitrary` trait for `ResultSequences` to generate random values
+    /// for property-based testing using the `proptest` framework.
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        prop::collection::vec(SequenceInstance::arbitrary(), 0..10)
+            .prop_map(ResultSequences)
+            .boxed()
+    }
+}
+
 impl Arbitrary for Point {
     /// Implements the `Arbitrary` trait for `ExtractedMatchValue` to generate random values for
     /// property-based testing using the `proptest` framework.
@@ -65,6 +192,143 @@ impl Arbitrary for ResultSearchValues {
     }
 }
 
+impl Arbitrary for ValueSeriesKind {
+    /// Implements the `Arbitrary` trait for `ValueSeriesKind` to generate random values for
+    /// property-based testing using the `proptest` framework.
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        prop_oneof![
+            Just(ValueSeriesKind::Value),
+            Just(ValueSeriesKind::Delta),
+            Just(ValueSeriesKind::Rate),
+            Just(ValueSeriesKind::Count),
+        ]
+        .boxed()
+    }
+}
+
+impl Arbitrary for ValueSeries {
+    /// Implements the `Arbitrary` trait for `ValueSeries` to generate random values for
+    /// property-based testing using the `proptest` framework.
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        (
+            any::<u8>(),
+            any::<u8>(),
+            any::<Option<String>>(),
+            ValueSeriesKind::arbitrary(),
+        )
+            .prop_map(|(id, term, name, kind)| ValueSeries {
+                id,
+                term,
+                name,
+                kind,
+            })
+            .boxed()
+    }
+}
+
+impl Arbitrary for ValueSeriesList {
+    /// Implements the `Arbitrary` trait for `ValueSeriesList` to generate random values for
+    /// property-based testing using the `proptest` framework.
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        prop::collection::vec(ValueSeries::arbitrary(), 0..10)
+            .prop_map(ValueSeriesList)
+            .boxed()
+    }
+}
+
+impl Arbitrary for ValueStatistics {
+    /// Implements the `Arbitrary` trait for `ValueStatistics` to generate random values for
+    /// property-based testing using the `proptest` framework.
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        (
+            any::<u8>(),
+            any::<u32>().prop_map(|n| n as u64),
+            prop::collection::vec(any::<f32>().prop_map(|n| n as f64), 7),
+        )
+            .prop_map(|(id, count, values)| ValueStatistics {
+                id,
+                count,
+                min: values[0],
+                max: values[1],
+                mean: values[2],
+                median: values[3],
+                p90: values[4],
+                p99: values[5],
+                stddev: values[6],
+            })
+            .boxed()
+    }
+}
+
+impl Arbitrary for ValueStatisticsList {
+    /// Implements the `Arbitrary` trait for `ValueStatisticsList` to generate random values for
+    /// property-based testing using the `proptest` framework.
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        prop::collection::vec(ValueStatistics::arbitrary(), 0..10)
+            .prop_map(ValueStatisticsList)
+            .boxed()
+    }
+}
+
+impl Arbitrary for LogTemplate {
+    /// Implements the `Arbitrary` trait for `LogTemplate` to generate random values for
+    /// property-based testing using the `proptest` framework.
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        (
+            any::<u32>(),
+            any::<String>(),
+            any::<String>(),
+            any::<u32>().prop_map(|n| n as u64),
+            any::<u32>().prop_map(|n| n as u64),
+            any::<u32>().prop_map(|n| n as u64),
+            any::<String>(),
+        )
+            .prop_map(
+                |(id, template, filter, count, first, last, example)| LogTemplate {
+                    id,
+                    template,
+                    filter,
+                    count,
+                    first,
+                    last,
+                    example,
+                },
+            )
+            .boxed()
+    }
+}
+
+impl Arbitrary for LogTemplateList {
+    /// Implements the `Arbitrary` trait for `LogTemplateList` to generate random values for
+    /// property-based testing using the `proptest` framework.
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        prop::collection::vec(LogTemplate::arbitrary(), 0..10)
+            .prop_map(LogTemplateList)
+            .boxed()
+    }
+}
+
 impl Arbitrary for ResultScaledDistribution {
     /// Implements the `Arbitrary` trait for `ExtractedMatchValue` to generate random values for
     /// property-based testing using the `proptest` framework.
@@ -156,8 +420,23 @@ impl Arbitrary for ResultSleep {
 
 test_msg!(NearestPosition, TESTS_USECASE_COUNT);
 test_msg!(ResultNearestPosition, TESTS_USECASE_COUNT);
+test_msg!(NearestTimestamp, TESTS_USECASE_COUNT);
+test_msg!(ResultNearestTimestamp, TESTS_USECASE_COUNT);
+test_msg!(ResultTimeRange, TESTS_USECASE_COUNT);
+test_msg!(TimeBucket, TESTS_USECASE_COUNT);
+test_msg!(ResultTimeDistribution, TESTS_USECASE_COUNT);
+test_msg!(SequenceViolation, TESTS_USECASE_COUNT);
+test_msg!(SequenceInstance, TESTS_USECASE_COUNT);
+test_msg!(ResultSequences, TESTS_USECASE_COUNT);
 test_msg!(Point, TESTS_USECASE_COUNT);
 test_msg!(ResultSearchValues, TESTS_USECASE_COUNT);
+test_msg!(ValueSeriesKind, TESTS_USECASE_COUNT);
+test_msg!(ValueSeries, TESTS_USECASE_COUNT);
+test_msg!(ValueSeriesList, TESTS_USECASE_COUNT);
+test_msg!(ValueStatistics, TESTS_USECASE_COUNT);
+test_msg!(ValueStatisticsList, TESTS_USECASE_COUNT);
+test_msg!(LogTemplate, TESTS_USECASE_COUNT);
+test_msg!(LogTemplateList, TESTS_USECASE_COUNT);
 test_msg!(ResultScaledDistribution, TESTS_USECASE_COUNT);
 test_msg!(ExtractedMatchValue, TESTS_USECASE_COUNT);
 test_msg!(ResultExtractedMatchValues, TESTS_USECASE_COUNT);
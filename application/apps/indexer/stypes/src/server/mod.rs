@@ -0,0 +1,213 @@
+//! Messages of the network API of the headless session server. A client sends
+//! `ServerRequest`s and receives `ServerMessage`s: responses to its requests and events of
+//! the sessions it's subscribed to. All messages are encoded with `encode` / `decode`.
+
+#[cfg(test)]
+mod proptest;
+
+use crate::*;
+
+/// Condition of a search.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[extend::encode_decode]
+#[cfg_attr(
+    all(test, feature = "test_and_gen"),
+    derive(TS),
+    ts(export, export_to = "server.ts")
+)]
+pub struct SearchCondition {
+    pub value: String,
+    pub is_regex: bool,
+    pub ignore_case: bool,
+    pub is_word: bool,
+}
+
+/// Request to an existing session.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[extend::encode_decode]
+#[cfg_attr(
+    all(test, feature = "test_and_gen"),
+    derive(TS),
+    ts(export, export_to = "server.ts")
+)]
+pub enum SessionRequest {
+    /// Starts observing of a source. Responds with `ServerResult::Operation`.
+    Observe(ObserveOptions),
+    /// Starts a search with the given conditions. Responds with `ServerResult::Operation`.
+    Search(Vec<SearchCondition>),
+    /// Drops the current search. Responds with `ServerResult::Done`.
+    DropSearch,
+    /// Responds with `ServerResult::Number` containing the count of rows of the session.
+    GetStreamLen,
+    /// Responds with `ServerResult::Number` containing the count of search matches.
+    GetSearchResultLen,
+    /// Responds with `ServerResult::Encoded` containing `GrabbedElementList`.
+    Grab(Range),
+    /// Responds with `ServerResult::Encoded` containing `GrabbedElementList`.
+    GrabSearch(Range),
+    /// Responds with `ServerResult::Encoded` containing `GrabbedElementList`.
+    GrabRanges(Ranges),
+    /// Grabs the rows of search matches within the range of search results with the rows
+    /// around them. Responds with `ServerResult::Encoded` containing `GrabbedElementList`.
+    GrabSearchContext {
+        range: Range,
+        before: u32,
+        after: u32,
+    },
+    /// Exports rows as text. Responds with `ServerResult::Operation`.
+    Export {
+        out_path: PathBuf,
+        ranges: Vec<Range>,
+        /// Columns to export; all columns are exported if empty.
+        columns: Vec<u32>,
+        spliter: Option<String>,
+        delimiter: Option<String>,
+    },
+    /// Exports rows in the format of the source. Responds with `ServerResult::Operation`.
+    ExportRaw {
+        out_path: PathBuf,
+        ranges: Vec<Range>,
+    },
+    /// Exports the rows of all search matches with the rows around them as text. Responds
+    /// with `ServerResult::Operation`.
+    ExportSearchContext {
+        out_path: PathBuf,
+        before: u32,
+        after: u32,
+        /// Columns to export; all columns are exported if empty.
+        columns: Vec<u32>,
+        spliter: Option<String>,
+        delimiter: Option<String>,
+    },
+    /// Responds with `ServerResult::Done`.
+    #[cfg_attr(all(test, feature = "test_and_gen"), ts(type = "number"))]
+    AddBookmark(u64),
+    /// Responds with `ServerResult::Done`.
+    #[cfg_attr(all(test, feature = "test_and_gen"), ts(type = "number"))]
+    RemoveBookmark(u64),
+    /// Responds with `ServerResult::Done`.
+    #[cfg_attr(all(test, feature = "test_and_gen"), ts(type = "Array<number>"))]
+    SetBookmarks(Vec<u64>),
+    /// Cancels the operation with the given ID. Responds with `ServerResult::Done`.
+    Abort(Uuid),
+    /// Subscribes the connection to the events of the session. Responds with
+    /// `ServerResult::Done`.
+    Subscribe,
+    /// Responds with `ServerResult::Done`.
+    Unsubscribe,
+}
+
+/// Command of the unbound session. Responds with `ServerResult::Encoded` containing
+/// `CommandOutcome` of the type the command returns in `UnboundSessionAPI`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[extend::encode_decode]
+#[cfg_attr(
+    all(test, feature = "test_and_gen"),
+    derive(TS),
+    ts(export, export_to = "server.ts")
+)]
+pub enum UnboundRequest {
+    ListFolderContent {
+        paths: Vec<String>,
+        depth: u32,
+        max_len: u32,
+        include_files: bool,
+        include_folders: bool,
+    },
+    IsFileBinary(String),
+    DetectFileFormat(String),
+    GetFilesChronology(Vec<String>),
+    CompareFiles(ComparisonOptions),
+    Grep(GrepOptions),
+    GetFileChecksum(String),
+    GetDltStats(Vec<String>),
+    GetSomeipStatistic(Vec<String>),
+    GetSerialPortsList,
+    GetRegexError(SearchCondition),
+    /// Cancels the command started by the request with the given ID. Responds with
+    /// `ServerResult::Done`.
+    #[cfg_attr(all(test, feature = "test_and_gen"), ts(type = "number"))]
+    Cancel(u64),
+}
+
+/// Command sent to the server.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[extend::encode_decode]
+#[cfg_attr(
+    all(test, feature = "test_and_gen"),
+    derive(TS),
+    ts(export, export_to = "server.ts")
+)]
+pub enum ServerCommand {
+    /// Creates a new session. Responds with `ServerResult::Session`.
+    CreateSession,
+    /// Destroys the session. Responds with `ServerResult::Done`.
+    DestroySession(Uuid),
+    Session(Uuid, SessionRequest),
+    Unbound(UnboundRequest),
+}
+
+/// Request sent to the server. The `id` is chosen by the client and is used to match the
+/// response with the request.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[extend::encode_decode]
+#[cfg_attr(
+    all(test, feature = "test_and_gen"),
+    derive(TS),
+    ts(export, export_to = "server.ts")
+)]
+pub struct ServerRequest {
+    #[cfg_attr(all(test, feature = "test_and_gen"), ts(type = "number"))]
+    pub id: u64,
+    pub command: ServerCommand,
+}
+
+/// Result of a request.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[extend::encode_decode]
+#[cfg_attr(
+    all(test, feature = "test_and_gen"),
+    derive(TS),
+    ts(export, export_to = "server.ts")
+)]
+pub enum ServerResult {
+    Done,
+    /// ID of a created session.
+    Session(Uuid),
+    /// ID of a started operation. The result of the operation is delivered with
+    /// `CallbackEvent::OperationDone` to the subscribers of the session.
+    Operation(Uuid),
+    #[cfg_attr(all(test, feature = "test_and_gen"), ts(type = "number"))]
+    Number(u64),
+    /// Encoded value of the type documented on the request.
+    Encoded(Vec<u8>),
+    Error(ComputationError),
+}
+
+/// Response to a request.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[extend::encode_decode]
+#[cfg_attr(
+    all(test, feature = "test_and_gen"),
+    derive(TS),
+    ts(export, export_to = "server.ts")
+)]
+pub struct ServerResponse {
+    #[cfg_attr(all(test, feature = "test_and_gen"), ts(type = "number"))]
+    pub id: u64,
+    pub result: ServerResult,
+}
+
+/// Message sent by the server.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[extend::encode_decode]
+#[cfg_attr(
+    all(test, feature = "test_and_gen"),
+    derive(TS),
+    ts(export, export_to = "server.ts")
+)]
+pub enum ServerMessage {
+    Response(ServerResponse),
+    /// Event of a session the client is subscribed to.
+    Event(Uuid, CallbackEvent),
+}
@@ -0,0 +1,232 @@
+use crate::*;
+
+impl Arbitrary for SearchCondition {
+    /// Implements the `Arbitrary` trait for `SearchCondition` to generate random instances
+    /// for property-based testing using the `proptest` framework.
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        (any::<String>(), any::<bool>(), any::<bool>(), any::<bool>())
+            .prop_map(|(value, is_regex, ignore_case, is_word)| SearchCondition {
+                value,
+                is_regex,
+                ignore_case,
+                is_word,
+            })
+            .boxed()
+    }
+}
+
+impl Arbitrary for SessionRequest {
+    /// Implements the `Arbitrary` trait for `SessionRequest` to generate random instances
+    /// for property-based testing using the `proptest` framework.
+    ///
+    /// # Details
+    /// - Generates all variants of `SessionRequest`; `u64` values are generated from `u32`.
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        prop_oneof![
+            any::<ObserveOptions>().prop_map(SessionRequest::Observe),
+            prop::collection::vec(any::<SearchCondition>(), 0..5).prop_map(SessionRequest::Search),
+            Just(SessionRequest::DropSearch),
+            Just(SessionRequest::GetStreamLen),
+            Just(SessionRequest::GetSearchResultLen),
+            any::<Range>().prop_map(SessionRequest::Grab),
+            any::<Range>().prop_map(SessionRequest::GrabSearch),
+            any::<Ranges>().prop_map(SessionRequest::GrabRanges),
+            (any::<Range>(), any::<u32>(), any::<u32>()).prop_map(|(range, before, after)| {
+                SessionRequest::GrabSearchContext {
+                    range,
+                    before,
+                    after,
+                }
+            }),
+            (
+                any::<PathBuf>(),
+                prop::collection::vec(any::<Range>(), 0..5),
+                prop::collection::vec(any::<u32>(), 0..5),
+                any::<Option<String>>(),
+                any::<Option<String>>(),
+            )
+                .prop_map(|(out_path, ranges, columns, spliter, delimiter)| {
+                    SessionRequest::Export {
+                        out_path,
+                        ranges,
+                        columns,
+                        spliter,
+                        delimiter,
+                    }
+                }),
+            (any::<PathBuf>(), prop::collection::vec(any::<Range>(), 0..5))
+                .prop_map(|(out_path, ranges)| SessionRequest::ExportRaw { out_path, ranges }),
+            (
+                any::<PathBuf>(),
+                any::<u32>(),
+                any::<u32>(),
+                prop::collection::vec(any::<u32>(), 0..5),
+                any::<Option<String>>(),
+                any::<Option<String>>(),
+            )
+                .prop_map(|(out_path, before, after, columns, spliter, delimiter)| {
+                    SessionRequest::ExportSearchContext {
+                        out_path,
+                        before,
+                        after,
+                        columns,
+                        spliter,
+                        delimiter,
+                    }
+                }),
+            any::<u32>().prop_map(|row| SessionRequest::AddBookmark(row as u64)),
+            any::<u32>().prop_map(|row| SessionRequest::RemoveBookmark(row as u64)),
+            prop::collection::vec(any::<u32>(), 0..10).prop_map(|rows| {
+                SessionRequest::SetBookmarks(rows.into_iter().map(|row| row as u64).collect())
+            }),
+            Just(SessionRequest::Abort(Uuid::new_v4())),
+            Just(SessionRequest::Subscribe),
+            Just(SessionRequest::Unsubscribe),
+        ]
+        .boxed()
+    }
+}
+
+impl Arbitrary for UnboundRequest {
+    /// Implements the `Arbitrary` trait for `UnboundRequest` to generate random instances
+    /// for property-based testing using the `proptest` framework.
+    ///
+    /// # Details
+    /// - Generates all variants of `UnboundRequest`; `u64` values are generated from `u32`.
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        prop_oneof![
+            (
+                prop::collection::vec(any::<String>(), 0..5),
+                any::<u32>(),
+                any::<u32>(),
+                any::<bool>(),
+                any::<bool>(),
+            )
+                .prop_map(|(paths, depth, max_len, include_files, include_folders)| {
+                    UnboundRequest::ListFolderContent {
+                        paths,
+                        depth,
+                        max_len,
+                        include_files,
+                        include_folders,
+                    }
+                }),
+            any::<String>().prop_map(UnboundRequest::IsFileBinary),
+            any::<String>().prop_map(UnboundRequest::DetectFileFormat),
+            prop::collection::vec(any::<String>(), 0..5)
+                .prop_map(UnboundRequest::GetFilesChronology),
+            any::<ComparisonOptions>().prop_map(UnboundRequest::CompareFiles),
+            any::<GrepOptions>().prop_map(UnboundRequest::Grep),
+            any::<String>().prop_map(UnboundRequest::GetFileChecksum),
+            prop::collection::vec(any::<String>(), 0..5).prop_map(UnboundRequest::GetDltStats),
+            prop::collection::vec(any::<String>(), 0..5)
+                .prop_map(UnboundRequest::GetSomeipStatistic),
+            Just(UnboundRequest::GetSerialPortsList),
+            any::<SearchCondition>().prop_map(UnboundRequest::GetRegexError),
+            any::<u32>().prop_map(|id| UnboundRequest::Cancel(id as u64)),
+        ]
+        .boxed()
+    }
+}
+
+impl Arbitrary for ServerCommand {
+    /// Implements the `Arbitrary` trait for `ServerCommand` to generate random instances
+    /// for property-based testing using the `proptest` framework.
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        prop_oneof![
+            Just(ServerCommand::CreateSession),
+            Just(ServerCommand::DestroySession(Uuid::new_v4())),
+            any::<SessionRequest>()
+                .prop_map(|request| ServerCommand::Session(Uuid::new_v4(), request)),
+            any::<UnboundRequest>().prop_map(ServerCommand::Unbound),
+        ]
+        .boxed()
+    }
+}
+
+impl Arbitrary for ServerRequest {
+    /// Implements the `Arbitrary` trait for `ServerRequest` to generate random instances
+    /// for property-based testing using the `proptest` framework.
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        (any::<u32>(), any::<ServerCommand>())
+            .prop_map(|(id, command)| ServerRequest {
+                id: id as u64,
+                command,
+            })
+            .boxed()
+    }
+}
+
+impl Arbitrary for ServerResult {
+    /// Implements the `Arbitrary` trait for `ServerResult` to generate random instances
+    /// for property-based testing using the `proptest` framework.
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        prop_oneof![
+            Just(ServerResult::Done),
+            Just(ServerResult::Session(Uuid::new_v4())),
+            Just(ServerResult::Operation(Uuid::new_v4())),
+            any::<u32>().prop_map(|n| ServerResult::Number(n as u64)),
+            any::<Vec<u8>>().prop_map(ServerResult::Encoded),
+            any::<ComputationError>().prop_map(ServerResult::Error),
+        ]
+        .boxed()
+    }
+}
+
+impl Arbitrary for ServerResponse {
+    /// Implements the `Arbitrary` trait for `ServerResponse` to generate random instances
+    /// for property-based testing using the `proptest` framework.
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        (any::<u32>(), any::<ServerResult>())
+            .prop_map(|(id, result)| ServerResponse {
+                id: id as u64,
+                result,
+            })
+            .boxed()
+    }
+}
+
+impl Arbitrary for ServerMessage {
+    /// Implements the `Arbitrary` trait for `ServerMessage` to generate random instances
+    /// for property-based testing using the `proptest` framework.
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        prop_oneof![
+            any::<ServerResponse>().prop_map(ServerMessage::Response),
+            any::<CallbackEvent>().prop_map(|event| ServerMessage::Event(Uuid::new_v4(), event)),
+        ]
+        .boxed()
+    }
+}
+
+test_msg!(SearchCondition, TESTS_USECASE_COUNT);
+test_msg!(SessionRequest, TESTS_USECASE_COUNT);
+test_msg!(UnboundRequest, TESTS_USECASE_COUNT);
+test_msg!(ServerCommand, TESTS_USECASE_COUNT);
+test_msg!(ServerRequest, TESTS_USECASE_COUNT);
+test_msg!(ServerResult, TESTS_USECASE_COUNT);
+test_msg!(ServerResponse, TESTS_USECASE_COUNT);
+test_msg!(ServerMessage, TESTS_USECASE_COUNT);
@@ -117,15 +117,35 @@ gen_encode_decode_fns!(UdpConnectionInfo);
 gen_encode_decode_fns!(ParserType);
 gen_encode_decode_fns!(DltParserSettings);
 gen_encode_decode_fns!(SomeIpParserSettings);
+gen_encode_decode_fns!(CanParserSettings);
 gen_encode_decode_fns!(Transport);
 gen_encode_decode_fns!(ProcessTransportConfig);
 gen_encode_decode_fns!(SerialTransportConfig);
+gen_encode_decode_fns!(SocketCanTransportConfig);
 gen_encode_decode_fns!(TCPTransportConfig);
 gen_encode_decode_fns!(UDPTransportConfig);
 gen_encode_decode_fns!(FileFormat);
+gen_encode_decode_fns!(NetworkTransport);
+gen_encode_decode_fns!(NetworkFilter);
+gen_encode_decode_fns!(FolderSortOrder);
+gen_encode_decode_fns!(FolderWatch);
 gen_encode_decode_fns!(ObserveOrigin);
 gen_encode_decode_fns!(FoldersScanningResult);
 gen_encode_decode_fns!(DltStatisticInfo);
+gen_encode_decode_fns!(DetectedParser);
+gen_encode_decode_fns!(TextEncoding);
+gen_encode_decode_fns!(FormatDetection);
+gen_encode_decode_fns!(FileTimeRange);
+gen_encode_decode_fns!(FilesOverlap);
+gen_encode_decode_fns!(FilesChronology);
+gen_encode_decode_fns!(MaskRule);
+gen_encode_decode_fns!(ComparisonOptions);
+gen_encode_decode_fns!(ComparedLine);
+gen_encode_decode_fns!(DiffHunk);
+gen_encode_decode_fns!(FilesComparison);
+gen_encode_decode_fns!(GrepOptions);
+gen_encode_decode_fns!(GrepMatch);
+gen_encode_decode_fns!(GrepResult);
 gen_encode_decode_fns!(Profile);
 gen_encode_decode_fns!(ProfileList);
 gen_encode_decode_fns!(PluginParserSettings);
@@ -162,6 +182,10 @@ gen_encode_decode_fns!(CommandOutcome<FoldersScanningResult>);
 gen_encode_decode_fns!(CommandOutcome<SerialPortsList>);
 gen_encode_decode_fns!(CommandOutcome<ProfileList>);
 gen_encode_decode_fns!(CommandOutcome<DltStatisticInfo>);
+gen_encode_decode_fns!(CommandOutcome<FormatDetection>);
+gen_encode_decode_fns!(CommandOutcome<FilesChronology>);
+gen_encode_decode_fns!(CommandOutcome<FilesComparison>);
+gen_encode_decode_fns!(CommandOutcome<GrepResult>);
 gen_encode_decode_fns!(CommandOutcome<MapKeyValue>);
 gen_encode_decode_fns!(CommandOutcome<()>);
 gen_encode_decode_fns!(CommandOutcome<i64>);
@@ -190,6 +214,16 @@ gen_encode_decode_fns!(GrabbedElementList);
 gen_encode_decode_fns!(AroundIndexes);
 gen_encode_decode_fns!(FilterMatch);
 gen_encode_decode_fns!(FilterMatchList);
+gen_encode_decode_fns!(Fold);
+gen_encode_decode_fns!(FoldList);
+gen_encode_decode_fns!(TimestampSettings);
+gen_encode_decode_fns!(TriggerCondition);
+gen_encode_decode_fns!(TriggerAction);
+gen_encode_decode_fns!(TriggerRule);
+gen_encode_decode_fns!(TriggerRules);
+gen_encode_decode_fns!(SequenceStep);
+gen_encode_decode_fns!(SequenceRule);
+gen_encode_decode_fns!(SequenceRules);
 gen_encode_decode_fns!(FolderEntity);
 gen_encode_decode_fns!(FolderEntityDetails);
 gen_encode_decode_fns!(FolderEntityType);
@@ -201,7 +235,30 @@ gen_encode_decode_fns!(ResultBool);
 gen_encode_decode_fns!(ResultSleep);
 gen_encode_decode_fns!(NearestPosition);
 gen_encode_decode_fns!(ResultNearestPosition);
+gen_encode_decode_fns!(NearestTimestamp);
+gen_encode_decode_fns!(ResultNearestTimestamp);
+gen_encode_decode_fns!(ResultTimeRange);
+gen_encode_decode_fns!(TimeBucket);
+gen_encode_decode_fns!(ResultTimeDistribution);
+gen_encode_decode_fns!(SequenceViolation);
+gen_encode_decode_fns!(SequenceInstance);
+gen_encode_decode_fns!(ResultSequences);
 gen_encode_decode_fns!(Point);
 gen_encode_decode_fns!(ResultSearchValues);
+gen_encode_decode_fns!(ValueSeriesKind);
+gen_encode_decode_fns!(ValueSeries);
+gen_encode_decode_fns!(ValueSeriesList);
+gen_encode_decode_fns!(ValueStatistics);
+gen_encode_decode_fns!(ValueStatisticsList);
+gen_encode_decode_fns!(LogTemplate);
+gen_encode_decode_fns!(LogTemplateList);
 gen_encode_decode_fns!(ResultScaledDistribution);
 gen_encode_decode_fns!(DltLevelDistribution);
+gen_encode_decode_fns!(SearchCondition);
+gen_encode_decode_fns!(SessionRequest);
+gen_encode_decode_fns!(UnboundRequest);
+gen_encode_decode_fns!(ServerCommand);
+gen_encode_decode_fns!(ServerRequest);
+gen_encode_decode_fns!(ServerResult);
+gen_encode_decode_fns!(ServerResponse);
+gen_encode_decode_fns!(ServerMessage);
@@ -1,6 +1,6 @@
 use crate::js::converting::filter::WrappedSearchFilter;
 use log::{debug, error};
-use node_bindgen::derive::node_bindgen;
+use node_bindgen::{core::buffer::JSArrayBuffer, derive::node_bindgen};
 
 use session::unbound::{UnboundSession, api::UnboundSessionAPI};
 use std::{convert::TryFrom, thread};
@@ -118,6 +118,62 @@ impl UnboundJobs {
             .await
     }
 
+    #[node_bindgen]
+    async fn detect_file_format(
+        &self,
+        id: i64,
+        file_path: String,
+    ) -> Result<stypes::CommandOutcome<stypes::FormatDetection>, stypes::ComputationError> {
+        self.api
+            .as_ref()
+            .ok_or(stypes::ComputationError::SessionUnavailable)?
+            .detect_file_format(u64_from_i64(id)?, file_path)
+            .await
+    }
+
+    #[node_bindgen]
+    async fn get_files_chronology(
+        &self,
+        id: i64,
+        files: Vec<String>,
+    ) -> Result<stypes::CommandOutcome<stypes::FilesChronology>, stypes::ComputationError> {
+        self.api
+            .as_ref()
+            .ok_or(stypes::ComputationError::SessionUnavailable)?
+            .get_files_chronology(u64_from_i64(id)?, files)
+            .await
+    }
+
+    #[node_bindgen]
+    async fn compare_files(
+        &self,
+        id: i64,
+        options: JSArrayBuffer,
+    ) -> Result<stypes::CommandOutcome<stypes::FilesComparison>, stypes::ComputationError> {
+        let options = stypes::ComparisonOptions::decode(&options)
+            .map_err(stypes::ComputationError::Decoding)?;
+        self.api
+            .as_ref()
+            .ok_or(stypes::ComputationError::SessionUnavailable)?
+            .compare_files(u64_from_i64(id)?, options)
+            .await
+    }
+
+    #[node_bindgen]
+    async fn grep(
+        &self,
+        id: i64,
+        options: JSArrayBuffer,
+    ) -> Result<stypes::CommandOutcome<stypes::GrepResult>, stypes::ComputationError> {
+        let options =
+            stypes::GrepOptions::decode(&options).map_err(stypes::ComputationError::Decoding)?;
+        self.api
+            .as_ref()
+            .ok_or(stypes::ComputationError::SessionUnavailable)?
+            .grep(u64_from_i64(id)?, options)
+            .await
+    }
+
     #[node_bindgen]
     async fn spawn_process(
         &self,
@@ -207,6 +207,46 @@ impl RustSession {
             )
     }
 
+    #[node_bindgen]
+    #[allow(clippy::too_many_arguments)]
+    async fn export_search_context(
+        &self,
+        out_path: String,
+        before: i64,
+        after: i64,
+        columns: Vec<i32>,
+        spliter: String,
+        delimiter: String,
+        operation_id: String,
+    ) -> Result<(), stypes::ComputationError> {
+        let before = u64::try_from(before).map_err(|_| stypes::ComputationError::InvalidData)?;
+        let after = u64::try_from(after).map_err(|_| stypes::ComputationError::InvalidData)?;
+        self.session
+            .as_ref()
+            .ok_or(stypes::ComputationError::SessionUnavailable)?
+            .export_search_context(
+                operations::uuid_from_str(&operation_id)?,
+                PathBuf::from(out_path),
+                before,
+                after,
+                columns
+                    .into_iter()
+                    .map(usize::try_from)
+                    .collect::<Result<Vec<usize>, _>>()
+                    .map_err(|_| {
+                        stypes::ComputationError::NativeError(stypes::NativeError {
+                            severity: stypes::Severity::ERROR,
+                            kind: stypes::NativeErrorKind::Io,
+                            message: Some(String::from(
+                                "Fail to get valid columns list. Supported type: [u8]",
+                            )),
+                        })
+                    })?,
+                (!spliter.is_empty()).then_some(spliter),
+                (!delimiter.is_empty()).then_some(delimiter),
+            )
+    }
+
     #[node_bindgen]
     async fn export_raw(
         &self,
@@ -352,6 +392,31 @@ impl RustSession {
             .await
     }
 
+    #[node_bindgen]
+    async fn expand_fold(&self, row: i64) -> Result<(), stypes::ComputationError> {
+        let row = u64::try_from(row).map_err(|_| stypes::ComputationError::InvalidData)?;
+        self.session
+            .as_ref()
+            .ok_or(stypes::ComputationError::SessionUnavailable)?
+            .expand_fold(row)
+            .await
+    }
+
+    #[node_bindgen]
+    async fn get_folds(
+        &self,
+        from: i64,
+        to: i64,
+    ) -> Result<stypes::FoldList, stypes::ComputationError> {
+        let from = u64::try_from(from).map_err(|_| stypes::ComputationError::InvalidData)?;
+        let to = u64::try_from(to).map_err(|_| stypes::ComputationError::InvalidData)?;
+        self.session
+            .as_ref()
+            .ok_or(stypes::ComputationError::SessionUnavailable)?
+            .get_folds(from..=to)
+            .await
+    }
+
     #[node_bindgen]
     async fn grab_search(
         &self,
@@ -369,6 +434,27 @@ impl RustSession {
             .await
     }
 
+    #[node_bindgen]
+    async fn grab_search_context(
+        &self,
+        start_line_index: i64,
+        number_of_lines: i64,
+        before: i64,
+        after: i64,
+    ) -> Result<GrabbedElementList, stypes::ComputationError> {
+        let start =
+            u64::try_from(start_line_index).map_err(|_| stypes::ComputationError::InvalidData)?;
+        let end = u64::try_from(start_line_index + number_of_lines - 1)
+            .map_err(|_| stypes::ComputationError::InvalidData)?;
+        let before = u64::try_from(before).map_err(|_| stypes::ComputationError::InvalidData)?;
+        let after = u64::try_from(after).map_err(|_| stypes::ComputationError::InvalidData)?;
+        self.session
+            .as_ref()
+            .ok_or(stypes::ComputationError::SessionUnavailable)?
+            .grab_search_context(LineRange::from(start..=end), before, after)
+            .await
+    }
+
     #[node_bindgen]
     async fn grab_ranges(
         &self,
@@ -509,6 +595,15 @@ impl RustSession {
             .await
     }
 
+    #[node_bindgen]
+    async fn get_dlt_stats(&self) -> Result<stypes::DltStatisticInfo, stypes::ComputationError> {
+        self.session
+            .as_ref()
+            .ok_or(stypes::ComputationError::SessionUnavailable)?
+            .get_dlt_stats()
+            .await
+    }
+
     #[node_bindgen]
     async fn extract_matches(
         &self,
@@ -615,6 +710,130 @@ impl RustSession {
             )
     }
 
+    #[node_bindgen]
+    async fn set_timestamp_settings(
+        &self,
+        settings: JSArrayBuffer,
+    ) -> Result<(), stypes::ComputationError> {
+        let settings = stypes::TimestampSettings::decode(&settings)
+            .map_err(stypes::ComputationError::Decoding)?;
+        self.session
+            .as_ref()
+            .ok_or(stypes::ComputationError::SessionUnavailable)?
+            .set_timestamp_settings(Some(settings))
+            .await
+    }
+
+    #[node_bindgen]
+    async fn set_triggers(&self, rules: JSArrayBuffer) -> Result<(), stypes::ComputationError> {
+        let rules =
+            stypes::TriggerRules::decode(&rules).map_err(stypes::ComputationError::Decoding)?;
+        self.session
+            .as_ref()
+            .ok_or(stypes::ComputationError::SessionUnavailable)?
+            .set_triggers(rules.0)
+            .await
+    }
+
+    #[node_bindgen]
+    async fn get_nearest_to_timestamp(
+        &self,
+        operation_id: String,
+        timestamp: i64,
+    ) -> Result<(), stypes::ComputationError> {
+        if timestamp < 0 {
+            return Err(stypes::ComputationError::InvalidArgs(format!(
+                "Invalid timestamp: {timestamp}"
+            )));
+        }
+        self.session
+            .as_ref()
+            .ok_or(stypes::ComputationError::SessionUnavailable)?
+            .get_nearest_to_timestamp(operations::uuid_from_str(&operation_id)?, timestamp as u64)
+    }
+
+    #[node_bindgen]
+    async fn get_time_range(
+        &self,
+        operation_id: String,
+        from: i64,
+        to: i64,
+    ) -> Result<(), stypes::ComputationError> {
+        if from < 0 || to < 0 || from > to {
+            return Err(stypes::ComputationError::InvalidArgs(format!(
+                "Invalid period: from = {from}; to = {to}"
+            )));
+        }
+        self.session
+            .as_ref()
+            .ok_or(stypes::ComputationError::SessionUnavailable)?
+            .get_time_range(
+                operations::uuid_from_str(&operation_id)?,
+                RangeInclusive::new(from as u64, to as u64),
+            )
+    }
+
+    #[node_bindgen]
+    async fn get_time_distribution(
+        &self,
+        operation_id: String,
+        bucket: i64,
+    ) -> Result<(), stypes::ComputationError> {
+        if bucket <= 0 {
+            return Err(stypes::ComputationError::InvalidArgs(format!(
+                "Invalid length of period: {bucket}"
+            )));
+        }
+        self.session
+            .as_ref()
+            .ok_or(stypes::ComputationError::SessionUnavailable)?
+            .get_time_distribution(operations::uuid_from_str(&operation_id)?, bucket as u64)
+    }
+
+    #[node_bindgen]
+    async fn mine_templates(&self, operation_id: String) -> Result<(), stypes::ComputationError> {
+        self.session
+            .as_ref()
+            .ok_or(stypes::ComputationError::SessionUnavailable)?
+            .mine_templates(operations::uuid_from_str(&operation_id)?)
+    }
+
+    #[node_bindgen]
+    async fn check_sequences(
+        &self,
+        rules: JSArrayBuffer,
+        operation_id: String,
+    ) -> Result<(), stypes::ComputationError> {
+        let rules =
+            stypes::SequenceRules::decode(&rules).map_err(stypes::ComputationError::Decoding)?;
+        self.session
+            .as_ref()
+            .ok_or(stypes::ComputationError::SessionUnavailable)?
+            .check_sequences(operations::uuid_from_str(&operation_id)?, rules.0)
+    }
+
+    #[node_bindgen]
+    async fn hide_templates(&self, ids: Vec<i64>) -> Result<(), stypes::ComputationError> {
+        let mut converted: Vec<u32> = vec![];
+        for id in ids.iter() {
+            converted.push(u32::try_from(*id).map_err(|_| stypes::ComputationError::InvalidData)?);
+        }
+        self.session
+            .as_ref()
+            .ok_or(stypes::ComputationError::SessionUnavailable)?
+            .hide_templates(Some(converted))
+            .await
+    }
+
+    #[node_bindgen]
+    async fn show_templates(&self) -> Result<(), stypes::ComputationError> {
+        self.session
+            .as_ref()
+            .ok_or(stypes::ComputationError::SessionUnavailable)?
+            .hide_templates(None)
+            .await
+    }
+
     #[node_bindgen]
     async fn send_into_sde(
         &self,
@@ -670,6 +889,38 @@ impl RustSession {
         Ok(ranges.into())
     }
 
+    #[node_bindgen]
+    async fn get_values_series(&self) -> Result<stypes::ValueSeriesList, stypes::ComputationError> {
+        self.session
+            .as_ref()
+            .ok_or(stypes::ComputationError::SessionUnavailable)?
+            .get_values_series()
+            .await
+    }
+
+    #[node_bindgen]
+    async fn get_values_statistics(
+        &self,
+        from: Option<i64>,
+        to: Option<i64>,
+    ) -> Result<stypes::ValueStatisticsList, stypes::ComputationError> {
+        let range: Option<RangeInclusive<u64>> = if let (Some(from), Some(to)) = (from, to) {
+            if from < 0 || to < 0 || from > to {
+                return Err(stypes::ComputationError::InvalidArgs(format!(
+                    "Invalid range:from = {from}; to = {to}"
+                )));
+            }
+            Some(RangeInclusive::new(from as u64, to as u64))
+        } else {
+            None
+        };
+        self.session
+            .as_ref()
+            .ok_or(stypes::ComputationError::SessionUnavailable)?
+            .get_values_statistics(range)
+            .await
+    }
+
     #[node_bindgen]
     async fn set_debug(&self, debug: bool) -> Result<(), stypes::ComputationError> {
         self.session
@@ -60,7 +60,10 @@ describe('Benchmark Tests', function () {
                                         .asDlt({
                                             filter_config: undefined,
                                             fibex_file_paths: [],
+                                            arxml_file_paths: undefined,
+                                            dbc_file_paths: undefined,
                                             with_storage_header: true,
+                                            detect_lifecycles: false,
                                             tz: undefined,
                                         })
                                         .get()
@@ -77,7 +80,10 @@ describe('Benchmark Tests', function () {
                                         .asDlt({
                                             filter_config: undefined,
                                             fibex_file_paths: [],
+                                            arxml_file_paths: undefined,
+                                            dbc_file_paths: undefined,
                                             with_storage_header: false,
+                                            detect_lifecycles: false,
                                             tz: undefined,
                                         })
                                         .get()
@@ -313,7 +313,10 @@ describe('Exporting', function () {
                         .asDlt({
                             fibex_file_paths: [],
                             filter_config: undefined,
+                            arxml_file_paths: undefined,
+                            dbc_file_paths: undefined,
                             with_storage_header: true,
+                            detect_lifecycles: false,
                             tz: undefined,
                         })
                         .file(filename)
@@ -409,7 +412,10 @@ describe('Exporting', function () {
                         .asDlt({
                             fibex_file_paths: [],
                             filter_config: undefined,
+                            arxml_file_paths: undefined,
+                            dbc_file_paths: undefined,
                             with_storage_header: true,
+                            detect_lifecycles: false,
                             tz: undefined,
                         })
                         .file(filename)
@@ -442,7 +448,10 @@ describe('Exporting', function () {
                                                     .asDlt({
                                                         fibex_file_paths: [],
                                                         filter_config: undefined,
+                                                        arxml_file_paths: undefined,
+                                                        dbc_file_paths: undefined,
                                                         with_storage_header: true,
+                                                        detect_lifecycles: false,
                                                         tz: undefined,
                                                     })
                                                     .file(output)
@@ -544,7 +553,10 @@ describe('Exporting', function () {
                         .asDlt({
                             fibex_file_paths: [],
                             filter_config: undefined,
+                            arxml_file_paths: undefined,
+                            dbc_file_paths: undefined,
                             with_storage_header: true,
+                            detect_lifecycles: false,
                             tz: undefined,
                         })
                         .files([filename_a, filename_b])
@@ -643,7 +655,10 @@ describe('Exporting', function () {
                         .asDlt({
                             fibex_file_paths: [],
                             filter_config: undefined,
+                            arxml_file_paths: undefined,
+                            dbc_file_paths: undefined,
                             with_storage_header: true,
+                            detect_lifecycles: false,
                             tz: undefined,
                         })
                         .files([filename_a, filename_b])
@@ -682,7 +697,10 @@ describe('Exporting', function () {
                                                     .asDlt({
                                                         fibex_file_paths: [],
                                                         filter_config: undefined,
+                                                        arxml_file_paths: undefined,
+                                                        dbc_file_paths: undefined,
                                                         with_storage_header: true,
+                                                        detect_lifecycles: false,
                                                         tz: undefined,
                                                     })
                                                     .file(output)
@@ -787,7 +805,10 @@ describe('Exporting', function () {
                         .asDlt({
                             fibex_file_paths: [],
                             filter_config: undefined,
+                            arxml_file_paths: undefined,
+                            dbc_file_paths: undefined,
                             with_storage_header: true,
+                            detect_lifecycles: false,
                             tz: undefined,
                         })
                         .files([filename_a, filename_b])
@@ -837,7 +858,10 @@ describe('Exporting', function () {
                                                     .asDlt({
                                                         fibex_file_paths: [],
                                                         filter_config: undefined,
+                                                        arxml_file_paths: undefined,
+                                                        dbc_file_paths: undefined,
                                                         with_storage_header: true,
+                                                        detect_lifecycles: false,
                                                         tz: undefined,
                                                     })
                                                     .file(output)
@@ -937,7 +961,10 @@ describe('Exporting', function () {
                         .asDlt({
                             fibex_file_paths: [],
                             filter_config: undefined,
+                            arxml_file_paths: undefined,
+                            dbc_file_paths: undefined,
                             with_storage_header: true,
+                            detect_lifecycles: false,
                             tz: undefined,
                         })
                         .file(filename)
@@ -1030,7 +1057,10 @@ describe('Exporting', function () {
                         .asDlt({
                             fibex_file_paths: [],
                             filter_config: undefined,
+                            arxml_file_paths: undefined,
+                            dbc_file_paths: undefined,
                             with_storage_header: true,
+                            detect_lifecycles: false,
                             tz: undefined,
                         })
                         .file(filename)
@@ -1122,7 +1152,10 @@ describe('Exporting', function () {
                         .asDlt({
                             fibex_file_paths: [],
                             filter_config: undefined,
+                            arxml_file_paths: undefined,
+                            dbc_file_paths: undefined,
                             with_storage_header: true,
+                            detect_lifecycles: false,
                             tz: undefined,
                         })
                         .file(filename)
@@ -1215,7 +1248,10 @@ describe('Exporting', function () {
                         .asDlt({
                             fibex_file_paths: [],
                             filter_config: undefined,
+                            arxml_file_paths: undefined,
+                            dbc_file_paths: undefined,
                             with_storage_header: true,
+                            detect_lifecycles: false,
                             tz: undefined,
                         })
                         .file(filename)
@@ -77,7 +77,10 @@ describe('Observe', function () {
                         .asDlt({
                             filter_config: undefined,
                             fibex_file_paths: [],
+                            arxml_file_paths: undefined,
+                            dbc_file_paths: undefined,
                             with_storage_header: false,
+                            detect_lifecycles: false,
                             tz: undefined,
                         })
                         .get()
@@ -134,7 +137,10 @@ describe('Observe', function () {
                         .asDlt({
                             filter_config: undefined,
                             fibex_file_paths: [],
+                            arxml_file_paths: undefined,
+                            dbc_file_paths: undefined,
                             with_storage_header: true,
+                            detect_lifecycles: false,
                             tz: undefined,
                         })
                         .get()
@@ -191,7 +197,10 @@ describe('Observe', function () {
                         .asDlt({
                             filter_config: undefined,
                             fibex_file_paths: undefined,
+                            arxml_file_paths: undefined,
+                            dbc_file_paths: undefined,
                             with_storage_header: true,
+                            detect_lifecycles: false,
                             tz: undefined,
                         })
                         .get()
@@ -586,7 +595,10 @@ describe('Observe', function () {
                         .asDlt({
                             filter_config: undefined,
                             fibex_file_paths: [],
+                            arxml_file_paths: undefined,
+                            dbc_file_paths: undefined,
                             with_storage_header: true,
+                            detect_lifecycles: false,
                             tz: undefined,
                         })
                         .get()
@@ -669,7 +681,10 @@ describe('Observe', function () {
                         .asDlt({
                             filter_config: undefined,
                             fibex_file_paths: [config.regular.files['someip-fibex']],
+                            arxml_file_paths: undefined,
+                            dbc_file_paths: undefined,
                             with_storage_header: true,
+                            detect_lifecycles: false,
                             tz: undefined,
                         })
                         .get()
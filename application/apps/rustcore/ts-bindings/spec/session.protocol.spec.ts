@@ -56,6 +56,10 @@ const MAP: { [key: string]: (buf: Uint8Array) => any } = {
     CommandOutcome_bool: protocol.decodeCommandOutcomeWithbool,
     CommandOutcome_FoldersScanningResult: protocol.decodeCommandOutcomeWithFoldersScanningResult,
     CommandOutcome_DltStatisticInfo: protocol.decodeCommandOutcomeWithDltStatisticInfo,
+    CommandOutcome_FormatDetection: protocol.decodeCommandOutcomeWithFormatDetection,
+    CommandOutcome_FilesChronology: protocol.decodeCommandOutcomeWithFilesChronology,
+    CommandOutcome_FilesComparison: protocol.decodeCommandOutcomeWithFilesComparison,
+    CommandOutcome_GrepResult: protocol.decodeCommandOutcomeWithGrepResult,
     CommandOutcome_ProfileList: protocol.decodeCommandOutcomeWithProfileList,
     CommandOutcome_MapKeyValue: protocol.decodeCommandOutcomeWithMapKeyValue,
     CommandOutcome_i64: protocol.decodeCommandOutcomeWithi64,
@@ -68,16 +72,22 @@ const MAP: { [key: string]: (buf: Uint8Array) => any } = {
     FileFormat: protocol.decodeFileFormat,
     FilterMatch: protocol.decodeFilterMatch,
     FilterMatchList: protocol.decodeFilterMatchList,
+    Fold: protocol.decodeFold,
+    FoldList: protocol.decodeFoldList,
     FolderEntity: protocol.decodeFolderEntity,
     FolderEntityDetails: protocol.decodeFolderEntityDetails,
     FolderEntityType: protocol.decodeFolderEntityType,
     FoldersScanningResult: protocol.decodeFoldersScanningResult,
+    FolderSortOrder: protocol.decodeFolderSortOrder,
+    FolderWatch: protocol.decodeFolderWatch,
     GrabbedElement: protocol.decodeGrabbedElement,
     GrabbedElementList: protocol.decodeGrabbedElementList,
     LifecycleTransition: protocol.decodeLifecycleTransition,
     MulticastInfo: protocol.decodeMulticastInfo,
     NativeError: protocol.decodeNativeError,
     NativeErrorKind: protocol.decodeNativeErrorKind,
+    NetworkFilter: protocol.decodeNetworkFilter,
+    NetworkTransport: protocol.decodeNetworkTransport,
     ObserveOptions: protocol.decodeObserveOptions,
     ObserveOrigin: protocol.decodeObserveOrigin,
     OperationDone: protocol.decodeOperationDone,
@@ -93,11 +103,33 @@ const MAP: { [key: string]: (buf: Uint8Array) => any } = {
     SomeIpParserSettings: protocol.decodeSomeIpParserSettings,
     SourceDefinition: protocol.decodeSourceDefinition,
     Sources: protocol.decodeSources,
+    TimestampSettings: protocol.decodeTimestampSettings,
+    TriggerCondition: protocol.decodeTriggerCondition,
+    TriggerAction: protocol.decodeTriggerAction,
+    TriggerRule: protocol.decodeTriggerRule,
+    TriggerRules: protocol.decodeTriggerRules,
+    SequenceStep: protocol.decodeSequenceStep,
+    SequenceRule: protocol.decodeSequenceRule,
+    SequenceRules: protocol.decodeSequenceRules,
     TCPTransportConfig: protocol.decodeTCPTransportConfig,
     Transport: protocol.decodeTransport,
     UdpConnectionInfo: protocol.decodeUdpConnectionInfo,
     UDPTransportConfig: protocol.decodeUDPTransportConfig,
     DltStatisticInfo: protocol.decodeDltStatisticInfo,
+    DetectedParser: protocol.decodeDetectedParser,
+    TextEncoding: protocol.decodeTextEncoding,
+    FormatDetection: protocol.decodeFormatDetection,
+    FileTimeRange: protocol.decodeFileTimeRange,
+    FilesOverlap: protocol.decodeFilesOverlap,
+    FilesChronology: protocol.decodeFilesChronology,
+    MaskRule: protocol.decodeMaskRule,
+    ComparisonOptions: protocol.decodeComparisonOptions,
+    ComparedLine: protocol.decodeComparedLine,
+    DiffHunk: protocol.decodeDiffHunk,
+    FilesComparison: protocol.decodeFilesComparison,
+    GrepOptions: protocol.decodeGrepOptions,
+    GrepMatch: protocol.decodeGrepMatch,
+    GrepResult: protocol.decodeGrepResult,
     Profile: protocol.decodeProfile,
     ProfileList: protocol.decodeProfileList,
     ExtractedMatchValue: protocol.decodeExtractedMatchValue,
@@ -107,8 +139,23 @@ const MAP: { [key: string]: (buf: Uint8Array) => any } = {
     ResultSleep: protocol.decodeResultSleep,
     NearestPosition: protocol.decodeNearestPosition,
     ResultNearestPosition: protocol.decodeResultNearestPosition,
+    NearestTimestamp: protocol.decodeNearestTimestamp,
+    ResultNearestTimestamp: protocol.decodeResultNearestTimestamp,
+    ResultTimeRange: protocol.decodeResultTimeRange,
+    TimeBucket: protocol.decodeTimeBucket,
+    ResultTimeDistribution: protocol.decodeResultTimeDistribution,
+    SequenceViolation: protocol.decodeSequenceViolation,
+    SequenceInstance: protocol.decodeSequenceInstance,
+    ResultSequences: protocol.decodeResultSequences,
     Point: protocol.decodePoint,
     ResultSearchValues: protocol.decodeResultSearchValues,
+    ValueSeriesKind: protocol.decodeValueSeriesKind,
+    ValueSeries: protocol.decodeValueSeries,
+    ValueSeriesList: protocol.decodeValueSeriesList,
+    ValueStatistics: protocol.decodeValueStatistics,
+    ValueStatisticsList: protocol.decodeValueStatisticsList,
+    LogTemplate: protocol.decodeLogTemplate,
+    LogTemplateList: protocol.decodeLogTemplateList,
     ResultScaledDistribution: protocol.decodeResultScaledDistribution,
     DltLevelDistribution: protocol.decodeDltLevelDistribution,
     PluginParserSettings: protocol.decodePluginParserSettings,
@@ -141,6 +188,14 @@ const MAP: { [key: string]: (buf: Uint8Array) => any } = {
     CommandOutcome_Option_InvalidPluginEntity:
         protocol.decodeCommandOutcomeWithOptionInvalidPluginEntity,
     CommandOutcome_Option_PluginRunData: protocol.decodeCommandOutcomeWithOptionPluginRunData,
+    SearchCondition: protocol.decodeSearchCondition,
+    SessionRequest: protocol.decodeSessionRequest,
+    UnboundRequest: protocol.decodeUnboundRequest,
+    ServerCommand: protocol.decodeServerCommand,
+    ServerRequest: protocol.decodeServerRequest,
+    ServerResult: protocol.decodeServerResult,
+    ServerResponse: protocol.decodeServerResponse,
+    ServerMessage: protocol.decodeServerMessage,
 };
 
 const OUTPUT_PATH_ENVVAR = 'CHIPMUNK_PROTOCOL_TEST_OUTPUT';
@@ -151,7 +206,10 @@ describe('Protocol', function () {
             function check(origin: $.IObserve) {
                 const bytes = protocol.encodeObserveOptions(origin);
                 const decoded = protocol.decodeObserveOptions(bytes);
-                expect(deepEqualObj(decoded, origin)).toBe(true);
+                // Options without network filter and ordering are decoded with empty ones.
+                expect(
+                    deepEqualObj(decoded, { network_filter: null, order_by_time: null, ...origin }),
+                ).toBe(true);
             }
             check({
                 origin: { File: ['somefile', $.Types.File.FileType.Text, 'path_to_file'] },
@@ -196,7 +254,10 @@ describe('Protocol', function () {
                     Dlt: {
                         fibex_file_paths: ['path'],
                         filter_config: undefined,
+                        arxml_file_paths: undefined,
+                        dbc_file_paths: undefined,
                         with_storage_header: true,
+                        detect_lifecycles: false,
                         tz: 'zz',
                     },
                 },
@@ -209,7 +270,10 @@ describe('Protocol', function () {
                     Dlt: {
                         fibex_file_paths: [],
                         filter_config: undefined,
+                        arxml_file_paths: undefined,
+                        dbc_file_paths: undefined,
                         with_storage_header: true,
+                        detect_lifecycles: false,
                         tz: 'zz',
                     },
                 },
@@ -222,7 +286,10 @@ describe('Protocol', function () {
                     Dlt: {
                         fibex_file_paths: undefined,
                         filter_config: undefined,
+                        arxml_file_paths: undefined,
+                        dbc_file_paths: undefined,
                         with_storage_header: true,
+                        detect_lifecycles: false,
                         tz: 'zz',
                     },
                 },
@@ -242,7 +309,10 @@ describe('Protocol', function () {
                             ecu_ids: ['test'],
                             context_ids: ['test'],
                         },
+                        arxml_file_paths: undefined,
+                        dbc_file_paths: undefined,
                         with_storage_header: true,
+                        detect_lifecycles: false,
                         tz: 'zz',
                     },
                 },
@@ -5,8 +5,14 @@ import { executor as ValuesGetterExecutor } from './session.stream.get_values.ex
 import { executor as AssignExecutor } from './session.stream.observe.executor';
 import { executor as ExportExecutor } from './session.stream.export.executor';
 import { executor as ExportRawExecutor } from './session.stream.export_raw.executor';
+import { executor as ExportContextExecutor } from './session.stream.export_context.executor';
 import { executor as ExtractExecutor } from './session.stream.extract.executor';
 import { executor as NearestExecutor } from './session.stream.nearest.executor';
+import { executor as NearestTimestampExecutor } from './session.stream.timestamp.executor';
+import { executor as TimeRangeExecutor } from './session.stream.time_range.executor';
+import { executor as TimeDistributionExecutor } from './session.stream.time_distribution.executor';
+import { executor as TemplatesExecutor } from './session.stream.templates.executor';
+import { executor as SequencesExecutor } from './session.stream.sequences.executor';
 
 export const Executors = {
     search: SearchExecutor,
@@ -16,6 +22,12 @@ export const Executors = {
     observe: AssignExecutor,
     export: ExportExecutor,
     exportRaw: ExportRawExecutor,
+    exportSearchContext: ExportContextExecutor,
     extract: ExtractExecutor,
     nearest: NearestExecutor,
+    nearestTimestamp: NearestTimestampExecutor,
+    timeRange: TimeRangeExecutor,
+    timeDistribution: TimeDistributionExecutor,
+    templates: TemplatesExecutor,
+    sequences: SequencesExecutor,
 };
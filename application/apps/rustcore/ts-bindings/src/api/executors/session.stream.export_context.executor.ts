@@ -0,0 +1,52 @@
+import { TExecutor, Logger, CancelablePromise, AsyncResultsExecutor } from './executor';
+import { RustSession } from '../../native/native.session';
+import { EventProvider } from '../../api/session.provider';
+import { TextExportOptions } from 'platform/types/exporting';
+
+import * as protocol from 'protocol';
+
+export interface Options {
+    dest: string;
+    before: number;
+    after: number;
+    opt: TextExportOptions;
+}
+
+export const executor: TExecutor<boolean, Options> = (
+    session: RustSession,
+    provider: EventProvider,
+    logger: Logger,
+    opt: Options,
+): CancelablePromise<boolean> => {
+    return AsyncResultsExecutor<boolean, Options>(
+        session,
+        provider,
+        logger,
+        opt,
+        function (session: RustSession, opt: Options, operationUuid: string): Promise<void> {
+            return session.exportSearchContext(
+                opt.dest,
+                opt.before,
+                opt.after,
+                opt.opt,
+                operationUuid,
+            );
+        },
+        function (
+            data: Uint8Array,
+            resolve: (done: boolean) => void,
+            reject: (err: Error) => void,
+        ) {
+            const result: boolean = protocol.decodeResultBool(data);
+            if (typeof result !== 'boolean') {
+                return reject(
+                    new Error(
+                        `Fail to parse export results. Invalid format. Expecting valid { boolean }; gotten: ${typeof data}`,
+                    ),
+                );
+            }
+            resolve(result);
+        },
+        'exporting',
+    );
+};
@@ -0,0 +1,45 @@
+import { TExecutor, Logger, CancelablePromise, AsyncResultsExecutor } from './executor';
+import { RustSession } from '../../native/native.session';
+import { EventProvider } from '../../api/session.provider';
+import { SequenceInstance, SequenceRule } from 'platform/types/bindings';
+
+import * as protocol from 'protocol';
+
+export const executor: TExecutor<SequenceInstance[], SequenceRule[]> = (
+    session: RustSession,
+    provider: EventProvider,
+    logger: Logger,
+    options: SequenceRule[],
+): CancelablePromise<SequenceInstance[]> => {
+    return AsyncResultsExecutor<SequenceInstance[], SequenceRule[]>(
+        session,
+        provider,
+        logger,
+        options,
+        function (
+            session: RustSession,
+            options: SequenceRule[],
+            operationUuid: string,
+        ): Promise<any> {
+            return session.checkSequences(options, operationUuid);
+        },
+        function (
+            data: Uint8Array,
+            resolve: (res: SequenceInstance[]) => void,
+            reject: (err: Error) => void,
+        ) {
+            try {
+                resolve(protocol.decodeResultSequences(data));
+            } catch (e) {
+                return reject(
+                    new Error(
+                        `Fail to parse checkSequences results. Error: ${
+                            e instanceof Error ? e.message : e
+                        }`,
+                    ),
+                );
+            }
+        },
+        'checkSequences',
+    );
+};
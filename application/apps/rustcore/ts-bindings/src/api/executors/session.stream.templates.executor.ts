@@ -0,0 +1,45 @@
+import { TExecutor, Logger, CancelablePromise, AsyncResultsExecutor } from './executor';
+import { RustSession } from '../../native/native.session';
+import { EventProvider } from '../../api/session.provider';
+import { LogTemplate } from 'platform/types/bindings';
+
+import * as protocol from 'protocol';
+
+export const executor: TExecutor<LogTemplate[], void> = (
+    session: RustSession,
+    provider: EventProvider,
+    logger: Logger,
+    options: void,
+): CancelablePromise<LogTemplate[]> => {
+    return AsyncResultsExecutor<LogTemplate[], void>(
+        session,
+        provider,
+        logger,
+        options,
+        function (
+            session: RustSession,
+            _options: void,
+            operationUuid: string,
+        ): Promise<any> {
+            return session.mineTemplates(operationUuid);
+        },
+        function (
+            data: Uint8Array,
+            resolve: (res: LogTemplate[]) => void,
+            reject: (err: Error) => void,
+        ) {
+            try {
+                resolve(protocol.decodeLogTemplateList(data));
+            } catch (e) {
+                return reject(
+                    new Error(
+                        `Fail to parse mineTemplates results. Error: ${
+                            e instanceof Error ? e.message : e
+                        }`,
+                    ),
+                );
+            }
+        },
+        'mineTemplates',
+    );
+};
@@ -0,0 +1,50 @@
+import { TExecutor, Logger, CancelablePromise, AsyncResultsExecutor } from './executor';
+import { RustSession } from '../../native/native.session';
+import { EventProvider } from '../../api/session.provider';
+import { TimeBucket } from 'platform/types/bindings';
+
+import * as protocol from 'protocol';
+
+export interface IExecuteTimeDistributionOptions {
+    // Length of a period in milliseconds
+    bucket: number;
+}
+
+export const executor: TExecutor<TimeBucket[], IExecuteTimeDistributionOptions> = (
+    session: RustSession,
+    provider: EventProvider,
+    logger: Logger,
+    options: IExecuteTimeDistributionOptions,
+): CancelablePromise<TimeBucket[]> => {
+    return AsyncResultsExecutor<TimeBucket[], IExecuteTimeDistributionOptions>(
+        session,
+        provider,
+        logger,
+        options,
+        function (
+            session: RustSession,
+            options: IExecuteTimeDistributionOptions,
+            operationUuid: string,
+        ): Promise<any> {
+            return session.getTimeDistribution(operationUuid, options.bucket);
+        },
+        function (
+            data: Uint8Array,
+            resolve: (res: TimeBucket[]) => void,
+            reject: (err: Error) => void,
+        ) {
+            try {
+                resolve(protocol.decodeResultTimeDistribution(data));
+            } catch (e) {
+                return reject(
+                    new Error(
+                        `Fail to parse getTimeDistribution results. Error: ${
+                            e instanceof Error ? e.message : e
+                        }`,
+                    ),
+                );
+            }
+        },
+        'getTimeDistribution',
+    );
+};
@@ -0,0 +1,52 @@
+import { TExecutor, Logger, CancelablePromise, AsyncResultsExecutor } from './executor';
+import { RustSession } from '../../native/native.session';
+import { EventProvider } from '../../api/session.provider';
+import { Range, ResultTimeRange } from 'platform/types/bindings';
+
+import * as protocol from 'protocol';
+
+export interface IExecuteTimeRangeOptions {
+    // Milliseconds since the UNIX epoch
+    from: number;
+    to: number;
+}
+
+export const executor: TExecutor<Range | undefined, IExecuteTimeRangeOptions> = (
+    session: RustSession,
+    provider: EventProvider,
+    logger: Logger,
+    options: IExecuteTimeRangeOptions,
+): CancelablePromise<Range | undefined> => {
+    return AsyncResultsExecutor<Range | undefined, IExecuteTimeRangeOptions>(
+        session,
+        provider,
+        logger,
+        options,
+        function (
+            session: RustSession,
+            options: IExecuteTimeRangeOptions,
+            operationUuid: string,
+        ): Promise<any> {
+            return session.getTimeRange(operationUuid, options.from, options.to);
+        },
+        function (
+            data: Uint8Array,
+            resolve: (res: Range | undefined) => void,
+            reject: (err: Error) => void,
+        ) {
+            try {
+                const result: ResultTimeRange = protocol.decodeResultTimeRange(data);
+                resolve(result === null ? undefined : result);
+            } catch (e) {
+                return reject(
+                    new Error(
+                        `Fail to parse getTimeRange results. Error: ${
+                            e instanceof Error ? e.message : e
+                        }`,
+                    ),
+                );
+            }
+        },
+        'getTimeRange',
+    );
+};
@@ -0,0 +1,51 @@
+import { TExecutor, Logger, CancelablePromise, AsyncResultsExecutor } from './executor';
+import { RustSession } from '../../native/native.session';
+import { EventProvider } from '../../api/session.provider';
+import { NearestTimestamp, ResultNearestTimestamp } from 'platform/types/bindings';
+
+import * as protocol from 'protocol';
+
+export interface IExecuteNearestTimestampOptions {
+    // Milliseconds since the UNIX epoch
+    timestamp: number;
+}
+
+export const executor: TExecutor<NearestTimestamp | undefined, IExecuteNearestTimestampOptions> = (
+    session: RustSession,
+    provider: EventProvider,
+    logger: Logger,
+    options: IExecuteNearestTimestampOptions,
+): CancelablePromise<NearestTimestamp | undefined> => {
+    return AsyncResultsExecutor<NearestTimestamp | undefined, IExecuteNearestTimestampOptions>(
+        session,
+        provider,
+        logger,
+        options,
+        function (
+            session: RustSession,
+            options: IExecuteNearestTimestampOptions,
+            operationUuid: string,
+        ): Promise<any> {
+            return session.getNearestToTimestamp(operationUuid, options.timestamp);
+        },
+        function (
+            data: Uint8Array,
+            resolve: (res: NearestTimestamp | undefined) => void,
+            reject: (err: Error) => void,
+        ) {
+            try {
+                const result: ResultNearestTimestamp = protocol.decodeResultNearestTimestamp(data);
+                resolve(result === null ? undefined : result);
+            } catch (e) {
+                return reject(
+                    new Error(
+                        `Fail to parse getNearestToTimestamp results. Error: ${
+                            e instanceof Error ? e.message : e
+                        }`,
+                    ),
+                );
+            }
+        },
+        'getNearestToTimestamp',
+    );
+};
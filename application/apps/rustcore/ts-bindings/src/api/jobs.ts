@@ -6,6 +6,12 @@ import { SomeipStatistic } from 'platform/types/observe/parser/someip';
 import {
     FoldersScanningResult,
     DltStatisticInfo,
+    FormatDetection,
+    FilesChronology,
+    ComparisonOptions,
+    FilesComparison,
+    GrepOptions,
+    GrepResult,
     Profile,
     ProfileList,
     MapKeyValue,
@@ -94,6 +100,67 @@ export class Jobs extends Base {
         return job;
     }
 
+    public detectFileFormat(filePath: string): CancelablePromise<FormatDetection> {
+        const sequence = this.sequence();
+        const job: CancelablePromise<FormatDetection> = this.execute(
+            (buf: Uint8Array): FormatDetection | Error => {
+                return decode<FormatDetection>(
+                    buf,
+                    protocol.decodeCommandOutcomeWithFormatDetection,
+                );
+            },
+            this.native.detectFileFormat(sequence, filePath),
+            sequence,
+            'detectFileFormat',
+        );
+        return job;
+    }
+
+    public getFilesChronology(files: string[]): CancelablePromise<FilesChronology> {
+        const sequence = this.sequence();
+        const job: CancelablePromise<FilesChronology> = this.execute(
+            (buf: Uint8Array): FilesChronology | Error => {
+                return decode<FilesChronology>(
+                    buf,
+                    protocol.decodeCommandOutcomeWithFilesChronology,
+                );
+            },
+            this.native.getFilesChronology(sequence, files),
+            sequence,
+            'getFilesChronology',
+        );
+        return job;
+    }
+
+    public compareFiles(options: ComparisonOptions): CancelablePromise<FilesComparison> {
+        const sequence = this.sequence();
+        const job: CancelablePromise<FilesComparison> = this.execute(
+            (buf: Uint8Array): FilesComparison | Error => {
+                return decode<FilesComparison>(
+                    buf,
+                    protocol.decodeCommandOutcomeWithFilesComparison,
+                );
+            },
+            this.native.compareFiles(sequence, protocol.encodeComparisonOptions(options)),
+            sequence,
+            'compareFiles',
+        );
+        return job;
+    }
+
+    public grep(options: GrepOptions): CancelablePromise<GrepResult> {
+        const sequence = this.sequence();
+        const job: CancelablePromise<GrepResult> = this.execute(
+            (buf: Uint8Array): GrepResult | Error => {
+                return decode<GrepResult>(buf, protocol.decodeCommandOutcomeWithGrepResult);
+            },
+            this.native.grep(sequence, protocol.encodeGrepOptions(options)),
+            sequence,
+            'grep',
+        );
+        return job;
+    }
+
     public spawnProcess(path: string, args: string[]): CancelablePromise<void> {
         const sequence = this.sequence();
         const job: CancelablePromise<void> = this.execute(
@@ -3,7 +3,7 @@ import { ISearchUpdated } from 'platform/types/filter';
 import { Computation } from '../provider/provider';
 import { EErrorKind, EErrorSeverity } from '../provider/provider.errors';
 import { IMapEntity, IMatchEntity, FilterMatch } from 'platform/types/filter';
-import { AttachmentInfo } from 'platform/types/bindings';
+import { AttachmentInfo, TimeBucket } from 'platform/types/bindings';
 
 import * as protocol from 'protocol';
 
@@ -50,6 +50,17 @@ export interface IAttachmentsUpdatedUpdated {
     attachment: AttachmentInfo;
 }
 
+export interface IFileRotatedEvent {
+    source_id: number;
+    pos: number;
+    truncated: boolean;
+}
+
+export interface ITriggerFiredEvent {
+    rule: number;
+    row: number;
+}
+
 export interface ISessionEvents {
     StreamUpdated: Subject<number>;
     FileRead: Subject<void>;
@@ -61,6 +72,9 @@ export interface ISessionEvents {
     MatchesUpdated: Subject<IEventMatchesUpdated>;
     Progress: Subject<IProgressEvent>;
     AttachmentsUpdated: Subject<IAttachmentsUpdatedUpdated>;
+    FileRotated: Subject<IFileRotatedEvent>;
+    TimeDistributionUpdated: Subject<TimeBucket[]>;
+    TriggerFired: Subject<ITriggerFiredEvent>;
     SessionError: Subject<IError>;
     OperationError: Subject<IErrorEvent>;
     SessionDestroyed: Subject<void>;
@@ -82,6 +96,9 @@ interface ISessionEventsSignatures {
     MatchesUpdated: 'MatchesUpdated';
     Progress: 'Progress';
     AttachmentsUpdated: 'AttachmentsUpdated';
+    FileRotated: 'FileRotated';
+    TimeDistributionUpdated: 'TimeDistributionUpdated';
+    TriggerFired: 'TriggerFired';
     SessionError: 'SessionError';
     OperationError: 'OperationError';
     SessionDestroyed: 'SessionDestroyed';
@@ -100,6 +117,9 @@ const SessionEventsSignatures: ISessionEventsSignatures = {
     IndexedMapUpdated: 'IndexedMapUpdated',
     MatchesUpdated: 'MatchesUpdated',
     AttachmentsUpdated: 'AttachmentsUpdated',
+    FileRotated: 'FileRotated',
+    TimeDistributionUpdated: 'TimeDistributionUpdated',
+    TriggerFired: 'TriggerFired',
     Progress: 'Progress',
     SessionError: 'SessionError',
     OperationError: 'OperationError',
@@ -127,6 +147,9 @@ interface ISessionEventsInterfaces {
         ];
     };
     AttachmentsUpdated: { self: 'object'; len: 'number'; attachment: typeof Object };
+    FileRotated: { self: 'object'; source_id: 'number'; pos: 'number'; truncated: 'boolean' };
+    TimeDistributionUpdated: { self: typeof Array };
+    TriggerFired: { self: 'object'; rule: 'number'; row: 'number' };
     SessionError: { self: 'object'; severity: 'string'; message: 'string'; kind: 'string' };
     OperationError: {
         self: 'object';
@@ -157,6 +180,9 @@ const SessionEventsInterfaces: ISessionEventsInterfaces = {
         ],
     },
     AttachmentsUpdated: { self: 'object', len: 'number', attachment: Object },
+    FileRotated: { self: 'object', source_id: 'number', pos: 'number', truncated: 'boolean' },
+    TimeDistributionUpdated: { self: Array },
+    TriggerFired: { self: 'object', rule: 'number', row: 'number' },
     SessionError: { self: 'object', severity: 'string', message: 'string', kind: 'string' },
     OperationError: {
         self: 'object',
@@ -185,6 +211,9 @@ export class EventProvider extends Computation<
         MatchesUpdated: new Subject<IEventMatchesUpdated>(), // dummy
         Progress: new Subject<IProgressEvent>(),
         AttachmentsUpdated: new Subject<IAttachmentsUpdatedUpdated>(),
+        FileRotated: new Subject<IFileRotatedEvent>(),
+        TimeDistributionUpdated: new Subject<TimeBucket[]>(),
+        TriggerFired: new Subject<ITriggerFiredEvent>(),
         SessionError: new Subject<IError>(),
         OperationError: new Subject<IErrorEvent>(),
         SessionDestroyed: new Subject<void>(),
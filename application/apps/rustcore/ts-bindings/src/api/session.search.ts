@@ -5,7 +5,16 @@ import { ICancelablePromise } from 'platform/env/promise';
 import { EventProvider } from '../api/session.provider';
 import { GrabbedElement } from 'platform/types/bindings/miscellaneous';
 import { IFilter, ISearchMap, TExtractedValues } from 'platform/types/filter';
-import { ResultSearchValues, NearestPosition } from 'platform/types/bindings';
+import {
+    ResultSearchValues,
+    NearestPosition,
+    ValueSeries,
+    ValueStatistics,
+    TimeBucket,
+    LogTemplate,
+    SequenceInstance,
+    SequenceRule,
+} from 'platform/types/bindings';
 import { Executors } from './executors/session.stream.executors';
 import { SearchTaskManager } from './executors/single.task.search';
 import { ValuesTaskManager } from './executors/single.task.values';
@@ -53,6 +62,23 @@ export class SessionSearch {
         return this.session.grabSearchChunk(start, len);
     }
 
+    /**
+     * Returns rows of search results with rows around them (like "grep -C"). Overlapping
+     * windows are merged; rows of matches can be recognized by their nature.
+     * @param start { number } - first row number in search result
+     * @param len { number } - count of rows in search result, starting from @param start
+     * @param before { number } - count of rows before each match
+     * @param after { number } - count of rows after each match
+     */
+    public grabContext(
+        start: number,
+        len: number,
+        before: number,
+        after: number,
+    ): Promise<GrabbedElement[]> {
+        return this.session.grabSearchContext(start, len, before, after);
+    }
+
     /**
      * Retruns a chunk of matches results, which were gotten with filters by @method setMatches
      * @param start { number } - first row number in search result
@@ -114,6 +140,81 @@ export class SessionSearch {
         });
     }
 
+    /**
+     * Returns descriptions of the datasets of the last search of values: the term each
+     * dataset belongs to, the name of its capture group and the kind of its values.
+     */
+    public getValuesSeries(): Promise<ValueSeries[]> {
+        return this.session.getValuesSeries();
+    }
+
+    /**
+     * Returns statistics (count, min/max, mean, median, p90/p99 and standard deviation) of
+     * the datasets of the last search of values. Without a range the statistics cover the
+     * whole session.
+     */
+    public getValuesStatistics(from?: number, to?: number): Promise<ValueStatistics[]> {
+        return this.session.getValuesStatistics(from, to);
+    }
+
+    /**
+     * Counts the matches of each filter within periods of time using the timestamps of rows
+     * (requires timestamp settings, see stream.setTimestampSettings()). The result isn't
+     * updated automatically: request it again on search updates to follow a live stream.
+     * @param bucket length of a period in milliseconds
+     */
+    public getTimeDistribution(bucket: number): ICancelablePromise<TimeBucket[]> {
+        return Executors.timeDistribution(this.session, this.provider, this.logger, {
+            bucket,
+        });
+    }
+
+    /**
+     * Mines templates of messages (constant tokens with parameters replaced by `<*>`) from
+     * the rows of the session. Each call continues from the rows mined before, so it can be
+     * repeated to follow a live stream.
+     */
+    public mineTemplates(): ICancelablePromise<LogTemplate[]> {
+        return Executors.templates(this.session, this.provider, this.logger, undefined);
+    }
+
+    /**
+     * Hides templates from the results of mining. Only the list of templates is affected,
+     * rows of hidden templates stay in the stream.
+     * @param ids IDs of templates
+     */
+    public hideTemplates(ids: number[]): Promise<void> {
+        return this.session.hideTemplates(ids);
+    }
+
+    /**
+     * Shows all templates hidden before
+     */
+    public showTemplates(): Promise<void> {
+        return this.session.showTemplates();
+    }
+
+    /**
+     * Checks the rows of the session against sequence rules: ordered steps following each
+     * other within max gaps, without forbidden rows in between. Returns all found instances
+     * of the rules; violated ones have `violation` set. Max gaps in time require timestamp
+     * settings (see stream.setTimestampSettings()).
+     * @param rules sequence rules
+     */
+    public checkSequences(rules: SequenceRule[]): ICancelablePromise<SequenceInstance[]> {
+        return Executors.sequences(this.session, this.provider, this.logger, rules);
+    }
+
+    /**
+     * Returns a filter matching the rows of the template
+     */
+    public templateToFilter(template: LogTemplate): IFilter {
+        return {
+            filter: template.filter,
+            flags: { reg: true, word: false, cases: true },
+        };
+    }
+
     public getNearest(positionInStream: number): ICancelablePromise<NearestPosition | undefined> {
         return Executors.nearest(this.session, this.provider, this.logger, {
             positionInStream,
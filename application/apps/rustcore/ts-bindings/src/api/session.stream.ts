@@ -6,7 +6,14 @@ import { SdeRequest, SdeResponse } from 'platform/types/sde';
 import { EventProvider } from '../api/session.provider';
 import { Executors } from './executors/session.stream.executors';
 import { EFileOptionsRequirements } from './executors/session.stream.observe.executor';
-import { GrabbedElement } from 'platform/types/bindings/miscellaneous';
+import { Fold, GrabbedElement } from 'platform/types/bindings/miscellaneous';
+import {
+    DltStatisticInfo,
+    NearestTimestamp,
+    Range,
+    TimestampSettings,
+    TriggerRule,
+} from 'platform/types/bindings';
 import { IRange } from 'platform/types/range';
 import { ISourceLink } from 'platform/types/observe/types';
 import { Attachment, IndexingMode } from 'platform/types/content';
@@ -69,6 +76,14 @@ export class SessionStream {
         return this._session.expandBreadcrumbs(seporator, offset, above);
     }
 
+    public expandFold(row: number): Promise<void> {
+        return this._session.expandFold(row);
+    }
+
+    public getFolds(from: number, to: number): Promise<Fold[]> {
+        return this._session.getFolds(from, to);
+    }
+
     public grabRanges(ranges: IRange[]): Promise<GrabbedElement[]> {
         return this._session.grabStreamRanges(ranges);
     }
@@ -81,6 +96,10 @@ export class SessionStream {
         return this._session.getSourcesDefinitions();
     }
 
+    public getDltStats(): Promise<DltStatisticInfo> {
+        return this._session.getDltStats();
+    }
+
     public observe(source: IObserve): ICancelablePromise<void> {
         return Executors.observe(this._session, this._provider, this._logger, source);
     }
@@ -111,6 +130,27 @@ export class SessionStream {
         return Executors.export(this._session, this._provider, this._logger, { dest, ranges, opt });
     }
 
+    /**
+     * Exports rows of search matches with rows around them (like "grep -C"). Overlapping
+     * windows are merged.
+     * @param dest path of the output file
+     * @param before count of rows before each match
+     * @param after count of rows after each match
+     */
+    public exportSearchContext(
+        dest: string,
+        before: number,
+        after: number,
+        opt: TextExportOptions,
+    ): ICancelablePromise<boolean> {
+        return Executors.exportSearchContext(this._session, this._provider, this._logger, {
+            dest,
+            before,
+            after,
+            opt,
+        });
+    }
+
     public exportRaw(dest: string, ranges: IRange[]): ICancelablePromise<boolean> {
         return Executors.exportRaw(this._session, this._provider, this._logger, { dest, ranges });
     }
@@ -118,4 +158,38 @@ export class SessionStream {
     public len(): Promise<number> {
         return this._session.getStreamLen();
     }
+
+    public setTimestampSettings(settings: TimestampSettings): Promise<void> {
+        return this._session.setTimestampSettings(settings);
+    }
+
+    /**
+     * Sets the trigger rules checked on each row written into the session while sources are
+     * observed. Rules are identified by their positions in TriggerFired events.
+     * @param rules rules replacing previous rules; an empty list turns them off
+     */
+    public setTriggers(rules: TriggerRule[]): Promise<void> {
+        return this._session.setTriggers(rules);
+    }
+
+    /**
+     * Finds the row with the timestamp nearest to the given one
+     * @param timestamp milliseconds since the UNIX epoch
+     */
+    public getNearestToTimestamp(
+        timestamp: number,
+    ): ICancelablePromise<NearestTimestamp | undefined> {
+        return Executors.nearestTimestamp(this._session, this._provider, this._logger, {
+            timestamp,
+        });
+    }
+
+    /**
+     * Finds the rows with timestamps within the given period; use grab() to read them
+     * @param from milliseconds since the UNIX epoch
+     * @param to milliseconds since the UNIX epoch
+     */
+    public getTimeRange(from: number, to: number): ICancelablePromise<Range | undefined> {
+        return Executors.timeRange(this._session, this._provider, this._logger, { from, to });
+    }
 }
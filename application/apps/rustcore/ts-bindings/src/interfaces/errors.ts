@@ -50,11 +50,14 @@ export enum Source {
     GetSearchLen = 'GetSearchLen',
     GetFilters = 'GetFilters',
     GetSourcesDefinitions = 'GetSourcesDefinitions',
+    GetDltStats = 'GetDltStats',
     GetOperationsStat = 'GetOperationsStat',
     SetDebug = 'SetDebug',
     SendIntoSde = 'SendIntoSde',
     GetAttachments = 'GetAttachments',
     GetIndexedRanges = 'GetIndexedRanges',
+    GetValuesSeries = 'GetValuesSeries',
+    GetValuesStatistics = 'GetValuesStatistics',
     Concat = 'Concat',
     Merge = 'Merge',
     Extract = 'Extract',
@@ -70,6 +73,8 @@ export enum Source {
     AddSelection = 'AddSelection',
     RemoveBookmark = 'RemoveBookmark',
     ExpandBreadcrumbs = 'ExpandBreadcrumbs',
+    ExpandFold = 'ExpandFold',
+    GetFolds = 'GetFolds',
     SetIndexingMode = 'SetIndexingMode',
     GetIndexedLen = 'GetIndexedLen',
     getAroundIndexes = 'getAroundIndexes',
@@ -14,6 +14,14 @@ export abstract class JobsNative {
 
     public abstract isFileBinary(sequence: number, filePath: string): Promise<Uint8Array>;
 
+    public abstract detectFileFormat(sequence: number, filePath: string): Promise<Uint8Array>;
+
+    public abstract getFilesChronology(sequence: number, files: string[]): Promise<Uint8Array>;
+
+    public abstract compareFiles(sequence: number, options: Uint8Array): Promise<Uint8Array>;
+
+    public abstract grep(sequence: number, options: Uint8Array): Promise<Uint8Array>;
+
     public abstract jobCancelTest(
         sequence: number,
         num_a: number,
@@ -3,7 +3,15 @@ import { RustSessionRequiered } from '../native/native.session.required';
 import { TEventEmitter } from '../provider/provider.general';
 import { Computation } from '../provider/provider';
 import { IFilter } from 'platform/types/filter';
-import { GrabbedElement } from 'platform/types/bindings/miscellaneous';
+import { Fold, GrabbedElement } from 'platform/types/bindings/miscellaneous';
+import {
+    DltStatisticInfo,
+    SequenceRule,
+    TimestampSettings,
+    TriggerRule,
+    ValueSeries,
+    ValueStatistics,
+} from 'platform/types/bindings';
 import { getNativeModule } from '../native/native';
 import { EFileOptionsRequirements } from '../api/executors/session.stream.observe.executor';
 import { Type, Source, NativeError } from '../interfaces/errors';
@@ -63,6 +71,10 @@ export abstract class RustSession extends RustSessionRequiered {
         above: boolean,
     ): Promise<void>;
 
+    public abstract expandFold(row: number): Promise<void>;
+
+    public abstract getFolds(from: number, to: number): Promise<Fold[]>;
+
     public abstract removeBookmark(row: number): Promise<void>;
 
     public abstract addBookmark(row: number): Promise<void>;
@@ -78,6 +90,20 @@ export abstract class RustSession extends RustSessionRequiered {
      */
     public abstract grabSearchChunk(start: number, len: number): Promise<GrabbedElement[]>;
 
+    /**
+     * Returns rows of search matches with rows around them (like "grep -C")
+     * @param start { number } first position in search results
+     * @param len { number } count of search results, starting from @param start
+     * @param before { number } count of rows before each match
+     * @param after { number } count of rows after each match
+     */
+    public abstract grabSearchContext(
+        start: number,
+        len: number,
+        before: number,
+        after: number,
+    ): Promise<GrabbedElement[]>;
+
     /**
      * TODO: @return needs interface. It should not be a string
      */
@@ -89,6 +115,12 @@ export abstract class RustSession extends RustSessionRequiered {
      */
     public abstract getSourcesDefinitions(): Promise<ISourceLink[]>;
 
+    /**
+     * Returns statistics of DLT messages of all DLT sources observed in the session.
+     * @returns { DltStatisticInfo }
+     */
+    public abstract getDltStats(): Promise<DltStatisticInfo>;
+
     public abstract getUuid(): string;
 
     public abstract getSessionFile(): Promise<string>;
@@ -146,6 +178,14 @@ export abstract class RustSession extends RustSessionRequiered {
 
     public abstract exportRaw(dest: string, ranges: IRange[], operationUuid: string): Promise<void>;
 
+    public abstract exportSearchContext(
+        dest: string,
+        before: number,
+        after: number,
+        opt: TextExportOptions,
+        operationUuid: string,
+    ): Promise<void>;
+
     public abstract isRawExportAvailable(): Promise<boolean>;
 
     public abstract searchNestedMatch(
@@ -181,6 +221,24 @@ export abstract class RustSession extends RustSessionRequiered {
         positionInStream: number,
     ): Promise<{ index: number; position: number } | undefined>;
 
+    public abstract setTimestampSettings(settings: TimestampSettings): Promise<void>;
+
+    public abstract setTriggers(rules: TriggerRule[]): Promise<void>;
+
+    public abstract getNearestToTimestamp(operationUuid: string, timestamp: number): Promise<void>;
+
+    public abstract getTimeRange(operationUuid: string, from: number, to: number): Promise<void>;
+
+    public abstract getTimeDistribution(operationUuid: string, bucket: number): Promise<void>;
+
+    public abstract mineTemplates(operationUuid: string): Promise<void>;
+
+    public abstract checkSequences(rules: SequenceRule[], operationUuid: string): Promise<void>;
+
+    public abstract hideTemplates(ids: number[]): Promise<void>;
+
+    public abstract showTemplates(): Promise<void>;
+
     public abstract sendIntoSde(
         targetOperationUuid: string,
         request: types.sde.SdeRequest,
@@ -189,6 +247,10 @@ export abstract class RustSession extends RustSessionRequiered {
     public abstract getAttachments(): Promise<Attachment[]>;
     public abstract getIndexedRanges(): Promise<IRange[]>;
 
+    public abstract getValuesSeries(): Promise<ValueSeries[]>;
+
+    public abstract getValuesStatistics(from?: number, to?: number): Promise<ValueStatistics[]>;
+
     public abstract abort(
         selfOperationUuid: string,
         targetOperationUuid: string,
@@ -233,6 +295,8 @@ export abstract class RustSessionNative {
 
     public abstract getSourcesDefinitions(): Promise<Uint8Array>;
 
+    public abstract getDltStats(): Promise<Uint8Array>;
+
     public abstract grab(start: number, len: number): Promise<Uint8Array>;
 
     public abstract grabIndexed(start: number, len: number): Promise<Uint8Array>;
@@ -249,6 +313,10 @@ export abstract class RustSessionNative {
         above: boolean,
     ): Promise<void>;
 
+    public abstract expandFold(row: number): Promise<void>;
+
+    public abstract getFolds(from: number, to: number): Promise<Uint8Array>;
+
     public abstract removeBookmark(row: number): Promise<void>;
 
     public abstract addBookmark(row: number): Promise<void>;
@@ -259,6 +327,13 @@ export abstract class RustSessionNative {
 
     public abstract grabSearch(start: number, len: number): Promise<Uint8Array>;
 
+    public abstract grabSearchContext(
+        start: number,
+        len: number,
+        before: number,
+        after: number,
+    ): Promise<Uint8Array>;
+
     public abstract getSearchLen(): Promise<number>;
 
     public abstract export(
@@ -270,6 +345,16 @@ export abstract class RustSessionNative {
         operationUuid: string,
     ): Promise<void>;
 
+    public abstract exportSearchContext(
+        dest: string,
+        before: number,
+        after: number,
+        columns: number[],
+        spliter: string,
+        delimiter: string,
+        operationUuid: string,
+    ): Promise<void>;
+
     public abstract exportRaw(
         dest: string,
         ranges: number[][],
@@ -335,12 +420,32 @@ export abstract class RustSessionNative {
         positionInStream: number,
     ): Promise<number[] | null>;
 
+    public abstract setTimestampSettings(settings: Uint8Array): Promise<void>;
+
+    public abstract setTriggers(rules: Uint8Array): Promise<void>;
+
+    public abstract getNearestToTimestamp(operationUuid: string, timestamp: number): Promise<void>;
+
+    public abstract getTimeRange(operationUuid: string, from: number, to: number): Promise<void>;
+
+    public abstract getTimeDistribution(operationUuid: string, bucket: number): Promise<void>;
+
+    public abstract mineTemplates(operationUuid: string): Promise<void>;
+
+    public abstract checkSequences(rules: Uint8Array, operationUuid: string): Promise<void>;
+
+    public abstract hideTemplates(ids: number[]): Promise<void>;
+
+    public abstract showTemplates(): Promise<void>;
+
     public abstract sendIntoSde(
         targetOperationUuid: string,
         request: Uint8Array,
     ): Promise<Uint8Array>;
     public abstract getAttachments(): Promise<Uint8Array>;
     public abstract getIndexedRanges(): Promise<Uint8Array>;
+    public abstract getValuesSeries(): Promise<Uint8Array>;
+    public abstract getValuesStatistics(from?: number, to?: number): Promise<Uint8Array>;
 
     public abstract abort(
         selfOperationUuid: string,
@@ -491,6 +596,34 @@ export class RustSessionWrapper extends RustSession {
         });
     }
 
+    public getDltStats(): Promise<DltStatisticInfo> {
+        return new Promise((resolve, reject) => {
+            this._provider.debug().emit.operation('getDltStats');
+            this._native
+                .getDltStats()
+                .then((buf: Uint8Array) => {
+                    try {
+                        resolve(protocol.decodeDltStatisticInfo(buf));
+                    } catch (err) {
+                        reject(
+                            new NativeError(
+                                new Error(
+                                    this._logger.error(
+                                        `Fail to decode message: ${utils.error(err)}`,
+                                    ),
+                                ),
+                                Type.InvalidOutput,
+                                Source.GetDltStats,
+                            ),
+                        );
+                    }
+                })
+                .catch((err) => {
+                    reject(NativeError.from(err));
+                });
+        });
+    }
+
     public grabStreamChunk(start: number, len: number): Promise<GrabbedElement[]> {
         return new Promise((resolve, reject) => {
             this._provider.debug().emit.operation('grab');
@@ -602,6 +735,46 @@ export class RustSessionWrapper extends RustSession {
         });
     }
 
+    public expandFold(row: number): Promise<void> {
+        return new Promise((resolve, reject) => {
+            this._provider.debug().emit.operation('expandFold');
+            this._native
+                .expandFold(row)
+                .then(resolve)
+                .catch((err) => {
+                    reject(NativeError.from(err));
+                });
+        });
+    }
+
+    public getFolds(from: number, to: number): Promise<Fold[]> {
+        return new Promise((resolve, reject) => {
+            this._provider.debug().emit.operation('getFolds');
+            this._native
+                .getFolds(from, to)
+                .then((buf: Uint8Array) => {
+                    try {
+                        resolve(protocol.decodeFoldList(buf));
+                    } catch (err) {
+                        reject(
+                            new NativeError(
+                                new Error(
+                                    this._logger.error(
+                                        `Fail to decode message: ${utils.error(err)}`,
+                                    ),
+                                ),
+                                Type.InvalidOutput,
+                                Source.GetFolds,
+                            ),
+                        );
+                    }
+                })
+                .catch((err) => {
+                    reject(NativeError.from(err));
+                });
+        });
+    }
+
     public removeBookmark(row: number): Promise<void> {
         return new Promise((resolve, reject) => {
             this._provider.debug().emit.operation('removeBookmark');
@@ -700,6 +873,39 @@ export class RustSessionWrapper extends RustSession {
         });
     }
 
+    public grabSearchContext(
+        start: number,
+        len: number,
+        before: number,
+        after: number,
+    ): Promise<GrabbedElement[]> {
+        return new Promise((resolve, reject) => {
+            this._provider.debug().emit.operation('grabSearchContext');
+            this._native
+                .grabSearchContext(start, len, before, after)
+                .then((buf: Uint8Array) => {
+                    try {
+                        resolve(protocol.decodeGrabbedElementList(buf));
+                    } catch (err) {
+                        reject(
+                            new NativeError(
+                                new Error(
+                                    this._logger.error(
+                                        `Fail to decode message: ${utils.error(err)}`,
+                                    ),
+                                ),
+                                Type.InvalidOutput,
+                                Source.GrabSearchChunk,
+                            ),
+                        );
+                    }
+                })
+                .catch((err) => {
+                    reject(NativeError.from(err));
+                });
+        });
+    }
+
     public grabMatchesChunk(start: number, len: number): string[] | NativeError {
         return new NativeError(new Error('Not implemented yet'), Type.Other, Source.GetSocketPath);
     }
@@ -785,6 +991,36 @@ export class RustSessionWrapper extends RustSession {
         });
     }
 
+    public exportSearchContext(
+        dest: string,
+        before: number,
+        after: number,
+        opt: TextExportOptions,
+        operationUuid: string,
+    ): Promise<void> {
+        return new Promise((resolve, reject) => {
+            try {
+                this._provider.debug().emit.operation('exportSearchContext', operationUuid);
+                this._native
+                    .exportSearchContext(
+                        dest,
+                        before,
+                        after,
+                        opt.columns,
+                        opt.spliter === undefined ? '' : opt.spliter,
+                        opt.delimiter === undefined ? '' : opt.delimiter,
+                        operationUuid,
+                    )
+                    .then(resolve)
+                    .catch((err: Error) => {
+                        reject(NativeError.from(err));
+                    });
+            } catch (err) {
+                return reject(new NativeError(NativeError.from(err), Type.Other, Source.Assign));
+            }
+        });
+    }
+
     public exportRaw(dest: string, ranges: IRange[], operationUuid: string): Promise<void> {
         return new Promise((resolve, reject) => {
             try {
@@ -998,6 +1234,112 @@ export class RustSessionWrapper extends RustSession {
         });
     }
 
+    public setTimestampSettings(settings: TimestampSettings): Promise<void> {
+        return new Promise((resolve, reject) => {
+            this._native
+                .setTimestampSettings(protocol.encodeTimestampSettings(settings))
+                .then(resolve)
+                .catch((err) => {
+                    reject(NativeError.from(err));
+                });
+        });
+    }
+
+    public setTriggers(rules: TriggerRule[]): Promise<void> {
+        return new Promise((resolve, reject) => {
+            this._native
+                .setTriggers(protocol.encodeTriggerRules(rules))
+                .then(resolve)
+                .catch((err) => {
+                    reject(NativeError.from(err));
+                });
+        });
+    }
+
+    public getNearestToTimestamp(operationUuid: string, timestamp: number): Promise<void> {
+        return new Promise((resolve, reject) => {
+            this._provider.debug().emit.operation('getNearestToTimestamp', operationUuid);
+            this._native
+                .getNearestToTimestamp(operationUuid, timestamp)
+                .then(resolve)
+                .catch((err) => {
+                    reject(NativeError.from(err));
+                });
+        });
+    }
+
+    public getTimeRange(operationUuid: string, from: number, to: number): Promise<void> {
+        return new Promise((resolve, reject) => {
+            this._provider.debug().emit.operation('getTimeRange', operationUuid);
+            this._native
+                .getTimeRange(operationUuid, from, to)
+                .then(resolve)
+                .catch((err) => {
+                    reject(NativeError.from(err));
+                });
+        });
+    }
+
+    public mineTemplates(operationUuid: string): Promise<void> {
+        return new Promise((resolve, reject) => {
+            this._provider.debug().emit.operation('mineTemplates', operationUuid);
+            this._native
+                .mineTemplates(operationUuid)
+                .then(resolve)
+                .catch((err) => {
+                    reject(NativeError.from(err));
+                });
+        });
+    }
+
+    public checkSequences(rules: SequenceRule[], operationUuid: string): Promise<void> {
+        return new Promise((resolve, reject) => {
+            this._provider.debug().emit.operation('checkSequences', operationUuid);
+            this._native
+                .checkSequences(protocol.encodeSequenceRules(rules), operationUuid)
+                .then(resolve)
+                .catch((err) => {
+                    reject(NativeError.from(err));
+                });
+        });
+    }
+
+    public hideTemplates(ids: number[]): Promise<void> {
+        return new Promise((resolve, reject) => {
+            this._provider.debug().emit.operation('hideTemplates');
+            this._native
+                .hideTemplates(ids)
+                .then(resolve)
+                .catch((err) => {
+                    reject(NativeError.from(err));
+                });
+        });
+    }
+
+    public showTemplates(): Promise<void> {
+        return new Promise((resolve, reject) => {
+            this._provider.debug().emit.operation('showTemplates');
+            this._native
+                .showTemplates()
+                .then(resolve)
+                .catch((err) => {
+                    reject(NativeError.from(err));
+                });
+        });
+    }
+
+    public getTimeDistribution(operationUuid: string, bucket: number): Promise<void> {
+        return new Promise((resolve, reject) => {
+            this._provider.debug().emit.operation('getTimeDistribution', operationUuid);
+            this._native
+                .getTimeDistribution(operationUuid, bucket)
+                .then(resolve)
+                .catch((err) => {
+                    reject(NativeError.from(err));
+                });
+        });
+    }
+
     public sendIntoSde(
         targetOperationUuid: string,
         request: types.sde.SdeRequest,
@@ -1082,6 +1424,65 @@ export class RustSessionWrapper extends RustSession {
         });
     }
 
+    public getValuesSeries(): Promise<ValueSeries[]> {
+        return new Promise((resolve, reject) => {
+            this._native
+                .getValuesSeries()
+                .then((buf: Uint8Array) => {
+                    try {
+                        resolve(protocol.decodeValueSeriesList(buf));
+                    } catch (err) {
+                        reject(
+                            new NativeError(
+                                new Error(
+                                    this._logger.error(
+                                        `Fail to decode message: ${utils.error(err)}`,
+                                    ),
+                                ),
+                                Type.InvalidOutput,
+                                Source.GetValuesSeries,
+                            ),
+                        );
+                    }
+                })
+                .catch((err) => {
+                    reject(NativeError.from(err));
+                });
+        });
+    }
+
+    public getValuesStatistics(from?: number, to?: number): Promise<ValueStatistics[]> {
+        return new Promise((resolve, reject) => {
+            (() => {
+                if (from === undefined || to === undefined) {
+                    return this._native.getValuesStatistics();
+                } else {
+                    return this._native.getValuesStatistics(from, to);
+                }
+            })()
+                .then((buf: Uint8Array) => {
+                    try {
+                        resolve(protocol.decodeValueStatisticsList(buf));
+                    } catch (err) {
+                        reject(
+                            new NativeError(
+                                new Error(
+                                    this._logger.error(
+                                        `Fail to decode message: ${utils.error(err)}`,
+                                    ),
+                                ),
+                                Type.InvalidOutput,
+                                Source.GetValuesStatistics,
+                            ),
+                        );
+                    }
+                })
+                .catch((err) => {
+                    reject(NativeError.from(err));
+                });
+        });
+    }
+
     public abort(selfOperationUuid: string, targetOperationUuid: string): NativeError | undefined {
         try {
             this._provider.debug().emit.operation('abort', selfOperationUuid);
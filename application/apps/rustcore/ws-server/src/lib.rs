@@ -0,0 +1,4 @@
+//! Headless Chipmunk sessions served over a local WebSocket API. See `README.md` for the
+//! protocol.
+
+pub mod server;
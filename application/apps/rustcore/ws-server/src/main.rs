@@ -0,0 +1,50 @@
+#![warn(unused_extern_crates)]
+
+use clap::Parser;
+use std::net::{IpAddr, Ipv4Addr, SocketAddr};
+use tokio::signal;
+use tokio_util::sync::CancellationToken;
+use ws_server::server;
+
+/// Runs Chipmunk sessions without UI and gives access to them over a WebSocket API.
+#[derive(Parser, Debug)]
+#[command(author, version, about)]
+struct Cli {
+    /// Address to listen on. Keep the default loopback address to make the API available
+    /// for local clients only.
+    #[arg(long, default_value_t = IpAddr::V4(Ipv4Addr::LOCALHOST))]
+    host: IpAddr,
+    /// Port to listen on.
+    #[arg(short, long, default_value_t = 7878)]
+    port: u16,
+}
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    env_logger::init();
+    let cli = Cli::parse();
+    let cancel = CancellationToken::new();
+    tokio::spawn(cancel_listener(cancel.clone()));
+    server::run(SocketAddr::new(cli.host, cli.port), cancel).await
+}
+
+/// Sends cancel signal when receiving Ctrl_c once, then forcing shutting down the server
+/// in case of receiving another cancel signal while gracefully shutting down.
+async fn cancel_listener(cancel: CancellationToken) {
+    loop {
+        match signal::ctrl_c().await {
+            Ok(()) if !cancel.is_cancelled() => {
+                println!("Shutting down...");
+                cancel.cancel();
+            }
+            Ok(()) => {
+                eprintln!("Forcing the server to exit...");
+                std::process::exit(1)
+            }
+            Err(err) => {
+                eprintln!("Unable to listen for shutdown signal: {err}");
+                std::process::exit(1)
+            }
+        }
+    }
+}
@@ -0,0 +1,200 @@
+use super::{handlers, sessions::Sessions};
+use futures::{SinkExt, StreamExt, stream::SplitStream};
+use log::{debug, error, warn};
+use session::unbound::api::UnboundSessionAPI;
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex, MutexGuard},
+};
+use stypes::{ServerCommand, ServerMessage, ServerRequest, ServerResponse, ServerResult};
+use tokio::{
+    net::TcpStream,
+    select,
+    sync::{
+        broadcast::error::RecvError,
+        mpsc::{UnboundedSender, unbounded_channel},
+    },
+};
+use tokio_tungstenite::{WebSocketStream, tungstenite::Message};
+use tokio_util::sync::CancellationToken;
+use uuid::Uuid;
+
+/// Connection of a client. Each request is handled on its own task, so long running
+/// commands don't delay other requests; responses are matched with requests by their ID.
+#[derive(Clone)]
+pub struct Connection {
+    index: u32,
+    sessions: Sessions,
+    unbound: UnboundSessionAPI,
+    /// Sessions the client is subscribed to with the tokens to stop forwarding their events.
+    subscriptions: Arc<Mutex<HashMap<Uuid, CancellationToken>>>,
+}
+
+impl Connection {
+    pub fn new(index: u32, sessions: Sessions, unbound: UnboundSessionAPI) -> Self {
+        Self {
+            index,
+            sessions,
+            unbound,
+            subscriptions: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+
+    pub async fn run(self, stream: TcpStream) -> anyhow::Result<()> {
+        let (mut sink, mut incoming) = tokio_tungstenite::accept_async(stream).await?.split();
+        let (tx_messages, mut rx_messages) = unbounded_channel::<ServerMessage>();
+        let writer = tokio::spawn(async move {
+            while let Some(msg) = rx_messages.recv().await {
+                let bytes = match msg.encode() {
+                    Ok(bytes) => bytes,
+                    Err(err) => {
+                        error!("Fail to encode message: {err}");
+                        continue;
+                    }
+                };
+                if let Err(err) = sink.send(Message::binary(bytes)).await {
+                    warn!("Fail to send message: {err}");
+                    return;
+                }
+            }
+            if let Err(err) = sink.close().await {
+                debug!("Fail to close connection: {err}");
+            }
+        });
+        let result = self.read(&mut incoming, &tx_messages).await;
+        self.unsubscribe_all();
+        // The writer stops once the responses to pending requests are sent and the
+        // forwarding of events is stopped, because both hold the last senders.
+        drop(tx_messages);
+        if let Err(err) = writer.await {
+            error!("Fail to finish writing messages: {err}");
+        }
+        result
+    }
+
+    async fn read(
+        &self,
+        incoming: &mut SplitStream<WebSocketStream<TcpStream>>,
+        tx_messages: &UnboundedSender<ServerMessage>,
+    ) -> anyhow::Result<()> {
+        while let Some(msg) = incoming.next().await {
+            let bytes = match msg? {
+                Message::Binary(bytes) => bytes,
+                Message::Close(_) => break,
+                Message::Text(_) => {
+                    warn!("Text messages aren't supported; requests should be encoded");
+                    continue;
+                }
+                _ => continue,
+            };
+            let request = match ServerRequest::decode(&bytes) {
+                Ok(request) => request,
+                Err(err) => {
+                    warn!("Fail to decode request: {err}");
+                    continue;
+                }
+            };
+            let connection = self.clone();
+            let tx_messages = tx_messages.clone();
+            tokio::spawn(async move {
+                let result = connection
+                    .handle(request.id, request.command, &tx_messages)
+                    .await
+                    .unwrap_or_else(ServerResult::Error);
+                let _ = tx_messages.send(ServerMessage::Response(ServerResponse {
+                    id: request.id,
+                    result,
+                }));
+            });
+        }
+        Ok(())
+    }
+
+    async fn handle(
+        &self,
+        id: u64,
+        command: ServerCommand,
+        tx_messages: &UnboundedSender<ServerMessage>,
+    ) -> Result<ServerResult, stypes::ComputationError> {
+        match command {
+            ServerCommand::CreateSession => {
+                Ok(ServerResult::Session(self.sessions.create().await?))
+            }
+            ServerCommand::DestroySession(uuid) => {
+                self.unsubscribe(&uuid);
+                self.sessions.destroy(&uuid).await?;
+                Ok(ServerResult::Done)
+            }
+            ServerCommand::Session(uuid, stypes::SessionRequest::Subscribe) => {
+                self.subscribe(uuid, tx_messages.clone()).await?;
+                Ok(ServerResult::Done)
+            }
+            ServerCommand::Session(uuid, stypes::SessionRequest::Unsubscribe) => {
+                self.unsubscribe(&uuid);
+                Ok(ServerResult::Done)
+            }
+            ServerCommand::Session(uuid, request) => {
+                handlers::session(&*self.sessions.get(&uuid).await?, request).await
+            }
+            ServerCommand::Unbound(request) => {
+                handlers::unbound(&self.unbound, self.index, id, request).await
+            }
+        }
+    }
+
+    /// Starts forwarding events of the session to the client.
+    async fn subscribe(
+        &self,
+        uuid: Uuid,
+        tx_messages: UnboundedSender<ServerMessage>,
+    ) -> Result<(), stypes::ComputationError> {
+        let mut events = self.sessions.subscribe(&uuid).await?;
+        let token = CancellationToken::new();
+        if let Some(previous) = self.subscriptions()?.insert(uuid, token.clone()) {
+            previous.cancel();
+        }
+        tokio::spawn(async move {
+            loop {
+                select! {
+                    _ = token.cancelled() => break,
+                    event = events.recv() => match event {
+                        Ok(event) => {
+                            if tx_messages.send(ServerMessage::Event(uuid, event)).is_err() {
+                                break;
+                            }
+                        }
+                        Err(RecvError::Lagged(skipped)) => {
+                            warn!("Subscriber of session {uuid} missed {skipped} events");
+                        }
+                        Err(RecvError::Closed) => break,
+                    }
+                }
+            }
+        });
+        Ok(())
+    }
+
+    fn unsubscribe(&self, uuid: &Uuid) {
+        if let Some(token) = self
+            .subscriptions()
+            .ok()
+            .and_then(|mut subscriptions| subscriptions.remove(uuid))
+        {
+            token.cancel();
+        }
+    }
+
+    fn unsubscribe_all(&self) {
+        if let Ok(mut subscriptions) = self.subscriptions() {
+            subscriptions.drain().for_each(|(_, token)| token.cancel());
+        }
+    }
+
+    fn subscriptions(
+        &self,
+    ) -> Result<MutexGuard<'_, HashMap<Uuid, CancellationToken>>, stypes::ComputationError> {
+        self.subscriptions
+            .lock()
+            .map_err(|e| stypes::ComputationError::IoOperation(e.to_string()))
+    }
+}
@@ -0,0 +1,217 @@
+use processor::{grabber::LineRange, search::filter::SearchFilter};
+use serde::Serialize;
+use session::{session::Session, unbound::api::UnboundSessionAPI};
+use std::ops::RangeInclusive;
+use stypes::{SearchCondition, ServerResult, SessionRequest, UnboundRequest};
+use uuid::Uuid;
+
+/// Mask of the request ID within the ID of an unbound job. The upper bits are the index of
+/// the connection, so requests of different clients never share the ID of a job.
+const JOB_REQUEST_MASK: u64 = 0xffff_ffff;
+
+pub async fn session(
+    session: &Session,
+    request: SessionRequest,
+) -> Result<ServerResult, stypes::ComputationError> {
+    let operation_id = Uuid::new_v4();
+    match request {
+        SessionRequest::Observe(options) => {
+            session.observe(operation_id, options)?;
+            Ok(ServerResult::Operation(operation_id))
+        }
+        SessionRequest::Search(conditions) => {
+            session.apply_search_filters(
+                operation_id,
+                conditions.into_iter().map(into_filter).collect(),
+            )?;
+            Ok(ServerResult::Operation(operation_id))
+        }
+        SessionRequest::DropSearch => {
+            session.drop_search().await?;
+            Ok(ServerResult::Done)
+        }
+        SessionRequest::GetStreamLen => {
+            Ok(ServerResult::Number(session.get_stream_len().await? as u64))
+        }
+        SessionRequest::GetSearchResultLen => Ok(ServerResult::Number(
+            session.get_search_result_len().await? as u64,
+        )),
+        SessionRequest::Grab(range) => encoded(
+            &session
+                .grab(LineRange::from(range.start..=range.end))
+                .await?,
+        ),
+        SessionRequest::GrabSearch(range) => encoded(
+            &session
+                .grab_search(LineRange::from(range.start..=range.end))
+                .await?,
+        ),
+        SessionRequest::GrabRanges(ranges) => {
+            encoded(&session.grab_ranges(into_ranges(ranges.0)).await?)
+        }
+        SessionRequest::GrabSearchContext {
+            range,
+            before,
+            after,
+        } => encoded(
+            &session
+                .grab_search_context(
+                    LineRange::from(range.start..=range.end),
+                    before as u64,
+                    after as u64,
+                )
+                .await?,
+        ),
+        SessionRequest::Export {
+            out_path,
+            ranges,
+            columns,
+            spliter,
+            delimiter,
+        } => {
+            session.export(
+                operation_id,
+                out_path,
+                into_ranges(ranges),
+                columns.into_iter().map(|column| column as usize).collect(),
+                spliter,
+                delimiter,
+            )?;
+            Ok(ServerResult::Operation(operation_id))
+        }
+        SessionRequest::ExportRaw { out_path, ranges } => {
+            session.export_raw(operation_id, out_path, into_ranges(ranges))?;
+            Ok(ServerResult::Operation(operation_id))
+        }
+        SessionRequest::ExportSearchContext {
+            out_path,
+            before,
+            after,
+            columns,
+            spliter,
+            delimiter,
+        } => {
+            session.export_search_context(
+                operation_id,
+                out_path,
+                before as u64,
+                after as u64,
+                columns.into_iter().map(|column| column as usize).collect(),
+                spliter,
+                delimiter,
+            )?;
+            Ok(ServerResult::Operation(operation_id))
+        }
+        SessionRequest::AddBookmark(row) => {
+            session.add_bookmark(row).await?;
+            Ok(ServerResult::Done)
+        }
+        SessionRequest::RemoveBookmark(row) => {
+            session.remove_bookmark(row).await?;
+            Ok(ServerResult::Done)
+        }
+        SessionRequest::SetBookmarks(rows) => {
+            session.set_bookmarks(rows).await?;
+            Ok(ServerResult::Done)
+        }
+        SessionRequest::Abort(target) => {
+            session.abort(operation_id, target)?;
+            Ok(ServerResult::Done)
+        }
+        SessionRequest::Subscribe | SessionRequest::Unsubscribe => {
+            Err(stypes::ComputationError::OperationNotSupported(
+                String::from("Subscriptions are managed by the connection"),
+            ))
+        }
+    }
+}
+
+/// Runs a command of the unbound session. `request_id` is the ID of the client's request,
+/// which becomes the ID of the job within the connection.
+pub async fn unbound(
+    api: &UnboundSessionAPI,
+    connection: u32,
+    request_id: u64,
+    request: UnboundRequest,
+) -> Result<ServerResult, stypes::ComputationError> {
+    let id = job_id(connection, request_id);
+    match request {
+        UnboundRequest::ListFolderContent {
+            paths,
+            depth,
+            max_len,
+            include_files,
+            include_folders,
+        } => encoded(
+            &api.list_folder_content(
+                id,
+                depth as usize,
+                max_len as usize,
+                paths,
+                include_files,
+                include_folders,
+            )
+            .await?,
+        ),
+        UnboundRequest::IsFileBinary(path) => encoded(&api.is_file_binary(id, path).await?),
+        UnboundRequest::DetectFileFormat(path) => encoded(&api.detect_file_format(id, path).await?),
+        UnboundRequest::GetFilesChronology(files) => {
+            encoded(&api.get_files_chronology(id, files).await?)
+        }
+        UnboundRequest::CompareFiles(options) => encoded(&api.compare_files(id, options).await?),
+        UnboundRequest::Grep(options) => encoded(&api.grep(id, options).await?),
+        UnboundRequest::GetFileChecksum(path) => encoded(&api.get_file_checksum(id, path).await?),
+        UnboundRequest::GetDltStats(files) => encoded(&api.get_dlt_stats(id, files).await?),
+        UnboundRequest::GetSomeipStatistic(files) => {
+            encoded(&api.get_someip_statistic(id, files).await?)
+        }
+        UnboundRequest::GetSerialPortsList => encoded(&api.get_serial_ports_list(id).await?),
+        UnboundRequest::GetRegexError(condition) => {
+            encoded(&api.get_regex_error(id, into_filter(condition)).await?)
+        }
+        UnboundRequest::Cancel(target) => {
+            api.cancel_job(&job_id(connection, target)).await?;
+            Ok(ServerResult::Done)
+        }
+    }
+}
+
+fn job_id(connection: u32, request_id: u64) -> u64 {
+    (u64::from(connection) << 32) | (request_id & JOB_REQUEST_MASK)
+}
+
+fn into_filter(condition: SearchCondition) -> SearchFilter {
+    SearchFilter::new(
+        condition.value,
+        condition.is_regex,
+        condition.ignore_case,
+        condition.is_word,
+    )
+}
+
+fn into_ranges(ranges: Vec<stypes::Range>) -> Vec<RangeInclusive<u64>> {
+    ranges
+        .into_iter()
+        .map(|range| range.start..=range.end)
+        .collect()
+}
+
+fn encoded<T: Serialize>(value: &T) -> Result<ServerResult, stypes::ComputationError> {
+    stypes::serialize(value)
+        .map(ServerResult::Encoded)
+        .map_err(|e| stypes::ComputationError::Encoding(e.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn job_ids_of_connections() {
+        assert_eq!(job_id(0, 5), 5);
+        assert_eq!(job_id(1, 5), (1 << 32) | 5);
+        assert_ne!(job_id(1, 5), job_id(2, 5));
+        // Only the lower bits of the request ID are used.
+        assert_eq!(job_id(1, (7 << 32) | 5), job_id(1, 5));
+    }
+}
@@ -0,0 +1,66 @@
+mod connection;
+mod handlers;
+mod sessions;
+
+use anyhow::Context;
+use connection::Connection;
+use log::{debug, info, warn};
+use session::unbound::UnboundSession;
+use sessions::Sessions;
+use std::net::SocketAddr;
+use tokio::{net::TcpListener, select};
+use tokio_util::sync::CancellationToken;
+
+/// Listens on the given address and serves clients until the server is cancelled.
+pub async fn run(addr: SocketAddr, cancel: CancellationToken) -> anyhow::Result<()> {
+    let listener = TcpListener::bind(addr)
+        .await
+        .with_context(|| format!("Fail to listen on {addr}"))?;
+    serve(listener, cancel).await
+}
+
+/// Accepts connections of clients until the server is cancelled. All connections share
+/// the same sessions and the same unbound session, so a session created by one client
+/// can be observed by others.
+pub async fn serve(listener: TcpListener, cancel: CancellationToken) -> anyhow::Result<()> {
+    let addr = listener.local_addr()?;
+    let (mut unbound, unbound_api) = UnboundSession::new();
+    unbound
+        .init()
+        .await
+        .context("Fail to init unbound session")?;
+    let sessions = Sessions::default();
+    info!("Listening on ws://{addr}");
+    let mut index: u32 = 0;
+    loop {
+        select! {
+            _ = cancel.cancelled() => break,
+            accepted = listener.accept() => {
+                let (stream, peer) = match accepted {
+                    Ok(accepted) => accepted,
+                    Err(err) => {
+                        warn!("Fail to accept connection: {err}");
+                        continue;
+                    }
+                };
+                index = index.wrapping_add(1);
+                let connection = Connection::new(index, sessions.clone(), unbound_api.clone());
+                tokio::spawn(async move {
+                    debug!("Client {peer} is connected");
+                    if let Err(err) = connection.run(stream).await {
+                        warn!("Connection with {peer} is closed with error: {err}");
+                    } else {
+                        debug!("Client {peer} is disconnected");
+                    }
+                });
+            }
+        }
+    }
+    sessions.destroy_all().await;
+    unbound_api
+        .shutdown()
+        .await
+        .context("Fail to shutdown unbound session")?;
+    unbound.finished.cancelled().await;
+    Ok(())
+}
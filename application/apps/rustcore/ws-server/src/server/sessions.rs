@@ -0,0 +1,85 @@
+use log::{debug, warn};
+use session::session::Session;
+use std::{collections::HashMap, sync::Arc};
+use tokio::sync::{RwLock, broadcast};
+use uuid::Uuid;
+
+/// Count of events kept for subscribers which don't read them fast enough.
+const EVENTS_CAPACITY: usize = 1024;
+
+struct Entry {
+    session: Arc<Session>,
+    events: broadcast::Sender<stypes::CallbackEvent>,
+}
+
+/// Sessions created by clients of the server.
+#[derive(Clone, Default)]
+pub struct Sessions {
+    entries: Arc<RwLock<HashMap<Uuid, Entry>>>,
+}
+
+impl Sessions {
+    /// Creates a new session and starts delivering its events to subscribers.
+    pub async fn create(&self) -> Result<Uuid, stypes::ComputationError> {
+        let uuid = Uuid::new_v4();
+        let (session, mut rx_callback_events) = Session::new(uuid).await?;
+        let (events, _) = broadcast::channel(EVENTS_CAPACITY);
+        let tx_events = events.clone();
+        tokio::spawn(async move {
+            while let Some(event) = rx_callback_events.recv().await {
+                // Sending fails if nobody is subscribed, which is fine.
+                let _ = tx_events.send(event);
+            }
+            let _ = tx_events.send(stypes::CallbackEvent::SessionDestroyed);
+            debug!("Events of session {uuid} are finished");
+        });
+        self.entries.write().await.insert(
+            uuid,
+            Entry {
+                session: Arc::new(session),
+                events,
+            },
+        );
+        Ok(uuid)
+    }
+
+    pub async fn get(&self, uuid: &Uuid) -> Result<Arc<Session>, stypes::ComputationError> {
+        self.entries
+            .read()
+            .await
+            .get(uuid)
+            .map(|entry| entry.session.clone())
+            .ok_or(stypes::ComputationError::SessionUnavailable)
+    }
+
+    pub async fn subscribe(
+        &self,
+        uuid: &Uuid,
+    ) -> Result<broadcast::Receiver<stypes::CallbackEvent>, stypes::ComputationError> {
+        self.entries
+            .read()
+            .await
+            .get(uuid)
+            .map(|entry| entry.events.subscribe())
+            .ok_or(stypes::ComputationError::SessionUnavailable)
+    }
+
+    pub async fn destroy(&self, uuid: &Uuid) -> Result<(), stypes::ComputationError> {
+        let entry = self
+            .entries
+            .write()
+            .await
+            .remove(uuid)
+            .ok_or(stypes::ComputationError::SessionUnavailable)?;
+        entry.session.stop(Uuid::new_v4()).await
+    }
+
+    pub async fn destroy_all(&self) {
+        let entries: Vec<(Uuid, Entry)> = self.entries.write().await.drain().collect();
+        for (uuid, entry) in entries {
+            if let Err(err) = entry.session.stop(Uuid::new_v4()).await {
+                warn!("Fail to destroy session {uuid}: {err}");
+            }
+        }
+    }
+}
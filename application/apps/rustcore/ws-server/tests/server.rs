@@ -0,0 +1,183 @@
+//! Tests driving the server over a WebSocket connection like a client does.
+
+use futures::{SinkExt, StreamExt};
+use std::time::Duration;
+use stypes::{
+    CallbackEvent, ServerCommand, ServerMessage, ServerRequest, ServerResult, SessionRequest,
+};
+use tokio::{net::TcpListener, net::TcpStream, time::timeout};
+use tokio_tungstenite::{MaybeTlsStream, WebSocketStream, tungstenite::Message};
+use tokio_util::sync::CancellationToken;
+use uuid::Uuid;
+use ws_server::server;
+
+const SAMPLE_TXT: &str = "../../../developing/resources/sample_utf_8.txt";
+
+/// Maximum time to wait for a message of the server.
+const MESSAGE_TIMEOUT: Duration = Duration::from_secs(30);
+
+struct Client {
+    socket: WebSocketStream<MaybeTlsStream<TcpStream>>,
+    /// Events received while waiting for a response.
+    events: Vec<(Uuid, CallbackEvent)>,
+    id: u64,
+}
+
+impl Client {
+    async fn connect(addr: &str) -> Self {
+        let (socket, _) = tokio_tungstenite::connect_async(format!("ws://{addr}"))
+            .await
+            .expect("Client should connect");
+        Self {
+            socket,
+            events: Vec::new(),
+            id: 0,
+        }
+    }
+
+    async fn recv(&mut self) -> ServerMessage {
+        loop {
+            let msg = timeout(MESSAGE_TIMEOUT, self.socket.next())
+                .await
+                .expect("Server should send a message in time")
+                .expect("Connection should be open")
+                .expect("Message should be received");
+            if let Message::Binary(bytes) = msg {
+                return ServerMessage::decode(&bytes).expect("Message should be decoded");
+            }
+        }
+    }
+
+    /// Sends the command and returns the result of its response. Events received in the
+    /// meantime are kept.
+    async fn request(&mut self, command: ServerCommand) -> ServerResult {
+        self.id += 1;
+        let request = ServerRequest {
+            id: self.id,
+            command,
+        };
+        self.socket
+            .send(Message::binary(
+                request.encode().expect("Request should be encoded"),
+            ))
+            .await
+            .expect("Request should be sent");
+        loop {
+            match self.recv().await {
+                ServerMessage::Response(response) if response.id == self.id => {
+                    return response.result;
+                }
+                ServerMessage::Response(response) => {
+                    panic!("Unexpected response to request {}", response.id)
+                }
+                ServerMessage::Event(session, event) => self.events.push((session, event)),
+            }
+        }
+    }
+
+    /// Returns the next event, starting with the events received while waiting for
+    /// responses.
+    async fn event(&mut self) -> (Uuid, CallbackEvent) {
+        if !self.events.is_empty() {
+            return self.events.remove(0);
+        }
+        match self.recv().await {
+            ServerMessage::Event(session, event) => (session, event),
+            ServerMessage::Response(response) => {
+                panic!("Unexpected response to request {}", response.id)
+            }
+        }
+    }
+}
+
+#[tokio::test]
+async fn observe_file() {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap().to_string();
+    let cancel = CancellationToken::new();
+    let running = tokio::spawn(server::serve(listener, cancel.clone()));
+    let mut client = Client::connect(&addr).await;
+
+    let ServerResult::Session(session) = client.request(ServerCommand::CreateSession).await else {
+        panic!("Session should be created");
+    };
+    assert!(matches!(
+        client
+            .request(ServerCommand::Session(session, SessionRequest::Subscribe))
+            .await,
+        ServerResult::Done
+    ));
+    let options = stypes::ObserveOptions {
+        origin: stypes::ObserveOrigin::File(
+            Uuid::new_v4().to_string(),
+            stypes::FileFormat::Text,
+            SAMPLE_TXT.into(),
+        ),
+        parser: stypes::ParserType::Text(()),
+        network_filter: None,
+        order_by_time: None,
+    };
+    let ServerResult::Operation(operation) = client
+        .request(ServerCommand::Session(
+            session,
+            SessionRequest::Observe(options),
+        ))
+        .await
+    else {
+        panic!("Observe should be started");
+    };
+
+    let mut started = false;
+    let mut updated = 0;
+    loop {
+        let (uuid, event) = client.event().await;
+        assert_eq!(uuid, session);
+        match event {
+            CallbackEvent::OperationStarted(uuid) if uuid == operation => started = true,
+            CallbackEvent::StreamUpdated(rows) => updated = rows,
+            CallbackEvent::FileRead => break,
+            CallbackEvent::OperationError { error, .. } => panic!("Observe failed: {error:?}"),
+            CallbackEvent::SessionError(error) => panic!("Session failed: {error:?}"),
+            _ => {}
+        }
+    }
+    assert!(started);
+    assert!(updated > 0);
+
+    let len = client
+        .request(ServerCommand::Session(
+            session,
+            SessionRequest::GetStreamLen,
+        ))
+        .await;
+    assert!(matches!(len, ServerResult::Number(rows) if rows == updated));
+    let ServerResult::Encoded(bytes) = client
+        .request(ServerCommand::Session(
+            session,
+            SessionRequest::Grab(stypes::Range { start: 0, end: 0 }),
+        ))
+        .await
+    else {
+        panic!("Rows should be grabbed");
+    };
+    let rows = stypes::GrabbedElementList::decode(&bytes).unwrap().0;
+    assert_eq!(rows.len(), 1);
+    assert!(rows[0].content.starts_with("Original by Markus Kuhn"));
+
+    assert!(matches!(
+        client.request(ServerCommand::DestroySession(session)).await,
+        ServerResult::Done
+    ));
+    assert!(matches!(
+        client
+            .request(ServerCommand::Session(
+                session,
+                SessionRequest::GetStreamLen
+            ))
+            .await,
+        ServerResult::Error(stypes::ComputationError::SessionUnavailable)
+    ));
+    client.socket.close(None).await.unwrap();
+    cancel.cancel();
+    running.await.unwrap().unwrap();
+}
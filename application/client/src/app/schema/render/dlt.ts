@@ -52,6 +52,14 @@ export class Implementation extends Render<Columns> {
             caption: 'PAYLOAD',
             desc: 'Payload',
         },
+        {
+            caption: 'LC',
+            desc: 'ECU lifecycle (if lifecycle detection is enabled)',
+        },
+        {
+            caption: 'ABSTIME',
+            desc: 'Absolute time computed from the lifecycle start and the timestamp (TMSP)',
+        },
     ];
 
     constructor() {
@@ -60,7 +68,7 @@ export class Implementation extends Render<Columns> {
             new Columns(
                 Implementation.HEADERS,
                 true,
-                [150, 20, 20, 20, 20, 20, 20, 20, 20, 20, -1],
+                [150, 20, 20, 20, 20, 20, 20, 20, 20, 20, -1, 20, 150],
                 MIN_COLUMN_WIDTH,
                 MAX_COLUMN_WIDTH,
             ),
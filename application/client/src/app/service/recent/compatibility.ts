@@ -35,8 +35,11 @@ export function optionsToParserSettings(
     return {
         filter_config,
         fibex_file_paths: options.fibex.length > 0 ? options.fibex : undefined,
+        arxml_file_paths: undefined,
+        dbc_file_paths: undefined,
         with_storage_header,
         tz: undefined,
+        detect_lifecycles: false,
     };
 }
 
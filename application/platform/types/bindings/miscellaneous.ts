@@ -1,4 +1,5 @@
 // This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { SearchCondition } from './server';
 
 /**
  * Data about indices (log entry numbers). Used to provide information about
@@ -26,6 +27,33 @@ export type FilterMatch = {
  */
 export type FilterMatchList = Array<FilterMatch>;
 
+/**
+ * Consecutive repeated log entries folded into one entry of the indexed map.
+ */
+export type Fold = {
+    /**
+     * The log entry shown instead of the folded ones.
+     */
+    row: number;
+    /**
+     * The count of folded log entries including the shown one.
+     */
+    count: number;
+    /**
+     * The timestamp of the first log entry if timestamps are indexed.
+     */
+    first: number | null;
+    /**
+     * The timestamp of the last log entry if timestamps are indexed.
+     */
+    last: number | null;
+};
+
+/**
+ * A list of folds.
+ */
+export type FoldList = Array<Fold>;
+
 /**
  * Information about a log entry.
  */
@@ -46,6 +74,8 @@ export type GrabbedElement = {
      * The nature of the log entry, represented as a bitmask. Possible values include:
      * - `SEARCH`: Nature = Nature(1)
      * - `BOOKMARK`: Nature = Nature(1 << 1)
+     * - `FOLDED`: Nature = Nature(1 << 2)
+     * - `UNFOLDED`: Nature = Nature(1 << 3)
      * - `EXPANDED`: Nature = Nature(1 << 5)
      * - `BREADCRUMB`: Nature = Nature(1 << 6)
      * - `BREADCRUMB_SEPARATOR`: Nature = Nature(1 << 7)
@@ -89,6 +119,45 @@ export type SdeResponse = {
     bytes: number;
 };
 
+/**
+ * Ordered steps which have to follow each other within their max gaps. Each log entry
+ * matching the first step starts a new instance of the rule.
+ */
+export type SequenceRule = {
+    /**
+     * The steps in the expected order.
+     */
+    steps: Array<SequenceStep>;
+    /**
+     * Conditions of log entries which must not occur between the first and the last step.
+     */
+    forbidden: Array<SearchCondition>;
+};
+
+/**
+ * A list of sequence rules. Rules are identified by their positions in the list.
+ */
+export type SequenceRules = Array<SequenceRule>;
+
+/**
+ * A step of a sequence rule.
+ */
+export type SequenceStep = {
+    /**
+     * The condition the log entry of the step matches.
+     */
+    condition: SearchCondition;
+    /**
+     * Max count of rows from the previous step. Not checked for the first step.
+     */
+    max_rows: number | null;
+    /**
+     * Max time in ms from the previous step. Not checked for the first step and for log
+     * entries without timestamps.
+     */
+    max_ms: number | null;
+};
+
 /**
  * Describes a data source.
  */
@@ -101,9 +170,84 @@ export type SourceDefinition = {
      * The user-friendly name of the source for display purposes.
      */
     alias: string;
+    /**
+     * The number of log entries of the source in the session.
+     */
+    count: number;
 };
 
 /**
  * A list of data sources.
  */
 export type Sources = Array<SourceDefinition>;
+
+/**
+ * Settings of extracting timestamps from the log entries of a text session.
+ */
+export type TimestampSettings = {
+    /**
+     * The format of timestamps, e.g. `%Y-%m-%d %H:%M:%S%.3f`. Common formats are
+     * detected if it isn't defined.
+     */
+    format: string | null;
+    /**
+     * The time zone of timestamps without offset, e.g. `Europe/Berlin`. UTC is used
+     * if it isn't defined.
+     */
+    tz: string | null;
+    /**
+     * The year of timestamps without year. If it isn't defined, the year the source file was
+     * modified is used (the current year for streams).
+     */
+    year: number | null;
+};
+
+/**
+ * Action executed when a trigger rule fires.
+ */
+export type TriggerAction =
+    | 'Notify'
+    | 'Bookmark'
+    | { StopAfter: number }
+    | { SendToSource: SdeRequest };
+
+/**
+ * Condition of a trigger rule, which is checked for each log entry written into the session.
+ */
+export type TriggerCondition =
+    | { Filter: SearchCondition }
+    | {
+          Threshold: {
+              /**
+               * The regex extracting the value.
+               */
+              filter: string;
+              /**
+               * The rule fires if the value is greater than this threshold.
+               */
+              above: number | null;
+              /**
+               * The rule fires if the value is less than this threshold.
+               */
+              below: number | null;
+          };
+      };
+
+/**
+ * A rule checked on each log entry while a source is observed.
+ */
+export type TriggerRule = {
+    /**
+     * The condition firing the rule.
+     */
+    condition: TriggerCondition;
+    /**
+     * The actions executed when the rule fires.
+     */
+    actions: Array<TriggerAction>;
+};
+
+/**
+ * A list of trigger rules. Rules are identified by their positions in the list.
+ */
+export type TriggerRules = Array<TriggerRule>;
@@ -1,8 +1,18 @@
 import { DltFilterConfig } from './dlt';
 import type { PluginParserSettings } from './plugins';
+import type { TimestampSettings } from './miscellaneous';
 
 // This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
 
+/**
+ * Settings for the CAN parser.
+ */
+export type CanParserSettings = { 
+/**
+ * Paths to DBC files used to decode the payload of frames into messages and signals.
+ */
+dbc_file_paths: Array<string> | null, };
+
 /**
  * Settings for the DLT parser.
  */
@@ -12,9 +22,20 @@ export type DltParserSettings = {
  */
 filter_config: DltFilterConfig, 
 /**
- * Paths to FIBEX files for additional interpretation of `payload` content.
+ * Paths to FIBEX files for additional interpretation of `payload` content. FlexRay
+ * network trace payloads are decoded with the FlexRay frames of these files.
  */
 fibex_file_paths: Array<string> | null, 
+/**
+ * Paths to AUTOSAR ARXML files describing non-verbose messages. Used for messages which
+ * aren't described by the FIBEX files.
+ */
+arxml_file_paths: Array<string> | null, 
+/**
+ * Paths to DBC files used to decode CAN network trace payloads into
+ * messages and signals.
+ */
+dbc_file_paths: Array<string> | null, 
 /**
  * Indicates whether the source contains a `StorageHeader`. Set to `true` if applicable.
  */
@@ -22,12 +43,43 @@ with_storage_header: boolean,
 /**
  * Timezone for timestamp adjustment. If specified, timestamps are converted to this timezone.
  */
-tz: string | null, };
+tz: string | null, 
+/**
+ * Enables the detection of ECU lifecycles. Adds the lifecycle and the absolute time,
+ * computed from the ECU uptime and the lifecycle start, as columns.
+ */
+detect_lifecycles: boolean, };
 
 /**
  * Supported file formats for observation.
  */
-export type FileFormat = 'PcapNG' | 'PcapLegacy' | 'Text' | 'Binary';
+export type FileFormat = 'PcapNG' | 'PcapLegacy' | 'Text' | 'Binary' | 'Blf';
+
+/**
+ * Order of the files in a watched folder, from the oldest file to the newest one.
+ */
+export type FolderSortOrder = "Name" | "Modified";
+
+/**
+ * Folder with rotating log files observed as a single source of data.
+ */
+export type FolderWatch = { 
+/**
+ * Path to the watched folder. Subfolders aren't included.
+ */
+path: string, 
+/**
+ * Glob pattern for the names of the files, e.g. `app*.log`. Supports `*` and `?`.
+ */
+pattern: string, 
+/**
+ * Order in which existing files are concatenated. The last file is the newest one.
+ */
+order: FolderSortOrder, 
+/**
+ * Format of the files.
+ */
+format: FileFormat, };
 
 /**
  * Multicast configuration information.
@@ -37,6 +89,37 @@ export type FileFormat = 'PcapNG' | 'PcapLegacy' | 'Text' | 'Binary';
  */
 export type MulticastInfo = { multiaddr: string, interface: string | null, };
 
+/**
+ * Filter of the packets of network traces (PCAP and PCAPNG files). Each defined criterion
+ * has to match, undefined criteria match any packet.
+ */
+export type NetworkFilter = { 
+/**
+ * Transport protocol of the packets.
+ */
+transport: NetworkTransport | null, 
+/**
+ * IP address of the sender, e.g. `192.168.0.1`.
+ */
+source_ip: string | null, 
+/**
+ * Port of the sender.
+ */
+source_port: number | null, 
+/**
+ * IP address of the receiver.
+ */
+destination_ip: string | null, 
+/**
+ * Port of the receiver.
+ */
+destination_port: number | null, };
+
+/**
+ * Transport protocol of the packets picked from network traces.
+ */
+export type NetworkTransport = "Tcp" | "Udp";
+
 /**
  * Options for observing data within a session.
  */
@@ -48,17 +131,27 @@ origin: ObserveOrigin,
 /**
  * The parser configuration to be applied.
  */
-parser: ParserType, };
+parser: ParserType, 
+/**
+ * Filter of the packets of network traces. Applied to PCAP and PCAPNG files only.
+ */
+network_filter: NetworkFilter | null, 
+/**
+ * Orders concatenated files chronologically by the timestamps of their first messages;
+ * overlapping files are reported as warnings. Timestamps of text files are extracted
+ * with the given settings. Applied to `ObserveOrigin::Concat` only.
+ */
+order_by_time: TimestampSettings | null, };
 
 /**
  * Describes the source of data for observation.
  */
-export type ObserveOrigin = { "File": [string, FileFormat, string] } | { "Concat": Array<[string, FileFormat, string]> } | { "Stream": [string, Transport] };
+export type ObserveOrigin = { "File": [string, FileFormat, string] } | { "Concat": Array<[string, FileFormat, string]> } | { "Stream": [string, Transport] } | { "Folder": [string, FolderWatch] };
 
 /**
  * Specifies the parser to be used for processing session data.
  */
-export type ParserType = { "Dlt": DltParserSettings } | { "SomeIp": SomeIpParserSettings } | { "Text": null } | { Plugin: PluginParserSettings };
+export type ParserType = { "Dlt": DltParserSettings } | { "SomeIp": SomeIpParserSettings } | { "Text": null } | { Plugin: PluginParserSettings } | { "Can": CanParserSettings };
 
 /**
  * Configuration for executing terminal commands.
@@ -114,6 +207,15 @@ send_data_delay: number,
  */
 exclusive: boolean, };
 
+/**
+ * Configuration for SocketCAN connections.
+ */
+export type SocketCanTransportConfig = { 
+/**
+ * The name of the CAN interface (e.g. `can0` or `vcan0`).
+ */
+interface: string, };
+
 /**
  * Settings for the SomeIp parser.
  */
@@ -135,7 +237,7 @@ bind_addr: string, };
 /**
  * Describes the transport source for a session.
  */
-export type Transport = { "Process": ProcessTransportConfig } | { "TCP": TCPTransportConfig } | { "UDP": UDPTransportConfig } | { "Serial": SerialTransportConfig };
+export type Transport = { "Process": ProcessTransportConfig } | { "TCP": TCPTransportConfig } | { "UDP": UDPTransportConfig } | { "Serial": SerialTransportConfig } | { "SocketCan": SocketCanTransportConfig };
 
 /**
  * Configuration for UDP connections.
@@ -7,6 +7,8 @@ import * as obj from '../env/obj';
 export enum IndexingMode {
     Regular = 0,
     Breadcrumbs = 1,
+    Folding = 2,
+    FoldingTemplates = 3,
 }
 
 export enum NatureTypes {
@@ -14,6 +16,7 @@ export enum NatureTypes {
     Bookmark = 1,
     Breadcrumb = 2,
     BreadcrumbSeporator = 3,
+    Folded = 4,
 }
 
 export class Nature {
@@ -31,6 +34,7 @@ export class Nature {
     public readonly bookmark: boolean;
     public readonly breadcrumb: boolean;
     public readonly seporator: boolean;
+    public readonly folded: boolean;
 
     constructor(int: number) {
         this.bits = Nature.getBits(int, 8);
@@ -38,6 +42,7 @@ export class Nature {
         this.bookmark = this.isBookmark();
         this.breadcrumb = this.isBreadcrumb();
         this.seporator = this.isSeporator();
+        this.folded = this.isFolded();
     }
 
     protected isMatch(): boolean {
@@ -48,6 +53,10 @@ export class Nature {
         return this.bits[6] === 1;
     }
 
+    protected isFolded(): boolean {
+        return this.bits[5] === 1;
+    }
+
     protected isBreadcrumb(): boolean {
         return this.bits[1] === 1;
     }
@@ -70,6 +79,9 @@ export class Nature {
         if (this.seporator) {
             types.push(NatureTypes.BreadcrumbSeporator);
         }
+        if (this.folded) {
+            types.push(NatureTypes.Folded);
+        }
         return types;
     }
     public isEq(nature: Nature): boolean {
@@ -78,6 +90,7 @@ export class Nature {
             this.match === nature.match &&
             this.breadcrumb === nature.breadcrumb &&
             this.seporator === nature.seporator &&
+            this.folded === nature.folded &&
             this.hidden === nature.hidden
         );
     }
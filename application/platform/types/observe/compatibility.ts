@@ -62,6 +62,7 @@ export const SDESupport: {
 } = {
     [Origin.Context.File]: false,
     [Origin.Context.Concat]: false,
+    [Origin.Context.Folder]: false,
     [Origin.Context.Plugin]: false,
     [Stream.Source.Process]: true,
     [Stream.Source.Serial]: true,
@@ -83,6 +84,7 @@ export const Configurable: {
         [Parser.Protocol.Plugin]: true,
     },
     [Origin.Context.Concat]: true,
+    [Origin.Context.Folder]: true,
     [Origin.Context.Plugin]: true,
     [Stream.Source.Process]: true,
     [Stream.Source.Serial]: true,
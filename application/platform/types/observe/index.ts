@@ -2,6 +2,8 @@ import { Configuration as Base, getCompatibilityMod } from './configuration';
 import { Mutable } from '../unity/mutable';
 import { LockToken } from '../../env/lock.token';
 import { Signature } from '../env/types';
+import { NetworkFilter } from '../bindings/observe';
+import { TimestampSettings } from '../bindings/miscellaneous';
 
 import * as Parser from './parser';
 import * as Origin from './origin';
@@ -17,6 +19,10 @@ export { IList, IOriginDetails, IJob } from './description';
 export interface IObserve {
     origin: Origin.IConfiguration;
     parser: Parser.IConfiguration;
+    // Filter of the packets of network traces (PCAP and PCAPNG files)
+    network_filter?: NetworkFilter;
+    // Orders concatenated files chronologically by the timestamps of their first messages
+    order_by_time?: TimestampSettings;
 }
 
 export class Observe
@@ -180,6 +186,8 @@ export class Observe
         return {
             origin: this.origin.storable(),
             parser: this.parser.storable(),
+            network_filter: this.configuration.network_filter,
+            order_by_time: this.configuration.order_by_time,
         };
     }
 
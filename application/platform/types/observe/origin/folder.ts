@@ -0,0 +1,194 @@
+import { error } from '../../../log/utils';
+import { Configuration as Base, ConfigurationStaticDesc } from '../configuration';
+import { Context, SourceUuid } from './index';
+import { OriginDetails, IOriginDetails, IList, Job, IJob, OriginType } from '../description';
+import { getFileName, getParentFolder } from '../../files';
+import { Statics } from '../../../env/decorators';
+import { unique } from '../../../env/sequence';
+
+import * as Types from '../types';
+import * as Parser from '../parser';
+import * as Sde from '../sde';
+import * as str from '../../../env/str';
+
+export enum SortOrder {
+    Name = 'Name',
+    Modified = 'Modified',
+}
+
+export interface IFolderWatch {
+    path: string;
+    // Glob pattern of file names, supports "*" and "?"
+    pattern: string;
+    // Order of files from the oldest to the newest one
+    order: SortOrder;
+    format: Types.File.FileType;
+}
+
+export type IConfiguration = [SourceUuid, IFolderWatch];
+
+@Statics<ConfigurationStaticDesc<IConfiguration, Context>>()
+export class Configuration
+    extends Base<IConfiguration, Configuration, Context>
+    implements OriginDetails, Sde.Support, Parser.Support, Job
+{
+    static desc(): IList {
+        return {
+            major: `Folder`,
+            minor: 'Rotating Log Files',
+            icon: 'folder',
+        };
+    }
+
+    static alias(): Context {
+        return Context.Folder;
+    }
+
+    static validate(configuration: IConfiguration): Error | IConfiguration {
+        try {
+            if (configuration instanceof Array && configuration.length === 2) {
+                str.asNotEmptyString(
+                    configuration[0],
+                    `SourceUuid isn't found: ${configuration[0]}`,
+                );
+                const watch = configuration[1];
+                str.asNotEmptyString(watch.path, `Origin.Folder.path isn't found: ${watch.path}`);
+                if (typeof watch.pattern !== 'string') {
+                    throw new Error(`Invalid Origin.Folder.pattern: ${watch.pattern}`);
+                }
+                if (watch.order !== SortOrder.Name && watch.order !== SortOrder.Modified) {
+                    throw new Error(`Invalid Origin.Folder.order: ${watch.order}`);
+                }
+                if (Types.File.getFileTypeFrom(watch.format) instanceof Error) {
+                    throw new Error(`Invalid Origin.Folder.format: ${watch.format}`);
+                }
+            } else {
+                throw new Error(
+                    `Source "${Context.Folder}" should be represented as an array, len = 2.`,
+                );
+            }
+            return configuration;
+        } catch (e) {
+            return new Error(error(e));
+        }
+    }
+
+    static inited(): boolean {
+        return true;
+    }
+
+    // Gives initial settings. Not necessarily valid.
+    static initial(): IConfiguration {
+        return [
+            unique(),
+            {
+                path: '',
+                pattern: '*',
+                order: SortOrder.Modified,
+                format: Types.File.FileType.Text,
+            },
+        ];
+    }
+
+    public source(): string | undefined {
+        return this.configuration[0];
+    }
+
+    public set(): {
+        path(path: string): Configuration;
+        pattern(pattern: string): Configuration;
+        order(order: SortOrder): Configuration;
+        type(type: Types.File.FileType): Configuration;
+        alias(alias?: string): Configuration;
+    } {
+        return {
+            path: (path: string): Configuration => {
+                this.configuration[1].path = path;
+                return this;
+            },
+            pattern: (pattern: string): Configuration => {
+                this.configuration[1].pattern = pattern;
+                return this;
+            },
+            order: (order: SortOrder): Configuration => {
+                this.configuration[1].order = order;
+                return this;
+            },
+            type: (type: Types.File.FileType): Configuration => {
+                this.configuration[1].format = type;
+                return this;
+            },
+            alias: (alias?: string): Configuration => {
+                this.configuration[0] = alias === undefined ? unique() : alias;
+                return this;
+            },
+        };
+    }
+
+    public path(): string {
+        return this.configuration[1].path;
+    }
+
+    public pattern(): string {
+        return this.configuration[1].pattern;
+    }
+
+    public filetype(): Types.File.FileType {
+        return this.configuration[1].format;
+    }
+
+    public desc(): IOriginDetails {
+        return {
+            major: `${getFileName(this.path())}/${this.pattern()}`,
+            minor: getParentFolder(this.path()),
+            icon: 'folder',
+            type: OriginType.file,
+            action: 'Watch',
+            state: {
+                running: 'watching',
+                stopped: '',
+            },
+        };
+    }
+
+    public asJob(): IJob {
+        return {
+            name: 'watching',
+            desc: `${getFileName(this.path())}/${this.pattern()}`,
+            icon: 'folder',
+        };
+    }
+
+    public getSupportedParsers(): Parser.Reference[] {
+        switch (this.configuration[1].format) {
+            case Types.File.FileType.Binary:
+                return [
+                    Parser.Dlt.Configuration,
+                    Parser.SomeIp.Configuration,
+                    Parser.Plugin.Configuration,
+                ];
+            case Types.File.FileType.PcapNG:
+                return [
+                    Parser.Dlt.Configuration,
+                    Parser.SomeIp.Configuration,
+                    Parser.Plugin.Configuration,
+                ];
+            case Types.File.FileType.PcapLegacy:
+                return [
+                    Parser.Dlt.Configuration,
+                    Parser.SomeIp.Configuration,
+                    Parser.Plugin.Configuration,
+                ];
+            case Types.File.FileType.Text:
+                return [Parser.Text.Configuration];
+            case Types.File.FileType.ParserPlugin:
+                return [Parser.Plugin.Configuration];
+        }
+    }
+
+    public override hash(): number {
+        return str.hash(
+            `${this.path()};${this.pattern()};${this.configuration[1].order};${this.filetype()}`,
+        );
+    }
+}
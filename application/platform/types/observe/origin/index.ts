@@ -7,11 +7,13 @@ import { OriginDetails, IOriginDetails, Job, IJob } from '../description';
 export * as File from './file';
 export * as Concat from './concat';
 export * as Stream from './stream';
+export * as Folder from './folder';
 export * as Plugin from './plugin';
 
 import * as File from './file';
 import * as Concat from './concat';
 import * as Stream from './stream';
+import * as Folder from './folder';
 import * as Plugin from './plugin';
 import * as Parser from '../parser';
 import * as Sde from '../sde';
@@ -21,6 +23,7 @@ export enum Context {
     File = 'File',
     Concat = 'Concat',
     Stream = 'Stream',
+    Folder = 'Folder',
     Plugin = 'Plugin',
 }
 
@@ -34,6 +37,7 @@ export interface IConfiguration {
     [Context.File]?: File.IConfiguration;
     [Context.Concat]?: Concat.IConfiguration;
     [Context.Stream]?: Stream.IConfiguration;
+    [Context.Folder]?: Folder.IConfiguration;
     [Context.Plugin]?: Plugin.IConfiguration;
 }
 
@@ -41,6 +45,7 @@ export const REGISTER = {
     [Context.File]: File.Configuration,
     [Context.Concat]: Concat.Configuration,
     [Context.Stream]: Stream.Configuration,
+    [Context.Folder]: Folder.Configuration,
     [Context.Plugin]: Plugin.Configuration,
 };
 
@@ -50,12 +55,14 @@ export type Declaration =
     | File.Configuration
     | Concat.Configuration
     | Stream.Configuration
+    | Folder.Configuration
     | Plugin.Configuration;
 
 export type OriginNature =
     | File.Configuration
     | Concat.Configuration
     | Stream.Stream.Declaration
+    | Folder.Configuration
     | Plugin.Configuration;
 
 @Statics<ConfigurationStatic<IConfiguration, undefined>>()
@@ -189,6 +196,8 @@ export class Configuration
                 return Concat.Configuration.desc().major;
             } else if (this.instance instanceof Stream.Configuration) {
                 return Stream.Configuration.desc().major;
+            } else if (this.instance instanceof Folder.Configuration) {
+                return Folder.Configuration.desc().major;
             } else {
                 throw new Error(`Origin type isn't supported yet`);
             }
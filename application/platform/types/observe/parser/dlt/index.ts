@@ -45,8 +45,11 @@ export interface IFilters {
 export interface IConfiguration {
     filter_config: IFilters | undefined;
     fibex_file_paths: string[] | undefined;
+    arxml_file_paths: string[] | undefined;
+    dbc_file_paths: string[] | undefined;
     with_storage_header: boolean;
     tz: string | undefined;
+    detect_lifecycles: boolean;
 }
 
 @Statics<ConfigurationStaticDesc<IConfiguration, Protocol>>()
@@ -70,8 +73,15 @@ export class Configuration
         try {
             obj.getAsBool(configuration, 'with_storage_header');
             obj.getAsNotEmptyStringsArrayOrUndefined(configuration, 'fibex_file_paths');
+            obj.getAsNotEmptyStringsArrayOrUndefined(configuration, 'arxml_file_paths');
+            obj.getAsNotEmptyStringsArrayOrUndefined(configuration, 'dbc_file_paths');
             obj.getAsObjOrUndefined(configuration, 'filter_config');
             obj.getAsNotEmptyStringOrAsUndefined(configuration, 'tz');
+            configuration.detect_lifecycles = obj.getAsBool(
+                configuration,
+                'detect_lifecycles',
+                false,
+            );
             const filter_config = configuration.filter_config;
             if (filter_config !== undefined) {
                 obj.getAsValidNumber(filter_config, 'min_log_level');
@@ -96,8 +106,11 @@ export class Configuration
         return {
             filter_config: undefined,
             fibex_file_paths: [],
+            arxml_file_paths: undefined,
+            dbc_file_paths: undefined,
             with_storage_header: true,
             tz: undefined,
+            detect_lifecycles: false,
         };
     }
 
@@ -177,7 +190,15 @@ export class Configuration
             `dlt:${(this.configuration.fibex_file_paths === undefined
                 ? []
                 : this.configuration.fibex_file_paths
+            ).join(';')};${(this.configuration.arxml_file_paths === undefined
+                ? []
+                : this.configuration.arxml_file_paths
+            ).join(';')};${(this.configuration.dbc_file_paths === undefined
+                ? []
+                : this.configuration.dbc_file_paths
             ).join(';')};${this.configuration.with_storage_header};${this.configuration.tz};${
+                this.configuration.detect_lifecycles
+            };${
                 filters.min_log_level
             };${filters.ecu_ids?.length};${filters.app_ids?.length};${filters.context_ids?.length}`,
         );
@@ -1,4 +1,5 @@
 export interface ISourceLink {
     id: number;
     alias: string;
+    count: number;
 }
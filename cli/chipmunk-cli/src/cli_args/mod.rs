@@ -36,6 +36,10 @@ pub struct Cli {
     /// Sets the argument separator for payload column in text output.
     #[arg(long = "args-sep", default_value_t = String::from(OUTPUT_ARGS_SEPARATOR_DEFAULT))]
     pub text_args_separator: String,
+    /// Path to a JSON file with sequence rules to check the parsed messages against.
+    /// The process exits with an error if any instance of the rules is violated.
+    #[arg(long = "assert", verbatim_doc_comment)]
+    pub assert_rules: Option<PathBuf>,
     /// Specifies the parser to use for incoming bytes.
     #[command(subcommand)]
     pub parser: Parser,
@@ -127,6 +131,7 @@ impl Cli {
             append_output,
             text_columns_separator: _,
             text_args_separator: _,
+            assert_rules,
             parser,
         } = self;
 
@@ -139,6 +144,14 @@ impl Cli {
             output_path.display()
         );
 
+        if let Some(rules) = assert_rules {
+            ensure!(
+                rules.is_file(),
+                "Sequence rules file doesn't exist. Path: {}",
+                rules.display()
+            );
+        }
+
         match parser {
             Parser::Dlt { fibex_files, input } => {
                 for fibex in fibex_files {
@@ -1,9 +1,18 @@
+use std::path::PathBuf;
+
 use clap::Parser as _;
-use cli_args::OutputFormat;
+use cli_args::{InputSource, OutputFormat};
+use parsers::LogMessage;
+use stypes::SequenceRule;
 use tokio_util::sync::CancellationToken;
 
 use session::{
-    format::{binary::MsgBinaryFormatter, text::MsgTextFormatter},
+    format::{
+        MessageFormatter,
+        assertions::{self, AssertingFormatter},
+        binary::MsgBinaryFormatter,
+        text::MsgTextFormatter,
+    },
     start_session,
 };
 
@@ -16,6 +25,12 @@ pub async fn run_app(cancel_token: CancellationToken) -> anyhow::Result<()> {
     let cli = cli_args::Cli::parse();
     cli.validate()?;
 
+    let assert_rules = cli
+        .assert_rules
+        .as_deref()
+        .map(assertions::load_rules)
+        .transpose()?;
+
     match cli.parser {
         cli_args::Parser::Dlt { fibex_files, input } => {
             // Create DLT parser.
@@ -39,10 +54,11 @@ pub async fn run_app(cancel_token: CancellationToken) -> anyhow::Result<()> {
                 OutputFormat::Binary => {
                     let binary_formatter = MsgBinaryFormatter::default();
 
-                    start_session(
+                    run_session(
                         parser,
                         input,
                         binary_formatter,
+                        assert_rules,
                         cli.output_path,
                         cancel_token,
                     )
@@ -57,8 +73,15 @@ pub async fn run_app(cancel_token: CancellationToken) -> anyhow::Result<()> {
                         cli.text_args_separator,
                     );
 
-                    start_session(parser, input, text_formatter, cli.output_path, cancel_token)
-                        .await?;
+                    run_session(
+                        parser,
+                        input,
+                        text_formatter,
+                        assert_rules,
+                        cli.output_path,
+                        cancel_token,
+                    )
+                    .await?;
                 }
             };
         }
@@ -66,3 +89,35 @@ pub async fn run_app(cancel_token: CancellationToken) -> anyhow::Result<()> {
 
     Ok(())
 }
+
+/// Starts the session, checking the parsed messages against the sequence rules if they
+/// are provided and returning an error if any of the rules is violated.
+async fn run_session<T, P, W>(
+    parser: P,
+    input: InputSource,
+    msg_formatter: W,
+    assert_rules: Option<Vec<SequenceRule>>,
+    output_path: PathBuf,
+    cancel_token: CancellationToken,
+) -> anyhow::Result<()>
+where
+    T: LogMessage,
+    P: parsers::Parser<T>,
+    W: MessageFormatter,
+{
+    let Some(rules) = assert_rules else {
+        return start_session(parser, input, msg_formatter, output_path, cancel_token).await;
+    };
+
+    let mut asserting_formatter = AssertingFormatter::new(msg_formatter, rules)?;
+    start_session(
+        parser,
+        input,
+        &mut asserting_formatter,
+        output_path,
+        cancel_token,
+    )
+    .await?;
+
+    asserting_formatter.finish()
+}
@@ -0,0 +1,135 @@
+//! Structures and methods to check parsed messages against sequence rules.
+
+use std::{fmt::Write as _, path::Path};
+
+use anyhow::{Context, ensure};
+
+use parsers::{
+    LogMessage,
+    dlt::fmt::{DLT_ARGUMENT_SENTINAL, DLT_COLUMN_SENTINAL},
+    text::timestamp::TimestampExtractor,
+};
+use processor::search::sequences::SequenceChecker;
+use stypes::{SequenceRule, SequenceViolation};
+
+use super::MessageFormatter;
+
+/// Loads sequence rules from a JSON file containing an array of rules.
+pub fn load_rules(path: &Path) -> anyhow::Result<Vec<SequenceRule>> {
+    let content = std::fs::read_to_string(path).context("Error while reading rules file")?;
+    serde_json::from_str(&content).context("Error while parsing sequence rules")
+}
+
+/// Wraps another formatter to check the written messages against sequence rules after
+/// writing them. Messages are checked in their text form with the separators of the
+/// parsers replaced by spaces. The timestamp of a message is taken from the message itself
+/// (e.g. the storage header or the ECU time of DLT messages) or, if it has none, from its
+/// text.
+pub struct AssertingFormatter<W> {
+    inner: W,
+    checker: SequenceChecker,
+    /// Extractor of timestamps from text, set only if rules have max gaps in time.
+    extractor: Option<TimestampExtractor>,
+    msg_buffer: String,
+    /// Count of checked messages, used as rows of the messages.
+    msg_count: u64,
+}
+
+impl<W> AssertingFormatter<W> {
+    /// Creates a new instance wrapping the given formatter.
+    ///
+    /// * `inner`: Formatter to write the messages with.
+    /// * `rules`: Sequence rules to check the messages against.
+    pub fn new(inner: W, rules: Vec<SequenceRule>) -> anyhow::Result<Self> {
+        let checker = SequenceChecker::new(rules).context("Invalid sequence rules")?;
+        // Timestamps are needed only if rules have max gaps in time.
+        let extractor = checker
+            .needs_timestamps()
+            .then(|| TimestampExtractor::new(None, None, None))
+            .transpose()
+            .context("Error while creating timestamps extractor")?;
+        Ok(Self {
+            inner,
+            checker,
+            extractor,
+            msg_buffer: String::new(),
+            msg_count: 0,
+        })
+    }
+
+    /// Prints the found instances of the rules, returning an error if any of them
+    /// is violated.
+    pub fn finish(self) -> anyhow::Result<()> {
+        const UNDERLINE_ANSI: &str = "\x1b[4m";
+        const RESET_ANSI: &str = "\x1b[0m";
+
+        let instances = self.checker.finish();
+
+        println!("{UNDERLINE_ANSI}Sequence Assertions{RESET_ANSI}:");
+
+        let mut violated = 0;
+        for instance in instances.iter() {
+            let rows = format!("{}..={}", instance.rows.start, instance.rows.end);
+            match &instance.violation {
+                None => println!("* Rule {} satisfied in messages {rows}", instance.rule),
+                Some(violation) => {
+                    violated += 1;
+                    let reason = match violation {
+                        SequenceViolation::Timeout(step) => format!("step {step} timed out"),
+                        SequenceViolation::Forbidden(condition) => {
+                            format!("forbidden condition {condition} occurred")
+                        }
+                        SequenceViolation::Unfinished(step) => {
+                            format!("step {step} never occurred")
+                        }
+                        SequenceViolation::Overflow(step) => {
+                            format!(
+                                "dropped while waiting for step {step}, too many pending instances"
+                            )
+                        }
+                    };
+                    println!(
+                        "* Rule {} violated in messages {rows}: {reason}",
+                        instance.rule
+                    );
+                }
+            }
+        }
+        if instances.is_empty() {
+            println!("* No instances of the rules found.");
+        }
+
+        ensure!(
+            violated == 0,
+            "{violated} of {} instances of sequence rules are violated",
+            instances.len()
+        );
+
+        Ok(())
+    }
+}
+
+impl<W: MessageFormatter> MessageFormatter for AssertingFormatter<W> {
+    fn write_msg<M>(&mut self, mut writer: impl std::io::Write, msg: &M) -> anyhow::Result<()>
+    where
+        M: LogMessage,
+    {
+        self.inner.write_msg(&mut writer, msg)?;
+
+        self.msg_buffer.clear();
+        write!(&mut self.msg_buffer, "{msg}")
+            .context("Error while writing parsed message to buffer")?;
+        let line = self
+            .msg_buffer
+            .replace([DLT_COLUMN_SENTINAL, DLT_ARGUMENT_SENTINAL], " ");
+        let timestamp = self
+            .extractor
+            .as_ref()
+            .and_then(|extractor| msg.timestamp().or_else(|| extractor.extract(&line)));
+
+        self.checker.check(self.msg_count, timestamp, &line);
+        self.msg_count += 1;
+
+        Ok(())
+    }
+}
@@ -2,6 +2,7 @@
 
 use parsers::LogMessage;
 
+pub mod assertions;
 pub mod binary;
 pub mod text;
 
@@ -13,3 +14,12 @@ pub trait MessageFormatter {
     where
         M: LogMessage;
 }
+
+impl<W: MessageFormatter> MessageFormatter for &mut W {
+    fn write_msg<M>(&mut self, writer: impl std::io::Write, msg: &M) -> anyhow::Result<()>
+    where
+        M: LogMessage,
+    {
+        (**self).write_msg(writer, msg)
+    }
+}
@@ -142,7 +142,11 @@ fn is_job_involved(
                         .any(|t| t.flatten_deps().contains(&Target::Core)),
                 },
                 // These targets aren't involved in the dependencies tree.
-                Target::CliDev | Target::Updater | Target::CliChipmunk | Target::PluginsApi => {
+                Target::CliDev
+                | Target::Updater
+                | Target::CliChipmunk
+                | Target::WsServer
+                | Target::PluginsApi => {
                     matches!(current_job, JobType::Lint)
                 }
                 // TS and Bindings targets need to be built with all their dependencies to perform the
@@ -178,7 +182,11 @@ fn is_job_involved(
                         .any(|t| t.flatten_deps().contains(&Target::Core)),
                 },
                 // These targets aren't involved in the dependencies tree.
-                Target::CliDev | Target::Updater | Target::CliChipmunk | Target::PluginsApi => {
+                Target::CliDev
+                | Target::Updater
+                | Target::CliChipmunk
+                | Target::WsServer
+                | Target::PluginsApi => {
                     matches!(current_job, JobType::Test { .. })
                 }
 